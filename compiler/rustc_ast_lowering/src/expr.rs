@@ -272,6 +272,9 @@ pub(super) fn lower_expr_mut(&mut self, e: &Expr) -> hir::Expr<'hir> {
                     let mut ex = self.lower_expr_mut(ex);
                     // Include parens in span, but only if it is a super-span.
                     if e.span.contains(ex.span) {
+                        // Remember the innermost span for `((expr))`, so suggestions can
+                        // still tell where the parentheses are.
+                        self.parenthesized.entry(ex.hir_id.local_id).or_insert(ex.span);
                         ex.span = self.lower_span(e.span);
                     }
                     // Merge attributes into the inner expression.
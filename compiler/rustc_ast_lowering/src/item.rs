@@ -71,6 +71,7 @@ fn with_lctx(
             node_id_to_local_id: Default::default(),
             local_id_to_def_id: SortedMap::new(),
             trait_map: Default::default(),
+            parenthesized: Default::default(),
 
             // Lowering state.
             catch_scope: None,
@@ -1254,10 +1255,17 @@ fn lower_fn_header(&mut self, h: FnHeader) -> hir::FnHeader {
     }
 
     pub(super) fn lower_abi(&mut self, abi: StrLit) -> abi::Abi {
-        abi::lookup(abi.symbol_unescaped.as_str()).unwrap_or_else(|| {
-            self.error_on_invalid_abi(abi);
-            abi::Abi::Rust
-        })
+        let name = abi.symbol_unescaped.as_str();
+        abi::lookup(name)
+            .or_else(|| {
+                let custom = self.sess.target.lookup_custom_abi(name)?;
+                debug_assert_eq!(custom.name, name);
+                Some(abi::Abi::Custom { name: abi.symbol_unescaped })
+            })
+            .unwrap_or_else(|| {
+                self.error_on_invalid_abi(abi);
+                abi::Abi::Rust
+            })
     }
 
     pub(super) fn lower_extern(&mut self, ext: Extern) -> abi::Abi {
@@ -1271,7 +1279,14 @@ pub(super) fn lower_extern(&mut self, ext: Extern) -> abi::Abi {
     fn error_on_invalid_abi(&self, abi: StrLit) {
         struct_span_err!(self.sess, abi.span, E0703, "invalid ABI: found `{}`", abi.symbol)
             .span_label(abi.span, "invalid ABI")
-            .help(&format!("valid ABIs: {}", abi::all_names().join(", ")))
+            .help(&format!(
+                "valid ABIs: {}",
+                abi::all_names()
+                    .into_iter()
+                    .chain(self.sess.target.custom_abis.iter().map(|abi| &*abi.name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
             .emit();
     }
 
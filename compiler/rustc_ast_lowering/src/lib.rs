@@ -122,6 +122,8 @@ struct LoweringContext<'a, 'hir: 'a> {
     item_local_id_counter: hir::ItemLocalId,
     local_id_to_def_id: SortedMap<ItemLocalId, LocalDefId>,
     trait_map: FxHashMap<ItemLocalId, Box<[TraitCandidate]>>,
+    /// Spans of parenthesized expressions without their parentheses, see `OwnerInfo`.
+    parenthesized: FxHashMap<ItemLocalId, Span>,
 
     impl_trait_defs: Vec<hir::GenericParam<'hir>>,
     impl_trait_bounds: Vec<hir::WherePredicate<'hir>>,
@@ -470,6 +472,7 @@ fn with_hir_id_owner(
         let current_node_ids = std::mem::take(&mut self.node_id_to_local_id);
         let current_id_to_def_id = std::mem::take(&mut self.local_id_to_def_id);
         let current_trait_map = std::mem::take(&mut self.trait_map);
+        let current_parenthesized = std::mem::take(&mut self.parenthesized);
         let current_owner = std::mem::replace(&mut self.current_hir_id_owner, def_id);
         let current_local_counter =
             std::mem::replace(&mut self.item_local_id_counter, hir::ItemLocalId::new(1));
@@ -492,6 +495,7 @@ fn with_hir_id_owner(
         self.node_id_to_local_id = current_node_ids;
         self.local_id_to_def_id = current_id_to_def_id;
         self.trait_map = current_trait_map;
+        self.parenthesized = current_parenthesized;
         self.current_hir_id_owner = current_owner;
         self.item_local_id_counter = current_local_counter;
         self.impl_trait_defs = current_impl_trait_defs;
@@ -506,6 +510,7 @@ fn make_owner_info(&mut self, node: hir::OwnerNode<'hir>) -> &'hir hir::OwnerInf
         let mut bodies = std::mem::take(&mut self.bodies);
         let local_id_to_def_id = std::mem::take(&mut self.local_id_to_def_id);
         let trait_map = std::mem::take(&mut self.trait_map);
+        let parenthesized = std::mem::take(&mut self.parenthesized);
 
         #[cfg(debug_assertions)]
         for (id, attrs) in attrs.iter() {
@@ -535,7 +540,7 @@ fn make_owner_info(&mut self, node: hir::OwnerNode<'hir>) -> &'hir hir::OwnerInf
             hir::AttributeMap { map: attrs, hash }
         };
 
-        self.arena.alloc(hir::OwnerInfo { nodes, parenting, attrs, trait_map })
+        self.arena.alloc(hir::OwnerInfo { nodes, parenting, attrs, trait_map, parenthesized })
     }
 
     /// Hash the HIR node twice, one deep and one shallow hash.  This allows to differentiate
@@ -273,6 +273,14 @@ fn check_abi(&self, abi: ast::StrLit, constness: ast::Const) {
                     "wasm ABI is experimental and subject to change"
                 );
             }
+            abi if self.sess.target.lookup_custom_abi(abi).is_some() => {
+                gate_feature_post!(
+                    &self,
+                    abi_target_defined,
+                    span,
+                    "target-defined ABIs are experimental and subject to change"
+                );
+            }
             abi => {
                 self.sess.parse_sess.span_diagnostic.delay_span_bug(
                     span,
@@ -16,3 +16,4 @@
 pub mod feature_gate;
 pub mod node_count;
 pub mod show_span;
+pub mod tool_attrs;
@@ -0,0 +1,367 @@
+//! Validation of tool attributes against the schemas declared with `#![register_tool_attr]`.
+//!
+//! A tool registered with `#![register_tool(tool)]` may describe its attributes with
+//!
+//! ```ignore (illustrative)
+//! #![register_tool_attr(tool::attr, targets(fn, mod), args(word, list))]
+//! ```
+//!
+//! where both `targets(..)` and `args(..)` are optional and default to allowing anything.
+//! Tools without any declared attribute keep accepting every `#[tool::..]` attribute, but once a
+//! tool declares one of its attributes, all uses of its namespace are checked: unknown attributes,
+//! attributes in positions the tool does not expect, and attributes with the wrong shape of
+//! arguments are reported instead of being silently ignored.
+
+use rustc_ast::visit::{self, AssocCtxt, Visitor};
+use rustc_ast::{self as ast, AttrItem, AttrKind, Attribute, MacArgs, NestedMetaItem};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_session::Session;
+use rustc_span::symbol::{sym, Ident, Symbol};
+use rustc_span::Span;
+
+/// Every position a tool attribute can be restricted to, as spelled in `targets(..)`.
+const TARGETS: &[&str] = &[
+    "crate",
+    "mod",
+    "extern_crate",
+    "use",
+    "fn",
+    "struct",
+    "enum",
+    "union",
+    "trait",
+    "impl",
+    "const",
+    "static",
+    "type",
+    "foreign_mod",
+    "global_asm",
+    "macro",
+    "field",
+    "variant",
+    "param",
+    "generic_param",
+    "let",
+    "arm",
+    "expr",
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum ArgsShape {
+    /// `#[tool::attr]`
+    Word,
+    /// `#[tool::attr(...)]`
+    List,
+    /// `#[tool::attr = "..."]`
+    NameValue,
+}
+
+impl ArgsShape {
+    fn from_name(name: Symbol) -> Option<ArgsShape> {
+        match name.as_str() {
+            "word" => Some(ArgsShape::Word),
+            "list" => Some(ArgsShape::List),
+            "name_value" => Some(ArgsShape::NameValue),
+            _ => None,
+        }
+    }
+
+    fn of(item: &AttrItem) -> ArgsShape {
+        match item.args {
+            MacArgs::Empty => ArgsShape::Word,
+            MacArgs::Delimited(..) => ArgsShape::List,
+            MacArgs::Eq(..) => ArgsShape::NameValue,
+        }
+    }
+
+    fn template(self, path: &str) -> String {
+        match self {
+            ArgsShape::Word => format!("`#[{}]`", path),
+            ArgsShape::List => format!("`#[{}(...)]`", path),
+            ArgsShape::NameValue => format!("`#[{} = \"...\"]`", path),
+        }
+    }
+}
+
+struct ToolAttrSchema {
+    /// The span of the `tool::attr` path in the declaration.
+    span: Span,
+    /// The allowed positions, or `None` if the attribute is allowed everywhere.
+    targets: Option<Vec<&'static str>>,
+    /// The allowed shapes of arguments, or `None` if any shape is allowed.
+    args: Option<Vec<ArgsShape>>,
+}
+
+/// Tool name to the schemas of the attributes it declared.
+type ToolAttrSchemas = FxHashMap<Symbol, FxHashMap<Symbol, ToolAttrSchema>>;
+
+pub fn check_crate(sess: &Session, krate: &ast::Crate) {
+    let schemas = collect_schemas(sess, &krate.attrs);
+    if schemas.is_empty() {
+        return;
+    }
+    let mut checker = ToolAttrChecker { sess, schemas, target: "crate" };
+    visit::walk_crate(&mut checker, krate);
+}
+
+fn collect_schemas(sess: &Session, attrs: &[Attribute]) -> ToolAttrSchemas {
+    let registered_tools: FxHashSet<Symbol> = sess
+        .filter_by_name(attrs, sym::register_tool)
+        .flat_map(|attr| attr.meta_item_list().unwrap_or_default())
+        .filter_map(|nested_meta| nested_meta.ident())
+        .map(|ident| ident.name)
+        .collect();
+
+    let mut schemas = ToolAttrSchemas::default();
+    for attr in sess.filter_by_name(attrs, sym::register_tool_attr) {
+        let Some(list) = attr.meta_item_list() else {
+            // The builtin attribute template check has already reported this.
+            continue;
+        };
+        let Some((tool, name, schema)) = parse_schema(sess, attr.span, &list) else {
+            continue;
+        };
+        if !registered_tools.contains(&tool.name) {
+            sess.struct_span_err(
+                tool.span,
+                &format!("`{}` is not a tool registered with `#![register_tool]`", tool),
+            )
+            .help(&format!("add `#![register_tool({})]` to the crate root", tool))
+            .emit();
+            continue;
+        }
+        let span = schema.span;
+        if let Some(old) = schemas.entry(tool.name).or_default().insert(name.name, schema) {
+            sess.struct_span_err(
+                span,
+                &format!("tool attribute `{}::{}` was already registered", tool, name),
+            )
+            .span_label(old.span, "already registered here")
+            .emit();
+        }
+    }
+    schemas
+}
+
+fn parse_schema(
+    sess: &Session,
+    attr_span: Span,
+    list: &[NestedMetaItem],
+) -> Option<(Ident, Ident, ToolAttrSchema)> {
+    let malformed = |span: Span, label: &str| {
+        sess.struct_span_err(span, "malformed `register_tool_attr` attribute input")
+            .span_label(span, label)
+            .emit();
+    };
+
+    let Some((first, rest)) = list.split_first() else {
+        malformed(attr_span, "expected a `tool::attr` path");
+        return None;
+    };
+    let (tool, name) = match first.meta_item() {
+        Some(mi) if mi.is_word() && mi.path.segments.len() == 2 => {
+            (mi.path.segments[0].ident, mi.path.segments[1].ident)
+        }
+        _ => {
+            malformed(first.span(), "expected a `tool::attr` path");
+            return None;
+        }
+    };
+
+    let mut schema = ToolAttrSchema { span: first.span(), targets: None, args: None };
+    for nested_meta in rest {
+        let Some(mi) = nested_meta.meta_item() else {
+            malformed(nested_meta.span(), "expected `targets(...)` or `args(...)`");
+            return None;
+        };
+        let Some(words) = mi.meta_item_list() else {
+            malformed(mi.span, "expected `targets(...)` or `args(...)`");
+            return None;
+        };
+        if mi.has_name(sym::targets) && schema.targets.is_none() {
+            let mut targets = vec![];
+            for word in words {
+                let name = word.ident().filter(|_| word.is_word()).map(|ident| ident.name);
+                match name.and_then(|name| TARGETS.iter().find(|&&t| t == name.as_str())) {
+                    Some(target) => targets.push(*target),
+                    None => {
+                        sess.struct_span_err(word.span(), "unknown tool attribute target")
+                            .note(&format!("expected one of: {}", TARGETS.join(", ")))
+                            .emit();
+                        return None;
+                    }
+                }
+            }
+            schema.targets = Some(targets);
+        } else if mi.has_name(sym::args) && schema.args.is_none() {
+            let mut args = vec![];
+            for word in words {
+                let shape = word.ident().filter(|_| word.is_word()).map(|ident| ident.name);
+                match shape.and_then(ArgsShape::from_name) {
+                    Some(shape) => args.push(shape),
+                    None => {
+                        sess.struct_span_err(word.span(), "unknown tool attribute argument shape")
+                            .note("expected one of: word, list, name_value")
+                            .emit();
+                        return None;
+                    }
+                }
+            }
+            schema.args = Some(args);
+        } else {
+            let label = if mi.has_name(sym::targets) || mi.has_name(sym::args) {
+                "`targets(...)` and `args(...)` may only be given once"
+            } else {
+                "expected `targets(...)` or `args(...)`"
+            };
+            malformed(mi.span, label);
+            return None;
+        }
+    }
+    Some((tool, name, schema))
+}
+
+struct ToolAttrChecker<'a> {
+    sess: &'a Session,
+    schemas: ToolAttrSchemas,
+    /// The kind of node whose attributes are currently being visited, as spelled in `targets(..)`.
+    target: &'static str,
+}
+
+impl ToolAttrChecker<'_> {
+    fn with_target(&mut self, target: &'static str, f: impl FnOnce(&mut Self)) {
+        let old = std::mem::replace(&mut self.target, target);
+        f(self);
+        self.target = old;
+    }
+
+    fn check_tool_attr(&self, attr: &Attribute, item: &AttrItem) {
+        let [tool, name] = &item.path.segments[..] else { return };
+        let Some(attrs) = self.schemas.get(&tool.ident.name) else { return };
+        let path = format!("{}::{}", tool.ident, name.ident);
+
+        let Some(schema) = attrs.get(&name.ident.name) else {
+            let mut declared: Vec<_> = attrs.keys().map(|name| format!("`{}`", name)).collect();
+            declared.sort();
+            self.sess
+                .struct_span_err(item.path.span, &format!("unknown tool attribute `{}`", path))
+                .note(&format!(
+                    "the tool `{}` only declares the attributes {}",
+                    tool.ident,
+                    declared.join(", ")
+                ))
+                .emit();
+            return;
+        };
+
+        if let Some(targets) = &schema.targets
+            && !targets.contains(&self.target)
+        {
+            let allowed: Vec<_> = targets.iter().map(|target| format!("`{}`", target)).collect();
+            let mut err = self.sess.struct_span_err(
+                attr.span,
+                &format!("tool attribute `{}` cannot be used on `{}`", path, self.target),
+            );
+            if allowed.is_empty() {
+                err.span_note(schema.span, "this attribute is not allowed on anything");
+            } else {
+                err.span_note(
+                    schema.span,
+                    &format!("this attribute is only allowed on {}", allowed.join(", ")),
+                );
+            }
+            err.emit();
+        }
+
+        if let Some(args) = &schema.args
+            && !args.contains(&ArgsShape::of(item))
+        {
+            let templates: Vec<_> = args.iter().map(|shape| shape.template(&path)).collect();
+            let mut err = self
+                .sess
+                .struct_span_err(attr.span, &format!("malformed `{}` attribute input", path));
+            if !templates.is_empty() {
+                err.help(&format!("the tool expects {}", templates.join(" or ")));
+            }
+            err.span_note(schema.span, "the accepted arguments are declared here");
+            err.emit();
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for ToolAttrChecker<'_> {
+    fn visit_attribute(&mut self, attr: &'a Attribute) {
+        if let AttrKind::Normal(item, _) = &attr.kind {
+            self.check_tool_attr(attr, item);
+        }
+    }
+
+    fn visit_item(&mut self, item: &'a ast::Item) {
+        let target = match item.kind {
+            ast::ItemKind::ExternCrate(..) => "extern_crate",
+            ast::ItemKind::Use(..) => "use",
+            ast::ItemKind::Static(..) => "static",
+            ast::ItemKind::Const(..) => "const",
+            ast::ItemKind::Fn(..) => "fn",
+            ast::ItemKind::Mod(..) => "mod",
+            ast::ItemKind::ForeignMod(..) => "foreign_mod",
+            ast::ItemKind::GlobalAsm(..) => "global_asm",
+            ast::ItemKind::TyAlias(..) => "type",
+            ast::ItemKind::Enum(..) => "enum",
+            ast::ItemKind::Struct(..) => "struct",
+            ast::ItemKind::Union(..) => "union",
+            ast::ItemKind::Trait(..) | ast::ItemKind::TraitAlias(..) => "trait",
+            ast::ItemKind::Impl(..) => "impl",
+            ast::ItemKind::MacCall(..) | ast::ItemKind::MacroDef(..) => "macro",
+        };
+        self.with_target(target, |this| visit::walk_item(this, item));
+    }
+
+    fn visit_foreign_item(&mut self, item: &'a ast::ForeignItem) {
+        let target = match item.kind {
+            ast::ForeignItemKind::Static(..) => "static",
+            ast::ForeignItemKind::Fn(..) => "fn",
+            ast::ForeignItemKind::TyAlias(..) => "type",
+            ast::ForeignItemKind::MacCall(..) => "macro",
+        };
+        self.with_target(target, |this| visit::walk_foreign_item(this, item));
+    }
+
+    fn visit_assoc_item(&mut self, item: &'a ast::AssocItem, ctxt: AssocCtxt) {
+        let target = match item.kind {
+            ast::AssocItemKind::Const(..) => "const",
+            ast::AssocItemKind::Fn(..) => "fn",
+            ast::AssocItemKind::TyAlias(..) => "type",
+            ast::AssocItemKind::MacCall(..) => "macro",
+        };
+        self.with_target(target, |this| visit::walk_assoc_item(this, item, ctxt));
+    }
+
+    fn visit_field_def(&mut self, field: &'a ast::FieldDef) {
+        self.with_target("field", |this| visit::walk_field_def(this, field));
+    }
+
+    fn visit_variant(&mut self, variant: &'a ast::Variant) {
+        self.with_target("variant", |this| visit::walk_variant(this, variant));
+    }
+
+    fn visit_param(&mut self, param: &'a ast::Param) {
+        self.with_target("param", |this| visit::walk_param(this, param));
+    }
+
+    fn visit_generic_param(&mut self, param: &'a ast::GenericParam) {
+        self.with_target("generic_param", |this| visit::walk_generic_param(this, param));
+    }
+
+    fn visit_local(&mut self, local: &'a ast::Local) {
+        self.with_target("let", |this| visit::walk_local(this, local));
+    }
+
+    fn visit_arm(&mut self, arm: &'a ast::Arm) {
+        self.with_target("arm", |this| visit::walk_arm(this, arm));
+    }
+
+    fn visit_expr(&mut self, expr: &'a ast::Expr) {
+        self.with_target("expr", |this| visit::walk_expr(this, expr));
+    }
+}
@@ -294,6 +294,21 @@ pub(crate) fn report_use_of_moved_or_uninitialized(
                 // restrict anyways.
             } else {
                 self.suggest_adding_copy_bounds(&mut err, ty, span);
+                // Cloning is only suggested when there is a single, unconditional move whose
+                // expression can be cloned as-is.
+                if let [move_site] = &move_site_vec[..]
+                    && !is_loop_move
+                    && !in_pattern
+                    && !is_partial_move
+                {
+                    let move_out = self.move_data.moves[move_site.moi];
+                    let moved_place = &self.move_data.move_paths[move_out.path].place;
+                    if let UseSpans::OtherUse(move_span) =
+                        self.move_spans(moved_place.as_ref(), move_out.source)
+                    {
+                        self.suggest_cloning(&mut err, moved_place.as_ref(), move_span);
+                    }
+                }
             }
 
             if needs_note {
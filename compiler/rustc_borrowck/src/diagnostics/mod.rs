@@ -6,6 +6,7 @@
 use rustc_hir as hir;
 use rustc_hir::def::Namespace;
 use rustc_hir::def_id::DefId;
+use rustc_hir::intravisit::{self, Visitor};
 use rustc_hir::GeneratorKind;
 use rustc_infer::infer::TyCtxtInferExt;
 use rustc_middle::mir::{
@@ -45,6 +46,23 @@
 
 pub(super) struct IncludingDowncast(pub(super) bool);
 
+/// Looks for a shorthand field initializer `S { x }` whose expression is at `span`.
+struct ShorthandFieldFinder {
+    span: Span,
+    found: bool,
+}
+
+impl<'v> Visitor<'v> for ShorthandFieldFinder {
+    fn visit_expr(&mut self, expr: &'v hir::Expr<'v>) {
+        if let hir::ExprKind::Struct(_, fields, _) = expr.kind
+            && fields.iter().any(|field| field.is_shorthand && field.expr.span == self.span)
+        {
+            self.found = true;
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
 impl<'cx, 'tcx> MirBorrowckCtxt<'cx, 'tcx> {
     /// Adds a suggestion when a closure is invoked twice with a moved variable or when a closure
     /// is moved after being invoked.
@@ -365,6 +383,51 @@ pub(super) fn note_type_does_not_implement_copy(
         }
     }
 
+    /// Suggest cloning `place`, moved by the expression at `span`, if its type is known to
+    /// implement `Clone`. Mutable references are never suggested to be cloned, as a second `&mut`
+    /// to the same place is never what the user wants.
+    pub(super) fn suggest_cloning(&self, err: &mut Diagnostic, place: PlaceRef<'tcx>, span: Span) {
+        let tcx = self.infcx.tcx;
+        let ty = place.ty(self.body, tcx).ty;
+        if let ty::Ref(_, _, hir::Mutability::Mut) = ty.kind() {
+            return;
+        }
+        // Only suggest cloning if the expression is exactly the moved place, as it would
+        // otherwise be unclear what gets cloned. Appending `.clone()` to `*x` would clone `x`.
+        let Ok(snippet) = tcx.sess.source_map().span_to_snippet(span) else { return };
+        if span.from_expansion()
+            || snippet.starts_with('*')
+            || self.describe_place(place).as_deref() != Some(&*snippet)
+        {
+            return;
+        }
+        let Some(clone_trait) = tcx.lang_items().clone_trait() else { return };
+        let is_clone = tcx.infer_ctxt().enter(|infcx| {
+            type_known_to_meet_bound_modulo_regions(
+                &infcx,
+                self.param_env,
+                infcx.tcx.erase_regions(ty),
+                clone_trait,
+                span,
+            )
+        });
+        if !is_clone {
+            return;
+        }
+        // In `S { x }`, the field has to be spelled out to clone `x`.
+        let body = tcx.hir().body(tcx.hir().body_owned_by(self.mir_hir_id()));
+        let mut finder = ShorthandFieldFinder { span, found: false };
+        finder.visit_body(body);
+        let suggestion =
+            if finder.found { format!(": {snippet}.clone()") } else { ".clone()".to_string() };
+        err.span_suggestion_verbose(
+            span.shrink_to_hi(),
+            "consider cloning the value if the performance cost is acceptable",
+            suggestion,
+            Applicability::MachineApplicable,
+        );
+    }
+
     pub(super) fn borrowed_content_source(
         &self,
         deref_base: PlaceRef<'tcx>,
@@ -476,6 +476,9 @@ fn add_move_hints(&self, error: GroupedMoveError<'tcx>, err: &mut Diagnostic, sp
                     None => "value".to_string(),
                 };
                 self.note_type_does_not_implement_copy(err, &place_desc, place_ty, Some(span), "");
+                if let UseSpans::OtherUse(span) = use_spans {
+                    self.suggest_cloning(err, original_path.as_ref(), span);
+                }
 
                 use_spans.args_span_label(err, format!("move out of {place_desc} occurs here"));
             }
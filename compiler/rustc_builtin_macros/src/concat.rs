@@ -1,7 +1,8 @@
 use rustc_ast as ast;
 use rustc_ast::tokenstream::TokenStream;
-use rustc_expand::base::{self, DummyResult};
+use rustc_expand::base::{self, ConcatPiece, DummyResult};
 use rustc_span::symbol::Symbol;
+use rustc_span::Span;
 
 use std::string::String;
 
@@ -14,12 +15,31 @@ pub fn expand_concat(
         return DummyResult::any(sp);
     };
     let mut accumulator = String::new();
+    let mut pieces = vec![];
     let mut missing_literal = vec![];
     let mut has_errors = false;
     for e in es {
+        let start = accumulator.len();
         match e.kind {
             ast::ExprKind::Lit(ref lit) => match lit.kind {
-                ast::LitKind::Str(ref s, _) | ast::LitKind::Float(ref s, _) => {
+                ast::LitKind::Str(s, style) => {
+                    accumulator.push_str(s.as_str());
+                    if let Some(nested) = cx.concat_pieces.get(&e.span) {
+                        // The literal was itself assembled by `concat!`.
+                        pieces.extend(nested.iter().map(|piece| ConcatPiece {
+                            range: start + piece.range.start..start + piece.range.end,
+                            ..piece.clone()
+                        }));
+                    } else {
+                        pieces.push(ConcatPiece {
+                            range: start..accumulator.len(),
+                            span: e.span,
+                            verbatim_offset: verbatim_offset(cx, e.span, s, style),
+                        });
+                    }
+                    continue;
+                }
+                ast::LitKind::Float(ref s, _) => {
                     accumulator.push_str(s.as_str());
                 }
                 ast::LitKind::Char(c) => {
@@ -50,6 +70,11 @@ pub fn expand_concat(
                 missing_literal.push(e.span);
             }
         }
+        pieces.push(ConcatPiece {
+            range: start..accumulator.len(),
+            span: e.span,
+            verbatim_offset: None,
+        });
     }
     if !missing_literal.is_empty() {
         let mut err = cx.struct_span_err(missing_literal, "expected a literal");
@@ -60,5 +85,23 @@ pub fn expand_concat(
         return DummyResult::any(sp);
     }
     let sp = cx.with_def_site_ctxt(sp);
+    cx.concat_pieces.insert(sp, pieces);
     base::MacEager::expr(cx.expr_str(sp, Symbol::intern(&accumulator)))
 }
+
+/// Returns the offset of the contents of a string literal in its source, if they are written out
+/// verbatim, so that spans inside the literal can be computed.
+fn verbatim_offset(
+    cx: &base::ExtCtxt<'_>,
+    span: Span,
+    contents: Symbol,
+    style: ast::StrStyle,
+) -> Option<usize> {
+    let snippet = cx.source_map().span_to_snippet(span).ok()?;
+    let (prefix, suffix) = match style {
+        ast::StrStyle::Cooked => (1, 1),
+        ast::StrStyle::Raw(n) => (n as usize + 2, n as usize + 1),
+    };
+    let source = snippet.get(prefix..snippet.len().checked_sub(suffix)?)?;
+    (source == contents.as_str()).then(|| prefix)
+}
@@ -9,6 +9,7 @@
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_errors::{pluralize, Applicability, MultiSpan, PResult};
 use rustc_expand::base::{self, *};
+use rustc_expand::expand::AstFragment;
 use rustc_parse_format as parse;
 use rustc_span::symbol::{sym, Ident, Symbol};
 use rustc_span::{InnerSpan, Span};
@@ -16,6 +17,7 @@
 
 use std::borrow::Cow;
 use std::collections::hash_map::Entry;
+use std::mem;
 
 #[derive(PartialEq)]
 enum ArgumentType {
@@ -29,6 +31,50 @@ enum Position {
     Named(Symbol, InnerSpan),
 }
 
+/// Where the format string was written.
+enum FormatSource {
+    /// A string literal.
+    Literal,
+    /// A string assembled by `concat!` from these pieces.
+    Concat(Vec<ConcatPiece>),
+    /// Some other macro, whose output cannot be pointed into.
+    Opaque,
+}
+
+/// A piece of the format string once literal arguments and nested `format_args!` calls have been
+/// flattened into it.
+enum FlatPiece<'a> {
+    Piece(parse::Piece<'a>),
+    /// Text that was not written in the format string itself.
+    Text(String),
+}
+
+impl FormatSource {
+    /// Maps a span inside the format string back to its source, if it can be pointed at. Spans
+    /// inside `concat!` pieces that are not written out verbatim, or that cross pieces, point at
+    /// the piece they start in.
+    fn inner_span(&self, fmtsp: Span, span: InnerSpan) -> Option<Span> {
+        match self {
+            FormatSource::Literal => Some(fmtsp.from_inner(span)),
+            FormatSource::Concat(pieces) => {
+                // The parser offsets all positions by the opening quote of the string.
+                let (start, end) = (span.start.checked_sub(1)?, span.end.checked_sub(1)?);
+                let piece = pieces.iter().find(|piece| piece.range.contains(&start))?;
+                Some(match piece.verbatim_offset {
+                    Some(offset) if end <= piece.range.end => {
+                        piece.span.from_inner(InnerSpan::new(
+                            offset + start - piece.range.start,
+                            offset + end - piece.range.start,
+                        ))
+                    }
+                    _ => piece.span,
+                })
+            }
+            FormatSource::Opaque => None,
+        }
+    }
+}
+
 struct Context<'a, 'b> {
     ecx: &'a mut ExtCtxt<'b>,
     /// The macro's call site. References to unstable formatting internals must
@@ -113,8 +159,8 @@ struct Context<'a, 'b> {
     /// All the formatting arguments that have formatting flags set, in order for diagnostics.
     arg_with_formatting: Vec<parse::FormatSpec<'a>>,
 
-    /// Whether this format string came from a string literal, as opposed to a macro.
-    is_literal: bool,
+    /// Where the format string was written, used to point into it.
+    fmt_source: FormatSource,
 }
 
 /// Parses the arguments from the given list of tokens, returning the diagnostic
@@ -234,6 +280,12 @@ fn parse_args<'a>(
 }
 
 impl<'a, 'b> Context<'a, 'b> {
+    /// Maps a span inside the format string back to its source, falling back to the whole
+    /// format string.
+    fn inner_span(&self, span: InnerSpan) -> Span {
+        self.fmt_source.inner_span(self.fmtsp, span).unwrap_or(self.fmtsp)
+    }
+
     /// The number of arguments that were explicitly given.
     fn num_args(&self) -> usize {
         self.args.len() - self.num_captured_args
@@ -296,7 +348,7 @@ fn verify_piece(&mut self, p: &parse::Piece<'_>) {
                         let sp = arg
                             .format
                             .ty_span
-                            .map(|sp| fmtsp.from_inner(InnerSpan::new(sp.start, sp.end)));
+                            .map(|sp| self.inner_span(InnerSpan::new(sp.start, sp.end)));
                         let mut err = self.ecx.struct_span_err(
                             sp.unwrap_or(fmtsp),
                             &format!("unknown format trait `{}`", arg.format.ty),
@@ -433,7 +485,7 @@ fn report_invalid_references(&self, numbered_position_args: bool) {
 
         for fmt in &self.arg_with_formatting {
             if let Some(span) = fmt.precision_span {
-                let span = self.fmtsp.from_inner(InnerSpan::new(span.start, span.end));
+                let span = self.inner_span(InnerSpan::new(span.start, span.end));
                 match fmt.precision {
                     parse::CountIsParam(pos) if pos > self.num_args() => {
                         e.span_label(
@@ -479,7 +531,7 @@ fn report_invalid_references(&self, numbered_position_args: bool) {
                 }
             }
             if let Some(span) = fmt.width_span {
-                let span = self.fmtsp.from_inner(InnerSpan::new(span.start, span.end));
+                let span = self.inner_span(InnerSpan::new(span.start, span.end));
                 match fmt.width {
                     parse::CountIsParam(pos) if pos > self.num_args() => {
                         e.span_label(
@@ -551,30 +603,22 @@ fn verify_arg_type(&mut self, arg: Position, ty: ArgumentType) {
                         self.verify_arg_type(Capture(idx), ty)
                     }
                     None => {
-                        // For the moment capturing variables from format strings expanded from macros is
-                        // disabled (see RFC #2795)
-                        if self.is_literal {
+                        // Capturing variables is only possible if the name can be pointed at
+                        // in the literal it was written in, as its span determines the scope
+                        // the variable is resolved in. For format strings expanded from other
+                        // macros this is disabled (see RFC #2795).
+                        if let Some(span) = self.fmt_source.inner_span(self.fmtsp, span) {
                             // Treat this name as a variable to capture from the surrounding scope
                             let idx = self.args.len();
                             self.arg_types.push(Vec::new());
                             self.arg_unique_types.push(Vec::new());
-                            let span = if self.is_literal {
-                                self.fmtsp.from_inner(span)
-                            } else {
-                                self.fmtsp
-                            };
                             self.num_captured_args += 1;
                             self.args.push(self.ecx.expr_ident(span, Ident::new(name, span)));
                             self.names.insert(name, idx);
                             self.verify_arg_type(Capture(idx), ty)
                         } else {
                             let msg = format!("there is no argument named `{}`", name);
-                            let sp = if self.is_literal {
-                                self.fmtsp.from_inner(span)
-                            } else {
-                                self.fmtsp
-                            };
-                            let mut err = self.ecx.struct_span_err(sp, &msg);
+                            let mut err = self.ecx.struct_span_err(self.fmtsp, &msg);
 
                             err.note(&format!(
                                 "did you intend to capture a variable `{}` from \
@@ -594,6 +638,155 @@ fn verify_arg_type(&mut self, arg: Position, ty: ArgumentType) {
         }
     }
 
+    /// Whether all arguments are used and all placeholders are valid, so that the arguments can
+    /// be rearranged by `flatten`.
+    fn is_well_formed(&self) -> bool {
+        self.invalid_refs.is_empty()
+            && self.arg_unique_types.iter().flatten().all(|ty| *ty != Placeholder("<invalid>"))
+            && self
+                .arg_types
+                .iter()
+                .enumerate()
+                .all(|(i, ty)| !ty.is_empty() || self.count_positions.contains_key(&i))
+    }
+
+    /// Writes the arguments that are only formatted with a plain `{}` into the format string if
+    /// they are literals, and splices in the pieces and arguments of nested `format_args!` calls,
+    /// so that they are formatted in a single pass. The placeholders are then verified again to
+    /// record the types of the new arguments.
+    fn flatten(&mut self, pieces: Vec<parse::Piece<'a>>) -> Vec<FlatPiece<'a>> {
+        let mut plain = vec![true; self.args.len()];
+        for piece in &pieces {
+            if let parse::NextArgument(arg) = piece {
+                for count in [arg.format.width, arg.format.precision] {
+                    if let parse::CountIsParam(i) = count {
+                        plain[i] = false;
+                    }
+                }
+                if let Some(i) = arg.position.index()
+                    && !is_plain_display(&arg.format)
+                {
+                    plain[i] = false;
+                }
+            }
+        }
+
+        // For every original argument, the index of its first new argument, and the pieces to
+        // replace its plain `{}` placeholders with.
+        let mut replacements = Vec::with_capacity(plain.len());
+        let mut args = Vec::with_capacity(plain.len());
+        for (arg, plain) in mem::take(&mut self.args).into_iter().zip(plain) {
+            let flat = if plain {
+                self.flatten_arg(arg)
+            } else {
+                FlatFormatArgs { pieces: vec![FlatFormatPiece::Argument(0)], args: vec![arg] }
+            };
+            replacements.push((args.len(), flat.pieces));
+            args.extend(flat.args);
+        }
+        self.args = args;
+
+        let mut flat_pieces = Vec::with_capacity(pieces.len());
+        for piece in pieces {
+            let mut arg = match piece {
+                parse::String(_) => {
+                    flat_pieces.push(FlatPiece::Piece(piece));
+                    continue;
+                }
+                parse::NextArgument(arg) => arg,
+            };
+            // Names were resolved and the format string is well-formed, so this is an index.
+            let (first, ref replacement) = replacements[arg.position.index().unwrap()];
+            if is_plain_display(&arg.format) {
+                flat_pieces.extend(replacement.iter().map(|piece| match *piece {
+                    FlatFormatPiece::Literal(ref text) => FlatPiece::Text(text.clone()),
+                    FlatFormatPiece::Argument(i) => {
+                        FlatPiece::Piece(parse::NextArgument(parse::Argument {
+                            position: parse::ArgumentIs(first + i),
+                            ..arg
+                        }))
+                    }
+                }));
+            } else {
+                arg.position = parse::ArgumentIs(first);
+                for count in [&mut arg.format.width, &mut arg.format.precision] {
+                    if let parse::CountIsParam(i) = count {
+                        *i = replacements[*i].0;
+                    }
+                }
+                flat_pieces.push(FlatPiece::Piece(parse::NextArgument(arg)));
+            }
+        }
+
+        self.num_captured_args = 0;
+        self.arg_types = self.args.iter().map(|_| Vec::new()).collect();
+        self.arg_unique_types = self.args.iter().map(|_| Vec::new()).collect();
+        self.count_args.clear();
+        self.count_positions.clear();
+        self.count_positions_count = 0;
+        self.curpiece = 0;
+        for piece in &flat_pieces {
+            if let FlatPiece::Piece(piece) = piece {
+                self.verify_piece(piece);
+            }
+        }
+        flat_pieces
+    }
+
+    /// Describes how an argument that is formatted with a plain `{}` is flattened: literals
+    /// become text, nested `format_args!` calls bring their own pieces and arguments, and other
+    /// expressions stay a single argument.
+    fn flatten_arg(&mut self, arg: P<ast::Expr>) -> FlatFormatArgs {
+        let arg = match arg.kind {
+            ast::ExprKind::Lit(ref lit) => {
+                if let Some(text) = literal_text(&lit.kind) {
+                    return FlatFormatArgs {
+                        pieces: vec![FlatFormatPiece::Literal(text)],
+                        args: Vec::new(),
+                    };
+                }
+                arg
+            }
+            ast::ExprKind::MacCall(ref mac)
+                if mac.path == sym::format_args || mac.path == sym::format_args_nl =>
+            {
+                // Expand the nested call eagerly, asking it to describe itself if it can be
+                // flattened. It is expanded as usual if it turns out to be some other macro.
+                let call_site = arg.span;
+                self.ecx.flat_format_args.insert(call_site, None);
+                let arg =
+                    self.ecx.expander().fully_expand_fragment(AstFragment::Expr(arg)).make_expr();
+                if let Some(Some(flat)) = self.ecx.flat_format_args.remove(&call_site) {
+                    return flat;
+                }
+                arg
+            }
+            _ => arg,
+        };
+        FlatFormatArgs { pieces: vec![FlatFormatPiece::Argument(0)], args: vec![arg] }
+    }
+
+    /// Describes this call for an enclosing `format_args!` call to flatten it into itself, if
+    /// all placeholders are a plain `{}`. Arguments containing macro calls are not moved into the
+    /// enclosing call, as they would be expanded a second time.
+    fn flat_format_args(&self, pieces: &[FlatPiece<'_>]) -> Option<FlatFormatArgs> {
+        if !self.count_args.is_empty() || self.args.iter().any(|arg| contains_mac_call(arg)) {
+            return None;
+        }
+        let pieces = pieces
+            .iter()
+            .map(|piece| match piece {
+                FlatPiece::Text(text) => Some(FlatFormatPiece::Literal(text.clone())),
+                FlatPiece::Piece(parse::String(s)) => Some(FlatFormatPiece::Literal(s.to_string())),
+                FlatPiece::Piece(parse::NextArgument(arg)) if is_plain_display(&arg.format) => {
+                    arg.position.index().map(FlatFormatPiece::Argument)
+                }
+                FlatPiece::Piece(parse::NextArgument(_)) => None,
+            })
+            .collect::<Option<_>>()?;
+        Some(FlatFormatArgs { pieces, args: self.args.clone() })
+    }
+
     /// Builds the mapping between format placeholders and argument objects.
     fn build_index_map(&mut self) {
         // NOTE: Keep the ordering the same as `into_expr`'s expansion would do!
@@ -1021,6 +1214,7 @@ pub fn expand_preparsed_format_args(
 
     let fmt_str = fmt_str.as_str(); // for the suggestions below
     let fmt_snippet = ecx.source_map().span_to_snippet(fmt_sp).ok();
+    let concat_pieces = ecx.concat_pieces.get(&fmt_span).cloned();
     let mut parser = parse::Parser::new(
         fmt_str,
         str_style,
@@ -1028,6 +1222,11 @@ pub fn expand_preparsed_format_args(
         append_newline,
         parse::ParseMode::Format,
     );
+    if concat_pieces.is_some() {
+        // Strings assembled by `concat!` have no escapes of their own, so the parser can record
+        // spans inside them just like for a literal. These are mapped back to the pieces below.
+        parser.is_literal = true;
+    }
 
     let mut unverified_pieces = Vec::new();
     while let Some(piece) = parser.next() {
@@ -1038,21 +1237,25 @@ pub fn expand_preparsed_format_args(
         }
     }
 
+    let fmt_source = match concat_pieces {
+        Some(pieces) => FormatSource::Concat(pieces),
+        None if parser.is_literal => FormatSource::Literal,
+        None => FormatSource::Opaque,
+    };
+
     if !parser.errors.is_empty() {
         let err = parser.errors.remove(0);
-        let sp = if efmt_kind_is_lit {
-            fmt_span.from_inner(InnerSpan::new(err.span.start, err.span.end))
-        } else {
-            // The format string could be another macro invocation, e.g.:
-            //     format!(concat!("abc", "{}"), 4);
-            // However, `err.span` is an inner span relative to the *result* of
-            // the macro invocation, which is why we would get a nonsensical
-            // result calling `fmt_span.from_inner(err.span)` as above, and
-            // might even end up inside a multibyte character (issue #86085).
-            // Therefore, we conservatively report the error for the entire
-            // argument span here.
-            fmt_span
-        };
+        // The format string could be another macro invocation, e.g.:
+        //     format!(concat!("abc", "{}"), 4);
+        // However, `err.span` is an inner span relative to the *result* of
+        // the macro invocation, which is why we would get a nonsensical
+        // result calling `fmt_span.from_inner(err.span)`, and might even end
+        // up inside a multibyte character (issue #86085). Therefore, unless
+        // the span can be mapped back to a piece of a `concat!`, we
+        // conservatively report the error for the entire argument span here.
+        let sp = fmt_source
+            .inner_span(fmt_span, InnerSpan::new(err.span.start, err.span.end))
+            .unwrap_or(fmt_span);
         let mut e = ecx.struct_span_err(sp, &format!("invalid format string: {}", err.description));
         e.span_label(sp, err.label + " in format string");
         if let Some(note) = err.note {
@@ -1070,7 +1273,11 @@ pub fn expand_preparsed_format_args(
     let arg_spans = parser
         .arg_places
         .iter()
-        .map(|span| fmt_span.from_inner(InnerSpan::new(span.start, span.end)))
+        .map(|span| {
+            fmt_source
+                .inner_span(fmt_span, InnerSpan::new(span.start, span.end))
+                .unwrap_or(fmt_span)
+        })
         .collect();
 
     let named_pos: FxHashSet<usize> = names.values().cloned().collect();
@@ -1098,7 +1305,7 @@ pub fn expand_preparsed_format_args(
         invalid_refs: Vec::new(),
         arg_spans,
         arg_with_formatting: Vec::new(),
-        is_literal: parser.is_literal,
+        fmt_source,
     };
 
     // This needs to happen *after* the Parser has consumed all pieces to create all the spans
@@ -1116,15 +1323,33 @@ pub fn expand_preparsed_format_args(
         parse::NextArgument(arg) => matches!(arg.position, parse::Position::ArgumentIs(_)),
     });
 
+    let flatten = cx.ecx.sess.opts.debugging_opts.flatten_format_args && cx.is_well_formed();
+    let pieces = if flatten {
+        cx.flatten(pieces)
+    } else {
+        pieces.into_iter().map(FlatPiece::Piece).collect()
+    };
+    // An enclosing `format_args!` call that expanded this one eagerly asks for its pieces.
+    let call_site = cx.ecx.call_site();
+    if flatten && cx.ecx.flat_format_args.contains_key(&call_site) {
+        let flat = cx.flat_format_args(&pieces);
+        cx.ecx.flat_format_args.insert(call_site, flat);
+    }
+
     cx.build_index_map();
 
     let mut arg_index_consumed = vec![0usize; cx.arg_index_map.len()];
 
     for piece in pieces {
-        if let Some(piece) = cx.build_piece(&piece, &mut arg_index_consumed) {
-            let s = cx.build_literal_string();
-            cx.str_pieces.push(s);
-            cx.pieces.push(piece);
+        match piece {
+            FlatPiece::Text(text) => cx.literal.push_str(&text),
+            FlatPiece::Piece(piece) => {
+                if let Some(piece) = cx.build_piece(&piece, &mut arg_index_consumed) {
+                    let s = cx.build_literal_string();
+                    cx.str_pieces.push(s);
+                    cx.pieces.push(piece);
+                }
+            }
         }
     }
 
@@ -1270,6 +1495,64 @@ macro_rules! check_foreign {
     cx.into_expr()
 }
 
+/// Whether the placeholder is a plain `{}`, i.e. `Display` without any formatting options.
+fn is_plain_display(format: &parse::FormatSpec<'_>) -> bool {
+    format.fill.is_none()
+        && format.align == parse::AlignUnknown
+        && format.flags == 0
+        && format.precision == parse::CountImplied
+        && format.width == parse::CountImplied
+        && format.ty.is_empty()
+}
+
+/// The text a literal is displayed as, for literals that can be written into the format string.
+fn literal_text(lit: &ast::LitKind) -> Option<String> {
+    match *lit {
+        ast::LitKind::Str(s, _) => Some(s.to_string()),
+        ast::LitKind::Char(c) => Some(c.to_string()),
+        ast::LitKind::Bool(b) => Some(b.to_string()),
+        ast::LitKind::Int(n, ty) => {
+            use ast::{IntTy::*, LitIntType::*, UintTy::*};
+            let max = match ty {
+                Signed(I8) => i8::MAX as u128,
+                Signed(I16) => i16::MAX as u128,
+                Signed(I32) | Unsuffixed => i32::MAX as u128,
+                Signed(I64) => i64::MAX as u128,
+                Signed(I128) => i128::MAX as u128,
+                Unsigned(U8) => u8::MAX.into(),
+                Unsigned(U16) => u16::MAX.into(),
+                Unsigned(U32) => u32::MAX.into(),
+                Unsigned(U64) => u64::MAX.into(),
+                Unsigned(U128) => u128::MAX,
+                // Their size depends on the target.
+                Signed(Isize) | Unsigned(Usize) => return None,
+            };
+            // Integers that do not fit their type are left for the `overflowing_literals` lint.
+            (n <= max).then(|| n.to_string())
+        }
+        _ => None,
+    }
+}
+
+fn contains_mac_call(e: &ast::Expr) -> bool {
+    struct ContainsMacCall(bool);
+
+    impl Visitor<'_> for ContainsMacCall {
+        fn visit_mac_call(&mut self, _: &ast::MacCall) {
+            self.0 = true;
+        }
+
+        fn visit_attribute(&mut self, _: &ast::Attribute) {
+            // This may be a proc macro attribute in expression position.
+            self.0 = true;
+        }
+    }
+
+    let mut visitor = ContainsMacCall(false);
+    visitor.visit_expr(e);
+    visitor.0
+}
+
 fn may_contain_yield_point(e: &ast::Expr) -> bool {
     struct MayContainYieldPoint(bool);
 
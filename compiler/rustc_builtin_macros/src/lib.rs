@@ -38,6 +38,7 @@
 mod global_allocator;
 mod log_syntax;
 mod source_util;
+mod target_clones;
 mod test;
 mod trace_macros;
 mod util;
@@ -95,6 +96,7 @@ pub fn register_builtin_macros(resolver: &mut dyn ResolverExpand) {
         cfg_eval: cfg_eval::expand,
         derive: derive::Expander,
         global_allocator: global_allocator::expand,
+        target_clones: target_clones::expand,
         test: test::expand_test,
         test_case: test::expand_test_case,
     }
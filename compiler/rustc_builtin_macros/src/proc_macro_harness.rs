@@ -7,6 +7,7 @@
 use rustc_ast_pretty::pprust;
 use rustc_expand::base::{parse_macro_name_and_helper_attrs, ExtCtxt, ResolverExpand};
 use rustc_expand::expand::{AstFragment, ExpansionConfig};
+use rustc_session::parse::feature_err;
 use rustc_session::Session;
 use rustc_span::hygiene::AstPass;
 use rustc_span::source_map::SourceMap;
@@ -121,7 +122,32 @@ fn collect_custom_derive(&mut self, item: &'a ast::Item, attr: &'a ast::Attribut
         }
     }
 
-    fn collect_attr_proc_macro(&mut self, item: &'a ast::Item) {
+    fn collect_attr_proc_macro(&mut self, item: &'a ast::Item, attr: &'a ast::Attribute) {
+        for option in attr.meta_item_list().unwrap_or_default() {
+            match option.ident() {
+                Some(ident)
+                    if option.is_word()
+                        && (ident.name == sym::cfg_eval || ident.name == sym::expand_derives) =>
+                {
+                    if !self.sess.features_untracked().proc_macro_cfg_eval {
+                        feature_err(
+                            &self.sess.parse_sess,
+                            sym::proc_macro_cfg_eval,
+                            option.span(),
+                            &format!("`#[proc_macro_attribute({})]` is experimental", ident),
+                        )
+                        .emit();
+                    }
+                }
+                _ => {
+                    self.handler
+                        .struct_span_err(option.span(), "unknown `proc_macro_attribute` option")
+                        .help("the supported options are `cfg_eval` and `expand_derives`")
+                        .emit();
+                }
+            }
+        }
+
         if self.in_root && item.vis.kind.is_pub() {
             self.macros.push(ProcMacro::Attr(ProcMacroDef {
                 id: item.id,
@@ -245,7 +271,7 @@ fn visit_item(&mut self, item: &'a ast::Item) {
         if attr.has_name(sym::proc_macro_derive) {
             self.collect_custom_derive(item, attr);
         } else if attr.has_name(sym::proc_macro_attribute) {
-            self.collect_attr_proc_macro(item);
+            self.collect_attr_proc_macro(item, attr);
         } else if attr.has_name(sym::proc_macro) {
             self.collect_bang_proc_macro(item);
         };
@@ -7,6 +7,7 @@
 use rustc_expand::module::DirOwnership;
 use rustc_parse::parser::{ForceCollect, Parser};
 use rustc_parse::{self, new_parser_from_file};
+use rustc_session::config::RemapPathScopeComponents;
 use rustc_session::lint::builtin::INCOMPLETE_INCLUDE;
 use rustc_span::symbol::Symbol;
 use rustc_span::{self, Pos, Span};
@@ -61,9 +62,9 @@ pub fn expand_file(
 
     let topmost = cx.expansion_cause().unwrap_or(sp);
     let loc = cx.source_map().lookup_char_pos(topmost.lo());
-    base::MacEager::expr(
-        cx.expr_str(topmost, Symbol::intern(&loc.file.name.prefer_remapped().to_string_lossy())),
-    )
+    let display_pref = cx.sess.filename_display_preference(RemapPathScopeComponents::MACRO);
+    let file = loc.file.name.display(display_pref).to_string_lossy();
+    base::MacEager::expr(cx.expr_str(topmost, Symbol::intern(&file)))
 }
 
 pub fn expand_stringify(
@@ -0,0 +1,279 @@
+//! Implementation of the `#[target_clones("feature", ...)]` attribute macro.
+//!
+//! The annotated function is turned into a dispatcher that, on every call, picks the first clone
+//! whose target features are detected at runtime, falling back to a clone compiled for the
+//! baseline target features:
+//!
+//! ```ignore (illustrative)
+//! #[target_clones("avx2", "sse4.1")]
+//! fn sum(xs: &[f32]) -> f32 { body }
+//!
+//! // becomes
+//!
+//! fn sum(arg0: &[f32]) -> f32 {
+//!     #[target_feature(enable = "avx2")]
+//!     unsafe fn sum_avx2(xs: &[f32]) -> f32 { body }
+//!     #[target_feature(enable = "sse4.1")]
+//!     unsafe fn sum_sse4_1(xs: &[f32]) -> f32 { body }
+//!     fn sum_default(xs: &[f32]) -> f32 { body }
+//!
+//!     if ::std::arch::is_x86_feature_detected!("avx2") {
+//!         unsafe { sum_avx2(arg0) }
+//!     } else if ::std::arch::is_x86_feature_detected!("sse4.1") {
+//!         unsafe { sum_sse4_1(arg0) }
+//!     } else {
+//!         unsafe { sum_default(arg0) }
+//!     }
+//! }
+//! ```
+
+use rustc_ast::ptr::P;
+use rustc_ast::token;
+use rustc_ast::tokenstream::{DelimSpan, TokenStream, TokenTree};
+use rustc_ast::visit::{self, Visitor};
+use rustc_ast::{self as ast, attr, Fn, FnSig, GenericArg, GenericParamKind, ItemKind, Unsafe};
+use rustc_expand::base::{Annotatable, ExtCtxt};
+use rustc_feature::AttributeTemplate;
+use rustc_parse::validate_attr;
+use rustc_span::symbol::{sym, Ident, Symbol};
+use rustc_span::Span;
+
+pub fn expand(
+    ecx: &mut ExtCtxt<'_>,
+    span: Span,
+    meta_item: &ast::MetaItem,
+    item: Annotatable,
+) -> Vec<Annotatable> {
+    let template = AttributeTemplate {
+        list: Some(r#""feature1", "feature2,feature3", ..."#),
+        ..Default::default()
+    };
+    let attribute = ecx.attribute(meta_item.clone());
+    validate_attr::check_builtin_attribute(
+        &ecx.sess.parse_sess,
+        &attribute,
+        sym::target_clones,
+        template,
+    );
+
+    let Some(feature_sets) = parse_feature_sets(ecx, meta_item) else {
+        return vec![item];
+    };
+    let Some(detect_macro) = feature_detection_macro(&ecx.sess.target.arch) else {
+        ecx.span_err(span, "`#[target_clones]` is not supported on this target");
+        return vec![item];
+    };
+
+    let Annotatable::Item(fn_item) = &item else {
+        ecx.span_err(item.span(), "`#[target_clones]` can only be applied to free functions");
+        return vec![item];
+    };
+    let ItemKind::Fn(box Fn { ref sig, ref generics, body: Some(ref body), .. }) = fn_item.kind
+    else {
+        ecx.span_err(item.span(), "`#[target_clones]` can only be applied to free functions");
+        return vec![item];
+    };
+    if sig.header.asyncness.is_async() || matches!(sig.header.constness, ast::Const::Yes(_)) {
+        ecx.span_err(sig.span, "`#[target_clones]` functions cannot be `async` or `const`");
+        return vec![item];
+    }
+    if sig.decl.c_variadic() {
+        ecx.span_err(sig.span, "`#[target_clones]` functions cannot be variadic");
+        return vec![item];
+    }
+    let mut impl_trait_finder = ImplTraitFinder(None);
+    for param in &sig.decl.inputs {
+        impl_trait_finder.visit_ty(&param.ty);
+    }
+    if let Some(impl_trait_span) = impl_trait_finder.0 {
+        ecx.span_err(
+            impl_trait_span,
+            "`#[target_clones]` functions cannot have `impl Trait` parameters",
+        );
+        return vec![item];
+    }
+
+    let span = ecx.with_def_site_ctxt(fn_item.span);
+    let call_site = ecx.with_call_site_ctxt(fn_item.span);
+
+    let args: Vec<_> = (0..sig.decl.inputs.len())
+        .map(|i| Ident::from_str_and_span(&format!("arg{}", i), span))
+        .collect();
+    let generic_args: Vec<_> = generics
+        .params
+        .iter()
+        .filter_map(|param| match param.kind {
+            GenericParamKind::Lifetime => None,
+            GenericParamKind::Type { .. } => {
+                Some(GenericArg::Type(ecx.ty_ident(span, param.ident)))
+            }
+            GenericParamKind::Const { .. } => {
+                let path = ecx.path_ident(span, param.ident);
+                Some(GenericArg::Const(ecx.anon_const(span, ast::ExprKind::Path(None, path))))
+            }
+        })
+        .collect();
+    let call_clone = |name: Ident| {
+        let path = ecx.path_all(span, false, vec![name], generic_args.clone());
+        let args = args.iter().map(|&arg| ecx.expr_ident(span, arg)).collect();
+        let call = ecx.expr_call(span, ecx.expr_path(path), args);
+        ecx.expr_block(P(ast::Block {
+            stmts: vec![ecx.stmt_expr(call)],
+            id: ast::DUMMY_NODE_ID,
+            rules: ast::BlockCheckMode::Unsafe(ast::CompilerGenerated),
+            span,
+            tokens: None,
+            could_be_bare_literal: false,
+        }))
+    };
+    let clone_fn = |name: Ident, attrs: Vec<ast::Attribute>, unsafety: Unsafe| {
+        let header = ast::FnHeader { unsafety, ..sig.header };
+        let kind = ItemKind::Fn(Box::new(Fn {
+            defaultness: ast::Defaultness::Final,
+            generics: generics.clone(),
+            sig: FnSig { header, decl: sig.decl.clone(), span: sig.span },
+            body: Some(body.clone()),
+        }));
+        ecx.stmt_item(span, ecx.item(span, name, attrs, kind))
+    };
+
+    let mut stmts = Vec::new();
+    let mut clones = Vec::new();
+    for &(features, features_span) in &feature_sets {
+        let name = clone_name(fn_item.ident, features.as_str(), span);
+        let enable =
+            attr::mk_name_value_item_str(Ident::new(sym::enable, span), features, features_span);
+        let target_feature = attr::mk_list_item(
+            Ident::new(sym::target_feature, span),
+            vec![ast::NestedMetaItem::MetaItem(enable)],
+        );
+        stmts.push(clone_fn(name, vec![ecx.attribute(target_feature)], Unsafe::Yes(span)));
+
+        let detected = features
+            .as_str()
+            .split(',')
+            .map(|feature| detect_feature(ecx, call_site, detect_macro, feature, features_span))
+            .reduce(|a, b| ecx.expr_binary(span, ast::BinOpKind::And, a, b))
+            .unwrap();
+        clones.push((detected, name));
+    }
+    let default_name = clone_name(fn_item.ident, "default", span);
+    stmts.push(clone_fn(default_name, Vec::new(), sig.header.unsafety));
+
+    let dispatch = clones.into_iter().rev().fold(call_clone(default_name), |els, (cond, name)| {
+        ecx.expr_if(span, cond, call_clone(name), Some(els))
+    });
+    stmts.push(ecx.stmt_expr(dispatch));
+
+    let inputs = sig
+        .decl
+        .inputs
+        .iter()
+        .zip(&args)
+        .map(|(param, &arg)| ecx.param(param.span, arg, param.ty.clone()))
+        .collect();
+    let dispatcher = ItemKind::Fn(Box::new(Fn {
+        defaultness: ast::Defaultness::Final,
+        generics: generics.clone(),
+        sig: FnSig {
+            header: sig.header,
+            decl: ecx.fn_decl(inputs, sig.decl.output.clone()),
+            span: sig.span,
+        },
+        body: Some(ecx.block(span, stmts)),
+    }));
+
+    let mut dispatcher_item = fn_item.clone();
+    dispatcher_item.kind = dispatcher;
+    vec![Annotatable::Item(dispatcher_item)]
+}
+
+/// Parses the list of comma-separated feature sets, one per clone.
+fn parse_feature_sets(ecx: &ExtCtxt<'_>, meta_item: &ast::MetaItem) -> Option<Vec<(Symbol, Span)>> {
+    let list = meta_item.meta_item_list()?;
+    if list.is_empty() {
+        ecx.span_err(meta_item.span, "`#[target_clones]` requires at least one set of features");
+        return None;
+    }
+    let mut feature_sets = Vec::with_capacity(list.len());
+    for nested in list {
+        match nested.literal().map(|lit| &lit.kind) {
+            Some(ast::LitKind::Str(features, _))
+                if features.as_str().split(',').all(|feature| !feature.is_empty()) =>
+            {
+                feature_sets.push((*features, nested.span()));
+            }
+            _ => {
+                ecx.span_err(
+                    nested.span(),
+                    "expected a string literal with a comma-separated list of target features",
+                );
+                return None;
+            }
+        }
+    }
+    Some(feature_sets)
+}
+
+/// The `std::arch` macro used to detect target features at runtime on `arch`.
+fn feature_detection_macro(arch: &str) -> Option<&'static str> {
+    Some(match arch {
+        "x86" | "x86_64" => "is_x86_feature_detected",
+        "aarch64" => "is_aarch64_feature_detected",
+        "arm" => "is_arm_feature_detected",
+        "mips" => "is_mips_feature_detected",
+        "mips64" => "is_mips64_feature_detected",
+        "powerpc" => "is_powerpc_feature_detected",
+        "powerpc64" => "is_powerpc64_feature_detected",
+        "riscv32" | "riscv64" => "is_riscv_feature_detected",
+        _ => return None,
+    })
+}
+
+/// Builds `::std::arch::$detect_macro!("$feature")`.
+fn detect_feature(
+    ecx: &ExtCtxt<'_>,
+    span: Span,
+    detect_macro: &str,
+    feature: &str,
+    feature_span: Span,
+) -> P<ast::Expr> {
+    let path = ecx.path_global(
+        span,
+        vec![
+            Ident::new(sym::std, span),
+            Ident::new(sym::arch, span),
+            Ident::from_str_and_span(detect_macro, span),
+        ],
+    );
+    let feature = token::Lit::new(token::Str, Symbol::intern(feature), None);
+    let feature = TokenTree::token(token::Literal(feature), ecx.with_call_site_ctxt(feature_span));
+    let mac = ast::MacCall {
+        path,
+        args: P(ast::MacArgs::Delimited(
+            DelimSpan::from_single(span),
+            ast::MacDelimiter::Parenthesis,
+            TokenStream::from(feature),
+        )),
+        prior_type_ascription: None,
+    };
+    ecx.expr(span, ast::ExprKind::MacCall(mac))
+}
+
+/// The name of the clone of `ident` for `features`, e.g. `sum_sse4_1` for `"sse4.1"`.
+fn clone_name(ident: Ident, features: &str, span: Span) -> Ident {
+    let features: String =
+        features.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+    Ident::from_str_and_span(&format!("{}_{}", ident, features), span)
+}
+
+struct ImplTraitFinder(Option<Span>);
+
+impl<'a> Visitor<'a> for ImplTraitFinder {
+    fn visit_ty(&mut self, ty: &'a ast::Ty) {
+        if let ast::TyKind::ImplTrait(..) = ty.kind {
+            self.0 = Some(ty.span);
+        }
+        visit::walk_ty(self, ty);
+    }
+}
@@ -0,0 +1,35 @@
+// Check `const` and `sym` operands of `asm!`.
+
+#![feature(asm_const, asm_sym)]
+
+#[cfg(target_arch = "x86_64")]
+use std::arch::asm;
+
+#[no_mangle]
+static mut COUNTER: u64 = 0;
+
+// Not exported, so it can only be called through the wrapper generated for `sym` operands.
+extern "C" fn add_to_counter(n: u64) {
+    unsafe {
+        COUNTER += n;
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+fn main() {
+    unsafe {
+        let x: u64;
+        asm!("mov {}, {}", out(reg) x, const 40 + 2);
+        assert_eq!(x, 42);
+
+        asm!("call {}", sym add_to_counter, in("rdi") 5u64, clobber_abi("C"));
+        assert_eq!(COUNTER, 5);
+
+        let y: u64;
+        asm!("mov {}, qword ptr [rip + {}]", out(reg) y, sym COUNTER);
+        assert_eq!(y, 5);
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn main() {}
@@ -231,6 +231,15 @@ enum BuilderKind {
         }
     }
 
+    fn create_dll_import_lib(
+        _sess: &Session,
+        _lib_name: &str,
+        _dll_imports: &[rustc_session::cstore::DllImport],
+        _tmpdir: &Path,
+    ) -> PathBuf {
+        bug!("creating dll imports is not supported");
+    }
+
     fn inject_dll_import_lib(
         &mut self,
         _lib_name: &str,
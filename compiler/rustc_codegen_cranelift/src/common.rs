@@ -345,9 +345,12 @@ pub(crate) fn get_caller_location(&mut self, mut source_info: mir::SourceInfo) -
         let span_to_caller_location = |fx: &mut FunctionCx<'_, '_, 'tcx>, span: Span| {
             let topmost = span.ctxt().outer_expn().expansion_cause().unwrap_or(span);
             let caller = fx.tcx.sess.source_map().lookup_char_pos(topmost.lo());
+            let display_pref = fx.tcx.sess.filename_display_preference(
+                rustc_session::config::RemapPathScopeComponents::MACRO,
+            );
             let const_loc = fx.tcx.const_caller_location((
                 rustc_span::symbol::Symbol::intern(
-                    &caller.file.name.prefer_remapped().to_string_lossy(),
+                    &caller.file.name.display(display_pref).to_string_lossy(),
                 ),
                 caller.line as u32,
                 caller.col_display as u32 + 1,
@@ -11,7 +11,7 @@
 
 pub(crate) fn codegen_inline_asm<'tcx>(
     fx: &mut FunctionCx<'_, '_, 'tcx>,
-    _span: Span,
+    span: Span,
     template: &[InlineAsmTemplatePiece],
     operands: &[InlineAsmOperand<'tcx>],
     options: InlineAsmOptions,
@@ -103,12 +103,54 @@ pub(crate) fn codegen_inline_asm<'tcx>(
     let mut inputs = Vec::new();
     let mut outputs = Vec::new();
 
+    // `const` and `sym` operands are substituted into the template as text.
+    let operand_strings = operands
+        .iter()
+        .map(|operand| match *operand {
+            InlineAsmOperand::Const { ref value } => {
+                let (const_value, ty) = crate::constant::eval_mir_constant(fx, value);
+                Some(rustc_codegen_ssa::common::asm_const_to_str(
+                    fx.tcx,
+                    span,
+                    const_value,
+                    fx.layout_of(ty),
+                ))
+            }
+            InlineAsmOperand::SymFn { ref value } => {
+                let literal = fx.monomorphize(value.literal);
+                if let ty::FnDef(def_id, substs) = *literal.ty().kind() {
+                    let instance = ty::Instance::resolve_for_fn_ptr(
+                        fx.tcx,
+                        ty::ParamEnv::reveal_all(),
+                        def_id,
+                        substs,
+                    )
+                    .unwrap();
+                    Some(create_wrapper_function(fx, instance))
+                } else {
+                    span_bug!(span, "invalid type for asm sym (fn)");
+                }
+            }
+            InlineAsmOperand::SymStatic { def_id } => {
+                assert!(fx.tcx.is_static(def_id));
+                // FIXME statics can't be wrapped like functions, so this only links if the static
+                // is exported from the codegen unit.
+                let instance = Instance::mono(fx.tcx, def_id).polymorphize(fx.tcx);
+                Some(fx.tcx.symbol_name(instance).name.to_owned())
+            }
+            InlineAsmOperand::In { .. }
+            | InlineAsmOperand::Out { .. }
+            | InlineAsmOperand::InOut { .. } => None,
+        })
+        .collect();
+
     let mut asm_gen = InlineAssemblyGenerator {
         tcx: fx.tcx,
         arch: fx.tcx.sess.asm_arch.unwrap(),
         enclosing_def_id: fx.instance.def_id(),
         template,
         operands,
+        operand_strings,
         options,
         registers: Vec::new(),
         stack_slots_clobber: Vec::new(),
@@ -158,9 +200,9 @@ pub(crate) fn codegen_inline_asm<'tcx>(
                     ));
                 }
             }
-            InlineAsmOperand::Const { value: _ } => todo!(),
-            InlineAsmOperand::SymFn { value: _ } => todo!(),
-            InlineAsmOperand::SymStatic { def_id: _ } => todo!(),
+            InlineAsmOperand::Const { .. }
+            | InlineAsmOperand::SymFn { .. }
+            | InlineAsmOperand::SymStatic { .. } => {}
         }
     }
 
@@ -173,6 +215,8 @@ struct InlineAssemblyGenerator<'a, 'tcx> {
     enclosing_def_id: DefId,
     template: &'a [InlineAsmTemplatePiece],
     operands: &'a [InlineAsmOperand<'tcx>],
+    /// The text to substitute for `const` and `sym` operands.
+    operand_strings: Vec<Option<String>>,
     options: InlineAsmOptions,
     registers: Vec<Option<InlineAsmReg>>,
     stack_slots_clobber: Vec<Option<Size>>,
@@ -441,13 +485,17 @@ fn generate_asm_wrapper(&self, asm_name: &str) -> String {
                     generated_asm.push_str(s);
                 }
                 InlineAsmTemplatePiece::Placeholder { operand_idx, modifier, span: _ } => {
-                    if self.options.contains(InlineAsmOptions::ATT_SYNTAX) {
-                        generated_asm.push('%');
+                    if let Some(string) = &self.operand_strings[*operand_idx] {
+                        generated_asm.push_str(string);
+                    } else {
+                        if self.options.contains(InlineAsmOptions::ATT_SYNTAX) {
+                            generated_asm.push('%');
+                        }
+                        self.registers[*operand_idx]
+                            .unwrap()
+                            .emit(&mut generated_asm, self.arch, *modifier)
+                            .unwrap();
                     }
-                    self.registers[*operand_idx]
-                        .unwrap()
-                        .emit(&mut generated_asm, self.arch, *modifier)
-                        .unwrap();
                 }
             }
         }
@@ -620,6 +668,54 @@ fn restore_register(
     }
 }
 
+/// Creates an exported function forwarding to `instance` and returns its name.
+///
+/// The inline asm is assembled by an external assembler into a separate object file, from which
+/// `instance` itself may not be reachable if it has internal linkage.
+fn create_wrapper_function<'tcx>(
+    fx: &mut FunctionCx<'_, '_, 'tcx>,
+    instance: Instance<'tcx>,
+) -> String {
+    let inline_asm_index = fx.cx.inline_asm_index.get();
+    fx.cx.inline_asm_index.set(inline_asm_index + 1);
+    let wrapper_name = format!(
+        "__inline_asm_{}_wrapper_n{}",
+        fx.cx.cgu_name.as_str().replace('.', "__").replace('-', "_"),
+        inline_asm_index
+    );
+
+    let sig = crate::abi::get_function_sig(fx.tcx, fx.module.isa().triple(), instance);
+    let wrapper_func_id = fx.module.declare_function(&wrapper_name, Linkage::Export, &sig).unwrap();
+    let callee_func_id = crate::abi::import_function(fx.tcx, fx.module, instance);
+
+    let mut ctx = Context::new();
+    ctx.func = Function::with_name_signature(ExternalName::user(0, 0), sig.clone());
+    {
+        let mut func_ctx = FunctionBuilderContext::new();
+        let mut bcx = FunctionBuilder::new(&mut ctx.func, &mut func_ctx);
+
+        let block = bcx.create_block();
+        bcx.switch_to_block(block);
+        let args = sig
+            .params
+            .iter()
+            .map(|param| bcx.append_block_param(block, param.value_type))
+            .collect::<Vec<Value>>();
+
+        let callee_func_ref = fx.module.declare_func_in_func(callee_func_id, &mut bcx.func);
+        let call_inst = bcx.ins().call(callee_func_ref, &args);
+        let results = bcx.inst_results(call_inst).to_vec(); // Clone to prevent borrow error
+
+        bcx.ins().return_(&results);
+        bcx.seal_all_blocks();
+        bcx.finalize();
+    }
+    fx.module.define_function(wrapper_func_id, &mut ctx).unwrap();
+    fx.cx.unwind_context.add_function(wrapper_func_id, &ctx, fx.module.isa());
+
+    wrapper_name
+}
+
 fn call_inline_asm<'tcx>(
     fx: &mut FunctionCx<'_, '_, 'tcx>,
     asm_name: &str,
@@ -208,6 +208,10 @@ enum BuilderKind<'a> {
         }
     }
 
+    fn create_dll_import_lib(_sess: &Session, _lib_name: &str, _dll_imports: &[DllImport], _tmpdir: &Path) -> PathBuf {
+        unimplemented!();
+    }
+
     fn inject_dll_import_lib(&mut self, _lib_name: &str, _dll_imports: &[DllImport], _tmpdir: &MaybeTempDir) {
         unimplemented!();
     }
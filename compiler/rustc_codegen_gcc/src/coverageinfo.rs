@@ -36,6 +36,11 @@ fn add_coverage_unreachable(&mut self, _instance: Instance<'tcx>, _region: CodeR
         // TODO(antoyo)
         false
     }
+
+    fn add_coverage_branch(&mut self, _instance: Instance<'tcx>, _true_operand: ExpressionOperandId, _false_operand: ExpressionOperandId, _region: CodeRegion) -> bool {
+        // TODO(antoyo)
+        false
+    }
 }
 
 impl<'gcc, 'tcx> CoverageInfoMethods<'tcx> for CodegenCx<'gcc, 'tcx> {
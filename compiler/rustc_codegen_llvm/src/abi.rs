@@ -316,7 +316,7 @@ fn arg_memory_ty(&self, arg_abi: &ArgAbi<'tcx, Ty<'tcx>>) -> &'ll Type {
 pub trait FnAbiLlvmExt<'ll, 'tcx> {
     fn llvm_type(&self, cx: &CodegenCx<'ll, 'tcx>) -> &'ll Type;
     fn ptr_to_llvm_type(&self, cx: &CodegenCx<'ll, 'tcx>) -> &'ll Type;
-    fn llvm_cconv(&self) -> llvm::CallConv;
+    fn llvm_cconv(&self) -> c_uint;
     fn apply_attrs_llfn(&self, cx: &CodegenCx<'ll, 'tcx>, llfn: &'ll Value);
     fn apply_attrs_callsite(&self, bx: &mut Builder<'_, 'll, 'tcx>, callsite: &'ll Value);
 }
@@ -390,8 +390,8 @@ fn ptr_to_llvm_type(&self, cx: &CodegenCx<'ll, 'tcx>) -> &'ll Type {
         }
     }
 
-    fn llvm_cconv(&self) -> llvm::CallConv {
-        match self.conv {
+    fn llvm_cconv(&self) -> c_uint {
+        let cconv = match self.conv {
             Conv::C | Conv::Rust | Conv::CCmseNonSecureCall => llvm::CCallConv,
             Conv::RustCold => llvm::ColdCallConv,
             Conv::AmdGpuKernel => llvm::AmdGpuKernel,
@@ -407,7 +407,10 @@ fn llvm_cconv(&self) -> llvm::CallConv {
             Conv::X86VectorCall => llvm::X86_VectorCall,
             Conv::X86_64SysV => llvm::X86_64_SysV,
             Conv::X86_64Win64 => llvm::X86_64_Win64,
-        }
+            // Target-defined ABIs directly specify the LLVM calling convention.
+            Conv::Custom(cconv) => return cconv,
+        };
+        cconv as c_uint
     }
 
     fn apply_attrs_llfn(&self, cx: &CodegenCx<'ll, 'tcx>, llfn: &'ll Value) {
@@ -555,7 +558,7 @@ fn apply_attrs_callsite(&self, bx: &mut Builder<'_, 'll, 'tcx>, callsite: &'ll V
         }
 
         let cconv = self.llvm_cconv();
-        if cconv != llvm::CCallConv {
+        if cconv != llvm::CCallConv as c_uint {
             llvm::SetInstructionCallConv(callsite, cconv);
         }
 
@@ -10,6 +10,8 @@
 use crate::llvm::{self, False, True};
 use crate::ModuleLlvm;
 
+use std::ptr;
+
 pub(crate) unsafe fn codegen(
     tcx: TyCtxt<'_>,
     module_llvm: &mut ModuleLlvm,
@@ -88,7 +90,8 @@ pub(crate) unsafe fn codegen(
             callee,
             args.as_ptr(),
             args.len() as c_uint,
-            None,
+            ptr::null(),
+            0,
         );
         llvm::LLVMSetTailCall(ret, True);
         if output.is_some() {
@@ -133,8 +136,15 @@ pub(crate) unsafe fn codegen(
         .enumerate()
         .map(|(i, _)| llvm::LLVMGetParam(llfn, i as c_uint))
         .collect::<Vec<_>>();
-    let ret =
-        llvm::LLVMRustBuildCall(llbuilder, ty, callee, args.as_ptr(), args.len() as c_uint, None);
+    let ret = llvm::LLVMRustBuildCall(
+        llbuilder,
+        ty,
+        callee,
+        args.as_ptr(),
+        args.len() as c_uint,
+        ptr::null(),
+        0,
+    );
     llvm::LLVMSetTailCall(ret, True);
     llvm::LLVMBuildRetVoid(llbuilder);
     llvm::LLVMDisposeBuilder(llbuilder);
@@ -0,0 +1,348 @@
+//! Lowering of the atomic operations that the target can't perform natively, for
+//! `-Zatomic-shims`. Depending on the target spec, these are either turned into calls to
+//! the `__atomic_*` library functions, or performed non-atomically while interrupts are
+//! masked.
+
+use crate::asm::inline_asm_call;
+use crate::builder::Builder;
+use crate::llvm;
+use crate::type_::Type;
+use crate::value::Value;
+
+use rustc_codegen_ssa::common::{AtomicOrdering, AtomicRmwBinOp, IntPredicate};
+use rustc_codegen_ssa::traits::*;
+use rustc_middle::bug;
+use rustc_target::abi::{Align, Size};
+use rustc_target::spec::AtomicShim;
+
+impl<'a, 'll, 'tcx> Builder<'a, 'll, 'tcx> {
+    /// Returns the shim to lower an atomic operation on a value of `size` to, or `None` if
+    /// the target can perform it natively. `read_modify_write` is set for all operations
+    /// except plain loads and stores.
+    pub(crate) fn atomic_shim(&self, size: Size, read_modify_write: bool) -> Option<AtomicShim> {
+        let shim = self.sess().atomic_shim()?;
+        let target = &self.sess().target;
+        let bits = size.bits();
+        let native = bits >= target.min_atomic_width()
+            && bits <= target.max_atomic_width()
+            && (!read_modify_write || target.atomic_cas);
+        if native { None } else { Some(shim) }
+    }
+
+    pub(crate) fn shim_atomic_load(
+        &mut self,
+        shim: AtomicShim,
+        ty: &'ll Type,
+        ptr: &'ll Value,
+        order: AtomicOrdering,
+        size: Size,
+    ) -> &'ll Value {
+        match shim {
+            AtomicShim::Libcall => {
+                let name = format!("__atomic_load_{}", size.bytes());
+                let args = [self.pointercast(ptr, self.type_i8p()), self.memorder(order)];
+                self.atomic_libcall(&name, &args, ty)
+            }
+            AtomicShim::Interrupts => {
+                let align = natural_align(size);
+                self.with_interrupts_masked(|bx| bx.load(ty, ptr, align))
+            }
+        }
+    }
+
+    pub(crate) fn shim_atomic_store(
+        &mut self,
+        shim: AtomicShim,
+        val: &'ll Value,
+        ptr: &'ll Value,
+        order: AtomicOrdering,
+        size: Size,
+    ) {
+        match shim {
+            AtomicShim::Libcall => {
+                let name = format!("__atomic_store_{}", size.bytes());
+                let args = [
+                    self.pointercast(ptr, self.type_i8p()),
+                    self.libcall_int_arg(val),
+                    self.memorder(order),
+                ];
+                self.atomic_libcall(&name, &args, self.type_void());
+            }
+            AtomicShim::Interrupts => {
+                let align = natural_align(size);
+                self.with_interrupts_masked(|bx| bx.store(val, ptr, align));
+            }
+        }
+    }
+
+    /// Returns the same `{ value, success }` pair as the `cmpxchg` instruction.
+    pub(crate) fn shim_atomic_cmpxchg(
+        &mut self,
+        shim: AtomicShim,
+        dst: &'ll Value,
+        cmp: &'ll Value,
+        src: &'ll Value,
+        order: AtomicOrdering,
+        failure_order: AtomicOrdering,
+        weak: bool,
+    ) -> &'ll Value {
+        let ty = self.val_ty(cmp);
+        let size = Size::from_bits(self.int_width(ty));
+        let align = natural_align(size);
+        let (old, success) = match shim {
+            AtomicShim::Libcall => {
+                let expected = self.alloca(ty, align);
+                self.store(cmp, expected, align);
+                let success = self.libcall_atomic_compare_exchange(
+                    dst,
+                    expected,
+                    src,
+                    order,
+                    failure_order,
+                    weak,
+                );
+                // On failure, the current value has been written to `expected`.
+                (self.load(ty, expected, align), success)
+            }
+            AtomicShim::Interrupts => self.with_interrupts_masked(|bx| {
+                let old = bx.load(ty, dst, align);
+                let success = bx.icmp(IntPredicate::IntEQ, old, cmp);
+                let new = bx.select(success, src, old);
+                bx.store(new, dst, align);
+                (old, success)
+            }),
+        };
+        let pair_ty = self.type_struct(&[ty, self.type_i1()], false);
+        let pair = self.insert_value(self.const_undef(pair_ty), old, 0);
+        self.insert_value(pair, success, 1)
+    }
+
+    /// Returns the previous value, like the `atomicrmw` instruction.
+    pub(crate) fn shim_atomic_rmw(
+        &mut self,
+        shim: AtomicShim,
+        op: AtomicRmwBinOp,
+        dst: &'ll Value,
+        src: &'ll Value,
+        order: AtomicOrdering,
+    ) -> &'ll Value {
+        let ty = self.val_ty(src);
+        let size = Size::from_bits(self.int_width(ty));
+        match shim {
+            AtomicShim::Libcall => {
+                let function = match op {
+                    AtomicRmwBinOp::AtomicXchg => "exchange",
+                    AtomicRmwBinOp::AtomicAdd => "fetch_add",
+                    AtomicRmwBinOp::AtomicSub => "fetch_sub",
+                    AtomicRmwBinOp::AtomicAnd => "fetch_and",
+                    AtomicRmwBinOp::AtomicNand => "fetch_nand",
+                    AtomicRmwBinOp::AtomicOr => "fetch_or",
+                    AtomicRmwBinOp::AtomicXor => "fetch_xor",
+                    AtomicRmwBinOp::AtomicMax
+                    | AtomicRmwBinOp::AtomicMin
+                    | AtomicRmwBinOp::AtomicUMax
+                    | AtomicRmwBinOp::AtomicUMin => {
+                        return self.libcall_atomic_min_max(op, dst, src, order);
+                    }
+                };
+                let name = format!("__atomic_{}_{}", function, size.bytes());
+                let args = [
+                    self.pointercast(dst, self.type_i8p()),
+                    self.libcall_int_arg(src),
+                    self.memorder(order),
+                ];
+                self.atomic_libcall(&name, &args, ty)
+            }
+            AtomicShim::Interrupts => {
+                let align = natural_align(size);
+                self.with_interrupts_masked(|bx| {
+                    let old = bx.load(ty, dst, align);
+                    let new = bx.rmw_new_value(op, old, src);
+                    bx.store(new, dst, align);
+                    old
+                })
+            }
+        }
+    }
+
+    /// There are no library functions for the atomic minimum and maximum operations, so
+    /// these are implemented with a compare-exchange loop.
+    fn libcall_atomic_min_max(
+        &mut self,
+        op: AtomicRmwBinOp,
+        dst: &'ll Value,
+        src: &'ll Value,
+        order: AtomicOrdering,
+    ) -> &'ll Value {
+        let ty = self.val_ty(src);
+        let size = Size::from_bits(self.int_width(ty));
+        let align = natural_align(size);
+
+        let expected = self.alloca(ty, align);
+        let initial =
+            self.shim_atomic_load(AtomicShim::Libcall, ty, dst, AtomicOrdering::Relaxed, size);
+        self.store(initial, expected, align);
+
+        let loop_bb = self.append_sibling_block("atomic_min_max_loop");
+        let done_bb = self.append_sibling_block("atomic_min_max_done");
+        self.br(loop_bb);
+
+        self.switch_to_block(loop_bb);
+        let old = self.load(ty, expected, align);
+        let new = self.rmw_new_value(op, old, src);
+        let success = self.libcall_atomic_compare_exchange(
+            dst,
+            expected,
+            new,
+            order,
+            AtomicOrdering::Relaxed,
+            true,
+        );
+        self.cond_br(success, done_bb, loop_bb);
+
+        self.switch_to_block(done_bb);
+        old
+    }
+
+    /// Calls `__atomic_compare_exchange_N` and returns whether the exchange succeeded as
+    /// an `i1`.
+    fn libcall_atomic_compare_exchange(
+        &mut self,
+        dst: &'ll Value,
+        expected: &'ll Value,
+        desired: &'ll Value,
+        order: AtomicOrdering,
+        failure_order: AtomicOrdering,
+        weak: bool,
+    ) -> &'ll Value {
+        let size = self.int_width(self.val_ty(desired)) / 8;
+        let name = format!("__atomic_compare_exchange_{}", size);
+        let args = [
+            self.pointercast(dst, self.type_i8p()),
+            self.pointercast(expected, self.type_i8p()),
+            self.libcall_int_arg(desired),
+            self.const_int(self.type_int(), i64::from(weak)),
+            self.memorder(order),
+            self.memorder(failure_order),
+        ];
+        let success = self.atomic_libcall(&name, &args, self.type_i8());
+        self.icmp(IntPredicate::IntNE, success, self.const_u8(0))
+    }
+
+    fn atomic_libcall(&mut self, name: &str, args: &[&'ll Value], ret: &'ll Type) -> &'ll Value {
+        let arg_tys: Vec<_> = args.iter().map(|&arg| self.val_ty(arg)).collect();
+        let fn_ty = self.type_func(&arg_tys, ret);
+        let llfn = self.declare_cfn(name, llvm::UnnamedAddr::No, fn_ty);
+        self.call(fn_ty, None, None, llfn, args, None)
+    }
+
+    /// Values narrower than a C `int` are passed zero-extended to an `int`, which is how
+    /// the unsigned integer parameters of the `__atomic_*` functions are passed in registers.
+    fn libcall_int_arg(&mut self, val: &'ll Value) -> &'ll Value {
+        let int = self.type_int();
+        if self.int_width(self.val_ty(val)) < self.int_width(int) {
+            self.zext(val, int)
+        } else {
+            val
+        }
+    }
+
+    /// Returns `order` as a value of the C11 `memory_order` enum.
+    fn memorder(&self, order: AtomicOrdering) -> &'ll Value {
+        let memorder = match order {
+            AtomicOrdering::Unordered | AtomicOrdering::Relaxed => 0,
+            AtomicOrdering::Acquire => 2,
+            AtomicOrdering::Release => 3,
+            AtomicOrdering::AcquireRelease => 4,
+            AtomicOrdering::SequentiallyConsistent => 5,
+        };
+        self.const_int(self.type_int(), memorder)
+    }
+
+    /// Computes the value that `op` stores, given the previous value `old`.
+    fn rmw_new_value(
+        &mut self,
+        op: AtomicRmwBinOp,
+        old: &'ll Value,
+        src: &'ll Value,
+    ) -> &'ll Value {
+        let select = |bx: &mut Self, predicate| {
+            let keep_old = bx.icmp(predicate, old, src);
+            bx.select(keep_old, old, src)
+        };
+        match op {
+            AtomicRmwBinOp::AtomicXchg => src,
+            AtomicRmwBinOp::AtomicAdd => self.add(old, src),
+            AtomicRmwBinOp::AtomicSub => self.sub(old, src),
+            AtomicRmwBinOp::AtomicAnd => self.and(old, src),
+            AtomicRmwBinOp::AtomicNand => {
+                let and = self.and(old, src);
+                self.not(and)
+            }
+            AtomicRmwBinOp::AtomicOr => self.or(old, src),
+            AtomicRmwBinOp::AtomicXor => self.xor(old, src),
+            AtomicRmwBinOp::AtomicMax => select(self, IntPredicate::IntSGT),
+            AtomicRmwBinOp::AtomicMin => select(self, IntPredicate::IntSLT),
+            AtomicRmwBinOp::AtomicUMax => select(self, IntPredicate::IntUGT),
+            AtomicRmwBinOp::AtomicUMin => select(self, IntPredicate::IntULT),
+        }
+    }
+
+    fn with_interrupts_masked<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        let state = self.mask_interrupts();
+        let result = f(self);
+        self.restore_interrupts(state);
+        result
+    }
+
+    /// Masks interrupts and returns the previous interrupt state. The `memory` clobber keeps
+    /// LLVM from moving memory accesses out of the critical section.
+    fn mask_interrupts(&mut self) -> &'ll Value {
+        let asm = match &*self.sess().target.arch {
+            "arm" => "mrs $0, PRIMASK\ncpsid i",
+            "riscv32" => "csrrci $0, mstatus, 8",
+            arch => bug!("interrupt masking is not supported on {}", arch),
+        };
+        let ty = self.type_isize();
+        self.interrupts_asm_call(asm, "=r,~{memory}", &[], ty)
+    }
+
+    fn restore_interrupts(&mut self, state: &'ll Value) {
+        let (asm, state) = match &*self.sess().target.arch {
+            "arm" => ("msr PRIMASK, $0", state),
+            // Set the `MIE` bit again if it was set before, without touching the rest of
+            // `mstatus`.
+            "riscv32" => ("csrs mstatus, $0", self.and(state, self.const_usize(8))),
+            arch => bug!("interrupt masking is not supported on {}", arch),
+        };
+        self.interrupts_asm_call(asm, "r,~{memory}", &[state], self.type_void());
+    }
+
+    fn interrupts_asm_call(
+        &mut self,
+        asm: &str,
+        cons: &str,
+        inputs: &[&'ll Value],
+        output: &'ll Type,
+    ) -> &'ll Value {
+        inline_asm_call(
+            self,
+            asm,
+            cons,
+            inputs,
+            output,
+            true,
+            false,
+            llvm::AsmDialect::Att,
+            &[],
+            false,
+            None,
+        )
+        .unwrap_or_else(|| bug!("failed to generate inline asm call for interrupt masking"))
+    }
+}
+
+/// Atomic operations are always performed on naturally aligned values.
+fn natural_align(size: Size) -> Align {
+    Align::from_bytes(size.bytes()).unwrap()
+}
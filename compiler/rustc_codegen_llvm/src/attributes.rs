@@ -5,7 +5,7 @@
 use rustc_hir::def_id::DefId;
 use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::ty::{self, TyCtxt};
-use rustc_session::config::OptLevel;
+use rustc_session::config::{BranchProtection, OptLevel, PAuthKey};
 use rustc_span::symbol::sym;
 use rustc_target::spec::abi::Abi;
 use rustc_target::spec::{FramePointer, SanitizerSet, StackProbeType, StackProtector};
@@ -92,12 +92,22 @@ pub fn uwtable_attr(llcx: &llvm::Context) -> &Attribute {
     llvm::CreateUWTableAttr(llcx, true)
 }
 
-pub fn frame_pointer_type_attr<'ll>(cx: &CodegenCx<'ll, '_>) -> Option<&'ll Attribute> {
-    let mut fp = cx.sess().target.frame_pointer;
+/// Computes the `frame-pointer` attribute for a function. A `#[frame_pointer(...)]` attribute on
+/// the function, passed in as `fn_attr`, takes precedence over `-Cforce-frame-pointers`.
+pub fn frame_pointer_type_attr<'ll>(
+    cx: &CodegenCx<'ll, '_>,
+    fn_attr: Option<FramePointer>,
+) -> Option<&'ll Attribute> {
+    let mut fp = match fn_attr {
+        Some(fp) => fp,
+        None if matches!(cx.sess().opts.cg.force_frame_pointers, Some(true)) => {
+            FramePointer::Always
+        }
+        None => cx.sess().target.frame_pointer,
+    };
     // "mcount" function relies on stack pointer.
     // See <https://sourceware.org/binutils/docs/gprof/Implementation.html>.
-    if cx.sess().instrument_mcount() || matches!(cx.sess().opts.cg.force_frame_pointers, Some(true))
-    {
+    if cx.sess().instrument_mcount() {
         fp = FramePointer::Always;
     }
     let attr_value = match fp {
@@ -184,6 +194,31 @@ fn stackprotector_attr<'ll>(cx: &CodegenCx<'ll, '_>) -> Option<&'ll Attribute> {
     Some(sspattr.create_attr(cx.llcx))
 }
 
+/// Get the LLVM attributes implementing `-Zbranch-protection`. These mirror the
+/// module flags emitted in `context::create_module`, but LLVM only consults the
+/// module flags for functions that don't have the attributes set themselves.
+fn branch_protection_attrs<'ll>(cx: &CodegenCx<'ll, '_>) -> SmallVec<[&'ll Attribute; 3]> {
+    let mut attrs = SmallVec::new();
+    let branch_protection = cx.sess().opts.debugging_opts.branch_protection;
+    let Some(BranchProtection { bti, pac_ret }) = branch_protection else {
+        return attrs;
+    };
+
+    if bti {
+        attrs.push(llvm::CreateAttrStringValue(cx.llcx, "branch-target-enforcement", "true"));
+    }
+    if let Some(pac_ret) = pac_ret {
+        let scope = if pac_ret.leaf { "all" } else { "non-leaf" };
+        let key = match pac_ret.key {
+            PAuthKey::A => "a_key",
+            PAuthKey::B => "b_key",
+        };
+        attrs.push(llvm::CreateAttrStringValue(cx.llcx, "sign-return-address", scope));
+        attrs.push(llvm::CreateAttrStringValue(cx.llcx, "sign-return-address-key", key));
+    }
+    attrs
+}
+
 pub fn target_cpu_attr<'ll>(cx: &CodegenCx<'ll, '_>) -> &'ll Attribute {
     let target_cpu = llvm_util::target_cpu(cx.tcx.sess);
     llvm::CreateAttrStringValue(cx.llcx, "target-cpu", target_cpu)
@@ -279,11 +314,16 @@ pub fn from_fn_attrs<'ll, 'tcx>(
         to_add.push(llvm::CreateAttrString(cx.llcx, "use-sample-profile"));
     }
 
-    // FIXME: none of these three functions interact with source level attributes.
-    to_add.extend(frame_pointer_type_attr(cx));
-    to_add.extend(instrument_function_attr(cx));
-    to_add.extend(probestack_attr(cx));
-    to_add.extend(stackprotector_attr(cx));
+    // Naked functions don't get a prologue or an epilogue, so none of the attributes that
+    // would make LLVM emit extra code around the function body apply to them.
+    if !codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::NAKED) {
+        to_add.extend(frame_pointer_type_attr(cx, codegen_fn_attrs.frame_pointer));
+        // FIXME: none of these three functions interact with source level attributes.
+        to_add.extend(instrument_function_attr(cx));
+        to_add.extend(probestack_attr(cx));
+        to_add.extend(stackprotector_attr(cx));
+        to_add.extend(branch_protection_attrs(cx));
+    }
 
     if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::COLD) {
         to_add.push(AttributeKind::Cold.create_attr(cx.llcx));
@@ -16,13 +16,16 @@
 use rustc_codegen_ssa::MemFlags;
 use rustc_data_structures::small_c_str::SmallCStr;
 use rustc_hir::def_id::DefId;
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrs;
 use rustc_middle::ty::layout::{
     FnAbiError, FnAbiOfHelpers, FnAbiRequest, LayoutError, LayoutOfHelpers, TyAndLayout,
 };
 use rustc_middle::ty::{self, Ty, TyCtxt};
 use rustc_span::Span;
+use rustc_symbol_mangling::kcfi_typeid_for_fnabi;
 use rustc_target::abi::{self, call::FnAbi, Align, Size, WrappingRange};
-use rustc_target::spec::{HasTargetSpec, Target};
+use rustc_target::spec::{HasTargetSpec, SanitizerSet, Target};
+use smallvec::SmallVec;
 use std::borrow::Cow;
 use std::ffi::CStr;
 use std::iter;
@@ -217,6 +220,8 @@ fn switch(
     fn invoke(
         &mut self,
         llty: &'ll Type,
+        fn_attrs: Option<&CodegenFnAttrs>,
+        fn_abi: Option<&FnAbi<'tcx, Ty<'tcx>>>,
         llfn: &'ll Value,
         args: &[&'ll Value],
         then: &'ll BasicBlock,
@@ -226,8 +231,12 @@ fn invoke(
         debug!("invoke {:?} with args ({:?})", llfn, args);
 
         let args = self.check_call("invoke", llty, llfn, args);
-        let bundle = funclet.map(|funclet| funclet.bundle());
-        let bundle = bundle.as_ref().map(|b| &*b.raw);
+        let kcfi_bundle = self.kcfi_operand_bundle(fn_attrs, fn_abi, llfn);
+        let bundles: SmallVec<[_; 2]> = funclet
+            .map(|funclet| &*funclet.bundle().raw)
+            .into_iter()
+            .chain(kcfi_bundle.as_ref().map(|b| &*b.raw))
+            .collect();
 
         unsafe {
             llvm::LLVMRustBuildInvoke(
@@ -238,7 +247,8 @@ fn invoke(
                 args.len() as c_uint,
                 then,
                 catch,
-                bundle,
+                bundles.as_ptr(),
+                bundles.len() as c_uint,
                 UNNAMED,
             )
         }
@@ -450,6 +460,9 @@ fn atomic_load(
         order: rustc_codegen_ssa::common::AtomicOrdering,
         size: Size,
     ) -> &'ll Value {
+        if let Some(shim) = self.atomic_shim(size, false) {
+            return self.shim_atomic_load(shim, ty, ptr, order, size);
+        }
         unsafe {
             let load = llvm::LLVMRustBuildAtomicLoad(
                 self.llbuilder,
@@ -642,6 +655,26 @@ fn type_metadata(&mut self, function: &'ll Value, typeid: String) {
         }
     }
 
+    fn kcfi_type_metadata(&mut self, function: &'ll Value, typeid: String) {
+        let v = [self.const_u32(llvm::kcfi_typeid(&typeid))];
+        let kind = "kcfi_type";
+        unsafe {
+            llvm::LLVMGlobalSetMetadata(
+                function,
+                llvm::LLVMGetMDKindIDInContext(
+                    self.cx.llcx,
+                    kind.as_ptr() as *const c_char,
+                    kind.len() as c_uint,
+                ),
+                llvm::LLVMValueAsMetadata(llvm::LLVMMDNodeInContext(
+                    self.cx.llcx,
+                    v.as_ptr(),
+                    v.len() as c_uint,
+                )),
+            )
+        }
+    }
+
     fn typeid_metadata(&mut self, typeid: String) -> Self::Value {
         unsafe {
             llvm::LLVMMDStringInContext(
@@ -695,6 +728,9 @@ fn atomic_store(
     ) {
         debug!("Store {:?} -> {:?}", val, ptr);
         let ptr = self.check_store(val, ptr);
+        if let Some(shim) = self.atomic_shim(size, false) {
+            return self.shim_atomic_store(shim, val, ptr, order, size);
+        }
         unsafe {
             let store = llvm::LLVMRustBuildAtomicStore(
                 self.llbuilder,
@@ -1068,6 +1104,10 @@ fn atomic_cmpxchg(
         failure_order: rustc_codegen_ssa::common::AtomicOrdering,
         weak: bool,
     ) -> &'ll Value {
+        let size = Size::from_bits(self.int_width(self.val_ty(cmp)));
+        if let Some(shim) = self.atomic_shim(size, true) {
+            return self.shim_atomic_cmpxchg(shim, dst, cmp, src, order, failure_order, weak);
+        }
         let weak = if weak { llvm::True } else { llvm::False };
         unsafe {
             llvm::LLVMRustBuildAtomicCmpXchg(
@@ -1088,6 +1128,10 @@ fn atomic_rmw(
         src: &'ll Value,
         order: rustc_codegen_ssa::common::AtomicOrdering,
     ) -> &'ll Value {
+        let size = Size::from_bits(self.int_width(self.val_ty(src)));
+        if let Some(shim) = self.atomic_shim(size, true) {
+            return self.shim_atomic_rmw(shim, op, dst, src, order);
+        }
         unsafe {
             llvm::LLVMBuildAtomicRMW(
                 self.llbuilder,
@@ -1159,7 +1203,8 @@ fn instrprof_increment(
                 llfn,
                 args.as_ptr() as *const &llvm::Value,
                 args.len() as c_uint,
-                None,
+                ptr::null(),
+                0,
             );
         }
     }
@@ -1167,6 +1212,8 @@ fn instrprof_increment(
     fn call(
         &mut self,
         llty: &'ll Type,
+        fn_attrs: Option<&CodegenFnAttrs>,
+        fn_abi: Option<&FnAbi<'tcx, Ty<'tcx>>>,
         llfn: &'ll Value,
         args: &[&'ll Value],
         funclet: Option<&Funclet<'ll>>,
@@ -1174,8 +1221,12 @@ fn call(
         debug!("call {:?} with args ({:?})", llfn, args);
 
         let args = self.check_call("call", llty, llfn, args);
-        let bundle = funclet.map(|funclet| funclet.bundle());
-        let bundle = bundle.as_ref().map(|b| &*b.raw);
+        let kcfi_bundle = self.kcfi_operand_bundle(fn_attrs, fn_abi, llfn);
+        let bundles: SmallVec<[_; 2]> = funclet
+            .map(|funclet| &*funclet.bundle().raw)
+            .into_iter()
+            .chain(kcfi_bundle.as_ref().map(|b| &*b.raw))
+            .collect();
 
         unsafe {
             llvm::LLVMRustBuildCall(
@@ -1184,7 +1235,8 @@ fn call(
                 llfn,
                 args.as_ptr() as *const &llvm::Value,
                 args.len() as c_uint,
-                bundle,
+                bundles.as_ptr(),
+                bundles.len() as c_uint,
             )
         }
     }
@@ -1223,6 +1275,30 @@ fn position_at_start(&mut self, llbb: &'ll BasicBlock) {
         }
     }
 
+    /// Returns the `kcfi` operand bundle that makes LLVM check the type identifier of the callee of
+    /// an indirect call to a function of type `fn_abi`, if KCFI is enabled for the caller.
+    fn kcfi_operand_bundle(
+        &self,
+        fn_attrs: Option<&CodegenFnAttrs>,
+        fn_abi: Option<&FnAbi<'tcx, Ty<'tcx>>>,
+        llfn: &'ll Value,
+    ) -> Option<llvm::OperandBundleDef<'ll>> {
+        if !self.tcx.sess.is_sanitizer_kcfi_enabled() {
+            return None;
+        }
+        let fn_abi = fn_abi?;
+        if fn_attrs.map_or(false, |attrs| attrs.no_sanitize.contains(SanitizerSet::KCFI)) {
+            return None;
+        }
+        let is_indirect_call = unsafe { llvm::LLVMIsAFunction(llfn).is_none() };
+        if !is_indirect_call {
+            return None;
+        }
+        let typeid = kcfi_typeid_for_fnabi(self.tcx, fn_abi);
+        let typeid = self.const_u32(llvm::kcfi_typeid(&typeid));
+        Some(llvm::OperandBundleDef::new("kcfi", &[typeid]))
+    }
+
     fn align_metadata(&mut self, load: &'ll Value, align: Align) {
         unsafe {
             let v = [self.cx.const_u64(align.bytes())];
@@ -1419,7 +1495,7 @@ pub fn va_arg(&mut self, list: &'ll Value, ty: &'ll Type) -> &'ll Value {
 
     pub(crate) fn call_intrinsic(&mut self, intrinsic: &str, args: &[&'ll Value]) -> &'ll Value {
         let (ty, f) = self.cx.get_intrinsic(intrinsic);
-        self.call(ty, f, args, None)
+        self.call(ty, None, None, f, args, None)
     }
 
     fn call_lifetime_intrinsic(&mut self, intrinsic: &str, ptr: &'ll Value, size: Size) {
@@ -1497,7 +1573,7 @@ fn fptoint_sat(
             };
             let f =
                 self.declare_cfn(&name, llvm::UnnamedAddr::No, self.type_func(&[src_ty], dest_ty));
-            Some(self.call(self.type_func(&[src_ty], dest_ty), f, &[val], None))
+            Some(self.call(self.type_func(&[src_ty], dest_ty), None, None, f, &[val], None))
         } else {
             None
         }
@@ -222,9 +222,13 @@ pub(crate) fn static_addr_of_mut(
         align: Align,
         kind: Option<&str>,
     ) -> &'ll Value {
+        // With `-Z pgo-value-profiling`, vtables keep their names even if other names are
+        // dropped, so that value profiles can be matched up with them.
+        let keep_name = !self.tcx.sess.fewer_names()
+            || (kind == Some("vtable") && self.tcx.sess.pgo_value_profiling());
         unsafe {
             let gv = match kind {
-                Some(kind) if !self.tcx.sess.fewer_names() => {
+                Some(kind) if keep_name => {
                     let name = self.generate_local_symbol_name(kind);
                     let gv = self.define_global(&name, self.val_ty(cv)).unwrap_or_else(|| {
                         bug!("symbol `{}` is already defined", name);
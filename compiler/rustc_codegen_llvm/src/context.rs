@@ -9,7 +9,7 @@
 use crate::value::Value;
 
 use cstr::cstr;
-use rustc_codegen_ssa::base::wants_msvc_seh;
+use rustc_codegen_ssa::base::{wants_msvc_seh, wants_new_eh_instructions, wants_wasm_eh};
 use rustc_codegen_ssa::traits::*;
 use rustc_data_structures::base_n;
 use rustc_data_structures::fx::FxHashMap;
@@ -251,6 +251,16 @@ pub unsafe fn create_module<'ll>(
         );
     }
 
+    if sess.is_sanitizer_kcfi_enabled() {
+        // The `kcfi_type` function metadata and the `kcfi` operand bundle are only lowered to
+        // checks by LLVM 16 and later.
+        if llvm_version < (16, 0, 0) {
+            sess.fatal("`-Zsanitizer=kcfi` requires LLVM 16 or higher");
+        }
+        let kcfi = "kcfi\0".as_ptr().cast();
+        llvm::LLVMRustAddModuleFlag(llmod, llvm::LLVMModFlagBehavior::Override, kcfi, 1);
+    }
+
     // Control Flow Guard is currently only supported by the MSVC linker on Windows.
     if sess.target.is_like_msvc {
         match sess.opts.cg.control_flow_guard {
@@ -277,9 +287,8 @@ pub unsafe fn create_module<'ll>(
     }
 
     if let Some(BranchProtection { bti, pac_ret }) = sess.opts.debugging_opts.branch_protection {
-        if sess.target.arch != "aarch64" {
-            sess.err("-Zbranch-protection is only supported on aarch64");
-        } else {
+        // Unsupported targets are rejected when validating the session options.
+        if sess.target.arch == "aarch64" {
             llvm::LLVMRustAddModuleFlag(
                 llmod,
                 llvm::LLVMModFlagBehavior::Error,
@@ -506,12 +515,16 @@ fn eh_personality(&self) -> &'ll Value {
         // `rust_eh_personality` function, but rather we wired it up to the
         // CRT's custom personality function, which forces LLVM to consider
         // landing pads as "landing pads for SEH".
+        //
+        // The same goes for wasm exception handling, which is selected through
+        // the `__gxx_wasm_personality_v0` personality function of the C++
+        // runtime.
         if let Some(llpersonality) = self.eh_personality.get() {
             return llpersonality;
         }
         let tcx = self.tcx;
         let llfn = match tcx.lang_items().eh_personality() {
-            Some(def_id) if !wants_msvc_seh(self.sess()) => self.get_fn_addr(
+            Some(def_id) if !wants_new_eh_instructions(self.sess()) => self.get_fn_addr(
                 ty::Instance::resolve(
                     tcx,
                     ty::ParamEnv::reveal_all(),
@@ -524,6 +537,8 @@ fn eh_personality(&self) -> &'ll Value {
             _ => {
                 let name = if wants_msvc_seh(self.sess()) {
                     "__CxxFrameHandler3"
+                } else if wants_wasm_eh(self.sess()) {
+                    "__gxx_wasm_personality_v0"
                 } else {
                     "rust_eh_personality"
                 };
@@ -563,7 +578,7 @@ fn compiler_used_statics(&self) -> &RefCell<Vec<&'ll Value>> {
     }
 
     fn set_frame_pointer_type(&self, llfn: &'ll Value) {
-        if let Some(attr) = attributes::frame_pointer_type_attr(self) {
+        if let Some(attr) = attributes::frame_pointer_type_attr(self, None) {
             attributes::apply_to_llfn(llfn, llvm::AttributePlace::Function, &[attr]);
         }
     }
@@ -647,6 +662,7 @@ macro_rules! mk_struct {
 
         let i8p = self.type_i8p();
         let void = self.type_void();
+        let t_token = self.type_token();
         let i1 = self.type_i1();
         let t_i8 = self.type_i8();
         let t_i16 = self.type_i16();
@@ -858,6 +874,8 @@ macro_rules! mk_struct {
 
         ifn!("llvm.expect.i1", fn(i1, i1) -> i1);
         ifn!("llvm.eh.typeid.for", fn(i8p) -> t_i32);
+        ifn!("llvm.wasm.get.exception", fn(t_token) -> i8p);
+        ifn!("llvm.wasm.get.ehselector", fn(t_token) -> t_i32);
         ifn!("llvm.localescape", fn(...) -> void);
         ifn!("llvm.localrecover", fn(i8p, i8p, t_i32) -> i8p);
         ifn!("llvm.x86.seh.recoverfp", fn(i8p, i8p) -> i8p);
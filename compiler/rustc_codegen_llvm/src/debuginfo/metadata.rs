@@ -34,10 +34,9 @@
 use rustc_middle::ty::layout::TyAndLayout;
 use rustc_middle::ty::subst::GenericArgKind;
 use rustc_middle::ty::{self, AdtKind, Instance, ParamEnv, Ty, TyCtxt};
-use rustc_session::config::{self, DebugInfo};
+use rustc_session::config::{self, DebugInfo, RemapPathScopeComponents};
 use rustc_span::symbol::Symbol;
 use rustc_span::FileName;
-use rustc_span::FileNameDisplayPreference;
 use rustc_span::{self, SourceFile};
 use rustc_target::abi::{Align, Size};
 use smallvec::smallvec;
@@ -547,19 +546,27 @@ fn alloc_new_file_metadata<'ll>(
                 let working_directory = &cx.sess().opts.working_dir;
                 debug!(?working_directory);
 
-                let filename = cx
-                    .sess()
-                    .source_map()
-                    .path_mapping()
-                    .to_embeddable_absolute_path(filename.clone(), working_directory);
-
-                // Construct the absolute path of the file
-                let abs_path = filename.remapped_path_if_available();
+                // Construct the absolute path of the file, which is remapped unless
+                // `-Zremap-path-scope` excludes debuginfo.
+                let prefer_remapped =
+                    cx.sess().should_prefer_remapped(RemapPathScopeComponents::DEBUGINFO);
+                let (working_directory, abs_path) = if prefer_remapped {
+                    let filename = cx
+                        .sess()
+                        .source_map()
+                        .path_mapping()
+                        .to_embeddable_absolute_path(filename.clone(), working_directory);
+                    (
+                        working_directory.remapped_path_if_available(),
+                        filename.remapped_path_if_available().to_path_buf(),
+                    )
+                } else {
+                    let working_directory = working_directory.local_path_if_available();
+                    (working_directory, working_directory.join(filename.local_path_if_available()))
+                };
                 debug!(?abs_path);
 
-                if let Ok(rel_path) =
-                    abs_path.strip_prefix(working_directory.remapped_path_if_available())
-                {
+                if let Ok(rel_path) = abs_path.strip_prefix(working_directory) {
                     // If the compiler's working directory (which also is the DW_AT_comp_dir of
                     // the compilation unit) is a prefix of the path we are about to emit, then
                     // only emit the part relative to the working directory.
@@ -575,10 +582,7 @@ fn alloc_new_file_metadata<'ll>(
                     // By moving the working directory portion into the `directory` part of the
                     // DIFile, we allow LLVM to emit just the relative path for DWARF, while
                     // still emitting the correct absolute path for CodeView.
-                    (
-                        working_directory.to_string_lossy(FileNameDisplayPreference::Remapped),
-                        rel_path.to_string_lossy().into_owned(),
-                    )
+                    (working_directory.to_string_lossy(), rel_path.to_string_lossy().into_owned())
                 } else {
                     ("".into(), abs_path.to_string_lossy().into_owned())
                 }
@@ -816,7 +820,8 @@ pub fn build_compile_unit_di_node<'ll, 'tcx>(
     let producer = format!("clang LLVM ({})", rustc_producer);
 
     let name_in_debuginfo = name_in_debuginfo.to_string_lossy();
-    let work_dir = tcx.sess.opts.working_dir.to_string_lossy(FileNameDisplayPreference::Remapped);
+    let work_dir_pref = tcx.sess.filename_display_preference(RemapPathScopeComponents::DEBUGINFO);
+    let work_dir = tcx.sess.opts.working_dir.to_string_lossy(work_dir_pref);
     let flags = "\0";
     let output_filenames = tcx.output_filenames(());
     let split_name = if tcx.sess.target_can_use_split_dwarf() {
@@ -827,7 +832,13 @@ pub fn build_compile_unit_di_node<'ll, 'tcx>(
                 Some(codegen_unit_name),
             )
             // We get a path relative to the working directory from split_dwarf_path
-            .map(|f| tcx.sess.source_map().path_mapping().map_prefix(f).0)
+            .map(|f| {
+                if tcx.sess.should_prefer_remapped(RemapPathScopeComponents::DEBUGINFO) {
+                    tcx.sess.source_map().path_mapping().map_prefix(f).0
+                } else {
+                    f
+                }
+            })
     } else {
         None
     }
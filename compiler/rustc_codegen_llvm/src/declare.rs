@@ -18,6 +18,7 @@
 use crate::llvm::AttributePlace::Function;
 use crate::type_::Type;
 use crate::value::Value;
+use libc::c_uint;
 use rustc_middle::ty::Ty;
 use smallvec::SmallVec;
 use tracing::debug;
@@ -29,7 +30,7 @@
 fn declare_raw_fn<'ll>(
     cx: &CodegenCx<'ll, '_>,
     name: &str,
-    callconv: llvm::CallConv,
+    callconv: c_uint,
     unnamed: llvm::UnnamedAddr,
     ty: &'ll Type,
 ) -> &'ll Value {
@@ -77,7 +78,7 @@ pub fn declare_cfn(
         unnamed: llvm::UnnamedAddr,
         fn_type: &'ll Type,
     ) -> &'ll Value {
-        declare_raw_fn(self, name, llvm::CCallConv, unnamed, fn_type)
+        declare_raw_fn(self, name, llvm::CCallConv as c_uint, unnamed, fn_type)
     }
 
     /// Declare a Rust function.
@@ -132,7 +133,11 @@ pub fn get_declared_value(&self, name: &str) -> Option<&'ll Value> {
     pub fn get_defined_value(&self, name: &str) -> Option<&'ll Value> {
         self.get_declared_value(name).and_then(|val| {
             let declaration = unsafe { llvm::LLVMIsDeclaration(val) != 0 };
-            if !declaration { Some(val) } else { None }
+            if !declaration {
+                Some(val)
+            } else {
+                None
+            }
         })
     }
 }
@@ -50,6 +50,7 @@ mod back {
 mod abi;
 mod allocator;
 mod asm;
+mod atomic_shim;
 mod attributes;
 mod base;
 mod builder;
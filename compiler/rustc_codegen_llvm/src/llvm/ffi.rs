@@ -795,9 +795,6 @@ pub(crate) fn code_region(
             }
         }
 
-        // This function might be used in the future; the LLVM API is still evolving, as is coverage
-        // support.
-        #[allow(dead_code)]
         pub(crate) fn branch_region(
             counter: coverage_map::Counter,
             false_counter: coverage_map::Counter,
@@ -1058,6 +1055,7 @@ pub fn LLVMStructTypeInContext<'a>(
 
     // Operations on other types
     pub fn LLVMVoidTypeInContext(C: &Context) -> &Type;
+    pub fn LLVMTokenTypeInContext(C: &Context) -> &Type;
     pub fn LLVMRustMetadataTypeInContext(C: &Context) -> &Type;
 
     // Operations on all values
@@ -1190,6 +1188,7 @@ pub fn LLVMCreateStringAttribute(
     pub fn LLVMRustCreateUWTableAttr(C: &Context, async_: bool) -> &Attribute;
 
     // Operations on functions
+    pub fn LLVMIsAFunction(Val: &Value) -> Option<&Value>;
     pub fn LLVMRustGetOrInsertFunction<'a>(
         M: &'a Module,
         Name: *const c_char,
@@ -1274,7 +1273,8 @@ pub fn LLVMRustBuildInvoke<'a>(
         NumArgs: c_uint,
         Then: &'a BasicBlock,
         Catch: &'a BasicBlock,
-        Bundle: Option<&OperandBundleDef<'a>>,
+        OpBundles: *const &OperandBundleDef<'a>,
+        NumOpBundles: c_uint,
         Name: *const c_char,
     ) -> &'a Value;
     pub fn LLVMBuildLandingPad<'a>(
@@ -1644,7 +1644,8 @@ pub fn LLVMRustBuildCall<'a>(
         Fn: &'a Value,
         Args: *const &'a Value,
         NumArgs: c_uint,
-        Bundle: Option<&OperandBundleDef<'a>>,
+        OpBundles: *const &OperandBundleDef<'a>,
+        NumOpBundles: c_uint,
     ) -> &'a Value;
     pub fn LLVMRustBuildMemCpy<'a>(
         B: &Builder<'a>,
@@ -2451,6 +2452,7 @@ pub fn LLVMRustBuildOperandBundleDef<'a>(
         NumInputs: c_uint,
     ) -> &'a mut OperandBundleDef<'a>;
     pub fn LLVMRustFreeOperandBundleDef<'a>(Bundle: &'a mut OperandBundleDef<'a>);
+    pub fn LLVMRustKCFITypeId(TypeId: *const c_char, TypeIdLen: size_t) -> u32;
 
     pub fn LLVMRustPositionBuilderAtStart<'a>(B: &Builder<'a>, BB: &'a BasicBlock);
 
@@ -134,14 +134,14 @@ fn from_str(s: &str) -> Result<Self, Self::Err> {
     }
 }
 
-pub fn SetInstructionCallConv(instr: &Value, cc: CallConv) {
+pub fn SetInstructionCallConv(instr: &Value, cc: c_uint) {
     unsafe {
-        LLVMSetInstructionCallConv(instr, cc as c_uint);
+        LLVMSetInstructionCallConv(instr, cc);
     }
 }
-pub fn SetFunctionCallConv(fn_: &Value, cc: CallConv) {
+pub fn SetFunctionCallConv(fn_: &Value, cc: c_uint) {
     unsafe {
-        LLVMSetFunctionCallConv(fn_, cc as c_uint);
+        LLVMSetFunctionCallConv(fn_, cc);
     }
 }
 
@@ -307,6 +307,12 @@ pub fn new(name: &str, vals: &[&'a Value]) -> Self {
     }
 }
 
+/// Returns the 32-bit hash of `typeid` that KCFI stores in front of functions and compares at
+/// indirect call sites.
+pub fn kcfi_typeid(typeid: &str) -> u32 {
+    unsafe { LLVMRustKCFITypeId(typeid.as_ptr().cast(), typeid.len()) }
+}
+
 impl Drop for OperandBundleDef<'_> {
     fn drop(&mut self) {
         unsafe {
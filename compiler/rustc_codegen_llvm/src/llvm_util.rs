@@ -2,6 +2,7 @@
 use crate::{llvm, llvm_util};
 use libc::c_int;
 use libloading::Library;
+use rustc_codegen_ssa::base::wants_wasm_eh;
 use rustc_codegen_ssa::target_features::{
     supported_target_features, tied_target_features, RUSTC_SPECIFIC_FEATURES,
 };
@@ -110,7 +111,11 @@ fn llvm_arg_to_arg_name(full_arg: &str) -> &str {
         }
 
         if sess.target.os == "emscripten" && sess.panic_strategy() == PanicStrategy::Unwind {
-            add("-enable-emscripten-cxx-exceptions", false);
+            if wants_wasm_eh(sess) {
+                add("-wasm-enable-eh", false);
+            } else {
+                add("-enable-emscripten-cxx-exceptions", false);
+            }
         }
 
         // HACK(eddyb) LLVM inserts `llvm.assume` calls to preserve align attributes
@@ -120,6 +125,17 @@ fn llvm_arg_to_arg_name(full_arg: &str) -> &str {
         // Use non-zero `import-instr-limit` multiplier for cold callsites.
         add("-import-cold-multiplier=0.1", false);
 
+        // Value profiling (and the indirect call promotion that uses its results) is on by
+        // default in LLVM's PGO pipelines, so it only needs to be turned off explicitly.
+        if sess.opts.debugging_opts.pgo_value_profiling == Some(false) {
+            if sess.opts.cg.profile_generate.enabled() {
+                add("-disable-vp", false);
+            }
+            if sess.opts.cg.profile_use.is_some() {
+                add("-disable-icp", false);
+            }
+        }
+
         for arg in sess_args {
             add(&(*arg), true);
         }
@@ -440,6 +456,11 @@ pub(crate) fn global_llvm_features(sess: &Session, diagnostics: bool) -> Vec<Str
             .map(String::from),
     );
 
+    // The exception handling instructions are needed for `-Zwasm-exception-handling`.
+    if wants_wasm_eh(sess) {
+        features.push("+exception-handling".to_string());
+    }
+
     // -Ctarget-features
     let supported_features = supported_target_features(sess);
     let mut featsmap = FxHashMap::default();
@@ -54,6 +54,15 @@ fn add_archive<F>(&mut self, archive: &Path, skip: F) -> io::Result<()>
 
     fn build(self);
 
+    /// Writes an import library for the functions `dll_imports` of the DLL `lib_name` into
+    /// `tmpdir` and returns its path.
+    fn create_dll_import_lib(
+        sess: &Session,
+        lib_name: &str,
+        dll_imports: &[DllImport],
+        tmpdir: &Path,
+    ) -> PathBuf;
+
     fn inject_dll_import_lib(
         &mut self,
         lib_name: &str,
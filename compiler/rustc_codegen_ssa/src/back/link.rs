@@ -1,9 +1,11 @@
 use rustc_arena::TypedArena;
 use rustc_ast::CRATE_NODE_ID;
+use rustc_data_structures::fingerprint::Fingerprint;
 use rustc_data_structures::fx::{FxHashSet, FxIndexMap};
 use rustc_data_structures::memmap::Mmap;
+use rustc_data_structures::stable_hasher::StableHasher;
 use rustc_data_structures::temp_dir::MaybeTempDir;
-use rustc_errors::{ErrorGuaranteed, Handler};
+use rustc_errors::{Diagnostic, ErrorGuaranteed, Handler};
 use rustc_fs_util::fix_windows_verbatim_for_gcc;
 use rustc_hir::def_id::CrateNum;
 use rustc_middle::middle::dependency_format::Linkage;
@@ -28,6 +30,7 @@
 use super::linker::{self, Linker};
 use super::metadata::{create_rmeta_file, MetadataPosition};
 use super::rpath::{self, RPathConfig};
+use crate::base::wants_wasm_eh;
 use crate::{
     looks_like_rust_object_file, CodegenResults, CompiledModule, CrateInfo, NativeLib,
     METADATA_FILENAME,
@@ -41,6 +44,7 @@
 use std::collections::BTreeSet;
 use std::ffi::OsString;
 use std::fs::{File, OpenOptions};
+use std::hash::Hash;
 use std::io::{BufWriter, Write};
 use std::lazy::OnceCell;
 use std::ops::Deref;
@@ -122,7 +126,7 @@ pub fn link_binary<'a, B: ArchiveBuilder<'a>>(
                         &out_filename,
                         codegen_results,
                         path.as_ref(),
-                    );
+                    )?;
                 }
             }
             if sess.opts.json_artifact_notifications {
@@ -193,21 +197,42 @@ pub fn link_binary<'a, B: ArchiveBuilder<'a>>(
 }
 
 pub fn each_linked_rlib(
+    sess: &Session,
     info: &CrateInfo,
     f: &mut dyn FnMut(CrateNum, &Path),
 ) -> Result<(), String> {
     let crates = info.used_crates.iter();
     let mut fmts = None;
-    for (ty, list) in info.dependency_formats.iter() {
-        match ty {
-            CrateType::Executable
-            | CrateType::Staticlib
-            | CrateType::Cdylib
-            | CrateType::ProcMacro => {
-                fmts = Some(list);
-                break;
+
+    let lto_active = matches!(sess.lto(), config::Lto::Fat | config::Lto::Thin);
+    if lto_active {
+        // All crate types are produced from the same LTO'd module, so they
+        // have to agree on which upstream crates get linked in statically.
+        if let Some((ty1, list1)) = info.dependency_formats.first() {
+            for (ty2, list2) in info.dependency_formats.iter().skip(1) {
+                if list1 != list2 {
+                    return Err(format!(
+                        "crate types `{:?}` and `{:?}` have incompatible dependency formats, \
+                         which is not supported with LTO",
+                        ty1, ty2
+                    ));
+                }
+            }
+            fmts = Some(list1);
+        }
+    } else {
+        for (ty, list) in info.dependency_formats.iter() {
+            match ty {
+                CrateType::Executable
+                | CrateType::Staticlib
+                | CrateType::Cdylib
+                | CrateType::ProcMacro
+                | CrateType::Dylib => {
+                    fmts = Some(list);
+                    break;
+                }
+                _ => {}
             }
-            _ => {}
         }
     }
     let Some(fmts) = fmts else {
@@ -215,7 +240,9 @@ pub fn each_linked_rlib(
     };
     for &cnum in crates {
         match fmts.get(cnum.as_usize() - 1) {
-            Some(&Linkage::NotLinked | &Linkage::IncludedFromDylib) => continue,
+            // Dynamically linked crates are not part of this artifact, so
+            // there is nothing to include from them.
+            Some(&Linkage::NotLinked | &Linkage::Dynamic | &Linkage::IncludedFromDylib) => continue,
             Some(_) => {}
             None => return Err("could not find formats for rlibs".to_string()),
         }
@@ -423,8 +450,16 @@ fn collate_raw_dylibs(
             let imports = dylib_table.entry(name.clone()).or_default();
             for import in &lib.dll_imports {
                 if let Some(old_import) = imports.insert(import.name, import) {
-                    // FIXME: when we add support for ordinals, figure out if we need to do anything
-                    // if we have two DllImport values with the same name but different ordinals.
+                    if import.ordinal != old_import.ordinal {
+                        sess.span_err(
+                            import.span,
+                            &format!(
+                                "multiple declarations of external function `{}` from \
+                                 library `{}` have different ordinals",
+                                import.name, name,
+                            ),
+                        );
+                    }
                     if import.calling_convention != old_import.calling_convention {
                         sess.span_err(
                             import.span,
@@ -469,7 +504,7 @@ fn link_staticlib<'a, B: ArchiveBuilder<'a>>(
         link_rlib::<B>(sess, codegen_results, RlibFlavor::StaticlibBase, out_filename, tempdir)?;
     let mut all_native_libs = vec![];
 
-    let res = each_linked_rlib(&codegen_results.crate_info, &mut |cnum, path| {
+    let res = each_linked_rlib(sess, &codegen_results.crate_info, &mut |cnum, path| {
         let name = codegen_results.crate_info.crate_name[&cnum];
         let native_libs = &codegen_results.crate_info.native_libraries[&cnum];
 
@@ -530,6 +565,15 @@ fn link_staticlib<'a, B: ArchiveBuilder<'a>>(
 
     ab.build();
 
+    // Staticlibs never go through the linker, so their DWARF objects have to be packaged here.
+    if sess.split_debuginfo() == SplitDebuginfo::Packed
+        && sess.opts.debuginfo != DebugInfo::None
+        && !sess.target.is_like_osx
+        && !sess.target.is_like_windows
+    {
+        link_dwarf_object(sess, codegen_results, out_filename, CrateType::Staticlib);
+    }
+
     if !all_native_libs.is_empty() {
         if sess.opts.prints.contains(&PrintRequest::NativeStaticLibs) {
             print_native_static_libs(sess, &all_native_libs);
@@ -549,13 +593,18 @@ fn escape_stdout_stderr_string(s: &[u8]) -> String {
 
 /// Use `thorin` (rust implementation of a dwarf packaging utility) to link DWARF objects into a
 /// DWARF package.
+///
+/// The package is written next to `out_filename` with `.dwp` appended (e.g. `libfoo.so.dwp`), which
+/// is where debuggers look for it.
 fn link_dwarf_object<'a>(
     sess: &'a Session,
     cg_results: &CodegenResults,
-    executable_out_filename: &Path,
+    out_filename: &Path,
+    crate_type: CrateType,
 ) {
-    let dwp_out_filename = executable_out_filename.with_extension("dwp");
-    debug!(?dwp_out_filename, ?executable_out_filename);
+    let mut dwp_out_filename = out_filename.to_path_buf().into_os_string();
+    dwp_out_filename.push(".dwp");
+    debug!(?dwp_out_filename, ?out_filename);
 
     #[derive(Default)]
     struct ThorinSession<Relocations> {
@@ -624,11 +673,11 @@ fn read_input<'arena>(&'arena self, path: &Path) -> std::io::Result<&'arena [u8]
         // found, but are provided explicitly above.
         //
         // Adding an executable is primarily done to make `thorin` check that all the referenced
-        // dwarf objects are found in the end.
-        package.add_executable(
-            &executable_out_filename,
-            thorin::MissingReferencedObjectBehaviour::Skip,
-        )?;
+        // dwarf objects are found in the end. Staticlibs are archives rather than linked objects,
+        // so there is nothing to check them against.
+        if crate_type != CrateType::Staticlib {
+            package.add_executable(out_filename, thorin::MissingReferencedObjectBehaviour::Skip)?;
+        }
 
         let output = package.finish()?.write()?;
         let mut output_stream = BufWriter::new(
@@ -663,7 +712,7 @@ fn link_natively<'a, B: ArchiveBuilder<'a>>(
     out_filename: &Path,
     codegen_results: &CodegenResults,
     tmpdir: &Path,
-) {
+) -> Result<(), ErrorGuaranteed> {
     info!("preparing {:?} to {:?}", crate_type, out_filename);
     let (linker_path, flavor) = linker_and_flavor(sess);
     let mut cmd = linker_with_args::<B>(
@@ -674,10 +723,17 @@ fn link_natively<'a, B: ArchiveBuilder<'a>>(
         tmpdir,
         out_filename,
         codegen_results,
-    );
+    )?;
 
     linker::disable_localization(&mut cmd);
 
+    let mut ld_candidates = select_ld_candidates(sess, flavor, &cmd).into_iter();
+    let mut ld = ld_candidates.next();
+    if let Some(ld) = ld {
+        cmd.arg(format!("-fuse-ld={}", ld.name));
+    }
+    let mut ld_failures = Vec::new();
+
     for &(ref k, ref v) in sess.target.link_env.as_ref() {
         cmd.env(k.as_ref(), v.as_ref());
     }
@@ -692,6 +748,28 @@ fn link_natively<'a, B: ArchiveBuilder<'a>>(
     // May have not found libraries in the right formats.
     sess.abort_if_errors();
 
+    // With incremental compilation, don't invoke the linker again if it would read the same
+    // inputs as when it produced the current output.
+    let link_fingerprint = link_fingerprint_path(sess, flavor, out_filename).and_then(|path| {
+        let outputs = link_outputs(sess, &cmd, out_filename, codegen_results);
+        let inputs = sess.time("link_fingerprint_inputs", || {
+            linker_inputs_fingerprint(sess, &cmd, tmpdir, &outputs, codegen_results)
+        })?;
+        Some((path, outputs, inputs))
+    });
+    if let Some((path, outputs, inputs)) = &link_fingerprint {
+        let previous = fs::read_to_string(path).ok();
+        if previous.is_some()
+            && previous == link_outputs_fingerprint(outputs).map(|o| link_record(*inputs, o))
+        {
+            let _timer = sess.timer("link_reused");
+            return Ok(());
+        }
+        // The record may be hard linked from the previous session directory, so remove it
+        // instead of overwriting it later.
+        let _ = fs::remove_file(path);
+    }
+
     // Invoke the system linker
     info!("{:?}", &cmd);
     let retry_on_segfault = env::var("RUSTC_RETRY_LINKER_ON_SEGFAULT").is_ok();
@@ -795,6 +873,37 @@ fn link_natively<'a, B: ArchiveBuilder<'a>>(
             continue;
         }
 
+        // With `-Z select-ld`, try again with the next best implementation of `ld`, unless the
+        // link failed for a reason that the next one would run into as well.
+        let next_ld = if ld.is_some() && is_ld_specific_failure(&output.status, &out) {
+            ld_candidates.next()
+        } else {
+            None
+        };
+        if let (Some(failed), Some(next)) = (ld, next_ld) {
+            info!("linker output: {:?}", out);
+            warn!(
+                "Linking with -fuse-ld={} failed. Retrying with -fuse-ld={}.",
+                failed.name, next.name
+            );
+            let failed_arg = format!("-fuse-ld={}", failed.name);
+            for arg in cmd.take_args() {
+                if arg.to_string_lossy() == failed_arg {
+                    cmd.arg(format!("-fuse-ld={}", next.name));
+                } else {
+                    cmd.arg(arg);
+                }
+            }
+            ld_failures.push(LdFailure {
+                name: failed.name,
+                status: output.status,
+                output: out.into_owned(),
+            });
+            ld = Some(next);
+            info!("{:?}", &cmd);
+            continue;
+        }
+
         // Here's a terribly awful hack that really shouldn't be present in any
         // compiler. Here an environment variable is supported to automatically
         // retry the linker invocation if the linker looks like it segfaulted.
@@ -857,6 +966,7 @@ fn is_illegal_instruction(_status: &ExitStatus) -> bool {
                     prog.status
                 ));
                 err.note(&format!("{:?}", &cmd)).note(&escaped_output);
+                note_ld_failures(&mut err, &ld_failures);
                 if escaped_output.contains("undefined reference to") {
                     err.help(
                         "some `extern` functions couldn't be found; some native libraries may \
@@ -918,6 +1028,14 @@ fn is_illegal_instruction(_status: &ExitStatus) -> bool {
 
                 sess.abort_if_errors();
             }
+            if let (Some(ld), Some(first)) = (ld, ld_failures.first()) {
+                let mut warn = sess.struct_warn(&format!(
+                    "linking with `-fuse-ld={}` failed, linked with `-fuse-ld={}` instead",
+                    first.name, ld.name
+                ));
+                note_ld_failures(&mut warn, &ld_failures);
+                warn.emit();
+            }
             info!("linker stderr:\n{}", escape_stdout_stderr_string(&prog.stderr));
             info!("linker stdout:\n{}", escape_stdout_stderr_string(&prog.stdout));
         }
@@ -997,7 +1115,9 @@ fn is_illegal_instruction(_status: &ExitStatus) -> bool {
         // We cannot rely on the .o paths in the executable because they may have been
         // remapped by --remap-path-prefix and therefore invalid, so we need to provide
         // the .o/.dwo paths explicitly.
-        SplitDebuginfo::Packed => link_dwarf_object(sess, codegen_results, out_filename),
+        SplitDebuginfo::Packed => {
+            link_dwarf_object(sess, codegen_results, out_filename, crate_type)
+        }
     }
 
     let strip = strip_value(sess);
@@ -1009,6 +1129,457 @@ fn is_illegal_instruction(_status: &ExitStatus) -> bool {
             Strip::None => {}
         }
     }
+
+    if let Some((path, outputs, inputs)) = link_fingerprint {
+        // This is only a cache, so failing to record the link is not an error.
+        if let Some(output) = link_outputs_fingerprint(&outputs) {
+            let _ = fs::write(path, link_record(inputs, output));
+        }
+    }
+
+    Ok(())
+}
+
+/// An implementation of `ld` that `cc` can be told to use with `-fuse-ld`.
+#[derive(Clone, Copy, Debug)]
+struct LdCandidate {
+    /// The value of `-fuse-ld`.
+    name: &'static str,
+    /// The program that `cc` runs for this implementation.
+    program: &'static str,
+    /// The architectures this implementation can link for, or `None` if it supports all of them.
+    archs: Option<&'static [&'static str]>,
+}
+
+/// The implementations of `ld` that `-Z select-ld` chooses from, best first.
+const LD_CANDIDATES: &[LdCandidate] = &[
+    LdCandidate { name: "lld", program: "ld.lld", archs: None },
+    LdCandidate {
+        name: "mold",
+        program: "ld.mold",
+        archs: Some(&["aarch64", "arm", "riscv64", "x86", "x86_64"]),
+    },
+    LdCandidate {
+        name: "gold",
+        program: "ld.gold",
+        archs: Some(&[
+            "aarch64",
+            "arm",
+            "powerpc",
+            "powerpc64",
+            "s390x",
+            "sparc",
+            "sparc64",
+            "x86",
+            "x86_64",
+        ]),
+    },
+    LdCandidate { name: "bfd", program: "ld.bfd", archs: None },
+];
+
+/// An implementation of `ld` that failed to link, before retrying with the next one.
+struct LdFailure {
+    name: &'static str,
+    status: ExitStatus,
+    output: String,
+}
+
+/// Returns the implementations of `ld` that are installed and support the target, best first,
+/// if `-Z select-ld` is enabled and applies to this link.
+fn select_ld_candidates(sess: &Session, flavor: LinkerFlavor, cmd: &Command) -> Vec<LdCandidate> {
+    // `-fuse-ld` is only understood by `cc` when linking ELF objects, and an explicit choice of
+    // `ld` always takes precedence.
+    if !sess.opts.debugging_opts.select_ld
+        || flavor != LinkerFlavor::Gcc
+        || sess.target.is_like_osx
+        || sess.target.is_like_windows
+        || sess.target.is_like_wasm
+        || sess.opts.debugging_opts.gcc_ld.is_some()
+        || cmd.get_args().iter().any(|arg| arg.to_string_lossy().starts_with("-fuse-ld="))
+    {
+        return Vec::new();
+    }
+    let path = env::var_os("PATH").unwrap_or_default();
+    LD_CANDIDATES
+        .iter()
+        .filter(|ld| ld.archs.map_or(true, |archs| archs.contains(&&*sess.target.arch)))
+        .filter(|ld| env::split_paths(&path).any(|dir| dir.join(ld.program).is_file()))
+        .copied()
+        .collect()
+}
+
+/// Returns whether a failed link is likely to succeed with another implementation of `ld`: the
+/// linker crashed, or did not understand an option it was passed. Other failures, like undefined
+/// symbols, come from the inputs of the link and would only be reported again, less clearly.
+fn is_ld_specific_failure(status: &ExitStatus, output: &str) -> bool {
+    if is_terminated_by_signal(status) {
+        return true;
+    }
+    if output.contains("undefined reference") || output.contains("undefined symbol") {
+        return false;
+    }
+    let ld_specific = Regex::new(
+        "terminated with signal|linker command failed due to signal|unable to execute command         |PLEASE submit a bug report|Stack dump:|internal error         |(unknown|unrecognized|unsupported) (command[- ]line )?(option|argument)         |invalid linker name in argument",
+    )
+    .unwrap();
+    ld_specific.is_match(output)
+}
+
+#[cfg(unix)]
+fn is_terminated_by_signal(status: &ExitStatus) -> bool {
+    use std::os::unix::prelude::*;
+    status.signal().is_some()
+}
+
+#[cfg(not(unix))]
+fn is_terminated_by_signal(_status: &ExitStatus) -> bool {
+    false
+}
+
+/// Notes why each implementation of `ld` that was tried before the last one failed.
+fn note_ld_failures(diag: &mut Diagnostic, failures: &[LdFailure]) {
+    for failure in failures {
+        let mut lines = failure.output.lines().map(str::trim).filter(|line| !line.is_empty());
+        let reason = lines.clone().find(|line| line.contains("error")).or_else(|| lines.next());
+        diag.note(&format!(
+            "linking with `-fuse-ld={}` failed with {}: {}",
+            failure.name,
+            failure.status,
+            reason.unwrap_or("no output")
+        ));
+    }
+}
+
+/// Returns the file in the incremental session directory recording the inputs of the last link
+/// of `out_filename`, if the link can be skipped when they did not change.
+fn link_fingerprint_path(
+    sess: &Session,
+    flavor: LinkerFlavor,
+    out_filename: &Path,
+) -> Option<PathBuf> {
+    // Only the C compiler driver can be asked where the libraries named on the command line are
+    // found. Packed split debuginfo is written next to the output by separate tools, which are not
+    // tracked either.
+    if sess.opts.incremental.is_none()
+        || flavor != LinkerFlavor::Gcc
+        || sess.split_debuginfo() == SplitDebuginfo::Packed
+    {
+        return None;
+    }
+    let file_name = out_filename.file_name()?.to_str()?;
+    Some(rustc_incremental::in_incr_comp_dir_sess(sess, &format!("{}.link-fingerprint", file_name)))
+}
+
+/// Splits an argument of the C compiler driver into the arguments it passes on to the linker.
+fn linker_arg_components(arg: &str) -> Vec<&str> {
+    match arg.strip_prefix("-Wl,") {
+        Some(args) => args.split(',').collect(),
+        None => vec![arg],
+    }
+}
+
+/// Returns the files a link produces, all of which must still exist for it to be skipped: the
+/// output itself, the import library of a Windows DLL and, with unpacked split debuginfo, the
+/// DWARF objects the output refers to.
+fn link_outputs(
+    sess: &Session,
+    cmd: &Command,
+    out_filename: &Path,
+    codegen_results: &CodegenResults,
+) -> Vec<PathBuf> {
+    let mut outputs = vec![out_filename.to_path_buf()];
+    for arg in cmd.get_args() {
+        let arg = arg.to_string_lossy();
+        for component in linker_arg_components(&arg) {
+            if let Some(implib) = component.strip_prefix("--out-implib=") {
+                outputs.push(PathBuf::from(implib));
+            }
+        }
+    }
+    if sess.split_debuginfo() == SplitDebuginfo::Unpacked {
+        outputs.extend(codegen_results.modules.iter().filter_map(|m| m.dwarf_object.clone()));
+    }
+    outputs
+}
+
+/// Environment variables read by the C compiler driver or the linker it runs, which are inherited
+/// by the linker command unless it sets them.
+const LINKER_ENV_VARS: &[&str] =
+    &["PATH", "COMPILER_PATH", "LIBRARY_PATH", "GCC_EXEC_PREFIX", "LD_LIBRARY_PATH", "LD_RUN_PATH"];
+
+/// Fingerprints everything the linker reads: the linker itself, its arguments and environment,
+/// the files named by its arguments, including after the `=` of options like `--version-script=`,
+/// and the libraries named by `-l`.
+///
+/// The C compiler driver adds inputs of its own, such as its startup objects, its support
+/// libraries and the directories it searches, and runs a linker it finds in `PATH`. So the
+/// command line of the linker is taken from what the driver reports with `-###`, and the linker
+/// program is looked up the way the driver does.
+///
+/// The object files produced by this session and the files in the temporary directory are
+/// written anew each time, so they are hashed by contents, and the name of the temporary
+/// directory, which changes between sessions, is left out. Other files are hashed by size and
+/// modification time. The outputs of the link are not inputs, even when it overwrites them.
+///
+/// A library named by `-l` is hashed together with the path it is found at, so that a library of
+/// the same name added earlier in the search order is noticed, and if it is a linker script, such
+/// as `libc.so` on glibc systems, with the files it names. Returns `None` if an input can't be
+/// tracked this way, such as a library found in none of the directories given by `-L` or a macOS
+/// framework.
+fn linker_inputs_fingerprint(
+    sess: &Session,
+    cmd: &Command,
+    tmpdir: &Path,
+    outputs: &[PathBuf],
+    codegen_results: &CodegenResults,
+) -> Option<Fingerprint> {
+    let generated: FxHashSet<&Path> = codegen_results
+        .modules
+        .iter()
+        .chain(&codegen_results.allocator_module)
+        .chain(&codegen_results.metadata_module)
+        .filter_map(|module| module.object.as_deref())
+        .collect();
+    let tmpdir_name = tmpdir.to_string_lossy();
+
+    let mut hasher = StableHasher::new();
+    let command = cmd.command();
+    command.get_program().hash(&mut hasher);
+    let env_var = |name: &str| match command.get_envs().find(|&(key, _)| key == name) {
+        Some((_, value)) => value.map(|value| value.to_owned()),
+        None => env::var_os(name),
+    };
+    for name in LINKER_ENV_VARS {
+        name.hash(&mut hasher);
+        env_var(name).hash(&mut hasher);
+    }
+    for (key, value) in command.get_envs() {
+        key.hash(&mut hasher);
+        value.hash(&mut hasher);
+    }
+
+    let hash_path = |hasher: &mut StableHasher, path: &Path| {
+        if outputs.iter().any(|output| output == path) {
+            return;
+        }
+        let by_contents = path.starts_with(tmpdir) || generated.contains(path);
+        if let Ok(metadata) = fs::metadata(path) {
+            if metadata.is_file() {
+                hash_linker_input(hasher, path, &metadata, by_contents);
+            }
+        }
+    };
+
+    let link_line = driver_link_line(cmd)?;
+    let (linker, args) = link_line.split_first()?;
+    hash_path(&mut hasher, Path::new(linker));
+    // GCC runs `collect2`, which runs the linker selected by `-fuse-ld=` that it finds first in
+    // the directories given by `-B`, `COMPILER_PATH` or `PATH`.
+    let mut linker_names = vec!["ld".to_string()];
+    linker_names.extend(
+        args.iter().filter_map(|arg| Some(format!("ld.{}", arg.strip_prefix("-fuse-ld=")?))),
+    );
+    let mut program_dirs: Vec<PathBuf> = command
+        .get_args()
+        .filter_map(|arg| Some(PathBuf::from(arg.to_str()?.strip_prefix("-B")?)))
+        .collect();
+    for name in ["COMPILER_PATH", "PATH"] {
+        if let Some(paths) = env_var(name) {
+            program_dirs.extend(env::split_paths(&paths));
+        }
+    }
+    for name in &linker_names {
+        let path = program_dirs.iter().map(|dir| dir.join(name)).find(|path| path.is_file());
+        path.hash(&mut hasher);
+        if let Some(path) = path {
+            hash_path(&mut hasher, &path);
+        }
+    }
+
+    for arg in args {
+        arg.replace(&*tmpdir_name, "").hash(&mut hasher);
+    }
+
+    let mut search_dirs = Vec::new();
+    let mut libs = Vec::new();
+    let mut components = args.iter().flat_map(|arg| linker_arg_components(arg));
+    while let Some(component) = components.next() {
+        let (option, value) = match component {
+            "-L" | "-l" | "-T" | "-o" | "-framework" => (component, components.next()?),
+            _ if component.len() > 2 && matches!(component.get(..2), Some("-L" | "-l" | "-T")) => {
+                component.split_at(2)
+            }
+            _ => ("", component),
+        };
+        match option {
+            "-L" => search_dirs.push(PathBuf::from(value)),
+            "-l" => libs.push(value.to_string()),
+            "-o" => {}
+            // Frameworks are found in directories the C compiler driver doesn't report.
+            "-framework" => return None,
+            _ => {
+                // Arguments starting with `@` name a file containing more arguments.
+                let value = value.strip_prefix('@').unwrap_or(value);
+                hash_path(&mut hasher, Path::new(value));
+                if let Some((_, path)) = value.split_once('=') {
+                    hash_path(&mut hasher, Path::new(path));
+                }
+            }
+        }
+    }
+
+    // Libraries named by linker scripts are looked up like those on the command line.
+    let mut seen = FxHashSet::default();
+    while let Some(lib) = libs.pop() {
+        if !seen.insert(lib.clone()) {
+            continue;
+        }
+        let path = find_linker_library(&sess.target, &search_dirs, &lib)?;
+        lib.hash(&mut hasher);
+        path.hash(&mut hasher);
+        hash_path(&mut hasher, &path);
+        for input in linker_script_inputs(&path) {
+            match input.strip_prefix("-l") {
+                Some(lib) => libs.push(lib.to_string()),
+                None if Path::new(&input).is_absolute() => {
+                    input.hash(&mut hasher);
+                    hash_path(&mut hasher, Path::new(&input));
+                }
+                None => libs.push(format!(":{}", input)),
+            }
+        }
+    }
+    Some(hasher.finish())
+}
+
+/// Returns the command line of the linker the C compiler driver runs when linking with the
+/// arguments of `cmd`, as it reports with `-###`, or `None` if it can't tell.
+fn driver_link_line(cmd: &Command) -> Option<Vec<String>> {
+    let mut command = cmd.command();
+    command.arg("-###");
+    let output = command.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    // Each command the driver would run is printed on a line of its own with all arguments
+    // quoted, the linker last.
+    let stderr = String::from_utf8(output.stderr).ok()?;
+    let line = stderr.lines().rev().find(|line| line.starts_with(" \""))?;
+    let mut args = Vec::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' => {}
+            '"' => {
+                let mut arg = String::new();
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => arg.push(chars.next()?),
+                        c => arg.push(c),
+                    }
+                }
+                args.push(arg);
+            }
+            _ => return None,
+        }
+    }
+    Some(args)
+}
+
+/// Returns the file the linker uses for `-l{lib}`, the first in `search_dirs` with one of the file
+/// names of the library.
+fn find_linker_library(target: &Target, search_dirs: &[PathBuf], lib: &str) -> Option<PathBuf> {
+    let file_names = match lib.strip_prefix(':') {
+        Some(verbatim) => vec![verbatim.to_string()],
+        None => {
+            let mut file_names = vec![
+                format!("{}{}{}", target.dll_prefix, lib, target.dll_suffix),
+                format!("{}{}{}", target.staticlib_prefix, lib, target.staticlib_suffix),
+            ];
+            if target.is_like_windows {
+                file_names.push(format!("lib{}.dll.a", lib));
+            } else if target.is_like_osx {
+                file_names.push(format!("lib{}.tbd", lib));
+            }
+            file_names
+        }
+    };
+    search_dirs
+        .iter()
+        .flat_map(|dir| file_names.iter().map(move |name| dir.join(name)))
+        .find(|path| path.is_file())
+}
+
+/// Returns the files and `-l` libraries named by the file at `path` if it is a linker script
+/// rather than an object, an archive or a shared library, and nothing otherwise.
+fn linker_script_inputs(path: &Path) -> Vec<String> {
+    let Ok(contents) = fs::read(path) else { return Vec::new() };
+    if contents.starts_with(b"\x7fELF") || contents.starts_with(b"!<arch>") {
+        return Vec::new();
+    }
+    let Ok(contents) = str::from_utf8(&contents) else { return Vec::new() };
+    let mut script = String::new();
+    let mut rest = contents;
+    while let Some((before, after)) = rest.split_once("/*") {
+        script.push_str(before);
+        rest = after.split_once("*/").map_or("", |(_, after)| after);
+    }
+    script.push_str(rest);
+    let script = script.replace('(', " ( ").replace(')', " ) ");
+
+    // Inputs are named inside `GROUP`, `INPUT` and `AS_NEEDED` commands, other commands like
+    // `OUTPUT_FORMAT` take arguments which aren't files.
+    let mut words = script
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|word| !word.is_empty())
+        .peekable();
+    let mut commands = Vec::new();
+    let mut inputs = Vec::new();
+    while let Some(word) = words.next() {
+        match word {
+            "(" => {}
+            ")" => {
+                commands.pop();
+            }
+            _ if words.peek() == Some(&"(") => commands.push(word),
+            _ if matches!(commands.last(), Some(&("GROUP" | "INPUT" | "AS_NEEDED"))) => {
+                inputs.push(word.to_string())
+            }
+            _ => {}
+        }
+    }
+    inputs
+}
+
+fn hash_linker_input(
+    hasher: &mut StableHasher,
+    path: &Path,
+    metadata: &fs::Metadata,
+    by_contents: bool,
+) {
+    if by_contents {
+        fs::read(path).ok().hash(hasher);
+    } else {
+        metadata.len().hash(hasher);
+        metadata.modified().ok().hash(hasher);
+    }
+}
+
+/// Fingerprints the outputs of a link by size and modification time, so that a link one of whose
+/// outputs was changed or removed since is done again.
+fn link_outputs_fingerprint(outputs: &[PathBuf]) -> Option<Fingerprint> {
+    let mut hasher = StableHasher::new();
+    for output in outputs {
+        let metadata = fs::metadata(output).ok()?;
+        metadata.len().hash(&mut hasher);
+        metadata.modified().ok()?.hash(&mut hasher);
+    }
+    Some(hasher.finish())
+}
+
+fn link_record(inputs: Fingerprint, output: Fingerprint) -> String {
+    format!("{} {}\n", inputs.to_hex(), output.to_hex())
 }
 
 // Temporarily support both -Z strip and -C strip
@@ -1857,7 +2428,7 @@ fn linker_with_args<'a, B: ArchiveBuilder<'a>>(
     tmpdir: &Path,
     out_filename: &Path,
     codegen_results: &CodegenResults,
-) -> Command {
+) -> Result<Command, ErrorGuaranteed> {
     let crt_objects_fallback = crt_objects_fallback(sess, crate_type);
     let cmd = &mut *super::linker::get_linker(
         sess,
@@ -1952,6 +2523,19 @@ fn linker_with_args<'a, B: ArchiveBuilder<'a>>(
         add_local_native_libraries(cmd, sess, codegen_results);
     }
 
+    // Link with the import libraries generated for the raw-dylib functions of this crate.
+    // Upstream rlibs already contain the import libraries for their own raw-dylib functions.
+    for (raw_dylib_name, raw_dylib_imports) in
+        collate_raw_dylibs(sess, &codegen_results.crate_info.used_libraries)?
+    {
+        cmd.add_object(&B::create_dll_import_lib(
+            sess,
+            &raw_dylib_name,
+            &raw_dylib_imports,
+            tmpdir,
+        ));
+    }
+
     // Upstream rust libraries and their nobundle static libraries
     add_upstream_rust_crates::<B>(cmd, sess, codegen_results, crate_type, tmpdir);
 
@@ -2007,7 +2591,7 @@ fn linker_with_args<'a, B: ArchiveBuilder<'a>>(
     // to it and remove the option.
     add_post_link_args(cmd, sess, flavor);
 
-    cmd.take_cmd()
+    Ok(cmd.take_cmd())
 }
 
 fn add_order_independent_options(
@@ -2048,12 +2632,15 @@ fn add_order_independent_options(
     }
 
     if sess.target.is_like_emscripten {
-        cmd.arg("-s");
-        cmd.arg(if sess.panic_strategy() == PanicStrategy::Abort {
-            "DISABLE_EXCEPTION_CATCHING=1"
+        if sess.panic_strategy() == PanicStrategy::Abort {
+            cmd.arg("-s");
+            cmd.arg("DISABLE_EXCEPTION_CATCHING=1");
+        } else if wants_wasm_eh(sess) {
+            cmd.arg("-fwasm-exceptions");
         } else {
-            "DISABLE_EXCEPTION_CATCHING=0"
-        });
+            cmd.arg("-s");
+            cmd.arg("DISABLE_EXCEPTION_CATCHING=0");
+        }
     }
 
     if flavor == LinkerFlavor::PtxLinker {
@@ -2229,10 +2816,8 @@ fn add_local_native_libraries(
                     cmd.link_staticlib(name, verbatim)
                 }
             }
-            NativeLibKind::RawDylib => {
-                // FIXME(#58713): Proper handling for raw dylibs.
-                bug!("raw_dylib feature not yet implemented");
-            }
+            // Import libraries for raw-dylib functions are generated and linked separately.
+            NativeLibKind::RawDylib => {}
         }
     }
 }
@@ -2713,3 +3298,6 @@ fn add_gcc_ld_path(cmd: &mut dyn Linker, sess: &Session, flavor: LinkerFlavor) {
         }
     }
 }
+
+#[cfg(all(unix, test))]
+mod tests;
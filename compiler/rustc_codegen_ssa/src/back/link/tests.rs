@@ -0,0 +1,51 @@
+use super::is_ld_specific_failure;
+use std::os::unix::process::ExitStatusExt;
+use std::process::ExitStatus;
+
+/// The status of a linker that exited with code 1.
+fn failed() -> ExitStatus {
+    ExitStatus::from_raw(1 << 8)
+}
+
+#[test]
+fn test_ld_crash_is_ld_specific() {
+    let outputs = [
+        "collect2: fatal error: ld terminated with signal 11 [Segmentation fault], core dumped",
+        "PLEASE submit a bug report to https://github.com/llvm/llvm-project/issues/\n\
+         Stack dump:\n0.\tProgram arguments: ld.lld",
+        "clang: error: unable to execute command: Segmentation fault (core dumped)",
+    ];
+    for output in outputs {
+        assert!(is_ld_specific_failure(&failed(), output), "{}", output);
+    }
+    // The C compiler driver itself was killed.
+    assert!(is_ld_specific_failure(&ExitStatus::from_raw(libc::SIGSEGV), ""));
+}
+
+#[test]
+fn test_unsupported_option_is_ld_specific() {
+    let outputs = [
+        "cc: error: unrecognized command-line option '-fuse-ld=mold'",
+        "clang: error: invalid linker name in argument '-fuse-ld=mold'",
+        "/usr/bin/ld.gold: fatal error: --pack-dyn-relocs=relr: unknown option",
+        "ld.lld: error: unknown argument '--no-undefined-version'",
+    ];
+    for output in outputs {
+        assert!(is_ld_specific_failure(&failed(), output), "{}", output);
+    }
+}
+
+#[test]
+fn test_input_errors_are_not_ld_specific() {
+    let outputs = [
+        "/usr/bin/ld: main.o: in function `main':\n\
+         main.c:(.text+0x5): undefined reference to `foo'\n\
+         collect2: error: ld returned 1 exit status",
+        "ld.lld: error: undefined symbol: foo\n>>> referenced by main.c\n\
+         clang: error: linker command failed with exit code 1 (use -v to see invocation)",
+        "mold: fatal: library not found: foo",
+    ];
+    for output in outputs {
+        assert!(!is_ld_specific_failure(&failed(), output), "{}", output);
+    }
+}
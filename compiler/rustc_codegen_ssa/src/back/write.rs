@@ -26,6 +26,7 @@
 use rustc_middle::middle::exported_symbols::SymbolExportInfo;
 use rustc_middle::ty::TyCtxt;
 use rustc_session::cgu_reuse_tracker::CguReuseTracker;
+use rustc_session::cgu_stats_tracker::CguStatsTracker;
 use rustc_session::config::{self, CrateType, Lto, OutputFilenames, OutputType};
 use rustc_session::config::{Passes, SwitchWithOptPath};
 use rustc_session::Session;
@@ -43,6 +44,7 @@
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::sync::Arc;
 use std::thread;
+use std::time::Instant;
 
 const PRE_LTO_BC_EXT: &str = "pre-lto.bc";
 
@@ -347,6 +349,8 @@ pub struct CodegenContext<B: WriteBackendMethods> {
     pub incr_comp_session_dir: Option<PathBuf>,
     // Used to update CGU re-use information during the thinlto phase.
     pub cgu_reuse_tracker: CguReuseTracker,
+    // Used to record the time spent on each CGU for `-Zprint-cgu-stats`.
+    pub cgu_stats_tracker: CguStatsTracker,
     // Channel back to the main control thread to send messages to
     pub coordinator_send: Sender<Box<dyn Any + Send>>,
 }
@@ -680,6 +684,14 @@ pub fn module_kind(&self) -> ModuleKind {
         }
     }
 
+    fn module_name(&self) -> &str {
+        match *self {
+            WorkItem::Optimize(ref m) => &m.name,
+            WorkItem::CopyPostLtoArtifacts(ref m) => &m.name,
+            WorkItem::LTO(ref m) => m.name(),
+        }
+    }
+
     fn start_profiling<'a>(&self, cgcx: &'a CodegenContext<B>) -> TimingGuard<'a> {
         match *self {
             WorkItem::Optimize(ref m) => {
@@ -1014,7 +1026,7 @@ fn start_executing_work<B: ExtraBackendMethods>(
         .expect("failed to spawn helper thread");
 
     let mut each_linked_rlib_for_lto = Vec::new();
-    drop(link::each_linked_rlib(crate_info, &mut |cnum, path| {
+    drop(link::each_linked_rlib(sess, crate_info, &mut |cnum, path| {
         if link::ignored_for_lto(sess, crate_info, cnum) {
             return;
         }
@@ -1045,6 +1057,7 @@ fn start_executing_work<B: ExtraBackendMethods>(
         worker: 0,
         incr_comp_session_dir: sess.incr_comp_session_dir_opt().map(|r| r.clone()),
         cgu_reuse_tracker: sess.cgu_reuse_tracker.clone(),
+        cgu_stats_tracker: sess.cgu_stats_tracker.clone(),
         coordinator_send,
         diag_emitter: shared_emitter.clone(),
         output_filenames: tcx.output_filenames(()).clone(),
@@ -1657,7 +1670,11 @@ fn drop(&mut self) {
         // surface that there was an error in this worker.
         bomb.result = {
             let _prof_timer = work.start_profiling(&cgcx);
-            Some(execute_work_item(&cgcx, work))
+            let module_name = work.module_name().to_string();
+            let start_time = Instant::now();
+            let result = execute_work_item(&cgcx, work);
+            cgcx.cgu_stats_tracker.add_backend_time(&module_name, start_time.elapsed());
+            Some(result)
         };
     })
     .expect("failed to spawn thread");
@@ -1761,7 +1778,7 @@ pub fn check(&self, sess: &Session, blocking: bool) {
 
                     let mut err = match level {
                         Level::Error { lint: false } => sess.struct_err(msg).forget_guarantee(),
-                        Level::Warning => sess.struct_warn(msg),
+                        Level::Warning(_) => sess.struct_warn(msg),
                         Level::Note => sess.struct_note_without_error(msg),
                         _ => bug!("Invalid inline asm diagnostic level"),
                     };
@@ -1831,6 +1848,24 @@ pub fn join(self, sess: &Session) -> (CodegenResults, FxHashMap<WorkProductId, W
 
         sess.cgu_reuse_tracker.check_expected_reuse(sess.diagnostic());
 
+        if sess.prof.enabled() || sess.cgu_stats_tracker.enabled() {
+            // The backend time of each codegen unit is already recorded by the profiling
+            // events of its work items, which have the name of the codegen unit as argument.
+            for module in &compiled_modules.modules {
+                if let Some(object_size) =
+                    module.object.as_ref().and_then(|object| fs::metadata(object).ok())
+                {
+                    sess.prof.artifact_size(
+                        "codegen_unit_object_size",
+                        &*module.name,
+                        object_size.len(),
+                    );
+                    sess.cgu_stats_tracker.set_object_size(&module.name, object_size.len());
+                }
+            }
+        }
+        sess.cgu_stats_tracker.print();
+
         sess.abort_if_errors();
 
         let work_products =
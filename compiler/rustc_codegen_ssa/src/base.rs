@@ -13,7 +13,7 @@
 
 use rustc_attr as attr;
 use rustc_data_structures::fx::FxHashMap;
-use rustc_data_structures::profiling::{get_resident_set_size, print_time_passes_entry};
+use rustc_data_structures::profiling::get_resident_set_size;
 
 use rustc_data_structures::sync::par_iter;
 #[cfg(parallel_compiler)]
@@ -338,6 +338,19 @@ pub fn wants_msvc_seh(sess: &Session) -> bool {
     sess.target.is_like_msvc
 }
 
+/// Returns `true` if this session's target uses the WebAssembly exception handling
+/// instructions for unwinding, i.e. `-Z wasm-exception-handling` was passed on a wasm target.
+pub fn wants_wasm_eh(sess: &Session) -> bool {
+    sess.target.is_like_wasm && sess.opts.debugging_opts.wasm_exception_handling
+}
+
+/// Returns `true` if this session's target requires the new exception handling
+/// LLVM IR instructions (`catchswitch`, `catchpad`, `cleanuppad`, ...) instead of
+/// the traditional `landingpad` based ones.
+pub fn wants_new_eh_instructions(sess: &Session) -> bool {
+    wants_wasm_eh(sess) || wants_msvc_seh(sess)
+}
+
 pub fn memcpy_ty<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
     bx: &mut Bx,
     dst: Bx::Value,
@@ -462,7 +475,7 @@ fn create_entry_fn<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
             (rust_main, start_ty, vec![arg_argc, arg_argv])
         };
 
-        let result = bx.call(start_ty, start_fn, &args, None);
+        let result = bx.call(start_ty, None, None, start_fn, &args, None);
         let cast = bx.intcast(result, cx.type_int(), true);
         bx.ret(cast);
 
@@ -743,7 +756,7 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
     if tcx.sess.time_passes() {
         let end_rss = get_resident_set_size();
 
-        print_time_passes_entry(
+        tcx.prof.time_passes_entry(
             "codegen_to_LLVM_IR",
             total_codegen_time,
             start_rss.unwrap(),
@@ -18,6 +18,7 @@
 use rustc_middle::ty::layout::{HasTyCtxt, LayoutOf};
 use rustc_middle::ty::print::{with_no_trimmed_paths, with_no_visible_paths};
 use rustc_middle::ty::{self, Instance, Ty, TypeFoldable};
+use rustc_session::config::RemapPathScopeComponents;
 use rustc_span::source_map::Span;
 use rustc_span::{sym, Symbol};
 use rustc_symbol_mangling::typeid_for_fnabi;
@@ -34,14 +35,14 @@ struct TerminatorCodegenHelper<'tcx> {
 }
 
 impl<'a, 'tcx> TerminatorCodegenHelper<'tcx> {
-    /// Returns the appropriate `Funclet` for the current funclet, if on MSVC,
+    /// Returns the appropriate `Funclet` for the current funclet, if on MSVC or wasm EH,
     /// either already previously cached, or newly created, by `landing_pad_for`.
     fn funclet<'b, Bx: BuilderMethods<'a, 'tcx>>(
         &self,
         fx: &'b mut FunctionCx<'a, 'tcx, Bx>,
     ) -> Option<&'b Bx::Funclet> {
         let funclet_bb = self.funclet_bb?;
-        if base::wants_msvc_seh(fx.cx.tcx().sess) {
+        if base::wants_new_eh_instructions(fx.cx.tcx().sess) {
             // If `landing_pad_for` hasn't been called yet to create the `Funclet`,
             // it has to be now. This may not seem necessary, as RPO should lead
             // to all the unwind edges being visited (and so to `landing_pad_for`
@@ -74,7 +75,9 @@ fn lltarget<Bx: BuilderMethods<'a, 'tcx>>(
         let target_funclet = fx.cleanup_kinds[target].funclet_bb(target);
         match (self.funclet_bb, target_funclet) {
             (None, None) => (lltarget, false),
-            (Some(f), Some(t_f)) if f == t_f || !base::wants_msvc_seh(fx.cx.tcx().sess) => {
+            (Some(f), Some(t_f))
+                if f == t_f || !base::wants_new_eh_instructions(fx.cx.tcx().sess) =>
+            {
                 (lltarget, false)
             }
             // jump *into* cleanup - need a landing pad if GNU, cleanup pad if MSVC
@@ -136,21 +139,22 @@ fn do_call<Bx: BuilderMethods<'a, 'tcx>>(
         // If there is a cleanup block and the function we're calling can unwind, then
         // do an invoke, otherwise do a call.
         let fn_ty = bx.fn_decl_backend_type(&fn_abi);
+        let fn_attrs = fx.cx.tcx().codegen_fn_attrs(fx.instance.def_id());
 
         let unwind_block = if let Some(cleanup) = cleanup.filter(|_| fn_abi.can_unwind) {
             Some(self.llblock(fx, cleanup))
         } else if fx.mir[self.bb].is_cleanup
             && fn_abi.can_unwind
-            && !base::wants_msvc_seh(fx.cx.tcx().sess)
+            && !base::wants_new_eh_instructions(fx.cx.tcx().sess)
         {
             // Exception must not propagate out of the execution of a cleanup (doing so
             // can cause undefined behaviour). We insert a double unwind guard for
             // functions that can potentially unwind to protect against this.
             //
-            // This is not necessary for SEH which does not use successive unwinding
-            // like Itanium EH. EH frames in SEH are different from normal function
-            // frames and SEH will abort automatically if an exception tries to
-            // propagate out from cleanup.
+            // This is not necessary for SEH (or wasm EH) which does not use successive
+            // unwinding like Itanium EH. EH frames in SEH are different from normal
+            // function frames and SEH will abort automatically if an exception tries
+            // to propagate out from cleanup.
             Some(fx.double_unwind_guard())
         } else {
             None
@@ -162,8 +166,16 @@ fn do_call<Bx: BuilderMethods<'a, 'tcx>>(
             } else {
                 fx.unreachable_block()
             };
-            let invokeret =
-                bx.invoke(fn_ty, fn_ptr, &llargs, ret_llbb, unwind_block, self.funclet(fx));
+            let invokeret = bx.invoke(
+                fn_ty,
+                Some(fn_attrs),
+                Some(fn_abi),
+                fn_ptr,
+                &llargs,
+                ret_llbb,
+                unwind_block,
+                self.funclet(fx),
+            );
             bx.apply_attrs_callsite(&fn_abi, invokeret);
             if fx.mir[self.bb].is_cleanup {
                 bx.do_not_inline(invokeret);
@@ -175,7 +187,8 @@ fn do_call<Bx: BuilderMethods<'a, 'tcx>>(
                 fx.store_return(bx, ret_dest, &fn_abi.ret, invokeret);
             }
         } else {
-            let llret = bx.call(fn_ty, fn_ptr, &llargs, self.funclet(fx));
+            let llret =
+                bx.call(fn_ty, Some(fn_attrs), Some(fn_abi), fn_ptr, &llargs, self.funclet(fx));
             bx.apply_attrs_callsite(&fn_abi, llret);
             if fx.mir[self.bb].is_cleanup {
                 // Cleanup is always the cold path. Don't inline
@@ -1268,8 +1281,10 @@ fn get_caller_location(
         let mut span_to_caller_location = |span: Span| {
             let topmost = span.ctxt().outer_expn().expansion_cause().unwrap_or(span);
             let caller = tcx.sess.source_map().lookup_char_pos(topmost.lo());
+            let display_pref =
+                tcx.sess.filename_display_preference(RemapPathScopeComponents::MACRO);
             let const_loc = tcx.const_caller_location((
-                Symbol::intern(&caller.file.name.prefer_remapped().to_string_lossy()),
+                Symbol::intern(&caller.file.name.display(display_pref).to_string_lossy()),
                 caller.line as u32,
                 caller.col_display as u32 + 1,
             ));
@@ -1332,7 +1347,7 @@ fn landing_pad_for(&mut self, bb: mir::BasicBlock) -> Bx::BasicBlock {
     // FIXME(eddyb) rename this to `eh_pad_for_uncached`.
     fn landing_pad_for_uncached(&mut self, bb: mir::BasicBlock) -> Bx::BasicBlock {
         let llbb = self.llbb(bb);
-        if base::wants_msvc_seh(self.cx.sess()) {
+        if base::wants_new_eh_instructions(self.cx.sess()) {
             let funclet;
             let ret_llbb;
             match self.mir[bb].terminator.as_ref().map(|t| &t.kind) {
@@ -1341,7 +1356,7 @@ fn landing_pad_for_uncached(&mut self, bb: mir::BasicBlock) -> Bx::BasicBlock {
                 // so that we assert that `extern` functions do indeed not panic,
                 // and if they do we abort the process.
                 //
-                // On MSVC these are tricky though (where we're doing funclets). If
+                // With funclets (MSVC and wasm EH) these are tricky though. If
                 // we were to do a cleanuppad (like below) the normal functions like
                 // `longjmp` would trigger the abort logic, terminating the
                 // program. Instead we insert the equivalent of `catch(...)` for C++
@@ -1370,12 +1385,19 @@ fn landing_pad_for_uncached(&mut self, bb: mir::BasicBlock) -> Bx::BasicBlock {
                     // C++ personality function, but `catch (...)` has no type so
                     // it's null. The 64 here is actually a bitfield which
                     // represents that this is a catch-all block.
+                    //
+                    // The `WasmEHPrepare` LLVM pass only recognizes a single "null"
+                    // as a catch-all block, so wasm EH doesn't get the other two.
                     let mut cp_bx = Bx::build(self.cx, cp_bb);
                     let null = cp_bx.const_null(
                         cp_bx.type_i8p_ext(cp_bx.cx().data_layout().instruction_address_space),
                     );
-                    let sixty_four = cp_bx.const_i32(64);
-                    funclet = cp_bx.catch_pad(cs, &[null, sixty_four, null]);
+                    funclet = if base::wants_msvc_seh(self.cx.sess()) {
+                        let sixty_four = cp_bx.const_i32(64);
+                        cp_bx.catch_pad(cs, &[null, sixty_four, null])
+                    } else {
+                        cp_bx.catch_pad(cs, &[null])
+                    };
                     cp_bx.br(llbb);
                 }
                 _ => {
@@ -1423,7 +1445,7 @@ fn unreachable_block(&mut self) -> Bx::BasicBlock {
 
     fn double_unwind_guard(&mut self) -> Bx::BasicBlock {
         self.double_unwind_guard.unwrap_or_else(|| {
-            assert!(!base::wants_msvc_seh(self.cx.sess()));
+            assert!(!base::wants_new_eh_instructions(self.cx.sess()));
 
             let llbb = Bx::append_block(self.cx, self.llfn, "abort");
             let mut bx = Bx::build(self.cx, llbb);
@@ -1436,7 +1458,7 @@ fn double_unwind_guard(&mut self) -> Bx::BasicBlock {
             let (fn_abi, fn_ptr) = common::build_langcall(&bx, None, LangItem::PanicNoUnwind);
             let fn_ty = bx.fn_decl_backend_type(&fn_abi);
 
-            let llret = bx.call(fn_ty, fn_ptr, &[], None);
+            let llret = bx.call(fn_ty, None, None, fn_ptr, &[], None);
             bx.apply_attrs_callsite(&fn_abi, llret);
             bx.do_not_inline(llret);
 
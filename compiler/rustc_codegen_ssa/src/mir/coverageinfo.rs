@@ -53,6 +53,17 @@ pub fn codegen_coverage(&self, bx: &mut Bx, coverage: Coverage, scope: SourceSco
                     code_region.expect("unreachable regions always have code regions"),
                 );
             }
+            CoverageKind::Branch { true_operand, false_operand } => {
+                bx.add_coverage_branch(
+                    instance,
+                    true_operand,
+                    false_operand,
+                    code_region.expect("branch regions always have code regions"),
+                );
+            }
+            CoverageKind::BlockMarker { .. } => {
+                bug!("`BlockMarker`s should have been removed by the `InstrumentCoverage` pass")
+            }
         }
     }
 }
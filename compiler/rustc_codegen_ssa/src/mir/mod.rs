@@ -3,8 +3,9 @@
 use rustc_middle::mir::interpret::ErrorHandled;
 use rustc_middle::ty::layout::{FnAbiOf, HasTyCtxt, TyAndLayout};
 use rustc_middle::ty::{self, Instance, Ty, TypeFoldable};
-use rustc_symbol_mangling::typeid_for_fnabi;
+use rustc_symbol_mangling::{kcfi_typeid_for_fnabi, typeid_for_fnabi};
 use rustc_target::abi::call::{FnAbi, PassMode};
+use rustc_target::spec::SanitizerSet;
 
 use std::iter;
 
@@ -49,7 +50,7 @@ pub struct FunctionCx<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> {
     /// The funclet status of each basic block
     cleanup_kinds: IndexVec<mir::BasicBlock, analyze::CleanupKind>,
 
-    /// When targeting MSVC, this stores the cleanup info for each funclet BB.
+    /// When targeting MSVC or using wasm EH, this stores the cleanup info for each funclet BB.
     /// This is initialized at the same time as the `landing_pads` entry for the
     /// funclets' head block, i.e. when needed by an unwind / `cleanup_ret` edge.
     funclets: IndexVec<mir::BasicBlock, Option<Bx::Funclet>>,
@@ -254,6 +255,15 @@ pub fn codegen_mir<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
         let typeid = typeid_for_fnabi(cx.tcx(), fn_abi);
         bx.type_metadata(llfn, typeid);
     }
+
+    // For backends that support KCFI (i.e., checking a type identifier stored in front of the
+    // callee at the call site), record the type identifier of the function being defined.
+    if cx.tcx().sess.is_sanitizer_kcfi_enabled()
+        && !cx.tcx().codegen_fn_attrs(instance.def_id()).no_sanitize.contains(SanitizerSet::KCFI)
+    {
+        let typeid = kcfi_typeid_for_fnabi(cx.tcx(), fn_abi);
+        bx.kcfi_type_metadata(llfn, typeid);
+    }
 }
 
 /// Produces, for each argument, a `Value` pointing at the
@@ -152,40 +152,7 @@ fn struct_generic(
             if let Some(span_msg) = span_msg {
                 err.span_label(self.span, span_msg);
             }
-            // Add spans for the stacktrace. Don't print a single-line backtrace though.
-            if self.stacktrace.len() > 1 {
-                // Helper closure to print duplicated lines.
-                let mut flush_last_line = |last_frame, times| {
-                    if let Some((line, span)) = last_frame {
-                        err.span_label(span, &line);
-                        // Don't print [... additional calls ...] if the number of lines is small
-                        if times < 3 {
-                            for _ in 0..times {
-                                err.span_label(span, &line);
-                            }
-                        } else {
-                            err.span_label(
-                                span,
-                                format!("[... {} additional calls {} ...]", times, &line),
-                            );
-                        }
-                    }
-                };
-
-                let mut last_frame = None;
-                let mut times = 0;
-                for frame_info in &self.stacktrace {
-                    let frame = (frame_info.to_string(), frame_info.span);
-                    if last_frame.as_ref() == Some(&frame) {
-                        times += 1;
-                    } else {
-                        flush_last_line(last_frame, times);
-                        last_frame = Some(frame);
-                        times = 0;
-                    }
-                }
-                flush_last_line(last_frame, times);
-            }
+            label_stacktrace(err, &self.stacktrace);
             // Let the caller attach any additional information it wants.
             decorate(err);
         };
@@ -237,3 +204,40 @@ fn struct_generic(
         }
     }
 }
+
+/// Adds spans for the stacktrace of a const evaluation to `err`, collapsing runs of identical
+/// frames. A single-line backtrace is not printed.
+pub(super) fn label_stacktrace(err: &mut Diagnostic, stacktrace: &[FrameInfo<'_>]) {
+    if stacktrace.len() <= 1 {
+        return;
+    }
+
+    // Helper closure to print duplicated lines.
+    let mut flush_last_line = |last_frame, times| {
+        if let Some((line, span)) = last_frame {
+            err.span_label(span, &line);
+            // Don't print [... additional calls ...] if the number of lines is small
+            if times < 3 {
+                for _ in 0..times {
+                    err.span_label(span, &line);
+                }
+            } else {
+                err.span_label(span, format!("[... {} additional calls {} ...]", times, &line));
+            }
+        }
+    };
+
+    let mut last_frame = None;
+    let mut times = 0;
+    for frame_info in stacktrace {
+        let frame = (frame_info.to_string(), frame_info.span);
+        if last_frame.as_ref() == Some(&frame) {
+            times += 1;
+        } else {
+            flush_last_line(last_frame, times);
+            last_frame = Some(frame);
+            times = 0;
+        }
+    }
+    flush_last_line(last_frame, times);
+}
@@ -2,13 +2,13 @@
 use crate::interpret::eval_nullary_intrinsic;
 use crate::interpret::{
     intern_const_alloc_recursive, Allocation, ConstAlloc, ConstValue, CtfeValidationMode, GlobalId,
-    Immediate, InternKind, InterpCx, InterpResult, MPlaceTy, MemoryKind, OpTy, RefTracking, Scalar,
-    ScalarMaybeUninit, StackPopCleanup,
+    ImmTy, Immediate, InternKind, InterpCx, InterpResult, MPlaceTy, MemoryKind, OpTy, RefTracking,
+    Scalar, ScalarMaybeUninit, StackPopCleanup,
 };
 
 use rustc_hir::def::DefKind;
 use rustc_middle::mir;
-use rustc_middle::mir::interpret::ErrorHandled;
+use rustc_middle::mir::interpret::{EncodedConstValue, ErrorHandled};
 use rustc_middle::mir::pretty::display_allocation;
 use rustc_middle::traits::Reveal;
 use rustc_middle::ty::layout::LayoutOf;
@@ -18,6 +18,7 @@
 use rustc_target::abi::{self, Abi};
 use std::borrow::Cow;
 use std::convert::TryInto;
+use std::iter;
 
 pub fn note_on_undefined_behavior_error() -> &'static str {
     "The rules on what exactly is undefined behavior aren't clear, \
@@ -104,6 +105,58 @@ pub(super) fn mk_eval_cx<'mir, 'tcx>(
     )
 }
 
+/// Evaluates a call of the `const fn` `instance` with constant arguments, so that the MIR
+/// const-propagation pass can fold it.
+///
+/// The arguments must not contain pointers, and only results that are immediates made of plain
+/// integers are returned, so that no value refers to the memory of another interpreter.
+/// Evaluation gives up silently on any error, or after evaluating `step_limit` terminators.
+pub fn eval_const_fn_call<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    instance: ty::Instance<'tcx>,
+    args: &[ConstValue<'tcx>],
+    step_limit: usize,
+) -> Option<ImmTy<'tcx>> {
+    assert!(step_limit > 0, "a step limit of zero would let the call run forever");
+    if instance.def.requires_caller_location(tcx) {
+        return None;
+    }
+    let mut ecx = mk_eval_cx(tcx, tcx.def_span(instance.def_id()), param_env, false);
+    ecx.machine.steps_remaining = step_limit;
+    let body = ecx.load_mir(instance.def, None).ok()?;
+    if body.spread_arg.is_some() || body.arg_count != args.len() {
+        return None;
+    }
+
+    let result: InterpResult<'tcx, ImmTy<'tcx>> = try {
+        let ret_layout =
+            ecx.layout_of(EarlyBinder(body.return_ty()).subst(tcx, instance.substs))?;
+        let ret = ecx.allocate(ret_layout, MemoryKind::Stack)?;
+        ecx.push_stack_frame(
+            instance,
+            body,
+            &ret.into(),
+            StackPopCleanup::Root { cleanup: false },
+        )?;
+        for (local, &arg) in iter::zip(body.args_iter(), args) {
+            let dest = ecx.eval_place(mir::Place::from(local))?;
+            let arg = ecx.const_val_to_op(arg, dest.layout.ty, Some(dest.layout))?;
+            ecx.copy_op(&arg, &dest)?;
+        }
+        ecx.run()?;
+        ecx.read_immediate(&ret.into())?
+    };
+    let result = result.ok()?;
+    let is_int =
+        |scalar: ScalarMaybeUninit| matches!(scalar, ScalarMaybeUninit::Scalar(Scalar::Int(_)));
+    match *result {
+        Immediate::Scalar(scalar) if is_int(scalar) => Some(result),
+        Immediate::ScalarPair(a, b) if is_int(a) && is_int(b) => Some(result),
+        _ => None,
+    }
+}
+
 /// This function converts an interpreter value into a constant that is meant for use in the
 /// type system.
 #[instrument(skip(ecx), level = "debug")]
@@ -253,9 +306,33 @@ pub fn eval_to_const_value_raw_provider<'tcx>(
         });
     }
 
+    // Constants of other crates have usually been evaluated when compiling those crates already.
+    if let Some(value) = const_value_from_metadata(tcx, key) {
+        return Ok(value);
+    }
+
     tcx.eval_to_allocation_raw(key).map(|val| turn_into_const_value(tcx, val, key))
 }
 
+/// Returns the value of `key` that the crate defining it recorded in its metadata, if any.
+fn const_value_from_metadata<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>,
+) -> Option<ConstValue<'tcx>> {
+    let GlobalId { instance, promoted } = key.value;
+    let ty::InstanceDef::Item(def) = instance.def else { return None };
+    if def.did.is_local() || promoted.is_some() || !instance.substs.is_empty() {
+        return None;
+    }
+    Some(match tcx.const_value_from_metadata(def.did)? {
+        EncodedConstValue::ValTree(valtree) => {
+            let ty = tcx.normalize_erasing_regions(key.param_env, tcx.type_of(def.did));
+            tcx.valtree_to_const_val((ty, valtree))
+        }
+        EncodedConstValue::Value(value) => value,
+    })
+}
+
 pub fn eval_to_allocation_raw_provider<'tcx>(
     tcx: TyCtxt<'tcx>,
     key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>,
@@ -301,6 +378,7 @@ pub fn eval_to_allocation_raw_provider<'tcx>(
         // they do not have to behave "as if" they were evaluated at runtime.
         CompileTimeInterpreter::new(tcx.const_eval_limit(), /*can_access_statics:*/ is_static),
     );
+    ecx.machine.next_progress_report = tcx.sess.opts.debugging_opts.const_eval_step_budget;
 
     let res = ecx.load_mir(cid.instance.def, cid.promoted);
     match res.and_then(|body| eval_body_using_ecx(&mut ecx, cid, &body)) {
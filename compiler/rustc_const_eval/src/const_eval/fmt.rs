@@ -0,0 +1,320 @@
+//! Formatting of `panic!()` messages during const evaluation.
+//!
+//! The `Display` and `Debug` impls behind a `fmt::Arguments` cannot be called in a const context,
+//! so the interpreter reads the `fmt::Arguments` passed to `panic_fmt` and formats the arguments
+//! itself. This only supports the types that are easy to format here: integers, `bool`, `char`
+//! and `str`, behind any number of references. Const checking rejects arguments of other types.
+
+use rustc_middle::ty::{self, Ty};
+use rustc_span::symbol::sym;
+use rustc_target::abi::Size;
+
+use crate::interpret::{InterpResult, MPlaceTy, OpTy};
+
+use super::CompileTimeEvalContext;
+
+// The flags of `core::fmt::rt::v1::FormatSpec`, see `FlagV1` in `core::fmt`.
+const SIGN_PLUS: u32 = 1 << 0;
+const ALTERNATE: u32 = 1 << 2;
+const SIGN_AWARE_ZERO_PAD: u32 = 1 << 3;
+const DEBUG_LOWER_HEX: u32 = 1 << 4;
+const DEBUG_UPPER_HEX: u32 = 1 << 5;
+
+/// Returns whether const-eval can format values of type `ty` in a panic message.
+pub fn is_const_formattable(ty: Ty<'_>) -> bool {
+    match *ty.kind() {
+        ty::Int(_) | ty::Uint(_) | ty::Bool | ty::Char | ty::Str => true,
+        ty::Ref(_, ty, _) => is_const_formattable(ty),
+        _ => false,
+    }
+}
+
+/// A value read from the interpreter that can be formatted.
+enum FmtValue {
+    Int { bits: u128, size: Size, signed: bool },
+    Bool(bool),
+    Char(char),
+    Str(String),
+}
+
+/// The formatting traits implemented for `FmtValue`.
+#[derive(Clone, Copy)]
+enum FmtTrait {
+    Display,
+    Debug,
+    Octal,
+    LowerHex,
+    UpperHex,
+    Binary,
+}
+
+#[derive(Clone, Copy)]
+enum FmtAlign {
+    Left,
+    Right,
+    Center,
+}
+
+/// The formatting options of an argument, mirroring `core::fmt::rt::v1::FormatSpec`.
+struct FmtSpec {
+    fill: char,
+    align: Option<FmtAlign>,
+    flags: u32,
+    precision: Option<usize>,
+    width: Option<usize>,
+}
+
+impl Default for FmtSpec {
+    fn default() -> Self {
+        FmtSpec { fill: ' ', align: None, flags: 0, precision: None, width: None }
+    }
+}
+
+impl FmtSpec {
+    /// Formats `value` the way the impl of `fmt_trait` in `core` would, or returns `None` if that
+    /// impl does not exist or is not supported.
+    fn format(&self, value: &FmtValue, fmt_trait: FmtTrait) -> Option<String> {
+        match (value, fmt_trait) {
+            (&FmtValue::Int { bits, size, signed }, _) => {
+                let fmt_trait = match fmt_trait {
+                    FmtTrait::Debug if self.flags & DEBUG_LOWER_HEX != 0 => FmtTrait::LowerHex,
+                    FmtTrait::Debug if self.flags & DEBUG_UPPER_HEX != 0 => FmtTrait::UpperHex,
+                    FmtTrait::Debug => FmtTrait::Display,
+                    fmt_trait => fmt_trait,
+                };
+                // Like in `core`, only decimal formatting takes the sign into account, the other
+                // radixes print the two's complement representation.
+                let (is_nonnegative, prefix, digits) = match fmt_trait {
+                    FmtTrait::Display if signed => {
+                        let value = size.sign_extend(bits) as i128;
+                        (value >= 0, "", value.unsigned_abs().to_string())
+                    }
+                    FmtTrait::Display => (true, "", bits.to_string()),
+                    FmtTrait::Octal => (true, "0o", format!("{bits:o}")),
+                    FmtTrait::LowerHex => (true, "0x", format!("{bits:x}")),
+                    FmtTrait::UpperHex => (true, "0x", format!("{bits:X}")),
+                    FmtTrait::Binary => (true, "0b", format!("{bits:b}")),
+                    FmtTrait::Debug => unreachable!(),
+                };
+                Some(self.pad_integral(is_nonnegative, prefix, &digits))
+            }
+            (&FmtValue::Bool(b), FmtTrait::Display | FmtTrait::Debug) => {
+                Some(self.pad(if b { "true" } else { "false" }))
+            }
+            (&FmtValue::Char(c), FmtTrait::Display) => Some(self.pad(c.encode_utf8(&mut [0; 4]))),
+            (FmtValue::Str(s), FmtTrait::Display) => Some(self.pad(s)),
+            // The `Debug` impls of `char` and `str` ignore the formatting options.
+            (&FmtValue::Char(c), FmtTrait::Debug) => Some(format!("{c:?}")),
+            (FmtValue::Str(s), FmtTrait::Debug) => Some(format!("{s:?}")),
+            _ => None,
+        }
+    }
+
+    /// Like `Formatter::pad_integral`.
+    fn pad_integral(&self, is_nonnegative: bool, prefix: &str, digits: &str) -> String {
+        let mut sign = String::new();
+        if !is_nonnegative {
+            sign.push('-');
+        } else if self.flags & SIGN_PLUS != 0 {
+            sign.push('+');
+        }
+        if self.flags & ALTERNATE != 0 {
+            sign.push_str(prefix);
+        }
+        let len = sign.len() + digits.len();
+        match self.width {
+            Some(width) if width > len && self.flags & SIGN_AWARE_ZERO_PAD != 0 => {
+                format!("{sign}{}{digits}", "0".repeat(width - len))
+            }
+            Some(width) if width > len => {
+                self.padding(&format!("{sign}{digits}"), width - len, FmtAlign::Right)
+            }
+            _ => format!("{sign}{digits}"),
+        }
+    }
+
+    /// Like `Formatter::pad`.
+    fn pad(&self, s: &str) -> String {
+        let s = match self.precision {
+            Some(max) => s.char_indices().nth(max).map_or(s, |(i, _)| &s[..i]),
+            None => s,
+        };
+        let len = s.chars().count();
+        match self.width {
+            Some(width) if width > len => self.padding(s, width - len, FmtAlign::Left),
+            _ => s.to_owned(),
+        }
+    }
+
+    fn padding(&self, s: &str, padding: usize, default: FmtAlign) -> String {
+        let (pre, post) = match self.align.unwrap_or(default) {
+            FmtAlign::Left => (0, padding),
+            FmtAlign::Right => (padding, 0),
+            FmtAlign::Center => (padding / 2, (padding + 1) / 2),
+        };
+        let fill = self.fill.to_string();
+        format!("{}{s}{}", fill.repeat(pre), fill.repeat(post))
+    }
+}
+
+impl<'mir, 'tcx> CompileTimeEvalContext<'mir, 'tcx> {
+    /// Formats the `fmt::Arguments` passed to `panic_fmt`, or returns `None` if some argument
+    /// cannot be formatted during const evaluation.
+    pub(super) fn format_panic_args(
+        &self,
+        fmt_args: &OpTy<'tcx>,
+    ) -> InterpResult<'tcx, Option<String>> {
+        // `fmt::Arguments` has the fields `pieces: &[&str]`, `fmt: Option<&[rt::v1::Argument]>`
+        // and `args: &[ArgumentV1]`, where `fmt` is `None` if all arguments use the default
+        // formatting options.
+        let pieces = self.deref_operand(&self.operand_field(fmt_args, 0)?)?;
+        let specs = self.operand_field(fmt_args, 1)?;
+        let args = self.deref_operand(&self.operand_field(fmt_args, 2)?)?;
+
+        let (_, variant) = self.read_discriminant(&specs)?;
+        let specs = match variant.as_u32() {
+            0 => None,
+            _ => {
+                let specs = self.operand_downcast(&specs, variant)?;
+                Some(self.deref_operand(&self.operand_field(&specs, 0)?)?)
+            }
+        };
+        let num_args = match &specs {
+            Some(specs) => specs.len(self)?,
+            None => args.len(self)?,
+        };
+
+        let mut msg = String::new();
+        for i in 0..num_args {
+            msg.push_str(self.read_piece(&pieces, i)?);
+            let (position, spec) = match &specs {
+                Some(specs) => {
+                    let spec = self.mplace_index(specs, i)?;
+                    let position = self.mplace_field(&spec, 0)?;
+                    let position = self.read_scalar(&position.into())?.to_machine_usize(self)?;
+                    let Some(spec) = self.read_fmt_spec(&self.mplace_field(&spec, 1)?)? else {
+                        return Ok(None);
+                    };
+                    (position, spec)
+                }
+                None => (i, FmtSpec::default()),
+            };
+            let Some(arg) = self.format_arg(&self.mplace_index(&args, position)?, &spec)? else {
+                return Ok(None);
+            };
+            msg.push_str(&arg);
+        }
+        if num_args < pieces.len(self)? {
+            msg.push_str(self.read_piece(&pieces, num_args)?);
+        }
+        Ok(Some(msg))
+    }
+
+    /// Formats the value behind the reference passed to `panic_display`, or returns `None` if it
+    /// cannot be formatted during const evaluation.
+    pub(super) fn format_panic_display(
+        &self,
+        arg: &OpTy<'tcx>,
+    ) -> InterpResult<'tcx, Option<String>> {
+        let value = self.read_fmt_value(self.deref_operand(arg)?)?;
+        Ok(value.and_then(|value| FmtSpec::default().format(&value, FmtTrait::Display)))
+    }
+
+    fn read_piece(&self, pieces: &MPlaceTy<'tcx>, index: u64) -> InterpResult<'tcx, &str> {
+        let piece = self.deref_operand(&self.mplace_index(pieces, index)?.into())?;
+        self.read_str(&piece)
+    }
+
+    /// Formats a `fmt::ArgumentV1` by looking at the formatting function it holds.
+    fn format_arg(
+        &self,
+        arg: &MPlaceTy<'tcx>,
+        spec: &FmtSpec,
+    ) -> InterpResult<'tcx, Option<String>> {
+        let tcx = *self.tcx;
+        let value = self.read_pointer(&self.mplace_field(arg, 0)?.into())?;
+        let formatter = self.read_pointer(&self.mplace_field(arg, 1)?.into())?;
+        let formatter = self.get_ptr_fn(formatter)?.as_instance()?;
+
+        // The formatting function is the `fmt` method of an impl of one of the formatting traits,
+        // which tells us how to format the value and what its type is.
+        let Some(impl_def_id) = tcx.impl_of_method(formatter.def_id()) else {
+            return Ok(None);
+        };
+        let Some(trait_def_id) = tcx.trait_id_of_impl(impl_def_id) else {
+            return Ok(None);
+        };
+        let fmt_trait = match tcx.get_diagnostic_name(trait_def_id) {
+            Some(sym::Display) => FmtTrait::Display,
+            Some(sym::Debug) => FmtTrait::Debug,
+            Some(sym::Octal) => FmtTrait::Octal,
+            Some(sym::LowerHex) => FmtTrait::LowerHex,
+            Some(sym::UpperHex) => FmtTrait::UpperHex,
+            Some(sym::Binary) => FmtTrait::Binary,
+            _ => return Ok(None),
+        };
+        let sig = formatter.ty(tcx, ty::ParamEnv::reveal_all()).fn_sig(tcx);
+        let sig = tcx.normalize_erasing_late_bound_regions(ty::ParamEnv::reveal_all(), sig);
+        let Some(value_ty) = sig.inputs()[0].builtin_deref(true) else {
+            return Ok(None);
+        };
+
+        let value = MPlaceTy::from_aligned_ptr(value, self.layout_of(value_ty.ty)?);
+        let value = self.read_fmt_value(value)?;
+        Ok(value.and_then(|value| spec.format(&value, fmt_trait)))
+    }
+
+    fn read_fmt_value(&self, mut place: MPlaceTy<'tcx>) -> InterpResult<'tcx, Option<FmtValue>> {
+        while place.layout.ty.is_ref() {
+            place = self.deref_operand(&place.into())?;
+        }
+        let value = match *place.layout.ty.kind() {
+            ty::Int(_) | ty::Uint(_) => FmtValue::Int {
+                bits: self.read_scalar(&place.into())?.to_bits(place.layout.size)?,
+                size: place.layout.size,
+                signed: place.layout.ty.is_signed(),
+            },
+            ty::Bool => FmtValue::Bool(self.read_scalar(&place.into())?.to_bool()?),
+            ty::Char => FmtValue::Char(self.read_scalar(&place.into())?.to_char()?),
+            ty::Str => FmtValue::Str(self.read_str(&place)?.to_owned()),
+            _ => return Ok(None),
+        };
+        Ok(Some(value))
+    }
+
+    /// Reads a `rt::v1::FormatSpec`, returning `None` for options that are not supported.
+    fn read_fmt_spec(&self, spec: &MPlaceTy<'tcx>) -> InterpResult<'tcx, Option<FmtSpec>> {
+        let fill = self.read_scalar(&self.mplace_field(spec, 0)?.into())?.to_char()?;
+        // `rt::v1::Alignment` is `Left`, `Right`, `Center` or `Unknown`.
+        let (_, align) = self.read_discriminant(&self.mplace_field(spec, 1)?.into())?;
+        let align = match align.as_u32() {
+            0 => Some(FmtAlign::Left),
+            1 => Some(FmtAlign::Right),
+            2 => Some(FmtAlign::Center),
+            _ => None,
+        };
+        let flags = self.read_scalar(&self.mplace_field(spec, 2)?.into())?.to_u32()?;
+        let Some(precision) = self.read_fmt_count(&self.mplace_field(spec, 3)?)? else {
+            return Ok(None);
+        };
+        let Some(width) = self.read_fmt_count(&self.mplace_field(spec, 4)?)? else {
+            return Ok(None);
+        };
+        Ok(Some(FmtSpec { fill, align, flags, precision, width }))
+    }
+
+    /// Reads a `rt::v1::Count`, returning `None` for counts that refer to another argument.
+    fn read_fmt_count(&self, count: &MPlaceTy<'tcx>) -> InterpResult<'tcx, Option<Option<usize>>> {
+        // `rt::v1::Count` is `Is(usize)`, `Param(usize)` or `Implied`.
+        let (_, variant) = self.read_discriminant(&count.into())?;
+        Ok(match variant.as_u32() {
+            0 => {
+                let count = self.mplace_field(&self.mplace_downcast(count, variant)?, 0)?;
+                let count = self.read_scalar(&count.into())?.to_machine_usize(self)?;
+                usize::try_from(count).ok().map(Some)
+            }
+            1 => None,
+            _ => Some(None),
+        })
+    }
+}
@@ -10,7 +10,10 @@
 
 use rustc_ast::Mutability;
 use rustc_hir::def_id::DefId;
+use rustc_hir::CRATE_HIR_ID;
 use rustc_middle::mir::AssertMessage;
+use rustc_session::lint::builtin::LONG_RUNNING_CONST_EVAL;
+use rustc_session::lint::Level;
 use rustc_session::Limit;
 use rustc_span::symbol::{sym, Symbol};
 use rustc_target::abi::{Align, Size};
@@ -52,20 +55,31 @@ fn hook_special_const_fn(
         } else if Some(def_id) == self.tcx.lang_items().panic_display()
             || Some(def_id) == self.tcx.lang_items().begin_panic_fn()
         {
-            // &str or &&str
+            // &str or &&str, or a reference to another type that const checking lets through
             assert!(args.len() == 1);
 
-            let mut msg_place = self.deref_operand(&args[0])?;
-            while msg_place.layout.ty.is_ref() {
-                msg_place = self.deref_operand(&msg_place.into())?;
-            }
-
-            let msg = Symbol::intern(self.read_str(&msg_place)?);
+            let Some(msg) = self.format_panic_display(&args[0])? else {
+                span_bug!(
+                    self.cur_span(),
+                    "unsupported `panic!()` argument: {:?}",
+                    args[0].layout.ty
+                )
+            };
+            let msg = Symbol::intern(&msg);
             let span = self.find_closest_untracked_caller_location();
             let (file, line, col) = self.location_triple_for_span(span);
             return Err(ConstEvalErrKind::Panic { msg, file, line, col }.into());
         } else if Some(def_id) == self.tcx.lang_items().panic_fmt() {
-            // For panic_fmt, call const_panic_fmt instead.
+            assert!(args.len() == 1);
+
+            if let Some(msg) = self.format_panic_args(&args[0])? {
+                let msg = Symbol::intern(&msg);
+                let span = self.find_closest_untracked_caller_location();
+                let (file, line, col) = self.location_triple_for_span(span);
+                return Err(ConstEvalErrKind::Panic { msg, file, line, col }.into());
+            }
+            // Otherwise, call const_panic_fmt instead, which only supports messages without
+            // arguments.
             if let Some(const_panic_fmt) = self.tcx.lang_items().const_panic_fmt() {
                 return Ok(Some(
                     ty::Instance::resolve(
@@ -81,6 +95,33 @@ fn hook_special_const_fn(
         }
         Ok(None)
     }
+
+    /// Reports `long_running_const_eval` with the calls being evaluated. If the lint is denied,
+    /// the evaluation is stopped.
+    fn report_long_running(&self) -> InterpResult<'tcx> {
+        let stacktrace = self.generate_stacktrace();
+        let lint_root =
+            stacktrace.iter().rev().find_map(|frame| frame.lint_root).unwrap_or(CRATE_HIR_ID);
+        let steps = self.machine.steps_taken;
+        self.tcx.struct_span_lint_hir(LONG_RUNNING_CONST_EVAL, lint_root, self.tcx.span, |lint| {
+            let mut err = lint.build("constant evaluation is taking a long time");
+            label_stacktrace(&mut err, &stacktrace);
+            err.note(&format!(
+                "this lint is reported after {} steps, and again every time the number of steps \
+                 doubles; use `-Z const-eval-step-budget` to change the number of steps",
+                steps,
+            ));
+            err.emit();
+        });
+
+        let (level, _) = self.tcx.lint_level_at_node(LONG_RUNNING_CONST_EVAL, lint_root);
+        if matches!(level, Level::Deny | Level::Forbid) {
+            if let Some(reported) = self.tcx.sess.has_errors() {
+                throw_inval!(AlreadyReported(reported));
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Extra machine state for CTFE, and the Machine instance
@@ -91,6 +132,14 @@ pub struct CompileTimeInterpreter<'mir, 'tcx> {
     /// Setting this to `0` disables the limit and allows the interpreter to run forever.
     pub steps_remaining: usize,
 
+    /// The number of terminators evaluated so far.
+    pub(super) steps_taken: usize,
+
+    /// The number of steps after which `long_running_const_eval` is reported next.
+    ///
+    /// Setting this to `0` disables the lint.
+    pub(super) next_progress_report: usize,
+
     /// The virtual call stack.
     pub(crate) stack: Vec<Frame<'mir, 'tcx, AllocId, ()>>,
 
@@ -107,6 +156,8 @@ impl<'mir, 'tcx> CompileTimeInterpreter<'mir, 'tcx> {
     pub(super) fn new(const_eval_limit: Limit, can_access_statics: bool) -> Self {
         CompileTimeInterpreter {
             steps_remaining: const_eval_limit.0,
+            steps_taken: 0,
+            next_progress_report: 0,
             stack: Vec::new(),
             can_access_statics,
         }
@@ -236,6 +287,11 @@ impl<'mir, 'tcx> interpret::Machine<'mir, 'tcx> for CompileTimeInterpreter<'mir,
 
     const PANIC_ON_ALLOC_FAIL: bool = false; // will be raised as a proper error
 
+    #[inline(always)]
+    fn enforce_scalar_validity(ecx: &InterpCx<'mir, 'tcx, Self>) -> bool {
+        ecx.tcx.sess.opts.debugging_opts.extra_const_ub_checks
+    }
+
     fn load_mir(
         ecx: &InterpCx<'mir, 'tcx, Self>,
         instance: ty::InstanceDef<'tcx>,
@@ -433,6 +489,12 @@ fn binary_ptr_op(
     }
 
     fn before_terminator(ecx: &mut InterpCx<'mir, 'tcx, Self>) -> InterpResult<'tcx> {
+        ecx.machine.steps_taken += 1;
+        if ecx.machine.steps_taken == ecx.machine.next_progress_report {
+            ecx.machine.next_progress_report = ecx.machine.steps_taken.saturating_mul(2);
+            ecx.report_long_running()?;
+        }
+
         // The step limit has already been hit in a previous call to `before_terminator`.
         if ecx.machine.steps_remaining == 0 {
             return Ok(());
@@ -15,12 +15,14 @@
 
 mod error;
 mod eval_queries;
+mod fmt;
 mod fn_queries;
 mod machine;
 mod valtrees;
 
 pub use error::*;
 pub use eval_queries::*;
+pub(crate) use fmt::is_const_formattable;
 pub use fn_queries::*;
 pub use machine::*;
 pub(crate) use valtrees::{const_to_valtree_inner, valtree_to_const_value};
@@ -6,7 +6,7 @@
     MemoryKind, PlaceTy, Scalar, ScalarMaybeUninit,
 };
 use rustc_span::source_map::DUMMY_SP;
-use rustc_target::abi::{Align, VariantIdx};
+use rustc_target::abi::VariantIdx;
 
 use crate::interpret::MPlaceTy;
 use crate::interpret::Value;
@@ -67,6 +67,11 @@ fn slice_branches<'tcx>(
         elems.push(valtree);
     }
 
+    // Have to account for empty slices and strings here
+    if elems.is_empty() {
+        *num_nodes += 1;
+    }
+
     Ok(ty::ValTree::Branch(ecx.tcx.arena.alloc_from_iter(elems)))
 }
 
@@ -104,6 +109,9 @@ pub(crate) fn const_to_valtree_inner<'tcx>(
         // agree with runtime equality tests.
         ty::FnPtr(_) | ty::RawPtr(_) => Err(ValTreeCreationError::NonSupportedType),
 
+        // Mutable references cannot be shared between all uses of a type-level constant.
+        ty::Ref(_, _, ty::Mutability::Mut) => Err(ValTreeCreationError::NonSupportedType),
+
         ty::Ref(_, _, _)  => {
             let Ok(derefd_place)= ecx.deref_operand(&place.into()) else {
                 return Err(ValTreeCreationError::Other);
@@ -222,13 +230,17 @@ fn create_pointee_place<'tcx>(
             ty::Str => tcx.mk_ty(ty::Uint(ty::UintTy::U8)),
             _ => unsized_inner_ty,
         };
-        let unsized_inner_ty_size =
-            tcx.layout_of(ty::ParamEnv::empty().and(unsized_inner_ty)).unwrap().layout.size();
+        let unsized_inner_layout =
+            tcx.layout_of(ty::ParamEnv::empty().and(unsized_inner_ty)).unwrap();
+        let unsized_inner_ty_size = unsized_inner_layout.layout.size();
         debug!(?unsized_inner_ty, ?unsized_inner_ty_size, ?num_elems);
 
         // for custom DSTs only the last field/element is unsized, but we need to also allocate
         // space for the other fields/elements
         let layout = tcx.layout_of(ty::ParamEnv::empty().and(ty)).unwrap();
+        // The elements of the unsized tail may be more aligned than the sized part, e.g. for
+        // `[u16]` whose sized part is empty.
+        let align = layout.align.abi.max(unsized_inner_layout.align.abi);
         let size_of_sized_part = layout.layout.size();
 
         // Get the size of the memory behind the DST
@@ -237,7 +249,7 @@ fn create_pointee_place<'tcx>(
         let ptr = ecx
             .allocate_ptr(
                 size_of_sized_part.checked_add(dst_size, &tcx).unwrap(),
-                Align::from_bytes(1).unwrap(),
+                align,
                 MemoryKind::Stack,
             )
             .unwrap();
@@ -374,18 +386,9 @@ fn valtree_into_mplace<'tcx>(
             dump_place(ecx, pointee_place.into());
             intern_const_alloc_recursive(ecx, InternKind::Constant, &pointee_place).unwrap();
 
-            let imm = match inner_ty.kind() {
-                ty::Slice(_) | ty::Str => {
-                    let len = valtree.unwrap_branch().len();
-                    let len_scalar = ScalarMaybeUninit::Scalar(Scalar::from_u64(len as u64));
-
-                    Immediate::ScalarPair(
-                        ScalarMaybeUninit::from_maybe_pointer((*pointee_place).ptr, &tcx),
-                        len_scalar,
-                    )
-                }
-                _ => pointee_place.to_ref(&tcx),
-            };
+            // `create_pointee_place` already put the length of slices, strings and custom DSTs into
+            // the metadata of the pointee, so this also works for references to unsized types.
+            let imm = pointee_place.to_ref(&tcx);
             debug!(?imm);
 
             ecx.write_immediate(imm, &(*place).into()).unwrap();
@@ -18,8 +18,8 @@
 use rustc_mir_dataflow::storage::always_live_locals;
 use rustc_query_system::ich::StableHashingContext;
 use rustc_session::Limit;
-use rustc_span::{Pos, Span};
-use rustc_target::abi::{call::FnAbi, Align, HasDataLayout, Size, TargetDataLayout};
+use rustc_span::{Pos, Span, Symbol};
+use rustc_target::abi::{call::FnAbi, Abi, Align, HasDataLayout, Size, TargetDataLayout};
 
 use super::{
     AllocId, GlobalId, Immediate, InterpErrorInfo, InterpResult, MPlaceTy, Machine, MemPlace,
@@ -135,6 +135,9 @@ pub struct FrameInfo<'tcx> {
     pub instance: ty::Instance<'tcx>,
     pub span: Span,
     pub lint_root: Option<hir::HirId>,
+    /// The rendered values of the scalar arguments of this frame, if requested with
+    /// `-Zconst-eval-frame-args`.
+    pub args: Vec<(Symbol, String)>,
 }
 
 /// Unwind information.
@@ -284,6 +287,9 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                     lo.col.to_usize() + 1
                 )?;
             }
+            for (i, (name, value)) in self.args.iter().enumerate() {
+                write!(f, "{} `{} = {}`", if i == 0 { " with" } else { "," }, name, value)?;
+            }
             Ok(())
         })
     }
@@ -948,12 +954,50 @@ pub fn generate_stacktrace(&self) -> Vec<FrameInfo<'tcx>> {
                 }
             });
             let span = frame.current_span();
+            let args = if self.tcx.sess.opts.debugging_opts.const_eval_frame_args {
+                self.frame_args(frame)
+            } else {
+                Vec::new()
+            };
 
-            frames.push(FrameInfo { span, instance: frame.instance, lint_root });
+            frames.push(FrameInfo { span, instance: frame.instance, lint_root, args });
         }
         trace!("generate stacktrace: {:#?}", frames);
         frames
     }
+
+    /// Renders the named arguments of `frame` that are plain integers, floats, `bool`s or
+    /// `char`s. Pointers, anything larger, and values that cannot be read are left out.
+    fn frame_args(
+        &self,
+        frame: &Frame<'mir, 'tcx, M::PointerTag, M::FrameExtra>,
+    ) -> Vec<(Symbol, String)> {
+        frame
+            .body
+            .args_iter()
+            .filter_map(|local| {
+                let name = frame.body.var_debug_info.iter().find_map(|info| match &info.value {
+                    mir::VarDebugInfoContents::Place(place)
+                        if place.local == local && place.projection.is_empty() =>
+                    {
+                        Some(info.name)
+                    }
+                    _ => None,
+                })?;
+                let op = self.access_local(frame, local, None).ok()?;
+                if !matches!(op.layout.abi, Abi::Scalar(_)) {
+                    return None;
+                }
+                let imm = self.read_immediate_raw(&op, /*force*/ false).ok()?.ok()?;
+                match *imm {
+                    Immediate::Scalar(ScalarMaybeUninit::Scalar(Scalar::Int(_))) => {
+                        Some((name, imm.to_string()))
+                    }
+                    _ => None,
+                }
+            })
+            .collect()
+    }
 }
 
 #[doc(hidden)]
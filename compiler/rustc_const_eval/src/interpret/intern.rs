@@ -19,7 +19,7 @@
 use rustc_errors::ErrorGuaranteed;
 use rustc_hir as hir;
 use rustc_middle::mir::interpret::InterpResult;
-use rustc_middle::ty::{self, layout::TyAndLayout, Ty};
+use rustc_middle::ty::{self, layout::TyAndLayout, Ty, TyCtxtAt};
 
 use rustc_ast::Mutability;
 
@@ -135,6 +135,8 @@ fn intern_shallow<'rt, 'mir, 'tcx, M: CompileTimeMachine<'mir, 'tcx, const_eval:
     };
     // link the alloc id to the actual allocation
     leftover_allocations.extend(alloc.relocations().iter().map(|&(_, alloc_id)| alloc_id));
+    // Interned allocations are never written to, so large uniform ones can be stored compactly.
+    alloc.compress_bytes();
     let alloc = tcx.intern_const_alloc(alloc);
     tcx.set_alloc_id_memory(alloc_id, alloc);
     None
@@ -361,12 +363,22 @@ pub fn intern_const_alloc_recursive<
     let mut todo: Vec<_> = leftover_allocations.iter().cloned().collect();
     debug!(?todo);
     debug!("dead_alloc_map: {:#?}", ecx.memory.dead_alloc_map);
+    let mut heap_error = None;
     while let Some(alloc_id) = todo.pop() {
-        if let Some((_, mut alloc)) = ecx.memory.alloc_map.remove(&alloc_id) {
+        if let Some((kind, mut alloc)) = ecx.memory.alloc_map.remove(&alloc_id) {
             // We can't call the `intern_shallow` method here, as its logic is tailored to safe
             // references and a `leftover_allocations` set (where we only have a todo-list here).
             // So we hand-roll the interning logic here again.
             match intern_kind {
+                // Anything but a shared reference could be used to deallocate or reallocate a
+                // heap allocation at runtime, e.g. by dropping a `Box` or `Vec`, which would hand
+                // memory that was never allocated by the runtime allocator back to it.
+                _ if kind == MemoryKind::Machine(const_eval::MemoryKind::Heap) => {
+                    if heap_error.is_none() {
+                        heap_error = Some(report_heap_in_final_value(ecx.tcx, intern_kind));
+                    }
+                    alloc.mutability = Mutability::Not;
+                }
                 // Statics may contain mutable allocations even behind relocations.
                 // Even for immutable statics it would be ok to have mutable allocations behind
                 // raw pointers, e.g. for `static FOO: *const AtomicUsize = &AtomicUsize::new(42)`.
@@ -417,7 +429,26 @@ pub fn intern_const_alloc_recursive<
             span_bug!(ecx.tcx.span, "encountered unknown alloc id {:?}", alloc_id);
         }
     }
-    Ok(())
+    match heap_error {
+        Some(reported) => Err(reported),
+        None => Ok(()),
+    }
+}
+
+fn report_heap_in_final_value(tcx: TyCtxtAt<'_>, intern_kind: InternKind) -> ErrorGuaranteed {
+    let what = match intern_kind {
+        InternKind::Static(_) => "static",
+        InternKind::Constant | InternKind::Promoted => "constant",
+    };
+    let mut err = tcx.sess.struct_span_err(
+        tcx.span,
+        &format!("heap allocation in the final value of a {what} is not behind a shared reference"),
+    );
+    err.note(
+        "memory allocated by `const_allocate` cannot be deallocated or reallocated at runtime, \
+         so it may only remain reachable through shared references",
+    );
+    err.emit()
 }
 
 impl<'mir, 'tcx: 'mir, M: super::intern::CompileTimeMachine<'mir, 'tcx, !>>
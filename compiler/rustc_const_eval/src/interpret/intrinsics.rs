@@ -389,6 +389,9 @@ pub fn emulate_intrinsic(
 
             sym::transmute => {
                 self.copy_op_transmute(&args[0], dest)?;
+                if M::enforce_scalar_validity(self) {
+                    self.check_scalar_validity(&self.place_to_op(dest)?, /*transmute*/ true)?;
+                }
             }
             sym::assert_inhabited | sym::assert_zero_valid | sym::assert_uninit_valid => {
                 let ty = instance.substs.type_at(0);
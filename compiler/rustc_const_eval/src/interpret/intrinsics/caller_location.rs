@@ -5,6 +5,7 @@
 use rustc_middle::mir::TerminatorKind;
 use rustc_middle::ty::layout::LayoutOf;
 use rustc_middle::ty::subst::Subst;
+use rustc_session::config::RemapPathScopeComponents;
 use rustc_span::{Span, Symbol};
 
 use crate::interpret::{
@@ -116,8 +117,10 @@ pub(crate) fn alloc_caller_location(
     pub(crate) fn location_triple_for_span(&self, span: Span) -> (Symbol, u32, u32) {
         let topmost = span.ctxt().outer_expn().expansion_cause().unwrap_or(span);
         let caller = self.tcx.sess.source_map().lookup_char_pos(topmost.lo());
+        let display_pref =
+            self.tcx.sess.filename_display_preference(RemapPathScopeComponents::MACRO);
         (
-            Symbol::intern(&caller.file.name.prefer_remapped().to_string_lossy()),
+            Symbol::intern(&caller.file.name.display(display_pref).to_string_lossy()),
             u32::try_from(caller.line).unwrap(),
             u32::try_from(caller.col_display).unwrap().checked_add(1).unwrap(),
         )
@@ -139,6 +139,12 @@ pub trait Machine<'mir, 'tcx>: Sized {
     /// Whether to enforce integers and floats not having provenance.
     fn enforce_number_no_provenance(ecx: &InterpCx<'mir, 'tcx, Self>) -> bool;
 
+    /// Whether to check that `bool`, `char`, enum and `NonZero` values are valid as soon as they
+    /// are loaded or produced by a transmute, rather than only when validating a whole value.
+    fn enforce_scalar_validity(_ecx: &InterpCx<'mir, 'tcx, Self>) -> bool {
+        false
+    }
+
     /// Whether function calls should be [ABI](Abi)-checked.
     fn enforce_abi(_ecx: &InterpCx<'mir, 'tcx, Self>) -> bool {
         true
@@ -382,6 +382,7 @@ pub fn check_ptr_access_align(
                 }
                 CheckInAllocMsg::PointerArithmeticTest
                 | CheckInAllocMsg::OffsetFromTest
+                | CheckInAllocMsg::FnPtrCallTest
                 | CheckInAllocMsg::InboundsTest => AllocCheck::Live,
             };
             let (size, align) = self.get_alloc_size_and_align(alloc_id, check)?;
@@ -729,7 +730,11 @@ pub fn get_ptr_fn(
         ptr: Pointer<Option<M::PointerTag>>,
     ) -> InterpResult<'tcx, FnVal<'tcx, M::ExtraFnVal>> {
         trace!("get_fn({:?})", ptr);
-        let (alloc_id, offset, _tag) = self.ptr_get_alloc_id(ptr)?;
+        // A pointer without provenance cannot have been created from a fn item, so we have no
+        // way of knowing which function it is meant to call.
+        let (alloc_id, offset, _tag) = self
+            .ptr_try_get_alloc_id(ptr)
+            .map_err(|addr| err_ub!(DanglingIntPointer(addr, CheckInAllocMsg::FnPtrCallTest)))?;
         if offset.bytes() != 0 {
             throw_ub!(InvalidFunctionPointer(Pointer::new(alloc_id, offset)))
         }
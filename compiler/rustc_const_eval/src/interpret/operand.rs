@@ -591,7 +591,13 @@ pub fn eval_operand(
         use rustc_middle::mir::Operand::*;
         let op = match *mir_op {
             // FIXME: do some more logic on `move` to invalidate the old location
-            Copy(place) | Move(place) => self.eval_place_to_op(place, layout)?,
+            Copy(place) | Move(place) => {
+                let op = self.eval_place_to_op(place, layout)?;
+                if M::enforce_scalar_validity(self) {
+                    self.check_scalar_validity(&op, /*transmute*/ false)?;
+                }
+                op
+            }
 
             Constant(ref constant) => {
                 let val =
@@ -1,6 +1,6 @@
 use std::convert::TryFrom;
 
-use rustc_apfloat::Float;
+use rustc_apfloat::{Float, StatusAnd};
 use rustc_middle::mir;
 use rustc_middle::mir::interpret::{InterpResult, Scalar};
 use rustc_middle::ty::layout::{LayoutOf, TyAndLayout};
@@ -104,11 +104,11 @@ fn binary_float_op<F: Float + Into<Scalar<M::PointerTag>>>(
             Le => (Scalar::from_bool(l <= r), self.tcx.types.bool),
             Gt => (Scalar::from_bool(l > r), self.tcx.types.bool),
             Ge => (Scalar::from_bool(l >= r), self.tcx.types.bool),
-            Add => ((l + r).value.into(), ty),
-            Sub => ((l - r).value.into(), ty),
-            Mul => ((l * r).value.into(), ty),
-            Div => ((l / r).value.into(), ty),
-            Rem => ((l % r).value.into(), ty),
+            Add => (fixup_nan(l + r, l, r).into(), ty),
+            Sub => (fixup_nan(l - r, l, r).into(), ty),
+            Mul => (fixup_nan(l * r, l, r).into(), ty),
+            Div => (fixup_nan(l / r, l, r).into(), ty),
+            Rem => (fixup_nan(l % r, l, r).into(), ty),
             _ => span_bug!(self.cur_span(), "invalid float op: `{:?}`", bin_op),
         };
         (val, false, ty)
@@ -437,3 +437,18 @@ pub fn unary_op(
         Ok(ImmTy::from_scalar(val, self.layout_of(ty)?))
     }
 }
+
+/// Picks the NaN that a float operation on `l` and `r` returns, if any: the first NaN operand
+/// with its quiet bit set, or the default quiet NaN if neither operand is NaN. Hardware does not
+/// agree on this, so we define it ourselves to keep compile-time evaluation deterministic.
+fn fixup_nan<F: Float>(res: StatusAnd<F>, l: F, r: F) -> F {
+    let res = res.value;
+    if !res.is_nan() {
+        return res;
+    }
+    let quiet_bit = 1 << (F::PRECISION - 2);
+    match [l, r].into_iter().find(|f| f.is_nan()) {
+        Some(nan) => F::from_bits(nan.to_bits() | quiet_bit),
+        None => F::NAN,
+    }
+}
@@ -1022,4 +1022,116 @@ pub fn const_validate_operand(
     pub fn validate_operand(&self, op: &OpTy<'tcx, M::PointerTag>) -> InterpResult<'tcx> {
         self.validate_operand_internal(op, vec![], None, None)
     }
+
+    /// Checks the invariants of the `bool`, `char`, enum and `NonZero` parts of `op`, i.e. that
+    /// its scalars are in their valid range and that an enum has a valid tag, without validating
+    /// the value as a whole. This is used when `M::enforce_scalar_validity` is set, on each value
+    /// that is loaded and on the result of each transmute, so that an invalid value is reported
+    /// where it is created or first used.
+    pub fn check_scalar_validity(
+        &self,
+        op: &OpTy<'tcx, M::PointerTag>,
+        transmute: bool,
+    ) -> InterpResult<'tcx> {
+        // Report the offset of the offending bytes in the allocation, if the value is in memory.
+        let (alloc, base) = match op.try_as_mplace() {
+            Ok(mplace) if !mplace.layout.is_zst() => match self.ptr_try_get_alloc_id(mplace.ptr) {
+                Ok((alloc_id, offset, _)) => (Some(alloc_id), offset),
+                Err(_) => (None, Size::ZERO),
+            },
+            _ => (None, Size::ZERO),
+        };
+        let invalid = |offset: Size, msg: String| {
+            err_ub!(InvalidValue { alloc, offset: base + offset, transmute, msg })
+        };
+
+        match op.layout.abi {
+            // Only read the value if there is something to check, reading e.g. a `MaybeUninit`
+            // holding part of a pointer fails.
+            Abi::Scalar(scalar_layout)
+                if !scalar_layout.is_always_valid(self) || op.layout.ty.is_char() =>
+            {
+                let imm = self.read_immediate_raw(op, /*force*/ true)?.unwrap();
+                let is_char = op.layout.ty.is_char();
+                if let Some(msg) =
+                    self.check_scalar_in_range(imm.to_scalar_or_uninit(), scalar_layout, is_char)
+                {
+                    return Err(invalid(Size::ZERO, msg).into());
+                }
+            }
+            Abi::ScalarPair(a_layout, b_layout)
+                if !a_layout.is_always_valid(self) || !b_layout.is_always_valid(self) =>
+            {
+                let imm = self.read_immediate_raw(op, /*force*/ true)?.unwrap();
+                let (a, b) = imm.to_scalar_or_uninit_pair();
+                let b_offset = a_layout.size(self).align_to(b_layout.align(self).abi);
+                if let Some(msg) = self.check_scalar_in_range(a, a_layout, /*is_char*/ false) {
+                    return Err(invalid(Size::ZERO, msg).into());
+                }
+                if let Some(msg) = self.check_scalar_in_range(b, b_layout, /*is_char*/ false) {
+                    return Err(invalid(b_offset, msg).into());
+                }
+            }
+            _ => {}
+        }
+
+        let tag_field = match (op.layout.ty.kind(), &op.layout.variants) {
+            (ty::Adt(adt, _), Variants::Multiple { tag_field, .. }) if adt.is_enum() => *tag_field,
+            _ => return Ok(()),
+        };
+        let msg = match self.read_discriminant(op) {
+            Ok(_) => return Ok(()),
+            Err(err) => match err.kind() {
+                err_ub!(InvalidTag(val)) => {
+                    format!("encountered {:x}, but expected a valid enum tag", val)
+                }
+                err_ub!(InvalidUninitBytes(None)) => {
+                    "encountered uninitialized bytes, but expected a valid enum tag".to_owned()
+                }
+                _ => return Err(err),
+            },
+        };
+        Err(invalid(op.layout.fields.offset(tag_field), msg).into())
+    }
+
+    /// Returns why `value` is not valid for `scalar_layout`, if it is not. `char`s are checked
+    /// separately, as their valid range does not exclude the surrogates. Pointers are not checked,
+    /// as their address is not known here.
+    fn check_scalar_in_range(
+        &self,
+        value: ScalarMaybeUninit<M::PointerTag>,
+        scalar_layout: ScalarAbi,
+        is_char: bool,
+    ) -> Option<String> {
+        if scalar_layout.is_always_valid(self) && !is_char {
+            return None;
+        }
+        let valid_range = scalar_layout.valid_range(self);
+        let max_value = scalar_layout.size(self).unsigned_int_max();
+        let expected = if scalar_layout.is_bool() {
+            "a boolean".to_owned()
+        } else if is_char {
+            "a valid unicode scalar value (in `0..=0x10FFFF` but not in `0xD800..=0xDFFF`)"
+                .to_owned()
+        } else {
+            format!("something {}", wrapping_range_format(valid_range, max_value))
+        };
+        let ScalarMaybeUninit::Scalar(value) = value else {
+            return Some(format!("encountered uninitialized bytes, but expected {}", expected));
+        };
+        let Ok(int) = value.try_to_int() else {
+            return None;
+        };
+        let bits = int.assert_bits(int.size());
+        let valid = if is_char {
+            char::from_u32(bits as u32).is_some()
+        } else {
+            valid_range.contains(bits)
+        };
+        if valid {
+            None
+        } else {
+            Some(format!("encountered {:#x}, but expected {}", bits, expected))
+        }
+    }
 }
@@ -23,7 +23,7 @@
 use super::qualifs::{self, CustomEq, HasMutInterior, NeedsDrop, NeedsNonConstDrop};
 use super::resolver::FlowSensitiveAnalysis;
 use super::{ConstCx, Qualif};
-use crate::const_eval::is_unstable_const_fn;
+use crate::const_eval::{is_const_formattable, is_unstable_const_fn};
 
 type QualifResults<'mir, 'tcx, Q> =
     rustc_mir_dataflow::ResultsCursor<'mir, 'tcx, FlowSensitiveAnalysis<'mir, 'mir, 'tcx, Q>>;
@@ -551,10 +551,8 @@ fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: Location) {
 
             Rvalue::UnaryOp(_, ref operand) => {
                 let ty = operand.ty(self.body, self.tcx);
-                if is_int_bool_or_char(ty) {
-                    // Int, bool, and char operations are fine.
-                } else if ty.is_floating_point() {
-                    self.check_op(ops::FloatingPointOp);
+                if is_int_bool_float_or_char(ty) {
+                    // Int, bool, float, and char operations are fine.
                 } else {
                     span_bug!(self.span, "non-primitive type in `Rvalue::UnaryOp`: {:?}", ty);
                 }
@@ -565,8 +563,8 @@ fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: Location) {
                 let lhs_ty = lhs.ty(self.body, self.tcx);
                 let rhs_ty = rhs.ty(self.body, self.tcx);
 
-                if is_int_bool_or_char(lhs_ty) && is_int_bool_or_char(rhs_ty) {
-                    // Int, bool, and char operations are fine.
+                if is_int_bool_float_or_char(lhs_ty) && is_int_bool_float_or_char(rhs_ty) {
+                    // Int, bool, float, and char operations are fine.
                 } else if lhs_ty.is_fn_ptr() || lhs_ty.is_unsafe_ptr() {
                     assert_eq!(lhs_ty, rhs_ty);
                     assert!(
@@ -580,8 +578,6 @@ fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: Location) {
                     );
 
                     self.check_op(ops::RawPtrComparison);
-                } else if lhs_ty.is_floating_point() || rhs_ty.is_floating_point() {
-                    self.check_op(ops::FloatingPointOp);
                 } else {
                     span_bug!(
                         self.span,
@@ -837,17 +833,54 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                     }
                 }
 
-                // const-eval of the `panic_display` fn assumes the argument is `&&str`
+                // const-eval of the `panic_display` fn can only format a few builtin types, and
+                // anything but `&&str` is unstable
                 if Some(callee) == tcx.lang_items().panic_display() {
-                    match args[0].ty(&self.ccx.body.local_decls, tcx).kind() {
+                    match *args[0].ty(&self.ccx.body.local_decls, tcx).kind() {
                         ty::Ref(_, ty, _) if matches!(ty.kind(), ty::Ref(_, ty, _) if ty.is_str()) =>
                         {
                             return;
                         }
+                        ty::Ref(_, ty, _) if is_const_formattable(ty) => {
+                            self.check_op(ops::PanicFmtArgs);
+                            return;
+                        }
+                        ty::Ref(_, ty, _) => self.check_op(ops::PanicFmtArg(ty.peel_refs())),
                         _ => self.check_op(ops::PanicNonStr),
                     }
                 }
 
+                // The same goes for the arguments of `panic_fmt`, which are wrapped in
+                // `fmt::ArgumentV1`s before being passed to it. Const-eval also only supports
+                // some of the formatting traits, and no widths or precisions taken from another
+                // argument.
+                if let Some(impl_def_id) = tcx.impl_of_method(callee)
+                    && let Some(adt_def) = tcx.type_of(impl_def_id).ty_adt_def()
+                    && tcx.is_diagnostic_item(sym::ArgumentV1, adt_def.did())
+                {
+                    // Formatting arguments is unstable. `ArgumentV1::new` itself is only
+                    // called by the other constructors.
+                    let name = tcx.item_name(callee);
+                    if name != sym::new {
+                        self.check_op(ops::PanicFmtArgs);
+                    }
+                    match name {
+                        sym::new => {}
+                        sym::new_pointer => self.check_op(ops::PanicFmtTrait("Pointer")),
+                        sym::new_lower_exp => self.check_op(ops::PanicFmtTrait("LowerExp")),
+                        sym::new_upper_exp => self.check_op(ops::PanicFmtTrait("UpperExp")),
+                        sym::from_usize => {
+                            self.check_op(ops::PanicFmtCount);
+                            return;
+                        }
+                        _ => {
+                            if let Some(ty) = substs.types().next() && !is_const_formattable(ty) {
+                                self.check_op(ops::PanicFmtArg(ty.peel_refs()));
+                            }
+                        }
+                    }
+                }
+
                 if Some(callee) == tcx.lang_items().exchange_malloc_fn() {
                     self.check_op(ops::HeapAllocation);
                     return;
@@ -1019,8 +1052,8 @@ fn place_as_reborrow<'tcx>(
     }
 }
 
-fn is_int_bool_or_char(ty: Ty<'_>) -> bool {
-    ty.is_bool() || ty.is_integral() || ty.is_char()
+fn is_int_bool_float_or_char(ty: Ty<'_>) -> bool {
+    ty.is_bool() || ty.is_integral() || ty.is_floating_point() || ty.is_char()
 }
 
 fn emit_unstable_in_stable_error(ccx: &ConstCx<'_, '_>, span: Span, gate: Symbol) {
@@ -55,15 +55,12 @@ fn build_error(
     ) -> DiagnosticBuilder<'tcx, ErrorGuaranteed>;
 }
 
+/// A function call where the callee is a pointer.
 #[derive(Debug)]
-pub struct FloatingPointOp;
-impl<'tcx> NonConstOp<'tcx> for FloatingPointOp {
-    fn status_in_item(&self, ccx: &ConstCx<'_, 'tcx>) -> Status {
-        if ccx.const_kind() == hir::ConstContext::ConstFn {
-            Status::Unstable(sym::const_fn_floating_point_arithmetic)
-        } else {
-            Status::Allowed
-        }
+pub struct FnCallIndirect;
+impl<'tcx> NonConstOp<'tcx> for FnCallIndirect {
+    fn status_in_item(&self, _ccx: &ConstCx<'_, 'tcx>) -> Status {
+        Status::Unstable(sym::const_fn_ptr_calls)
     }
 
     fn build_error(
@@ -73,23 +70,7 @@ fn build_error(
     ) -> DiagnosticBuilder<'tcx, ErrorGuaranteed> {
         feature_err(
             &ccx.tcx.sess.parse_sess,
-            sym::const_fn_floating_point_arithmetic,
-            span,
-            &format!("floating point arithmetic is not allowed in {}s", ccx.const_kind()),
-        )
-    }
-}
-
-/// A function call where the callee is a pointer.
-#[derive(Debug)]
-pub struct FnCallIndirect;
-impl<'tcx> NonConstOp<'tcx> for FnCallIndirect {
-    fn build_error(
-        &self,
-        ccx: &ConstCx<'_, 'tcx>,
-        span: Span,
-    ) -> DiagnosticBuilder<'tcx, ErrorGuaranteed> {
-        ccx.tcx.sess.struct_span_err(
+            sym::const_fn_ptr_calls,
             span,
             &format!("function pointer calls are not allowed in {}s", ccx.const_kind()),
         )
@@ -637,6 +618,84 @@ fn build_error(
     }
 }
 
+/// Formatting arguments of a `panic!()` message, or any other use of `format_args!()`.
+#[derive(Debug)]
+pub struct PanicFmtArgs;
+impl<'tcx> NonConstOp<'tcx> for PanicFmtArgs {
+    fn status_in_item(&self, _: &ConstCx<'_, 'tcx>) -> Status {
+        Status::Unstable(sym::const_panic_fmt_args)
+    }
+
+    fn build_error(
+        &self,
+        ccx: &ConstCx<'_, 'tcx>,
+        span: Span,
+    ) -> DiagnosticBuilder<'tcx, ErrorGuaranteed> {
+        feature_err(
+            &ccx.tcx.sess.parse_sess,
+            sym::const_panic_fmt_args,
+            span,
+            &format!("formatting arguments is unstable in {}s", ccx.const_kind()),
+        )
+    }
+}
+
+/// An argument to a formatting `panic!()` whose type const-eval cannot format.
+#[derive(Debug)]
+pub struct PanicFmtArg<'tcx>(pub Ty<'tcx>);
+impl<'tcx> NonConstOp<'tcx> for PanicFmtArg<'tcx> {
+    fn build_error(
+        &self,
+        ccx: &ConstCx<'_, 'tcx>,
+        span: Span,
+    ) -> DiagnosticBuilder<'tcx, ErrorGuaranteed> {
+        let mut err = ccx.tcx.sess.struct_span_err(
+            span,
+            &format!("cannot format a value of type `{}` in a const context", self.0),
+        );
+        err.note("only integers, `bool`, `char` and `str` can be formatted in a const context");
+        err
+    }
+}
+
+/// An argument to a formatting `panic!()` using a formatting trait const-eval does not support.
+#[derive(Debug)]
+pub struct PanicFmtTrait(pub &'static str);
+impl<'tcx> NonConstOp<'tcx> for PanicFmtTrait {
+    fn build_error(
+        &self,
+        ccx: &ConstCx<'_, 'tcx>,
+        span: Span,
+    ) -> DiagnosticBuilder<'tcx, ErrorGuaranteed> {
+        let mut err = ccx.tcx.sess.struct_span_err(
+            span,
+            &format!("cannot format a value with `{}` in a const context", self.0),
+        );
+        err.note(
+            "only `Display`, `Debug`, `Octal`, `LowerHex`, `UpperHex` and `Binary` can be used \
+            in a const context",
+        );
+        err
+    }
+}
+
+/// A width or precision of a formatting `panic!()` taken from another argument, as in `{:1$}`
+/// or `{:.*}`.
+#[derive(Debug)]
+pub struct PanicFmtCount;
+impl<'tcx> NonConstOp<'tcx> for PanicFmtCount {
+    fn build_error(
+        &self,
+        ccx: &ConstCx<'_, 'tcx>,
+        span: Span,
+    ) -> DiagnosticBuilder<'tcx, ErrorGuaranteed> {
+        ccx.tcx.sess.struct_span_err(
+            span,
+            "widths and precisions taken from an argument cannot be used in a const context",
+        )
+    }
+}
+
 /// Comparing raw pointers for equality.
 /// Not currently intended to ever be allowed, even behind a feature gate: operation depends on
 /// allocation base addresses that are not known at compile-time.
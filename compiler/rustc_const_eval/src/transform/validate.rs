@@ -2,20 +2,22 @@
 
 use rustc_data_structures::fx::FxHashSet;
 use rustc_index::bit_set::BitSet;
+use rustc_index::vec::IndexVec;
 use rustc_infer::infer::TyCtxtInferExt;
 use rustc_middle::mir::interpret::Scalar;
 use rustc_middle::mir::visit::NonUseContext::VarDebugInfo;
-use rustc_middle::mir::visit::{PlaceContext, Visitor};
+use rustc_middle::mir::visit::{MutatingUseContext, NonMutatingUseContext, PlaceContext, Visitor};
 use rustc_middle::mir::{
-    traversal, AggregateKind, BasicBlock, BinOp, Body, BorrowKind, Local, Location, MirPass,
-    MirPhase, Operand, Place, PlaceElem, PlaceRef, ProjectionElem, Rvalue, SourceScope, Statement,
-    StatementKind, Terminator, TerminatorKind, UnOp, START_BLOCK,
+    traversal, AggregateKind, BasicBlock, BinOp, Body, BorrowKind, Local, LocalKind, Location,
+    MirPass, MirPhase, Operand, Place, PlaceElem, PlaceRef, ProjectionElem, Rvalue, SourceScope,
+    Statement, StatementKind, Terminator, TerminatorKind, UnOp, START_BLOCK,
 };
 use rustc_middle::ty::fold::BottomUpFolder;
 use rustc_middle::ty::{self, InstanceDef, ParamEnv, Ty, TyCtxt, TypeFoldable};
 use rustc_mir_dataflow::impls::MaybeStorageLive;
 use rustc_mir_dataflow::storage::always_live_locals;
 use rustc_mir_dataflow::{Analysis, ResultsCursor};
+use rustc_session::config::MirValidationLevel;
 use rustc_target::abi::{Size, VariantIdx};
 
 #[derive(Copy, Clone, Debug)]
@@ -33,6 +35,9 @@ pub struct Validator {
     /// invariants of the new dialect. A phase that changes dialects never checks the new invariants
     /// itself.
     pub mir_phase: MirPhase,
+    /// Whether to also check that locals assigned exactly once are assigned before they are used.
+    /// This is only done for `-Zvalidate-mir=full`, as it needs dominators for every body.
+    pub check_definitions: bool,
 }
 
 impl<'tcx> MirPass<'tcx> for Validator {
@@ -54,7 +59,7 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
             .iterate_to_fixpoint()
             .into_results_cursor(body);
 
-        TypeChecker {
+        let mut checker = TypeChecker {
             when: &self.when,
             body,
             tcx,
@@ -64,8 +69,53 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
             storage_liveness,
             place_cache: Vec::new(),
             value_cache: Vec::new(),
+        };
+        checker.visit_body(body);
+
+        // Before drop elaboration, drops and fake reads may refer to uninitialized locals.
+        if self.check_definitions && mir_phase >= MirPhase::DropsLowered {
+            checker.check_definitions_dominate_uses();
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct LocalDefUses {
+    /// The location of the last assignment to the whole local.
+    def: Option<Location>,
+    /// Whether the local is assigned more than once, or mutated in some other way.
+    not_ssa: bool,
+    uses: Vec<Location>,
+}
+
+struct DefUseCollector {
+    locals: IndexVec<Local, LocalDefUses>,
+}
+
+impl<'tcx> Visitor<'tcx> for DefUseCollector {
+    fn visit_local(&mut self, local: &Local, context: PlaceContext, location: Location) {
+        let info = &mut self.locals[*local];
+        match context {
+            PlaceContext::MutatingUse(
+                MutatingUseContext::Store
+                | MutatingUseContext::AsmOutput
+                | MutatingUseContext::Call
+                | MutatingUseContext::Yield,
+            ) => {
+                if info.def.replace(location).is_some() {
+                    info.not_ssa = true;
+                }
+            }
+            PlaceContext::MutatingUse(MutatingUseContext::Retag)
+            | PlaceContext::NonMutatingUse(
+                NonMutatingUseContext::Inspect
+                | NonMutatingUseContext::Copy
+                | NonMutatingUseContext::Move
+                | NonMutatingUseContext::Projection,
+            ) => info.uses.push(location),
+            PlaceContext::MutatingUse(_) | PlaceContext::NonMutatingUse(_) => info.not_ssa = true,
+            PlaceContext::NonUse(_) => {}
         }
-        .visit_body(body);
     }
 }
 
@@ -137,6 +187,43 @@ fn fail(&self, location: Location, msg: impl AsRef<str>) {
         );
     }
 
+    /// Checks that every local assigned exactly once is assigned before each of its uses, i.e.
+    /// that its assignment dominates all of its reachable uses. Locals that are assigned more than
+    /// once, mutated in place, borrowed or dropped are not checked, nor are arguments and
+    /// zero-sized locals, whose assignments may legitimately be removed.
+    fn check_definitions_dominate_uses(&self) {
+        let mut collector = DefUseCollector {
+            locals: IndexVec::from_elem(LocalDefUses::default(), &self.body.local_decls),
+        };
+        collector.visit_body(self.body);
+
+        let dominators = self.body.dominators();
+        for (local, info) in collector.locals.iter_enumerated() {
+            let Some(def) = info.def else { continue };
+            if info.not_ssa || self.body.local_kind(local) == LocalKind::Arg {
+                continue;
+            }
+            let ty = self.body.local_decls[local].ty;
+            if !matches!(self.tcx.layout_of(self.param_env.and(ty)), Ok(layout) if !layout.is_zst())
+            {
+                continue;
+            }
+            for &location in &info.uses {
+                if self.reachable_blocks.contains(location.block)
+                    && (location == def || !def.dominates(location, &dominators))
+                {
+                    self.fail(
+                        location,
+                        format!(
+                            "use of {:?} is not dominated by its only assignment at {:?}",
+                            local, def
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
     fn check_edge(&self, location: Location, bb: BasicBlock, edge_kind: EdgeKind) {
         if bb == START_BLOCK {
             self.fail(location, "start block must not have predecessors")
@@ -216,7 +303,8 @@ fn visit_local(&mut self, local: &Local, context: PlaceContext, location: Locati
 
     fn visit_operand(&mut self, operand: &Operand<'tcx>, location: Location) {
         // This check is somewhat expensive, so only run it when -Zvalidate-mir is passed.
-        if self.tcx.sess.opts.debugging_opts.validate_mir && self.mir_phase < MirPhase::DropsLowered
+        if self.tcx.sess.opts.debugging_opts.validate_mir != MirValidationLevel::Off
+            && self.mir_phase < MirPhase::DropsLowered
         {
             // `Operand::Copy` is only supposed to be used with `Copy` types.
             if let Operand::Copy(place) = operand {
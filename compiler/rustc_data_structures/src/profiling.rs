@@ -85,9 +85,11 @@
 use crate::fx::FxHashMap;
 
 use std::borrow::Borrow;
+use std::cell::Cell;
 use std::collections::hash_map::Entry;
 use std::convert::Into;
 use std::error::Error;
+use std::fmt::Write;
 use std::fs;
 use std::path::Path;
 use std::process;
@@ -96,7 +98,7 @@
 
 pub use measureme::EventId;
 use measureme::{EventIdBuilder, Profiler, SerializableString, StringId};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use smallvec::SmallVec;
 
 bitflags::bitflags! {
@@ -163,6 +165,10 @@ pub struct SelfProfilerRef {
 
     // Print extra verbose generic activities to stdout
     print_extra_verbose_generic_activities: bool,
+
+    // Record verbose generic activities in a tree instead of printing them, for
+    // `-Ztime-passes-format=json`
+    time_passes_tree: Option<Arc<TimePassesTree>>,
 }
 
 impl SelfProfilerRef {
@@ -170,17 +176,24 @@ pub fn new(
         profiler: Option<Arc<SelfProfiler>>,
         print_verbose_generic_activities: bool,
         print_extra_verbose_generic_activities: bool,
+        time_passes_format: TimePassesFormat,
     ) -> SelfProfilerRef {
         // If there is no SelfProfiler then the filter mask is set to NONE,
         // ensuring that nothing ever tries to actually access it.
         let event_filter_mask =
             profiler.as_ref().map_or(EventFilter::empty(), |p| p.event_filter_mask);
 
+        let time_passes_tree = (print_verbose_generic_activities
+            && time_passes_format == TimePassesFormat::Json)
+            .then(|| Arc::new(TimePassesTree::new(print_extra_verbose_generic_activities)));
+        let print = time_passes_tree.is_none();
+
         SelfProfilerRef {
             profiler,
             event_filter_mask,
-            print_verbose_generic_activities,
-            print_extra_verbose_generic_activities,
+            print_verbose_generic_activities: print && print_verbose_generic_activities,
+            print_extra_verbose_generic_activities: print && print_extra_verbose_generic_activities,
+            time_passes_tree,
         }
     }
 
@@ -220,8 +233,9 @@ pub fn verbose_generic_activity<'a>(
     ) -> VerboseTimingGuard<'a> {
         let message =
             if self.print_verbose_generic_activities { Some(event_label.to_owned()) } else { None };
+        let pass = self.time_passes_tree.as_ref().map(|tree| tree.start_pass(event_label, None));
 
-        VerboseTimingGuard::start(message, self.generic_activity(event_label))
+        VerboseTimingGuard::start(message, pass, self.generic_activity(event_label))
     }
 
     /// Start profiling an extra verbose generic activity. Profiling continues until the
@@ -241,8 +255,34 @@ pub fn extra_verbose_generic_activity<'a, A>(
         } else {
             None
         };
+        let pass = match &self.time_passes_tree {
+            Some(tree) if tree.extra_verbose => {
+                Some(tree.start_pass(event_label, Some(event_arg.borrow().to_owned())))
+            }
+            _ => None,
+        };
 
-        VerboseTimingGuard::start(message, self.generic_activity_with_arg(event_label, event_arg))
+        VerboseTimingGuard::start(
+            message,
+            pass,
+            self.generic_activity_with_arg(event_label, event_arg),
+        )
+    }
+
+    /// Reports a pass that was timed by the caller, like `print_time_passes_entry`. With
+    /// `-Ztime-passes-format=json`, the pass is recorded in the tree of passes instead, as
+    /// part of the pass that is currently running on this thread.
+    pub fn time_passes_entry(
+        &self,
+        event_label: &'static str,
+        dur: Duration,
+        start_rss: Option<usize>,
+        end_rss: Option<usize>,
+    ) {
+        match &self.time_passes_tree {
+            Some(tree) => tree.add_pass(event_label, dur, start_rss, end_rss),
+            None => print_time_passes_entry(event_label, dur, start_rss, end_rss),
+        }
     }
 
     /// Start profiling a generic activity. Profiling continues until the
@@ -715,13 +755,19 @@ pub fn run<R>(self, f: impl FnOnce() -> R) -> R {
 #[must_use]
 pub struct VerboseTimingGuard<'a> {
     start_and_message: Option<(Instant, Option<usize>, String)>,
+    _pass: Option<TimedPass<'a>>,
     _guard: TimingGuard<'a>,
 }
 
 impl<'a> VerboseTimingGuard<'a> {
-    pub fn start(message: Option<String>, _guard: TimingGuard<'a>) -> Self {
+    pub fn start(
+        message: Option<String>,
+        _pass: Option<TimedPass<'a>>,
+        _guard: TimingGuard<'a>,
+    ) -> Self {
         VerboseTimingGuard {
             _guard,
+            _pass,
             start_and_message: message.map(|msg| (Instant::now(), get_resident_set_size(), msg)),
         }
     }
@@ -789,6 +835,195 @@ pub fn print_time_passes_entry(
     eprintln!("time: {:>7}{}\t{}", duration_to_secs_str(dur), mem_string, what);
 }
 
+/// The format of the `-Ztime-passes` output.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum TimePassesFormat {
+    /// A line for every pass, printed when the pass ends.
+    Text,
+    /// A single JSON document printed at the end of the compilation session, in which
+    /// passes are nested in the pass they ran in.
+    Json,
+}
+
+/// The version of the `-Ztime-passes-format=json` output. Bump this whenever the
+/// meaning of an existing field changes.
+const TIME_PASSES_JSON_VERSION: u32 = 1;
+
+thread_local! {
+    /// The index of the innermost pass that is running on this thread, for
+    /// `-Ztime-passes-format=json`.
+    static CURRENT_PASS: Cell<Option<usize>> = Cell::new(None);
+}
+
+struct PassNode {
+    label: &'static str,
+    arg: Option<String>,
+    parent: Option<usize>,
+    start: Instant,
+    /// `None` while the pass is still running.
+    duration: Option<Duration>,
+    start_rss: Option<usize>,
+    end_rss: Option<usize>,
+}
+
+/// The passes of a compilation session as recorded for `-Ztime-passes-format=json`. The
+/// first pass is the whole session, every other pass is part of the pass that was running
+/// on the same thread when it started, or of the session for passes that run on threads of
+/// their own, like the optimization of a codegen unit. The tree is printed when it is
+/// dropped together with the session.
+struct TimePassesTree {
+    /// Whether extra verbose activities, like the ones for every codegen unit, are recorded.
+    extra_verbose: bool,
+    passes: Mutex<Vec<PassNode>>,
+}
+
+impl TimePassesTree {
+    fn new(extra_verbose: bool) -> TimePassesTree {
+        let session = PassNode {
+            label: "total",
+            arg: None,
+            parent: None,
+            start: Instant::now(),
+            duration: None,
+            start_rss: get_resident_set_size(),
+            end_rss: None,
+        };
+        TimePassesTree { extra_verbose, passes: Mutex::new(vec![session]) }
+    }
+
+    fn push_pass(&self, mut pass: PassNode) -> usize {
+        pass.parent = Some(CURRENT_PASS.with(|current| current.get()).unwrap_or(0));
+        let mut passes = self.passes.lock();
+        passes.push(pass);
+        passes.len() - 1
+    }
+
+    fn start_pass(&self, label: &'static str, arg: Option<String>) -> TimedPass<'_> {
+        let index = self.push_pass(PassNode {
+            label,
+            arg,
+            parent: None,
+            start: Instant::now(),
+            duration: None,
+            start_rss: get_resident_set_size(),
+            end_rss: None,
+        });
+        let parent = CURRENT_PASS.with(|current| current.replace(Some(index)));
+        TimedPass { tree: self, index, parent }
+    }
+
+    fn add_pass(
+        &self,
+        label: &'static str,
+        dur: Duration,
+        start_rss: Option<usize>,
+        end_rss: Option<usize>,
+    ) {
+        let now = Instant::now();
+        self.push_pass(PassNode {
+            label,
+            arg: None,
+            parent: None,
+            start: now.checked_sub(dur).unwrap_or(now),
+            duration: Some(dur),
+            start_rss,
+            end_rss,
+        });
+    }
+}
+
+impl Drop for TimePassesTree {
+    fn drop(&mut self) {
+        let end_rss = get_resident_set_size();
+        let passes = self.passes.get_mut();
+        passes[0].duration = Some(passes[0].start.elapsed());
+        passes[0].end_rss = end_rss;
+
+        let mut children = vec![vec![]; passes.len()];
+        for (index, pass) in passes.iter().enumerate() {
+            if let Some(parent) = pass.parent {
+                children[parent].push(index);
+            }
+        }
+
+        let mut json = String::new();
+        write!(json, "{{\"version\":{},\"passes\":[", TIME_PASSES_JSON_VERSION).unwrap();
+        write_pass_json(&mut json, passes, &children, 0);
+        json.push_str("]}");
+        eprintln!("{}", json);
+    }
+}
+
+fn write_pass_json(json: &mut String, passes: &[PassNode], children: &[Vec<usize>], index: usize) {
+    let pass = &passes[index];
+    let session_start = passes[0].start;
+    json.push_str("{\"name\":");
+    write_json_string(json, pass.label);
+    if let Some(arg) = &pass.arg {
+        json.push_str(",\"arg\":");
+        write_json_string(json, arg);
+    }
+    // Passes that are still running when the session ends are cut off at its end.
+    let duration = pass.duration.unwrap_or_else(|| pass.start.elapsed());
+    write!(
+        json,
+        ",\"start\":{:.6},\"duration\":{:.6}",
+        pass.start.saturating_duration_since(session_start).as_secs_f64(),
+        duration.as_secs_f64()
+    )
+    .unwrap();
+    if let Some(start_rss) = pass.start_rss {
+        write!(json, ",\"rss_start\":{}", start_rss).unwrap();
+    }
+    if let Some(end_rss) = pass.end_rss {
+        write!(json, ",\"rss_end\":{}", end_rss).unwrap();
+    }
+    if let (Some(start_rss), Some(end_rss)) = (pass.start_rss, pass.end_rss) {
+        write!(json, ",\"rss_delta\":{}", end_rss as i128 - start_rss as i128).unwrap();
+    }
+    json.push_str(",\"children\":[");
+    for (i, &child) in children[index].iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        write_pass_json(json, passes, children, child);
+    }
+    json.push_str("]}");
+}
+
+fn write_json_string(json: &mut String, s: &str) {
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+/// A pass that is recorded for `-Ztime-passes-format=json`. The pass ends when this is
+/// dropped.
+pub struct TimedPass<'a> {
+    tree: &'a TimePassesTree,
+    index: usize,
+    /// The pass that was running on this thread when this one started.
+    parent: Option<usize>,
+}
+
+impl Drop for TimedPass<'_> {
+    fn drop(&mut self) {
+        let end_rss = get_resident_set_size();
+        let mut passes = self.tree.passes.lock();
+        let pass = &mut passes[self.index];
+        pass.duration = Some(pass.start.elapsed());
+        pass.end_rss = end_rss;
+        CURRENT_PASS.with(|current| current.set(self.parent));
+    }
+}
+
 // Hack up our own formatting for the duration to make it easier for scripts
 // to parse (always use the same number of decimal places and the same unit).
 pub fn duration_to_secs_str(dur: std::time::Duration) -> String {
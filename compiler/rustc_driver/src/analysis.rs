@@ -0,0 +1,143 @@
+//! A typed view of a crate after analysis, for tools built on top of the compiler.
+//!
+//! [`Callbacks::after_analysis`](crate::Callbacks::after_analysis) hands out the raw
+//! [`Queries`](rustc_interface::Queries), which change whenever the compiler's pipeline does.
+//! [`Callbacks::after_typed_analysis`](crate::Callbacks::after_typed_analysis) instead
+//! gets an [`AnalysisContext`], which only exposes the things that tools like clippy commonly
+//! need, under names and signatures that are kept stable across refactorings of the queries
+//! behind them. The [`TyCtxt`] is still available for everything else, with the usual lack of
+//! guarantees.
+
+use rustc_errors::{DiagnosticBuilder, ErrorGuaranteed, MultiSpan};
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{CrateNum, DefId, LocalDefId, LOCAL_CRATE};
+use rustc_hir::HirId;
+use rustc_middle::lint::LintDiagnosticBuilder;
+use rustc_middle::mir::Body;
+use rustc_middle::ty::layout::{LayoutError, TyAndLayout};
+use rustc_middle::ty::{ParamEnv, Ty, TyCtxt, TypeckResults};
+use rustc_session::lint::Lint;
+use rustc_span::Symbol;
+
+/// A crate of the crate graph of the analyzed crate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CrateInfo {
+    pub cnum: CrateNum,
+    pub name: Symbol,
+    /// Whether this is the crate being compiled.
+    pub is_local: bool,
+}
+
+/// The analyzed crate, passed to [`Callbacks::after_typed_analysis`].
+///
+/// Analysis has succeeded when this is handed out, so the queries below don't report errors
+/// of their own for the crate being compiled.
+///
+/// [`Callbacks::after_typed_analysis`]: crate::Callbacks::after_typed_analysis
+#[derive(Clone, Copy)]
+pub struct AnalysisContext<'tcx> {
+    tcx: TyCtxt<'tcx>,
+}
+
+impl<'tcx> AnalysisContext<'tcx> {
+    pub(crate) fn new(tcx: TyCtxt<'tcx>) -> Self {
+        AnalysisContext { tcx }
+    }
+
+    /// The type context, for everything that isn't covered by the methods below. Unlike
+    /// them, the queries of the type context may change from one release to the next.
+    pub fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    /// The name of the crate being compiled.
+    pub fn crate_name(&self) -> Symbol {
+        self.tcx.crate_name(LOCAL_CRATE)
+    }
+
+    /// All crates of the crate graph, starting with the crate being compiled.
+    pub fn crates(&self) -> impl Iterator<Item = CrateInfo> + 'tcx {
+        let tcx = self.tcx;
+        std::iter::once(LOCAL_CRATE).chain(tcx.crates(()).iter().copied()).map(move |cnum| {
+            CrateInfo { cnum, name: tcx.crate_name(cnum), is_local: cnum == LOCAL_CRATE }
+        })
+    }
+
+    /// Finds a crate of the crate graph by name.
+    pub fn find_crate(&self, name: Symbol) -> Option<CrateInfo> {
+        self.crates().find(|krate| krate.name == name)
+    }
+
+    /// The items of the crate being compiled that have a body, like functions, closures and
+    /// constants.
+    pub fn body_owners(&self) -> impl Iterator<Item = LocalDefId> + 'tcx {
+        self.tcx.hir().body_owners()
+    }
+
+    /// The path of `def_id` as it would be printed in a diagnostic.
+    pub fn def_path_str(&self, def_id: DefId) -> String {
+        self.tcx.def_path_str(def_id)
+    }
+
+    /// The types the type checker computed for the body of `def_id`, which has to be one of the
+    /// [`body_owners`](Self::body_owners).
+    pub fn typeck_results(&self, def_id: LocalDefId) -> &'tcx TypeckResults<'tcx> {
+        self.tcx.typeck(def_id)
+    }
+
+    /// The optimized MIR of the function or closure `def_id`, which can be in any crate of the
+    /// crate graph. Returns `None` for other items, and for functions whose MIR isn't
+    /// available, like most non-generic functions of other crates.
+    pub fn optimized_mir(&self, def_id: DefId) -> Option<&'tcx Body<'tcx>> {
+        let is_fn = matches!(
+            self.tcx.def_kind(def_id),
+            DefKind::Fn | DefKind::AssocFn | DefKind::Closure | DefKind::Generator
+        );
+        if is_fn && self.tcx.is_mir_available(def_id) {
+            Some(self.tcx.optimized_mir(def_id))
+        } else {
+            None
+        }
+    }
+
+    /// The layout of `ty` in `param_env`, or why it can't be computed, for example because
+    /// `ty` is too big or depends on generic parameters.
+    pub fn layout_of(
+        &self,
+        param_env: ParamEnv<'tcx>,
+        ty: Ty<'tcx>,
+    ) -> Result<TyAndLayout<'tcx>, LayoutError<'tcx>> {
+        self.tcx.layout_of(param_env.and(ty))
+    }
+
+    /// Creates an error. Like all diagnostics, it has to be emitted with `emit`.
+    pub fn struct_span_err(
+        &self,
+        span: impl Into<MultiSpan>,
+        msg: &str,
+    ) -> DiagnosticBuilder<'tcx, ErrorGuaranteed> {
+        self.tcx.sess.struct_span_err(span, msg)
+    }
+
+    /// Creates a warning. Like all diagnostics, it has to be emitted with `emit`.
+    pub fn struct_span_warn(
+        &self,
+        span: impl Into<MultiSpan>,
+        msg: &str,
+    ) -> DiagnosticBuilder<'tcx, ()> {
+        self.tcx.sess.struct_span_warn(span, msg)
+    }
+
+    /// Emits `lint` at `span`, at the level set for it at `hir_id` with attributes like
+    /// `#[allow]`, which is also where `--cap-lints` and the command line flags apply. The
+    /// lint is not emitted if it is allowed there.
+    pub fn emit_lint(
+        &self,
+        lint: &'static Lint,
+        hir_id: HirId,
+        span: impl Into<MultiSpan>,
+        decorate: impl for<'a> FnOnce(LintDiagnosticBuilder<'a, ()>),
+    ) {
+        self.tcx.struct_span_lint_hir(lint, hir_id, span, decorate)
+    }
+}
@@ -0,0 +1,82 @@
+//! Rendering of the error code explanations printed by `rustc --explain`.
+
+use rustc_span::edition::Edition;
+use std::io::{self, Write};
+use termcolor::{Buffer, Color, ColorSpec, WriteColor};
+
+/// Renders the markdown explanation of an error code for the terminal.
+///
+/// The doctest attributes of code blocks and the lines hidden from the documentation (starting
+/// with `# `) are removed. Examples written for another edition than `edition`, the one rustc
+/// was invoked with, start with a comment saying which edition they need. With `color`,
+/// headings, code blocks and inline code are highlighted instead of printed as markdown.
+pub fn render(description: &str, edition: Edition, color: bool) -> String {
+    let mut buf = if color { Buffer::ansi() } else { Buffer::no_color() };
+    render_to(&mut buf, description, edition, color).expect("writing to a buffer cannot fail");
+    String::from_utf8_lossy(buf.as_slice()).into_owned()
+}
+
+fn render_to(
+    buf: &mut Buffer,
+    description: &str,
+    edition: Edition,
+    color: bool,
+) -> io::Result<()> {
+    let mut code_spec = ColorSpec::new();
+    code_spec.set_fg(Some(Color::Cyan));
+    let mut heading_spec = ColorSpec::new();
+    heading_spec.set_bold(true).set_underline(true);
+
+    let mut is_in_code_block = false;
+    for line in description.lines() {
+        let indent_level = line.find(|c: char| !c.is_whitespace()).unwrap_or_else(|| line.len());
+        let (indent, dedented_line) = line.split_at(indent_level);
+        if let Some(attrs) = dedented_line.strip_prefix("```") {
+            is_in_code_block = !is_in_code_block;
+            if !color {
+                writeln!(buf, "{}```", indent)?;
+            }
+            if !is_in_code_block {
+                continue;
+            }
+            let example_edition = attrs
+                .split(',')
+                .filter_map(|attr| attr.trim().strip_prefix("edition")?.parse::<Edition>().ok())
+                .last();
+            if let Some(example_edition) = example_edition.filter(|e| *e != edition) {
+                buf.set_color(&code_spec)?;
+                write!(buf, "{}// this example needs `--edition {}`", indent, example_edition)?;
+                buf.reset()?;
+                writeln!(buf)?;
+            }
+        } else if is_in_code_block {
+            if dedented_line.starts_with("# ") {
+                continue;
+            }
+            buf.set_color(&code_spec)?;
+            write!(buf, "{}", line)?;
+            buf.reset()?;
+            writeln!(buf)?;
+        } else if color && dedented_line.starts_with('#') {
+            buf.set_color(&heading_spec)?;
+            write!(buf, "{}{}", indent, dedented_line.trim_start_matches('#').trim_start())?;
+            buf.reset()?;
+            writeln!(buf)?;
+        } else if color {
+            // Highlight the inline code between backticks.
+            for (i, part) in line.split('`').enumerate() {
+                if i % 2 == 1 {
+                    buf.set_color(&code_spec)?;
+                    write!(buf, "{}", part)?;
+                    buf.reset()?;
+                } else {
+                    write!(buf, "{}", part)?;
+                }
+            }
+            writeln!(buf)?;
+        } else {
+            writeln!(buf, "{}", line)?;
+        }
+    }
+    Ok(())
+}
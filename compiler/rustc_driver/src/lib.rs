@@ -15,12 +15,15 @@
 
 pub extern crate rustc_plugin_impl as plugin;
 
+use analysis::AnalysisContext;
 use rustc_ast as ast;
 use rustc_codegen_ssa::{traits::CodegenBackend, CodegenResults};
-use rustc_data_structures::profiling::{get_resident_set_size, print_time_passes_entry};
+use rustc_data_structures::profiling::{
+    get_resident_set_size, print_time_passes_entry, TimePassesFormat,
+};
 use rustc_data_structures::sync::SeqCst;
 use rustc_errors::registry::{InvalidErrorCode, Registry};
-use rustc_errors::{ErrorGuaranteed, PResult};
+use rustc_errors::{ColorConfig, ErrorGuaranteed, PResult};
 use rustc_feature::find_gated_cfg;
 use rustc_interface::util::{self, collect_crate_types, get_codegen_backend};
 use rustc_interface::{interface, Queries};
@@ -36,12 +39,15 @@
 use rustc_session::lint::{Lint, LintId};
 use rustc_session::{config, DiagnosticOutput, Session};
 use rustc_session::{early_error, early_error_no_abort, early_warn};
+use rustc_span::edition::Edition;
 use rustc_span::source_map::{FileLoader, FileName};
 use rustc_span::symbol::sym;
 use rustc_target::json::ToJson;
+use rustc_target::spec::{Target, TargetTriple};
 
 use std::borrow::Cow;
 use std::cmp::max;
+use std::collections::BTreeMap;
 use std::default::Default;
 use std::env;
 use std::ffi::OsString;
@@ -49,13 +55,16 @@
 use std::io::{self, Read, Write};
 use std::lazy::SyncLazy;
 use std::panic::{self, catch_unwind};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{self, Command, Stdio};
 use std::str;
 use std::time::Instant;
 
+pub mod analysis;
 pub mod args;
+mod explain;
 pub mod pretty;
+mod sysroot;
 
 /// Exit status code used for successful compilation and help output.
 pub const EXIT_SUCCESS: i32 = 0;
@@ -112,6 +121,13 @@ fn after_analysis<'tcx>(
     ) -> Compilation {
         Compilation::Continue
     }
+    /// Called after a successful analysis, right before `after_analysis`, with a view of the
+    /// analyzed crate that is kept stable across changes to the compiler's queries. Tools
+    /// should prefer this over `after_analysis`. Return value instructs the compiler whether
+    /// to continue the compilation afterwards (defaults to `Compilation::Continue`)
+    fn after_typed_analysis<'tcx>(&mut self, _cx: &AnalysisContext<'tcx>) -> Compilation {
+        Compilation::Continue
+    }
 }
 
 #[derive(Default)]
@@ -123,8 +139,10 @@ impl Callbacks for TimePassesCallbacks {
     fn config(&mut self, config: &mut interface::Config) {
         // If a --prints=... option has been given, we don't print the "total"
         // time because it will mess up the --prints output. See #64339.
+        // With `-Ztime-passes-format=json`, the total is part of the JSON output.
         self.time_passes = config.opts.prints.is_empty()
-            && (config.opts.debugging_opts.time_passes || config.opts.debugging_opts.time);
+            && (config.opts.debugging_opts.time_passes || config.opts.debugging_opts.time)
+            && config.opts.debugging_opts.time_passes_format == TimePassesFormat::Text;
         config.opts.trimmed_def_paths = TrimmedDefPaths::GoodPath;
     }
 }
@@ -208,7 +226,7 @@ fn run_compiler(
     let sopts = config::build_session_options(&matches);
 
     if let Some(ref code) = matches.opt_str("explain") {
-        handle_explain(diagnostics_registry(), code, sopts.error_format);
+        handle_explain(diagnostics_registry(), code, sopts.error_format, sopts.edition);
         return Ok(());
     }
 
@@ -233,6 +251,21 @@ fn run_compiler(
         registry: diagnostics_registry(),
     };
 
+    if let Some(sysroot_dir) = matches.opt_str("assemble-sysroot") {
+        callbacks.config(&mut config);
+        return interface::run_compiler(config, |compiler| {
+            let sess = compiler.session();
+            let metadata_loader = compiler.codegen_backend().metadata_loader();
+            sysroot::assemble_sysroot(
+                sess,
+                &*metadata_loader,
+                Path::new(&sysroot_dir),
+                &matches.free,
+            );
+            sess.compile_status()
+        });
+    }
+
     match make_input(config.opts.error_format, &matches.free) {
         Err(reported) => return Err(reported),
         Ok(Some((input, input_file_path))) => {
@@ -395,6 +428,14 @@ fn run_compiler(
                 result
             })?;
 
+            let compilation = queries
+                .global_ctxt()?
+                .peek_mut()
+                .enter(|tcx| callbacks.after_typed_analysis(&AnalysisContext::new(tcx)));
+            if compilation == Compilation::Stop {
+                return early_exit();
+            }
+
             if callbacks.after_analysis(compiler, queries) == Compilation::Stop {
                 return early_exit();
             }
@@ -405,6 +446,10 @@ fn run_compiler(
                 sess.code_stats.print_type_sizes();
             }
 
+            if sess.opts.debugging_opts.enum_layout_report {
+                sess.code_stats.print_enum_layout_report();
+            }
+
             let linker = queries.linker()?;
             Ok(Some(linker))
         })?;
@@ -503,7 +548,7 @@ pub fn and_then<F: FnOnce() -> Compilation>(self, next: F) -> Compilation {
     }
 }
 
-fn handle_explain(registry: Registry, code: &str, output: ErrorOutputType) {
+fn handle_explain(registry: Registry, code: &str, output: ErrorOutputType, edition: Edition) {
     let upper_cased_code = code.to_ascii_uppercase();
     let normalised = if upper_cased_code.starts_with('E') {
         upper_cased_code
@@ -512,25 +557,17 @@ fn handle_explain(registry: Registry, code: &str, output: ErrorOutputType) {
     };
     match registry.try_find_description(&normalised) {
         Ok(Some(description)) => {
-            let mut is_in_code_block = false;
-            let mut text = String::new();
-            // Slice off the leading newline and print.
-            for line in description.lines() {
-                let indent_level =
-                    line.find(|c: char| !c.is_whitespace()).unwrap_or_else(|| line.len());
-                let dedented_line = &line[indent_level..];
-                if dedented_line.starts_with("```") {
-                    is_in_code_block = !is_in_code_block;
-                    text.push_str(&line[..(indent_level + 3)]);
-                } else if is_in_code_block && dedented_line.starts_with("# ") {
-                    continue;
-                } else {
-                    text.push_str(line);
-                }
-                text.push('\n');
-            }
+            let color = match output {
+                ErrorOutputType::HumanReadable(kind) => match kind.unzip().1 {
+                    ColorConfig::Always => true,
+                    ColorConfig::Auto => stdout_isatty(),
+                    ColorConfig::Never => false,
+                },
+                _ => false,
+            };
+            let text = explain::render(description, edition, color);
             if stdout_isatty() {
-                show_content_with_pager(&text);
+                show_content_with_pager(&text, color);
             } else {
                 print!("{}", text);
             }
@@ -544,14 +581,19 @@ fn handle_explain(registry: Registry, code: &str, output: ErrorOutputType) {
     }
 }
 
-fn show_content_with_pager(content: &str) {
+fn show_content_with_pager(content: &str, color: bool) {
     let pager_name = env::var_os("PAGER").unwrap_or_else(|| {
         if cfg!(windows) { OsString::from("more.com") } else { OsString::from("less") }
     });
 
     let mut fallback_to_println = false;
 
-    match Command::new(pager_name).stdin(Stdio::piped()).spawn() {
+    let mut pager = Command::new(&pager_name);
+    if color && pager_name == "less" {
+        // Let `less` pass the color escape sequences through.
+        pager.arg("-R");
+    }
+    match pager.stdin(Stdio::piped()).spawn() {
         Ok(mut pager) => {
             if let Some(pipe) = pager.stdin.as_mut() {
                 if pipe.write_all(content.as_bytes()).is_err() {
@@ -664,6 +706,34 @@ fn print_crate_info(
             TargetSpec => {
                 println!("{}", serde_json::to_string_pretty(&sess.target.to_json()).unwrap());
             }
+            AllTargetSpecs => {
+                let targets = rustc_target::spec::builtin_targets_json();
+                println!("{}", serde_json::to_string_pretty(&targets).unwrap());
+            }
+            AllTargetCfgs => {
+                // Every cfg name maps to its sorted values, which are empty for names like `unix`.
+                let mut targets = BTreeMap::new();
+                for &triple in rustc_target::spec::TARGETS {
+                    let target = Target::expect_builtin(&TargetTriple::from_triple(triple));
+                    let cfg = config::target_configuration(sess, &target)
+                        .unwrap_or_else(|err| sess.fatal(&format!("{}: {}", triple, err)));
+                    let mut cfgs = BTreeMap::<String, Vec<String>>::new();
+                    for (name, value) in cfg {
+                        let values = cfgs.entry(name.to_string()).or_default();
+                        values.extend(value.map(|value| value.to_string()));
+                    }
+                    for values in cfgs.values_mut() {
+                        values.sort();
+                    }
+                    targets.insert(triple, cfgs);
+                }
+                println!("{}", serde_json::to_string_pretty(&targets).unwrap());
+            }
+            TargetCapability(cap) => {
+                for value in sess.target.capability(cap) {
+                    println!("{}", value);
+                }
+            }
             FileNames | CrateName => {
                 let input = input.unwrap_or_else(|| {
                     early_error(ErrorOutputType::default(), "no input file provided")
@@ -726,6 +796,15 @@ fn print_crate_info(
             | TargetFeatures => {
                 codegen_backend.print(*req, sess);
             }
+            DeploymentTarget => match rustc_target::spec::apple_deployment_target(&sess.target) {
+                Some(Ok(deployment_target)) => println!("deployment_target={}", deployment_target),
+                // The error was already reported when the session was created.
+                Some(Err(_)) => {}
+                None => early_error(
+                    ErrorOutputType::default(),
+                    "only Apple targets currently support deployment version info",
+                ),
+            },
             // Any output here interferes with Cargo's parsing of other printed output
             NativeStaticLibs => {}
             LinkArgs => {}
@@ -1215,6 +1294,19 @@ pub fn report_ice(info: &panic::PanicInfo<'_>, bug_report_url: &str) {
 
     interface::try_print_query_stack(&handler, num_frames);
 
+    let header = format!("{}\n{}", info, xs[2..].join("\n"));
+    match interface::try_dump_ice(&header) {
+        Some(Ok(path)) => handler.note_without_error(&format!(
+            "the full query stack and the source of the failing item were written to `{}`, \
+             please attach it to the bug report",
+            path.display()
+        )),
+        Some(Err(err)) => {
+            handler.note_without_error(&format!("failed to write the `-Zdump-ice` report: {}", err))
+        }
+        None => {}
+    }
+
     #[cfg(windows)]
     unsafe {
         if env::var("RUSTC_BREAK_ON_ICE").is_ok() {
@@ -0,0 +1,130 @@
+//! `rustc --assemble-sysroot`: builds a sysroot out of prebuilt library crates, such as `core`,
+//! `alloc` and `std` built separately for a custom target, after checking that the crates can be
+//! linked with each other and with crates compiled by this rustc.
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_metadata::locator::{self, CrateSummary};
+use rustc_session::cstore::MetadataLoader;
+use rustc_session::filesearch;
+use rustc_session::Session;
+use rustc_span::symbol::{sym, Symbol};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Checks the crate files in `libs` and copies them into the target library directory of the
+/// sysroot at `sysroot`. The crates must have been compiled by this rustc, for the target of the
+/// session, with the same panic strategy, and against each other. Problems are reported as errors
+/// on the session, in which case nothing is copied.
+pub fn assemble_sysroot(
+    sess: &Session,
+    metadata_loader: &dyn MetadataLoader,
+    sysroot: &Path,
+    libs: &[String],
+) {
+    if libs.is_empty() {
+        sess.err("no library files were given to assemble the sysroot from");
+        return;
+    }
+    let target = sess.opts.target_triple.triple();
+
+    let mut crates: Vec<(PathBuf, CrateSummary)> = vec![];
+    let mut crates_by_name: FxHashMap<Symbol, usize> = FxHashMap::default();
+    for lib in libs {
+        let path = PathBuf::from(lib);
+        if path.file_name().is_none() {
+            sess.err(&format!("cannot use `{}` in a sysroot: it does not name a file", lib));
+            continue;
+        }
+        let summary = match locator::read_crate_summary(&sess.target, &path, metadata_loader) {
+            Ok(summary) => summary,
+            Err(err) => {
+                sess.err(&format!("cannot use `{}` in a sysroot: {}", path.display(), err));
+                continue;
+            }
+        };
+        if summary.triple.triple() != target {
+            sess.err(&format!(
+                "`{}` was compiled for the target `{}`, but the sysroot is for `{}`",
+                summary.name,
+                summary.triple.triple(),
+                target,
+            ));
+        }
+        if let Some(&i) = crates_by_name.get(&summary.name) {
+            sess.err(&format!(
+                "the crate `{}` is provided twice, by `{}` and `{}`",
+                summary.name,
+                crates[i].0.display(),
+                path.display(),
+            ));
+            continue;
+        }
+        crates_by_name.insert(summary.name, crates.len());
+        crates.push((path, summary));
+    }
+    if sess.has_errors().is_some() {
+        return;
+    }
+
+    let Some(&core) = crates_by_name.get(&sym::core) else {
+        sess.err("no `core` crate was provided");
+        return;
+    };
+    let (_, core) = &crates[core];
+    for (path, summary) in &crates {
+        // Panic runtimes are compiled with the panic strategy they implement.
+        if !summary.panic_runtime && summary.panic_strategy != core.panic_strategy {
+            sess.err(&format!(
+                "`{}` was compiled with `-C panic={}`, but `core` with `-C panic={}`",
+                summary.name,
+                summary.panic_strategy.desc(),
+                core.panic_strategy.desc(),
+            ));
+        }
+        for &(dep, hash) in &summary.deps {
+            match crates_by_name.get(&dep).map(|&i| &crates[i]) {
+                Some((_, dep_summary)) if dep_summary.hash == hash => {}
+                Some((dep_path, _)) => {
+                    sess.err(&format!(
+                        "`{}` (from `{}`) was compiled against a different version of `{}` than \
+                         the one in `{}`",
+                        summary.name,
+                        path.display(),
+                        dep,
+                        dep_path.display(),
+                    ));
+                }
+                None => {
+                    sess.err(&format!(
+                        "`{}` (from `{}`) depends on `{}`, which was not provided",
+                        summary.name,
+                        path.display(),
+                        dep,
+                    ));
+                }
+            }
+        }
+    }
+    if sess.has_errors().is_some() {
+        return;
+    }
+
+    let lib_dir = filesearch::make_target_lib_path(sysroot, target);
+    if let Err(err) = fs::create_dir_all(&lib_dir) {
+        sess.err(&format!("failed to create the directory `{}`: {}", lib_dir.display(), err));
+        return;
+    }
+    for (path, _) in &crates {
+        // Paths without a file name were reported when reading the crates.
+        let Some(file_name) = path.file_name() else { continue };
+        let dest = lib_dir.join(file_name);
+        if let Err(err) = fs::copy(path, &dest) {
+            sess.err(&format!(
+                "failed to copy `{}` to `{}`: {}",
+                path.display(),
+                dest.display(),
+                err
+            ));
+        }
+    }
+}
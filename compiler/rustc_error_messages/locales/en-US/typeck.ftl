@@ -129,3 +129,77 @@ typeck-manual-implementation =
     .help = add `#![feature(unboxed_closures)]` to the crate attributes to enable
 
 typeck-substs-on-overridden-impl = could not resolve substs on overridden impl
+
+typeck-missing-required-bounds-on-gat =
+    missing required {$count ->
+        [one] bound
+        *[other] bounds
+    } on `{$ident}`
+    .suggestion = add the required where {$count ->
+        [one] clause
+        *[other] clauses
+    }
+    .note = {$count ->
+        [one] this bound is
+        *[other] these bounds are
+    } currently required to ensure that impls have maximum flexibility
+    .feedback-note = we are soliciting feedback, see issue #87479 <https://github.com/rust-lang/rust/issues/87479> for more information
+
+typeck-const-param-ty-param-not-structural =
+    `{$ty}` is not guaranteed to `#[derive(PartialEq, Eq)]`, so may not be used as the type of a const parameter
+    .label = `{$ty}` may not derive both `PartialEq` and `Eq`
+    .note = it is not currently possible to use a type parameter as the type of a const parameter
+
+typeck-const-param-ty-not-structural =
+    `{$ty}` must be annotated with `#[derive(PartialEq, Eq)]` to be used as the type of a const parameter
+    .label = `{$ty}` doesn't derive both `PartialEq` and `Eq`
+
+typeck-invalid-self-parameter-type =
+    invalid `self` parameter type: {$receiver_ty}
+    .note = type of `self` must be `Self` or a type that dereferences to it
+    .help = consider changing to `self`, `&self`, `&mut self`, `self: Box<Self>`, `self: Rc<Self>`, `self: Arc<Self>`, or `self: Pin<P>` (where P is one of the previous types except `Self`)
+
+typeck-unused-parameter =
+    parameter `{$param_name}` is never used
+    .label = unused parameter
+    .const-param-help = if you intended `{$param_name}` to be a const parameter, use `const {$param_name}: usize` instead
+
+typeck-unused-parameter-marker-help =
+    consider removing `{$param_name}`, referring to it in a field, or using a marker such as `{$marker}`
+
+typeck-unused-parameter-no-marker-help =
+    consider removing `{$param_name}` or referring to it in a field
+
+typeck-self-ty-needs-arbitrary-self-types =
+    `{$receiver_ty}` cannot be used as the type of `self` without the `arbitrary_self_types` feature
+    .help = consider changing to `self`, `&self`, `&mut self`, `self: Box<Self>`, `self: Rc<Self>`, `self: Arc<Self>`, or `self: Pin<P>` (where P is one of the previous types except `Self`)
+
+typeck-auto-trait-impl-default = impls of auto traits cannot be default
+    .label = auto trait
+    .defaultness-label = default because of this
+
+typeck-negative-impl-default = negative impls cannot be default impls
+
+typeck-fn-lang-item-call-self-not-ref =
+    first argument of `call` in `{$fn_lang_item_name}` lang item must be a reference
+
+typeck-fn-lang-item-call-arg-count =
+    `call` function in `{$fn_lang_item_name}` lang item takes exactly two arguments
+
+typeck-fn-lang-item-call-not-fn =
+    `call` trait item in `{$fn_lang_item_name}` lang item must be a function
+
+typeck-marker-trait-assoc-item = marker traits cannot have associated items
+
+typeck-trait-object-of-own-trait =
+    associated item referring to unboxed trait object for its own trait
+    .label = in this trait
+    .suggestion = you might have meant to use `Self` to refer to the implementing type
+
+typeck-const-param-ty-forbidden =
+    using {$unsupported_type} as const generic parameters is forbidden
+
+typeck-const-param-ty-unsupported =
+    `{$ty}` is forbidden as the type of a const generic parameter
+    .note = the only supported types are integers, `bool` and `char`
+    .help = more complex types are supported with `#![feature(adt_const_params)]`
@@ -5,11 +5,13 @@
 //!
 //! [annotate_snippets]: https://docs.rs/crate/annotate-snippets/
 
-use crate::emitter::FileWithAnnotatedLines;
-use crate::snippet::Line;
+use crate::emitter::{
+    normalize_whitespace, ColorConfig, FileWithAnnotatedLines, MAX_SUGGESTIONS,
+    OUTPUT_REPLACEMENTS,
+};
 use crate::{
     CodeSuggestion, Diagnostic, DiagnosticId, DiagnosticMessage, Emitter, FluentBundle,
-    LazyFallbackBundle, Level, MultiSpan, Style, SubDiagnostic,
+    LazyFallbackBundle, Level, MultiSpan, Style, SubDiagnostic, SuggestionStyle,
 };
 use annotate_snippets::display_list::{DisplayList, FormatOptions};
 use annotate_snippets::snippet::*;
@@ -17,6 +19,7 @@
 use rustc_error_messages::FluentArgs;
 use rustc_span::source_map::SourceMap;
 use rustc_span::SourceFile;
+use termcolor::ColorChoice;
 
 /// Generates diagnostics using annotate-snippet
 pub struct AnnotateSnippetEmitterWriter {
@@ -28,6 +31,7 @@ pub struct AnnotateSnippetEmitterWriter {
     short_message: bool,
     /// If true, will normalize line numbers with `LL` to prevent noise in UI test diffs.
     ui_testing: bool,
+    color: bool,
 
     macro_backtrace: bool,
 }
@@ -76,9 +80,21 @@ fn should_show_explain(&self) -> bool {
     }
 }
 
-/// Provides the source string for the given `line` of `file`
-fn source_string(file: Lrc<SourceFile>, line: &Line) -> String {
-    file.get_line(line.line_index - 1).map(|a| a.to_string()).unwrap_or_default()
+/// Provides the source string for the given 1-based `line_index` of `file`
+fn source_string(file: &SourceFile, line_index: usize) -> String {
+    file.get_line(line_index - 1).map(|a| a.to_string()).unwrap_or_default()
+}
+
+/// Maps a column of `line`, counted in characters, to the same column after the line went
+/// through `normalize_whitespace`.
+fn normalized_col(line: &str, col: usize) -> usize {
+    line.chars()
+        .take(col)
+        .map(|c| match OUTPUT_REPLACEMENTS.iter().find(|(replaced, _)| *replaced == c) {
+            Some((_, replacement)) => replacement.chars().count(),
+            None => 1,
+        })
+        .sum()
 }
 
 /// Maps `Diagnostic::Level` to `snippet::AnnotationType`
@@ -87,7 +103,7 @@ fn annotation_type_for_level(level: Level) -> AnnotationType {
         Level::Bug | Level::DelayedBug | Level::Fatal | Level::Error { .. } => {
             AnnotationType::Error
         }
-        Level::Warning => AnnotationType::Warning,
+        Level::Warning(_) => AnnotationType::Warning,
         Level::Note | Level::OnceNote => AnnotationType::Note,
         Level::Help => AnnotationType::Help,
         // FIXME(#59346): Not sure how to map this level
@@ -103,6 +119,7 @@ pub fn new(
         fluent_bundle: Option<Lrc<FluentBundle>>,
         fallback_bundle: LazyFallbackBundle,
         short_message: bool,
+        color_config: ColorConfig,
         macro_backtrace: bool,
     ) -> Self {
         Self {
@@ -111,6 +128,7 @@ pub fn new(
             fallback_bundle,
             short_message,
             ui_testing: false,
+            color: color_config.to_color_choice() != ColorChoice::Never,
             macro_backtrace,
         }
     }
@@ -130,86 +148,171 @@ fn emit_messages_default(
         args: &FluentArgs<'_>,
         code: &Option<DiagnosticId>,
         msp: &MultiSpan,
-        _children: &[SubDiagnostic],
-        _suggestions: &[CodeSuggestion],
+        children: &[SubDiagnostic],
+        suggestions: &[CodeSuggestion],
     ) {
         let message = self.translate_messages(messages, args);
-        if let Some(source_map) = &self.source_map {
-            // Make sure our primary file comes first
-            let primary_lo = if let Some(ref primary_span) = msp.primary_span().as_ref() {
-                if primary_span.is_dummy() {
-                    // FIXME(#59346): Not sure when this is the case and what
-                    // should be done if it happens
-                    return;
-                } else {
-                    source_map.lookup_char_pos(primary_span.lo())
-                }
-            } else {
-                // FIXME(#59346): Not sure when this is the case and what
-                // should be done if it happens
-                return;
+        if *level == Level::FailureNote {
+            // Failure notes are printed as they are, like `emitter.rs` does.
+            eprintln!("{}", message);
+            return;
+        }
+
+        // Like in `emitter.rs`, children with a span get their own snippet, while children
+        // without a span and the suggestions which weren't turned into a label of the primary
+        // span are shown below the last snippet.
+        let mut snippets = vec![(*level, message.into_owned(), code.clone(), msp, Vec::new())];
+        for child in children {
+            let message = self.translate_messages(&child.message, args).into_owned();
+            if child.span.primary_span().map_or(false, |span| !span.is_dummy()) {
+                snippets.push((child.level, message, None, &child.span, Vec::new()));
+            } else if let Some((.., footer)) = snippets.last_mut() {
+                footer.push((annotation_type_for_level(child.level), message));
+            }
+        }
+        for sugg in suggestions {
+            if sugg.style == SuggestionStyle::CompletelyHidden {
+                continue;
+            }
+            let msg = self.translate_message(&sugg.msg, args).into_owned();
+            let substitutions = match &self.source_map {
+                Some(sm) if sugg.style != SuggestionStyle::HideCodeAlways => sugg.splice_lines(sm),
+                _ => Vec::new(),
             };
+            let Some((.., footer)) = snippets.last_mut() else { continue };
+            if substitutions.is_empty() {
+                footer.push((AnnotationType::Help, msg));
+            }
+            for (substitution, ..) in substitutions.into_iter().take(MAX_SUGGESTIONS) {
+                let substitution = normalize_whitespace(substitution.trim());
+                let label = if substitution.contains('\n') {
+                    format!("{}:\n{}", msg, substitution)
+                } else {
+                    format!("{}: `{}`", msg, substitution)
+                };
+                footer.push((AnnotationType::Help, label));
+            }
+        }
+
+        for (level, message, code, msp, footer) in &snippets {
+            self.emit_snippet(*level, message, code, msp, args, footer);
+        }
+        if !self.short_message {
+            eprintln!();
+        }
+    }
+
+    /// Prints a single snippet, with all the annotated lines of `msp` grouped by file.
+    fn emit_snippet(
+        &self,
+        level: Level,
+        message: &str,
+        code: &Option<DiagnosticId>,
+        msp: &MultiSpan,
+        args: &FluentArgs<'_>,
+        footer: &[(AnnotationType, String)],
+    ) {
+        // owned: file name, first line index, source, annotations
+        type Owned = (String, usize, String, Vec<((usize, usize), String, AnnotationType)>);
+        let mut slices: Vec<Owned> = Vec::new();
+        let primary_span = msp.primary_span().filter(|span| !span.is_dummy());
+        if let (Some(source_map), Some(primary_span)) = (&self.source_map, primary_span) {
+            let primary_lo = source_map.lookup_char_pos(primary_span.lo());
             let mut annotated_files = FileWithAnnotatedLines::collect_annotations(self, args, msp);
+            // Make sure our primary file comes first
             if let Ok(pos) =
                 annotated_files.binary_search_by(|x| x.file.name.cmp(&primary_lo.file.name))
             {
                 annotated_files.swap(0, pos);
             }
-            // owned: line source, line index, annotations
-            type Owned = (String, usize, Vec<crate::snippet::Annotation>);
-            let filename = source_map.filename_for_diagnostics(&primary_lo.file.name);
-            let origin = filename.to_string_lossy();
-            let annotated_files: Vec<Owned> = annotated_files
-                .into_iter()
-                .flat_map(|annotated_file| {
-                    let file = annotated_file.file;
-                    annotated_file
-                        .lines
-                        .into_iter()
-                        .map(|line| {
-                            (source_string(file.clone(), &line), line.line_index, line.annotations)
-                        })
-                        .collect::<Vec<Owned>>()
-                })
-                .collect();
-            let snippet = Snippet {
-                title: Some(Annotation {
-                    label: Some(&message),
-                    id: code.as_ref().map(|c| match c {
-                        DiagnosticId::Error(val) | DiagnosticId::Lint { name: val, .. } => {
-                            val.as_str()
-                        }
-                    }),
-                    annotation_type: annotation_type_for_level(*level),
-                }),
-                footer: vec![],
-                opt: FormatOptions { color: true, anonymized_line_numbers: self.ui_testing },
-                slices: annotated_files
-                    .iter()
-                    .map(|(source, line_index, annotations)| {
-                        Slice {
-                            source,
-                            line_start: *line_index,
-                            origin: Some(&origin),
-                            // FIXME(#59346): Not really sure when `fold` should be true or false
-                            fold: false,
-                            annotations: annotations
-                                .iter()
-                                .map(|annotation| SourceAnnotation {
-                                    range: (annotation.start_col, annotation.end_col),
-                                    label: annotation.label.as_deref().unwrap_or_default(),
-                                    annotation_type: annotation_type_for_level(*level),
-                                })
-                                .collect(),
+            for mut annotated_file in annotated_files {
+                annotated_file.lines.sort_by_key(|line| line.line_index);
+                let (Some(first), Some(last)) =
+                    (annotated_file.lines.first(), annotated_file.lines.last())
+                else {
+                    continue;
+                };
+                let (first, last) = (first.line_index, last.line_index);
+                let file = annotated_file.file;
+                let filename = source_map.filename_for_diagnostics(&file.name);
+
+                // All the lines between the first and the last annotated one make up the slice,
+                // the ones without annotations are folded away when they're far enough apart.
+                let mut source = String::new();
+                let mut annotations = Vec::new();
+                let mut lines = annotated_file.lines.into_iter().peekable();
+                for line_index in first..=last {
+                    let text = source_string(&file, line_index);
+                    let offset = source.chars().count();
+                    if let Some(line) = lines.next_if(|line| line.line_index == line_index) {
+                        // FIXME(#59346): Multiline annotations are only shown by their start and
+                        // end columns on each line.
+                        for annotation in line.annotations {
+                            let range = (
+                                offset + normalized_col(&text, annotation.start_col),
+                                offset + normalized_col(&text, annotation.end_col),
+                            );
+                            // Primary spans are underlined with `^` and secondary ones with `-`.
+                            let annotation_type = if annotation.is_primary {
+                                AnnotationType::Error
+                            } else {
+                                AnnotationType::Info
+                            };
+                            annotations.push((
+                                range,
+                                annotation.label.unwrap_or_default(),
+                                annotation_type,
+                            ));
                         }
-                    })
-                    .collect(),
-            };
-            // FIXME(#59346): Figure out if we can _always_ print to stderr or not.
-            // `emitter.rs` has the `Destination` enum that lists various possible output
-            // destinations.
-            eprintln!("{}", DisplayList::from(snippet))
+                    }
+                    source.push_str(&normalize_whitespace(&text));
+                    source.push('\n');
+                }
+                source.pop();
+                slices.push((filename.to_string_lossy().into_owned(), first, source, annotations));
+            }
         }
-        // FIXME(#59346): Is it ok to return None if there's no source_map?
+
+        let snippet = Snippet {
+            title: Some(Annotation {
+                label: Some(message),
+                id: code.as_ref().map(|c| match c {
+                    DiagnosticId::Error(val) | DiagnosticId::Lint { name: val, .. } => {
+                        val.as_str()
+                    }
+                }),
+                annotation_type: annotation_type_for_level(level),
+            }),
+            footer: footer
+                .iter()
+                .map(|(annotation_type, label)| Annotation {
+                    label: Some(label.as_str()),
+                    id: None,
+                    annotation_type: *annotation_type,
+                })
+                .collect(),
+            opt: FormatOptions { color: self.color, anonymized_line_numbers: self.ui_testing },
+            slices: slices
+                .iter()
+                .map(|(origin, line_start, source, annotations)| Slice {
+                    source,
+                    line_start: *line_start,
+                    origin: Some(origin.as_str()),
+                    fold: true,
+                    annotations: annotations
+                        .iter()
+                        .map(|(range, label, annotation_type)| SourceAnnotation {
+                            range: *range,
+                            label,
+                            annotation_type: *annotation_type,
+                        })
+                        .collect(),
+                })
+                .collect(),
+        };
+        // FIXME(#59346): Figure out if we can _always_ print to stderr or not.
+        // `emitter.rs` has the `Destination` enum that lists various possible output
+        // destinations.
+        eprintln!("{}", DisplayList::from(snippet))
     }
 }
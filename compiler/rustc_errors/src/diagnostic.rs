@@ -6,7 +6,7 @@
 use rustc_data_structures::stable_map::FxHashMap;
 use rustc_error_messages::FluentValue;
 use rustc_lint_defs::{Applicability, LintExpectationId};
-use rustc_span::edition::LATEST_STABLE_EDITION;
+use rustc_span::edition::{Edition, LATEST_STABLE_EDITION};
 use rustc_span::symbol::{Ident, Symbol};
 use rustc_span::{Span, DUMMY_SP};
 use std::borrow::Cow;
@@ -107,6 +107,24 @@ pub struct Diagnostic {
     /// If diagnostic is from Lint, custom hash function ignores notes
     /// otherwise hash is based on the all the fields
     pub is_lint: bool,
+
+    /// Where the lint came from, for diagnostics of lints with future breakage, which are
+    /// reported in the future incompatibility report.
+    pub future_breakage: Option<FutureBreakageProvenance>,
+}
+
+/// Where a lint with future breakage came from, for the future incompatibility report of
+/// `--json=future-incompat`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Encodable, Decodable)]
+pub struct FutureBreakageProvenance {
+    /// How the level of the lint was set: `default`, `attribute`, `command-line` or
+    /// `config-file`.
+    pub level_source: String,
+    /// The edition in which the code becomes an error or changes meaning, if the lint is tied
+    /// to one.
+    pub edition: Option<Edition>,
+    /// The issue tracking the future incompatibility, as written in the lint's declaration.
+    pub reference: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Encodable, Decodable)]
@@ -192,6 +210,7 @@ pub fn new_with_code<M: Into<DiagnosticMessage>>(
             args: vec![],
             sort_span: DUMMY_SP,
             is_lint: false,
+            future_breakage: None,
         }
     }
 
@@ -208,7 +227,7 @@ pub fn is_error(&self) -> bool {
             | Level::Error { .. }
             | Level::FailureNote => true,
 
-            Level::Warning
+            Level::Warning(_)
             | Level::Note
             | Level::OnceNote
             | Level::Help
@@ -221,7 +240,9 @@ pub fn update_unstable_expectation_id(
         &mut self,
         unstable_to_stable: &FxHashMap<LintExpectationId, LintExpectationId>,
     ) {
-        if let Level::Expect(expectation_id) = &mut self.level {
+        if let Level::Expect(expectation_id) | Level::Warning(Some(expectation_id)) =
+            &mut self.level
+        {
             if expectation_id.is_stable() {
                 return;
             }
@@ -445,7 +466,7 @@ pub fn span_note_once<S: Into<MultiSpan>>(
 
     /// Add a warning attached to this diagnostic.
     pub fn warn(&mut self, msg: impl Into<SubdiagnosticMessage>) -> &mut Self {
-        self.sub(Level::Warning, msg, MultiSpan::new(), None);
+        self.sub(Level::Warning(None), msg, MultiSpan::new(), None);
         self
     }
 
@@ -456,7 +477,7 @@ pub fn span_warn<S: Into<MultiSpan>>(
         sp: S,
         msg: impl Into<SubdiagnosticMessage>,
     ) -> &mut Self {
-        self.sub(Level::Warning, msg, sp.into(), None);
+        self.sub(Level::Warning(None), msg, sp.into(), None);
         self
     }
 
@@ -556,6 +577,7 @@ pub fn multipart_suggestion_with_style(
                     .into_iter()
                     .map(|(span, snippet)| SubstitutionPart { snippet, span })
                     .collect(),
+                applicability: None,
             }],
             msg: self.subdiagnostic_message_to_diagnostic_message(msg),
             style,
@@ -583,6 +605,7 @@ pub fn tool_only_multipart_suggestion(
                     .into_iter()
                     .map(|(span, snippet)| SubstitutionPart { snippet, span })
                     .collect(),
+                applicability: None,
             }],
             msg: self.subdiagnostic_message_to_diagnostic_message(msg),
             style: SuggestionStyle::CompletelyHidden,
@@ -637,6 +660,7 @@ pub fn span_suggestion_with_style(
         self.push_suggestion(CodeSuggestion {
             substitutions: vec![Substitution {
                 parts: vec![SubstitutionPart { snippet: suggestion.to_string(), span: sp }],
+                applicability: None,
             }],
             msg: self.subdiagnostic_message_to_diagnostic_message(msg),
             style,
@@ -676,7 +700,10 @@ pub fn span_suggestions(
         suggestions.sort();
         let substitutions = suggestions
             .into_iter()
-            .map(|snippet| Substitution { parts: vec![SubstitutionPart { snippet, span: sp }] })
+            .map(|snippet| Substitution {
+                parts: vec![SubstitutionPart { snippet, span: sp }],
+                applicability: None,
+            })
             .collect();
         self.push_suggestion(CodeSuggestion {
             substitutions,
@@ -702,6 +729,7 @@ pub fn multipart_suggestions(
                         .into_iter()
                         .map(|(span, snippet)| SubstitutionPart { snippet, span })
                         .collect(),
+                    applicability: None,
                 })
                 .collect(),
             msg: self.subdiagnostic_message_to_diagnostic_message(msg),
@@ -710,6 +738,46 @@ pub fn multipart_suggestions(
         });
         self
     }
+
+    /// Prints out a message with several alternative edits of the code, each with its own
+    /// applicability, ordered from the most to the least preferred one. Tools can offer these
+    /// as a choice of fixes rather than applying a single one.
+    /// See also [`Diagnostic::multipart_suggestions()`].
+    pub fn ranked_suggestions(
+        &mut self,
+        msg: impl Into<SubdiagnosticMessage>,
+        suggestions: impl IntoIterator<Item = (Vec<(Span, String)>, Applicability)>,
+    ) -> &mut Self {
+        let substitutions: Vec<_> = suggestions
+            .into_iter()
+            .map(|(parts, applicability)| {
+                assert!(!parts.is_empty());
+                Substitution {
+                    parts: parts
+                        .into_iter()
+                        .map(|(span, snippet)| SubstitutionPart { snippet, span })
+                        .collect(),
+                    applicability: Some(applicability),
+                }
+            })
+            .collect();
+        // Tools that don't look at the alternatives' applicabilities only see this one, so don't
+        // claim more certainty than every alternative has.
+        let mut applicabilities = substitutions.iter().filter_map(|sub| sub.applicability);
+        let first = applicabilities.next().unwrap_or(Applicability::Unspecified);
+        let applicability = if applicabilities.all(|applicability| applicability == first) {
+            first
+        } else {
+            Applicability::MaybeIncorrect
+        };
+        self.push_suggestion(CodeSuggestion {
+            substitutions,
+            msg: self.subdiagnostic_message_to_diagnostic_message(msg),
+            style: SuggestionStyle::ShowCode,
+            applicability,
+        });
+        self
+    }
     /// Prints out a message with a suggested edit of the code. If the suggestion is presented
     /// inline, it will only show the message and not the suggestion.
     ///
@@ -800,6 +868,14 @@ pub fn code(&mut self, s: DiagnosticId) -> &mut Self {
         self
     }
 
+    pub fn set_future_breakage_provenance(
+        &mut self,
+        provenance: FutureBreakageProvenance,
+    ) -> &mut Self {
+        self.future_breakage = Some(provenance);
+        self
+    }
+
     pub fn clear_code(&mut self) -> &mut Self {
         self.code = None;
         self
@@ -500,6 +500,11 @@ pub fn span_labels(
         suggestions: impl Iterator<Item = Vec<(Span, String)>>,
         applicability: Applicability,
     ) -> &mut Self);
+    forward!(pub fn ranked_suggestions(
+        &mut self,
+        msg: impl Into<SubdiagnosticMessage>,
+        suggestions: impl IntoIterator<Item = (Vec<(Span, String)>, Applicability)>,
+    ) -> &mut Self);
     forward!(pub fn span_suggestion_short(
         &mut self,
         sp: Span,
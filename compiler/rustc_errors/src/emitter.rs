@@ -10,7 +10,7 @@
 use Destination::*;
 
 use rustc_span::source_map::SourceMap;
-use rustc_span::{SourceFile, Span};
+use rustc_span::{FileName, SourceFile, Span};
 
 use crate::snippet::{Annotation, AnnotationType, Line, MultilineAnnotation, Style, StyledString};
 use crate::styled_buffer::StyledBuffer;
@@ -452,8 +452,8 @@ fn render_multispan_macro_backtrace(&self, span: &mut MultiSpan, always_backtrac
             // FIXME(eddyb) use `retain` on `macro_backtrace` to remove all the
             // entries we don't want to print, to make sure the indices being
             // printed are contiguous (or omitted if there's only one entry).
-            let macro_backtrace: Vec<_> = sp.macro_backtrace().collect();
-            for (i, trace) in macro_backtrace.iter().rev().enumerate() {
+            let macro_backtrace: Vec<_> = sp.macro_backtrace_with_transparency().collect();
+            for (i, (trace, transparency)) in macro_backtrace.iter().rev().enumerate() {
                 if trace.def_site.is_dummy() {
                     continue;
                 }
@@ -462,15 +462,16 @@ fn render_multispan_macro_backtrace(&self, span: &mut MultiSpan, always_backtrac
                     new_labels.push((
                         trace.def_site,
                         format!(
-                            "in this expansion of `{}`{}",
+                            "in this expansion of `{}` ({}{})",
                             trace.kind.descr(),
                             if macro_backtrace.len() > 1 {
                                 // if macro_backtrace.len() == 1 it'll be
                                 // pointed at by "in this macro invocation"
-                                format!(" (#{})", i + 1)
+                                format!("#{}, ", i + 1)
                             } else {
                                 String::new()
                             },
+                            transparency.descr(),
                         ),
                     ));
                 }
@@ -674,7 +675,7 @@ pub enum ColorConfig {
 }
 
 impl ColorConfig {
-    fn to_color_choice(self) -> ColorChoice {
+    pub(crate) fn to_color_choice(self) -> ColorChoice {
         match self {
             ColorConfig::Always => {
                 if atty::is(atty::Stream::Stderr) {
@@ -696,6 +697,84 @@ fn suggests_using_colors(self) -> bool {
     }
 }
 
+/// Whether error codes, lint names and primary source locations are printed as hyperlinks,
+/// using the OSC 8 escape sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TerminalUrl {
+    No,
+    Yes,
+    /// Print hyperlinks if stderr is a terminal that is known to support them.
+    Auto,
+}
+
+impl TerminalUrl {
+    /// Returns whether hyperlinks should be printed to stderr.
+    pub fn enabled_for_stderr(self, color_config: ColorConfig) -> bool {
+        match self {
+            TerminalUrl::No => false,
+            TerminalUrl::Yes => true,
+            TerminalUrl::Auto => {
+                color_config != ColorConfig::Never
+                    && atty::is(atty::Stream::Stderr)
+                    && terminal_supports_urls()
+            }
+        }
+    }
+}
+
+/// Guesses whether the terminal supports OSC 8 hyperlinks from the environment variables that
+/// terminal emulators set, as there is no way to query this.
+fn terminal_supports_urls() -> bool {
+    let var = |name| std::env::var(name).ok();
+    if let Some(force) = var("FORCE_HYPERLINK") {
+        return force != "0";
+    }
+    if var("DOMTERM").is_some() || var("WT_SESSION").is_some() || var("KONSOLE_VERSION").is_some() {
+        return true;
+    }
+    // VTE based terminals, like GNOME Terminal, support hyperlinks since version 0.50.
+    if var("VTE_VERSION").and_then(|v| v.parse::<u32>().ok()).map_or(false, |v| v >= 5000) {
+        return true;
+    }
+    matches!(
+        var("TERM_PROGRAM").as_deref(),
+        Some("Hyper" | "iTerm.app" | "terminology" | "WezTerm" | "vscode")
+    ) || matches!(var("TERM").as_deref(), Some("xterm-kitty" | "alacritty" | "foot"))
+}
+
+/// Wraps `text` in an OSC 8 escape sequence that makes terminals show it as a link to `url`.
+/// The default base URL of the documentation that error codes and lint names link to.
+pub const DEFAULT_DOCS_BASE_URL: &str = "https://doc.rust-lang.org";
+
+fn hyperlink(text: &str, url: &str) -> String {
+    format!("\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\")
+}
+
+/// Returns the `file://` URL of a local source file.
+fn file_url(path: &Path) -> Option<String> {
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().ok()?.join(path)
+    };
+    let path = path.to_str()?;
+    let mut url = String::from("file://");
+    // Windows paths start with a drive letter instead of a `/`.
+    if !path.starts_with('/') {
+        url.push('/');
+    }
+    for byte in path.bytes() {
+        match byte {
+            b'\\' => url.push('/'),
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                url.push(byte as char)
+            }
+            _ => url.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    Some(url)
+}
+
 /// Handles the writing of `HumanReadableErrorType::Default` and `HumanReadableErrorType::Short`
 pub struct EmitterWriter {
     dst: Destination,
@@ -706,6 +785,10 @@ pub struct EmitterWriter {
     teach: bool,
     ui_testing: bool,
     terminal_width: Option<usize>,
+    terminal_urls: bool,
+    /// Where the documentation that error codes and lint names link to is, when hyperlinks are
+    /// enabled.
+    docs_base_url: String,
 
     macro_backtrace: bool,
 }
@@ -738,6 +821,8 @@ pub fn stderr(
             teach,
             ui_testing: false,
             terminal_width,
+            terminal_urls: false,
+            docs_base_url: DEFAULT_DOCS_BASE_URL.to_string(),
             macro_backtrace,
         }
     }
@@ -762,6 +847,8 @@ pub fn new(
             teach,
             ui_testing: false,
             terminal_width,
+            terminal_urls: false,
+            docs_base_url: DEFAULT_DOCS_BASE_URL.to_string(),
             macro_backtrace,
         }
     }
@@ -771,6 +858,65 @@ pub fn ui_testing(mut self, ui_testing: bool) -> Self {
         self
     }
 
+    pub fn terminal_urls(mut self, terminal_urls: bool) -> Self {
+        self.terminal_urls = terminal_urls;
+        self
+    }
+
+    /// Sets the base URL of the documentation linked to when hyperlinks are enabled, for example
+    /// to point to a local copy of the docs.
+    pub fn docs_base_url(mut self, docs_base_url: Option<String>) -> Self {
+        if let Some(url) = docs_base_url {
+            self.docs_base_url = url.trim_end_matches('/').to_string();
+        }
+        self
+    }
+
+    /// Links the name of the lint that emitted a diagnostic to its documentation wherever it
+    /// appears in `text`, if hyperlinks are enabled.
+    fn link_lint_name<'a>(&self, text: Cow<'a, str>, code: &Option<DiagnosticId>) -> Cow<'a, str> {
+        let Some(DiagnosticId::Lint { name, .. }) = code else { return text };
+        // Tool lints are documented by their tool.
+        if !self.terminal_urls || name.contains("::") || !text.contains(name.as_str()) {
+            return text;
+        }
+        let url = format!("{}/rustc/lints/listing/index.html?search={}", self.docs_base_url, name);
+        let is_ident_char = |c: char| c.is_alphanumeric() || c == '_';
+        let mut linked = String::with_capacity(text.len());
+        let mut rest = &*text;
+        while let Some(pos) = rest.find(name.as_str()) {
+            let end = pos + name.len();
+            linked.push_str(&rest[..pos]);
+            if rest[..pos].ends_with(is_ident_char) || rest[end..].starts_with(is_ident_char) {
+                linked.push_str(name);
+            } else {
+                linked.push_str(&hyperlink(name, &url));
+            }
+            rest = &rest[end..];
+        }
+        linked.push_str(rest);
+        Cow::Owned(linked)
+    }
+
+    /// Returns the location of `file` as `path:line:col`, linked to the file if hyperlinks are
+    /// enabled.
+    fn file_location(&self, sm: &SourceMap, file: &SourceFile, line: usize, col: usize) -> String {
+        let location = format!(
+            "{}:{}:{}",
+            sm.filename_for_diagnostics(&file.name),
+            sm.doctest_offset_line(&file.name, line),
+            col,
+        );
+        let url = match &file.name {
+            FileName::Real(name) if self.terminal_urls => name.local_path().and_then(file_url),
+            _ => None,
+        };
+        match url {
+            Some(url) => hyperlink(&location, &url),
+            None => location,
+        }
+    }
+
     fn maybe_anonymized(&self, line_num: usize) -> String {
         if self.ui_testing { ANONYMIZED_LINE_NUM.to_string() } else { line_num.to_string() }
     }
@@ -1307,6 +1453,7 @@ fn msg_to_buffer(
         padding: usize,
         label: &str,
         override_style: Option<Style>,
+        code: &Option<DiagnosticId>,
     ) {
         // The extra 5 ` ` is padding that's always needed to align to the `note: `:
         //
@@ -1356,7 +1503,7 @@ fn style_or_override(style: Style, override_: Option<Style>) -> Style {
         //                very *weird* formats
         //                see?
         for &(ref text, ref style) in msg.iter() {
-            let text = self.translate_message(text, args);
+            let text = self.link_lint_name(self.translate_message(text, args), code);
             let lines = text.split('\n').collect::<Vec<_>>();
             if lines.len() > 1 {
                 for (i, line) in lines.iter().enumerate() {
@@ -1395,7 +1542,7 @@ fn emit_message_default(
                 buffer.append(0, level.to_str(), Style::MainHeaderMsg);
                 buffer.append(0, ": ", Style::NoStyle);
             }
-            self.msg_to_buffer(&mut buffer, msg, args, max_line_num_len, "note", None);
+            self.msg_to_buffer(&mut buffer, msg, args, max_line_num_len, "note", None, code);
         } else {
             let mut label_width = 0;
             // The failure note level itself does not provide any useful diagnostic information
@@ -1406,7 +1553,12 @@ fn emit_message_default(
             // only render error codes, not lint codes
             if let Some(DiagnosticId::Error(ref code)) = *code {
                 buffer.append(0, "[", Style::Level(*level));
-                buffer.append(0, &code, Style::Level(*level));
+                if self.terminal_urls {
+                    let url = format!("{}/error-index.html#{}", self.docs_base_url, code);
+                    buffer.append(0, &hyperlink(code, &url), Style::Level(*level));
+                } else {
+                    buffer.append(0, &code, Style::Level(*level));
+                }
                 buffer.append(0, "]", Style::Level(*level));
                 label_width += 2 + code.len();
             }
@@ -1416,7 +1568,7 @@ fn emit_message_default(
                 label_width += 2;
             }
             for &(ref text, _) in msg.iter() {
-                let text = self.translate_message(text, args);
+                let text = self.link_lint_name(self.translate_message(text, args), code);
                 // Account for newlines to align output to its label.
                 for (line, text) in normalize_whitespace(&text).lines().enumerate() {
                     buffer.append(
@@ -1474,12 +1626,7 @@ fn emit_message_default(
                     buffer.prepend(buffer_msg_line_offset, "--> ", Style::LineNumber);
                     buffer.append(
                         buffer_msg_line_offset,
-                        &format!(
-                            "{}:{}:{}",
-                            sm.filename_for_diagnostics(&loc.file.name),
-                            sm.doctest_offset_line(&loc.file.name, loc.line),
-                            loc.col.0 + 1,
-                        ),
+                        &self.file_location(sm, &loc.file, loc.line, loc.col.0 + 1),
                         Style::LineAndColumn,
                     );
                     for _ in 0..max_line_num_len {
@@ -1489,10 +1636,8 @@ fn emit_message_default(
                     buffer.prepend(
                         0,
                         &format!(
-                            "{}:{}:{}: ",
-                            sm.filename_for_diagnostics(&loc.file.name),
-                            sm.doctest_offset_line(&loc.file.name, loc.line),
-                            loc.col.0 + 1,
+                            "{}: ",
+                            self.file_location(sm, &loc.file, loc.line, loc.col.0 + 1)
                         ),
                         Style::LineAndColumn,
                     );
@@ -1748,6 +1893,7 @@ fn emit_suggestion_default(
             max_line_num_len,
             "suggestion",
             Some(Style::HeaderMsg),
+            &None,
         );
 
         let mut row_num = 2;
@@ -2042,13 +2188,18 @@ fn emit_messages_default(
                     }
                 }
                 if !self.short_message {
+                    // Children don't have codes of their own, but they can mention the lint.
+                    let lint_code = match code {
+                        Some(DiagnosticId::Lint { .. }) => code,
+                        _ => &None,
+                    };
                     for child in children {
                         let span = child.render_span.as_ref().unwrap_or(&child.span);
                         if let Err(err) = self.emit_message_default(
                             &span,
                             &child.message,
                             args,
-                            &None,
+                            lint_code,
                             &child.level,
                             max_line_num_len,
                             true,
@@ -2285,7 +2436,7 @@ fn num_decimal_digits(num: usize) -> usize {
 }
 
 // We replace some characters so the CLI output is always consistent and underlines aligned.
-const OUTPUT_REPLACEMENTS: &[(char, &str)] = &[
+pub(crate) const OUTPUT_REPLACEMENTS: &[(char, &str)] = &[
     ('\t', "    "),   // We do our own tab replacement
     ('\u{200D}', ""), // Replace ZWJ with nothing for consistent terminal output of grapheme clusters.
     ('\u{202A}', ""), // The following unicode text flow control characters are inconsistently
@@ -2299,7 +2450,7 @@ fn num_decimal_digits(num: usize) -> usize {
     ('\u{2069}', ""),
 ];
 
-fn normalize_whitespace(str: &str) -> String {
+pub(crate) fn normalize_whitespace(str: &str) -> String {
     let mut s = str.to_string();
     for (c, replacement) in OUTPUT_REPLACEMENTS {
         s = s.replace(*c, replacement);
@@ -21,7 +21,7 @@
 
 use rustc_data_structures::sync::Lrc;
 use rustc_error_messages::FluentArgs;
-use rustc_span::hygiene::ExpnData;
+use rustc_span::hygiene::{ExpnData, Transparency};
 use rustc_span::Span;
 use std::io::{self, Write};
 use std::path::Path;
@@ -154,9 +154,20 @@ fn emit_future_breakage_report(&mut self, diags: Vec<crate::Diagnostic>) {
             .into_iter()
             .map(|mut diag| {
                 if diag.level == crate::Level::Allow {
-                    diag.level = crate::Level::Warning;
+                    diag.level = crate::Level::Warning(None);
+                }
+                let lint = match &diag.code {
+                    Some(DiagnosticId::Lint { name, .. }) => Some(name.clone()),
+                    _ => None,
+                };
+                let provenance = diag.future_breakage.take();
+                FutureBreakageItem {
+                    diagnostic: Diagnostic::from_errors_diagnostic(&diag, self),
+                    lint,
+                    level_source: provenance.as_ref().map(|p| p.level_source.clone()),
+                    edition: provenance.as_ref().and_then(|p| p.edition).map(|e| e.to_string()),
+                    tracking_issue: provenance.and_then(|p| p.reference),
                 }
-                FutureBreakageItem { diagnostic: Diagnostic::from_errors_diagnostic(&diag, self) }
             })
             .collect();
         let report = FutureIncompatReport { future_incompat_report: data };
@@ -241,6 +252,9 @@ struct DiagnosticSpan {
     suggested_replacement: Option<String>,
     /// If the suggestion is approximate
     suggestion_applicability: Option<Applicability>,
+    /// For the alternatives of a ranked suggestion, the rank of the alternative this
+    /// replacement belongs to, starting at 0 for the most preferred one.
+    suggestion_rank: Option<usize>,
     /// Macro invocations that created the code at this span, if any.
     expansion: Option<Box<DiagnosticSpanMacroExpansion>>,
 }
@@ -267,6 +281,11 @@ struct DiagnosticSpanMacroExpansion {
 
     /// span where macro was defined (if known)
     def_site_span: DiagnosticSpan,
+
+    /// hygiene of the identifiers this expansion produced: "transparent" (resolved at the
+    /// call site), "semi-transparent" (local variables, labels and `$crate` resolved at the
+    /// def site, the rest at the call site) or "opaque" (resolved at the def site)
+    transparency: &'static str,
 }
 
 #[derive(Serialize)]
@@ -288,6 +307,15 @@ struct ArtifactNotification<'a> {
 #[derive(Serialize)]
 struct FutureBreakageItem {
     diagnostic: Diagnostic,
+    /// The name of the lint which emitted the diagnostic.
+    lint: Option<String>,
+    /// How the level of the lint was set: `default`, `attribute`, `command-line` or
+    /// `config-file`.
+    level_source: Option<String>,
+    /// The edition in which the code becomes an error or changes meaning, if any.
+    edition: Option<String>,
+    /// The issue tracking the future incompatibility.
+    tracking_issue: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -393,7 +421,7 @@ fn from_sub_diagnostic(
 impl DiagnosticSpan {
     fn from_span_label(
         span: SpanLabel,
-        suggestion: Option<(&String, Applicability)>,
+        suggestion: Option<(&String, Applicability, Option<usize>)>,
         args: &FluentArgs<'_>,
         je: &JsonEmitter,
     ) -> DiagnosticSpan {
@@ -410,7 +438,7 @@ fn from_span_etc(
         span: Span,
         is_primary: bool,
         label: Option<String>,
-        suggestion: Option<(&String, Applicability)>,
+        suggestion: Option<(&String, Applicability, Option<usize>)>,
         je: &JsonEmitter,
     ) -> DiagnosticSpan {
         // obtain the full backtrace from the `macro_backtrace`
@@ -418,7 +446,7 @@ fn from_span_etc(
         // backtrace ourselves, but the `macro_backtrace` helper makes
         // some decision, such as dropping some frames, and I don't
         // want to duplicate that logic here.
-        let backtrace = span.macro_backtrace();
+        let backtrace = span.macro_backtrace_with_transparency();
         DiagnosticSpan::from_span_full(span, is_primary, label, suggestion, backtrace, je)
     }
 
@@ -426,13 +454,13 @@ fn from_span_full(
         span: Span,
         is_primary: bool,
         label: Option<String>,
-        suggestion: Option<(&String, Applicability)>,
-        mut backtrace: impl Iterator<Item = ExpnData>,
+        suggestion: Option<(&String, Applicability, Option<usize>)>,
+        mut backtrace: impl Iterator<Item = (ExpnData, Transparency)>,
         je: &JsonEmitter,
     ) -> DiagnosticSpan {
         let start = je.sm.lookup_char_pos(span.lo());
         let end = je.sm.lookup_char_pos(span.hi());
-        let backtrace_step = backtrace.next().map(|bt| {
+        let backtrace_step = backtrace.next().map(|(bt, transparency)| {
             let call_site = Self::from_span_full(bt.call_site, false, None, None, backtrace, je);
             let def_site_span = Self::from_span_full(
                 je.sm.guess_head_span(bt.def_site),
@@ -446,6 +474,7 @@ fn from_span_full(
                 span: call_site,
                 macro_decl_name: bt.kind.descr(),
                 def_site_span,
+                transparency: transparency.descr(),
             })
         });
 
@@ -461,6 +490,7 @@ fn from_span_full(
             text: DiagnosticSpanLine::from_span(span, je),
             suggested_replacement: suggestion.map(|x| x.0.clone()),
             suggestion_applicability: suggestion.map(|x| x.1),
+            suggestion_rank: suggestion.and_then(|x| x.2),
             expansion: backtrace_step,
             label,
         }
@@ -485,13 +515,16 @@ fn from_suggestion(
         suggestion
             .substitutions
             .iter()
-            .flat_map(|substitution| {
+            .enumerate()
+            .flat_map(|(rank, substitution)| {
+                let applicability = suggestion.applicability_of(substitution);
+                let rank = substitution.applicability.map(|_| rank);
                 substitution.parts.iter().map(move |suggestion_inner| {
                     let span_label =
                         SpanLabel { span: suggestion_inner.span, is_primary: true, label: None };
                     DiagnosticSpan::from_span_label(
                         span_label,
-                        Some((&suggestion_inner.snippet, suggestion.applicability)),
+                        Some((&suggestion_inner.snippet, applicability, rank)),
                         args,
                         je,
                     )
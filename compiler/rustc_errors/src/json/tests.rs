@@ -16,6 +16,16 @@ struct TestData {
     spans: Vec<SpanTestData>,
 }
 
+#[derive(Deserialize, Debug, PartialEq, Eq)]
+struct ChildrenTestData {
+    children: Vec<ChildTestData>,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Eq)]
+struct ChildTestData {
+    message: String,
+}
+
 #[derive(Deserialize, Debug, PartialEq, Eq)]
 struct SpanTestData {
     pub byte_start: u32,
@@ -202,3 +212,48 @@ fn span_after_crlf() {
         },
     )
 }
+
+/// Test that diagnostic hooks can append notes to the diagnostics with a given error code.
+#[test]
+fn diagnostic_hook_adds_note() {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), "foo".to_owned());
+        let fallback_bundle =
+            crate::fallback_fluent_bundle(rustc_error_messages::DEFAULT_LOCALE_RESOURCES, false);
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let je = JsonEmitter::new(
+            Box::new(Shared { data: output.clone() }),
+            None,
+            sm,
+            None,
+            fallback_bundle,
+            false,
+            HumanReadableErrorType::Short(ColorConfig::Never),
+            None,
+            false,
+        );
+
+        let span = Span::with_root_ctxt(BytePos(0), BytePos(3));
+        let handler = Handler::with_emitter(true, None, Box::new(je));
+        handler.add_diagnostic_hook(|diag| {
+            if diag.code == Some(DiagnosticId::Error("E0001".to_owned())) {
+                diag.note("see the team wiki");
+            }
+        });
+        handler
+            .struct_span_err_with_code(span, "foo", DiagnosticId::Error("E0001".to_owned()))
+            .emit();
+        handler.span_err(span, "bar");
+
+        let bytes = output.lock().unwrap();
+        let actual_output = str::from_utf8(&bytes).unwrap();
+        let mut lines = actual_output.lines();
+        let first: ChildrenTestData = serde_json::from_str(lines.next().unwrap()).unwrap();
+        let second: ChildrenTestData = serde_json::from_str(lines.next().unwrap()).unwrap();
+        let note = ChildTestData { message: "see the team wiki".to_owned() };
+        assert_eq!(first.children, vec![note]);
+        assert!(second.children.is_empty());
+    })
+}
@@ -18,7 +18,7 @@
 #[macro_use]
 extern crate tracing;
 
-pub use emitter::ColorConfig;
+pub use emitter::{ColorConfig, TerminalUrl};
 
 use rustc_lint_defs::LintExpectationId;
 use Level::*;
@@ -37,7 +37,7 @@
 pub use rustc_lint_defs::{pluralize, Applicability};
 use rustc_span::source_map::SourceMap;
 use rustc_span::HashStableContext;
-use rustc_span::{Loc, Span};
+use rustc_span::{BytePos, Loc, Span};
 
 use std::borrow::Cow;
 use std::hash::Hash;
@@ -55,9 +55,13 @@
 pub mod json;
 mod lock;
 pub mod registry;
+pub mod sarif;
 mod snippet;
 mod styled_buffer;
 
+#[cfg(test)]
+mod tests;
+
 pub use snippet::Style;
 
 pub type PResult<'a, T> = Result<T, DiagnosticBuilder<'a, ErrorGuaranteed>>;
@@ -130,6 +134,11 @@ pub struct CodeSuggestion {
 /// See the docs on `CodeSuggestion::substitutions`
 pub struct Substitution {
     pub parts: Vec<SubstitutionPart>,
+    /// The applicability of this alternative on its own. This is only set for the alternatives
+    /// of a ranked suggestion (see `Diagnostic::ranked_suggestions`), which are ordered from the
+    /// most to the least preferred one; other alternatives share the suggestion's applicability
+    /// and are in no particular order.
+    pub applicability: Option<Applicability>,
 }
 
 #[derive(Clone, Debug, PartialEq, Hash, Encodable, Decodable)]
@@ -167,6 +176,11 @@ pub fn is_replacement(&self, sm: &SourceMap) -> bool {
 }
 
 impl CodeSuggestion {
+    /// Returns the applicability of `substitution`, one of the alternatives of this suggestion.
+    pub fn applicability_of(&self, substitution: &Substitution) -> Applicability {
+        substitution.applicability.unwrap_or(self.applicability)
+    }
+
     /// Returns the assembled code suggestions, whether they should be shown with an underline
     /// and whether the substitution only differs in capitalization.
     pub fn splice_lines(
@@ -370,7 +384,7 @@ impl error::Error for ExplicitBug {}
 
 pub use diagnostic::{
     AddSubdiagnostic, Diagnostic, DiagnosticArg, DiagnosticArgValue, DiagnosticId,
-    DiagnosticStyledString, IntoDiagnosticArg, SubDiagnostic,
+    DiagnosticStyledString, FutureBreakageProvenance, IntoDiagnosticArg, SubDiagnostic,
 };
 pub use diagnostic_builder::{DiagnosticBuilder, EmissionGuarantee};
 use std::backtrace::Backtrace;
@@ -414,6 +428,23 @@ struct HandlerInner {
     /// twice.
     emitted_diagnostics: FxHashSet<u128>,
 
+    /// Errors emitted while collecting mono items, keyed by a hash of their message, primary
+    /// span and code, to avoid reporting the same post-monomorphization error once per
+    /// instantiation. Later errors with the same key are suppressed and only summarized by
+    /// `print_error_count`.
+    emitted_errors: FxIndexMap<u128, DuplicateErrors>,
+
+    /// Whether errors are currently added to `emitted_errors`, see
+    /// `Handler::deduplicating_instantiation_errors`.
+    deduplicate_instantiation_errors: bool,
+
+    /// The key in `emitted_errors` of the last error, if it was suppressed as a duplicate.
+    last_duplicate_error: Option<u128>,
+
+    /// The number of errors that were not printed because of `-Z max-errors`, grouped by
+    /// error code and file.
+    truncated_errors: FxIndexMap<(Option<String>, Option<String>), usize>,
+
     /// Stashed diagnostics emitted in one stage of the compiler that may be
     /// stolen by other stages (e.g. to improve them and add more information).
     /// The stashed diagnostics count towards the total error count.
@@ -447,6 +478,22 @@ struct HandlerInner {
     ///
     /// [RFC-2383]: https://rust-lang.github.io/rfcs/2383-lint-reasons.html
     fulfilled_expectations: FxHashSet<LintExpectationId>,
+
+    /// Callbacks registered with [`Handler::add_diagnostic_hook`], run on every diagnostic
+    /// right before it is emitted.
+    diagnostic_hooks: Vec<DiagnosticHook>,
+}
+
+/// A callback which can inspect a diagnostic before it is emitted and append notes to it.
+pub type DiagnosticHook = Box<dyn FnMut(&mut Diagnostic) + sync::Send>;
+
+/// Errors that were suppressed because an error with the same message, primary span and code
+/// was already emitted.
+struct DuplicateErrors {
+    span: Span,
+    count: usize,
+    /// Notes about where the suppressed errors were encountered, listed with `-Z verbose`.
+    origins: Vec<(Span, String)>,
 }
 
 /// A key denoting where from a diagnostic was stashed.
@@ -479,6 +526,12 @@ pub struct HandlerFlags {
     pub macro_backtrace: bool,
     /// If true, identical diagnostics are reported only once.
     pub deduplicate_diagnostics: bool,
+    /// If true, list where the errors suppressed as duplicates were encountered.
+    /// (rustc: see `-Z verbose`)
+    pub list_duplicate_errors: bool,
+    /// If set, errors after the first `N` ones are only summarized.
+    /// (rustc: see `-Z max-errors`)
+    pub max_errors: Option<usize>,
 }
 
 impl Drop for HandlerInner {
@@ -580,11 +633,16 @@ pub fn with_emitter_and_flags(
                 taught_diagnostics: Default::default(),
                 emitted_diagnostic_codes: Default::default(),
                 emitted_diagnostics: Default::default(),
+                emitted_errors: Default::default(),
+                deduplicate_instantiation_errors: false,
+                last_duplicate_error: None,
+                truncated_errors: Default::default(),
                 stashed_diagnostics: Default::default(),
                 future_breakage_diagnostics: Vec::new(),
                 check_unstable_expect_diagnostics: false,
                 unstable_expect_diagnostics: Vec::new(),
                 fulfilled_expectations: Default::default(),
+                diagnostic_hooks: Vec::new(),
             }),
         }
     }
@@ -613,9 +671,23 @@ pub fn reset_err_count(&self) {
         inner.taught_diagnostics = Default::default();
         inner.emitted_diagnostic_codes = Default::default();
         inner.emitted_diagnostics = Default::default();
+        inner.emitted_errors = Default::default();
+        inner.last_duplicate_error = None;
+        inner.truncated_errors = Default::default();
         inner.stashed_diagnostics = Default::default();
     }
 
+    /// Registers a callback which is run on every diagnostic that is about to be emitted.
+    ///
+    /// This lets tools embedding rustc append their own notes, help messages or links to
+    /// diagnostics, e.g. depending on their error code (`Diagnostic::code`) or primary span,
+    /// without replacing the emitter. Drivers can register hooks from the `parse_sess_created`
+    /// callback of `rustc_interface::Config`. Hooks should only add children to the diagnostic
+    /// and not change its level, message or code.
+    pub fn add_diagnostic_hook(&self, hook: impl FnMut(&mut Diagnostic) + sync::Send + 'static) {
+        self.inner.borrow_mut().diagnostic_hooks.push(Box::new(hook));
+    }
+
     /// Stash a given diagnostic with the given `Span` and `StashKey` as the key for later stealing.
     pub fn stash_diagnostic(&self, span: Span, key: StashKey, diag: Diagnostic) {
         let mut inner = self.inner.borrow_mut();
@@ -654,6 +726,24 @@ pub fn struct_span_warn(
         result
     }
 
+    /// Construct a builder at the `Warning` level at the given `span` and with the `msg`.
+    /// The [`LintExpectationId`] is used for expected lint diagnostics, which should
+    /// also emit a warning due to the `force-warn` flag.
+    ///
+    /// Attempting to `.emit()` the builder will only emit if either:
+    /// * `can_emit_warnings` is `true`
+    /// * `is_force_warn` was set in `DiagnosticId::Lint`
+    pub fn struct_span_warn_with_expectation(
+        &self,
+        span: impl Into<MultiSpan>,
+        msg: impl Into<DiagnosticMessage>,
+        id: LintExpectationId,
+    ) -> DiagnosticBuilder<'_, ()> {
+        let mut result = self.struct_warn_with_expectation(msg, id);
+        result.set_span(span);
+        result
+    }
+
     /// Construct a builder at the `Allow` level at the given `span` and with the `msg`.
     pub fn struct_span_allow(
         &self,
@@ -684,7 +774,22 @@ pub fn struct_span_warn_with_code(
     /// * `can_emit_warnings` is `true`
     /// * `is_force_warn` was set in `DiagnosticId::Lint`
     pub fn struct_warn(&self, msg: impl Into<DiagnosticMessage>) -> DiagnosticBuilder<'_, ()> {
-        DiagnosticBuilder::new(self, Level::Warning, msg)
+        DiagnosticBuilder::new(self, Level::Warning(None), msg)
+    }
+
+    /// Construct a builder at the `Warning` level with the `msg`. The [`LintExpectationId`]
+    /// is used for expected lint diagnostics, which should also emit a warning due to the
+    /// `force-warn` flag.
+    ///
+    /// Attempting to `.emit()` the builder will only emit if either:
+    /// * `can_emit_warnings` is `true`
+    /// * `is_force_warn` was set in `DiagnosticId::Lint`
+    pub fn struct_warn_with_expectation(
+        &self,
+        msg: impl Into<DiagnosticMessage>,
+        id: LintExpectationId,
+    ) -> DiagnosticBuilder<'_, ()> {
+        DiagnosticBuilder::new(self, Level::Warning(Some(id)), msg)
     }
 
     /// Construct a builder at the `Allow` level with the `msg`.
@@ -838,7 +943,7 @@ pub fn span_err_with_code(
     }
 
     pub fn span_warn(&self, span: impl Into<MultiSpan>, msg: impl Into<DiagnosticMessage>) {
-        self.emit_diag_at_span(Diagnostic::new(Warning, msg), span);
+        self.emit_diag_at_span(Diagnostic::new(Warning(None), msg), span);
     }
 
     pub fn span_warn_with_code(
@@ -847,7 +952,7 @@ pub fn span_warn_with_code(
         msg: impl Into<DiagnosticMessage>,
         code: DiagnosticId,
     ) {
-        self.emit_diag_at_span(Diagnostic::new_with_code(Warning, Some(code), msg), span);
+        self.emit_diag_at_span(Diagnostic::new_with_code(Warning(None), Some(code), msg), span);
     }
 
     pub fn span_bug(&self, span: impl Into<MultiSpan>, msg: impl Into<DiagnosticMessage>) -> ! {
@@ -881,6 +986,32 @@ pub fn span_note_without_error(
         self.emit_diag_at_span(Diagnostic::new(Note, msg), span);
     }
 
+    /// Runs `f`, reporting errors that only differ from an earlier one in their labels, notes
+    /// and suggestions once. This is meant for the mono item collector, which hits the same
+    /// post-monomorphization error once per instantiation; errors emitted outside of `f` are
+    /// never merged this way.
+    pub fn deduplicating_instantiation_errors<R>(&self, f: impl FnOnce() -> R) -> R {
+        let prev = std::mem::replace(
+            &mut self.inner.borrow_mut().deduplicate_instantiation_errors,
+            true,
+        );
+        let result = f();
+        self.inner.borrow_mut().deduplicate_instantiation_errors = prev;
+        result
+    }
+
+    /// Emits a note about where the last error was encountered, e.g. the instantiation that
+    /// triggered a post-monomorphization error. If that error was suppressed as a duplicate of
+    /// an earlier one, the note is kept to be listed with `-Z verbose` instead.
+    pub fn span_note_error_origin(&self, span: Span, msg: impl Into<String>) {
+        let mut inner = self.inner.borrow_mut();
+        if let Some(key) = inner.last_duplicate_error {
+            inner.emitted_errors[&key].origins.push((span, msg.into()));
+        } else {
+            inner.emit_diag_at_span(Diagnostic::new(Note, msg.into()), span);
+        }
+    }
+
     pub fn span_note_diag(
         &self,
         span: Span,
@@ -901,7 +1032,7 @@ pub fn err(&self, msg: impl Into<DiagnosticMessage>) -> ErrorGuaranteed {
     }
 
     pub fn warn(&self, msg: impl Into<DiagnosticMessage>) {
-        let mut db = DiagnosticBuilder::new(self, Warning, msg);
+        let mut db = DiagnosticBuilder::new(self, Warning(None), msg);
         db.emit();
     }
 
@@ -1013,7 +1144,10 @@ pub fn update_unstable_expectation_id(
                 .expect("all diagnostics inside `unstable_expect_diagnostics` must have a `LintExpectationId`");
             inner.fulfilled_expectations.insert(stable_id);
 
-            (*TRACK_DIAGNOSTICS)(&diag);
+            // Force-warned diagnostics have already been tracked when they were emitted.
+            if let Level::Expect(_) = diag.level {
+                (*TRACK_DIAGNOSTICS)(&diag);
+            }
         }
 
         inner
@@ -1078,7 +1212,7 @@ fn emit_diagnostic(&mut self, diagnostic: &mut Diagnostic) -> Option<ErrorGuaran
             self.future_breakage_diagnostics.push(diagnostic.clone());
         }
 
-        if diagnostic.level == Warning
+        if matches!(diagnostic.level, Warning(_))
             && !self.flags.can_emit_warnings
             && !diagnostic.is_force_warn()
         {
@@ -1091,10 +1225,13 @@ fn emit_diagnostic(&mut self, diagnostic: &mut Diagnostic) -> Option<ErrorGuaran
         // The `LintExpectationId` can be stable or unstable depending on when it was created.
         // Diagnostics created before the definition of `HirId`s are unstable and can not yet
         // be stored. Instead, they are buffered until the `LintExpectationId` is replaced by
-        // a stable one by the `LintLevelsBuilder`.
-        if let Level::Expect(LintExpectationId::Unstable { .. }) = diagnostic.level {
+        // a stable one by the `LintLevelsBuilder`. Force-warned lints are still emitted right
+        // away, only the fulfillment of their expectation is delayed.
+        if let Some(LintExpectationId::Unstable { .. }) = diagnostic.level.get_expectation_id() {
             self.unstable_expect_diagnostics.push(diagnostic.clone());
-            return None;
+            if let Level::Expect(_) = diagnostic.level {
+                return None;
+            }
         }
 
         (*TRACK_DIAGNOSTICS)(diagnostic);
@@ -1104,12 +1241,24 @@ fn emit_diagnostic(&mut self, diagnostic: &mut Diagnostic) -> Option<ErrorGuaran
             return None;
         } else if diagnostic.level == Allow {
             return None;
+        } else if let Warning(Some(expectation_id)) = diagnostic.level {
+            if expectation_id.is_stable() {
+                self.fulfilled_expectations.insert(expectation_id);
+            }
+        }
+
+        for hook in &mut self.diagnostic_hooks {
+            hook(diagnostic);
         }
 
         if let Some(ref code) = diagnostic.code {
             self.emitted_diagnostic_codes.insert(code.clone());
         }
 
+        if diagnostic.is_error() {
+            self.last_duplicate_error = None;
+        }
+
         let already_emitted = |this: &mut Self| {
             let mut hasher = StableHasher::new();
             diagnostic.hash(&mut hasher);
@@ -1119,7 +1268,9 @@ fn emit_diagnostic(&mut self, diagnostic: &mut Diagnostic) -> Option<ErrorGuaran
 
         // Only emit the diagnostic if we've been asked to deduplicate and
         // haven't already emitted an equivalent diagnostic.
-        if !(self.flags.deduplicate_diagnostics && already_emitted(self)) {
+        if !(self.flags.deduplicate_diagnostics
+            && (already_emitted(self) || self.is_duplicate_instantiation_error(diagnostic)))
+        {
             debug!(?diagnostic);
             debug!(?self.emitted_diagnostics);
             let already_emitted_sub = |sub: &mut SubDiagnostic| {
@@ -1136,10 +1287,12 @@ fn emit_diagnostic(&mut self, diagnostic: &mut Diagnostic) -> Option<ErrorGuaran
 
             diagnostic.children.drain_filter(already_emitted_sub).for_each(|_| {});
 
-            self.emitter.emit_diagnostic(&diagnostic);
+            if !self.truncate(diagnostic) {
+                self.emitter.emit_diagnostic(&diagnostic);
+            }
             if diagnostic.is_error() {
                 self.deduplicated_err_count += 1;
-            } else if diagnostic.level == Warning {
+            } else if matches!(diagnostic.level, Warning(_)) {
                 self.deduplicated_warn_count += 1;
             }
         }
@@ -1158,6 +1311,116 @@ fn emit_diagnostic(&mut self, diagnostic: &mut Diagnostic) -> Option<ErrorGuaran
         }
     }
 
+    /// Checks whether `diagnostic` is an error emitted while collecting mono items, and an error
+    /// with the same message, primary span and code was already emitted there. If so,
+    /// `diagnostic` is only counted towards the summary of that error.
+    fn is_duplicate_instantiation_error(&mut self, diagnostic: &Diagnostic) -> bool {
+        let Some(span) = diagnostic.span.primary_span() else { return false };
+        if !self.deduplicate_instantiation_errors || !diagnostic.is_error() {
+            return false;
+        }
+
+        let mut hasher = StableHasher::new();
+        (&diagnostic.message, span, &diagnostic.code).hash(&mut hasher);
+        let key = hasher.finish();
+        if let Some(duplicates) = self.emitted_errors.get_mut(&key) {
+            duplicates.count += 1;
+            self.last_duplicate_error = Some(key);
+            return true;
+        }
+        self.emitted_errors.insert(key, DuplicateErrors { span, count: 0, origins: Vec::new() });
+        false
+    }
+
+    /// Checks whether `diagnostic` should not be printed because of `-Z max-errors`, and if it is
+    /// an error, counts it towards the summary printed by `print_error_count`. Notes and help
+    /// messages are dropped too after errors were truncated, as they refer to those errors.
+    fn truncate(&mut self, diagnostic: &Diagnostic) -> bool {
+        match diagnostic.level {
+            Level::Error { .. } => {}
+            Level::Note | Level::OnceNote | Level::Help => {
+                return !self.truncated_errors.is_empty();
+            }
+            _ => return false,
+        }
+        let Some(max_errors) = self.flags.max_errors else { return false };
+        if self.deduplicated_err_count < max_errors {
+            return false;
+        }
+
+        let code = diagnostic.code.as_ref().map(|code| match code {
+            DiagnosticId::Error(code) => code.clone(),
+            DiagnosticId::Lint { name, .. } => name.clone(),
+        });
+        let file = diagnostic.span.primary_span().and_then(|span| {
+            let sm = self.emitter.source_map()?;
+            let file = sm.lookup_source_file(span.lo());
+            Some(sm.filename_for_diagnostics(&file.name).to_string())
+        });
+        *self.truncated_errors.entry((code, file)).or_default() += 1;
+        true
+    }
+
+    /// Emits a note summarizing the errors that were not printed because of `-Z max-errors`.
+    fn emit_truncated_errors_summary(&mut self) {
+        let truncated_errors = std::mem::take(&mut self.truncated_errors);
+        let Some(max_errors) = self.flags.max_errors.take() else { return };
+        if truncated_errors.is_empty() {
+            return;
+        }
+
+        let count: usize = truncated_errors.values().sum();
+        let mut diag = Diagnostic::new(
+            Note,
+            format!(
+                "{} more error{} not printed because of `-Z max-errors={}`",
+                count,
+                if count == 1 { " was" } else { "s were" },
+                max_errors,
+            ),
+        );
+        for ((code, file), count) in truncated_errors {
+            let code = match code {
+                Some(code) => format!(" with code `{}`", code),
+                None => String::new(),
+            };
+            let file = match file {
+                Some(file) => format!(" in {}", file),
+                None => String::new(),
+            };
+            diag.note(format!("{} error{}{}{}", count, pluralize!(count), code, file));
+        }
+        diag.help("use `--error-format=json` to get every error");
+        self.emit_diagnostic(&mut diag);
+    }
+
+    /// Emits a note for every error that was suppressed as a duplicate, saying how many times
+    /// it was triggered again.
+    fn emit_duplicate_error_summaries(&mut self) {
+        for (_, duplicates) in std::mem::take(&mut self.emitted_errors) {
+            if duplicates.count == 0 {
+                continue;
+            }
+            let mut diag = Diagnostic::new(
+                Note,
+                format!(
+                    "this error was also triggered {} more time{} by other instantiations or items",
+                    duplicates.count,
+                    pluralize!(duplicates.count),
+                ),
+            );
+            if self.flags.list_duplicate_errors {
+                for (span, msg) in duplicates.origins {
+                    diag.span_note(span, msg);
+                }
+            } else if !duplicates.origins.is_empty() {
+                diag.note("run with `-Z verbose` to see where they were encountered");
+            }
+            self.emit_diag_at_span(diag, duplicates.span);
+        }
+        self.last_duplicate_error = None;
+    }
+
     fn emit_artifact_notification(&mut self, path: &Path, artifact_type: &str) {
         self.emitter.emit_artifact_notification(path, artifact_type);
     }
@@ -1174,6 +1437,8 @@ fn treat_err_as_bug(&self) -> bool {
 
     fn print_error_count(&mut self, registry: &Registry) {
         self.emit_stashed_diagnostics();
+        self.emit_truncated_errors_summary();
+        self.emit_duplicate_error_summaries();
 
         let warnings = match self.deduplicated_warn_count {
             0 => String::new(),
@@ -1192,7 +1457,7 @@ fn print_error_count(&mut self, registry: &Registry) {
         match (errors.len(), warnings.len()) {
             (0, 0) => return,
             (0, _) => self.emitter.emit_diagnostic(&Diagnostic::new(
-                Level::Warning,
+                Level::Warning(None),
                 DiagnosticMessage::Str(warnings),
             )),
             (_, 0) => {
@@ -1425,7 +1690,10 @@ pub enum Level {
         /// If this error comes from a lint, don't abort compilation even when abort_if_errors() is called.
         lint: bool,
     },
-    Warning,
+    /// This [`LintExpectationId`] is used for expected lint diagnostics, which should
+    /// also emit a warning due to the `force-warn` flag. In all other cases this should
+    /// be `None`.
+    Warning(Option<LintExpectationId>),
     Note,
     /// A note that is only emitted once.
     OnceNote,
@@ -1448,7 +1716,7 @@ fn color(self) -> ColorSpec {
             Bug | DelayedBug | Fatal | Error { .. } => {
                 spec.set_fg(Some(Color::Red)).set_intense(true);
             }
-            Warning => {
+            Warning(_) => {
                 spec.set_fg(Some(Color::Yellow)).set_intense(cfg!(windows));
             }
             Note | OnceNote => {
@@ -1467,7 +1735,7 @@ pub fn to_str(self) -> &'static str {
         match self {
             Bug | DelayedBug => "error: internal compiler error",
             Fatal | Error { .. } => "error",
-            Warning => "warning",
+            Warning(_) => "warning",
             Note | OnceNote => "note",
             Help => "help",
             FailureNote => "failure-note",
@@ -1482,13 +1750,17 @@ pub fn is_failure_note(&self) -> bool {
 
     pub fn get_expectation_id(&self) -> Option<LintExpectationId> {
         match self {
-            Level::Expect(id) => Some(*id),
+            Level::Expect(id) | Level::Warning(Some(id)) => Some(*id),
             _ => None,
         }
     }
 }
 
 // FIXME(eddyb) this doesn't belong here AFAICT, should be moved to callsite.
+/// Suggests `'_` for the `n` lifetimes elided in the path at `path_span`.
+///
+/// If `incl_angl_brckt` is set the path segment has no generic arguments and `insertion_span` is
+/// its identifier, otherwise `insertion_span` covers the generic arguments, brackets included.
 pub fn add_elided_lifetime_in_path_suggestion(
     source_map: &SourceMap,
     diag: &mut Diagnostic,
@@ -1498,15 +1770,25 @@ pub fn add_elided_lifetime_in_path_suggestion(
     insertion_span: Span,
 ) {
     diag.span_label(path_span, format!("expected lifetime parameter{}", pluralize!(n)));
-    if source_map.span_to_snippet(insertion_span).is_err() {
+    let Ok(snippet) = source_map.span_to_snippet(insertion_span) else {
         // Do not try to suggest anything if generated by a proc-macro.
         return;
-    }
+    };
     let anon_lts = vec!["'_"; n].join(", ");
-    let suggestion =
-        if incl_angl_brckt { format!("<{}>", anon_lts) } else { format!("{}, ", anon_lts) };
+    let (insertion_span, suggestion) = if incl_angl_brckt {
+        (insertion_span.shrink_to_hi(), format!("<{}>", anon_lts))
+    } else {
+        // Insert right after the opening bracket, which is a single byte even when it was
+        // lexed as part of a `<<` token.
+        let span = insertion_span.with_lo(insertion_span.lo() + BytePos(1)).shrink_to_lo();
+        let no_args = snippet
+            .strip_prefix('<')
+            .and_then(|args| args.strip_suffix('>'))
+            .map_or(false, |args| args.trim().is_empty());
+        (span, if no_args { anon_lts } else { format!("{}, ", anon_lts) })
+    };
     diag.span_suggestion_verbose(
-        insertion_span.shrink_to_hi(),
+        insertion_span,
         &format!("indicate the anonymous lifetime{}", pluralize!(n)),
         suggestion,
         Applicability::MachineApplicable,
@@ -0,0 +1,415 @@
+//! A SARIF emitter for errors, used for `--error-format=sarif`.
+//!
+//! SARIF (the Static Analysis Results Interchange Format) is what GitHub code scanning and
+//! other tools that collect the results of static analyzers consume. See
+//! <https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html> for the format.
+//!
+//! Unlike the JSON output, a SARIF log is a single document. The diagnostics are therefore
+//! collected as they are emitted, and the log is only written when the emitter is dropped.
+
+use rustc_span::source_map::{FilePathMapping, SourceMap};
+
+use crate::emitter::Emitter;
+use crate::registry::Registry;
+use crate::{CodeSuggestion, DiagnosticId, FluentBundle, LazyFallbackBundle, Level, MultiSpan};
+
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_data_structures::sync::Lrc;
+use rustc_error_messages::FluentArgs;
+use rustc_span::Span;
+use std::io::{self, Write};
+use std::mem;
+
+use serde::Serialize;
+
+#[cfg(test)]
+mod tests;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+pub struct SarifEmitter {
+    dst: Box<dyn Write + Send>,
+    registry: Option<Registry>,
+    sm: Lrc<SourceMap>,
+    fluent_bundle: Option<Lrc<FluentBundle>>,
+    fallback_bundle: LazyFallbackBundle,
+    /// The rules that the results refer to, keyed by their ID, in the order in which they
+    /// were first used.
+    rules: FxIndexMap<String, ReportingDescriptor>,
+    results: Vec<SarifResult>,
+}
+
+impl SarifEmitter {
+    pub fn stderr(
+        registry: Option<Registry>,
+        source_map: Lrc<SourceMap>,
+        fluent_bundle: Option<Lrc<FluentBundle>>,
+        fallback_bundle: LazyFallbackBundle,
+    ) -> SarifEmitter {
+        SarifEmitter::new(
+            Box::new(io::BufWriter::new(io::stderr())),
+            registry,
+            source_map,
+            fluent_bundle,
+            fallback_bundle,
+        )
+    }
+
+    pub fn basic(
+        fluent_bundle: Option<Lrc<FluentBundle>>,
+        fallback_bundle: LazyFallbackBundle,
+    ) -> SarifEmitter {
+        let file_path_mapping = FilePathMapping::empty();
+        SarifEmitter::stderr(
+            None,
+            Lrc::new(SourceMap::new(file_path_mapping)),
+            fluent_bundle,
+            fallback_bundle,
+        )
+    }
+
+    pub fn new(
+        dst: Box<dyn Write + Send>,
+        registry: Option<Registry>,
+        source_map: Lrc<SourceMap>,
+        fluent_bundle: Option<Lrc<FluentBundle>>,
+        fallback_bundle: LazyFallbackBundle,
+    ) -> SarifEmitter {
+        SarifEmitter {
+            dst,
+            registry,
+            sm: source_map,
+            fluent_bundle,
+            fallback_bundle,
+            rules: Default::default(),
+            results: vec![],
+        }
+    }
+
+    /// Returns the ID of the rule for `code`, and its index in the rules of the run. The
+    /// rule is added to the run when it is first used.
+    fn rule(&mut self, code: &DiagnosticId) -> (String, usize) {
+        let id = match code {
+            DiagnosticId::Error(code) => code.clone(),
+            DiagnosticId::Lint { name, .. } => name.clone(),
+        };
+        if let Some(index) = self.rules.get_index_of(&id) {
+            return (id, index);
+        }
+
+        let descriptor = match code {
+            DiagnosticId::Error(code) => ReportingDescriptor {
+                id: id.clone(),
+                help_uri: Some(format!("https://doc.rust-lang.org/error-index.html#{}", code)),
+                full_description: self
+                    .registry
+                    .as_ref()
+                    .and_then(|registry| registry.try_find_description(code).ok().flatten())
+                    .map(|explanation| Message { text: explanation.to_string() }),
+            },
+            DiagnosticId::Lint { .. } => {
+                ReportingDescriptor { id: id.clone(), help_uri: None, full_description: None }
+            }
+        };
+        let (index, _) = self.rules.insert_full(id.clone(), descriptor);
+        (id, index)
+    }
+
+    /// Adds a location for every labelled span of `msp`. Primary spans become locations of
+    /// the result, the others related locations.
+    fn add_locations(
+        &self,
+        msp: &MultiSpan,
+        args: &FluentArgs<'_>,
+        locations: &mut Vec<Location>,
+        related_locations: &mut Vec<Location>,
+    ) {
+        for span_label in msp.span_labels() {
+            if span_label.span.is_dummy() {
+                continue;
+            }
+            let label = span_label.label.as_ref().map(|m| self.translate_message(m, args));
+            let location = self.location(span_label.span, label.map(|m| m.into_owned()));
+            if span_label.is_primary {
+                locations.push(location);
+            } else {
+                related_locations.push(location);
+            }
+        }
+    }
+
+    fn location(&self, span: Span, message: Option<String>) -> Location {
+        let (artifact_location, region) = self.region(span);
+        Location {
+            physical_location: PhysicalLocation { artifact_location, region },
+            message: message.map(|text| Message { text }),
+        }
+    }
+
+    fn region(&self, span: Span) -> (ArtifactLocation, Region) {
+        let start = self.sm.lookup_char_pos(span.lo());
+        let end = self.sm.lookup_char_pos(span.hi());
+        let file_name = self.sm.filename_for_diagnostics(&start.file.name).to_string();
+        let byte_offset = start.file.original_relative_byte_pos(span.lo()).0;
+        let byte_end = start.file.original_relative_byte_pos(span.hi()).0;
+        let region = Region {
+            start_line: start.line,
+            start_column: start.col.0 + 1,
+            end_line: end.line,
+            end_column: end.col.0 + 1,
+            byte_offset,
+            byte_length: byte_end - byte_offset,
+        };
+        (ArtifactLocation { uri: file_name.replace('\\', "/") }, region)
+    }
+
+    /// Returns a fix for every alternative of `suggestion`.
+    fn fixes(&self, suggestion: &CodeSuggestion, args: &FluentArgs<'_>) -> Vec<Fix> {
+        let description = self.translate_message(&suggestion.msg, args).into_owned();
+        suggestion
+            .substitutions
+            .iter()
+            .map(|substitution| {
+                let mut artifact_changes: Vec<ArtifactChange> = vec![];
+                for part in &substitution.parts {
+                    let (artifact_location, deleted_region) = self.region(part.span);
+                    let replacement = Replacement {
+                        deleted_region,
+                        inserted_content: ArtifactContent { text: part.snippet.clone() },
+                    };
+                    match artifact_changes
+                        .iter_mut()
+                        .find(|change| change.artifact_location == artifact_location)
+                    {
+                        Some(change) => change.replacements.push(replacement),
+                        None => artifact_changes.push(ArtifactChange {
+                            artifact_location,
+                            replacements: vec![replacement],
+                        }),
+                    }
+                }
+                Fix { description: Message { text: description.clone() }, artifact_changes }
+            })
+            .collect()
+    }
+}
+
+impl Emitter for SarifEmitter {
+    fn emit_diagnostic(&mut self, diag: &crate::Diagnostic) {
+        let level = match diag.level {
+            Level::Bug | Level::DelayedBug | Level::Fatal | Level::Error { .. } => "error",
+            Level::Warning(_) => "warning",
+            Level::Note | Level::OnceNote | Level::Help => "note",
+            // Summaries like "aborting due to previous error" aren't results of their own.
+            Level::FailureNote | Level::Allow | Level::Expect(_) => return,
+        };
+
+        let args = self.to_fluent_args(diag.args());
+        let mut message = self.translate_messages(&diag.message, &args).into_owned();
+        let mut locations = vec![];
+        let mut related_locations = vec![];
+        self.add_locations(&diag.span, &args, &mut locations, &mut related_locations);
+
+        // Notes and help messages with a span become related locations, the others are
+        // appended to the message, like they are in the human readable output.
+        for child in &diag.children {
+            let child_message = self.translate_messages(&child.message, &args);
+            let span = child.render_span.as_ref().unwrap_or(&child.span);
+            match span.primary_span() {
+                Some(primary_span) if !primary_span.is_dummy() => {
+                    let text = format!("{}: {}", child.level, child_message);
+                    related_locations.push(self.location(primary_span, Some(text)));
+                }
+                _ => message.push_str(&format!("\n{}: {}", child.level, child_message)),
+            }
+        }
+
+        let fixes = diag
+            .suggestions
+            .iter()
+            .flatten()
+            .flat_map(|suggestion| self.fixes(suggestion, &args))
+            .collect();
+
+        let (rule_id, rule_index) = match &diag.code {
+            Some(code) => {
+                let (rule_id, rule_index) = self.rule(code);
+                (Some(rule_id), Some(rule_index))
+            }
+            None => (None, None),
+        };
+
+        self.results.push(SarifResult {
+            rule_id,
+            rule_index,
+            level,
+            message: Message { text: message },
+            locations,
+            related_locations,
+            fixes,
+        });
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        Some(&self.sm)
+    }
+
+    fn fluent_bundle(&self) -> Option<&Lrc<FluentBundle>> {
+        self.fluent_bundle.as_ref()
+    }
+
+    fn fallback_fluent_bundle(&self) -> &FluentBundle {
+        &**self.fallback_bundle
+    }
+}
+
+impl Drop for SarifEmitter {
+    fn drop(&mut self) {
+        let log = SarifLog {
+            schema: SARIF_SCHEMA,
+            version: "2.1.0",
+            runs: vec![Run {
+                tool: Tool {
+                    driver: ToolComponent {
+                        name: "rustc",
+                        information_uri: "https://www.rust-lang.org/",
+                        rules: mem::take(&mut self.rules).into_values().collect(),
+                    },
+                },
+                column_kind: "unicodeCodePoints",
+                results: mem::take(&mut self.results),
+            }],
+        };
+        // There's nobody left to report a failure to at this point.
+        let _ = writeln!(&mut self.dst, "{}", serde_json::to_string(&log).unwrap())
+            .and_then(|_| self.dst.flush());
+    }
+}
+
+// The following data types are provided just for serialisation. They only contain the
+// parts of the SARIF object model that rustc fills in.
+
+#[derive(Serialize)]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run {
+    tool: Tool,
+    /// How the columns of regions are counted.
+    column_kind: &'static str,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+struct Tool {
+    driver: ToolComponent,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ToolComponent {
+    name: &'static str,
+    information_uri: &'static str,
+    rules: Vec<ReportingDescriptor>,
+}
+
+/// A rule, i.e. an error code or a lint.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportingDescriptor {
+    id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help_uri: Option<String>,
+    /// The explanation of an error code.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    full_description: Option<Message>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_index: Option<usize>,
+    /// "error", "warning" or "note".
+    level: &'static str,
+    message: Message,
+    locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related_locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fixes: Vec<Fix>,
+}
+
+#[derive(Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+    /// The label of the span.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<Message>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    region: Region,
+}
+
+#[derive(Serialize, PartialEq)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    /// 1-based.
+    start_line: usize,
+    /// 1-based, character offset.
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+    byte_offset: u32,
+    byte_length: u32,
+}
+
+/// A structured suggestion.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Fix {
+    description: Message,
+    artifact_changes: Vec<ArtifactChange>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactChange {
+    artifact_location: ArtifactLocation,
+    replacements: Vec<Replacement>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Replacement {
+    deleted_region: Region,
+    inserted_content: ArtifactContent,
+}
+
+#[derive(Serialize)]
+struct ArtifactContent {
+    text: String,
+}
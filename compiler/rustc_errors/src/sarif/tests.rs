@@ -0,0 +1,125 @@
+use super::*;
+
+use crate::{Applicability, Handler};
+use rustc_span::{BytePos, Span};
+
+use std::path::Path;
+use std::str;
+use std::sync::{Arc, Mutex};
+
+struct Shared<T> {
+    data: Arc<Mutex<T>>,
+}
+
+impl<T: Write> Write for Shared<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.data.lock().unwrap().flush()
+    }
+}
+
+/// Compiles `code` with the diagnostics emitted by `f`, and returns the SARIF log.
+fn test_sarif(code: &str, f: impl FnOnce(&Handler)) -> serde_json::Value {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), code.to_owned());
+        let fallback_bundle =
+            crate::fallback_fluent_bundle(rustc_error_messages::DEFAULT_LOCALE_RESOURCES, false);
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let se = SarifEmitter::new(
+            Box::new(Shared { data: output.clone() }),
+            None,
+            sm,
+            None,
+            fallback_bundle,
+        );
+
+        let handler = Handler::with_emitter(true, None, Box::new(se));
+        f(&handler);
+        // The log is only written when the emitter is dropped.
+        drop(handler);
+
+        let bytes = output.lock().unwrap();
+        serde_json::from_str(str::from_utf8(&bytes).unwrap()).unwrap()
+    })
+}
+
+fn span(lo: u32, hi: u32) -> Span {
+    Span::with_root_ctxt(BytePos(lo), BytePos(hi))
+}
+
+#[test]
+fn results_and_rules() {
+    let log = test_sarif("fn main() {}\nfn main() {}\n", |handler| {
+        let code = DiagnosticId::Error("E0428".to_owned());
+        handler.struct_span_warn(span(3, 7), "first").code(code.clone()).emit();
+        handler.struct_span_warn(span(16, 20), "second").code(code).emit();
+        handler.struct_span_warn(span(16, 20), "third").emit();
+    });
+
+    assert_eq!(log["version"], "2.1.0");
+    let run = &log["runs"][0];
+    let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0]["id"], "E0428");
+    assert_eq!(rules[0]["helpUri"], "https://doc.rust-lang.org/error-index.html#E0428");
+
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[1]["level"], "warning");
+    assert_eq!(results[1]["message"]["text"], "second");
+    assert_eq!(results[1]["ruleId"], "E0428");
+    assert_eq!(results[1]["ruleIndex"], 0);
+    assert!(results[2].get("ruleId").is_none());
+
+    let location = &results[1]["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "test.rs");
+    assert_eq!(
+        location["region"],
+        serde_json::json!({
+            "startLine": 2,
+            "startColumn": 4,
+            "endLine": 2,
+            "endColumn": 8,
+            "byteOffset": 16,
+            "byteLength": 4,
+        })
+    );
+}
+
+#[test]
+fn related_locations_and_fixes() {
+    let log = test_sarif("let x = 1;\nx = 2;\n", |handler| {
+        handler
+            .struct_span_warn(span(11, 16), "cannot assign twice")
+            .span_label(span(11, 16), "cannot assign twice to immutable variable")
+            .span_label(span(4, 5), "first assignment")
+            .span_suggestion(
+                span(4, 5),
+                "consider making this binding mutable",
+                "mut x",
+                Applicability::MachineApplicable,
+            )
+            .note("see the book")
+            .emit();
+    });
+
+    let result = &log["runs"][0]["results"][0];
+    assert_eq!(result["message"]["text"], "cannot assign twice\nnote: see the book");
+    assert_eq!(
+        result["locations"][0]["message"]["text"],
+        "cannot assign twice to immutable variable"
+    );
+    assert_eq!(result["relatedLocations"][0]["message"]["text"], "first assignment");
+
+    let fix = &result["fixes"][0];
+    assert_eq!(fix["description"]["text"], "consider making this binding mutable");
+    let change = &fix["artifactChanges"][0];
+    assert_eq!(change["artifactLocation"]["uri"], "test.rs");
+    assert_eq!(change["replacements"][0]["deletedRegion"]["byteOffset"], 4);
+    assert_eq!(change["replacements"][0]["insertedContent"]["text"], "mut x");
+}
@@ -0,0 +1,82 @@
+use super::*;
+
+use rustc_span::BytePos;
+
+use std::sync::{Arc, Mutex};
+
+/// Collects the messages of the notes attached to every emitted diagnostic.
+struct RecordingEmitter {
+    notes: Arc<Mutex<Vec<Vec<String>>>>,
+    fallback_bundle: LazyFallbackBundle,
+}
+
+impl Emitter for RecordingEmitter {
+    fn emit_diagnostic(&mut self, diag: &Diagnostic) {
+        let notes = diag
+            .children
+            .iter()
+            .map(|child| child.message.iter().map(|(msg, _)| format!("{:?}", msg)).collect())
+            .collect();
+        self.notes.lock().unwrap().push(notes);
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        None
+    }
+
+    fn fluent_bundle(&self) -> Option<&Lrc<FluentBundle>> {
+        None
+    }
+
+    fn fallback_fluent_bundle(&self) -> &FluentBundle {
+        &**self.fallback_bundle
+    }
+}
+
+/// Emits the same error twice with a different note, optionally while collecting mono items,
+/// and returns the notes of the errors that were printed.
+fn emit_twice_with_different_notes(while_collecting_mono_items: bool) -> Vec<Vec<String>> {
+    rustc_span::create_default_session_globals_then(|| {
+        let notes = Arc::new(Mutex::new(Vec::new()));
+        let emitter = RecordingEmitter {
+            notes: notes.clone(),
+            fallback_bundle: fallback_fluent_bundle(DEFAULT_LOCALE_RESOURCES, false),
+        };
+        let handler = Handler::with_emitter_and_flags(
+            Box::new(emitter),
+            HandlerFlags {
+                can_emit_warnings: true,
+                deduplicate_diagnostics: true,
+                ..Default::default()
+            },
+        );
+
+        let span = Span::with_root_ctxt(BytePos(0), BytePos(3));
+        let emit = || {
+            handler.struct_span_err(span, "foo").note("first").emit();
+            handler.struct_span_err(span, "foo").note("second").emit();
+        };
+        if while_collecting_mono_items {
+            handler.deduplicating_instantiation_errors(emit);
+        } else {
+            emit();
+        }
+        assert_eq!(handler.err_count(), 2);
+
+        let notes = notes.lock().unwrap().clone();
+        notes
+    })
+}
+
+#[test]
+fn same_message_and_span_with_different_notes_are_kept() {
+    let notes = emit_twice_with_different_notes(false);
+    assert_eq!(notes.len(), 2);
+    assert_ne!(notes[0], notes[1]);
+}
+
+#[test]
+fn duplicate_instantiation_errors_are_merged() {
+    let notes = emit_twice_with_different_notes(true);
+    assert_eq!(notes.len(), 1);
+}
@@ -25,6 +25,7 @@
 
 use std::default::Default;
 use std::iter;
+use std::ops::Range;
 use std::path::PathBuf;
 use std::rc::Rc;
 
@@ -708,6 +709,12 @@ pub struct SyntaxExtension {
     /// Built-in macros have a couple of special properties like availability
     /// in `#[no_implicit_prelude]` modules, so we have to keep this flag.
     pub builtin_name: Option<Symbol>,
+    /// Whether this attribute macro receives its input with `#[cfg]` and `#[cfg_attr]` already
+    /// evaluated, as requested with `#[proc_macro_attribute(cfg_eval)]`.
+    pub cfg_eval_input: bool,
+    /// Whether the `#[derive]`s following this attribute macro are expanded before it,
+    /// as requested with `#[proc_macro_attribute(expand_derives)]`.
+    pub expand_derives_first: bool,
 }
 
 impl SyntaxExtension {
@@ -736,6 +743,8 @@ pub fn default(kind: SyntaxExtensionKind, edition: Edition) -> SyntaxExtension {
             helper_attrs: Vec::new(),
             edition,
             builtin_name: None,
+            cfg_eval_input: false,
+            expand_derives_first: false,
             kind,
         }
     }
@@ -772,6 +781,15 @@ pub fn new(
                 )
             })
             .unwrap_or_else(|| (None, helper_attrs));
+        let (cfg_eval_input, expand_derives_first) = sess
+            .find_by_name(attrs, sym::proc_macro_attribute)
+            .and_then(|attr| attr.meta_item_list())
+            .map_or((false, false), |list| {
+                (
+                    attr::list_contains_name(&list, sym::cfg_eval),
+                    attr::list_contains_name(&list, sym::expand_derives),
+                )
+            });
         let (stability, const_stability) = attr::find_stability(&sess, attrs, span);
         if let Some((_, sp)) = const_stability {
             sess.parse_sess
@@ -797,6 +815,8 @@ pub fn new(
             helper_attrs,
             edition,
             builtin_name,
+            cfg_eval_input,
+            expand_derives_first,
         }
     }
 
@@ -972,6 +992,34 @@ pub struct ExpansionData {
     pub is_trailing_mac: bool,
 }
 
+/// A piece of a string assembled by `concat!`.
+#[derive(Clone, Debug)]
+pub struct ConcatPiece {
+    /// The byte range of the piece in the assembled string.
+    pub range: Range<usize>,
+    /// The span of the literal the piece came from.
+    pub span: Span,
+    /// The offset of the piece's contents in the source of the literal, if they are written out
+    /// verbatim, i.e. without escapes.
+    pub verbatim_offset: Option<usize>,
+}
+
+/// A `format_args!` call whose placeholders are all plain `{}`, so that it can be flattened into
+/// an enclosing `format_args!` call.
+#[derive(Clone, Debug)]
+pub struct FlatFormatArgs {
+    pub pieces: Vec<FlatFormatPiece>,
+    /// The arguments, in the order they were written and are evaluated in.
+    pub args: Vec<P<ast::Expr>>,
+}
+
+#[derive(Clone, Debug)]
+pub enum FlatFormatPiece {
+    Literal(String),
+    /// A `{}` placeholder for the argument with this index.
+    Argument(usize),
+}
+
 /// One of these is made during expansion and incrementally updated as we go;
 /// when a macro expansion occurs, the resulting nodes have the `backtrace()
 /// -> expn_data` of their expansion context stored into their span.
@@ -986,6 +1034,12 @@ pub struct ExtCtxt<'a> {
     /// (or during eager expansion, but that's a hack).
     pub force_mode: bool,
     pub expansions: FxHashMap<Span, Vec<String>>,
+    /// The pieces of every string assembled by `concat!`, keyed by the span of the result, so
+    /// that `format_args!` can point into the literals they were written in.
+    pub concat_pieces: FxHashMap<Span, Vec<ConcatPiece>>,
+    /// Requests from `format_args!` to flatten the nested `format_args!` call at this call site,
+    /// answered by the nested call when it is expanded if it can be flattened.
+    pub flat_format_args: FxHashMap<Span, Option<FlatFormatArgs>>,
     /// Used for running pre-expansion lints on freshly loaded modules.
     pub(super) lint_store: LintStoreExpandDyn<'a>,
     /// When we 'expand' an inert attribute, we leave it
@@ -1019,6 +1073,8 @@ pub fn new(
             },
             force_mode: false,
             expansions: FxHashMap::default(),
+            concat_pieces: FxHashMap::default(),
+            flat_format_args: FxHashMap::default(),
             expanded_inert_attrs: MarkedAttrs::new(),
         }
     }
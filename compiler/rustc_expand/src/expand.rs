@@ -1,4 +1,5 @@
 use crate::base::*;
+use crate::cfg_eval::cfg_eval;
 use crate::config::StripUnconfigured;
 use crate::hygiene::SyntaxContext;
 use crate::mbe::macro_rules::annotate_err_with_kind;
@@ -449,7 +450,7 @@ pub fn fully_expand_fragment(&mut self, input_fragment: AstFragment) -> AstFragm
             self.cx.force_mode = force;
 
             let fragment_kind = invoc.fragment_kind;
-            let (expanded_fragment, new_invocations) = match self.expand_invoc(invoc, &ext.kind) {
+            let (expanded_fragment, new_invocations) = match self.expand_invoc(invoc, &ext) {
                 ExpandResult::Ready(fragment) => {
                     let mut derive_invocations = Vec::new();
                     let derive_placeholders = self
@@ -616,7 +617,7 @@ fn error_wrong_fragment_kind(&mut self, kind: AstFragmentKind, mac: &ast::MacCal
     fn expand_invoc(
         &mut self,
         invoc: Invocation,
-        ext: &SyntaxExtensionKind,
+        ext: &SyntaxExtension,
     ) -> ExpandResult<AstFragment, Invocation> {
         let recursion_limit =
             self.cx.reduced_recursion_limit.unwrap_or(self.cx.ecfg.recursion_limit);
@@ -633,7 +634,7 @@ fn expand_invoc(
 
         let (fragment_kind, span) = (invoc.fragment_kind, invoc.span());
         ExpandResult::Ready(match invoc.kind {
-            InvocationKind::Bang { mac, .. } => match ext {
+            InvocationKind::Bang { mac, .. } => match &ext.kind {
                 SyntaxExtensionKind::Bang(expander) => {
                     let Ok(tok_result) = expander.expand(self.cx, span, mac.args.inner_tokens()) else {
                         return ExpandResult::Ready(fragment_kind.dummy(span));
@@ -655,8 +656,30 @@ fn expand_invoc(
                 }
                 _ => unreachable!(),
             },
-            InvocationKind::Attr { attr, pos, mut item, derives } => match ext {
+            InvocationKind::Attr { attr, pos, mut item, derives } => match &ext.kind {
                 SyntaxExtensionKind::Attr(expander) => {
+                    if ext.expand_derives_first && !derives.is_empty() {
+                        // Move the attribute after the derives following it, so that they are
+                        // collected and expanded first, and expand it again afterwards.
+                        item.visit_attrs(|attrs| {
+                            let pos = attrs[pos..]
+                                .iter()
+                                .rposition(|a| a.has_name(sym::derive))
+                                .map_or(pos, |last| pos + last + 1);
+                            attrs.insert(pos, attr)
+                        });
+                        return ExpandResult::Ready(
+                            fragment_kind.expect_from_annotatables(iter::once(item)),
+                        );
+                    }
+                    if ext.cfg_eval_input {
+                        item = cfg_eval(
+                            self.cx.sess,
+                            self.cx.ecfg.features,
+                            item,
+                            self.cx.current_expansion.lint_node_id,
+                        );
+                    }
                     self.gate_proc_macro_input(&item);
                     self.gate_proc_macro_attr_item(span, &item);
                     let tokens = match &item {
@@ -731,10 +754,10 @@ fn expand_invoc(
                 }
                 _ => unreachable!(),
             },
-            InvocationKind::Derive { path, item } => match ext {
+            InvocationKind::Derive { path, item } => match &ext.kind {
                 SyntaxExtensionKind::Derive(expander)
                 | SyntaxExtensionKind::LegacyDerive(expander) => {
-                    if let SyntaxExtensionKind::Derive(..) = ext {
+                    if let SyntaxExtensionKind::Derive(..) = ext.kind {
                         self.gate_proc_macro_input(&item);
                     }
                     let meta = ast::MetaItem { kind: MetaItemKind::Word, span, path };
@@ -25,6 +25,7 @@
 pub mod build;
 #[macro_use]
 pub mod config;
+pub mod cfg_eval;
 pub mod expand;
 pub mod module;
 pub mod proc_macro;
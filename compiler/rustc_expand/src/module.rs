@@ -1,12 +1,15 @@
 use crate::base::ModuleData;
 use rustc_ast::ptr::P;
 use rustc_ast::{token, Attribute, Inline, Item, ModSpans};
-use rustc_errors::{struct_span_err, DiagnosticBuilder, ErrorGuaranteed};
+use rustc_errors::{
+    struct_span_err, Applicability, Diagnostic, DiagnosticBuilder, ErrorGuaranteed,
+};
 use rustc_parse::new_parser_from_file;
 use rustc_parse::validate_attr;
 use rustc_session::parse::ParseSess;
 use rustc_session::Session;
-use rustc_span::symbol::{sym, Ident};
+use rustc_span::lev_distance::find_best_match_for_name;
+use rustc_span::symbol::{sym, Ident, Symbol};
 use rustc_span::Span;
 
 use std::path::{self, Path, PathBuf};
@@ -70,7 +73,7 @@ pub(crate) fn parse_external_mod(
     };
     // (1) ...instead, we return a dummy module.
     let (items, spans, file_path) =
-        result.map_err(|err| err.report(sess, span)).unwrap_or_default();
+        result.map_err(|err| err.report(sess, span, module)).unwrap_or_default();
 
     // Extract the directory path for submodules of the module.
     let dir_path = file_path.parent().unwrap_or(&file_path).to_owned();
@@ -242,7 +245,7 @@ pub fn default_submod_path<'a>(
 }
 
 impl ModError<'_> {
-    fn report(self, sess: &Session, span: Span) -> ErrorGuaranteed {
+    fn report(self, sess: &Session, span: Span, module: &ModuleData) -> ErrorGuaranteed {
         let diag = &sess.parse_sess.span_diagnostic;
         match self {
             ModError::CircularInclusion(file_paths) => {
@@ -278,6 +281,7 @@ fn report(self, sess: &Session, span: Span) -> ErrorGuaranteed {
                     default_path.display(),
                     secondary_path.display(),
                 ));
+                suggest_existing_mod_file(sess, &mut err, ident, span, &default_path, module);
                 err
             }
             ModError::MultipleCandidates(ident, default_path, secondary_path) => {
@@ -297,3 +301,88 @@ fn report(self, sess: &Session, span: Span) -> ErrorGuaranteed {
         }.emit()
     }
 }
+
+/// Looks around the expected location of the missing file for module `ident` for a file
+/// that was probably meant to be the module, and suggests how to load it.
+fn suggest_existing_mod_file(
+    sess: &Session,
+    err: &mut Diagnostic,
+    ident: Ident,
+    span: Span,
+    default_path: &Path,
+    module: &ModuleData,
+) {
+    let Some(expected_dir) = default_path.parent() else { return };
+    let source_map = sess.source_map();
+    let is_candidate = |path: &Path| {
+        source_map.file_exists(path) && !module.file_path_stack.iter().any(|p| p == path)
+    };
+    let indentation = source_map.indentation_before(span).unwrap_or_default();
+    let suggest_path_attr = |err: &mut Diagnostic, msg: &str, path: &Path| {
+        // `#[path]` is relative to the directory of the declaring file, and accepts `/` as a
+        // separator on every platform.
+        let Ok(relative) = path.strip_prefix(&module.dir_path) else { return };
+        let relative: Vec<_> = relative.iter().map(|c| c.to_string_lossy()).collect();
+        err.span_suggestion_verbose(
+            span.shrink_to_lo(),
+            msg,
+            format!("#[path = \"{}\"]\n{}", relative.join("/"), indentation),
+            Applicability::MaybeIncorrect,
+        );
+    };
+
+    // A `mod foo;` in `bar.rs` is loaded from `bar/foo.rs`, but the file is easily put next to
+    // `bar.rs` instead.
+    if expected_dir != module.dir_path {
+        let misplaced = [
+            module.dir_path.join(format!("{}.rs", ident.name)),
+            module.dir_path.join(ident.as_str()).join("mod.rs"),
+        ];
+        if let Some(path) = misplaced.iter().find(|path| is_candidate(path)) {
+            err.note(&format!(
+                "modules declared in a non-`mod.rs` file are loaded from a directory named \
+                 after that file, so \"{}\" is not used",
+                path.display(),
+            ));
+            suggest_path_attr(
+                err,
+                "to load the module from that file, add a `path` attribute",
+                path,
+            );
+            return;
+        }
+    }
+
+    // Otherwise look for a file or directory with a name similar to the module's.
+    let Ok(entries) = std::fs::read_dir(expected_dir) else { return };
+    let mut candidates: Vec<(Symbol, PathBuf)> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let (name, file_path) = if path.extension().map_or(false, |ext| ext == "rs") {
+                (path.file_stem()?.to_str()?, path.clone())
+            } else {
+                (path.file_name()?.to_str()?, path.join("mod.rs"))
+            };
+            is_candidate(&file_path).then(|| (Symbol::intern(name), file_path))
+        })
+        .collect();
+    // Directory listings are unordered, keep the suggestion stable.
+    candidates.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+    let names: Vec<_> = candidates.iter().map(|(name, _)| *name).collect();
+    let Some(similar) = find_best_match_for_name(&names, ident.name, None) else { return };
+    let Some((_, path)) = candidates.iter().find(|(name, _)| *name == similar) else { return };
+
+    let msg = format!("a module file with a similar name exists at \"{}\"", path.display());
+    let similar_ident = Ident::with_dummy_span(similar);
+    if rustc_lexer::is_ident(similar.as_str()) && !similar_ident.is_reserved() {
+        err.span_suggestion_verbose(
+            ident.span,
+            &format!("{msg}, you might have meant to declare that module"),
+            similar,
+            Applicability::MaybeIncorrect,
+        );
+        suggest_path_attr(err, &format!("or load it as module `{}`", ident), path);
+    } else {
+        suggest_path_attr(err, &format!("{msg}, add a `path` attribute to load it"), path);
+    }
+}
@@ -285,6 +285,8 @@ pub fn set(&self, features: &mut Features, span: Span) {
     (active, abi_msp430_interrupt, "1.16.0", Some(38487), None),
     /// Allows `extern "ptx-*" fn()`.
     (active, abi_ptx, "1.15.0", Some(38788), None),
+    /// Allows using ABIs defined by the `custom-abis` field of the target specification.
+    (active, abi_target_defined, "1.63.0", None, None),
     /// Allows `extern "x86-interrupt" fn()`.
     (active, abi_x86_interrupt, "1.17.0", Some(40180), None),
     /// Allows additional const parameter types, such as `&'static str` or user defined types
@@ -341,12 +343,14 @@ pub fn set(&self, features: &mut Features, span: Span) {
     (active, const_eval_limit, "1.43.0", Some(67217), None),
     /// Allows the definition of `const extern fn` and `const unsafe extern fn`.
     (active, const_extern_fn, "1.40.0", Some(64926), None),
-    /// Allows basic arithmetic on floating point types in a `const fn`.
-    (active, const_fn_floating_point_arithmetic, "1.48.0", Some(57241), None),
+    /// Allows calling function pointers in const contexts.
+    (active, const_fn_ptr_calls, "1.63.0", None, None),
     /// Allows `for _ in _` loops in const contexts.
     (active, const_for, "1.56.0", Some(87575), None),
     /// Allows using `&mut` in constant functions.
     (active, const_mut_refs, "1.41.0", Some(57349), None),
+    /// Allows formatting arguments in `panic!()` messages in const contexts.
+    (active, const_panic_fmt_args, "1.63.0", None, None),
     /// Be more precise when looking for live drops in a const context.
     (active, const_precise_live_drops, "1.46.0", Some(73255), None),
     /// Allows references to types with interior mutability within constants
@@ -355,6 +359,8 @@ pub fn set(&self, features: &mut Features, span: Span) {
     (active, const_trait_impl, "1.42.0", Some(67792), None),
     /// Allows the `?` operator in const contexts.
     (active, const_try, "1.56.0", Some(74935), None),
+    /// Allows using `#[ctor_priority]` to order the static constructors of a program.
+    (active, ctor_priority, "1.63.0", None, None),
     /// Allows non-builtin attributes in inner attribute position.
     (active, custom_inner_attributes, "1.30.0", Some(54726), None),
     /// Allows custom test frameworks with `#![test_runner]` and `#[test_case]`.
@@ -395,6 +401,8 @@ pub fn set(&self, features: &mut Features, span: Span) {
     (active, ffi_returns_twice, "1.34.0", Some(58314), None),
     /// Allows using `#[repr(align(...))]` on function items
     (active, fn_align, "1.53.0", Some(82232), None),
+    /// Allows `#[frame_pointer(...)]` to control frame pointer omission per function.
+    (active, frame_pointer_attribute, "1.63.0", None, None),
     /// Allows defining generators.
     (active, generators, "1.21.0", Some(43122), None),
     /// Infer generic args for both consts and types.
@@ -482,6 +490,8 @@ pub fn set(&self, features: &mut Features, span: Span) {
     (active, plugin, "1.0.0", Some(29597), None),
     /// Allows exhaustive integer pattern matching on `usize` and `isize`.
     (active, precise_pointer_size_matching, "1.32.0", Some(56354), None),
+    /// Allows `#[proc_macro_attribute(cfg_eval, expand_derives)]`.
+    (active, proc_macro_cfg_eval, "1.63.0", None, None),
     /// Allows macro attributes on expressions, statements and non-inline modules.
     (active, proc_macro_hygiene, "1.30.0", Some(54727), None),
     /// Allows the use of raw-dylibs (RFC 2627).
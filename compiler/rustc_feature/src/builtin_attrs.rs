@@ -292,7 +292,10 @@ pub struct BuiltinAttribute {
         proc_macro_derive, Normal,
         template!(List: "TraitName, /*opt*/ attributes(name1, name2, ...)"), ErrorFollowing,
     ),
-    ungated!(proc_macro_attribute, Normal, template!(Word), ErrorFollowing),
+    ungated!(
+        proc_macro_attribute, Normal, template!(Word, List: "cfg_eval, expand_derives"),
+        ErrorFollowing,
+    ),
 
     // Lints:
     ungated!(
@@ -452,6 +455,10 @@ pub struct BuiltinAttribute {
         instruction_set, Normal, template!(List: "set"), ErrorPreceding,
         isa_attribute, experimental!(instruction_set)
     ),
+    gated!(
+        frame_pointer, Normal, template!(List: "always|non_leaf|none"), ErrorPreceding,
+        frame_pointer_attribute, experimental!(frame_pointer)
+    ),
 
     gated!(
         ffi_returns_twice, Normal, template!(Word), WarnFollowing, experimental!(ffi_returns_twice)
@@ -466,11 +473,20 @@ pub struct BuiltinAttribute {
         register_tool, CrateLevel, template!(List: "tool1, tool2, ..."), DuplicatesOk,
         experimental!(register_tool),
     ),
+    gated!(
+        register_tool_attr, CrateLevel,
+        template!(List: "tool::attr, targets(target1, target2, ...), args(word|list|name_value)"),
+        DuplicatesOk, register_tool, experimental!(register_tool_attr),
+    ),
 
     gated!(
         cmse_nonsecure_entry, Normal, template!(Word), WarnFollowing,
         experimental!(cmse_nonsecure_entry)
     ),
+    gated!(
+        ctor_priority, Normal, template!(List: "priority"), ErrorPreceding,
+        experimental!(ctor_priority)
+    ),
     // RFC 2632
     gated!(
         const_trait, Normal, template!(Word), WarnFollowing, const_trait_impl,
@@ -868,6 +868,10 @@ pub struct OwnerInfo<'hir> {
     /// Map indicating what traits are in scope for places where this
     /// is relevant; generated by resolve.
     pub trait_map: FxHashMap<ItemLocalId, Box<[TraitCandidate]>>,
+    /// Map from each expression that was written inside parentheses to the span
+    /// of the expression without them. The span of the HIR expression itself
+    /// includes the parentheses.
+    pub parenthesized: FxHashMap<ItemLocalId, Span>,
 }
 
 impl<'tcx> OwnerInfo<'tcx> {
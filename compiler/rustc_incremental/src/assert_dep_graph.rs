@@ -1,6 +1,6 @@
 //! This pass is only used for the UNIT TESTS and DEBUGGING NEEDS
 //! around dependency graph construction. It serves two purposes; it
-//! will dump graphs in graphviz form to disk, and it searches for
+//! will dump graphs in graphviz or JSON form to disk, and it searches for
 //! `#[rustc_if_this_changed]` and `#[rustc_then_this_would_need]`
 //! annotations. These annotations can be used to test whether paths
 //! exist in the graph. These checks run after codegen, so they view the
@@ -34,7 +34,7 @@
 //! ```
 
 use rustc_ast as ast;
-use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::graph::implementation::{Direction, NodeIndex, INCOMING, OUTGOING};
 use rustc_graphviz as dot;
 use rustc_hir as hir;
@@ -44,13 +44,16 @@
     DepGraphQuery, DepKind, DepNode, DepNodeExt, DepNodeFilter, EdgeFilter,
 };
 use rustc_middle::hir::nested_filter;
+use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::TyCtxt;
 use rustc_span::symbol::{sym, Symbol};
 use rustc_span::Span;
+use serde_json::json;
 
 use std::env;
 use std::fs::{self, File};
 use std::io::{BufWriter, Write};
+use std::path::Path;
 
 #[allow(missing_docs)]
 pub fn assert_dep_graph(tcx: TyCtxt<'_>) {
@@ -59,6 +62,10 @@ pub fn assert_dep_graph(tcx: TyCtxt<'_>) {
             tcx.dep_graph.with_query(dump_graph);
         }
 
+        if let Some(path) = &tcx.sess.opts.debugging_opts.dump_dep_graph_json {
+            tcx.dep_graph.with_query(|query| dump_graph_json(tcx, query, path));
+        }
+
         if !tcx.sess.opts.debugging_opts.query_dep_graph {
             return;
         }
@@ -263,6 +270,56 @@ fn dump_graph(query: &DepGraphQuery) {
     }
 }
 
+/// Writes the dependency graph to `path` as a JSON object with a `nodes` array, holding the
+/// query kind and the path of the key of each node, and an `edges` array of `[source, target]`
+/// pairs of indices into `nodes`, meaning that `source` read the result of `target`.
+///
+/// With `-Z dump-dep-graph-json-filter`, only the nodes of the given query kinds or whose key
+/// is a definition under one of the given paths are written, with the edges between them.
+fn dump_graph_json(tcx: TyCtxt<'_>, query: &DepGraphQuery, path: &Path) {
+    let filter = tcx.sess.opts.debugging_opts.dump_dep_graph_json_filter.as_deref();
+    let filter: Option<Vec<&str>> = filter.map(|filter| filter.split(',').map(str::trim).collect());
+
+    let mut node_indices = FxHashMap::default();
+    let mut nodes = vec![];
+    for node in query.nodes() {
+        let kind = format!("{:?}", node.kind);
+        let def_path =
+            node.extract_def_id(tcx).map(|def_id| with_no_trimmed_paths!(tcx.def_path_str(def_id)));
+        let selected = filter.as_ref().map_or(true, |filter| {
+            filter.iter().any(|&entry| entry == kind || is_under_path(def_path.as_deref(), entry))
+        });
+        if selected {
+            node_indices.insert(node, nodes.len());
+            nodes.push(json!({ "kind": kind, "def_path": def_path }));
+        }
+    }
+
+    let edges: Vec<_> = query
+        .edges()
+        .into_iter()
+        .filter_map(|(source, target)| {
+            Some([*node_indices.get(source)?, *node_indices.get(target)?])
+        })
+        .collect();
+
+    let graph = json!({ "nodes": nodes, "edges": edges });
+    if let Err(err) = fs::write(path, graph.to_string()) {
+        tcx.sess.err(&format!(
+            "failed to write the dependency graph to `{}`: {}",
+            path.display(),
+            err
+        ));
+    }
+}
+
+/// Returns `true` if `def_path` is `prefix` or the path of a definition nested inside it.
+fn is_under_path(def_path: Option<&str>, prefix: &str) -> bool {
+    def_path
+        .and_then(|def_path| def_path.strip_prefix(prefix))
+        .map_or(false, |rest| rest.is_empty() || rest.starts_with("::"))
+}
+
 #[allow(missing_docs)]
 pub struct GraphvizDepGraph(FxHashSet<DepKind>, Vec<(DepKind, DepKind)>);
 
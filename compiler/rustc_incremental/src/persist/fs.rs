@@ -50,6 +50,23 @@
 //! any more and will delete those. It will also delete any finalized session
 //! directories for a given crate except for the most recent one.
 //!
+//! The incremental compilation directory itself contains one crate directory
+//! for each crate and set of compiler flags that was compiled with it, and
+//! those are only deleted on request. With `-Z incremental-gc-sessions=N`,
+//! the crate directories of a crate that were not used by one of the last N
+//! sessions compiling a crate of the same name are deleted, and with
+//! `-Z incremental-max-size=N`, the least recently used crate directories are
+//! deleted until the whole directory takes less than N MiB. Sessions are
+//! counted per crate name because build systems like Cargo share the
+//! incremental compilation directory between all crates of a workspace: the
+//! crates that are still part of the workspace keep their cache however many
+//! other crates are compiled after them, while the caches left behind by old
+//! compiler flags are deleted. Since every session creates a new session
+//! directory named after its timestamp, the newest session directory of a
+//! crate directory tells when it was last used. A crate directory is only
+//! deleted if the exclusive locks of all its session directories can be
+//! acquired.
+//!
 //! ## Synchronization
 //!
 //! There is some synchronization needed in order for the compiler to be able to
@@ -394,6 +411,12 @@ pub fn finalize_session_directory(sess: &Session, svh: Svh) {
     }
 
     let _ = garbage_collect_session_directories(sess);
+    if let Err(err) = garbage_collect_crate_directories(sess) {
+        sess.warn(&format!(
+            "Failed to garbage collect the incremental compilation directory: {}",
+            err
+        ));
+    }
 }
 
 pub fn delete_all_session_dir_contents(sess: &Session) -> io::Result<()> {
@@ -903,6 +926,208 @@ pub fn garbage_collect_session_directories(sess: &Session) -> io::Result<()> {
     Ok(())
 }
 
+/// Deletes the crate directories of the incremental compilation directory that were not used
+/// in the last `-Z incremental-gc-sessions` sessions of their crate or that exceed
+/// `-Z incremental-max-size`. The crate directory of the current session is never deleted.
+fn garbage_collect_crate_directories(sess: &Session) -> io::Result<()> {
+    let keep_sessions = sess.opts.debugging_opts.incremental_gc_sessions;
+    let max_size =
+        sess.opts.debugging_opts.incremental_max_size.map(|mib| mib.saturating_mul(1024 * 1024));
+    if keep_sessions.is_none() && max_size.is_none() {
+        return Ok(());
+    }
+
+    debug!("garbage_collect_crate_directories() - begin");
+
+    let current_crate_directory = sess.incr_comp_session_dir().parent().unwrap().to_path_buf();
+    let incr_comp_directory = current_crate_directory.parent().unwrap();
+
+    let mut current_size = 0;
+    let mut crate_directories = vec![];
+    for dir_entry in incr_comp_directory.read_dir()? {
+        let Ok(dir_entry) = dir_entry else {
+            // Ignore any errors
+            continue;
+        };
+        if !dir_entry.file_type().map_or(false, |file_type| file_type.is_dir()) {
+            continue;
+        }
+
+        let path = dir_entry.path();
+        let size = directory_size(&path).unwrap_or(0);
+        if path == current_crate_directory {
+            current_size = size;
+            continue;
+        }
+
+        // Leave alone the directories that don't contain any session or that were used only
+        // moments ago, they may be in the middle of being set up by another compiler process.
+        match last_session_timestamp(&path) {
+            Some(timestamp) if is_old_enough_to_be_collected(timestamp) => {
+                crate_directories.push((timestamp, size, path));
+            }
+            _ => {
+                debug!(
+                    "garbage_collect_crate_directories() - not collecting `{}`, not old enough",
+                    path.display()
+                );
+            }
+        }
+    }
+
+    let to_evict = crate_directories_to_evict(
+        crate_directories,
+        &current_crate_directory,
+        current_size,
+        keep_sessions,
+        max_size,
+    );
+    for path in to_evict {
+        debug!("garbage_collect_crate_directories() - deleting `{}`", path.display());
+
+        if let Err(err) = delete_crate_directory(&path) {
+            sess.warn(&format!(
+                "Failed to garbage collect incremental compilation crate directory `{}`: {}",
+                path.display(),
+                err
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the crate directories to delete, least recently used first, so that the remaining
+/// ones were used in the last `keep_sessions` sessions of a crate of the same name and take at
+/// most `max_size` bytes.
+///
+/// `crate_directories` holds the timestamp of the last session and the size of each crate
+/// directory except for the current one, `current_crate_directory`, which takes `current_size`
+/// bytes and is always kept.
+fn crate_directories_to_evict(
+    mut crate_directories: Vec<(SystemTime, u64, PathBuf)>,
+    current_crate_directory: &Path,
+    current_size: u64,
+    keep_sessions: Option<usize>,
+    max_size: Option<u64>,
+) -> Vec<PathBuf> {
+    // Sort the most recently used directories first.
+    crate_directories.sort_by(|(a, ..), (b, ..)| b.cmp(a));
+
+    let mut stale = vec![false; crate_directories.len()];
+    if let Some(keep_sessions) = keep_sessions {
+        // The current session is the most recent one of all.
+        let mut kept_sessions = FxHashMap::default();
+        kept_sessions.insert(crate_directory_crate_name(current_crate_directory), 1);
+        for (index, (_, _, path)) in crate_directories.iter().enumerate() {
+            let kept = kept_sessions.entry(crate_directory_crate_name(path)).or_insert(0);
+            if *kept >= keep_sessions {
+                stale[index] = true;
+            } else {
+                *kept += 1;
+            }
+        }
+    }
+    let mut total_size =
+        current_size + crate_directories.iter().map(|&(_, size, _)| size).sum::<u64>();
+
+    let mut evicted = vec![];
+    for (index, (_, size, path)) in crate_directories.into_iter().enumerate().rev() {
+        if stale[index] || max_size.map_or(false, |max_size| total_size > max_size) {
+            total_size -= size;
+            evicted.push(path);
+        }
+    }
+    evicted
+}
+
+/// Returns the name of the crate whose caches `crate_directory` holds, see `crate_path`.
+fn crate_directory_crate_name(crate_directory: &Path) -> &str {
+    let name = crate_directory.file_name().and_then(|name| name.to_str()).unwrap_or("");
+    name.rsplit_once('-').map_or(name, |(crate_name, _)| crate_name)
+}
+
+/// Returns the timestamp of the most recent session directory in `crate_directory`.
+fn last_session_timestamp(crate_directory: &Path) -> Option<SystemTime> {
+    crate_directory
+        .read_dir()
+        .ok()?
+        .filter_map(|dir_entry| {
+            let entry_name = dir_entry.ok()?.file_name();
+            extract_timestamp_from_session_dir(&entry_name.to_string_lossy()).ok()
+        })
+        .max()
+}
+
+/// Returns the total size of the files in `path` and its subdirectories. Files hard-linked
+/// from several session directories are counted once per link.
+fn directory_size(path: &Path) -> io::Result<u64> {
+    let mut size = 0;
+    for dir_entry in path.read_dir()? {
+        let dir_entry = dir_entry?;
+        let file_type = dir_entry.file_type()?;
+        if file_type.is_dir() {
+            size += directory_size(&dir_entry.path())?;
+        } else if file_type.is_file() {
+            size += dir_entry.metadata()?.len();
+        }
+    }
+    Ok(size)
+}
+
+/// Deletes all session directories and lock files of a crate directory, and then the crate
+/// directory itself. Nothing is deleted if one of the sessions is still in use by another
+/// compiler process.
+fn delete_crate_directory(crate_directory: &Path) -> io::Result<()> {
+    let mut session_directories = vec![];
+    let mut lock_files = vec![];
+    for dir_entry in crate_directory.read_dir()? {
+        let dir_entry = dir_entry?;
+        let entry_name = dir_entry.file_name();
+        let entry_name = entry_name.to_string_lossy();
+        if is_session_directory_lock_file(&entry_name) {
+            lock_files.push(dir_entry.path());
+        } else if is_session_directory(&entry_name) {
+            session_directories.push(dir_entry.path());
+        }
+    }
+
+    // Hold on to an exclusive lock on every session directory until they are all deleted, so
+    // that no other process starts copying from them in the meantime.
+    let mut locks = Vec::with_capacity(lock_files.len());
+    for lock_file_path in &lock_files {
+        match flock::Lock::new(
+            lock_file_path,
+            false, // don't wait
+            false, // don't create the lock-file
+            true,
+        ) {
+            // get an exclusive lock
+            Ok(lock) => locks.push(lock),
+            Err(_) => {
+                debug!(
+                    "garbage_collect_crate_directories() - not collecting `{}`, still in use",
+                    crate_directory.display()
+                );
+                return Ok(());
+            }
+        }
+    }
+
+    for session_directory in &session_directories {
+        safe_remove_dir_all(session_directory)?;
+    }
+    for lock_file_path in &lock_files {
+        safe_remove_file(lock_file_path)?;
+    }
+    mem::drop(locks);
+
+    // This fails if another compiler process started a new session in the crate directory in
+    // the meantime, which is fine: the directory is in use again.
+    let _ = std_fs::remove_dir(crate_directory);
+    Ok(())
+}
+
 fn delete_old(sess: &Session, path: &Path) {
     debug!("garbage_collect_session_directories() - deleting `{}`", path.display());
 
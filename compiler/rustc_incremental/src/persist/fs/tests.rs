@@ -24,6 +24,68 @@ fn test_all_except_most_recent() {
     );
 }
 
+#[test]
+fn test_crate_directories_to_evict() {
+    let crate_directories = || {
+        vec![
+            (UNIX_EPOCH + Duration::new(2, 0), 20, PathBuf::from("a-2")),
+            (UNIX_EPOCH + Duration::new(4, 0), 40, PathBuf::from("a-4")),
+            (UNIX_EPOCH + Duration::new(1, 0), 10, PathBuf::from("a-1")),
+            (UNIX_EPOCH + Duration::new(3, 0), 30, PathBuf::from("a-3")),
+        ]
+    };
+    let current = Path::new("a-5");
+
+    // Nothing is evicted without limits
+    assert_eq!(crate_directories_to_evict(crate_directories(), current, 100, None, None), vec![]);
+
+    // Keep the current session and the two most recent other ones
+    assert_eq!(
+        crate_directories_to_evict(crate_directories(), current, 100, Some(3), None),
+        vec![PathBuf::from("a-1"), PathBuf::from("a-2")]
+    );
+
+    // The current session is always kept
+    assert_eq!(
+        crate_directories_to_evict(crate_directories(), current, 100, Some(0), None).len(),
+        4
+    );
+
+    // Evict the least recently used directories until the total size fits
+    assert_eq!(
+        crate_directories_to_evict(crate_directories(), current, 100, None, Some(170)),
+        vec![PathBuf::from("a-1"), PathBuf::from("a-2")]
+    );
+    assert_eq!(
+        crate_directories_to_evict(crate_directories(), current, 100, None, Some(50)).len(),
+        4
+    );
+
+    // Both limits apply
+    assert_eq!(
+        crate_directories_to_evict(crate_directories(), current, 100, Some(4), Some(180)),
+        vec![PathBuf::from("a-1"), PathBuf::from("a-2")]
+    );
+}
+
+#[test]
+fn test_crate_directories_to_evict_per_crate() {
+    // Like the crates of a workspace sharing the incremental directory
+    let crate_directories = vec![
+        (UNIX_EPOCH + Duration::new(1, 0), 10, PathBuf::from("a-1")),
+        (UNIX_EPOCH + Duration::new(2, 0), 10, PathBuf::from("b-2")),
+        (UNIX_EPOCH + Duration::new(3, 0), 10, PathBuf::from("c-3")),
+        (UNIX_EPOCH + Duration::new(4, 0), 10, PathBuf::from("a-4")),
+        (UNIX_EPOCH + Duration::new(5, 0), 10, PathBuf::from("b-5")),
+    ];
+
+    // Only the caches superseded by a more recent one of the same crate are evicted
+    assert_eq!(
+        crate_directories_to_evict(crate_directories, Path::new("c-6"), 10, Some(1), None),
+        vec![PathBuf::from("a-1"), PathBuf::from("b-2"), PathBuf::from("c-3")]
+    );
+}
+
 #[test]
 fn test_timestamp_serialization() {
     for i in 0..1_000u64 {
@@ -181,7 +181,8 @@ pub fn build_dep_graph(
         prev_graph,
         prev_work_products,
         encoder,
-        sess.opts.debugging_opts.query_dep_graph,
+        sess.opts.debugging_opts.query_dep_graph
+            || sess.opts.debugging_opts.dump_dep_graph_json.is_some(),
         sess.opts.debugging_opts.incremental_info,
     ))
 }
@@ -960,6 +960,23 @@ fn push_comma(
         }
     }
 
+    /// Adds a part of the compared types that is the same on both sides, elided as `_` unless
+    /// `-Z full-type-diffs` was passed.
+    fn push_common(
+        &self,
+        value: &mut DiagnosticStyledString,
+        other_value: &mut DiagnosticStyledString,
+        common: impl fmt::Display,
+    ) {
+        let common = if self.tcx.sess.opts.debugging_opts.full_type_diffs {
+            common.to_string()
+        } else {
+            "_".to_string()
+        };
+        value.push_normal(common.clone());
+        other_value.push_normal(common);
+    }
+
     /// For generic types with parameters with defaults, remove the parameters corresponding to
     /// the defaults. This repeats a lot of the logic found in `ty::print::pretty`.
     fn strip_generic_default_params(
@@ -1110,6 +1127,9 @@ fn cmp_fn_sig(
 
     /// Compares two given types, eliding parts that are the same between them and highlighting
     /// relevant differences, and return two representation of those types for highlighted printing.
+    ///
+    /// This is shared by all the expected/found notes, e.g. for E0308 and E0271. Passing
+    /// `-Z full-type-diffs` keeps the common parts in the output instead of replacing them by `_`.
     pub fn cmp(
         &self,
         t1: Ty<'tcx>,
@@ -1223,6 +1243,9 @@ fn lifetime_display(lifetime: Region<'_>) -> String {
                         } else if lifetimes.0.is_late_bound() {
                             values.0.push_normal(l1);
                             values.1.push_normal(l2);
+                        } else if self.tcx.sess.opts.debugging_opts.full_type_diffs {
+                            values.0.push_normal(l1);
+                            values.1.push_normal(l2);
                         } else {
                             values.0.push_normal("'_");
                             values.1.push_normal("'_");
@@ -1242,8 +1265,7 @@ fn lifetime_display(lifetime: Region<'_>) -> String {
                     for (i, (ta1, ta2)) in type_arguments.take(num_display_types).enumerate() {
                         let i = i + regions_len;
                         if ta1 == ta2 {
-                            values.0.push_normal("_");
-                            values.1.push_normal("_");
+                            self.push_common(&mut values.0, &mut values.1, ta1);
                         } else {
                             let (x1, x2) = self.cmp(ta1, ta2);
                             (values.0).0.extend(x1.0);
@@ -1258,8 +1280,7 @@ fn lifetime_display(lifetime: Region<'_>) -> String {
                     for (i, (ca1, ca2)) in const_arguments.enumerate() {
                         let i = i + consts_offset;
                         if ca1 == ca2 {
-                            values.0.push_normal("_");
-                            values.1.push_normal("_");
+                            self.push_common(&mut values.0, &mut values.1, ca1);
                         } else {
                             values.0.push_highlighted(ca1.to_string());
                             values.1.push_highlighted(ca2.to_string());
@@ -1438,7 +1459,10 @@ fn lifetime_display(lifetime: Region<'_>) -> String {
             _ => {
                 if t1 == t2 {
                     // The two types are the same, elide and don't highlight.
-                    (DiagnosticStyledString::normal("_"), DiagnosticStyledString::normal("_"))
+                    let mut values =
+                        (DiagnosticStyledString::new(), DiagnosticStyledString::new());
+                    self.push_common(&mut values.0, &mut values.1, t1);
+                    values
                 } else {
                     // We couldn't find anything in common, highlight everything.
                     (
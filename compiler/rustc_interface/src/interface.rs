@@ -20,6 +20,9 @@
 use rustc_session::{DiagnosticOutput, Session};
 use rustc_span::source_map::{FileLoader, FileName};
 use rustc_span::symbol::sym;
+use std::fmt::Write;
+use std::fs;
+use std::io;
 use std::path::PathBuf;
 use std::result;
 
@@ -358,3 +361,51 @@ pub fn try_print_query_stack(handler: &Handler, num_frames: Option<usize>) {
         eprintln!("we're just showing a limited slice of the query stack");
     }
 }
+
+/// Writes the report requested with `-Zdump-ice` for the panic described by `header`, if the
+/// compiler panicked while the type context was available. Returns the path of the report.
+pub fn try_dump_ice(header: &str) -> Option<io::Result<PathBuf>> {
+    // Like in `try_print_query_stack`, this is called from a panic hook, so it only reads state
+    // that is already computed and doesn't run any queries.
+    ty::tls::with_context_opt(|icx| {
+        let icx = icx?;
+        let tcx = icx.tcx;
+        let path = tcx.sess.opts.debugging_opts.dump_ice.clone()?;
+        let source_map = tcx.sess.source_map();
+        let stack = QueryCtxt::from_tcx(tcx).try_collect_query_stack(icx.query);
+
+        let mut report = String::new();
+        writeln!(report, "{}", header).unwrap();
+
+        writeln!(report, "\nquery stack:").unwrap();
+        for (i, (frame, span)) in stack.iter().enumerate() {
+            writeln!(report, "#{} [{}] {}", i, frame.name, frame.description).unwrap();
+            if !span.is_dummy() {
+                writeln!(report, "    at {}", source_map.span_to_embeddable_string(*span)).unwrap();
+            }
+        }
+
+        let features = tcx.sess.features_untracked();
+        writeln!(report, "\nenabled features:").unwrap();
+        let lang_features = features.declared_lang_features.iter().map(|&(name, ..)| name);
+        let lib_features = features.declared_lib_features.iter().map(|&(name, _)| name);
+        for name in lang_features.chain(lib_features) {
+            writeln!(report, "#![feature({})]", name).unwrap();
+        }
+
+        // The innermost query with a span is the one for the failing item. That span often
+        // only covers the head of the item, so the lines following it are included as well.
+        if let Some(&(_, span)) = stack.iter().find(|(_, span)| !span.is_dummy()) {
+            let location = source_map.span_to_embeddable_string(span);
+            writeln!(report, "\nsource of the failing item at {}:", location).unwrap();
+            let lo = source_map.lookup_char_pos(span.lo());
+            let hi = source_map.lookup_char_pos(span.hi());
+            for line in lo.line..=hi.line.max(lo.line + 20) {
+                let Some(text) = lo.file.get_line(line - 1) else { break };
+                writeln!(report, "{:>5} | {}", line, text).unwrap();
+            }
+        }
+
+        Some(fs::write(&path, report).map(|()| path))
+    })
+}
@@ -68,7 +68,11 @@ pub fn parse<'a>(sess: &'a Session, input: &Input) -> PResult<'a, ast::Crate> {
     }
 
     if sess.opts.debugging_opts.hir_stats {
-        hir_stats::print_ast_stats(&krate, "PRE EXPANSION AST STATS");
+        hir_stats::print_ast_stats(
+            &krate,
+            "PRE EXPANSION AST STATS",
+            sess.opts.debugging_opts.hir_stats_top_items,
+        );
     }
 
     Ok(krate)
@@ -366,6 +370,10 @@ pub fn configure_and_expand(
         rustc_ast_passes::ast_validation::check_crate(sess, &krate, resolver.lint_buffer())
     });
 
+    sess.time("tool_attribute_validation", || {
+        rustc_ast_passes::tool_attrs::check_crate(sess, &krate)
+    });
+
     let crate_types = sess.crate_types();
     let is_executable_crate = crate_types.contains(&CrateType::Executable);
     let is_proc_macro_crate = crate_types.contains(&CrateType::ProcMacro);
@@ -415,7 +423,11 @@ pub fn configure_and_expand(
     }
 
     if sess.opts.debugging_opts.hir_stats {
-        hir_stats::print_ast_stats(&krate, "POST EXPANSION AST STATS");
+        hir_stats::print_ast_stats(
+            &krate,
+            "POST EXPANSION AST STATS",
+            sess.opts.debugging_opts.hir_stats_top_items,
+        );
     }
 
     resolver.resolve_crate(&krate);
@@ -976,6 +988,11 @@ fn analysis(tcx: TyCtxt<'_>, (): ()) -> Result<()> {
                         tcx.hir()
                             .par_for_each_module(|module| tcx.ensure().check_mod_deathness(module));
                     },
+                    {
+                        tcx.hir().par_for_each_module(|module| {
+                            tcx.ensure().check_mod_unused_generic_params(module)
+                        });
+                    },
                     {
                         sess.time("lint_checking", || {
                             rustc_lint::check_crate(tcx, || {
@@ -1,8 +1,11 @@
 use crate::interface::parse_cfgspecs;
 
 use rustc_data_structures::fx::FxHashSet;
-use rustc_errors::{emitter::HumanReadableErrorType, registry, ColorConfig};
-use rustc_session::config::InstrumentCoverage;
+use rustc_data_structures::profiling::TimePassesFormat;
+use rustc_errors::{emitter::HumanReadableErrorType, registry, ColorConfig, TerminalUrl};
+use rustc_session::config::IncrementalVerifyIch;
+use rustc_session::config::MirValidationLevel;
+use rustc_session::config::RemapPathScopeComponents;
 use rustc_session::config::Strip;
 use rustc_session::config::{build_configuration, build_session_options, to_crate_config};
 use rustc_session::config::{
@@ -13,6 +16,7 @@
     SymbolManglingVersion, WasiExecModel,
 };
 use rustc_session::config::{CFGuard, ExternEntry, LinkerPluginLto, LtoCli, SwitchWithOptPath};
+use rustc_session::config::{CoverageOptions, InstrumentCoverage};
 use rustc_session::lint::Level;
 use rustc_session::search_paths::SearchPath;
 use rustc_session::utils::{CanonicalizedPath, NativeLib, NativeLibKind};
@@ -285,6 +289,20 @@ fn test_lint_cap_hash_different() {
     assert_non_crate_hash_different(&v2, &v3);
 }
 
+#[test]
+fn test_lint_config_hash_different() {
+    let mut v1 = Options::default();
+    let mut v2 = Options::default();
+    let v3 = Options::default();
+
+    v1.lint_config.levels = vec![(String::from("a"), Level::Allow)];
+    v2.lint_config.levels = vec![(String::from("a"), Level::Deny)];
+
+    assert_non_crate_hash_different(&v1, &v2);
+    assert_non_crate_hash_different(&v1, &v3);
+    assert_non_crate_hash_different(&v2, &v3);
+}
+
 #[test]
 fn test_search_paths_tracking_hash_different_order() {
     let mut v1 = Options::default();
@@ -649,24 +667,33 @@ macro_rules! untracked {
     untracked!(dlltool, Some(PathBuf::from("custom_dlltool.exe")));
     untracked!(dont_buffer_diagnostics, true);
     untracked!(dump_dep_graph, true);
+    untracked!(dump_dep_graph_json, Some(PathBuf::from("dep-graph.json")));
+    untracked!(dump_dep_graph_json_filter, Some(String::from("typeck,foo::bar")));
+    untracked!(dump_ice, Some(PathBuf::from("ice.txt")));
     untracked!(dump_mir, Some(String::from("abc")));
     untracked!(dump_mir_dataflow, true);
     untracked!(dump_mir_dir, String::from("abc"));
     untracked!(dump_mir_exclude_pass_number, true);
     untracked!(dump_mir_graphviz, true);
     untracked!(emit_stack_sizes, true);
+    untracked!(full_type_diffs, true);
     untracked!(future_incompat_test, true);
     untracked!(hir_stats, true);
+    untracked!(hir_stats_top_items, Some(10));
     untracked!(identify_regions, true);
+    untracked!(incremental_gc_sessions, Some(3));
     untracked!(incremental_ignore_spans, true);
     untracked!(incremental_info, true);
-    untracked!(incremental_verify_ich, true);
+    untracked!(incremental_max_size, Some(512));
+    untracked!(incremental_verify_ich, IncrementalVerifyIch::Sample(32));
     untracked!(input_stats, true);
     untracked!(keep_hygiene_data, true);
     untracked!(link_native_libraries, false);
+    untracked!(lint_config, Some(PathBuf::from("lints.toml")));
     untracked!(llvm_time_trace, true);
     untracked!(ls, true);
     untracked!(macro_backtrace, true);
+    untracked!(max_errors, Some(10));
     untracked!(meta_stats, true);
     untracked!(nll_facts, true);
     untracked!(no_analysis, true);
@@ -678,6 +705,7 @@ macro_rules! untracked {
     // `pre_link_arg` is omitted because it just forwards to `pre_link_args`.
     untracked!(pre_link_args, vec![String::from("abc"), String::from("def")]);
     untracked!(profile_closures, true);
+    untracked!(print_cgu_stats, true);
     untracked!(print_llvm_passes, true);
     untracked!(print_mono_items, Some(String::from("abc")));
     untracked!(print_type_sizes, true);
@@ -689,17 +717,20 @@ macro_rules! untracked {
     untracked!(span_debug, true);
     untracked!(span_free_formats, true);
     untracked!(temps_dir, Some(String::from("abc")));
+    untracked!(terminal_urls, TerminalUrl::Yes);
+    untracked!(terminal_urls_base, Some(String::from("file:///usr/share/doc/rust/html")));
     untracked!(terminal_width, Some(80));
     untracked!(threads, 99);
     untracked!(time, true);
     untracked!(time_llvm_passes, true);
     untracked!(time_passes, true);
+    untracked!(time_passes_format, TimePassesFormat::Json);
     untracked!(trace_macros, true);
     untracked!(trim_diagnostic_paths, false);
     untracked!(ui_testing, true);
     untracked!(unpretty, Some("expanded".to_string()));
     untracked!(unstable_options, true);
-    untracked!(validate_mir, true);
+    untracked!(validate_mir, MirValidationLevel::Full);
     untracked!(verbose, true);
 
     macro_rules! tracked {
@@ -717,6 +748,7 @@ macro_rules! tracked {
     tracked!(always_encode_mir, true);
     tracked!(asm_comments, true);
     tracked!(assume_incomplete_release, true);
+    tracked!(atomic_shims, true);
     tracked!(binary_dep_depinfo, true);
     tracked!(
         branch_protection,
@@ -727,13 +759,20 @@ macro_rules! tracked {
     );
     tracked!(chalk, true);
     tracked!(codegen_backend, Some("abc".to_string()));
+    tracked!(const_eval_frame_args, true);
+    tracked!(const_eval_step_budget, 1000);
+    tracked!(coverage_options, CoverageOptions { branch: true });
     tracked!(crate_attr, vec!["abc".to_string()]);
     tracked!(debug_info_for_profiling, true);
     tracked!(debug_macros, true);
     tracked!(dep_info_omit_d_target, true);
     tracked!(drop_tracking, true);
     tracked!(dual_proc_macros, true);
+    tracked!(dylib_lto, true);
+    tracked!(enum_layout_report, true);
+    tracked!(extra_const_ub_checks, true);
     tracked!(fewer_names, Some(true));
+    tracked!(flatten_format_args, false);
     tracked!(force_unstable_if_unmarked, true);
     tracked!(fuel, Some(("abc".to_string(), 99)));
     tracked!(function_sections, Some(false));
@@ -762,6 +801,7 @@ macro_rules! tracked {
     tracked!(osx_rpath_install_name, true);
     tracked!(panic_abort_tests, true);
     tracked!(panic_in_drop, PanicStrategy::Abort);
+    tracked!(pgo_value_profiling, Some(true));
     tracked!(pick_stable_methods_before_any_unstable, false);
     tracked!(plt, Some(true));
     tracked!(polonius, true);
@@ -774,11 +814,13 @@ macro_rules! tracked {
     tracked!(relax_elf_relocations, Some(true));
     tracked!(relro_level, Some(RelroLevel::Full));
     tracked!(remap_cwd_prefix, Some(PathBuf::from("abc")));
+    tracked!(remap_path_scope, RemapPathScopeComponents::MACRO);
     tracked!(report_delayed_bugs, true);
     tracked!(sanitizer, SanitizerSet::ADDRESS);
     tracked!(sanitizer_memory_track_origins, 2);
     tracked!(sanitizer_recover, SanitizerSet::ADDRESS);
     tracked!(saturating_float_casts, Some(true));
+    tracked!(select_ld, true);
     tracked!(share_generics, Some(true));
     tracked!(show_span, Some(String::from("abc")));
     tracked!(simulate_remapped_rust_src_base, Some(PathBuf::from("/rustc/abc")));
@@ -797,6 +839,7 @@ macro_rules! tracked {
     tracked!(use_ctors_section, Some(true));
     tracked!(verify_llvm_ir, true);
     tracked!(wasi_exec_model, Some(WasiExecModel::Reactor));
+    tracked!(wasm_exception_handling, true);
 
     macro_rules! tracked_no_crate_hash {
         ($name: ident, $non_default_value: expr) => {
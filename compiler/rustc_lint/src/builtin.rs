@@ -38,6 +38,7 @@
 use rustc_hir::def_id::{DefId, LocalDefId, LocalDefIdSet, CRATE_DEF_ID};
 use rustc_hir::{ForeignItemKind, GenericParamKind, HirId, PatKind, PredicateOrigin};
 use rustc_index::vec::Idx;
+use rustc_infer::infer::TyCtxtInferExt;
 use rustc_middle::lint::{in_external_macro, LintDiagnosticBuilder};
 use rustc_middle::ty::layout::{LayoutError, LayoutOf};
 use rustc_middle::ty::print::with_no_trimmed_paths;
@@ -50,6 +51,7 @@
 use rustc_span::symbol::{kw, sym, Ident, Symbol};
 use rustc_span::{BytePos, InnerSpan, Span};
 use rustc_target::abi::VariantIdx;
+use rustc_trait_selection::traits::query::evaluate_obligation::InferCtxtExt;
 use rustc_trait_selection::traits::{self, misc::can_type_implement_copy};
 
 use crate::nonstandard_style::{method_context, MethodLateContext};
@@ -1658,10 +1660,36 @@ fn check_item(&mut self, cx: &LateContext<'_>, it: &hir::Item<'_>) {
     "these bounds don't depend on an type parameters"
 }
 
+declare_lint! {
+    /// The `trivially_false_bounds` lint detects trait bounds that don't
+    /// depend on any type parameters and can never hold.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// #![feature(trivial_bounds)]
+    /// # #![allow(trivial_bounds)]
+    /// pub fn f() where String: Copy {}
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// Without the `trivial_bounds` feature, a bound that is never true is an
+    /// error. With the feature it is accepted, but the item can't be used,
+    /// as the bound has to hold wherever the item is referred to. This is
+    /// rarely intended outside of macros, and usually means that the subject
+    /// of the bound is a typo, like `String: Copy` instead of `T: Copy`.
+    TRIVIALLY_FALSE_BOUNDS,
+    Warn,
+    "bounds that don't depend on any type parameters and never hold"
+}
+
 declare_lint_pass!(
     /// Lint for trait and lifetime bounds that don't depend on type parameters
     /// which either do nothing, or stop the item from being used.
-    TrivialConstraints => [TRIVIAL_BOUNDS]
+    TrivialConstraints => [TRIVIAL_BOUNDS, TRIVIALLY_FALSE_BOUNDS]
 );
 
 impl<'tcx> LateLintPass<'tcx> for TrivialConstraints {
@@ -1699,6 +1727,28 @@ fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::Item<'tcx>) {
                         ))
                         .emit();
                     });
+
+                    // Like the check of these bounds without the feature, only look at the
+                    // bounds without late-bound regions, and skip the ones that need to be
+                    // normalized.
+                    if let Trait(..) = predicate.kind().skip_binder()
+                        && !predicate.has_late_bound_regions()
+                        && !predicate.has_projections()
+                        && !cx.tcx.infer_ctxt().enter(|infcx| {
+                            let obligation = traits::Obligation::new(
+                                traits::ObligationCause::dummy(),
+                                ty::ParamEnv::empty(),
+                                predicate,
+                            );
+                            infcx.predicate_may_hold(&obligation)
+                        })
+                    {
+                        cx.struct_span_lint(TRIVIALLY_FALSE_BOUNDS, span, |lint| {
+                            lint.build(&format!("trait bound `{}` can never hold", predicate))
+                                .note("the bound has to hold wherever the item is used")
+                                .emit();
+                        });
+                    }
                 }
             }
         }
@@ -1724,7 +1774,8 @@ fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::Item<'tcx>) {
         UNSTABLE_FEATURES,
         UNREACHABLE_PUB,
         TYPE_ALIAS_BOUNDS,
-        TRIVIAL_BOUNDS
+        TRIVIAL_BOUNDS,
+        TRIVIALLY_FALSE_BOUNDS
     ]
 );
 
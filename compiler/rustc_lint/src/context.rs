@@ -22,7 +22,7 @@
 use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::sync;
 use rustc_errors::{add_elided_lifetime_in_path_suggestion, struct_span_err};
-use rustc_errors::{Applicability, MultiSpan, SuggestionStyle};
+use rustc_errors::{Applicability, DiagnosticBuilder, MultiSpan, SuggestionStyle};
 use rustc_hir as hir;
 use rustc_hir::def::Res;
 use rustc_hir::def_id::{CrateNum, DefId};
@@ -323,8 +323,8 @@ pub fn check_lint_name_cmdline(
         level: Level,
         registered_tools: &RegisteredTools,
     ) {
-        let (tool_name, lint_name_only) = parse_lint_and_tool_name(lint_name);
-        if lint_name_only == crate::WARNINGS.name_lower() && level == Level::ForceWarn {
+        let (_, lint_name_only) = parse_lint_and_tool_name(lint_name);
+        if lint_name_only == crate::WARNINGS.name_lower() && matches!(level, Level::ForceWarn(_)) {
             struct_span_err!(
                 sess,
                 DUMMY_SP,
@@ -335,7 +335,47 @@ pub fn check_lint_name_cmdline(
             .emit();
             return;
         }
-        let db = match self.check_lint_name(lint_name_only, tool_name, registered_tools) {
+        if let Some(mut db) = self.check_requested_lint_name(sess, lint_name, registered_tools) {
+            let msg = format!(
+                "requested on the command line with `{} {}`",
+                match level {
+                    Level::Allow => "-A",
+                    Level::Warn => "-W",
+                    Level::ForceWarn(_) => "--force-warn",
+                    Level::Deny => "-D",
+                    Level::Forbid => "-F",
+                    Level::Expect(_) => {
+                        unreachable!("lints with the level of `expect` should not run this code");
+                    }
+                },
+                lint_name
+            );
+            db.note(&msg);
+            db.emit();
+        }
+    }
+
+    /// Like `check_lint_name_cmdline`, for the names of the `-Zlint-config` file.
+    pub fn check_lint_name_in_config(
+        &self,
+        sess: &Session,
+        lint_name: &str,
+        registered_tools: &RegisteredTools,
+    ) {
+        if let Some(mut db) = self.check_requested_lint_name(sess, lint_name, registered_tools) {
+            db.note(&format!("requested in the lint configuration file with `{}`", lint_name));
+            db.emit();
+        }
+    }
+
+    fn check_requested_lint_name<'s>(
+        &self,
+        sess: &'s Session,
+        lint_name: &str,
+        registered_tools: &RegisteredTools,
+    ) -> Option<DiagnosticBuilder<'s, ()>> {
+        let (tool_name, lint_name_only) = parse_lint_and_tool_name(lint_name);
+        match self.check_lint_name(lint_name_only, tool_name, registered_tools) {
             CheckLintNameResult::Ok(_) => None,
             CheckLintNameResult::Warning(ref msg, _) => Some(sess.struct_warn(msg)),
             CheckLintNameResult::NoLint(suggestion) => {
@@ -367,25 +407,6 @@ pub fn check_lint_name_cmdline(
                 )
                 .forget_guarantee(),
             ),
-        };
-
-        if let Some(mut db) = db {
-            let msg = format!(
-                "requested on the command line with `{} {}`",
-                match level {
-                    Level::Allow => "-A",
-                    Level::Warn => "-W",
-                    Level::ForceWarn => "--force-warn",
-                    Level::Deny => "-D",
-                    Level::Forbid => "-F",
-                    Level::Expect(_) => {
-                        unreachable!("lints with the level of `expect` should not run this code");
-                    }
-                },
-                lint_name
-            );
-            db.note(&msg);
-            db.emit();
         }
     }
 
@@ -0,0 +1,136 @@
+use crate::{context::LintContext, LateContext, LateLintPass};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_errors::MultiSpan;
+use rustc_hir as hir;
+use rustc_middle::mir::{ProjectionElem, VarDebugInfoContents};
+use rustc_middle::ty::layout::LayoutOf;
+use rustc_middle::ty::{self, GeneratorSubsts};
+use rustc_session::lint::Level;
+use rustc_span::symbol::sym;
+use rustc_span::Span;
+
+declare_lint! {
+    /// The `large_future` lint detects `async fn`s whose future takes more memory than a limit,
+    /// 16384 bytes unless `large_future.limit` is set in the lint configuration file.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,edition2018
+    /// #![warn(large_future)]
+    /// async fn send(data: [u8; 32768]) {
+    ///     std::future::ready(()).await;
+    ///     drop(data);
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// The future of an `async fn` stores all the values that are alive across an `.await`, so
+    /// it can get very large, and it is moved around and copied into the futures of its
+    /// callers. Boxing the large values, or the future itself with `Box::pin`, keeps them out of
+    /// the future.
+    ///
+    /// This lint is "allow" by default because computing the size of futures requires their
+    /// optimized MIR, which is expensive to compute when only checking a crate.
+    pub LARGE_FUTURE,
+    Allow,
+    "detects `async fn`s with a large future"
+}
+
+declare_lint_pass!(LargeFuture => [LARGE_FUTURE]);
+
+/// The limit when the lint configuration file doesn't set `large_future.limit`.
+const DEFAULT_LIMIT: u64 = 16384;
+
+/// How many of the largest values saved in the future are pointed out.
+const REPORTED_LOCALS: usize = 3;
+
+impl<'tcx> LateLintPass<'tcx> for LargeFuture {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx hir::Expr<'tcx>) {
+        let hir::ExprKind::Closure(_, _, body_id, _, _) = expr.kind else { return };
+        let async_fn = hir::GeneratorKind::Async(hir::AsyncGeneratorKind::Fn);
+        if cx.tcx.hir().body(body_id).generator_kind != Some(async_fn) {
+            return;
+        }
+        // Don't compute the layout of every future when the lint is allowed, as it is by default.
+        if cx.tcx.lint_level_at_node(LARGE_FUTURE, expr.hir_id).0 == Level::Allow {
+            return;
+        }
+        let ty = cx.typeck_results().expr_ty(expr);
+        let ty::Generator(def_id, ..) = *ty.kind() else { return };
+        // The layout of generic futures can't be computed without knowing their parameters.
+        let Ok(layout) = cx.layout_of(ty) else { return };
+        let limit = cx
+            .sess()
+            .opts
+            .lint_config
+            .integer_option("large_future", "limit")
+            .unwrap_or(DEFAULT_LIMIT);
+        let size = layout.size.bytes();
+        if size <= limit {
+            return;
+        }
+        let Some(generator_layout) = cx.tcx.generator_layout(def_id) else { return };
+
+        // The debuginfo of the saved locals refers to them as fields of the variants of the
+        // generator, which is how their names are found.
+        let mut names = FxHashMap::default();
+        for var in &cx.tcx.optimized_mir(def_id).var_debug_info {
+            let VarDebugInfoContents::Place(place) = &var.value else { continue };
+            if let [.., ProjectionElem::Downcast(_, variant), ProjectionElem::Field(field, _)] =
+                place.projection[..]
+            {
+                let fields = generator_layout.variant_fields.get(variant);
+                if let Some(&local) = fields.and_then(|fields| fields.get(field)) {
+                    names.entry(local).or_insert((var.name, var.source_info.span));
+                }
+            }
+        }
+
+        let mut locals: Vec<_> = generator_layout
+            .field_tys
+            .iter_enumerated()
+            .filter_map(|(local, &ty)| Some((local, cx.layout_of(ty).ok()?.size.bytes())))
+            .filter(|&(_, size)| size > 0)
+            .collect();
+        locals.sort_by_key(|&(local, size)| (std::cmp::Reverse(size), local));
+
+        let fn_span = cx.tcx.def_span(cx.tcx.hir().get_parent_item(expr.hir_id));
+        cx.struct_span_lint(LARGE_FUTURE, fn_span, |lint| {
+            let mut err = lint.build(&format!(
+                "the future of this `async fn` takes {} bytes, more than the limit of {} bytes",
+                size, limit
+            ));
+            for &(local, local_size) in locals.iter().take(REPORTED_LOCALS) {
+                // The first variants are the unresumed, returned and poisoned states, the
+                // others are the awaits.
+                let awaits: Vec<Span> = generator_layout
+                    .variant_fields
+                    .iter_enumerated()
+                    .filter(|(variant, fields)| {
+                        variant.as_usize() > GeneratorSubsts::POISONED && fields.contains(&local)
+                    })
+                    .map(|(variant, _)| generator_layout.variant_source_info[variant].span)
+                    .collect();
+                let (what, mut spans) = match names.get(&local) {
+                    // The future of an `.await` is kept in a binding of the desugaring.
+                    Some(&(name, _)) if name == sym::__awaitee => {
+                        ("an awaited future".to_owned(), MultiSpan::from_spans(awaits.clone()))
+                    }
+                    Some(&(name, span)) => (format!("`{}`", name), MultiSpan::from_span(span)),
+                    None => (
+                        format!("a value of type `{}`", generator_layout.field_tys[local]),
+                        MultiSpan::from_spans(awaits.clone()),
+                    ),
+                };
+                for &span in &awaits {
+                    spans.push_span_label(span, "kept alive across this await");
+                }
+                err.span_note(spans, &format!("{} takes {} bytes of the future", what, local_size));
+            }
+            err.emit();
+        });
+    }
+}
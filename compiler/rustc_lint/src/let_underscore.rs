@@ -0,0 +1,96 @@
+use crate::{LateContext, LateLintPass, LintContext};
+use rustc_errors::Applicability;
+use rustc_hir as hir;
+
+declare_lint! {
+    /// The `let_underscore_drop` lint detects `let _ = expr;` statements where the value of
+    /// `expr` has a destructor, which runs right away instead of at the end of the scope.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// #![warn(let_underscore_drop)]
+    /// use std::sync::Mutex;
+    ///
+    /// let data = Mutex::new(0);
+    /// let _ = data.lock().unwrap();
+    /// println!("data is locked, or is it?");
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// `_` is not a binding, so the value of `let _ = expr;` is not stored anywhere and is
+    /// dropped at the end of the statement. This is different from a binding like `let _guard =
+    /// expr;`, whose value is only dropped at the end of the scope, which matters for values
+    /// like lock guards whose destructor has side effects. Binding the value to a name starting
+    /// with an underscore keeps it alive, while `drop(expr)` makes it clear that it is dropped
+    /// immediately.
+    ///
+    /// This lint is "allow" by default because `let _ = expr;` is also commonly used to
+    /// silence `must_use` warnings on values whose destructor doesn't matter.
+    pub LET_UNDERSCORE_DROP,
+    Allow,
+    "detects `let _ = expr;` statements where `expr` has a destructor"
+}
+
+declare_lint_pass!(LetUnderscore => [LET_UNDERSCORE_DROP]);
+
+impl<'tcx> LateLintPass<'tcx> for LetUnderscore {
+    fn check_block(&mut self, cx: &LateContext<'tcx>, block: &'tcx hir::Block<'tcx>) {
+        for (i, stmt) in block.stmts.iter().enumerate() {
+            let hir::StmtKind::Local(local) = stmt.kind else { continue };
+            // Whether anything runs between the end of the statement and the end of the scope,
+            // in which case it matters when the value is dropped.
+            let is_last = i + 1 == block.stmts.len() && block.expr.is_none();
+            check_local(cx, local, is_last);
+        }
+    }
+}
+
+fn check_local<'tcx>(cx: &LateContext<'tcx>, local: &'tcx hir::Local<'tcx>, is_last: bool) {
+    if !matches!(local.pat.kind, hir::PatKind::Wild)
+        || local.source != hir::LocalSource::Normal
+        || local.span.from_expansion()
+    {
+        return;
+    }
+    let Some(init) = local.init else { return };
+    // `let _ = place;` doesn't move out of the place, so nothing is dropped.
+    if init.is_syntactic_place_expr() {
+        return;
+    }
+    let ty = cx.typeck_results().expr_ty(init);
+    if !ty.has_significant_drop(cx.tcx, cx.param_env) {
+        return;
+    }
+    cx.struct_span_lint(LET_UNDERSCORE_DROP, local.span, |lint| {
+        let mut err = lint.build("non-binding `let` on a value with a destructor");
+        err.span_label(local.span, format!("this value of type `{}` is dropped immediately", ty));
+        err.note(
+            "unlike a binding like `let _guard = ...`, `let _ = ...` doesn't keep the value \
+             alive until the end of the scope",
+        );
+        if !is_last {
+            err.span_suggestion_verbose(
+                local.pat.span,
+                "bind the value to an unused variable to drop it at the end of the scope",
+                "_unused",
+                Applicability::MachineApplicable,
+            );
+        }
+        // The type annotation would be lost, and with it maybe the inferred type of `init`.
+        if local.ty.is_none() {
+            err.multipart_suggestion_verbose(
+                "call `drop` to make it explicit that the value is dropped immediately",
+                vec![
+                    (local.span.until(init.span), "drop(".to_owned()),
+                    (init.span.shrink_to_hi(), ")".to_owned()),
+                ],
+                Applicability::MachineApplicable,
+            );
+        }
+        err.emit();
+    });
+}
@@ -111,13 +111,15 @@ fn process_command_line(&mut self, sess: &Session, store: &LintStore) {
             let orig_level = level;
             let lint_flag_val = Symbol::intern(lint_name);
 
-            let Ok(ids) = store.find_lints(&lint_name) else {
+            let Ok(ids) = store.find_lints(lint_name) else {
                 // errors handled in check_lint_name_cmdline above
                 continue
             };
             for id in ids {
                 // ForceWarn and Forbid cannot be overridden
-                if let Some((Level::ForceWarn | Level::Forbid, _)) = self.current_specs().get(&id) {
+                if let Some((Level::ForceWarn(_) | Level::Forbid, _)) =
+                    self.current_specs().get(&id)
+                {
                     continue;
                 }
 
@@ -127,6 +129,40 @@ fn process_command_line(&mut self, sess: &Session, store: &LintStore) {
                 }
             }
         }
+
+        self.process_lint_config(sess, store);
+    }
+
+    /// Applies the levels of the `-Zlint-config` file on top of the command line ones. The
+    /// levels of groups are applied first, so that the levels of their lints can override
+    /// them regardless of the order of the file.
+    fn process_lint_config(&mut self, sess: &Session, store: &LintStore) {
+        let (groups, lints): (Vec<_>, Vec<_>) = sess
+            .opts
+            .lint_config
+            .levels
+            .iter()
+            .partition(|(lint_name, _)| store.is_lint_group(Symbol::intern(lint_name)));
+        for &(ref lint_name, level) in groups.into_iter().chain(lints) {
+            store.check_lint_name_in_config(sess, lint_name, self.registered_tools);
+            let Ok(ids) = store.find_lints(lint_name) else {
+                // errors handled in check_lint_name_in_config above
+                continue
+            };
+            for id in ids {
+                // ForceWarn and Forbid cannot be overridden
+                if let Some((Level::ForceWarn(_) | Level::Forbid, _)) =
+                    self.current_specs().get(&id)
+                {
+                    continue;
+                }
+
+                if self.check_gated_lint(id, DUMMY_SP) {
+                    let src = LintLevelSource::ConfigFile(Symbol::intern(lint_name), level);
+                    self.current_specs_mut().insert(id, (level, src));
+                }
+            }
+        }
     }
 
     /// Attempts to insert the `id` to `level_src` map entry. If unsuccessful
@@ -152,8 +188,9 @@ fn insert_spec(&mut self, id: LintId, (level, src): LevelAndSource) {
                 let id_name = id.lint.name_lower();
                 let fcw_warning = match old_src {
                     LintLevelSource::Default => false,
-                    LintLevelSource::Node(symbol, _, _) => self.store.is_lint_group(symbol),
+                    LintLevelSource::Node(symbol, _, _, _) => self.store.is_lint_group(symbol),
                     LintLevelSource::CommandLine(symbol, _) => self.store.is_lint_group(symbol),
+                    LintLevelSource::ConfigFile(symbol, _) => self.store.is_lint_group(symbol),
                 };
                 debug!(
                     "fcw_warning={:?}, specs.get(&id) = {:?}, old_src={:?}, id_name={:?}",
@@ -172,7 +209,7 @@ fn insert_spec(&mut self, id: LintId, (level, src): LevelAndSource) {
                                 id.to_string()
                             ));
                         }
-                        LintLevelSource::Node(_, forbid_source_span, reason) => {
+                        LintLevelSource::Node(_, _, forbid_source_span, reason) => {
                             diag.span_label(forbid_source_span, "`forbid` level set here");
                             if let Some(rationale) = reason {
                                 diag.note(rationale.as_str());
@@ -181,6 +218,9 @@ fn insert_spec(&mut self, id: LintId, (level, src): LevelAndSource) {
                         LintLevelSource::CommandLine(_, _) => {
                             diag.note("`forbid` lint level was set on command line");
                         }
+                        LintLevelSource::ConfigFile(..) => {
+                            diag.note("`forbid` lint level was set in the lint configuration file");
+                        }
                     }
                 };
                 if !fcw_warning {
@@ -226,8 +266,14 @@ fn insert_spec(&mut self, id: LintId, (level, src): LevelAndSource) {
             return;
         }
 
-        if let Level::ForceWarn = old_level {
-            self.current_specs_mut().insert(id, (old_level, old_src));
+        // A `--force-warn` can't be overridden, but an expectation of the lint is still
+        // fulfilled by the emitted warning.
+        if let Level::ForceWarn(_) = old_level {
+            let expect_id = match level {
+                Level::Expect(expect_id) => Some(expect_id),
+                _ => None,
+            };
+            self.current_specs_mut().insert(id, (Level::ForceWarn(expect_id), old_src));
         } else {
             self.current_specs_mut().insert(id, (level, src));
         }
@@ -389,6 +435,7 @@ pub(crate) fn push(
                         }
                         let src = LintLevelSource::Node(
                             meta_item.path.segments.last().expect("empty lint name").ident.name,
+                            level,
                             sp,
                             reason,
                         );
@@ -406,6 +453,7 @@ pub(crate) fn push(
                                     &format!("{}::{}", tool_ident.unwrap().name, name);
                                 let src = LintLevelSource::Node(
                                     Symbol::intern(complete_name),
+                                    level,
                                     sp,
                                     reason,
                                 );
@@ -452,6 +500,7 @@ pub(crate) fn push(
 
                                 let src = LintLevelSource::Node(
                                     Symbol::intern(&new_lint_name),
+                                    level,
                                     sp,
                                     reason,
                                 );
@@ -560,7 +609,7 @@ pub(crate) fn push(
                     if let CheckLintNameResult::Ok(ids) =
                         self.store.check_lint_name(&new_name, None, self.registered_tools)
                     {
-                        let src = LintLevelSource::Node(Symbol::intern(&new_name), sp, reason);
+                        let src = LintLevelSource::Node(Symbol::intern(&new_name), level, sp, reason);
                         for &id in ids {
                             if self.check_gated_lint(id, attr.span) {
                                 self.insert_spec(id, (level, src));
@@ -585,7 +634,7 @@ pub(crate) fn push(
                     continue;
                 }
 
-                let LintLevelSource::Node(lint_attr_name, lint_attr_span, _) = *src else {
+                let LintLevelSource::Node(lint_attr_name, _, lint_attr_span, _) = *src else {
                     continue
                 };
 
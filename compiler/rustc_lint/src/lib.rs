@@ -51,7 +51,9 @@
 mod expect;
 pub mod hidden_unicode_codepoints;
 mod internal;
+mod large_future;
 mod late;
+mod let_underscore;
 mod levels;
 mod methods;
 mod non_ascii_idents;
@@ -60,7 +62,9 @@
 mod noop_method_call;
 mod pass_by_value;
 mod passes;
+mod redundant_bounds;
 mod redundant_semicolon;
+pub mod tool;
 mod traits;
 mod types;
 mod unused;
@@ -83,12 +87,15 @@
 use enum_intrinsics_non_enums::EnumIntrinsicsNonEnums;
 use hidden_unicode_codepoints::*;
 use internal::*;
+use large_future::LargeFuture;
+use let_underscore::LetUnderscore;
 use methods::*;
 use non_ascii_idents::*;
 use non_fmt_panic::NonPanicFmt;
 use nonstandard_style::*;
 use noop_method_call::*;
 use pass_by_value::*;
+use redundant_bounds::RedundantBounds;
 use redundant_semicolon::*;
 use traits::*;
 use types::*;
@@ -100,7 +107,7 @@
 pub use context::{EarlyContext, LateContext, LintContext};
 pub use early::{check_ast_node, EarlyCheckNode};
 pub use late::check_crate;
-pub use passes::{EarlyLintPass, LateLintPass};
+pub use passes::{EarlyLintPass, EarlyLintPassObject, LateLintPass, LateLintPassObject};
 pub use rustc_session::lint::Level::{self, *};
 pub use rustc_session::lint::{BufferedEarlyLint, FutureIncompatibleInfo, Lint, LintId};
 pub use rustc_session::lint::{LintArray, LintPass};
@@ -181,6 +188,9 @@ macro_rules! late_lint_passes {
                 EnumIntrinsicsNonEnums: EnumIntrinsicsNonEnums,
                 InvalidAtomicOrdering: InvalidAtomicOrdering,
                 NamedAsmLabels: NamedAsmLabels,
+                LargeFuture: LargeFuture,
+                LetUnderscore: LetUnderscore,
+                RedundantBounds: RedundantBounds,
             ]
         );
     };
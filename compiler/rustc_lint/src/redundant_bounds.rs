@@ -0,0 +1,150 @@
+use crate::{LateContext, LateLintPass, LintContext};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_errors::Applicability;
+use rustc_hir as hir;
+use rustc_hir::def_id::LocalDefId;
+use rustc_middle::ty;
+use rustc_span::Span;
+use rustc_trait_selection::traits::supertraits;
+
+declare_lint! {
+    /// The `redundant_bounds` lint detects trait bounds that are written more than once, or
+    /// that are already implied by a supertrait of another bound on the same type.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// #![warn(redundant_bounds)]
+    /// fn max<T: Ord + PartialOrd + Ord>(a: T, b: T) -> T {
+    ///     if a > b { a } else { b }
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// A bound that is repeated, or that names a supertrait of another bound (`PartialOrd` is a
+    /// supertrait of `Ord`), doesn't add any requirement and can be removed. Only bounds written
+    /// in generic parameter lists and `where` clauses are checked, and bounds with associated
+    /// type bindings are never considered redundant since the bindings constrain more than the
+    /// trait itself.
+    pub REDUNDANT_BOUNDS,
+    Allow,
+    "detects trait bounds that are duplicated or implied by another bound"
+}
+
+declare_lint_pass!(RedundantBounds => [REDUNDANT_BOUNDS]);
+
+impl<'tcx> LateLintPass<'tcx> for RedundantBounds {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::Item<'tcx>) {
+        match item.kind {
+            hir::ItemKind::Fn(_, ref generics, _)
+            | hir::ItemKind::Enum(_, ref generics)
+            | hir::ItemKind::Struct(_, ref generics)
+            | hir::ItemKind::Union(_, ref generics)
+            | hir::ItemKind::Trait(_, _, ref generics, _, _)
+            | hir::ItemKind::Impl(hir::Impl { ref generics, .. }) => {
+                check_generics(cx, item.def_id, generics)
+            }
+            _ => {}
+        }
+    }
+
+    fn check_trait_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::TraitItem<'tcx>) {
+        check_generics(cx, item.def_id, &item.generics);
+    }
+
+    fn check_impl_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::ImplItem<'tcx>) {
+        check_generics(cx, item.def_id, &item.generics);
+    }
+}
+
+/// A trait bound as written by the user, with its position in `Generics::predicates`.
+struct WrittenBound<'tcx> {
+    predicate_pos: usize,
+    bound_pos: usize,
+    span: Span,
+    predicate: ty::PolyTraitPredicate<'tcx>,
+}
+
+fn check_generics<'tcx>(
+    cx: &LateContext<'tcx>,
+    def_id: LocalDefId,
+    generics: &'tcx hir::Generics<'tcx>,
+) {
+    // Every bound is lowered to its own predicate with the bound's span, so duplicates are
+    // kept apart here even though they are equal.
+    let lowered: FxHashMap<Span, ty::PolyTraitPredicate<'tcx>> = cx
+        .tcx
+        .explicit_predicates_of(def_id)
+        .predicates
+        .iter()
+        .filter_map(|&(predicate, span)| Some((span, predicate.to_opt_poly_trait_pred()?)))
+        .collect();
+
+    let mut bounds = Vec::new();
+    for (predicate_pos, predicate) in generics.predicates.iter().enumerate() {
+        let hir::WherePredicate::BoundPredicate(predicate) = predicate else { continue };
+        for (bound_pos, bound) in predicate.bounds.iter().enumerate() {
+            let hir::GenericBound::Trait(poly_trait_ref, modifier) = bound else { continue };
+            let has_bindings = poly_trait_ref
+                .trait_ref
+                .path
+                .segments
+                .last()
+                .map_or(false, |segment| !segment.args().bindings.is_empty());
+            let span = bound.span();
+            if *modifier != hir::TraitBoundModifier::None || has_bindings || span.from_expansion()
+            {
+                continue;
+            }
+            let Some(&predicate) = lowered.get(&span) else { continue };
+            bounds.push(WrittenBound { predicate_pos, bound_pos, span, predicate });
+        }
+    }
+
+    for (i, bound) in bounds.iter().enumerate() {
+        let removal_span = generics.span_for_bound_removal(bound.predicate_pos, bound.bound_pos);
+        if let Some(first) = bounds[..i].iter().find(|other| other.predicate == bound.predicate) {
+            cx.struct_span_lint(REDUNDANT_BOUNDS, bound.span, |lint| {
+                lint.build(&format!("duplicate trait bound `{}`", bound.predicate))
+                    .span_label(first.span, "first specified here")
+                    .span_suggestion_verbose(
+                        removal_span,
+                        "remove the duplicate bound",
+                        "",
+                        Applicability::MachineApplicable,
+                    )
+                    .emit();
+            });
+            continue;
+        }
+
+        let trait_ref = bound.predicate.to_poly_trait_ref();
+        let implied_by = bounds.iter().find(|other| {
+            let other_trait_ref = other.predicate.to_poly_trait_ref();
+            other_trait_ref != trait_ref
+                && supertraits(cx.tcx, other_trait_ref).any(|supertrait| supertrait == trait_ref)
+        });
+        if let Some(implied_by) = implied_by {
+            cx.struct_span_lint(REDUNDANT_BOUNDS, bound.span, |lint| {
+                lint.build(&format!(
+                    "trait bound `{}` is implied by `{}`",
+                    bound.predicate, implied_by.predicate
+                ))
+                .span_label(
+                    implied_by.span,
+                    format!("this bound already implies `{}`", bound.predicate),
+                )
+                .span_suggestion_verbose(
+                    removal_span,
+                    "remove the redundant bound",
+                    "",
+                    Applicability::MachineApplicable,
+                )
+                .emit();
+            });
+        }
+    }
+}
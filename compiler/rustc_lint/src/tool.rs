@@ -0,0 +1,197 @@
+//! An interface for tools like clippy that run lints of their own from a custom driver.
+//!
+//! Drivers get the [`LintStore`] in the `register_lints` callback of
+//! `rustc_interface::Config`, but its methods follow the needs of the compiler and change
+//! along with it. Tools should instead wrap it in a [`ToolLintRegistry`], which also checks
+//! that their lints and lint groups are namespaced under the name of the tool, like
+//! `clippy::needless_return`. Lints that are declared with `@future_incompatible` in
+//! [`declare_tool_lint!`](rustc_session::declare_tool_lint) are part of the
+//! `future_incompatible` group and of the future incompatibility report, like the ones of
+//! rustc.
+//!
+//! The late lint passes of tools get a [`LateContext`], which can be wrapped in a
+//! [`ToolLateContext`]. The signatures of the methods of both wrappers are only changed along
+//! with [`TOOL_API_VERSION`], so that tools can check that they are built against the
+//! interface they expect.
+
+use crate::context::{LateContext, LintContext, LintStore};
+use crate::passes::{EarlyLintPassObject, LateLintPassObject};
+use rustc_data_structures::sync;
+use rustc_errors::MultiSpan;
+use rustc_hir as hir;
+use rustc_hir::def::Res;
+use rustc_hir::def_id::DefId;
+use rustc_middle::lint::{in_external_macro, LintDiagnosticBuilder};
+use rustc_middle::ty::{ParamEnv, Ty, TyCtxt, TypeckResults};
+use rustc_session::lint::{Level, Lint, LintId};
+use rustc_span::symbol::Symbol;
+use rustc_span::Span;
+
+/// The version of [`ToolLintRegistry`] and [`ToolLateContext`], which is bumped whenever one
+/// of their methods is changed or removed.
+pub const TOOL_API_VERSION: u32 = 1;
+
+/// Registers the lints, lint groups and lint passes of a tool.
+pub struct ToolLintRegistry<'a> {
+    store: &'a mut LintStore,
+    tool: &'static str,
+}
+
+impl<'a> ToolLintRegistry<'a> {
+    /// Registers things for the tool `tool`, whose lints are named `tool::name`.
+    pub fn new(store: &'a mut LintStore, tool: &'static str) -> Self {
+        ToolLintRegistry { store, tool }
+    }
+
+    #[track_caller]
+    fn check_name(&self, name: &str, what: &str) {
+        if !name.strip_prefix(self.tool).map_or(false, |name| name.starts_with("::")) {
+            panic!("{} `{}` of `{}` must be named `{}::...`", what, name, self.tool, self.tool);
+        }
+    }
+
+    /// Registers lints declared with [`declare_tool_lint!`](rustc_session::declare_tool_lint).
+    #[track_caller]
+    pub fn register_lints(&mut self, lints: &[&'static Lint]) {
+        for lint in lints {
+            self.check_name(lint.name, "lint");
+        }
+        self.store.register_lints(lints);
+    }
+
+    /// Registers the lint group `name`, which has to be namespaced like the lints.
+    #[track_caller]
+    pub fn register_group(&mut self, name: &'static str, lints: &[&'static Lint]) {
+        self.check_name(name, "lint group");
+        let lints = lints.iter().map(|&lint| LintId::of(lint)).collect();
+        self.store.register_group(true, name, None, lints);
+    }
+
+    /// Registers a pass that runs on the AST after macro expansion.
+    pub fn register_early_pass(
+        &mut self,
+        pass: impl Fn() -> EarlyLintPassObject + 'static + sync::Send + sync::Sync,
+    ) {
+        self.store.register_early_pass(pass);
+    }
+
+    /// Registers a pass that runs once on the HIR of the whole crate, after type checking.
+    pub fn register_late_pass(
+        &mut self,
+        pass: impl Fn() -> LateLintPassObject + 'static + sync::Send + sync::Sync,
+    ) {
+        self.store.register_late_pass(pass);
+    }
+
+    /// Registers a pass that is created anew for every module, and doesn't see the items of
+    /// other modules. This is cheaper than [`register_late_pass`](Self::register_late_pass)
+    /// with incremental compilation.
+    pub fn register_late_mod_pass(
+        &mut self,
+        pass: impl Fn() -> LateLintPassObject + 'static + sync::Send + sync::Sync,
+    ) {
+        self.store.register_late_mod_pass(pass);
+    }
+
+    /// Makes `old_name` an alias of the registered lint `new_name`, which warns when used.
+    #[track_caller]
+    pub fn register_renamed(&mut self, old_name: &str, new_name: &str) {
+        self.check_name(old_name, "lint");
+        self.store.register_renamed(old_name, new_name);
+    }
+
+    /// Makes the removed lint `name` warn with `reason` when used.
+    #[track_caller]
+    pub fn register_removed(&mut self, name: &str, reason: &str) {
+        self.check_name(name, "lint");
+        self.store.register_removed(name, reason);
+    }
+}
+
+/// The context of the late lint passes of tools.
+#[derive(Clone, Copy)]
+pub struct ToolLateContext<'a, 'tcx> {
+    cx: &'a LateContext<'tcx>,
+}
+
+impl<'a, 'tcx> ToolLateContext<'a, 'tcx> {
+    pub fn new(cx: &'a LateContext<'tcx>) -> Self {
+        ToolLateContext { cx }
+    }
+
+    /// The wrapped context, for everything that isn't covered by the methods below.
+    pub fn inner(&self) -> &'a LateContext<'tcx> {
+        self.cx
+    }
+
+    /// The type context. Unlike the methods below, its queries may change from one release to
+    /// the next.
+    pub fn tcx(&self) -> TyCtxt<'tcx> {
+        self.cx.tcx
+    }
+
+    /// The parameter environment of the item being checked.
+    pub fn param_env(&self) -> ParamEnv<'tcx> {
+        self.cx.param_env
+    }
+
+    /// The types the type checker computed for the current body, or `None` outside of bodies.
+    pub fn typeck_results(&self) -> Option<&'tcx TypeckResults<'tcx>> {
+        self.cx.maybe_typeck_results()
+    }
+
+    /// The type of `expr`, which has to be in the current body, after adjustments like
+    /// auto-deref are applied.
+    pub fn expr_ty(&self, expr: &hir::Expr<'_>) -> Ty<'tcx> {
+        self.cx.typeck_results().expr_ty_adjusted(expr)
+    }
+
+    /// What the path `qpath` of the node `id` in the current body resolves to.
+    pub fn qpath_res(&self, qpath: &hir::QPath<'_>, id: hir::HirId) -> Res {
+        self.cx.qpath_res(qpath, id)
+    }
+
+    /// The path of `def_id`, like `["std", "vec", "Vec"]`.
+    pub fn def_path(&self, def_id: DefId) -> Vec<Symbol> {
+        self.cx.get_def_path(def_id)
+    }
+
+    /// Whether the path of `def_id` is `path`.
+    pub fn match_def_path(&self, def_id: DefId, path: &[Symbol]) -> bool {
+        self.cx.match_def_path(def_id, path)
+    }
+
+    /// Whether `span` comes from a macro defined in another crate, where tools usually
+    /// shouldn't lint.
+    pub fn in_external_macro(&self, span: Span) -> bool {
+        in_external_macro(self.cx.sess(), span)
+    }
+
+    /// The level of `lint` at the node `hir_id`, with the attributes and command line flags
+    /// taken into account.
+    pub fn lint_level(&self, lint: &'static Lint, hir_id: hir::HirId) -> Level {
+        self.cx.tcx.lint_level_at_node(lint, hir_id).0
+    }
+
+    /// Emits `lint` at `span`, at the level of the node being checked. The lint is not emitted
+    /// if it is allowed there.
+    pub fn emit_lint(
+        &self,
+        lint: &'static Lint,
+        span: impl Into<MultiSpan>,
+        decorate: impl for<'b> FnOnce(LintDiagnosticBuilder<'b, ()>),
+    ) {
+        self.cx.struct_span_lint(lint, span, decorate)
+    }
+
+    /// Like [`emit_lint`](Self::emit_lint), at the level of the node `hir_id` instead.
+    pub fn emit_lint_at(
+        &self,
+        lint: &'static Lint,
+        hir_id: hir::HirId,
+        span: impl Into<MultiSpan>,
+        decorate: impl for<'b> FnOnce(LintDiagnosticBuilder<'b, ()>),
+    ) {
+        self.cx.tcx.struct_span_lint_hir(lint, hir_id, span, decorate)
+    }
+}
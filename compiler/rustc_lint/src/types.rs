@@ -1438,6 +1438,9 @@ fn check_item(&mut self, cx: &LateContext<'_>, it: &hir::Item<'_>) {
     /// - Passing in a pair of orderings to `AtomicType::compare_exchange`,
     ///   `AtomicType::compare_exchange_weak`, or `AtomicType::fetch_update`
     ///   where the failure ordering is stronger than the success ordering.
+    ///
+    /// The methods are also checked when they are called through a path, like
+    /// `AtomicUsize::load(&atom, Ordering::Release)`.
     INVALID_ATOMIC_ORDERING,
     Deny,
     "usage of invalid atomic ordering in atomic operations and memory fences"
@@ -1467,10 +1470,29 @@ fn inherent_atomic_method_call<'hir>(
             sym::AtomicI64,
             sym::AtomicI128,
         ];
-        if let ExprKind::MethodCall(ref method_path, args, _) = &expr.kind
-            && recognized_names.contains(&method_path.ident.name)
-            && let Some(m_def_id) = cx.typeck_results().type_dependent_def_id(expr.hir_id)
-            && let Some(impl_did) = cx.tcx.impl_of_method(m_def_id)
+        let (method_name, m_def_id, args) = match expr.kind {
+            ExprKind::MethodCall(ref method_path, args, _)
+                if recognized_names.contains(&method_path.ident.name) =>
+            {
+                let m_def_id = cx.typeck_results().type_dependent_def_id(expr.hir_id)?;
+                (method_path.ident.name, m_def_id, args)
+            }
+            // Calls through a path, like `AtomicU8::load(&atom, ordering)`.
+            ExprKind::Call(ref func, args) if let ExprKind::Path(ref func_qpath) = func.kind => {
+                let segment = match func_qpath {
+                    hir::QPath::Resolved(_, path) => path.segments.last()?,
+                    hir::QPath::TypeRelative(_, segment) => *segment,
+                    hir::QPath::LangItem(..) => return None,
+                };
+                if !recognized_names.contains(&segment.ident.name) {
+                    return None;
+                }
+                let m_def_id = cx.qpath_res(func_qpath, func.hir_id).opt_def_id()?;
+                (segment.ident.name, m_def_id, args)
+            }
+            _ => return None,
+        };
+        if let Some(impl_did) = cx.tcx.impl_of_method(m_def_id)
             && let Some(adt) = cx.tcx.type_of(impl_did).ty_adt_def()
             // skip extension traits, only lint functions from the standard library
             && cx.tcx.trait_id_of_impl(impl_did).is_none()
@@ -1478,7 +1500,7 @@ fn inherent_atomic_method_call<'hir>(
             && cx.tcx.is_diagnostic_item(sym::atomic_mod, parent)
             && ATOMIC_TYPES.contains(&cx.tcx.item_name(adt.did()))
         {
-            return Some((method_path.ident.name, args));
+            return Some((method_name, args));
         }
         None
     }
@@ -1520,11 +1542,13 @@ fn check_atomic_load_store(cx: &LateContext<'_>, expr: &Expr<'_>) {
             cx.struct_span_lint(INVALID_ATOMIC_ORDERING, ordering_arg.span, |diag| {
                 if method == sym::load {
                     diag.build("atomic loads cannot have `Release` or `AcqRel` ordering")
+                        .note("`Release` orderings only apply to operations that write to memory")
                         .help("consider using ordering modes `Acquire`, `SeqCst` or `Relaxed`")
                         .emit()
                 } else {
                     debug_assert_eq!(method, sym::store);
                     diag.build("atomic stores cannot have `Acquire` or `AcqRel` ordering")
+                        .note("`Acquire` orderings only apply to operations that read from memory")
                         .help("consider using ordering modes `Release`, `SeqCst` or `Relaxed`")
                         .emit();
                 }
@@ -1543,6 +1567,7 @@ fn check_memory_fence(cx: &LateContext<'_>, expr: &Expr<'_>) {
         {
             cx.struct_span_lint(INVALID_ATOMIC_ORDERING, args[0].span, |diag| {
                 diag.build("memory fences cannot have `Relaxed` ordering")
+                    .note("a `Relaxed` fence doesn't order any memory accesses")
                     .help("consider using ordering modes `Acquire`, `Release`, `AcqRel` or `SeqCst`")
                     .emit();
             });
@@ -1589,6 +1614,7 @@ fn check_atomic_compare_exchange(cx: &LateContext<'_>, expr: &Expr<'_>) {
                         method,
                     );
                     diag.build(&msg)
+                        .note("a failed comparison only loads the value, so it can't release")
                         .help(&format!("consider using {} instead", suggested))
                         .emit();
                 });
@@ -1601,6 +1627,11 @@ fn check_atomic_compare_exchange(cx: &LateContext<'_>, expr: &Expr<'_>) {
                             success_ord,
                         );
                         diag.build(&msg)
+                            .note(
+                                "`Relaxed` failure orderings are allowed with any success \
+                                 ordering, `Acquire` ones with `Acquire`, `AcqRel` or `SeqCst`, \
+                                 and `SeqCst` ones only with `SeqCst`",
+                            )
                             .help(&format!("consider using {} instead", suggested))
                             .emit();
                     });
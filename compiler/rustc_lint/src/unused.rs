@@ -7,7 +7,9 @@
 use rustc_hir as hir;
 use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::DefId;
+use rustc_infer::traits::util::elaborate_predicates;
 use rustc_middle::ty::adjustment;
+use rustc_middle::ty::subst::Subst;
 use rustc_middle::ty::{self, Ty};
 use rustc_span::symbol::Symbol;
 use rustc_span::symbol::{kw, sym};
@@ -203,9 +205,13 @@ fn check_must_use_ty<'tcx>(
                     check_must_use_ty(cx, boxed_ty, expr, span, descr_pre, descr_post, plural_len)
                 }
                 ty::Adt(def, _) => check_must_use_def(cx, def.did(), span, descr_pre, descr_post),
-                ty::Opaque(def, _) => {
-                    let mut has_emitted = false;
-                    for &(predicate, _) in cx.tcx.explicit_item_bounds(def) {
+                ty::Opaque(def, substs) => {
+                    let bounds = cx.tcx.explicit_item_bounds(def).iter().map(|&(bound, _)| bound);
+                    // Look at the bounds as written first, and only then at their supertraits,
+                    // so that `impl StreamExt` is reported like `impl Stream` would be.
+                    let elaborated = elaborate_predicates(cx.tcx, bounds.clone())
+                        .map(|obligation| obligation.predicate);
+                    for predicate in bounds.chain(elaborated) {
                         // We only look at the `DefId`, so it is safe to skip the binder here.
                         if let ty::PredicateKind::Trait(ref poly_trait_predicate) =
                             predicate.kind().skip_binder()
@@ -214,12 +220,15 @@ fn check_must_use_ty<'tcx>(
                             let descr_pre =
                                 &format!("{}implementer{} of ", descr_pre, plural_suffix,);
                             if check_must_use_def(cx, def_id, span, descr_pre, descr_post) {
-                                has_emitted = true;
-                                break;
+                                return true;
                             }
                         }
                     }
-                    has_emitted
+                    // None of the bounds are `#[must_use]`, but the type behind the opaque
+                    // type might be.
+                    let hidden_ty = cx.tcx.bound_type_of(def).subst(cx.tcx, substs);
+                    let descr_pre = &format!("{}opaque type{} hiding ", descr_pre, plural_suffix);
+                    check_must_use_ty(cx, hidden_ty, expr, span, descr_pre, descr_post, plural_len)
                 }
                 ty::Dynamic(binder, _) => {
                     let mut has_emitted = false;
@@ -316,6 +325,12 @@ fn check_must_use_def(
                     if let Some(note) = attr.value_str() {
                         err.note(note.as_str());
                     }
+                    if cx.tcx.lang_items().future_trait() == Some(def_id) {
+                        err.help(
+                            "`.await` the future, or spawn it on an executor to run it \
+                             in the background",
+                        );
+                    }
                     err.emit();
                 });
                 true
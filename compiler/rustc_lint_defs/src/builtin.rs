@@ -293,6 +293,48 @@
     report_in_external_macro
 }
 
+declare_lint! {
+    /// The `long_running_const_eval` lint detects constant evaluation that
+    /// takes a long time.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// #![feature(const_eval_limit)]
+    /// #![const_eval_limit = "0"]
+    ///
+    /// const SUM: u64 = {
+    ///     let mut sum = 0;
+    ///     let mut i = 0;
+    ///     while i < 2_000_000 {
+    ///         sum += i;
+    ///         i += 1;
+    ///     }
+    ///     sum
+    /// };
+    /// # fn main() {}
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// Constant evaluation is interpreted by the compiler, so a constant that
+    /// does a lot of work, or loops forever when the step limit of
+    /// `#![const_eval_limit]` is lifted, can make compilation very slow
+    /// without any indication of where the time goes. This lint is reported
+    /// when the evaluation of a constant has run for a number of steps, and
+    /// again each time that number doubles, pointing at the function calls
+    /// being evaluated at that moment. The number of steps can be changed with
+    /// `-Z const-eval-step-budget`.
+    ///
+    /// If the lint is denied, the evaluation is stopped with an error the first
+    /// time the lint is reported.
+    pub LONG_RUNNING_CONST_EVAL,
+    Warn,
+    "constant evaluation that takes a long time"
+}
+
 declare_lint! {
     /// The `unused_imports` lint detects imports that are never used.
     ///
@@ -3160,6 +3202,7 @@
         PUB_USE_OF_PRIVATE_EXTERN_CRATE,
         INVALID_TYPE_PARAM_DEFAULT,
         CONST_ERR,
+        LONG_RUNNING_CONST_EVAL,
         RENAMED_AND_REMOVED_LINTS,
         UNALIGNED_REFERENCES,
         CONST_ITEM_MUTATION,
@@ -3229,6 +3272,9 @@
         SUSPICIOUS_AUTO_TRAIT_IMPLS,
         UNEXPECTED_CFGS,
         DEPRECATED_WHERE_CLAUSE_LOCATION,
+        RETURN_TYPE_IMPLIED_BOUNDS,
+        UNUSED_GENERIC_PARAMETERS,
+        FFI_UNWIND_BOUNDARIES,
         TEST_UNSTABLE_LINT,
     ]
 }
@@ -3874,6 +3920,99 @@
     "deprecated where clause location"
 }
 
+declare_lint! {
+    /// The `return_type_implied_bounds` lint detects functions whose signature is only
+    /// well-formed because the return type is assumed to be well-formed.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// pub fn leak<'a, T>(value: Box<T>) -> &'a mut T {
+    ///     Box::leak(value)
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// The types of the arguments and of the return value of a function are assumed to be
+    /// well-formed when checking its signature, so bounds like `T: 'a` that they need don't have
+    /// to be written as where clauses. Callers only prove that the types of the arguments are
+    /// well-formed though, so doing the same for the return type is unsound, and will stop
+    /// being done in a future release. Adding the where clauses that only the return type implies
+    /// keeps the function compiling.
+    pub RETURN_TYPE_IMPLIED_BOUNDS,
+    Warn,
+    "detects functions relying on their return type for implied bounds",
+    @future_incompatible = FutureIncompatibleInfo {
+        reference: "issue #27579 <https://github.com/rust-lang/rust/issues/27579>",
+    };
+}
+
+declare_lint! {
+    /// The `unused_generic_parameters` lint detects type and const parameters of functions that
+    /// are used neither by the signature, nor by the where clauses, nor by the body of the
+    /// function.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// #![warn(unused_generic_parameters)]
+    /// pub fn answer<T>() -> u32 {
+    ///     42
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// Unused parameters have to be given explicitly at every call of the function, since they
+    /// can't be inferred, and are usually left over from a refactoring. Unlike the unused
+    /// parameters of structs and enums (E0392), they are not an error. The parameters of methods
+    /// of trait impls are not linted since they have to match the ones of the trait.
+    ///
+    /// This lint is "allow" by default because it requires the optimized MIR of every generic
+    /// function, which is expensive to compute when only checking a crate.
+    pub UNUSED_GENERIC_PARAMETERS,
+    Allow,
+    "detects generic parameters of functions that are never used"
+}
+
+declare_lint! {
+    /// The `ffi_unwind_boundaries` lint detects functions defined with an ABI that doesn't
+    /// allow unwinding, like `extern "C"`, that may panic.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// #![warn(ffi_unwind_boundaries)]
+    /// pub extern "C" fn get(bytes: &[u8; 4], i: usize) -> u8 {
+    ///     bytes[i]
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// Functions like `extern "C" fn` are usually called from foreign code, which doesn't
+    /// expect to be unwound through. Letting a panic unwind out of them is undefined behavior,
+    /// and aborts the process with `#![feature(c_unwind)]`. Functions that are meant to unwind
+    /// into their foreign callers should use an ABI like `extern "C-unwind"` instead, and the
+    /// others should catch panics with [`std::panic::catch_unwind`].
+    ///
+    /// This lint is "allow" by default because most functions can panic when compiling with
+    /// `-C panic=unwind`, for example by indexing a slice, and it doesn't apply with
+    /// `-C panic=abort`.
+    ///
+    /// [`std::panic::catch_unwind`]: https://doc.rust-lang.org/std/panic/fn.catch_unwind.html
+    pub FFI_UNWIND_BOUNDARIES,
+    Allow,
+    "detects functions with a non-unwinding ABI that may panic"
+}
+
 declare_lint! {
     /// The `test_unstable_lint` lint tests unstable lints and is perma-unstable.
     ///
@@ -168,7 +168,11 @@ pub enum Level {
     /// The `warn` level will produce a warning if the lint was violated, however the
     /// compiler will continue with its execution.
     Warn,
-    ForceWarn,
+    /// This lint level is a special case of the `warn` level, set with `--force-warn`. It
+    /// can't be overridden by attributes. The [`LintExpectationId`] is the one of an
+    /// `expect` attribute that it overrode, which still counts as fulfilled when the lint
+    /// is emitted.
+    ForceWarn(Option<LintExpectationId>),
     /// The `deny` level will produce an error and stop further execution after the lint
     /// pass is complete.
     Deny,
@@ -184,7 +188,7 @@ pub fn as_str(self) -> &'static str {
             Level::Allow => "allow",
             Level::Expect(_) => "expect",
             Level::Warn => "warn",
-            Level::ForceWarn => "force-warn",
+            Level::ForceWarn(_) => "force-warn",
             Level::Deny => "deny",
             Level::Forbid => "forbid",
         }
@@ -219,7 +223,7 @@ pub fn from_attr(attr: &Attribute) -> Option<Level> {
 
     pub fn is_error(self) -> bool {
         match self {
-            Level::Allow | Level::Expect(_) | Level::Warn | Level::ForceWarn => false,
+            Level::Allow | Level::Expect(_) | Level::Warn | Level::ForceWarn(_) => false,
             Level::Deny | Level::Forbid => true,
         }
     }
@@ -635,6 +639,24 @@ macro_rules! declare_tool_lint {
     ) => (
          $crate::declare_tool_lint!{$(#[$attr])* $vis $tool::$NAME, $Level, $desc, $rep}
     );
+    (
+        $(#[$attr:meta])* $vis:vis $tool:ident ::$NAME:ident, $Level:ident, $desc:expr,
+        @future_incompatible = FutureIncompatibleInfo { $($field:ident : $val:expr),* $(,)* };
+    ) => (
+        $(#[$attr])*
+        $vis static $NAME: &$crate::Lint = &$crate::Lint {
+            name: &concat!(stringify!($tool), "::", stringify!($NAME)),
+            default_level: $crate::$Level,
+            desc: $desc,
+            edition_lint_opts: None,
+            is_plugin: true,
+            future_incompatible: Some($crate::FutureIncompatibleInfo {
+                $($field: $val,)*
+                ..$crate::FutureIncompatibleInfo::default_fields_for_macro()
+            }),
+            ..$crate::Lint::default_fields_for_macro()
+        };
+    );
     (
         $(#[$attr:meta])* $vis:vis $tool:ident ::$NAME:ident, $Level:ident, $desc:expr,
         $external:expr
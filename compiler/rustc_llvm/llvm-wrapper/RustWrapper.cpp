@@ -13,6 +13,7 @@
 #include "llvm/Pass.h"
 #include "llvm/Bitcode/BitcodeWriterPass.h"
 #include "llvm/Support/Signals.h"
+#include "llvm/Support/xxhash.h"
 #include "llvm/ADT/Optional.h"
 
 #include <iostream>
@@ -1366,13 +1367,20 @@ extern "C" void LLVMRustFreeOperandBundleDef(OperandBundleDef *Bundle) {
   delete Bundle;
 }
 
+// Mirrors how Clang derives the KCFI type identifier from a typeinfo name.
+extern "C" uint32_t LLVMRustKCFITypeId(const char *TypeId, size_t TypeIdLen) {
+  return static_cast<uint32_t>(xxHash64(StringRef(TypeId, TypeIdLen)));
+}
+
 extern "C" LLVMValueRef LLVMRustBuildCall(LLVMBuilderRef B, LLVMTypeRef Ty, LLVMValueRef Fn,
                                           LLVMValueRef *Args, unsigned NumArgs,
-                                          OperandBundleDef *Bundle) {
+                                          OperandBundleDef **OpBundles,
+                                          unsigned NumOpBundles) {
   Value *Callee = unwrap(Fn);
   FunctionType *FTy = unwrap<FunctionType>(Ty);
-  unsigned Len = Bundle ? 1 : 0;
-  ArrayRef<OperandBundleDef> Bundles = makeArrayRef(Bundle, Len);
+  SmallVector<OperandBundleDef, 2> Bundles;
+  for (unsigned I = 0; I < NumOpBundles; ++I)
+    Bundles.push_back(*OpBundles[I]);
   return wrap(unwrap(B)->CreateCall(
       FTy, Callee, makeArrayRef(unwrap(Args), NumArgs), Bundles));
 }
@@ -1414,11 +1422,13 @@ extern "C" LLVMValueRef
 LLVMRustBuildInvoke(LLVMBuilderRef B, LLVMTypeRef Ty, LLVMValueRef Fn,
                     LLVMValueRef *Args, unsigned NumArgs,
                     LLVMBasicBlockRef Then, LLVMBasicBlockRef Catch,
-                    OperandBundleDef *Bundle, const char *Name) {
+                    OperandBundleDef **OpBundles, unsigned NumOpBundles,
+                    const char *Name) {
   Value *Callee = unwrap(Fn);
   FunctionType *FTy = unwrap<FunctionType>(Ty);
-  unsigned Len = Bundle ? 1 : 0;
-  ArrayRef<OperandBundleDef> Bundles = makeArrayRef(Bundle, Len);
+  SmallVector<OperandBundleDef, 2> Bundles;
+  for (unsigned I = 0; I < NumOpBundles; ++I)
+    Bundles.push_back(*OpBundles[I]);
   return wrap(unwrap(B)->CreateInvoke(FTy, Callee, unwrap(Then), unwrap(Catch),
                                       makeArrayRef(unwrap(Args), NumArgs),
                                       Bundles, Name));
@@ -40,6 +40,9 @@ pub struct CStore {
     allocator_kind: Option<AllocatorKind>,
     /// This crate has a `#[global_allocator]` item.
     has_global_allocator: bool,
+    /// This crate needs an allocator, but neither a `#[global_allocator]` nor a default
+    /// library allocator was found. Reported together with missing weak lang items.
+    missing_global_allocator: bool,
 
     /// This map is used to verify we get no hash conflicts between
     /// `StableCrateId` values.
@@ -194,6 +197,10 @@ pub(crate) fn has_global_allocator(&self) -> bool {
         self.has_global_allocator
     }
 
+    pub(crate) fn missing_global_allocator(&self) -> bool {
+        self.missing_global_allocator
+    }
+
     pub fn report_unused_deps(&self, tcx: TyCtxt<'_>) {
         let json_unused_externs = tcx.sess.opts.json_unused_externs;
 
@@ -241,6 +248,7 @@ pub fn new(
                 injected_panic_runtime: None,
                 allocator_kind: None,
                 has_global_allocator: false,
+                missing_global_allocator: false,
                 stable_crate_ids,
                 unused_externs: Vec::new(),
             },
@@ -851,10 +859,9 @@ fn inject_allocator_crate(&mut self, krate: &ast::Crate) {
         if !self.sess.contains_name(&krate.attrs, sym::default_lib_allocator)
             && !self.cstore.iter_crate_data().any(|(_, data)| data.has_default_lib_allocator())
         {
-            self.sess.err(
-                "no global memory allocator found but one is required; link to std or add \
-                 `#[global_allocator]` to a static item that implements the GlobalAlloc trait",
-            );
+            // Reported by `rustc_passes::weak_lang_items`, so that it can be bundled with
+            // the other items a `#![no_std]` binary has to provide itself.
+            self.cstore.missing_global_allocator = true;
         }
         self.cstore.allocator_kind = Some(AllocatorKind::Default);
     }
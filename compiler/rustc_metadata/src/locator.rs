@@ -229,7 +229,7 @@
 use rustc_session::Session;
 use rustc_span::symbol::{sym, Symbol};
 use rustc_span::Span;
-use rustc_target::spec::{Target, TargetTriple};
+use rustc_target::spec::{PanicStrategy, Target, TargetTriple};
 
 use snap::read::FrameDecoder;
 use std::fmt::Write as _;
@@ -852,6 +852,17 @@ fn find_plugin_registrar_impl<'a>(
     }
 }
 
+fn crate_flavor(path: &Path) -> CrateFlavor {
+    let filename = path.file_name().unwrap().to_str().unwrap();
+    if filename.ends_with(".rlib") {
+        CrateFlavor::Rlib
+    } else if filename.ends_with(".rmeta") {
+        CrateFlavor::Rmeta
+    } else {
+        CrateFlavor::Dylib
+    }
+}
+
 /// A diagnostic function for dumping crate metadata to an output stream.
 pub fn list_file_metadata(
     target: &Target,
@@ -859,20 +870,51 @@ pub fn list_file_metadata(
     metadata_loader: &dyn MetadataLoader,
     out: &mut dyn Write,
 ) -> IoResult<()> {
-    let filename = path.file_name().unwrap().to_str().unwrap();
-    let flavor = if filename.ends_with(".rlib") {
-        CrateFlavor::Rlib
-    } else if filename.ends_with(".rmeta") {
-        CrateFlavor::Rmeta
-    } else {
-        CrateFlavor::Dylib
-    };
-    match get_metadata_section(target, flavor, path, metadata_loader) {
+    match get_metadata_section(target, crate_flavor(path), path, metadata_loader) {
         Ok(metadata) => metadata.list_crate_metadata(out),
         Err(msg) => write!(out, "{}\n", msg),
     }
 }
 
+/// The parts of the metadata of a crate file that decide which crates it can be linked with.
+pub struct CrateSummary {
+    pub name: Symbol,
+    pub hash: Svh,
+    pub triple: TargetTriple,
+    pub panic_strategy: PanicStrategy,
+    pub panic_runtime: bool,
+    /// The names and hashes of the crates this crate was compiled against.
+    pub deps: Vec<(Symbol, Svh)>,
+}
+
+/// Reads the metadata of the crate file at `path` without loading the crate into a session, for
+/// checking crate files against each other. Fails if the file name is not valid UTF-8, or if the
+/// file has no metadata or was compiled by another version of rustc.
+pub fn read_crate_summary(
+    target: &Target,
+    path: &Path,
+    metadata_loader: &dyn MetadataLoader,
+) -> Result<CrateSummary, String> {
+    // `crate_flavor` relies on the file name to be valid UTF-8.
+    match path.file_name() {
+        None => return Err("it does not name a file".to_string()),
+        Some(name) if name.to_str().is_none() => {
+            return Err("its file name is not valid UTF-8".to_string());
+        }
+        Some(_) => {}
+    }
+    let metadata = get_metadata_section(target, crate_flavor(path), path, metadata_loader)
+        .map_err(|err| err.to_string())?;
+    let found_version = metadata.get_rustc_version();
+    if found_version != rustc_version() {
+        return Err(format!(
+            "it was compiled by an incompatible version of rustc ({})",
+            found_version
+        ));
+    }
+    Ok(metadata.crate_summary())
+}
+
 // ------------------------------------------ Error reporting -------------------------------------
 
 #[derive(Clone)]
@@ -137,6 +137,15 @@ fn process_item(&mut self, id: rustc_hir::ItemId) {
                                         "link kind `raw-dylib` is only supported on Windows targets"
                                     )
                                     .emit();
+                                } else if !matches!(
+                                    &*sess.target.arch,
+                                    "x86" | "x86_64" | "aarch64"
+                                ) {
+                                    sess.span_err(
+                                        span,
+                                        "link kind `raw-dylib` is only supported on x86, x86_64 \
+                                         and aarch64",
+                                    );
                                 } else if !features.raw_dylib {
                                     feature_err(
                                         &sess.parse_sess,
@@ -1,6 +1,7 @@
 // Decoding metadata from a single crate's metadata
 
 use crate::creader::{CStore, CrateMetadataRef};
+use crate::locator::CrateSummary;
 use crate::rmeta::*;
 
 use rustc_ast as ast;
@@ -39,7 +40,7 @@
 
 use proc_macro::bridge::client::ProcMacro;
 use std::io;
-use std::iter::TrustedLen;
+use std::iter::{self, TrustedLen};
 use std::mem;
 use std::num::NonZeroUsize;
 use std::path::Path;
@@ -50,6 +51,8 @@
 use rustc_span::hygiene::HygieneDecodeContext;
 
 mod cstore_impl;
+#[cfg(test)]
+mod tests;
 
 /// A reference to the raw binary version of crate metadata.
 /// A `MetadataBlob` internally is just a reference counted pointer to
@@ -718,6 +721,18 @@ pub(crate) fn list_crate_metadata(&self, out: &mut dyn io::Write) -> io::Result<
         write!(out, "\n")?;
         Ok(())
     }
+
+    pub(crate) fn crate_summary(&self) -> CrateSummary {
+        let root = self.get_root();
+        CrateSummary {
+            name: root.name,
+            hash: root.hash,
+            triple: root.triple,
+            panic_strategy: root.panic_strategy,
+            panic_runtime: root.panic_runtime,
+            deps: root.crate_deps.decode(self).map(|dep| (dep.name, dep.hash)).collect(),
+        }
+    }
 }
 
 impl CrateRoot {
@@ -1173,28 +1188,46 @@ fn get_ctor_def_id_and_kind(self, node_id: DefIndex) -> Option<(DefId, CtorKind)
         }
     }
 
+    /// The index of the item whose attributes `id` has.
+    fn attrs_index(self, id: DefIndex) -> DefIndex {
+        if self.root.tables.attributes.get(self, id).is_some() {
+            return id;
+        }
+        // Structure and variant constructors don't have any attributes encoded for them,
+        // but we assume that someone passing a constructor ID actually wants to look at
+        // the attributes on the corresponding struct or variant.
+        let def_key = self.def_key(id);
+        assert_eq!(def_key.disambiguated_data.data, DefPathData::Ctor);
+        def_key.parent.expect("no parent for a constructor")
+    }
+
     fn get_item_attrs(
         self,
         id: DefIndex,
         sess: &'a Session,
+    ) -> impl Iterator<Item = ast::Attribute> + 'a {
+        let id = self.attrs_index(id);
+        let doc_attrs = self
+            .root
+            .tables
+            .doc_attributes
+            .get(self, id)
+            .into_iter()
+            .flat_map(move |doc_attrs| doc_attrs.decode((self, sess)));
+        merge_doc_attrs(doc_attrs, self.get_item_non_doc_attrs(id, sess))
+    }
+
+    /// Like `get_item_attrs`, without decoding the doc strings.
+    fn get_item_non_doc_attrs(
+        self,
+        id: DefIndex,
+        sess: &'a Session,
     ) -> impl Iterator<Item = ast::Attribute> + 'a {
         self.root
             .tables
             .attributes
-            .get(self, id)
-            .unwrap_or_else(|| {
-                // Structure and variant constructors don't have any attributes encoded for them,
-                // but we assume that someone passing a constructor ID actually wants to look at
-                // the attributes on the corresponding struct or variant.
-                let def_key = self.def_key(id);
-                assert_eq!(def_key.disambiguated_data.data, DefPathData::Ctor);
-                let parent_id = def_key.parent.expect("no parent for a constructor");
-                self.root
-                    .tables
-                    .attributes
-                    .get(self, parent_id)
-                    .expect("no encoded attributes for a structure or variant")
-            })
+            .get(self, self.attrs_index(id))
+            .expect("no encoded attributes for a structure or variant")
             .decode((self, sess))
     }
 
@@ -1826,6 +1859,26 @@ fn reverse_translate_def_id(&self, did: DefId) -> Option<DefId> {
 
 // Cannot be implemented on 'ProcMacro', as libproc_macro
 // does not depend on librustc_ast
+/// Puts the doc strings of an item, decoded with their positions among all of its attributes,
+/// back at those positions among the other attributes.
+fn merge_doc_attrs<T>(
+    doc_attrs: impl Iterator<Item = (u32, T)>,
+    mut attrs: impl Iterator<Item = T>,
+) -> impl Iterator<Item = T> {
+    let mut doc_attrs = doc_attrs.peekable();
+    let mut position = 0;
+    iter::from_fn(move || {
+        let attr = match doc_attrs.peek() {
+            Some(&(doc_position, _)) if doc_position == position => {
+                doc_attrs.next().map(|(_, attr)| attr)
+            }
+            _ => attrs.next(),
+        };
+        position += 1;
+        attr
+    })
+}
+
 fn macro_kind(raw: &ProcMacro) -> MacroKind {
     match raw {
         ProcMacro::CustomDerive { .. } => MacroKind::Derive,
@@ -218,6 +218,7 @@ fn into_args(self) -> (DefId, SimplifiedType) {
     coerce_unsized_info => { table }
     mir_const_qualif => { table }
     rendered_const => { table }
+    const_value_from_metadata => { table }
     asyncness => { table_direct }
     fn_arg_names => { table }
     generator_kind => { table }
@@ -235,6 +236,9 @@ fn into_args(self) -> (DefId, SimplifiedType) {
     inherent_impls => { cdata.get_inherent_implementations_for_type(tcx, def_id.index) }
     is_foreign_item => { cdata.is_foreign_item(def_id.index) }
     item_attrs => { tcx.arena.alloc_from_iter(cdata.get_item_attrs(def_id.index, tcx.sess)) }
+    item_non_doc_attrs => {
+        tcx.arena.alloc_from_iter(cdata.get_item_non_doc_attrs(def_id.index, tcx.sess))
+    }
     trait_of_item => { cdata.get_trait_of_item(def_id.index) }
     is_mir_available => { cdata.is_item_mir_available(def_id.index) }
     is_ctfe_mir_available => { cdata.is_ctfe_mir_available(def_id.index) }
@@ -325,6 +329,9 @@ pub(in crate::rmeta) fn provide(providers: &mut Providers) {
     // resolve! Does this work? Unsure! That's what the issue is about
     *providers = Providers {
         allocator_kind: |tcx, ()| CStore::from_tcx(tcx).allocator_kind(),
+        missing_global_allocator: |tcx, ()| CStore::from_tcx(tcx).missing_global_allocator(),
+        // Local constants are evaluated instead.
+        const_value_from_metadata: |_, _| None,
         is_dllimport_foreign_item: |tcx, id| match tcx.native_library_kind(id) {
             Some(
                 NativeLibKind::Dylib { .. } | NativeLibKind::RawDylib | NativeLibKind::Unspecified,
@@ -564,6 +571,15 @@ pub fn item_attrs_untracked<'a>(
         self.get_crate_data(def_id.krate).get_item_attrs(def_id.index, sess)
     }
 
+    /// Like `item_attrs_untracked`, without the doc strings.
+    pub fn item_non_doc_attrs_untracked<'a>(
+        &'a self,
+        def_id: DefId,
+        sess: &'a Session,
+    ) -> impl Iterator<Item = ast::Attribute> + 'a {
+        self.get_crate_data(def_id.krate).get_item_non_doc_attrs(def_id.index, sess)
+    }
+
     pub fn get_proc_macro_quoted_span_untracked(
         &self,
         cnum: CrateNum,
@@ -0,0 +1,31 @@
+use super::merge_doc_attrs;
+
+fn merge(doc_attrs: Vec<(u32, &'static str)>, attrs: Vec<&'static str>) -> Vec<&'static str> {
+    merge_doc_attrs(doc_attrs.into_iter(), attrs.into_iter()).collect()
+}
+
+#[test]
+fn no_doc_attrs() {
+    assert_eq!(merge(vec![], vec!["inline", "must_use"]), ["inline", "must_use"]);
+}
+
+#[test]
+fn only_doc_attrs() {
+    assert_eq!(merge(vec![(0, "/// a"), (1, "/// b")], vec![]), ["/// a", "/// b"]);
+}
+
+#[test]
+fn doc_attrs_keep_their_positions() {
+    // /// a
+    // #[doc(hidden)]
+    // /// b
+    // /// c
+    // #[must_use]
+    // #[doc = "d"]
+    let doc_attrs = vec![(0, "/// a"), (2, "/// b"), (3, "/// c"), (5, "#[doc = \"d\"]")];
+    let attrs = vec!["#[doc(hidden)]", "#[must_use]"];
+    assert_eq!(
+        merge(doc_attrs, attrs),
+        ["/// a", "#[doc(hidden)]", "/// b", "/// c", "#[must_use]", "#[doc = \"d\"]"]
+    );
+}
@@ -21,7 +21,7 @@
 use rustc_middle::middle::exported_symbols::{
     metadata_symbol_name, ExportedSymbol, SymbolExportInfo,
 };
-use rustc_middle::mir::interpret;
+use rustc_middle::mir::interpret::{self, EncodedConstValue};
 use rustc_middle::traits::specialization_graph;
 use rustc_middle::ty::codec::TyEncoder;
 use rustc_middle::ty::fast_reject::{self, SimplifiedType, TreatParams};
@@ -72,6 +72,8 @@ pub(super) struct EncodeContext<'a, 'tcx> {
     required_source_files: Option<GrowableBitSet<usize>>,
     is_proc_macro: bool,
     hygiene_ctxt: &'a HygieneEncodeContext,
+    // The number of bytes of the `doc_attributes` side table, for `-Zmeta-stats`.
+    doc_attr_bytes: usize,
 }
 
 /// If the current crate is a proc-macro, returns early with `Lazy:empty()`.
@@ -762,6 +764,7 @@ fn encode_crate_root(&mut self) -> LazyValue<CrateRoot> {
             p("incoherent_impls", incoherent_impls_bytes);
             p("mir", mir_bytes);
             p("item", item_bytes);
+            p("  of which doc attrs", self.doc_attr_bytes);
             p("interpret_alloc_index", interpret_alloc_index_bytes);
             p("proc-macro-data", proc_macro_data_bytes);
             p("tables", tables_bytes);
@@ -936,6 +939,35 @@ fn should_encode_variances(def_kind: DefKind) -> bool {
     }
 }
 
+/// The largest valtree that constant values are recorded as in the metadata; larger values are
+/// recorded as `ConstValue`s, which store their bytes more compactly.
+const MAX_ENCODED_VALTREE_NODES: u64 = 1024;
+
+/// An upper bound of the number of nodes in the valtree of any value of type `ty`, or `None` if
+/// values of `ty` may point to other allocations.
+fn valtree_node_bound<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> Option<u64> {
+    match *ty.kind() {
+        ty::Bool | ty::Char | ty::Int(_) | ty::Uint(_) | ty::Float(_) => Some(1),
+        ty::Tuple(tys) => {
+            tys.iter().try_fold(1u64, |n, ty| n.checked_add(valtree_node_bound(tcx, ty)?))
+        }
+        ty::Array(elem, len) => {
+            let len = len.try_eval_usize(tcx, ty::ParamEnv::reveal_all())?;
+            len.checked_mul(valtree_node_bound(tcx, elem)?)?.checked_add(1)
+        }
+        // Enums additionally store the variant index.
+        ty::Adt(def, substs) if !def.is_union() => {
+            def.variants().iter().try_fold(0u64, |max, variant| {
+                let nodes = variant.fields.iter().try_fold(2u64, |n, field| {
+                    n.checked_add(valtree_node_bound(tcx, field.ty(tcx, substs))?)
+                })?;
+                Some(max.max(nodes))
+            })
+        }
+        _ => None,
+    }
+}
+
 fn should_encode_generics(def_kind: DefKind) -> bool {
     match def_kind {
         DefKind::Struct
@@ -974,16 +1006,34 @@ fn should_encode_generics(def_kind: DefKind) -> bool {
 
 impl<'a, 'tcx> EncodeContext<'a, 'tcx> {
     fn encode_attrs(&mut self, def_id: LocalDefId) {
-        let mut attrs = self
+        // Doc strings are only needed by rustdoc and a few diagnostics, but they are usually
+        // the bulk of the attributes, so they are encoded in a side table which is only decoded
+        // when all attributes of an item are requested. Each one is encoded with its position
+        // among all the attributes, so that they can be decoded in source order.
+        let mut attrs = Vec::new();
+        let mut doc_attrs = Vec::new();
+        let all_attrs = self
             .tcx
             .hir()
             .attrs(self.tcx.hir().local_def_id_to_hir_id(def_id))
             .iter()
             .filter(|attr| !rustc_feature::is_builtin_only_local(attr.name_or_empty()));
+        for (position, attr) in all_attrs.enumerate() {
+            if attr.doc_str().is_some() {
+                doc_attrs.push((position as u32, attr.clone()));
+            } else {
+                attrs.push(attr);
+            }
+        }
 
-        record_array!(self.tables.attributes[def_id.to_def_id()] <- attrs.clone());
-        if attrs.any(|attr| attr.may_have_doc_links()) {
-            self.tables.may_have_doc_links.set(def_id.local_def_index, ());
+        record_array!(self.tables.attributes[def_id.to_def_id()] <- attrs);
+        if !doc_attrs.is_empty() {
+            if doc_attrs.iter().any(|(_, attr)| attr.may_have_doc_links()) {
+                self.tables.may_have_doc_links.set(def_id.local_def_index, ());
+            }
+            let i = self.position();
+            record_array!(self.tables.doc_attributes[def_id.to_def_id()] <- doc_attrs);
+            self.doc_attr_bytes += self.position() - i;
         }
     }
 
@@ -1389,6 +1439,26 @@ fn encode_deprecation(&mut self, def_id: DefId) {
         }
     }
 
+    /// Returns the value of the constant `def_id` to record in the metadata, so that other crates
+    /// can use it without evaluating the constant again. Generic constants and constants that
+    /// fail to evaluate are not recorded; other crates evaluate those themselves.
+    fn const_value_for_metadata(&mut self, def_id: DefId) -> Option<EncodedConstValue<'tcx>> {
+        let tcx = self.tcx;
+        if tcx.generics_of(def_id).count() != 0 {
+            return None;
+        }
+        let cid = interpret::GlobalId { instance: ty::Instance::mono(tcx, def_id), promoted: None };
+        let param_env = tcx.param_env(def_id).with_reveal_all_normalized(tcx).with_const();
+        let key = tcx.erase_regions(param_env.and(cid));
+        let ty = tcx.normalize_erasing_regions(param_env, tcx.type_of(def_id));
+        if valtree_node_bound(tcx, ty).map_or(false, |nodes| nodes <= MAX_ENCODED_VALTREE_NODES) {
+            if let Ok(Some(valtree)) = tcx.eval_to_valtree(key) {
+                return Some(EncodedConstValue::ValTree(valtree));
+            }
+        }
+        tcx.eval_to_const_value_raw(key).ok().map(EncodedConstValue::Value)
+    }
+
     fn encode_rendered_const_for_body(&mut self, body_id: hir::BodyId) -> String {
         let hir = self.tcx.hir();
         let body = hir.body(body_id);
@@ -1411,6 +1481,9 @@ fn encode_info_for_item(&mut self, def_id: DefId, item: &'tcx hir::Item<'tcx>) {
                 let const_data = self.encode_rendered_const_for_body(body_id);
                 record!(self.tables.mir_const_qualif[def_id] <- qualifs);
                 record!(self.tables.rendered_const[def_id] <- const_data);
+                if let Some(value) = self.const_value_for_metadata(def_id) {
+                    record!(self.tables.const_value_from_metadata[def_id] <- value);
+                }
                 EntryKind::Const
             }
             hir::ItemKind::Fn(ref sig, .., body) => {
@@ -2208,6 +2281,7 @@ fn encode_metadata_impl(tcx: TyCtxt<'_>) -> EncodedMetadata {
         required_source_files,
         is_proc_macro: tcx.sess.crate_types().contains(&CrateType::ProcMacro),
         hygiene_ctxt: &hygiene_ctxt,
+        doc_attr_bytes: 0,
     };
 
     // Encode the rustc version string in a predictable location.
@@ -335,6 +335,9 @@ fn encode(&self, buf: &mut MemEncoder) -> LazyTables {
 define_tables! {
     kind: Table<DefIndex, LazyValue<EntryKind>>,
     attributes: Table<DefIndex, LazyArray<ast::Attribute>>,
+    // Doc comments and `#[doc = "..."]` attributes, which aren't in `attributes`, with their
+    // positions among all the attributes of the item.
+    doc_attributes: Table<DefIndex, LazyArray<(u32, ast::Attribute)>>,
     children: Table<DefIndex, LazyArray<DefIndex>>,
 
     opt_def_kind: Table<DefIndex, DefKind>,
@@ -371,6 +374,7 @@ fn encode(&self, buf: &mut MemEncoder) -> LazyTables {
     coerce_unsized_info: Table<DefIndex, LazyValue<ty::adjustment::CoerceUnsizedInfo>>,
     mir_const_qualif: Table<DefIndex, LazyValue<mir::ConstQualifs>>,
     rendered_const: Table<DefIndex, LazyValue<String>>,
+    const_value_from_metadata: Table<DefIndex, LazyValue<mir::interpret::EncodedConstValue<'static>>>,
     asyncness: Table<DefIndex, hir::IsAsync>,
     fn_arg_names: Table<DefIndex, LazyArray<Ident>>,
     generator_kind: Table<DefIndex, LazyValue<hir::GeneratorKind>>,
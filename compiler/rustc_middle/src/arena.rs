@@ -85,6 +85,7 @@ macro_rules! arena_types {
             [decode] attribute: rustc_ast::Attribute,
             [] name_set: rustc_data_structures::fx::FxHashSet<rustc_span::symbol::Symbol>,
             [] hir_id_set: rustc_hir::HirIdSet,
+            [] lifetime_bindings: rustc_data_structures::fx::FxHashMap<rustc_hir::def_id::LocalDefId, rustc_middle::middle::resolve_lifetime::LifetimeBinding>,
 
             // Interned types
             [] tys: rustc_data_structures::intern::WithStableHash<rustc_middle::ty::TyS<'tcx>>,
@@ -86,6 +86,10 @@ pub fn provide(providers: &mut Providers) {
         hir.get_module_parent_node(hir.local_def_id_to_hir_id(id))
     };
     providers.hir_crate = |tcx, ()| tcx.untracked_crate;
+    providers.item_non_doc_attrs = |tcx, id| {
+        let attrs = tcx.hir().attrs(tcx.hir().local_def_id_to_hir_id(id.expect_local()));
+        tcx.arena.alloc_from_iter(attrs.iter().filter(|attr| attr.doc_str().is_none()).cloned())
+    };
     providers.hir_crate_items = map::hir_crate_items;
     providers.crate_hash = map::crate_hash;
     providers.hir_module_items = map::hir_module_items;
@@ -145,4 +149,7 @@ pub fn provide(providers: &mut Providers) {
     };
     providers.in_scope_traits_map =
         |tcx, id| tcx.hir_crate(()).owners[id].as_owner().map(|owner_info| &owner_info.trait_map);
+    providers.parenthesized_exprs_map = |tcx, id| {
+        tcx.hir_crate(()).owners[id].as_owner().map(|owner_info| &owner_info.parenthesized)
+    };
 }
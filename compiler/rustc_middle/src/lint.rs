@@ -3,7 +3,8 @@
 use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
 use rustc_errors::{
-    Diagnostic, DiagnosticBuilder, DiagnosticId, EmissionGuarantee, ErrorGuaranteed, MultiSpan,
+    Diagnostic, DiagnosticBuilder, DiagnosticId, EmissionGuarantee, ErrorGuaranteed,
+    FutureBreakageProvenance, MultiSpan,
 };
 use rustc_hir::HirId;
 use rustc_index::vec::IndexVec;
@@ -25,28 +26,37 @@ pub enum LintLevelSource {
     Default,
 
     /// Lint level was set by an attribute.
-    Node(Symbol, Span, Option<Symbol> /* RFC 2383 reason */),
+    /// The provided `Level` is the level of the attribute.
+    /// (The actual level may be lower due to `--cap-lints`.)
+    Node(Symbol, Level, Span, Option<Symbol> /* RFC 2383 reason */),
 
     /// Lint level was set by a command-line flag.
     /// The provided `Level` is the level specified on the command line.
     /// (The actual level may be lower due to `--cap-lints`.)
     CommandLine(Symbol, Level),
+
+    /// Lint level was set in the `-Zlint-config` file.
+    /// The provided `Symbol` is the name of the lint or group it was set for and the `Level`
+    /// the level set in the file. (The actual level may be lower due to `--cap-lints`.)
+    ConfigFile(Symbol, Level),
 }
 
 impl LintLevelSource {
     pub fn name(&self) -> Symbol {
         match *self {
             LintLevelSource::Default => symbol::kw::Default,
-            LintLevelSource::Node(name, _, _) => name,
+            LintLevelSource::Node(name, _, _, _) => name,
             LintLevelSource::CommandLine(name, _) => name,
+            LintLevelSource::ConfigFile(name, _) => name,
         }
     }
 
     pub fn span(&self) -> Span {
         match *self {
             LintLevelSource::Default => DUMMY_SP,
-            LintLevelSource::Node(_, span, _) => span,
+            LintLevelSource::Node(_, _, span, _) => span,
             LintLevelSource::CommandLine(_, _) => DUMMY_SP,
+            LintLevelSource::ConfigFile(_, _) => DUMMY_SP,
         }
     }
 }
@@ -115,7 +125,7 @@ pub fn get_lint_level(
 
         // Ensure that we never exceed the `--cap-lints` argument
         // unless the source is a --force-warn
-        level = if let LintLevelSource::CommandLine(_, Level::ForceWarn) = src {
+        level = if let LintLevelSource::CommandLine(_, Level::ForceWarn(_)) = src {
             level
         } else {
             cmp::min(level, self.lint_cap)
@@ -249,16 +259,25 @@ pub fn forget_guarantee(self) -> LintDiagnosticBuilder<'a, ()> {
     }
 }
 
+/// Adds notes to `err` pointing at the attribute, flag or configuration that set the level of
+/// `lint`, and at `--cap-lints` when it lowered that level to the actual `level`.
 pub fn explain_lint_level_source(
+    sess: &Session,
     lint: &'static Lint,
     level: Level,
     src: LintLevelSource,
     err: &mut Diagnostic,
 ) {
     let name = lint.name_lower();
+    let requested_level = match src {
+        LintLevelSource::Default => lint.default_level(sess.edition()),
+        LintLevelSource::Node(_, level, _, _)
+        | LintLevelSource::CommandLine(_, level)
+        | LintLevelSource::ConfigFile(_, level) => level,
+    };
     match src {
         LintLevelSource::Default => {
-            err.note_once(&format!("`#[{}({})]` on by default", level.as_str(), name));
+            err.note_once(&format!("`#[{}({})]` on by default", requested_level.as_str(), name));
         }
         LintLevelSource::CommandLine(lint_flag_val, orig_level) => {
             let flag = match orig_level {
@@ -266,7 +285,7 @@ pub fn explain_lint_level_source(
                 Level::Deny => "-D",
                 Level::Forbid => "-F",
                 Level::Allow => "-A",
-                Level::ForceWarn => "--force-warn",
+                Level::ForceWarn(_) => "--force-warn",
                 Level::Expect(_) => {
                     unreachable!("the expect level does not have a commandline flag")
                 }
@@ -285,13 +304,29 @@ pub fn explain_lint_level_source(
                 ));
             }
         }
-        LintLevelSource::Node(lint_attr_name, src, reason) => {
+        LintLevelSource::ConfigFile(lint_config_name, _) => {
+            if lint_config_name.as_str() == name {
+                err.note_once(&format!(
+                    "`{}` set to `{}` in the lint configuration file",
+                    name,
+                    requested_level.as_str()
+                ));
+            } else {
+                err.note_once(&format!(
+                    "`{}` set to `{}` by `{}` in the lint configuration file",
+                    name,
+                    requested_level.as_str(),
+                    lint_config_name
+                ));
+            }
+        }
+        LintLevelSource::Node(lint_attr_name, _, src, reason) => {
             if let Some(rationale) = reason {
                 err.note(rationale.as_str());
             }
             err.span_note_once(src, "the lint level is defined here");
             if lint_attr_name.as_str() != name {
-                let level_str = level.as_str();
+                let level_str = requested_level.as_str();
                 err.note_once(&format!(
                     "`#[{}({})]` implied by `#[{}({})]`",
                     level_str, name, level_str, lint_attr_name
@@ -299,6 +334,23 @@ pub fn explain_lint_level_source(
             }
         }
     }
+    // `get_lint_level` lowers the requested level to `--cap-lints` and to the caps of the
+    // driver, e.g. rustdoc, without changing the source. Unlike the notes above, this is added
+    // to every capped lint, as the level in the message would otherwise contradict them.
+    if level < requested_level {
+        if sess.opts.lint_cap == Some(level) {
+            err.note(&format!(
+                "the lint level is capped at `{}` by `--cap-lints {}`",
+                level.as_str(),
+                level.as_str()
+            ));
+        } else {
+            err.note(&format!(
+                "the lint level is capped at `{}` by the compiler driver",
+                level.as_str()
+            ));
+        }
+    }
 }
 
 pub fn struct_lint_level<'s, 'd>(
@@ -352,8 +404,14 @@ fn struct_lint_level_impl<'s, 'd>(
                 // create a `DiagnosticBuilder` and continue as we would for warnings.
                 sess.struct_expect("", expect_id)
             }
-            (Level::Warn | Level::ForceWarn, Some(span)) => sess.struct_span_warn(span, ""),
-            (Level::Warn | Level::ForceWarn, None) => sess.struct_warn(""),
+            (Level::ForceWarn(Some(expect_id)), Some(span)) => {
+                sess.struct_span_warn_with_expectation(span, "", expect_id)
+            }
+            (Level::ForceWarn(Some(expect_id)), None) => {
+                sess.struct_warn_with_expectation("", expect_id)
+            }
+            (Level::Warn | Level::ForceWarn(None), Some(span)) => sess.struct_span_warn(span, ""),
+            (Level::Warn | Level::ForceWarn(None), None) => sess.struct_warn(""),
             (Level::Deny | Level::Forbid, Some(span)) => {
                 let mut builder = sess.diagnostic().struct_err_lint("");
                 builder.set_span(span);
@@ -395,12 +453,29 @@ fn struct_lint_level_impl<'s, 'd>(
             return;
         }
 
-        explain_lint_level_source(lint, level, src, &mut err);
+        explain_lint_level_source(sess, lint, level, src, &mut err);
 
         let name = lint.name_lower();
-        let is_force_warn = matches!(level, Level::ForceWarn);
+        let is_force_warn = matches!(level, Level::ForceWarn(_));
         err.code(DiagnosticId::Lint { name, has_future_breakage, is_force_warn });
 
+        if has_future_breakage {
+            let level_source = match src {
+                LintLevelSource::Default => "default",
+                LintLevelSource::Node(..) => "attribute",
+                LintLevelSource::CommandLine(..) => "command-line",
+                LintLevelSource::ConfigFile(..) => "config-file",
+            };
+            err.set_future_breakage_provenance(FutureBreakageProvenance {
+                level_source: level_source.to_owned(),
+                edition: future_incompatible.and_then(|f| f.reason.edition()),
+                reference: future_incompatible
+                    .map(|f| f.reference)
+                    .filter(|reference| !reference.is_empty())
+                    .map(str::to_owned),
+            });
+        }
+
         if let Some(future_incompatible) = future_incompatible {
             let explanation = match future_incompatible.reason {
                 FutureIncompatibilityReason::FutureReleaseError
@@ -1,7 +1,7 @@
 use crate::mir::mono::Linkage;
 use rustc_attr::{InlineAttr, InstructionSetAttr, OptimizeAttr};
 use rustc_span::symbol::Symbol;
-use rustc_target::spec::SanitizerSet;
+use rustc_target::spec::{FramePointer, SanitizerSet};
 
 #[derive(Clone, TyEncodable, TyDecodable, HashStable, Debug)]
 pub struct CodegenFnAttrs {
@@ -41,6 +41,9 @@ pub struct CodegenFnAttrs {
     /// The `#[repr(align(...))]` attribute. Indicates the value of which the function should be
     /// aligned to.
     pub alignment: Option<u32>,
+    /// The `#[frame_pointer(...)]` attribute. Overrides `-Cforce-frame-pointers` and the target
+    /// default for the annotated function.
+    pub frame_pointer: Option<FramePointer>,
 }
 
 bitflags! {
@@ -111,6 +114,7 @@ pub const fn new() -> CodegenFnAttrs {
             no_sanitize: SanitizerSet::empty(),
             instruction_set: None,
             alignment: None,
+            frame_pointer: None,
         }
     }
 
@@ -25,7 +25,13 @@ pub fn provide(providers: &mut ty::query::Providers) {
             tcx.hir().krate_attrs(),
             tcx.sess,
             sym::move_size_limit,
-            tcx.sess.opts.debugging_opts.move_size_limit.unwrap_or(0),
+            tcx.sess
+                .opts
+                .lint_config
+                .integer_option("large_assignments", "limit")
+                .map(|limit| limit as usize)
+                .or(tcx.sess.opts.debugging_opts.move_size_limit)
+                .unwrap_or(0),
         ),
         type_length_limit: get_limit(
             tcx.hir().krate_attrs(),
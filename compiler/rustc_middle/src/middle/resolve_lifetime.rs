@@ -38,6 +38,49 @@ pub fn insert(&mut self, value: T) {
 
 pub type ObjectLifetimeDefault = Set1<Region>;
 
+/// Why a lifetime parameter of a function is early- or late-bound, see the
+/// `lifetime_binding_map` query.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, HashStable)]
+pub enum LifetimeBinding {
+    /// Late-bound, as the lifetime appears in the argument types and not in any bounds.
+    LateBoundByInputs,
+    /// Late-bound, as the lifetime appears neither in bounds nor in the return type, even though
+    /// the argument types don't constrain it.
+    LateBoundUnconstrained,
+    /// Early-bound, as the lifetime appears in a bound on the generics or in a `where` clause.
+    EarlyBoundByBounds,
+    /// Early-bound, as the lifetime appears in the return type but not in the argument types.
+    EarlyBoundByReturnType,
+}
+
+impl LifetimeBinding {
+    pub fn is_late_bound(self) -> bool {
+        match self {
+            LifetimeBinding::LateBoundByInputs | LifetimeBinding::LateBoundUnconstrained => true,
+            LifetimeBinding::EarlyBoundByBounds | LifetimeBinding::EarlyBoundByReturnType => false,
+        }
+    }
+
+    /// Explains the binding of a lifetime to the user, e.g. "late-bound because it appears
+    /// in argument types and not in any bounds".
+    pub fn descr(self) -> &'static str {
+        match self {
+            LifetimeBinding::LateBoundByInputs => {
+                "late-bound because it appears in argument types and not in any bounds"
+            }
+            LifetimeBinding::LateBoundUnconstrained => {
+                "late-bound because it appears neither in the return type nor in any bounds"
+            }
+            LifetimeBinding::EarlyBoundByBounds => {
+                "early-bound because it appears in a bound or `where` clause"
+            }
+            LifetimeBinding::EarlyBoundByReturnType => {
+                "early-bound because it appears in the return type but not in argument types"
+            }
+        }
+    }
+}
+
 /// Maps the id of each lifetime reference to the lifetime decl
 /// that it corresponds to.
 #[derive(Default, HashStable, Debug)]
@@ -1,7 +1,7 @@
 //! Metadata from source code coverage analysis and instrumentation.
 
 use rustc_macros::HashStable;
-use rustc_span::Symbol;
+use rustc_span::{Span, Symbol};
 
 use std::cmp::Ord;
 use std::fmt::{self, Debug, Formatter};
@@ -86,6 +86,17 @@ pub struct MappedExpressionIndex {
     }
 }
 
+rustc_index::newtype_index! {
+    /// Identifies a `CoverageKind::BlockMarker` statement inserted during MIR building, so that
+    /// the coverage instrumentor can find the blocks on either side of a branch even after the
+    /// CFG has been simplified.
+    pub struct BlockMarkerId {
+        derive [HashStable]
+        DEBUG_FORMAT = "BlockMarkerId({})",
+        MAX = 0xFFFF_FFFF,
+    }
+}
+
 impl From<CounterValueReference> for ExpressionOperandId {
     #[inline]
     fn from(v: CounterValueReference) -> ExpressionOperandId {
@@ -113,6 +124,18 @@ pub enum CoverageKind {
         rhs: ExpressionOperandId,
     },
     Unreachable,
+    /// Marks the start of a block that one arm of a branch leads to. Only used between MIR
+    /// building and the `InstrumentCoverage` pass, which removes these markers again.
+    BlockMarker {
+        id: BlockMarkerId,
+    },
+    /// A branch region, counting how often the condition evaluated to `true` and to `false`.
+    /// The statement itself does not increment anything, the operands refer to counters or
+    /// expressions injected elsewhere in the function.
+    Branch {
+        true_operand: ExpressionOperandId,
+        false_operand: ExpressionOperandId,
+    },
 }
 
 impl CoverageKind {
@@ -122,6 +145,8 @@ pub fn as_operand_id(&self) -> ExpressionOperandId {
             Counter { id, .. } => ExpressionOperandId::from(id),
             Expression { id, .. } => ExpressionOperandId::from(id),
             Unreachable => bug!("Unreachable coverage cannot be part of an expression"),
+            BlockMarker { .. } => bug!("BlockMarker coverage cannot be part of an expression"),
+            Branch { .. } => bug!("Branch coverage cannot be part of an expression"),
         }
     }
 
@@ -144,6 +169,13 @@ fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
                 rhs.index(),
             ),
             Unreachable => write!(fmt, "Unreachable"),
+            BlockMarker { id } => write!(fmt, "BlockMarker({:?})", id.index()),
+            Branch { true_operand, false_operand } => write!(
+                fmt,
+                "Branch(true: {:?}, false: {:?})",
+                true_operand.index(),
+                false_operand.index(),
+            ),
         }
     }
 }
@@ -193,3 +225,22 @@ pub fn is_subtract(&self) -> bool {
         matches!(self, Self::Subtract)
     }
 }
+
+/// Branch coverage information collected while building MIR, consumed by the
+/// `InstrumentCoverage` pass.
+#[derive(Clone, Debug, TyEncodable, TyDecodable, HashStable)]
+pub struct BranchInfo {
+    /// 1 more than the highest-numbered `BlockMarker` that was inserted. Markers are
+    /// numbered from 0, so this is also the number of markers.
+    pub num_block_markers: usize,
+    pub branch_spans: Vec<BranchSpan>,
+}
+
+/// A single condition, together with the markers of the blocks that are entered when it
+/// evaluates to `true` and to `false`.
+#[derive(Clone, Debug, TyEncodable, TyDecodable, HashStable)]
+pub struct BranchSpan {
+    pub span: Span,
+    pub true_marker: BlockMarkerId,
+    pub false_marker: BlockMarkerId,
+}
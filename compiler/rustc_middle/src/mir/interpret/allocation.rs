@@ -1,8 +1,10 @@
 //! The virtual memory representation of the MIR interpreter.
 
 use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::convert::{TryFrom, TryInto};
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::iter;
 use std::ops::{Deref, Range};
 use std::ptr;
@@ -10,6 +12,9 @@
 use rustc_ast::Mutability;
 use rustc_data_structures::intern::Interned;
 use rustc_data_structures::sorted_map::SortedMap;
+use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
+use rustc_data_structures::sync::OnceCell;
+use rustc_serialize::{Decodable, Decoder, Encodable, Encoder};
 use rustc_span::DUMMY_SP;
 use rustc_target::abi::{Align, HasDataLayout, Size};
 
@@ -30,7 +35,7 @@
 pub struct Allocation<Tag = AllocId, Extra = ()> {
     /// The actual bytes of the allocation.
     /// Note that the bytes of a pointer represent the offset of the pointer.
-    bytes: Box<[u8]>,
+    bytes: AllocBytes,
     /// Maps from byte addresses to extra data for each pointer.
     /// Only the first byte of a pointer is inserted into the map; i.e.,
     /// every entry in this map applies to `pointer_size` consecutive bytes starting
@@ -77,6 +82,230 @@ pub fn inner(self) -> &'tcx Allocation<Tag, Extra> {
     }
 }
 
+/// Reads of up to this many bytes from an allocation whose bytes are all the same are served
+/// from `UNIFORM_BYTES`, without decompressing the allocation.
+const UNIFORM_READ_LEN: usize = 64;
+
+/// `UNIFORM_BYTES[b]` is `UNIFORM_READ_LEN` times the byte `b`.
+static UNIFORM_BYTES: [[u8; UNIFORM_READ_LEN]; 256] = {
+    let mut bytes = [[0; UNIFORM_READ_LEN]; 256];
+    let mut b = 0;
+    while b < 256 {
+        bytes[b] = [b as u8; UNIFORM_READ_LEN];
+        b += 1;
+    }
+    bytes
+};
+
+/// Runs of equal bytes at least this long are run-length encoded in crate metadata.
+const MIN_ENCODED_RUN_LEN: usize = 16;
+
+/// The bytes of an allocation.
+///
+/// Large allocations whose bytes are all the same, like zeroed lookup tables, are stored as just
+/// that byte once they are interned. Small reads of them are served from a shared buffer; the
+/// bytes are only materialized when they are written to or read as a whole. This is transparent
+/// to the users of `Allocation`: comparing, hashing and encoding only look at the contents.
+#[derive(Clone)]
+enum AllocBytes {
+    Full(Box<[u8]>),
+    Uniform {
+        byte: u8,
+        len: usize,
+        /// The materialized bytes, for reads longer than `UNIFORM_READ_LEN`.
+        full: OnceCell<Box<[u8]>>,
+    },
+}
+
+impl AllocBytes {
+    fn uniform(byte: u8, len: usize) -> Self {
+        AllocBytes::Uniform { byte, len, full: OnceCell::new() }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        match self {
+            AllocBytes::Full(bytes) => bytes.len(),
+            AllocBytes::Uniform { len, .. } => *len,
+        }
+    }
+
+    #[inline]
+    fn get(&self, range: Range<usize>) -> &[u8] {
+        match self {
+            AllocBytes::Full(bytes) => &bytes[range],
+            AllocBytes::Uniform { byte, len, full } => {
+                assert!(range.start <= range.end && range.end <= *len, "out-of-bounds read");
+                if range.len() <= UNIFORM_READ_LEN {
+                    &UNIFORM_BYTES[usize::from(*byte)][..range.len()]
+                } else {
+                    &full.get_or_init(|| vec![*byte; *len].into_boxed_slice())[range]
+                }
+            }
+        }
+    }
+
+    /// Materializes the bytes so that they can be written to.
+    fn get_mut(&mut self) -> &mut [u8] {
+        if let AllocBytes::Uniform { byte, len, full } = self {
+            let bytes = full.take().unwrap_or_else(|| vec![*byte; *len].into_boxed_slice());
+            *self = AllocBytes::Full(bytes);
+        }
+        match self {
+            AllocBytes::Full(bytes) => bytes,
+            AllocBytes::Uniform { .. } => unreachable!(),
+        }
+    }
+
+    /// Only stores a single byte if all bytes are the same. Small allocations are left alone, as
+    /// there is little to gain.
+    fn compress(&mut self) {
+        if let AllocBytes::Full(bytes) = self
+            && let [first, rest @ ..] = &bytes[..]
+            && rest.len() >= UNIFORM_READ_LEN
+            && rest.iter().all(|b| b == first)
+        {
+            *self = AllocBytes::uniform(*first, bytes.len());
+        }
+    }
+
+    /// Calls `f` on consecutive chunks of the bytes, which are cut at the same offsets for all
+    /// representations of the same contents.
+    fn for_each_chunk(&self, mut f: impl FnMut(&[u8])) {
+        let len = self.len();
+        for start in (0..len).step_by(UNIFORM_READ_LEN) {
+            f(self.get(start..len.min(start + UNIFORM_READ_LEN)));
+        }
+    }
+}
+
+impl PartialEq for AllocBytes {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                AllocBytes::Uniform { byte: a, len: a_len, .. },
+                AllocBytes::Uniform { byte: b, len: b_len, .. },
+            ) => a_len == b_len && (a == b || *a_len == 0),
+            _ => self.cmp(other) == Ordering::Equal,
+        }
+    }
+}
+
+impl Eq for AllocBytes {}
+
+impl PartialOrd for AllocBytes {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for AllocBytes {
+    /// Compares the contents lexicographically, like `[u8]` does.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let mut start = 0;
+        loop {
+            match (start < self.len(), start < other.len()) {
+                (false, false) => return Ordering::Equal,
+                (false, true) => return Ordering::Less,
+                (true, false) => return Ordering::Greater,
+                (true, true) => {
+                    let a = self.get(start..self.len().min(start + UNIFORM_READ_LEN));
+                    let b = other.get(start..other.len().min(start + UNIFORM_READ_LEN));
+                    match a.cmp(b) {
+                        Ordering::Equal => start += UNIFORM_READ_LEN,
+                        ord => return ord,
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Hash for AllocBytes {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        self.for_each_chunk(|chunk| state.write(chunk));
+    }
+}
+
+impl<CTX> HashStable<CTX> for AllocBytes {
+    fn hash_stable(&self, _: &mut CTX, hasher: &mut StableHasher) {
+        self.hash(hasher);
+    }
+}
+
+impl fmt::Debug for AllocBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Printed like a `Box<[u8]>`, whatever the representation.
+        match self {
+            AllocBytes::Full(bytes) => bytes.fmt(f),
+            AllocBytes::Uniform { byte, len, .. } => {
+                f.debug_list().entries(iter::repeat(byte).take(*len)).finish()
+            }
+        }
+    }
+}
+
+/// The bytes are encoded as a sequence of literal byte strings and runs of the same byte, each
+/// preceded by its length shifted left by one, with the lowest bit set for runs.
+impl<S: Encoder> Encodable<S> for AllocBytes {
+    fn encode(&self, s: &mut S) {
+        s.emit_usize(self.len());
+        let bytes = match self {
+            AllocBytes::Full(bytes) => bytes,
+            AllocBytes::Uniform { byte, len, .. } => {
+                if *len > 0 {
+                    s.emit_usize(len << 1 | 1);
+                    s.emit_u8(*byte);
+                }
+                return;
+            }
+        };
+        let mut literal_start = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            let run_len = bytes[i..].iter().take_while(|&&b| b == bytes[i]).count();
+            if run_len >= MIN_ENCODED_RUN_LEN {
+                if literal_start < i {
+                    s.emit_usize((i - literal_start) << 1);
+                    s.emit_raw_bytes(&bytes[literal_start..i]);
+                }
+                s.emit_usize(run_len << 1 | 1);
+                s.emit_u8(bytes[i]);
+                literal_start = i + run_len;
+            }
+            i += run_len;
+        }
+        if literal_start < bytes.len() {
+            s.emit_usize((bytes.len() - literal_start) << 1);
+            s.emit_raw_bytes(&bytes[literal_start..]);
+        }
+    }
+}
+
+impl<D: Decoder> Decodable<D> for AllocBytes {
+    fn decode(d: &mut D) -> Self {
+        let len = d.read_usize();
+        let mut bytes = Vec::new();
+        while bytes.len() < len {
+            let header = d.read_usize();
+            let segment_len = header >> 1;
+            if header & 1 == 1 {
+                let byte = d.read_u8();
+                if segment_len == len {
+                    // A single run: keep the allocation compressed.
+                    return AllocBytes::uniform(byte, len);
+                }
+                bytes.resize(bytes.len() + segment_len, byte);
+            } else {
+                bytes.extend_from_slice(d.read_raw_bytes(segment_len));
+            }
+        }
+        assert_eq!(bytes.len(), len, "malformed allocation bytes");
+        AllocBytes::Full(bytes.into_boxed_slice())
+    }
+}
+
 /// We have our own error type that does not know about the `AllocId`; that information
 /// is added when converting to `InterpError`.
 #[derive(Debug)]
@@ -156,7 +385,7 @@ pub fn from_bytes<'a>(
         let bytes = Box::<[u8]>::from(slice.into());
         let size = Size::from_bytes(bytes.len());
         Self {
-            bytes,
+            bytes: AllocBytes::Full(bytes),
             relocations: Relocations::new(),
             init_mask: InitMask::new(size, true),
             align,
@@ -189,7 +418,7 @@ pub fn uninit<'tcx>(size: Size, align: Align, panic_on_fail: bool) -> InterpResu
         // SAFETY: the box was zero-allocated, which is a valid initial value for Box<[u8]>
         let bytes = unsafe { bytes.assume_init() };
         Ok(Allocation {
-            bytes,
+            bytes: AllocBytes::Full(bytes),
             relocations: Relocations::new(),
             init_mask: InitMask::new(size, false),
             align,
@@ -214,7 +443,7 @@ pub fn convert_tag_add_extra<Tag, Extra>(
         let endian = cx.data_layout().endian;
         for &(offset, alloc_id) in self.relocations.iter() {
             let idx = offset.bytes_usize();
-            let ptr_bytes = &mut bytes[idx..idx + ptr_size];
+            let ptr_bytes = &mut bytes.get_mut()[idx..idx + ptr_size];
             let bits = read_target_uint(endian, ptr_bytes).unwrap();
             let (ptr_tag, ptr_offset) =
                 tagger(Pointer::new(alloc_id, Size::from_bytes(bits))).into_parts();
@@ -248,7 +477,7 @@ pub fn size(&self) -> Size {
     /// edges) at all.
     /// This must not be used for reads affecting the interpreter execution.
     pub fn inspect_with_uninit_and_ptr_outside_interpreter(&self, range: Range<usize>) -> &[u8] {
-        &self.bytes[range]
+        self.bytes.get(range)
     }
 
     /// Returns the mask indicating which bytes are initialized.
@@ -260,6 +489,12 @@ pub fn init_mask(&self) -> &InitMask {
     pub fn relocations(&self) -> &Relocations<Tag> {
         &self.relocations
     }
+
+    /// Stores the bytes compactly if they are all the same. Meant to be called on allocations
+    /// that are about to be interned, as they will not be written to anymore.
+    pub fn compress_bytes(&mut self) {
+        self.bytes.compress();
+    }
 }
 
 /// Byte accessors.
@@ -268,7 +503,7 @@ impl<Tag: Provenance, Extra> Allocation<Tag, Extra> {
     /// caring about relocations. It just deduplicates some code between `read_scalar`
     /// and `get_bytes_internal`.
     fn get_bytes_even_more_internal(&self, range: AllocRange) -> &[u8] {
-        &self.bytes[range.start.bytes_usize()..range.end().bytes_usize()]
+        self.bytes.get(range.start.bytes_usize()..range.end().bytes_usize())
     }
 
     /// The last argument controls whether we error out when there are uninitialized or pointer
@@ -337,7 +572,7 @@ pub fn get_bytes_mut(
         self.mark_init(range, true);
         self.clear_relocations(cx, range)?;
 
-        Ok(&mut self.bytes[range.start.bytes_usize()..range.end().bytes_usize()])
+        Ok(&mut self.bytes.get_mut()[range.start.bytes_usize()..range.end().bytes_usize()])
     }
 
     /// A raw pointer variant of `get_bytes_mut` that avoids invalidating existing aliases into this memory.
@@ -349,8 +584,9 @@ pub fn get_bytes_mut_ptr(
         self.mark_init(range, true);
         self.clear_relocations(cx, range)?;
 
-        assert!(range.end().bytes_usize() <= self.bytes.len()); // need to do our own bounds-check
-        let begin_ptr = self.bytes.as_mut_ptr().wrapping_add(range.start.bytes_usize());
+        let bytes = self.bytes.get_mut();
+        assert!(range.end().bytes_usize() <= bytes.len()); // need to do our own bounds-check
+        let begin_ptr = bytes.as_mut_ptr().wrapping_add(range.start.bytes_usize());
         let len = range.end().bytes_usize() - range.start.bytes_usize();
         Ok(ptr::slice_from_raw_parts_mut(begin_ptr, len))
     }
@@ -665,13 +901,50 @@ pub fn mark_relocation_range(&mut self, relocations: AllocationRelocations<Tag>)
 
 /// A bitmask where each bit refers to the byte with the same index. If the bit is `true`, the byte
 /// is initialized. If it is `false` the byte is uninitialized.
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Hash, TyEncodable, TyDecodable)]
-#[derive(HashStable)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Hash, HashStable)]
 pub struct InitMask {
     blocks: Vec<Block>,
     len: Size,
 }
 
+/// The mask is encoded as the lengths of the alternating runs of initialized and uninitialized
+/// bytes, which is much smaller than the blocks for large, mostly initialized allocations.
+impl<S: Encoder> Encodable<S> for InitMask {
+    fn encode(&self, s: &mut S) {
+        self.len.encode(s);
+        if self.len == Size::ZERO {
+            return;
+        }
+        s.emit_bool(self.get(Size::ZERO));
+        for chunk in self.range_as_init_chunks(Size::ZERO, self.len) {
+            let range = chunk.range();
+            s.emit_u64((range.end - range.start).bytes());
+        }
+    }
+}
+
+impl<D: Decoder> Decodable<D> for InitMask {
+    fn decode(d: &mut D) -> Self {
+        let len = Size::decode(d);
+        if len == Size::ZERO {
+            return InitMask::new(len, false);
+        }
+        let initial = d.read_bool();
+        let mut mask = InitMask::new(len, initial);
+        let mut start = Size::ZERO;
+        let mut is_init = initial;
+        while start < len {
+            let end = start + Size::from_bytes(d.read_u64());
+            if is_init != initial {
+                mask.set_range_inbounds(start, end, is_init);
+            }
+            start = end;
+            is_init = !is_init;
+        }
+        mask
+    }
+}
+
 impl InitMask {
     pub const BLOCK_SIZE: u64 = 64;
 
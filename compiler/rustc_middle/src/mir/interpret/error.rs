@@ -180,6 +180,8 @@ pub enum CheckInAllocMsg {
     PointerArithmeticTest,
     /// We are doing pointer offset_from.
     OffsetFromTest,
+    /// We are calling a function pointer.
+    FnPtrCallTest,
     /// None of the above -- generic/unspecific inbounds test.
     InboundsTest,
 }
@@ -196,6 +198,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 CheckInAllocMsg::MemoryAccessTest => "memory access failed: ",
                 CheckInAllocMsg::PointerArithmeticTest => "pointer arithmetic failed: ",
                 CheckInAllocMsg::OffsetFromTest => "out-of-bounds offset_from: ",
+                CheckInAllocMsg::FnPtrCallTest => "calling function pointer failed: ",
                 CheckInAllocMsg::InboundsTest => "",
             }
         )
@@ -284,6 +287,17 @@ pub enum UndefinedBehaviorInfo<'tcx> {
         path: Option<String>,
         msg: String,
     },
+    /// A value that is invalid at its type was loaded, or produced by a transmute. Only thrown
+    /// by machines that check these values eagerly, see `-Zextra-const-ub-checks`.
+    InvalidValue {
+        /// The allocation the value is stored in, or `None` if it is not stored in memory.
+        alloc: Option<AllocId>,
+        /// The offset of the offending bytes, in the allocation or in the value.
+        offset: Size,
+        /// Whether the value was produced by a transmute, rather than loaded.
+        transmute: bool,
+        msg: String,
+    },
     /// Using a non-boolean `u8` as bool.
     InvalidBool(u8),
     /// Using a non-character `u32` as character.
@@ -377,6 +391,26 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             ValidationFailure { path: Some(path), msg } => {
                 write!(f, "type validation failed at {}: {}", path, msg)
             }
+            InvalidValue { alloc, offset, transmute, msg } => {
+                let what = if *transmute { "transmuting to" } else { "loading" };
+                match alloc {
+                    Some(alloc) => write!(
+                        f,
+                        "{} an invalid value at byte offset {} of {}: {}",
+                        what,
+                        offset.bytes(),
+                        alloc,
+                        msg
+                    ),
+                    None => write!(
+                        f,
+                        "{} an invalid value at byte offset {} of the value: {}",
+                        what,
+                        offset.bytes(),
+                        msg
+                    ),
+                }
+            }
             InvalidBool(b) => {
                 write!(f, "interpreting an invalid 8-bit value as a bool: 0x{:02x}", b)
             }
@@ -557,6 +591,7 @@ pub fn formatted_string(&self) -> bool {
             self,
             InterpError::Unsupported(UnsupportedOpInfo::Unsupported(_))
                 | InterpError::UndefinedBehavior(UndefinedBehaviorInfo::ValidationFailure { .. })
+                | InterpError::UndefinedBehavior(UndefinedBehaviorInfo::InvalidValue { .. })
                 | InterpError::UndefinedBehavior(UndefinedBehaviorInfo::Ub(_))
         )
     }
@@ -124,7 +124,9 @@ macro_rules! throw_machine_stop {
     UninitBytesAccess, UnsupportedOpInfo,
 };
 
-pub use self::value::{get_slice_bytes, ConstAlloc, ConstValue, Scalar, ScalarMaybeUninit};
+pub use self::value::{
+    get_slice_bytes, ConstAlloc, ConstValue, EncodedConstValue, Scalar, ScalarMaybeUninit,
+};
 
 pub use self::allocation::{
     alloc_range, AllocRange, Allocation, ConstAllocation, InitChunk, InitChunkIter, InitMask,
@@ -8,7 +8,7 @@
 use rustc_macros::HashStable;
 use rustc_target::abi::{HasDataLayout, Size};
 
-use crate::ty::{Lift, ParamEnv, ScalarInt, Ty, TyCtxt};
+use crate::ty::{self, Lift, ParamEnv, ScalarInt, Ty, TyCtxt};
 
 use super::{
     AllocId, AllocRange, ConstAllocation, InterpResult, Pointer, PointerArithmetic, Provenance,
@@ -65,6 +65,17 @@ fn lift_to_tcx(self, tcx: TyCtxt<'tcx>) -> Option<ConstValue<'tcx>> {
     }
 }
 
+/// The value of a constant as recorded in the metadata of the crate that defines it, so that
+/// other crates can use it without evaluating the constant again.
+#[derive(Copy, Clone, Debug, TyEncodable, TyDecodable, HashStable)]
+pub enum EncodedConstValue<'tcx> {
+    /// The value as a valtree, which needs no allocations. Only used for small values of types
+    /// without references.
+    ValTree(ty::ValTree<'tcx>),
+    /// The value of any other constant.
+    Value(ConstValue<'tcx>),
+}
+
 impl<'tcx> ConstValue<'tcx> {
     #[inline]
     pub fn try_to_scalar(&self) -> Option<Scalar<AllocId>> {
@@ -325,6 +325,10 @@ pub struct Body<'tcx> {
     /// potentially allow things like `[u8; std::mem::size_of::<T>() * 0]` due to this.
     pub is_polymorphic: bool,
 
+    /// Branch coverage information collected while building MIR, if
+    /// `-Zcoverage-options=branch` is enabled. Taken by the `InstrumentCoverage` pass.
+    pub coverage_branch_info: Option<Box<coverage::BranchInfo>>,
+
     predecessor_cache: PredecessorCache,
     switch_source_cache: SwitchSourceCache,
     is_cyclic: GraphIsCyclicCache,
@@ -375,6 +379,7 @@ pub fn new(
             span,
             required_consts: Vec::new(),
             is_polymorphic: false,
+            coverage_branch_info: None,
             predecessor_cache: PredecessorCache::new(),
             switch_source_cache: SwitchSourceCache::new(),
             is_cyclic: GraphIsCyclicCache::new(),
@@ -405,6 +410,7 @@ pub fn new_cfg_only(basic_blocks: IndexVec<BasicBlock, BasicBlockData<'tcx>>) ->
             required_consts: Vec::new(),
             var_debug_info: Vec::new(),
             is_polymorphic: false,
+            coverage_branch_info: None,
             predecessor_cache: PredecessorCache::new(),
             switch_source_cache: SwitchSourceCache::new(),
             is_cyclic: GraphIsCyclicCache::new(),
@@ -12,6 +12,7 @@
 use rustc_session::config::OptLevel;
 use rustc_span::source_map::Span;
 use rustc_span::symbol::Symbol;
+use std::cmp;
 use std::fmt;
 use std::hash::Hash;
 
@@ -384,6 +385,19 @@ fn item_sort_key<'tcx>(tcx: TyCtxt<'tcx>, item: MonoItem<'tcx>) -> ItemSortKey<'
         items
     }
 
+    /// Returns the (at most) `n` largest items of this codegen unit together with their
+    /// estimated sizes, largest first.
+    pub fn largest_items(&self, tcx: TyCtxt<'tcx>, n: usize) -> Vec<(MonoItem<'tcx>, usize)> {
+        let mut items: Vec<_> = self
+            .items_in_deterministic_order(tcx)
+            .into_iter()
+            .map(|(item, _)| (item, item.size_estimate(tcx)))
+            .collect();
+        items.sort_by_key(|&(_, size)| cmp::Reverse(size));
+        items.truncate(n);
+        items
+    }
+
     pub fn codegen_dep_node(&self, tcx: TyCtxt<'tcx>) -> DepNode {
         crate::dep_graph::make_compile_codegen_unit(tcx, self.name())
     }
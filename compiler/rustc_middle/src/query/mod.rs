@@ -20,6 +20,9 @@
         desc { "trigger a delay span bug" }
     }
 
+    /// The outputs of name resolution. This is an untracked input that is recomputed in every
+    /// session, so queries should prefer the finer-grained queries projecting out of it (like
+    /// `module_reexports` or `names_imported_by_glob_use`), whose results are hashed.
     query resolutions(_: ()) -> &'tcx ty::ResolverOutputs {
         eval_always
         no_hash
@@ -971,6 +974,13 @@
         remap_env_constness
     }
 
+    /// The value of the non-generic constant `key` of another crate, as evaluated when that
+    /// crate was compiled, or `None` if it was not recorded in its metadata.
+    query const_value_from_metadata(key: DefId) -> Option<mir::interpret::EncodedConstValue<'tcx>> {
+        desc { |tcx| "loading the value of `{}` from crate metadata", tcx.def_path_str(key) }
+        separate_provide_extern
+    }
+
     /// Converts a type level constant value into `ConstValue`
     query valtree_to_const_val(key: (Ty<'tcx>, ty::ValTree<'tcx>)) -> ConstValue<'tcx> {
         desc { "convert type-level constant value to mir constant value"}
@@ -1119,6 +1129,16 @@
         separate_provide_extern
     }
 
+    /// Returns the attributes on the item at `def_id`, except for doc comments and
+    /// `#[doc = "..."]`, which extern crates store separately so that looking up other
+    /// attributes doesn't decode them.
+    ///
+    /// Do not use this directly, use `tcx.get_attrs` instead.
+    query item_non_doc_attrs(def_id: DefId) -> &'tcx [ast::Attribute] {
+        desc { |tcx| "collecting non-doc attributes of `{}`", tcx.def_path_str(def_id) }
+        separate_provide_extern
+    }
+
     query codegen_fn_attrs(def_id: DefId) -> CodegenFnAttrs {
         desc { |tcx| "computing codegen attributes of `{}`", tcx.def_path_str(def_id) }
         storage(ArenaCacheSelector<'tcx>)
@@ -1399,6 +1419,9 @@
         -> Option<&'tcx FxHashMap<ItemLocalId, Box<[TraitCandidate]>>> {
         desc { "traits in scope at a block" }
     }
+    query parenthesized_exprs_map(_: LocalDefId) -> Option<&'tcx FxHashMap<ItemLocalId, Span>> {
+        desc { "looking up parenthesized expressions" }
+    }
 
     query module_reexports(def_id: LocalDefId) -> Option<&'tcx [ModChild]> {
         desc { |tcx| "looking up reexports of module `{}`", tcx.def_path_str(def_id.to_def_id()) }
@@ -1587,6 +1610,12 @@
     query is_late_bound_map(_: LocalDefId) -> Option<&'tcx FxHashSet<LocalDefId>> {
         desc { "testing if a region is late bound" }
     }
+    /// For each lifetime parameter of a function, records whether it is early- or late-bound
+    /// and why. `is_late_bound_map` is derived from this.
+    query lifetime_binding_map(_: LocalDefId)
+        -> Option<&'tcx FxHashMap<LocalDefId, LifetimeBinding>> {
+        desc { "classifying lifetime parameters as early- or late-bound" }
+    }
     /// For a given item (like a struct), gets the default lifetimes to be used
     /// for each parameter if a trait object were to be passed for that parameter.
     /// For example, for `struct Foo<'a, T, U>`, this would be `['static, 'static]`.
@@ -1719,6 +1748,10 @@
         eval_always
         desc { "allocator kind for the current crate" }
     }
+    query missing_global_allocator(_: ()) -> bool {
+        eval_always
+        desc { "checking whether the current crate is missing a global allocator" }
+    }
 
     query upvars_mentioned(def_id: DefId) -> Option<&'tcx FxIndexMap<hir::HirId, hir::Upvar>> {
         desc { |tcx| "collecting upvars mentioned in `{}`", tcx.def_path_str(def_id) }
@@ -1732,6 +1765,18 @@
     query names_imported_by_glob_use(def_id: LocalDefId) -> &'tcx FxHashSet<Symbol> {
         desc { |tcx| "names_imported_by_glob_use for `{}`", tcx.def_path_str(def_id.to_def_id()) }
     }
+    /// The names imported by glob imports anywhere in the current crate.
+    ///
+    /// Unlike going through `resolutions`, depending on this only invalidates the caller
+    /// when the set of names changes.
+    query all_names_imported_by_glob_use(_: ()) -> &'tcx FxHashSet<Symbol> {
+        desc { "looking up all names imported by glob imports" }
+    }
+    /// Extern prelude entries. The value is `true` if the entry was introduced
+    /// via `extern crate` item and not `--extern` option or compiler built-in.
+    query extern_prelude(_: ()) -> &'tcx FxHashMap<Symbol, bool> {
+        desc { "looking up the extern prelude" }
+    }
 
     query stability_index(_: ()) -> stability::Index {
         storage(ArenaCacheSelector<'tcx>)
@@ -1786,6 +1831,12 @@
         }
         separate_provide_extern
     }
+    query check_mod_unused_generic_params(key: LocalDefId) -> () {
+        desc {
+            |tcx| "checking for unused generic parameters in {}",
+                describe_as_module(key, tcx)
+        }
+    }
     query backend_optimization_level(_: ()) -> OptLevel {
         desc { "optimization level used by backend" }
     }
@@ -137,7 +137,8 @@ pub fn method_call(&self, tcx: TyCtxt<'tcx>, source: Ty<'tcx>) -> (DefId, Substs
 /// into method calls:
 /// - using `x.some_method()` syntax, where some_method takes `&mut self`,
 /// - using `Foo::some_method(&mut x, ...)` syntax,
-/// - binary assignment operators (`+=`, `-=`, `*=`, etc.).
+/// - binary assignment operators (`+=`, `-=`, `*=`, etc.),
+/// - overloaded mutable indexing (`v[i] = ...`), which desugars to `IndexMut::index_mut`.
 /// Anything else should be rejected until generalized two-phase borrow support
 /// is implemented. Right now, dataflow can't handle the general case where there
 /// is more than one use of a mutable borrow, and we don't want to accept too much
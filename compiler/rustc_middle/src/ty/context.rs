@@ -2760,6 +2760,11 @@ pub fn in_scope_traits(self, id: HirId) -> Option<&'tcx [TraitCandidate]> {
         Some(&*candidates)
     }
 
+    /// If the expression `id` was written inside parentheses, returns its span without them.
+    pub fn parenthesized_inner_span(self, id: HirId) -> Option<Span> {
+        self.parenthesized_exprs_map(id.owner)?.get(&id.local_id).copied()
+    }
+
     pub fn named_region(self, id: HirId) -> Option<resolve_lifetime::Region> {
         debug!(?id, "named_region");
         self.named_region_map(id.owner).and_then(|map| map.get(&id.local_id).cloned())
@@ -2772,6 +2777,13 @@ pub fn is_late_bound(self, id: HirId) -> bool {
         })
     }
 
+    /// Explains why the lifetime parameter `param` of a function is early- or late-bound, for
+    /// errors that hinge on the difference.
+    pub fn lifetime_binding_note(self, param: LocalDefId) -> Option<String> {
+        let binding = *self.lifetime_binding_map(self.local_parent(param))?.get(&param)?;
+        Some(format!("`{}` is {}", self.item_name(param.to_def_id()), binding.descr()))
+    }
+
     pub fn late_bound_vars(self, id: HirId) -> &'tcx List<ty::BoundVariableKind> {
         self.mk_bound_variable_kinds(
             self.late_bound_vars_map(id.owner)
@@ -2858,6 +2870,10 @@ pub fn provide(providers: &mut ty::query::Providers) {
     providers.names_imported_by_glob_use = |tcx, id| {
         tcx.arena.alloc(tcx.resolutions(()).glob_map.get(&id).cloned().unwrap_or_default())
     };
+    providers.all_names_imported_by_glob_use = |tcx, ()| {
+        tcx.arena.alloc(tcx.resolutions(()).glob_map.values().flatten().copied().collect())
+    };
+    providers.extern_prelude = |tcx, ()| &tcx.resolutions(()).extern_prelude;
 
     providers.extern_mod_stmt_cnum =
         |tcx, id| tcx.resolutions(()).extern_crate_map.get(&id).cloned();
@@ -6,6 +6,7 @@
 use crate::ty::{AdtKind, Visibility};
 use crate::ty::{DefId, SubstsRef};
 
+use rustc_target::abi::VariantIdx;
 use rustc_type_ir::sty::TyKind::*;
 
 mod def_id_forest;
@@ -129,6 +130,22 @@ fn uninhabited_from(
             )
         }
     }
+
+    /// Checks whether values of this enum can never be in the variant `variant`, because it is
+    /// uninhabited, no matter from where. Such variants need no drop glue.
+    pub fn is_variant_statically_uninhabited(
+        self,
+        tcx: TyCtxt<'tcx>,
+        variant: VariantIdx,
+        substs: SubstsRef<'tcx>,
+        param_env: ty::ParamEnv<'tcx>,
+    ) -> bool {
+        self.is_enum()
+            && !self
+                .variant(variant)
+                .uninhabited_from(tcx, substs, AdtKind::Enum, param_env)
+                .is_empty()
+    }
 }
 
 impl<'tcx> VariantDef {
@@ -2970,7 +2970,8 @@ pub fn fn_can_unwind<'tcx>(tcx: TyCtxt<'tcx>, fn_def_id: Option<DefId>, abi: Spe
         | Wasm
         | RustIntrinsic
         | PlatformIntrinsic
-        | Unadjusted => false,
+        | Unadjusted
+        | Custom { .. } => false,
         Rust | RustCall | RustCold => tcx.sess.panic_strategy() == PanicStrategy::Unwind,
     }
 }
@@ -3003,6 +3004,10 @@ pub fn conv_from_spec_abi(tcx: TyCtxt<'_>, abi: SpecAbi) -> Conv {
         AvrInterrupt => Conv::AvrInterrupt,
         AvrNonBlockingInterrupt => Conv::AvrNonBlockingInterrupt,
         Wasm => Conv::C,
+        Custom { name } => match tcx.sess.target.lookup_custom_abi(name.as_str()) {
+            Some(abi) => Conv::Custom(abi.calling_convention),
+            None => bug!("custom ABI `{}` is not defined by the target", name),
+        },
 
         // These API constants ought to be more specific...
         Cdecl { .. } => Conv::C,
@@ -2198,14 +2198,18 @@ pub fn get_attrs_unchecked(self, did: DefId) -> &'tcx [ast::Attribute] {
     }
 
     /// Gets all attributes with the given name.
+    ///
+    /// Doc comments and `#[doc = "..."]` are not included, so that the `#[doc(...)]` attributes
+    /// of extern items can be found without decoding their doc strings. Use
+    /// `get_attrs_unchecked` to get those.
     pub fn get_attrs(self, did: DefId, attr: Symbol) -> ty::Attributes<'tcx> {
-        let filter_fn = move |a: &&ast::Attribute| a.has_name(attr);
+        let filter_fn = move |a: &&ast::Attribute| a.has_name(attr) && a.doc_str().is_none();
         if let Some(did) = did.as_local() {
             self.hir().attrs(self.hir().local_def_id_to_hir_id(did)).iter().filter(filter_fn)
         } else if cfg!(debug_assertions) && rustc_feature::is_builtin_only_local(attr) {
             bug!("tried to access the `only_local` attribute `{}` from an extern crate", attr);
         } else {
-            self.item_attrs(did).iter().filter(filter_fn)
+            self.item_non_doc_attrs(did).iter().filter(filter_fn)
         }
     }
 
@@ -2,6 +2,7 @@
 use rustc_index::vec::{Idx, IndexVec};
 
 use crate::middle::exported_symbols::ExportedSymbol;
+use crate::mir::interpret::EncodedConstValue;
 use crate::mir::Body;
 use crate::thir::abstract_const::Node;
 use crate::ty::{
@@ -116,4 +117,5 @@ impl $crate::ty::ParameterizedOverTcx for $ident<'static> {
     Body,
     Node,
     ExportedSymbol,
+    EncodedConstValue,
 }
@@ -2755,12 +2755,10 @@ fn trimmed_def_paths(tcx: TyCtxt<'_>, (): ()) -> FxHashMap<DefId, Symbol> {
     let unique_symbols_rev: &mut FxHashMap<(Namespace, Symbol), Option<DefId>> =
         &mut FxHashMap::default();
 
-    for symbol_set in tcx.resolutions(()).glob_map.values() {
-        for symbol in symbol_set {
-            unique_symbols_rev.insert((Namespace::TypeNS, *symbol), None);
-            unique_symbols_rev.insert((Namespace::ValueNS, *symbol), None);
-            unique_symbols_rev.insert((Namespace::MacroNS, *symbol), None);
-        }
+    for &symbol in tcx.all_names_imported_by_glob_use(()) {
+        unique_symbols_rev.insert((Namespace::TypeNS, symbol), None);
+        unique_symbols_rev.insert((Namespace::ValueNS, symbol), None);
+        unique_symbols_rev.insert((Namespace::MacroNS, symbol), None);
     }
 
     for_each_def(tcx, |ident, ns, def_id| {
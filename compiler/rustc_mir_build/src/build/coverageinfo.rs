@@ -0,0 +1,88 @@
+//! Collection of branch coverage information while building MIR, for
+//! `-Zcoverage-options=branch`.
+//!
+//! Branches are only identified here. The actual counters are added later by
+//! the `InstrumentCoverage` pass, which finds the blocks on either side of
+//! each branch through the `BlockMarker` statements inserted below.
+
+use crate::build::{Builder, CFG};
+
+use rustc_hir::def_id::LocalDefId;
+use rustc_middle::mir::coverage::{BlockMarkerId, BranchInfo, BranchSpan, CoverageKind};
+use rustc_middle::mir::{BasicBlock, Coverage, SourceInfo, Statement, StatementKind};
+use rustc_middle::thir::Expr;
+use rustc_middle::ty::TyCtxt;
+
+pub(crate) struct BranchInfoBuilder {
+    num_block_markers: usize,
+    branch_spans: Vec<BranchSpan>,
+}
+
+impl BranchInfoBuilder {
+    /// Creates a new branch info builder, but only if branch coverage instrumentation is
+    /// enabled and `def_id` is a function or closure body that can get instrumented.
+    pub(crate) fn new_if_enabled(tcx: TyCtxt<'_>, def_id: LocalDefId) -> Option<Self> {
+        if tcx.sess.instrument_coverage_branch()
+            && tcx.hir().body_owner_kind(def_id).is_fn_or_closure()
+        {
+            Some(Self { num_block_markers: 0, branch_spans: vec![] })
+        } else {
+            None
+        }
+    }
+
+    fn next_block_marker_id(&mut self) -> BlockMarkerId {
+        let id = BlockMarkerId::from_usize(self.num_block_markers);
+        self.num_block_markers += 1;
+        id
+    }
+
+    fn inject_block_marker(
+        &mut self,
+        cfg: &mut CFG<'_>,
+        source_info: SourceInfo,
+        block: BasicBlock,
+    ) -> BlockMarkerId {
+        let id = self.next_block_marker_id();
+        let statement = Statement {
+            source_info,
+            kind: StatementKind::Coverage(Box::new(Coverage {
+                kind: CoverageKind::BlockMarker { id },
+                code_region: None,
+            })),
+        };
+        cfg.push(block, statement);
+        id
+    }
+
+    pub(crate) fn into_done(self) -> Option<Box<BranchInfo>> {
+        let Self { num_block_markers, branch_spans } = self;
+
+        if num_block_markers == 0 {
+            assert!(branch_spans.is_empty());
+            return None;
+        }
+
+        Some(Box::new(BranchInfo { num_block_markers, branch_spans }))
+    }
+}
+
+impl Builder<'_, '_> {
+    /// If branch coverage is enabled, inject marker statements into `then_block` and
+    /// `else_block`, and record their IDs together with the span of the condition
+    /// `expr`, so that the `InstrumentCoverage` pass can turn them into a branch region.
+    pub(crate) fn visit_coverage_branch_condition(
+        &mut self,
+        expr: &Expr<'_>,
+        then_block: BasicBlock,
+        else_block: BasicBlock,
+    ) {
+        let Some(branch_info) = self.coverage_branch_info.as_mut() else { return };
+
+        let source_info = SourceInfo { span: expr.span, scope: self.source_scope };
+        let true_marker = branch_info.inject_block_marker(&mut self.cfg, source_info, then_block);
+        let false_marker = branch_info.inject_block_marker(&mut self.cfg, source_info, else_block);
+
+        branch_info.branch_spans.push(BranchSpan { span: expr.span, true_marker, false_marker });
+    }
+}
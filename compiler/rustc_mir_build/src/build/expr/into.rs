@@ -167,12 +167,14 @@ pub(crate) fn expr_into_dest(
                     this.cfg.start_new_block(),
                 );
 
-                let lhs = unpack!(block = this.as_local_operand(block, &this.thir[lhs]));
+                let lhs_expr = &this.thir[lhs];
+                let lhs = unpack!(block = this.as_local_operand(block, lhs_expr));
                 let blocks = match op {
                     LogicalOp::And => (else_block, shortcircuit_block),
                     LogicalOp::Or => (shortcircuit_block, else_block),
                 };
                 let term = TerminatorKind::if_(this.tcx, lhs, blocks.0, blocks.1);
+                this.visit_coverage_branch_condition(lhs_expr, blocks.0, blocks.1);
                 this.cfg.terminate(block, source_info, term);
 
                 this.cfg.push_assign_constant(
@@ -190,9 +192,33 @@ pub(crate) fn expr_into_dest(
                 );
                 this.cfg.goto(shortcircuit_block, source_info, join_block);
 
-                let rhs = unpack!(else_block = this.as_local_operand(else_block, &this.thir[rhs]));
-                this.cfg.push_assign(else_block, source_info, destination, Rvalue::Use(rhs));
-                this.cfg.goto(else_block, source_info, join_block);
+                let rhs_expr = &this.thir[rhs];
+                let rhs = unpack!(else_block = this.as_local_operand(else_block, rhs_expr));
+                if this.coverage_branch_info.is_some() {
+                    // With branch coverage, the right-hand side gets a branch of its own, so
+                    // that it is reported separately from the value of the whole expression.
+                    let (rhs_true_block, rhs_false_block) =
+                        (this.cfg.start_new_block(), this.cfg.start_new_block());
+                    let term = TerminatorKind::if_(this.tcx, rhs, rhs_true_block, rhs_false_block);
+                    this.visit_coverage_branch_condition(rhs_expr, rhs_true_block, rhs_false_block);
+                    this.cfg.terminate(else_block, source_info, term);
+                    for (block, value) in [(rhs_true_block, true), (rhs_false_block, false)] {
+                        this.cfg.push_assign_constant(
+                            block,
+                            source_info,
+                            destination,
+                            Constant {
+                                span: rhs_expr.span,
+                                user_ty: None,
+                                literal: ConstantKind::from_bool(this.tcx, value),
+                            },
+                        );
+                        this.cfg.goto(block, source_info, join_block);
+                    }
+                } else {
+                    this.cfg.push_assign(else_block, source_info, destination, Rvalue::Use(rhs));
+                    this.cfg.goto(else_block, source_info, join_block);
+                }
 
                 join_block.unit()
             }
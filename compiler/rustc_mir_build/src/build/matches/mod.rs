@@ -92,6 +92,9 @@ pub(crate) fn then_else_break(
                 let else_block = this.cfg.start_new_block();
                 let term = TerminatorKind::if_(this.tcx, operand, then_block, else_block);
 
+                // Record branch coverage info for this condition.
+                this.visit_coverage_branch_condition(expr, then_block, else_block);
+
                 let source_info = this.source_info(expr_span);
                 this.cfg.terminate(block, source_info, term);
                 this.break_for_else(else_block, break_scope, source_info);
@@ -452,6 +452,9 @@ struct Builder<'a, 'tcx> {
     unit_temp: Option<Place<'tcx>>,
 
     var_debug_info: Vec<VarDebugInfo<'tcx>>,
+
+    /// Collects branch coverage information, if `-Zcoverage-options=branch` is enabled.
+    coverage_branch_info: Option<coverageinfo::BranchInfoBuilder>,
 }
 
 impl<'a, 'tcx> Builder<'a, 'tcx> {
@@ -903,6 +906,7 @@ fn new(
             var_indices: Default::default(),
             unit_temp: None,
             var_debug_info: vec![],
+            coverage_branch_info: coverageinfo::BranchInfoBuilder::new_if_enabled(tcx, def.did),
         };
 
         assert_eq!(builder.cfg.start_new_block(), START_BLOCK);
@@ -922,7 +926,7 @@ fn finish(self) -> Body<'tcx> {
             }
         }
 
-        Body::new(
+        let mut body = Body::new(
             MirSource::item(self.def_id),
             self.cfg.basic_blocks,
             self.source_scopes,
@@ -933,7 +937,10 @@ fn finish(self) -> Body<'tcx> {
             self.fn_span,
             self.generator_kind,
             self.typeck_results.tainted_by_errors,
-        )
+        );
+        body.coverage_branch_info =
+            self.coverage_branch_info.and_then(|branch_info| branch_info.into_done());
+        body
     }
 
     fn args_and_body(
@@ -1143,6 +1150,7 @@ fn get_unit_temp(&mut self) -> Place<'tcx> {
 
 mod block;
 mod cfg;
+mod coverageinfo;
 mod expr;
 mod matches;
 mod misc;
@@ -1,7 +1,7 @@
 use crate::build::ExprCategory;
 use rustc_middle::thir::visit::{self, Visitor};
 
-use rustc_errors::struct_span_err;
+use rustc_errors::{struct_span_err, Applicability};
 use rustc_hir as hir;
 use rustc_middle::mir::BorrowKind;
 use rustc_middle::thir::*;
@@ -34,6 +34,24 @@ struct UnsafetyVisitor<'a, 'tcx> {
     in_union_destructure: bool,
     param_env: ParamEnv<'tcx>,
     inside_adt: bool,
+    /// The span of the innermost expression around the current one that could be wrapped in an
+    /// `unsafe` block without changing its meaning, if any. Used to suggest minimal `unsafe`
+    /// blocks for `unsafe_op_in_unsafe_fn`.
+    wrap_span: Option<Span>,
+    /// Whether `wrap_span` is a whole expression statement, whose `unsafe` block can be merged
+    /// with that of the next statement.
+    wrap_span_is_stmt: bool,
+    /// Unsafe operations found in an unsafe fn body, linted once the whole body has been visited
+    /// so that the suggested `unsafe` blocks of adjacent operations can be merged.
+    unsafe_ops_in_unsafe_fn: Vec<UnsafeOpInUnsafeFn>,
+}
+
+struct UnsafeOpInUnsafeFn {
+    hir_id: hir::HirId,
+    span: Span,
+    kind: UnsafeOpKind,
+    wrap_span: Option<Span>,
+    wrap_span_is_stmt: bool,
 }
 
 impl<'tcx> UnsafetyVisitor<'_, 'tcx> {
@@ -70,6 +88,17 @@ fn in_safety_context(&mut self, safety_context: SafetyContext, f: impl FnOnce(&m
         }
     }
 
+    /// Like `visit::walk_block`, but allows the tail expression to be wrapped in `unsafe`.
+    fn walk_block(&mut self, block: &Block) {
+        for &stmt in &*block.stmts {
+            self.visit_stmt(&self.thir[stmt]);
+        }
+        if let Some(expr) = block.expr {
+            let expr = &self.thir[expr];
+            self.with_wrap_span(Some(expr.span), |this| this.visit_expr(expr));
+        }
+    }
+
     fn requires_unsafe(&mut self, span: Span, kind: UnsafeOpKind) {
         let unsafe_op_in_unsafe_fn_allowed = self.unsafe_op_in_unsafe_fn_allowed();
         match self.safety_context {
@@ -82,22 +111,14 @@ fn requires_unsafe(&mut self, span: Span, kind: UnsafeOpKind) {
             }
             SafetyContext::UnsafeFn if unsafe_op_in_unsafe_fn_allowed => {}
             SafetyContext::UnsafeFn => {
-                let (description, note) = kind.description_and_note(self.tcx);
                 // unsafe_op_in_unsafe_fn is disallowed
-                self.tcx.struct_span_lint_hir(
-                    UNSAFE_OP_IN_UNSAFE_FN,
-                    self.hir_context,
+                self.unsafe_ops_in_unsafe_fn.push(UnsafeOpInUnsafeFn {
+                    hir_id: self.hir_context,
                     span,
-                    |lint| {
-                        lint.build(&format!(
-                            "{} is unsafe and requires unsafe block (error E0133)",
-                            description,
-                        ))
-                        .span_label(span, kind.simple_description())
-                        .note(note)
-                        .emit();
-                    },
-                )
+                    kind,
+                    wrap_span: self.wrap_span.filter(|span| !span.from_expansion()),
+                    wrap_span_is_stmt: self.wrap_span_is_stmt,
+                });
             }
             SafetyContext::Safe => {
                 let (description, note) = kind.description_and_note(self.tcx);
@@ -135,6 +156,120 @@ fn warn_unused_unsafe(
         });
     }
 
+    /// Emits `unsafe_op_in_unsafe_fn` for the unsafe operations found in the body. Operations
+    /// whose `unsafe` blocks would be nested in each other, or that are consecutive expression
+    /// statements, share a single suggestion, attached to the first of them.
+    fn lint_unsafe_ops_in_unsafe_fn(&mut self) {
+        let ops = std::mem::take(&mut self.unsafe_ops_in_unsafe_fn);
+        let source_map = self.tcx.sess.source_map();
+
+        let mut by_position: Vec<_> =
+            (0..ops.len()).filter(|&i| ops[i].wrap_span.is_some()).collect();
+        by_position.sort_by_key(|&i| ops[i].wrap_span.unwrap().lo());
+        // Each group also records whether it ends with a whole expression statement: merging
+        // anything else across a `;` would change the type of e.g. a `let` initializer.
+        let mut groups: Vec<(hir::HirId, Span, bool)> = vec![];
+        let mut group_of = vec![None; ops.len()];
+        for i in by_position {
+            let (hir_id, span, is_stmt) =
+                (ops[i].hir_id, ops[i].wrap_span.unwrap(), ops[i].wrap_span_is_stmt);
+            match groups.last_mut() {
+                Some((group_hir_id, group_span, _))
+                    if *group_hir_id == hir_id && span.lo() < group_span.hi() =>
+                {
+                    *group_span = group_span.to(span);
+                }
+                Some((group_hir_id, group_span, group_is_stmt))
+                    if *group_hir_id == hir_id
+                        && *group_is_stmt
+                        && is_stmt
+                        && source_map
+                            .span_to_snippet(group_span.between(span))
+                            .map_or(false, |between| between.trim() == ";") =>
+                {
+                    *group_span = group_span.to(span);
+                }
+                _ => groups.push((hir_id, span, is_stmt)),
+            }
+            group_of[i] = Some(groups.len() - 1);
+        }
+
+        let mut group_sizes = vec![0; groups.len()];
+        for &group in group_of.iter().flatten() {
+            group_sizes[group] += 1;
+        }
+        let mut suggested = vec![false; groups.len()];
+        for (op, group) in ops.into_iter().zip(group_of) {
+            let UnsafeOpInUnsafeFn { hir_id, span, kind, .. } = op;
+            let suggestion = group.filter(|&group| !std::mem::replace(&mut suggested[group], true));
+            let (description, note) = kind.description_and_note(self.tcx);
+            self.tcx.struct_span_lint_hir(UNSAFE_OP_IN_UNSAFE_FN, hir_id, span, |lint| {
+                let mut db = lint.build(&format!(
+                    "{} is unsafe and requires unsafe block (error E0133)",
+                    description,
+                ));
+                db.span_label(span, kind.simple_description()).note(note);
+                if let Some(group) = suggestion {
+                    let wrap_span = groups[group].1;
+                    let msg = if group_sizes[group] == 1 {
+                        "wrap the unsafe operation in an `unsafe` block"
+                    } else {
+                        "wrap the unsafe operations in an `unsafe` block"
+                    };
+                    db.multipart_suggestion(
+                        msg,
+                        vec![
+                            (wrap_span.shrink_to_lo(), "unsafe { ".to_string()),
+                            (wrap_span.shrink_to_hi(), " }".to_string()),
+                        ],
+                        Applicability::MachineApplicable,
+                    );
+                }
+                db.emit();
+            });
+        }
+    }
+
+    /// Whether `expr` can be replaced by `unsafe { expr }` without changing the meaning of the
+    /// program: it must be a value rather than a place, and be written out in the source rather
+    /// than being an adjustment (which shares the span of the expression it adjusts).
+    fn is_wrappable(&self, expr: &Expr<'tcx>) -> bool {
+        match expr.kind {
+            ExprKind::Borrow { arg, .. } | ExprKind::AddressOf { arg, .. } => {
+                self.thir[arg].span != expr.span
+            }
+            ExprKind::Pointer { source, .. }
+            | ExprKind::NeverToAny { source }
+            | ExprKind::Use { source }
+            | ExprKind::Cast { source } => self.thir[source].span != expr.span,
+            // Overloaded operators are lowered to calls, whose results may be used as places.
+            ExprKind::Call { from_hir_call, .. } => from_hir_call,
+            ExprKind::Unary { .. }
+            | ExprKind::Binary { .. }
+            | ExprKind::LogicalOp { .. }
+            | ExprKind::Assign { .. }
+            | ExprKind::AssignOp { .. }
+            | ExprKind::Adt { .. }
+            | ExprKind::Tuple { .. }
+            | ExprKind::Array { .. }
+            | ExprKind::Repeat { .. }
+            | ExprKind::Box { .. }
+            | ExprKind::InlineAsm { .. }
+            | ExprKind::Return { .. }
+            | ExprKind::Break { .. }
+            | ExprKind::Yield { .. } => true,
+            _ => false,
+        }
+    }
+
+    fn with_wrap_span(&mut self, wrap_span: Option<Span>, f: impl FnOnce(&mut Self)) {
+        let prev_wrap_span = std::mem::replace(&mut self.wrap_span, wrap_span);
+        let prev_wrap_span_is_stmt = std::mem::replace(&mut self.wrap_span_is_stmt, false);
+        f(self);
+        self.wrap_span = prev_wrap_span;
+        self.wrap_span_is_stmt = prev_wrap_span_is_stmt;
+    }
+
     /// Whether the `unsafe_op_in_unsafe_fn` lint is `allow`ed at the current HIR node.
     fn unsafe_op_in_unsafe_fn_allowed(&self) -> bool {
         self.tcx.lint_level_at_node(UNSAFE_OP_IN_UNSAFE_FN, self.hir_context).0 == Level::Allow
@@ -196,21 +331,65 @@ fn visit_block(&mut self, block: &Block) {
             // an outer unsafe block
             BlockSafety::BuiltinUnsafe => {
                 self.in_safety_context(SafetyContext::BuiltinUnsafeBlock, |this| {
-                    visit::walk_block(this, block)
+                    this.walk_block(block)
                 });
             }
             BlockSafety::ExplicitUnsafe(hir_id) => {
                 self.in_safety_context(
                     SafetyContext::UnsafeBlock { span: block.span, hir_id, used: false },
-                    |this| visit::walk_block(this, block),
+                    |this| this.walk_block(block),
                 );
             }
             BlockSafety::Safe => {
-                visit::walk_block(self, block);
+                self.walk_block(block);
             }
         }
     }
 
+    fn visit_stmt(&mut self, stmt: &Stmt<'tcx>) {
+        match stmt.kind {
+            StmtKind::Expr { expr, .. } => {
+                let expr = &self.thir[expr];
+                // A place expression statement is not moved out of, but would be if wrapped.
+                let wrap_span = (ExprCategory::of(&expr.kind) != Some(ExprCategory::Place)
+                    || expr.ty.is_copy_modulo_regions(self.tcx.at(expr.span), self.param_env))
+                .then_some(expr.span);
+                self.with_wrap_span(wrap_span, |this| {
+                    this.wrap_span_is_stmt = wrap_span.is_some();
+                    this.visit_expr(expr)
+                });
+            }
+            StmtKind::Let { initializer, ref pattern, .. } => {
+                if let Some(init) = initializer {
+                    let init = &self.thir[init];
+                    // Bindings by reference would borrow the block's result instead of the place.
+                    let wrap_span = (ExprCategory::of(&init.kind) != Some(ExprCategory::Place)
+                        || matches!(
+                            *pattern.kind,
+                            PatKind::Binding { mode: BindingMode::ByValue, subpattern: None, .. }
+                        ))
+                    .then_some(init.span);
+                    self.with_wrap_span(wrap_span, |this| this.visit_expr(init));
+                }
+                self.with_wrap_span(None, |this| this.visit_pat(pattern));
+            }
+        }
+    }
+
+    fn visit_arm(&mut self, arm: &Arm<'tcx>) {
+        match arm.guard {
+            Some(Guard::If(expr)) => self.visit_expr(&self.thir[expr]),
+            Some(Guard::IfLet(ref pat, expr)) => {
+                self.with_wrap_span(None, |this| this.visit_pat(pat));
+                self.visit_expr(&self.thir[expr]);
+            }
+            None => {}
+        }
+        self.with_wrap_span(None, |this| this.visit_pat(&arm.pattern));
+        let body = &self.thir[arm.body];
+        self.with_wrap_span(Some(body.span), |this| this.visit_expr(body));
+    }
+
     fn visit_pat(&mut self, pat: &Pat<'tcx>) {
         if self.in_union_destructure {
             match *pat.kind {
@@ -290,6 +469,11 @@ fn visit_pat(&mut self, pat: &Pat<'tcx>) {
     }
 
     fn visit_expr(&mut self, expr: &Expr<'tcx>) {
+        if self.wrap_span != Some(expr.span) && self.is_wrappable(expr) {
+            self.with_wrap_span(Some(expr.span), |this| this.visit_expr(expr));
+            return;
+        }
+
         // could we be in the LHS of an assignment to a field?
         match expr.kind {
             ExprKind::Field { .. }
@@ -420,9 +604,16 @@ fn visit_expr(&mut self, expr: &Expr<'tcx>) {
                 });
                 let closure_thir = &closure_thir.borrow();
                 let hir_context = self.tcx.hir().local_def_id_to_hir_id(closure_id);
-                let mut closure_visitor =
-                    UnsafetyVisitor { thir: closure_thir, hir_context, ..*self };
+                let mut closure_visitor = UnsafetyVisitor {
+                    thir: closure_thir,
+                    hir_context,
+                    wrap_span: Some(closure_thir[expr].span),
+                    wrap_span_is_stmt: false,
+                    unsafe_ops_in_unsafe_fn: vec![],
+                    ..*self
+                };
                 closure_visitor.visit_expr(&closure_thir[expr]);
+                self.unsafe_ops_in_unsafe_fn.append(&mut closure_visitor.unsafe_ops_in_unsafe_fn);
                 // Unsafe blocks can be used in closures, make sure to take it into account
                 self.safety_context = closure_visitor.safety_context;
             }
@@ -674,8 +865,12 @@ pub fn check_unsafety<'tcx>(tcx: TyCtxt<'tcx>, def: ty::WithOptConstParam<LocalD
         in_union_destructure: false,
         param_env: tcx.param_env(def.did),
         inside_adt: false,
+        wrap_span: Some(thir[expr].span),
+        wrap_span_is_stmt: false,
+        unsafe_ops_in_unsafe_fn: vec![],
     };
     visitor.visit_expr(&thir[expr]);
+    visitor.lint_unsafe_ops_in_unsafe_fn();
 }
 
 pub(crate) fn thir_check_unsafety<'tcx>(tcx: TyCtxt<'tcx>, def_id: LocalDefId) {
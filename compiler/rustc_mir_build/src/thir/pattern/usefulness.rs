@@ -739,13 +739,27 @@ fn lint_non_exhaustive_omitted_patterns<'p, 'tcx>(
     hir_id: HirId,
     witnesses: Vec<DeconstructedPat<'p, 'tcx>>,
 ) {
+    /// The most patterns listed in the note, when there are too many for the label.
+    const NOTE_LIMIT: usize = 16;
+
     let joined_patterns = joined_uncovered_patterns(cx, &witnesses);
     cx.tcx.struct_span_lint_hir(NON_EXHAUSTIVE_OMITTED_PATTERNS, hir_id, sp, |build| {
         let mut lint = build.build("some variants are not matched explicitly");
         lint.span_label(sp, pattern_not_covered_label(&witnesses, &joined_patterns));
-        lint.help(
-            "ensure that all variants are matched explicitly by adding the suggested match arms",
-        );
+        // The label only lists the first few patterns, so list more of them in a note.
+        if witnesses.len() > 3 {
+            let listed = witnesses.len().min(NOTE_LIMIT);
+            let patterns: Vec<_> =
+                witnesses[..listed].iter().map(|w| format!("`{}`", w.to_pat(cx))).collect();
+            let patterns = if listed < witnesses.len() {
+                format!("{} and {} more", patterns.join(", "), witnesses.len() - listed)
+            } else {
+                let (last, init) = patterns.split_last().unwrap();
+                format!("{} and {}", init.join(", "), last)
+            };
+            lint.note(&format!("this wildcard currently matches {}", patterns));
+        }
+        lint.help("ensure that all variants are matched explicitly by adding match arms for them");
         lint.note(&format!(
             "the matched value is of type `{}` and the `non_exhaustive_omitted_patterns` attribute was found",
             scrut_ty,
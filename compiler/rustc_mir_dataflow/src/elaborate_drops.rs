@@ -485,6 +485,13 @@ fn open_drop_for_multivariant(
 
         for (variant_index, discr) in adt.discriminants(tcx) {
             let variant = &adt.variant(variant_index);
+            let param_env = self.elaborator.param_env();
+            if adt.is_variant_statically_uninhabited(tcx, variant_index, substs, param_env) {
+                // The value is never in this variant, so it does not need a drop ladder: leave it
+                // to the otherwise branch, which does not drop anything on its behalf.
+                have_otherwise = true;
+                continue;
+            }
             let subpath = self.elaborator.downcast_subpath(self.path, variant_index);
 
             if let Some(variant_path) = subpath {
@@ -523,7 +530,6 @@ fn open_drop_for_multivariant(
             } else {
                 have_otherwise = true;
 
-                let param_env = self.elaborator.param_env();
                 let have_field_with_drop_glue = variant
                     .fields
                     .iter()
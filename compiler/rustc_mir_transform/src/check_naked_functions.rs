@@ -0,0 +1,77 @@
+//! Validates the MIR of naked functions.
+//!
+//! Naked functions have no prologue, so their body must not contain anything but a single
+//! `noreturn` inline assembly block with `const` and `sym` operands. Violations are reported
+//! to the user by the HIR-level check in `rustc_passes`; this makes sure that MIR building
+//! didn't introduce any code of its own.
+
+use rustc_ast::InlineAsmOptions;
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
+use rustc_middle::mir::*;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::Span;
+
+use crate::MirLint;
+
+pub struct CheckNakedFunctions;
+
+impl<'tcx> MirLint<'tcx> for CheckNakedFunctions {
+    fn run_lint(&self, tcx: TyCtxt<'tcx>, body: &Body<'tcx>) {
+        let def_id = body.source.def_id();
+        if body.source.promoted.is_some()
+            || body.tainted_by_errors.is_some()
+            || !tcx.def_kind(def_id).is_fn_like()
+            || !tcx.codegen_fn_attrs(def_id).flags.contains(CodegenFnAttrFlags::NAKED)
+        {
+            return;
+        }
+
+        let invalid = |span: Span, what: &str| {
+            tcx.sess.delay_span_bug(span, &format!("{} in the MIR of a naked function", what));
+        };
+
+        let mut asm_blocks = 0;
+        for (_, data) in traversal::reachable(body) {
+            for statement in &data.statements {
+                match statement.kind {
+                    StatementKind::StorageLive(_)
+                    | StatementKind::StorageDead(_)
+                    | StatementKind::FakeRead(_)
+                    | StatementKind::AscribeUserType(..)
+                    | StatementKind::Nop => {}
+                    _ => invalid(statement.source_info.span, "unexpected statement"),
+                }
+            }
+
+            let terminator = data.terminator();
+            match terminator.kind {
+                TerminatorKind::Goto { .. } => {}
+                TerminatorKind::InlineAsm { ref operands, options, destination, .. } => {
+                    asm_blocks += 1;
+                    if destination.is_some() || !options.contains(InlineAsmOptions::NORETURN) {
+                        invalid(terminator.source_info.span, "returning `asm!` block");
+                    }
+                    let unsupported_operand = operands.iter().any(|op| {
+                        !matches!(
+                            op,
+                            InlineAsmOperand::Const { .. }
+                                | InlineAsmOperand::SymFn { .. }
+                                | InlineAsmOperand::SymStatic { .. }
+                        )
+                    });
+                    if unsupported_operand {
+                        invalid(
+                            terminator.source_info.span,
+                            "`asm!` operand other than `const` or `sym`",
+                        );
+                    }
+                }
+                _ => invalid(terminator.source_info.span, "unexpected terminator"),
+            }
+        }
+
+        if asm_blocks != 1 {
+            invalid(body.span, &format!("{} `asm!` blocks", asm_blocks));
+        }
+    }
+}
@@ -1,9 +1,13 @@
+use rustc_errors::Applicability;
+use rustc_hir as hir;
 use rustc_hir::def_id::LocalDefId;
+use rustc_hir::intravisit;
 use rustc_middle::mir::visit::{PlaceContext, Visitor};
 use rustc_middle::mir::*;
 use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::{self, TyCtxt};
 use rustc_session::lint::builtin::UNALIGNED_REFERENCES;
+use rustc_span::{sym, Span};
 
 use crate::util;
 use crate::MirLint;
@@ -49,6 +53,113 @@ fn unsafe_derive_on_repr_packed(tcx: TyCtxt<'_>, def_id: LocalDefId) {
     });
 }
 
+impl<'tcx> PackedRefChecker<'_, 'tcx> {
+    /// Builds a fix for a borrow of `place` at `span` that avoids creating the reference.
+    ///
+    /// An explicit `&pkd.field` becomes `ptr::addr_of!(pkd.field)`, and an autoref'd
+    /// method receiver `pkd.field.method()` becomes `{ pkd.field }.method()`, which
+    /// copies the field out first. The `addr_of!` fix only keeps the code compiling when
+    /// the reference is immediately turned into a raw pointer, so it is only machine
+    /// applicable in that case.
+    fn suggestion(
+        &self,
+        place: Place<'tcx>,
+        context: PlaceContext,
+        span: Span,
+    ) -> Option<(String, Vec<(Span, String)>, Applicability)> {
+        if span.from_expansion() {
+            return None;
+        }
+        let hir = self.tcx.hir();
+        let def_id = self.body.source.def_id().as_local()?;
+        let body_id = hir.maybe_body_owned_by(hir.local_def_id_to_hir_id(def_id))?;
+        let mut finder = BorrowExprFinder { span, expr: None };
+        intravisit::Visitor::visit_body(&mut finder, hir.body(body_id));
+        let expr = finder.expr?;
+        match expr.kind {
+            hir::ExprKind::AddrOf(hir::BorrowKind::Ref, mutbl, inner) => {
+                let macro_name = match mutbl {
+                    hir::Mutability::Not => "addr_of",
+                    hir::Mutability::Mut => "addr_of_mut",
+                };
+                let no_std = self.tcx.sess.contains_name(hir.krate_attrs(), sym::no_std);
+                let krate = if no_std { "core" } else { "std" };
+                let applicability = if self.is_coerced_to_raw_ptr(def_id, expr) {
+                    Applicability::MachineApplicable
+                } else {
+                    Applicability::MaybeIncorrect
+                };
+                Some((
+                    format!(
+                        "use `{}!` to create a raw pointer without an intermediate reference",
+                        macro_name
+                    ),
+                    vec![
+                        (span.until(inner.span), format!("{}::ptr::{}!(", krate, macro_name)),
+                        (span.shrink_to_hi(), ")".to_string()),
+                    ],
+                    applicability,
+                ))
+            }
+            hir::ExprKind::MethodCall(_, [receiver, ..], _)
+                if matches!(context, PlaceContext::NonMutatingUse(_))
+                    && matches!(receiver.kind, hir::ExprKind::Field(..) | hir::ExprKind::Index(..))
+                    && place
+                        .ty(self.body, self.tcx)
+                        .ty
+                        .is_copy_modulo_regions(self.tcx.at(span), self.param_env) =>
+            {
+                Some((
+                    "copy the field contents before calling the method".to_string(),
+                    vec![
+                        (receiver.span.shrink_to_lo(), "{ ".to_string()),
+                        (receiver.span.shrink_to_hi(), " }".to_string()),
+                    ],
+                    Applicability::MachineApplicable,
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the reference created by `expr` is only used as a raw pointer, either through
+    /// a coercion or an `as` cast to a raw pointer type.
+    fn is_coerced_to_raw_ptr(&self, def_id: LocalDefId, expr: &hir::Expr<'_>) -> bool {
+        let hir = self.tcx.hir();
+        let typeck_results = self.tcx.typeck(def_id);
+        if typeck_results.expr_ty_adjusted(expr).is_unsafe_ptr() {
+            return true;
+        }
+        match hir.find(hir.get_parent_node(expr.hir_id)) {
+            Some(hir::Node::Expr(parent @ hir::Expr { kind: hir::ExprKind::Cast(..), .. })) => {
+                typeck_results.expr_ty(parent).is_unsafe_ptr()
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Finds the borrow or method call expression a MIR borrow was lowered from.
+struct BorrowExprFinder<'hir> {
+    span: Span,
+    expr: Option<&'hir hir::Expr<'hir>>,
+}
+
+impl<'hir> intravisit::Visitor<'hir> for BorrowExprFinder<'hir> {
+    fn visit_expr(&mut self, expr: &'hir hir::Expr<'hir>) {
+        if self.expr.is_some() {
+            return;
+        }
+        if expr.span == self.span
+            && matches!(expr.kind, hir::ExprKind::AddrOf(..) | hir::ExprKind::MethodCall(..))
+        {
+            self.expr = Some(expr);
+        } else {
+            intravisit::walk_expr(self, expr);
+        }
+    }
+}
+
 impl<'tcx> Visitor<'tcx> for PackedRefChecker<'_, 'tcx> {
     fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location) {
         // Make sure we know where in the MIR we are.
@@ -76,6 +187,7 @@ fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, _location:
                     self.tcx.ensure().unsafe_derive_on_repr_packed(impl_def_id.expect_local());
                 } else {
                     let source_info = self.source_info;
+                    let suggestion = self.suggestion(*place, context, source_info.span);
                     let lint_root = self.body.source_scopes[source_info.scope]
                         .local_data
                         .as_ref()
@@ -86,18 +198,21 @@ fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, _location:
                         lint_root,
                         source_info.span,
                         |lint| {
-                            lint.build("reference to packed field is unaligned")
-                                .note(
-                                    "fields of packed structs are not properly aligned, and creating \
-                                    a misaligned reference is undefined behavior (even if that \
-                                    reference is never dereferenced)",
-                                )
-                                .help(
-                                    "copy the field contents to a local variable, or replace the \
-                                    reference with a raw pointer and use `read_unaligned`/`write_unaligned` \
-                                    (loads and stores via `*p` must be properly aligned even when using raw pointers)"
-                                )
-                                .emit();
+                            let mut err = lint.build("reference to packed field is unaligned");
+                            err.note(
+                                "fields of packed structs are not properly aligned, and creating \
+                                a misaligned reference is undefined behavior (even if that \
+                                reference is never dereferenced)",
+                            )
+                            .help(
+                                "copy the field contents to a local variable, or replace the \
+                                reference with a raw pointer and use `read_unaligned`/`write_unaligned` \
+                                (loads and stores via `*p` must be properly aligned even when using raw pointers)"
+                            );
+                            if let Some((msg, parts, applicability)) = suggestion {
+                                err.multipart_suggestion(msg, parts, applicability);
+                            }
+                            err.emit();
                         },
                     );
                 }
@@ -27,11 +27,12 @@
 use rustc_trait_selection::traits;
 
 use crate::MirPass;
+use rustc_const_eval::const_eval::eval_const_fn_call;
 use rustc_const_eval::interpret::{
-    self, compile_time_machine, AllocId, ConstAllocation, ConstValue, CtfeValidationMode, Frame,
-    ImmTy, Immediate, InterpCx, InterpResult, LocalState, LocalValue, MemPlace, MemoryKind, OpTy,
-    Operand as InterpOperand, PlaceTy, Pointer, Scalar, ScalarMaybeUninit, StackPopCleanup,
-    StackPopUnwind,
+    self, compile_time_machine, AllocId, Allocation, ConstAllocation, ConstValue,
+    CtfeValidationMode, Frame, ImmTy, Immediate, InterpCx, InterpResult, LocalState, LocalValue,
+    MemPlace, MemoryKind, OpTy, Operand as InterpOperand, PlaceTy, Pointer, Scalar,
+    ScalarMaybeUninit, StackPopCleanup, StackPopUnwind,
 };
 
 /// The maximum number of bytes that we'll allocate space for a local or the return value.
@@ -39,6 +40,12 @@
 /// Severely regress performance.
 const MAX_ALLOC_LIMIT: u64 = 1024;
 
+/// Calls to `const fn`s whose body has more basic blocks than this are never evaluated.
+const MAX_CALLEE_BLOCKS: usize = 16;
+
+/// The number of terminators that evaluating a call to a `const fn` may execute before giving up.
+const MAX_CALL_STEPS: usize = 128;
+
 /// Macro for machine-specific `InterpError` without allocation.
 /// (These will never be shown to the user, but they help diagnose ICEs.)
 macro_rules! throw_machine_stop_str {
@@ -682,6 +689,70 @@ fn eval_rvalue_with_identities(
         })
     }
 
+    /// Evaluates a call to a small `const fn` whose arguments are all known, and stores the result
+    /// in `destination`, so that uses of the result get propagated.
+    ///
+    /// The call itself is left in place, even though it is now redundant, as removing it is
+    /// left to later passes.
+    fn const_prop_call(
+        &mut self,
+        func: &Operand<'tcx>,
+        args: &[Operand<'tcx>],
+        destination: Place<'tcx>,
+    ) -> Option<()> {
+        if self.tcx.sess.mir_opt_level() < 2
+            || !destination.projection.is_empty()
+            || self.ecx.machine.can_const_prop[destination.local] != ConstPropMode::FullConstProp
+        {
+            return None;
+        }
+
+        let ty::FnDef(def_id, substs) = *func.ty(self.local_decls, self.tcx).kind() else {
+            return None;
+        };
+        if substs.needs_subst() || !self.tcx.is_const_fn_raw(def_id) {
+            return None;
+        }
+        let instance = Instance::resolve(self.tcx, self.param_env, def_id, substs).ok()??;
+        let ty::InstanceDef::Item(def) = instance.def else { return None };
+        if !self.tcx.is_ctfe_mir_available(def.did)
+            || self.tcx.mir_for_ctfe_opt_const_arg(def).basic_blocks().len() > MAX_CALLEE_BLOCKS
+        {
+            return None;
+        }
+
+        let args = args.iter().map(|arg| self.const_arg(arg)).collect::<Option<Vec<_>>>()?;
+        let result = eval_const_fn_call(self.tcx, self.param_env, instance, &args, MAX_CALL_STEPS)?;
+        trace!("evaluated call to {:?} to {:?}", instance, result);
+        self.use_ecx(|this| {
+            let dest = this.ecx.eval_place(destination)?;
+            this.ecx.write_immediate(*result, &dest)
+        })
+    }
+
+    /// Returns the value of `arg` as a `ConstValue` that does not refer to the memory of our
+    /// interpreter, if it is known and does not contain pointers.
+    fn const_arg(&mut self, arg: &Operand<'tcx>) -> Option<ConstValue<'tcx>> {
+        let op = self.eval_operand(arg)?;
+        match self.use_ecx(|this| this.ecx.read_immediate_raw(&op, /*force*/ false))? {
+            Ok(imm) => match *imm {
+                Immediate::Scalar(ScalarMaybeUninit::Scalar(scalar @ Scalar::Int(_))) => {
+                    Some(ConstValue::Scalar(scalar))
+                }
+                _ => None,
+            },
+            Err(mplace) => {
+                // This fails if the argument contains pointers or uninitialized bytes.
+                let bytes = self.ecx.read_bytes_ptr(mplace.ptr, mplace.layout.size).ok()?;
+                let alloc = Allocation::from_bytes(bytes, mplace.align, Mutability::Not);
+                Some(ConstValue::ByRef {
+                    alloc: self.tcx.intern_const_alloc(alloc),
+                    offset: Size::ZERO,
+                })
+            }
+        }
+    }
+
     /// Creates a new `Operand::Constant` from a `Scalar` value
     fn operand_from_scalar(&self, scalar: Scalar, ty: Ty<'tcx>, span: Span) -> Operand<'tcx> {
         Operand::Constant(Box::new(Constant {
@@ -1115,8 +1186,11 @@ fn visit_terminator(&mut self, terminator: &mut Terminator<'tcx>, location: Loca
             // Every argument in our function calls have already been propagated in `visit_operand`.
             //
             // NOTE: because LLVM codegen gives slight performance regressions with it, so this is
-            // gated on `mir_opt_level=3`.
-            TerminatorKind::Call { .. } => {}
+            // gated on `mir_opt_level=3`. Calls to small `const fn`s with known arguments are
+            // evaluated, so that their result can be propagated.
+            TerminatorKind::Call { ref func, ref args, destination, .. } => {
+                self.const_prop_call(func, args, *destination);
+            }
         }
 
         // We remove all Locals which are restricted in propagation to their containing blocks and
@@ -38,15 +38,16 @@ pub fn enable_debug(&mut self) {
     }
 
     /// Makes `CoverageKind` `Counter`s and `Expressions` for the `BasicCoverageBlock`s directly or
-    /// indirectly associated with `CoverageSpans`, and returns additional `Expression`s
-    /// representing intermediate values.
+    /// indirectly associated with `CoverageSpans` (or listed in `branch_bcbs`), and returns
+    /// additional `Expression`s representing intermediate values.
     pub fn make_bcb_counters(
         &mut self,
         basic_coverage_blocks: &mut CoverageGraph,
         coverage_spans: &[CoverageSpan],
+        branch_bcbs: &[BasicCoverageBlock],
     ) -> Result<Vec<CoverageKind>, Error> {
         let mut bcb_counters = BcbCounters::new(self, basic_coverage_blocks);
-        bcb_counters.make_bcb_counters(coverage_spans)
+        bcb_counters.make_bcb_counters(coverage_spans, branch_bcbs)
     }
 
     fn make_counter<F>(&mut self, debug_block_label_fn: F) -> CoverageKind
@@ -141,6 +142,7 @@ fn new(
     fn make_bcb_counters(
         &mut self,
         coverage_spans: &[CoverageSpan],
+        branch_bcbs: &[BasicCoverageBlock],
     ) -> Result<Vec<CoverageKind>, Error> {
         debug!("make_bcb_counters(): adding a counter or expression to each BasicCoverageBlock");
         let num_bcbs = self.basic_coverage_blocks.num_nodes();
@@ -150,6 +152,11 @@ fn make_bcb_counters(
         for covspan in coverage_spans {
             bcbs_with_coverage.insert(covspan.bcb);
         }
+        // The blocks on either side of a branch are counted for the branch region, even if they
+        // don't have any `CoverageSpan`s.
+        for &bcb in branch_bcbs {
+            bcbs_with_coverage.insert(bcb);
+        }
 
         // Walk the `CoverageGraph`. For each `BasicCoverageBlock` node with an associated
         // `CoverageSpan`, add a counter. If the `BasicCoverageBlock` branches, add a counter or
@@ -308,6 +308,12 @@ pub fn format_counter(&self, counter_kind: &CoverageKind) -> String {
                 format!("Expression({})", self.format_counter_kind(counter_kind))
             }
             CoverageKind::Unreachable { .. } => "Unreachable".to_owned(),
+            CoverageKind::BlockMarker { id } => format!("BlockMarker({})", id.index()),
+            CoverageKind::Branch { true_operand, false_operand } => format!(
+                "Branch(true: {}, false: {})",
+                self.format_operand(true_operand),
+                self.format_operand(false_operand),
+            ),
         }
     }
 
@@ -523,6 +529,10 @@ pub fn add_expression_operands(&mut self, expression: &CoverageKind) {
             if let CoverageKind::Expression { id, lhs, rhs, .. } = *expression {
                 used_expression_operands.entry(lhs).or_insert_with(Vec::new).push(id);
                 used_expression_operands.entry(rhs).or_insert_with(Vec::new).push(id);
+            } else if let CoverageKind::Branch { true_operand, false_operand } = *expression {
+                // Branches have no ID of their own, but their operands are used all the same.
+                used_expression_operands.entry(true_operand).or_insert_with(Vec::new);
+                used_expression_operands.entry(false_operand).or_insert_with(Vec::new);
             }
         }
     }
@@ -28,7 +28,7 @@
 };
 use rustc_middle::ty::TyCtxt;
 use rustc_span::def_id::DefId;
-use rustc_span::source_map::SourceMap;
+use rustc_span::source_map::{original_sp, SourceMap};
 use rustc_span::{CharPos, ExpnKind, Pos, SourceFile, Span, Symbol};
 
 /// A simple error message wrapper for `coverage::Error`s.
@@ -54,6 +54,19 @@ fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
     }
 
     fn run_pass(&self, tcx: TyCtxt<'tcx>, mir_body: &mut mir::Body<'tcx>) {
+        self.instrument_function(tcx, mir_body);
+
+        // Branch coverage info, and the `BlockMarker` statements it refers to, are only needed
+        // while instrumenting, even if the function ended up not being instrumented at all.
+        if tcx.sess.instrument_coverage_branch() {
+            mir_body.coverage_branch_info = None;
+            remove_block_markers(mir_body);
+        }
+    }
+}
+
+impl InstrumentCoverage {
+    fn instrument_function<'tcx>(&self, tcx: TyCtxt<'tcx>, mir_body: &mut mir::Body<'tcx>) {
         let mir_source = mir_body.source;
 
         // If the InstrumentCoverage pass is called on promoted MIRs, skip them.
@@ -94,6 +107,12 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, mir_body: &mut mir::Body<'tcx>) {
             return;
         }
 
+        // Naked functions can only contain a single `asm!` block, there's no room for counters.
+        if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::NAKED) {
+            trace!("InstrumentCoverage skipped for {:?} (naked function)", mir_source.def_id());
+            return;
+        }
+
         trace!("InstrumentCoverage starting for {:?}", mir_source.def_id());
         Instrumentor::new(&self.name(), tcx, mir_body).inject_counters();
         trace!("InstrumentCoverage done for {:?}", mir_source.def_id());
@@ -109,6 +128,15 @@ struct Instrumentor<'a, 'tcx> {
     body_span: Span,
     basic_coverage_blocks: CoverageGraph,
     coverage_counters: CoverageCounters,
+    coverage_branches: Vec<CoverageBranch>,
+}
+
+/// A branch recorded during MIR building (see `mir::coverage::BranchInfo`), mapped to the
+/// `BasicCoverageBlock`s entered when its condition is `true` and `false`.
+struct CoverageBranch {
+    span: Span,
+    true_bcb: BasicCoverageBlock,
+    false_bcb: BasicCoverageBlock,
 }
 
 impl<'a, 'tcx> Instrumentor<'a, 'tcx> {
@@ -138,6 +166,12 @@ fn new(pass_name: &'a str, tcx: TyCtxt<'tcx>, mir_body: &'a mut mir::Body<'tcx>)
 
         let function_source_hash = hash_mir_source(tcx, hir_body);
         let basic_coverage_blocks = CoverageGraph::from_mir(mir_body);
+        let coverage_branches = match mir_body.coverage_branch_info.take() {
+            Some(branch_info) => {
+                extract_coverage_branches(mir_body, &branch_info, &basic_coverage_blocks, body_span)
+            }
+            None => Vec::new(),
+        };
         Self {
             pass_name,
             tcx,
@@ -147,6 +181,7 @@ fn new(pass_name: &'a str, tcx: TyCtxt<'tcx>, mir_body: &'a mut mir::Body<'tcx>)
             body_span,
             basic_coverage_blocks,
             coverage_counters: CoverageCounters::new(function_source_hash),
+            coverage_branches,
         }
     }
 
@@ -201,9 +236,19 @@ fn inject_counters(&'a mut self) {
         //
         // Intermediate expressions (used to compute other `Expression` values), which have no
         // direct associate to any `BasicCoverageBlock`, are returned in the method `Result`.
-        let intermediate_expressions_or_error = self
-            .coverage_counters
-            .make_bcb_counters(&mut self.basic_coverage_blocks, &coverage_spans);
+        //
+        // The `BasicCoverageBlock`s on either side of a branch need a counter as well, even if
+        // they have no `CoverageSpan`s of their own.
+        let branch_bcbs = self
+            .coverage_branches
+            .iter()
+            .flat_map(|branch| [branch.true_bcb, branch.false_bcb])
+            .collect::<Vec<_>>();
+        let intermediate_expressions_or_error = self.coverage_counters.make_bcb_counters(
+            &mut self.basic_coverage_blocks,
+            &coverage_spans,
+            &branch_bcbs,
+        );
 
         let (result, intermediate_expressions) = match intermediate_expressions_or_error {
             Ok(intermediate_expressions) => {
@@ -215,6 +260,12 @@ fn inject_counters(&'a mut self) {
                     }
                 }
 
+                ////////////////////////////////////////////////////
+                // Inject a `Branch` statement for each branch, referring to the counters of the
+                // BCBs on either side of it. This has to happen before the counters are taken
+                // out of the BCBs below.
+                self.inject_branch_regions(&mut debug_used_expressions);
+
                 ////////////////////////////////////////////////////
                 // Remove the counter or edge counter from of each `CoverageSpan`s associated
                 // `BasicCoverageBlock`, and inject a `Coverage` statement into the MIR.
@@ -330,6 +381,33 @@ fn inject_coverage_span_counters(
         }
     }
 
+    /// Inject a `Branch` statement, with the branch's code region, for each `CoverageBranch`. The
+    /// operands of the branch are the counters of the BCBs that are entered when the condition is
+    /// `true` and `false`, respectively.
+    fn inject_branch_regions(&mut self, debug_used_expressions: &mut debug::UsedExpressions) {
+        let source_map = self.tcx.sess.source_map();
+        let body_span = self.body_span;
+        let file_name = Symbol::intern(&self.source_file.name.prefer_remapped().to_string_lossy());
+
+        for &CoverageBranch { span, true_bcb, false_bcb } in &self.coverage_branches {
+            let bcb_operand = |bcb| {
+                self.bcb_data(bcb)
+                    .counter()
+                    .expect("every branch BasicCoverageBlock should have a counter")
+                    .as_operand_id()
+            };
+            let branch_kind = CoverageKind::Branch {
+                true_operand: bcb_operand(true_bcb),
+                false_operand: bcb_operand(false_bcb),
+            };
+            debug_used_expressions.add_expression_operands(&branch_kind);
+
+            let code_region =
+                make_code_region(source_map, file_name, &self.source_file, span, body_span);
+            inject_branch_region(self.mir_body, branch_kind, code_region);
+        }
+    }
+
     /// `inject_coverage_span_counters()` looped through the `CoverageSpan`s and injected the
     /// counter from the `CoverageSpan`s `BasicCoverageBlock`, removing it from the BCB in the
     /// process (via `take_counter()`).
@@ -501,6 +579,87 @@ fn inject_intermediate_expression(mir_body: &mut mir::Body<'_>, expression: Cove
     data.statements.push(statement);
 }
 
+// Branch regions don't count anything themselves (their operands do), so just like intermediate
+// expressions, they are injected into the coverage map without generating executable code.
+fn inject_branch_region(
+    mir_body: &mut mir::Body<'_>,
+    branch: CoverageKind,
+    code_region: CodeRegion,
+) {
+    debug_assert!(matches!(branch, CoverageKind::Branch { .. }));
+    debug!("  injecting branch {:?} at code region: {:?}", branch, code_region);
+    let data = &mut mir_body[mir::START_BLOCK];
+    let source_info = data.terminator().source_info;
+    let statement = Statement {
+        source_info,
+        kind: StatementKind::Coverage(Box::new(Coverage {
+            kind: branch,
+            code_region: Some(code_region),
+        })),
+    };
+    data.statements.push(statement);
+}
+
+/// Finds the blocks marked by the `BlockMarker` statements of each branch in `branch_info`, and
+/// maps the branches to `CoverageBranch`es. Branches with blocks that were removed or that are
+/// not part of the coverage graph, or with spans outside of the function body (e.g. from macro
+/// expansions), are skipped.
+fn extract_coverage_branches(
+    mir_body: &mir::Body<'_>,
+    branch_info: &BranchInfo,
+    basic_coverage_blocks: &CoverageGraph,
+    body_span: Span,
+) -> Vec<CoverageBranch> {
+    let mut block_markers = IndexVec::<BlockMarkerId, Option<BasicBlock>>::from_elem_n(
+        None,
+        branch_info.num_block_markers,
+    );
+    for (bb, data) in mir_body.basic_blocks().iter_enumerated() {
+        for statement in &data.statements {
+            if let StatementKind::Coverage(box Coverage {
+                kind: CoverageKind::BlockMarker { id },
+                ..
+            }) = statement.kind
+            {
+                block_markers[id] = Some(bb);
+            }
+        }
+    }
+
+    let bcb_from_marker =
+        |marker: BlockMarkerId| basic_coverage_blocks.bcb_from_bb(block_markers[marker]?);
+
+    branch_info
+        .branch_spans
+        .iter()
+        .filter_map(|&BranchSpan { span, true_marker, false_marker }| {
+            let span = original_sp(span, body_span).with_ctxt(body_span.ctxt());
+            if !body_span.contains(span) {
+                return None;
+            }
+            let true_bcb = bcb_from_marker(true_marker)?;
+            let false_bcb = bcb_from_marker(false_marker)?;
+            Some(CoverageBranch { span, true_bcb, false_bcb })
+        })
+        .collect()
+}
+
+/// Turns all `BlockMarker` statements into `Nop`s. They are only used to find the blocks on
+/// either side of a branch during instrumentation.
+fn remove_block_markers(mir_body: &mut mir::Body<'_>) {
+    for data in mir_body.basic_blocks_mut() {
+        for statement in &mut data.statements {
+            if let StatementKind::Coverage(box Coverage {
+                kind: CoverageKind::BlockMarker { .. },
+                ..
+            }) = statement.kind
+            {
+                statement.make_nop();
+            }
+        }
+    }
+}
+
 /// Convert the Span into its file name, start line and column, and end line and column
 fn make_code_region(
     source_map: &SourceMap,
@@ -11,10 +11,18 @@
 //!     number of iterations of dest prop between the first and second application of this transform
 //!     will still not cause any further changes.
 //!
+//! When run as a standalone pass, this additionally removes the `StorageLive` and `StorageDead`
+//! statements of locals which are no longer mentioned anywhere else in the body once their stores
+//! have been eliminated. This is common in the MIR produced by derive expansions and inlining,
+//! where temporaries are often initialized only to never be read.
+//!
 
 use rustc_index::bit_set::BitSet;
 use rustc_middle::{
-    mir::{visit::Visitor, *},
+    mir::{
+        visit::{NonUseContext, PlaceContext, Visitor},
+        *,
+    },
     ty::TyCtxt,
 };
 use rustc_mir_dataflow::{impls::MaybeTransitiveLiveLocals, Analysis};
@@ -134,6 +142,46 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
     }
 }
 
+/// Removes the storage statements of locals that are not mentioned anywhere else in the body.
+///
+/// This is not part of [`eliminate`], since dest prop relies on that function not touching
+/// storage statements.
+fn remove_dead_storage_markers(body: &mut Body<'_>) {
+    let mut mentioned = MentionedLocals(BitSet::new_empty(body.local_decls.len()));
+    mentioned.visit_body(body);
+    let mentioned = mentioned.0;
+
+    for data in body.basic_blocks_mut() {
+        for statement in data.statements.iter_mut() {
+            match statement.kind {
+                StatementKind::StorageLive(local) | StatementKind::StorageDead(local)
+                    if !mentioned.contains(local) =>
+                {
+                    statement.make_nop();
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Collects all locals that are mentioned in a body other than in storage statements.
+///
+/// Debuginfo counts as a mention, so that we never remove the storage of a user variable that is
+/// still visible in the debugger.
+struct MentionedLocals(BitSet<Local>);
+
+impl<'tcx> Visitor<'tcx> for MentionedLocals {
+    fn visit_local(&mut self, local: &Local, context: PlaceContext, _: Location) {
+        match context {
+            PlaceContext::NonUse(NonUseContext::StorageLive | NonUseContext::StorageDead) => {}
+            _ => {
+                self.0.insert(*local);
+            }
+        }
+    }
+}
+
 pub struct DeadStoreElimination;
 
 impl<'tcx> MirPass<'tcx> for DeadStoreElimination {
@@ -144,5 +192,6 @@ fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
     fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         let borrowed = get_borrowed_locals(body);
         eliminate(tcx, body, &borrowed);
+        remove_dead_storage_markers(body);
     }
 }
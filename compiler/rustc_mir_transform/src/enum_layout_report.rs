@@ -0,0 +1,145 @@
+//! Records the layout chosen for every enum used by a body, for `-Zenum-layout-report`.
+//!
+//! Unlike `-Zprint-type-sizes`, which dumps every layout computed during the session, this only
+//! looks at the monomorphic enum types of locals, and reports how their discriminant is encoded,
+//! how much of their niche is left, and how much padding each variant leaves. For enums whose
+//! field order is fixed by a `repr`, it also suggests a field order that would shrink the enum.
+
+use crate::MirPass;
+use rustc_middle::mir::Body;
+use rustc_middle::ty::layout::{LayoutCx, LayoutOf, TyAndLayout};
+use rustc_middle::ty::{self, AdtDef, TyCtxt, TypeFoldable};
+use rustc_session::code_stats::{
+    EnumLayoutInfo, EnumReorderHint, EnumTagEncoding, EnumVariantLayout, FieldInfo,
+};
+use rustc_target::abi::{Size, TagEncoding, Variants};
+
+pub struct EnumLayoutReport;
+
+impl<'tcx> MirPass<'tcx> for EnumLayoutReport {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.opts.debugging_opts.enum_layout_report
+    }
+
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        let cx = LayoutCx { tcx, param_env: ty::ParamEnv::reveal_all() };
+        for local_decl in body.local_decls.iter() {
+            let ty = local_decl.ty;
+            let adt_def = match *ty.kind() {
+                ty::Adt(adt_def, _) if adt_def.is_enum() => adt_def,
+                _ => continue,
+            };
+            if ty.has_param_types_or_consts() {
+                continue;
+            }
+            if let Ok(layout) = cx.layout_of(ty) {
+                tcx.sess.code_stats.record_enum_layout(enum_layout_info(&cx, adt_def, layout));
+            }
+        }
+    }
+}
+
+fn enum_layout_info<'tcx>(
+    cx: &LayoutCx<'tcx, TyCtxt<'tcx>>,
+    adt_def: AdtDef<'tcx>,
+    layout: TyAndLayout<'tcx>,
+) -> EnumLayoutInfo {
+    let (encoding, tag_size) = match layout.variants {
+        Variants::Single { .. } => (EnumTagEncoding::None, Size::ZERO),
+        Variants::Multiple { tag, tag_encoding: TagEncoding::Direct, .. } => {
+            let size = tag.size(cx);
+            (EnumTagEncoding::Direct { size: size.bytes() }, size)
+        }
+        Variants::Multiple {
+            tag,
+            tag_encoding: TagEncoding::Niche { dataful_variant, ref niche_variants, .. },
+            tag_field,
+            ..
+        } => {
+            let encoding = EnumTagEncoding::Niche {
+                untagged_variant: adt_def.variant(dataful_variant).name.to_string(),
+                niche_variants: u64::from(
+                    niche_variants.end().as_u32() - niche_variants.start().as_u32() + 1,
+                ),
+                offset: layout.fields.offset(tag_field).bytes(),
+                size: tag.size(cx).bytes(),
+            };
+            // The tag lives inside a field of the untagged variant, so it takes no extra space.
+            (encoding, Size::ZERO)
+        }
+    };
+
+    let mut variants = vec![];
+    let mut reordered_size = tag_size;
+    let mut reordered_variants = vec![];
+    for (index, variant_def) in adt_def.variants().iter_enumerated() {
+        if let Variants::Single { index: single } = layout.variants
+            && single != index
+        {
+            // Other variants of single-variant layouts are uninhabited and take no space.
+            continue;
+        }
+        let variant_layout = layout.for_variant(cx, index);
+        let fields: Vec<_> = variant_def
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, field_def)| {
+                let field_layout = variant_layout.field(cx, i);
+                FieldInfo {
+                    name: field_def.name.to_string(),
+                    offset: variant_layout.fields.offset(i).bytes(),
+                    size: field_layout.size.bytes(),
+                    align: field_layout.align.abi.bytes(),
+                }
+            })
+            .collect();
+
+        let used = tag_size.bytes() + fields.iter().map(|f| f.size).sum::<u64>();
+        if adt_def.repr().inhibit_struct_field_reordering_opt() {
+            let (end, order) = best_field_order(&fields, tag_size.bytes());
+            reordered_size = reordered_size.max(Size::from_bytes(end));
+            if order.iter().enumerate().any(|(pos, &i)| pos != i) {
+                let order = order.into_iter().map(|i| fields[i].name.clone()).collect();
+                reordered_variants.push((variant_def.name.to_string(), order));
+            }
+        }
+        variants.push(EnumVariantLayout {
+            name: variant_def.name.to_string(),
+            padding: layout.size.bytes().saturating_sub(used),
+            fields,
+        });
+    }
+
+    let reordered_size = reordered_size.align_to(layout.align.abi);
+    let reorder_hint = (adt_def.repr().inhibit_struct_field_reordering_opt()
+        && reordered_size < layout.size)
+        .then(|| EnumReorderHint { size: reordered_size.bytes(), variants: reordered_variants });
+
+    EnumLayoutInfo {
+        type_description: format!("{:?}", layout.ty),
+        size: layout.size.bytes(),
+        align: layout.align.abi.bytes(),
+        encoding,
+        available_niche_values: layout.largest_niche.map(|niche| niche.available(cx)),
+        variants,
+        reorder_hint,
+    }
+}
+
+/// Lays the fields out after `start` the way the compiler would for a `repr(Rust)` type: by
+/// increasing alignment after a prefix such as a tag, and by decreasing alignment otherwise.
+/// Returns the end of the last field and the new order, as field indices.
+fn best_field_order(fields: &[FieldInfo], start: u64) -> (u64, Vec<usize>) {
+    let mut order: Vec<_> = (0..fields.len()).collect();
+    if start > 0 {
+        order.sort_by_key(|&i| fields[i].align);
+    } else {
+        order.sort_by_key(|&i| std::cmp::Reverse(fields[i].align));
+    }
+    let end = order.iter().fold(start, |offset, &i| {
+        let align = fields[i].align.max(1);
+        (offset + align - 1) / align * align + fields[i].size
+    });
+    (end, order)
+}
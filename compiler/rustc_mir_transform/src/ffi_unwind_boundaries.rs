@@ -0,0 +1,135 @@
+//! Lints functions defined with an ABI that doesn't allow unwinding, like `extern "C"`, when
+//! they contain calls that may panic.
+//!
+//! This runs on the built MIR, before `AbortUnwindingCalls` replaces the unwind edges of such
+//! functions with aborts.
+
+use rustc_ast::InlineAsmOptions;
+use rustc_errors::Applicability;
+use rustc_hir::def::DefKind;
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, layout, TyCtxt};
+use rustc_session::lint::builtin::FFI_UNWIND_BOUNDARIES;
+use rustc_span::{InnerSpan, Span};
+use rustc_target::spec::abi::Abi;
+use rustc_target::spec::PanicStrategy;
+
+use crate::MirLint;
+
+pub struct FfiUnwindBoundaries;
+
+/// How many of the calls that may panic are pointed out.
+const REPORTED_CALLS: usize = 3;
+
+impl<'tcx> MirLint<'tcx> for FfiUnwindBoundaries {
+    fn run_lint(&self, tcx: TyCtxt<'tcx>, body: &Body<'tcx>) {
+        let def_id = body.source.def_id();
+        if body.source.promoted.is_some()
+            || body.tainted_by_errors.is_some()
+            || !matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+            // Nothing unwinds with `-C panic=abort`.
+            || tcx.sess.panic_strategy() != PanicStrategy::Unwind
+            || tcx.codegen_fn_attrs(def_id).flags.contains(CodegenFnAttrFlags::NEVER_UNWIND)
+        {
+            return;
+        }
+        let abi = tcx.fn_sig(def_id).abi();
+        let Some(unwind_abi) = unwinding_variant(abi) else { return };
+
+        // Only calls and assertions are considered, since drops hardly ever panic and
+        // `FalseUnwind` never does.
+        let panicking: Vec<Span> = body
+            .basic_blocks()
+            .iter()
+            .filter(|block| !block.is_cleanup)
+            .filter_map(|block| {
+                let terminator = block.terminator();
+                let may_panic = match &terminator.kind {
+                    TerminatorKind::Call { func, .. } => {
+                        let ty = func.ty(body, tcx);
+                        let callee = match *ty.kind() {
+                            ty::FnDef(def_id, _) => Some(def_id),
+                            _ => None,
+                        };
+                        let abi = ty.fn_sig(tcx).abi();
+                        // Calls of other functions with such an ABI aren't expected to unwind
+                        // either, even though they may without `#![feature(c_unwind)]`.
+                        unwinding_variant(abi).is_none() && layout::fn_can_unwind(tcx, callee, abi)
+                    }
+                    TerminatorKind::Assert { .. } => true,
+                    TerminatorKind::InlineAsm { options, .. } => {
+                        options.contains(InlineAsmOptions::MAY_UNWIND)
+                    }
+                    _ => false,
+                };
+                may_panic.then_some(terminator.source_info.span)
+            })
+            .collect();
+        if panicking.is_empty() {
+            return;
+        }
+
+        let local_def_id = def_id.expect_local();
+        let hir_id = tcx.hir().local_def_id_to_hir_id(local_def_id);
+        let span = tcx.def_span(def_id);
+        tcx.struct_span_lint_hir(FFI_UNWIND_BOUNDARIES, hir_id, span, |lint| {
+            let mut err = lint.build(&format!(
+                "this `extern \"{}\"` function may panic, but its ABI doesn't allow unwinding",
+                abi.name()
+            ));
+            for &span in panicking.iter().take(REPORTED_CALLS) {
+                err.span_note(span, "a panic may unwind from here");
+            }
+            if tcx.features().c_unwind {
+                err.note("a panic unwinding out of this function aborts the process");
+            } else {
+                err.note("a panic unwinding out of this function is undefined behavior");
+            }
+            // Point at the ABI string if it is spelled out in the signature.
+            let quoted = format!("\"{}\"", abi.name());
+            let abi_span = tcx.hir().fn_sig_by_hir_id(hir_id).and_then(|sig| {
+                let snippet = tcx.sess.source_map().span_to_snippet(sig.span).ok()?;
+                let start = snippet.find(&quoted)?;
+                Some(sig.span.from_inner(InnerSpan::new(start, start + quoted.len())))
+            });
+            let msg = format!(
+                "use `extern \"{}\"` if the panic should unwind into the caller",
+                unwind_abi.name()
+            );
+            match abi_span {
+                Some(abi_span) => {
+                    err.span_suggestion_verbose(
+                        abi_span,
+                        &msg,
+                        format!("\"{}\"", unwind_abi.name()),
+                        Applicability::MaybeIncorrect,
+                    );
+                }
+                None => {
+                    err.help(&msg);
+                }
+            }
+            err.help("to keep the ABI, catch the panic with `std::panic::catch_unwind`");
+            err.emit();
+        });
+    }
+}
+
+/// The variant of `abi` that allows unwinding, if `abi` is one that doesn't.
+fn unwinding_variant(abi: Abi) -> Option<Abi> {
+    let unwind = true;
+    Some(match abi {
+        Abi::C { unwind: false } => Abi::C { unwind },
+        Abi::Cdecl { unwind: false } => Abi::Cdecl { unwind },
+        Abi::Stdcall { unwind: false } => Abi::Stdcall { unwind },
+        Abi::Fastcall { unwind: false } => Abi::Fastcall { unwind },
+        Abi::Vectorcall { unwind: false } => Abi::Vectorcall { unwind },
+        Abi::Thiscall { unwind: false } => Abi::Thiscall { unwind },
+        Abi::Aapcs { unwind: false } => Abi::Aapcs { unwind },
+        Abi::Win64 { unwind: false } => Abi::Win64 { unwind },
+        Abi::SysV64 { unwind: false } => Abi::SysV64 { unwind },
+        Abi::System { unwind: false } => Abi::System { unwind },
+        _ => return None,
+    })
+}
@@ -69,6 +69,7 @@
 };
 use rustc_mir_dataflow::storage;
 use rustc_mir_dataflow::{self, Analysis};
+use rustc_session::config::MirValidationLevel;
 use rustc_target::abi::VariantIdx;
 use rustc_target::spec::PanicStrategy;
 use std::{iter, ops};
@@ -1307,7 +1308,7 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
 
         sanitize_witness(tcx, body, interior, upvars, &liveness_info.saved_locals);
 
-        if tcx.sess.opts.debugging_opts.validate_mir {
+        if tcx.sess.opts.debugging_opts.validate_mir != MirValidationLevel::Off {
             let mut vis = EnsureGeneratorFieldAssignmentsNeverAlias {
                 assigned_local: None,
                 saved_locals: &liveness_info.saved_locals,
@@ -40,6 +40,7 @@
 mod add_moves_for_packed_drops;
 mod add_retag;
 mod check_const_item_mutation;
+mod check_naked_functions;
 mod check_packed_ref;
 pub mod check_unsafety;
 // This pass is public to allow external drivers to perform MIR cleanup
@@ -57,6 +58,8 @@
 pub mod dump_mir;
 mod early_otherwise_branch;
 mod elaborate_drops;
+mod enum_layout_report;
+mod ffi_unwind_boundaries;
 mod function_item_references;
 mod generator;
 mod inline;
@@ -68,6 +71,7 @@
 mod multiple_return_terminators;
 mod normalize_array_len;
 mod nrvo;
+mod remove_bounds_checks;
 // This pass is public to allow external drivers to perform MIR cleanup
 pub mod remove_false_edges;
 mod remove_noop_landing_pads;
@@ -233,6 +237,8 @@ fn mir_const<'tcx>(
             &Lint(check_packed_ref::CheckPackedRef),
             &Lint(check_const_item_mutation::CheckConstItemMutation),
             &Lint(function_item_references::FunctionItemReferences),
+            &Lint(check_naked_functions::CheckNakedFunctions),
+            &Lint(ffi_unwind_boundaries::FfiUnwindBoundaries),
             // What we need to do constant evaluation.
             &simplify::SimplifyCfg::new("initial"),
             &rustc_peek::SanityCheck, // Just a lint
@@ -484,6 +490,7 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
             // Const-prop runs unconditionally, but doesn't mutate the MIR at mir-opt-level=0.
             &const_debuginfo::ConstDebugInfo,
             &o1(simplify_branches::SimplifyConstCondition::new("after-const-prop")),
+            &remove_bounds_checks::RemoveBoundsChecks,
             &early_otherwise_branch::EarlyOtherwiseBranch,
             &simplify_comparison_integral::SimplifyComparisonIntegral,
             &simplify_try::SimplifyArmIdentity,
@@ -497,6 +504,7 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
             &simplify::SimplifyLocals,
             &multiple_return_terminators::MultipleReturnTerminators,
             &deduplicate_blocks::DeduplicateBlocks,
+            &enum_layout_report::EnumLayoutReport,
             // Some cleanup necessary at least for LLVM and potentially other codegen backends.
             &add_call_guards::CriticalCallEdges,
             &marker::PhaseChange(MirPhase::Optimized),
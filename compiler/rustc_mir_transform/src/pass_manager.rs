@@ -2,6 +2,7 @@
 
 use rustc_middle::mir::{self, Body, MirPhase};
 use rustc_middle::ty::TyCtxt;
+use rustc_session::config::MirValidationLevel;
 use rustc_session::Session;
 
 use crate::{validate, MirPass};
@@ -80,7 +81,7 @@ pub fn run_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>, passes: &[&dyn
     let overridden_passes = &tcx.sess.opts.debugging_opts.mir_enable_passes;
     trace!(?overridden_passes);
 
-    if validate {
+    if validate >= MirValidationLevel::Basic {
         validate_body(tcx, body, format!("start of phase transition from {:?}", start_phase));
     }
 
@@ -122,18 +123,19 @@ pub fn run_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>, passes: &[&dyn
             body.phase = new_phase;
         }
 
-        if validate {
+        if validate == MirValidationLevel::Full {
             validate_body(tcx, body, format!("after pass {}", pass.name()));
         }
     }
 
-    if validate || body.phase == MirPhase::Optimized {
+    if validate >= MirValidationLevel::Basic || body.phase == MirPhase::Optimized {
         validate_body(tcx, body, format!("end of phase transition to {:?}", body.phase));
     }
 }
 
 pub fn validate_body<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>, when: String) {
-    validate::Validator { when, mir_phase: body.phase }.run_pass(tcx, body);
+    let check_definitions = tcx.sess.opts.debugging_opts.validate_mir == MirValidationLevel::Full;
+    validate::Validator { when, mir_phase: body.phase, check_definitions }.run_pass(tcx, body);
 }
 
 pub fn dump_mir<'tcx>(
@@ -0,0 +1,434 @@
+//! This pass removes bounds checks which are known to always succeed.
+//!
+//! It performs a simple, intra-procedural integer range analysis: for every `Assert` terminator
+//! that guards an indexing operation, we look for a dominating `switchInt` on a comparison like
+//! `Lt(index, len)`, whose "in bounds" edge must have been taken in order to reach the assertion.
+//! If neither the index nor the indexed place can have been modified in between, the index is
+//! known to lie in `0..len`, and the bounds check is replaced with a `goto`.
+//!
+//! This catches the common `while i < slice.len() { slice[i] }` and `if i < N { array[i] }`
+//! patterns without relying on LLVM to recover the information, which notably helps debug builds.
+//!
+//! Values are tracked through locals with a single assignment, such as the temporaries created
+//! for copies of the index or for reborrows of the indexed slice. Any local whose address is
+//! taken is treated as unknown. Writes through pointers are not tracked, so lengths are only
+//! considered if they cannot be changed by such a write, see `RangeAnalysis::has_stable_len`.
+
+use crate::dead_store_elimination::get_borrowed_locals;
+use crate::MirPass;
+use rustc_data_structures::graph::dominators::Dominators;
+use rustc_index::bit_set::BitSet;
+use rustc_index::vec::IndexVec;
+use rustc_middle::mir::visit::{PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, ParamEnv, TyCtxt};
+
+pub struct RemoveBoundsChecks;
+
+impl<'tcx> MirPass<'tcx> for RemoveBoundsChecks {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 1
+    }
+
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+        let redundant = {
+            let analysis = RangeAnalysis::new(tcx, param_env, body);
+            body.basic_blocks()
+                .indices()
+                .filter(|&bb| analysis.is_redundant_bounds_check(bb))
+                .collect::<Vec<_>>()
+        };
+
+        for bb in redundant {
+            let terminator = body.basic_blocks_mut()[bb].terminator_mut();
+            let TerminatorKind::Assert { target, .. } = terminator.kind else {
+                bug!("expected a bounds check in {:?}", bb);
+            };
+            debug!("removing bounds check in {:?}", bb);
+            terminator.kind = TerminatorKind::Goto { target };
+        }
+    }
+}
+
+/// An upper bound on the value of an index.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Bound<'tcx> {
+    /// A constant, exclusive upper bound.
+    Const(u128),
+    /// The length of a slice or array.
+    Len(Place<'tcx>),
+}
+
+/// The value of an index.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Value {
+    Const(u128),
+    Local(Local),
+}
+
+/// A value, together with the location at which it was read from its root local, if any.
+#[derive(Clone, Copy, Debug)]
+struct Resolved<T> {
+    value: T,
+    read: Option<(Local, Location)>,
+}
+
+struct RangeAnalysis<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    body: &'a Body<'tcx>,
+    dominators: Dominators<BasicBlock>,
+    borrowed: BitSet<Local>,
+    /// For each local that is assigned exactly once, the location and value of that assignment.
+    defs: IndexVec<Local, Option<(Location, &'a Rvalue<'tcx>)>>,
+}
+
+impl<'a, 'tcx> RangeAnalysis<'a, 'tcx> {
+    fn new(tcx: TyCtxt<'tcx>, param_env: ParamEnv<'tcx>, body: &'a Body<'tcx>) -> Self {
+        let mut counter = AssignmentCounter {
+            // Arguments are implicitly assigned on entry, so they never have a single definition.
+            assignments: IndexVec::from_fn_n(
+                |local: Local| if local.index() <= body.arg_count { 1 } else { 0 },
+                body.local_decls.len(),
+            ),
+        };
+        counter.visit_body(body);
+
+        let mut defs = IndexVec::from_elem(None, &body.local_decls);
+        for (block, data) in body.basic_blocks().iter_enumerated() {
+            for (statement_index, statement) in data.statements.iter().enumerate() {
+                if let StatementKind::Assign(box (place, rvalue)) = &statement.kind
+                    && let Some(local) = place.as_local()
+                    && counter.assignments[local] == 1
+                {
+                    defs[local] = Some((Location { block, statement_index }, rvalue));
+                }
+            }
+        }
+
+        RangeAnalysis {
+            tcx,
+            param_env,
+            body,
+            dominators: body.dominators(),
+            borrowed: get_borrowed_locals(body),
+            defs,
+        }
+    }
+
+    fn def(&self, local: Local) -> Option<(Location, &'a Rvalue<'tcx>)> {
+        if self.borrowed.contains(local) {
+            None
+        } else {
+            self.defs[local]
+        }
+    }
+
+    /// Returns `true` if `bb` ends in a bounds check which is known to always succeed.
+    fn is_redundant_bounds_check(&self, bb: BasicBlock) -> bool {
+        let terminator = self.body[bb].terminator();
+        let TerminatorKind::Assert {
+            cond: Operand::Copy(cond) | Operand::Move(cond),
+            expected: true,
+            msg: AssertKind::BoundsCheck { .. },
+            ..
+        } = &terminator.kind
+        else {
+            return false;
+        };
+        if !self.dominators.is_reachable(bb) {
+            return false;
+        }
+        let check_loc = self.body.terminator_loc(bb);
+
+        // The condition of the assertion, along with everything it is computed from, must be
+        // computed in this block.
+        let Some(cond) = cond.as_local() else { return false };
+        let Some((cond_loc, Rvalue::BinaryOp(BinOp::Lt, box (index, len)))) = self.def(cond) else {
+            return false;
+        };
+        if cond_loc.block != bb {
+            return false;
+        }
+        let (Some(index), Some(len)) =
+            (self.resolve_value(index, cond_loc), self.resolve_bound(len, cond_loc))
+        else {
+            return false;
+        };
+        if [index.read, len.read].into_iter().flatten().any(|(_, loc)| loc.block != bb) {
+            return false;
+        }
+
+        // Constant indices are in range if they are below a statically known length.
+        if let Value::Const(index) = index.value {
+            return self.bound_value(len.value).map_or(false, |len| index < len);
+        }
+
+        self.dominators
+            .dominators(bb)
+            .any(|dom| self.dominating_check_implies(dom, check_loc, index, len))
+    }
+
+    /// Returns `true` if `dom` ends in a comparison between an index and a bound which, when the
+    /// edge towards `check_loc` is taken, implies that `index < len` holds at `check_loc`.
+    fn dominating_check_implies(
+        &self,
+        dom: BasicBlock,
+        check_loc: Location,
+        index: Resolved<Value>,
+        len: Resolved<Bound<'tcx>>,
+    ) -> bool {
+        let TerminatorKind::SwitchInt {
+            discr: Operand::Copy(discr) | Operand::Move(discr),
+            targets,
+            ..
+        } = &self.body[dom].terminator().kind
+        else {
+            return false;
+        };
+
+        // Find the edge which must be taken in order to reach the bounds check. That edge must
+        // lead to a block which has no other predecessors, so that reaching it implies that the
+        // comparison was performed right before.
+        let Some(succ) =
+            targets.all_targets().iter().copied().find(|&succ| {
+                succ != dom && self.dominators.is_dominated_by(check_loc.block, succ)
+            })
+        else {
+            return false;
+        };
+        if self.body.predecessors()[succ].len() != 1 {
+            return false;
+        }
+        let taken = if let Some((value, _)) = targets.iter().find(|&(_, target)| target == succ) {
+            value != 0
+        } else if targets.iter().all(|(value, _)| value == 0) {
+            true
+        } else {
+            return false;
+        };
+
+        let Some(discr) = discr.as_local() else { return false };
+        let Some((cmp_loc, Rvalue::BinaryOp(op, box (lhs, rhs)))) = self.def(discr) else {
+            return false;
+        };
+        if cmp_loc.block != dom {
+            return false;
+        }
+        let (less, greater) = match (op, taken) {
+            (BinOp::Lt, true) | (BinOp::Ge, false) => (lhs, rhs),
+            (BinOp::Gt, true) | (BinOp::Le, false) => (rhs, lhs),
+            _ => return false,
+        };
+        let (Some(check_index), Some(check_len)) =
+            (self.resolve_value(less, cmp_loc), self.resolve_bound(greater, cmp_loc))
+        else {
+            return false;
+        };
+        if check_index.value != index.value || !self.bound_implies(check_len.value, len.value) {
+            return false;
+        }
+
+        // All values read before the comparison must still be the same at the comparison, and
+        // all values read after it must be the same as at the comparison.
+        [check_index.read, check_len.read, index.read, len.read].into_iter().flatten().all(
+            |(local, read)| {
+                if read.block == check_loc.block {
+                    self.is_unmodified_between(local, cmp_loc, check_loc)
+                } else {
+                    self.is_unmodified_between(local, read, cmp_loc)
+                }
+            },
+        )
+    }
+
+    /// Returns `true` if `check < len` implies `check < bound`.
+    fn bound_implies(&self, check: Bound<'tcx>, bound: Bound<'tcx>) -> bool {
+        match (check, bound) {
+            (Bound::Len(check), Bound::Len(bound)) if check == bound => true,
+            _ => match (self.bound_value(check), self.bound_value(bound)) {
+                (Some(check), Some(bound)) => check <= bound,
+                _ => false,
+            },
+        }
+    }
+
+    /// Returns the statically known value of a bound, if any.
+    fn bound_value(&self, bound: Bound<'tcx>) -> Option<u128> {
+        match bound {
+            Bound::Const(value) => Some(value),
+            Bound::Len(place) => match place.ty(self.body, self.tcx).ty.kind() {
+                ty::Array(_, len) => len.try_eval_usize(self.tcx, self.param_env).map(u128::from),
+                _ => None,
+            },
+        }
+    }
+
+    /// Resolves an operand used at `loc` to a constant or to the local it was copied from.
+    fn resolve_value(&self, operand: &Operand<'tcx>, loc: Location) -> Option<Resolved<Value>> {
+        match operand {
+            Operand::Constant(constant) => {
+                let value =
+                    constant.literal.try_eval_bits(self.tcx, self.param_env, constant.ty())?;
+                Some(Resolved { value: Value::Const(value), read: None })
+            }
+            Operand::Copy(place) | Operand::Move(place) => {
+                let local = place.as_local()?;
+                if self.borrowed.contains(local) {
+                    return None;
+                }
+                match self.def(local) {
+                    Some((def_loc, Rvalue::Use(operand))) => self.resolve_value(operand, def_loc),
+                    _ => Some(Resolved { value: Value::Local(local), read: Some((local, loc)) }),
+                }
+            }
+        }
+    }
+
+    /// Resolves an operand used at `loc` to a constant or to the length of a place.
+    fn resolve_bound(
+        &self,
+        operand: &Operand<'tcx>,
+        loc: Location,
+    ) -> Option<Resolved<Bound<'tcx>>> {
+        match operand {
+            Operand::Constant(constant) => {
+                let value =
+                    constant.literal.try_eval_bits(self.tcx, self.param_env, constant.ty())?;
+                Some(Resolved { value: Bound::Const(value), read: None })
+            }
+            Operand::Copy(place) | Operand::Move(place) => {
+                let local = place.as_local()?;
+                match self.def(local)? {
+                    (def_loc, Rvalue::Use(operand)) => self.resolve_bound(operand, def_loc),
+                    (def_loc, Rvalue::Len(place)) => {
+                        let (place, read) = self.resolve_place(*place, def_loc)?;
+                        if !self.has_stable_len(place) {
+                            return None;
+                        }
+                        Some(Resolved { value: Bound::Len(place), read: Some((place.local, read)) })
+                    }
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// Follows copies and reborrows of the base local of a place used at `loc`, returning the
+    /// resulting place and the location at which its base local was read.
+    fn resolve_place(&self, place: Place<'tcx>, loc: Location) -> Option<(Place<'tcx>, Location)> {
+        if self.borrowed.contains(place.local)
+            || place
+                .projection
+                .iter()
+                .any(|elem| !matches!(elem, ProjectionElem::Deref | ProjectionElem::Field(..)))
+        {
+            return None;
+        }
+        match self.def(place.local) {
+            Some((def_loc, Rvalue::Use(Operand::Copy(base) | Operand::Move(base)))) => {
+                self.resolve_place(base.project_deeper(&place.projection, self.tcx), def_loc)
+            }
+            Some((def_loc, Rvalue::Ref(_, _, base)))
+                if place.projection.first() == Some(&ProjectionElem::Deref) =>
+            {
+                self.resolve_place(base.project_deeper(&place.projection[1..], self.tcx), def_loc)
+            }
+            _ => Some((place, loc)),
+        }
+    }
+
+    /// Returns `true` if the length of `place` can only change through a direct modification of
+    /// its base local. The length of a slice is the metadata of the last pointer the place goes
+    /// through, and that of an array is static, so this holds if every other pointer the place
+    /// goes through is a shared reference. With `s: &mut &[T]` for example, the length of `**s`
+    /// changes with `*s = &[]`, which does not modify `s` itself.
+    fn has_stable_len(&self, place: Place<'tcx>) -> bool {
+        let last_deref = place.projection.iter().rposition(|elem| elem == ProjectionElem::Deref);
+        place.iter_projections().enumerate().all(|(i, (base, elem))| {
+            elem != ProjectionElem::Deref
+                || Some(i) == last_deref
+                || matches!(base.ty(self.body, self.tcx).ty.kind(), ty::Ref(_, _, Mutability::Not))
+        })
+    }
+
+    /// Returns `true` if `local` cannot be modified on any path from `from` to `to`, where `from`
+    /// must dominate `to`.
+    fn is_unmodified_between(&self, local: Local, from: Location, to: Location) -> bool {
+        if !self.dominators.is_dominated_by(to.block, from.block) {
+            return false;
+        }
+        let modifies = |bb: BasicBlock, statements: std::ops::Range<usize>, terminator: bool| {
+            let mut finder = ModificationFinder { local, found: false };
+            let data = &self.body[bb];
+            for statement_index in statements {
+                finder.visit_statement(
+                    &data.statements[statement_index],
+                    Location { block: bb, statement_index },
+                );
+            }
+            if terminator {
+                finder.visit_terminator(data.terminator(), self.body.terminator_loc(bb));
+            }
+            finder.found
+        };
+
+        if from.block == to.block && from.statement_index <= to.statement_index {
+            return !modifies(from.block, from.statement_index + 1..to.statement_index, false);
+        }
+
+        let from_len = self.body[from.block].statements.len();
+        if modifies(from.block, from.statement_index + 1..from_len, true)
+            || modifies(to.block, 0..to.statement_index, false)
+        {
+            return false;
+        }
+
+        // Every block on a path from `from` to `to` that does not pass through `from` again.
+        let mut visited = BitSet::new_empty(self.body.basic_blocks().len());
+        let mut stack = vec![to.block];
+        while let Some(bb) = stack.pop() {
+            for &pred in &self.body.predecessors()[bb] {
+                if pred == from.block || !visited.insert(pred) {
+                    continue;
+                }
+                let len = self.body[pred].statements.len();
+                if modifies(pred, 0..len, true) {
+                    return false;
+                }
+                stack.push(pred);
+            }
+        }
+        true
+    }
+}
+
+/// Counts the modifications of every local.
+struct AssignmentCounter {
+    assignments: IndexVec<Local, usize>,
+}
+
+impl<'tcx> Visitor<'tcx> for AssignmentCounter {
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location: Location) {
+        if context.is_mutating_use() && !place.is_indirect() {
+            self.assignments[place.local] += 1;
+        }
+        self.super_place(place, context, location);
+    }
+}
+
+/// Checks whether any statement or terminator modifies a local.
+struct ModificationFinder {
+    local: Local,
+    found: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for ModificationFinder {
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location: Location) {
+        if place.local == self.local && context.is_mutating_use() && !place.is_indirect() {
+            self.found = true;
+        }
+        self.super_place(place, context, location);
+    }
+}
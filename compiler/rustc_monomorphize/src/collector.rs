@@ -343,17 +343,20 @@ pub fn collect_crate_mono_items(
         let inlining_map: MTRef<'_, _> = &mut inlining_map;
 
         tcx.sess.time("monomorphization_collector_graph_walk", || {
-            par_iter(roots).for_each(|root| {
-                let mut recursion_depths = DefIdMap::default();
-                collect_items_rec(
-                    tcx,
-                    dummy_spanned(root),
-                    visited,
-                    &mut recursion_depths,
-                    recursion_limit,
-                    inlining_map,
-                );
-            });
+            // The same post-monomorphization error is usually hit by several instantiations.
+            tcx.sess.diagnostic().deduplicating_instantiation_errors(|| {
+                par_iter(roots).for_each(|root| {
+                    let mut recursion_depths = DefIdMap::default();
+                    collect_items_rec(
+                        tcx,
+                        dummy_spanned(root),
+                        visited,
+                        &mut recursion_depths,
+                        recursion_limit,
+                        inlining_map,
+                    );
+                });
+            })
         });
     }
 
@@ -524,9 +527,9 @@ fn collect_items_rec<'tcx>(
         && starting_point.node.is_user_defined()
     {
         let formatted_item = with_no_trimmed_paths!(starting_point.node.to_string());
-        tcx.sess.span_note_without_error(
+        tcx.sess.diagnostic().span_note_error_origin(
             starting_point.span,
-            &format!("the above error was encountered while instantiating `{}`", formatted_item),
+            format!("the above error was encountered while instantiating `{}`", formatted_item),
         );
     }
     inlining_map.lock_mut().record_accesses(starting_point.node, &neighbors.items);
@@ -0,0 +1,155 @@
+//! A partitioning strategy that tries to reduce the number of duplicated
+//! monomorphizations.
+//!
+//! The default strategy merges the two smallest codegen units until the
+//! target number of codegen units is reached, without looking at what ends up
+//! being inlined into them. Heavily used generic instances (and `#[inline]`
+//! functions) therefore get copied into most of the final codegen units.
+//!
+//! This strategy places root items exactly like the default one, but when
+//! merging it looks at the items that will later get inlined into each
+//! codegen unit. The smallest codegen unit is merged into the codegen unit
+//! it shares the largest amount of inlined code with (weighted by the
+//! estimated size of the shared instances), as long as that doesn't produce a
+//! codegen unit that is much larger than the average one.
+
+use std::cmp;
+
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_middle::mir::mono::{CodegenUnit, MonoItem};
+use rustc_span::symbol::Symbol;
+
+use super::PartitioningCx;
+use crate::partitioning::default::{follow_inlining, DefaultPartitioning};
+use crate::partitioning::merging;
+use crate::partitioning::{Partitioner, PostInliningPartitioning, PreInliningPartitioning};
+
+pub struct AffinityPartitioning;
+
+impl<'tcx> Partitioner<'tcx> for AffinityPartitioning {
+    fn place_root_mono_items(
+        &mut self,
+        cx: &PartitioningCx<'_, 'tcx>,
+        mono_items: &mut dyn Iterator<Item = MonoItem<'tcx>>,
+    ) -> PreInliningPartitioning<'tcx> {
+        DefaultPartitioning.place_root_mono_items(cx, mono_items)
+    }
+
+    fn merge_codegen_units(
+        &mut self,
+        cx: &PartitioningCx<'_, 'tcx>,
+        initial_partitioning: &mut PreInliningPartitioning<'tcx>,
+    ) {
+        merge_codegen_units_by_affinity(cx, initial_partitioning);
+    }
+
+    fn place_inlined_mono_items(
+        &mut self,
+        cx: &PartitioningCx<'_, 'tcx>,
+        initial_partitioning: PreInliningPartitioning<'tcx>,
+    ) -> PostInliningPartitioning<'tcx> {
+        DefaultPartitioning.place_inlined_mono_items(cx, initial_partitioning)
+    }
+
+    fn internalize_symbols(
+        &mut self,
+        cx: &PartitioningCx<'_, 'tcx>,
+        partitioning: &mut PostInliningPartitioning<'tcx>,
+    ) {
+        DefaultPartitioning.internalize_symbols(cx, partitioning)
+    }
+}
+
+fn merge_codegen_units_by_affinity<'tcx>(
+    cx: &PartitioningCx<'_, 'tcx>,
+    initial_partitioning: &mut PreInliningPartitioning<'tcx>,
+) {
+    assert!(cx.target_cgu_count >= 1);
+    let codegen_units = &mut initial_partitioning.codegen_units;
+
+    // Start off with a deterministic order. All the choices below break ties
+    // by position, so this keeps the result deterministic as well.
+    codegen_units.sort_by(|a, b| a.name().as_str().partial_cmp(b.name().as_str()).unwrap());
+
+    // This map keeps track of what got merged into what.
+    let mut cgu_contents: FxHashMap<Symbol, Vec<Symbol>> =
+        codegen_units.iter().map(|cgu| (cgu.name(), vec![cgu.name()])).collect();
+
+    if codegen_units.len() <= cx.target_cgu_count {
+        merging::assign_merged_codegen_unit_names(cx, codegen_units, cgu_contents);
+        return;
+    }
+
+    let mut item_sizes: FxHashMap<MonoItem<'tcx>, usize> = FxHashMap::default();
+
+    // For every codegen unit, the set of items that will be inlined into it
+    // later on, i.e. the items that get duplicated if they are needed by more
+    // than one codegen unit.
+    let mut inlined_items: Vec<FxHashSet<MonoItem<'tcx>>> = codegen_units
+        .iter()
+        .map(|cgu| {
+            let mut reachable = FxHashSet::default();
+            for root in cgu.items().keys() {
+                follow_inlining(*root, cx.inlining_map, &mut reachable);
+            }
+            reachable.retain(|item| !cgu.items().contains_key(item));
+            for item in &reachable {
+                item_sizes.entry(*item).or_insert_with(|| item.size_estimate(cx.tcx));
+            }
+            reachable
+        })
+        .collect();
+
+    // Don't let a single codegen unit grow far beyond the size it would have
+    // if everything was distributed evenly, otherwise we would lose most of
+    // the parallelism.
+    let total_size: usize = codegen_units.iter().map(|cgu| cgu.size_estimate()).sum();
+    let size_limit = cmp::max(2 * total_size / cx.target_cgu_count, 1);
+
+    while codegen_units.len() > cx.target_cgu_count {
+        let smallest =
+            (0..codegen_units.len()).min_by_key(|&i| codegen_units[i].size_estimate()).unwrap();
+        let smallest_size = codegen_units[smallest].size_estimate();
+
+        let affinity = |other: usize| -> usize {
+            let (a, b) = (&inlined_items[smallest], &inlined_items[other]);
+            let (a, b) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+            a.iter().filter(|item| b.contains(item)).map(|item| item_sizes[item]).sum()
+        };
+
+        let candidates = || (0..codegen_units.len()).filter(|&i| i != smallest);
+        let within_limit =
+            |&i: &usize| smallest_size + codegen_units[i].size_estimate() <= size_limit;
+
+        // Prefer the codegen unit with the highest affinity, then the
+        // smallest one, then the first one.
+        let pick = |i: usize| {
+            (affinity(i), cmp::Reverse(codegen_units[i].size_estimate()), cmp::Reverse(i))
+        };
+        let target = candidates()
+            .filter(within_limit)
+            .max_by_key(|&i| pick(i))
+            .or_else(|| candidates().max_by_key(|&i| pick(i)))
+            .unwrap();
+
+        let mut consumed = codegen_units.remove(smallest);
+        let consumed_inlined_items = inlined_items.remove(smallest);
+        let target = if target > smallest { target - 1 } else { target };
+
+        let target_cgu = &mut codegen_units[target];
+        target_cgu.modify_size_estimate(consumed.size_estimate());
+        for (k, v) in consumed.items_mut().drain() {
+            target_cgu.items_mut().insert(k, v);
+        }
+        inlined_items[target].extend(consumed_inlined_items);
+
+        // Record that `target_cgu` now contains all the stuff that was in
+        // `consumed` before.
+        let mut consumed_cgu_names = cgu_contents.remove(&consumed.name()).unwrap();
+        cgu_contents.get_mut(&target_cgu.name()).unwrap().append(&mut consumed_cgu_names);
+
+        debug!("CodegenUnit {} merged into CodegenUnit {}", consumed.name(), target_cgu.name());
+    }
+
+    merging::assign_merged_codegen_unit_names(cx, codegen_units, cgu_contents);
+}
@@ -177,24 +177,10 @@ fn place_inlined_mono_items(
             new_partitioning.push(new_codegen_unit);
         }
 
-        return PostInliningPartitioning {
+        PostInliningPartitioning {
             codegen_units: new_partitioning,
             mono_item_placements,
             internalization_candidates,
-        };
-
-        fn follow_inlining<'tcx>(
-            mono_item: MonoItem<'tcx>,
-            inlining_map: &InliningMap<'tcx>,
-            visited: &mut FxHashSet<MonoItem<'tcx>>,
-        ) {
-            if !visited.insert(mono_item) {
-                return;
-            }
-
-            inlining_map.with_inlining_candidates(mono_item, |target| {
-                follow_inlining(target, inlining_map, visited);
-            });
         }
     }
 
@@ -263,6 +249,22 @@ fn internalize_symbols(
     }
 }
 
+/// Collects `mono_item` and everything that transitively gets inlined into it
+/// into `visited`.
+pub(super) fn follow_inlining<'tcx>(
+    mono_item: MonoItem<'tcx>,
+    inlining_map: &InliningMap<'tcx>,
+    visited: &mut FxHashSet<MonoItem<'tcx>>,
+) {
+    if !visited.insert(mono_item) {
+        return;
+    }
+
+    inlining_map.with_inlining_candidates(mono_item, |target| {
+        follow_inlining(target, inlining_map, visited);
+    });
+}
+
 fn characteristic_def_id_of_mono_item<'tcx>(
     tcx: TyCtxt<'tcx>,
     mono_item: MonoItem<'tcx>,
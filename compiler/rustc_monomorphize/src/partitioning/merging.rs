@@ -55,6 +55,17 @@ pub fn merge_codegen_units<'tcx>(
         );
     }
 
+    assign_merged_codegen_unit_names(cx, codegen_units, cgu_contents);
+}
+
+/// Renames the codegen units after merging. `cgu_contents` maps the name of
+/// every remaining codegen unit to the names of all the initial codegen units
+/// that got merged into it.
+pub fn assign_merged_codegen_unit_names<'tcx>(
+    cx: &PartitioningCx<'_, 'tcx>,
+    codegen_units: &mut [CodegenUnit<'tcx>],
+    cgu_contents: FxHashMap<Symbol, Vec<Symbol>>,
+) {
     let cgu_name_builder = &mut CodegenUnitNameBuilder::new(cx.tcx);
 
     if cx.tcx.sess.opts.incremental.is_some() {
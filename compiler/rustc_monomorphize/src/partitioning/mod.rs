@@ -92,12 +92,13 @@
 //! source-level module, functions from the same module will be available for
 //! inlining, even when they are not marked `#[inline]`.
 
+mod affinity;
 mod default;
 mod merging;
 
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync;
-use rustc_hir::def_id::DefIdSet;
+use rustc_hir::def_id::{DefIdSet, LOCAL_CRATE};
 use rustc_middle::mir::mono::MonoItem;
 use rustc_middle::mir::mono::{CodegenUnit, Linkage};
 use rustc_middle::ty::print::with_no_trimmed_paths;
@@ -108,6 +109,10 @@
 use crate::collector::InliningMap;
 use crate::collector::{self, MonoItemCollectionMode};
 
+/// How many of the largest items of each codegen unit are recorded for
+/// self-profiling and `-Zprint-cgu-stats`.
+const LARGEST_ITEMS_PER_CGU: usize = 10;
+
 pub struct PartitioningCx<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
     target_cgu_count: usize,
@@ -148,6 +153,7 @@ fn get_partitioner<'tcx>(tcx: TyCtxt<'tcx>) -> Box<dyn Partitioner<'tcx>> {
 
     match strategy {
         "default" => Box::new(default::DefaultPartitioning),
+        "affinity" => Box::new(affinity::AffinityPartitioning),
         _ => tcx.sess.fatal("unknown partitioning strategy"),
     }
 }
@@ -194,6 +200,10 @@ pub fn partition<'tcx>(
 
     debug_dump(tcx, "POST INLINING:", post_inlining.codegen_units.iter());
 
+    if tcx.prof.enabled() {
+        record_duplication_stats(tcx, &post_inlining.codegen_units);
+    }
+
     // Next we try to make as many symbols "internal" as possible, so LLVM has
     // more freedom to optimize.
     if !tcx.sess.link_dead_code() {
@@ -304,6 +314,46 @@ fn debug_dump<'a, 'tcx, I>(tcx: TyCtxt<'tcx>, label: &str, cgus: I)
     debug!("{}", dump());
 }
 
+/// Records how many mono items ended up being copied into more than one
+/// codegen unit, and how large these copies are estimated to be in total.
+fn record_duplication_stats<'tcx>(tcx: TyCtxt<'tcx>, codegen_units: &[CodegenUnit<'tcx>]) {
+    let mut copies: FxHashMap<MonoItem<'tcx>, u64> = FxHashMap::default();
+    for cgu in codegen_units {
+        for mono_item in cgu.items().keys() {
+            *copies.entry(*mono_item).or_insert(0) += 1;
+        }
+    }
+
+    let mut duplicated_items = 0;
+    let mut duplicated_copies = 0;
+    let mut duplicated_size = 0;
+    for (mono_item, count) in copies {
+        if count > 1 {
+            duplicated_items += 1;
+            duplicated_copies += count - 1;
+            duplicated_size += (count - 1) * mono_item.size_estimate(tcx) as u64;
+        }
+    }
+
+    debug!(
+        "{} mono items duplicated across codegen units ({} extra copies, estimated size {})",
+        duplicated_items, duplicated_copies, duplicated_size
+    );
+
+    let crate_name = tcx.crate_name(LOCAL_CRATE);
+    tcx.prof.artifact_size("cgu_duplicated_mono_items", crate_name.as_str(), duplicated_items);
+    tcx.prof.artifact_size(
+        "cgu_duplicated_mono_item_copies",
+        crate_name.as_str(),
+        duplicated_copies,
+    );
+    tcx.prof.artifact_size(
+        "cgu_duplicated_mono_items_size_estimate",
+        crate_name.as_str(),
+        duplicated_size,
+    );
+}
+
 #[inline(never)] // give this a place in the profiler
 fn assert_symbols_are_distinct<'a, 'tcx, I>(tcx: TyCtxt<'tcx>, mono_items: I)
 where
@@ -394,13 +444,36 @@ fn collect_and_partition_mono_items<'tcx>(
     });
 
     if tcx.prof.enabled() {
-        // Record CGU size estimates for self-profiling.
+        // Record CGU size estimates for self-profiling, together with the largest
+        // instances of each CGU, which tell which instantiations make it so big.
         for cgu in codegen_units {
             tcx.prof.artifact_size(
                 "codegen_unit_size_estimate",
                 cgu.name().as_str(),
                 cgu.size_estimate() as u64,
             );
+            for (mono_item, size) in cgu.largest_items(tcx, LARGEST_ITEMS_PER_CGU) {
+                tcx.prof.artifact_size(
+                    "codegen_unit_item_size_estimate",
+                    with_no_trimmed_paths!(format!("{} {}", cgu.name(), mono_item)),
+                    size as u64,
+                );
+            }
+        }
+    }
+
+    if tcx.sess.cgu_stats_tracker.enabled() {
+        for cgu in codegen_units {
+            let largest_items = cgu
+                .largest_items(tcx, LARGEST_ITEMS_PER_CGU)
+                .into_iter()
+                .map(|(mono_item, size)| (with_no_trimmed_paths!(mono_item.to_string()), size))
+                .collect();
+            tcx.sess.cgu_stats_tracker.set_size_estimate(
+                cgu.name().as_str(),
+                cgu.size_estimate(),
+                largest_items,
+            );
         }
     }
 
@@ -5,7 +5,11 @@
 //! generic parameters are unused (and eventually, in what ways generic parameters are used - only
 //! for their size, offset of a field, etc.).
 
-use rustc_hir::{def::DefKind, def_id::DefId, ConstContext};
+use rustc_hir::{
+    def::DefKind,
+    def_id::{DefId, LocalDefId},
+    ConstContext,
+};
 use rustc_index::bit_set::FiniteBitSet;
 use rustc_middle::mir::{
     visit::{TyContext, Visitor},
@@ -18,6 +22,8 @@
     subst::SubstsRef,
     Const, Ty, TyCtxt,
 };
+use rustc_session::lint::builtin::UNUSED_GENERIC_PARAMETERS;
+use rustc_session::lint::Level;
 use rustc_span::symbol::sym;
 use std::convert::TryInto;
 use std::ops::ControlFlow;
@@ -25,6 +31,7 @@
 /// Provide implementations of queries relating to polymorphization analysis.
 pub fn provide(providers: &mut Providers) {
     providers.unused_generic_params = unused_generic_params;
+    providers.check_mod_unused_generic_params = check_mod_unused_generic_params;
 }
 
 /// Determine which generic parameters are used by the instance.
@@ -41,6 +48,24 @@ fn unused_generic_params<'tcx>(
         return FiniteBitSet::new_empty();
     }
 
+    let unused_parameters = compute_unused_generic_params(tcx, instance);
+
+    // Emit errors for debugging and testing if enabled.
+    if !unused_parameters.is_empty() {
+        let def_id = instance.def_id();
+        emit_unused_generic_params_error(tcx, def_id, tcx.generics_of(def_id), &unused_parameters);
+    }
+
+    unused_parameters
+}
+
+/// Determine which generic parameters are used by the instance, regardless of whether
+/// polymorphization is enabled. Closures, generators and constants in the body of the instance are
+/// assumed to use all the parameters they are given unless polymorphization is enabled.
+fn compute_unused_generic_params<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: ty::InstanceDef<'tcx>,
+) -> FiniteBitSet<u32> {
     let def_id = instance.def_id();
     // Exit early if this instance should not be polymorphized.
     if !should_polymorphize(tcx, def_id, instance) {
@@ -76,14 +101,88 @@ fn unused_generic_params<'tcx>(
     vis.visit_body(body);
     debug!(?unused_parameters, "(end)");
 
-    // Emit errors for debugging and testing if enabled.
-    if !unused_parameters.is_empty() {
-        emit_unused_generic_params_error(tcx, def_id, generics, &unused_parameters);
-    }
-
     unused_parameters
 }
 
+/// Lints the functions and inherent methods of a module with type or const parameters that are
+/// used neither by their signature, nor by their where clauses, nor by their body.
+fn check_mod_unused_generic_params(tcx: TyCtxt<'_>, module_def_id: LocalDefId) {
+    let module_items = tcx.hir_module_items(module_def_id);
+    let items = module_items.items().map(|id| id.def_id);
+    let impl_items = module_items.impl_items().map(|id| id.def_id);
+    for def_id in items.chain(impl_items) {
+        match tcx.def_kind(def_id) {
+            DefKind::Fn => {}
+            // The parameters of methods of trait impls have to match the ones of the trait.
+            DefKind::AssocFn
+                if tcx
+                    .impl_of_method(def_id.to_def_id())
+                    .map_or(false, |impl_def_id| tcx.impl_trait_ref(impl_def_id).is_none()) => {}
+            _ => continue,
+        }
+        let hir_id = tcx.hir().local_def_id_to_hir_id(def_id);
+        // Don't compute the optimized MIR of every function when the lint is allowed, as it is by
+        // default.
+        if tcx.lint_level_at_node(UNUSED_GENERIC_PARAMETERS, hir_id).0 == Level::Allow {
+            continue;
+        }
+        let generics = tcx.generics_of(def_id);
+        let has_own_params = generics.params.iter().any(|param| {
+            !matches!(param.kind, ty::GenericParamDefKind::Lifetime)
+                && !tcx.def_span(param.def_id).from_expansion()
+        });
+        if !has_own_params {
+            continue;
+        }
+        let instance = ty::InstanceDef::Item(ty::WithOptConstParam::unknown(def_id.to_def_id()));
+        if !should_polymorphize(tcx, def_id.to_def_id(), instance) {
+            continue;
+        }
+
+        let mut unused_parameters = compute_unused_generic_params(tcx, instance);
+        let mut vis = MarkUsedGenericParams {
+            tcx,
+            def_id: def_id.to_def_id(),
+            unused_parameters: &mut unused_parameters,
+        };
+        tcx.fn_sig(def_id).visit_with(&mut vis);
+        for (predicate, _) in tcx.predicates_of(def_id).predicates {
+            predicate.visit_with(&mut vis);
+        }
+
+        let unused: Vec<_> = generics
+            .params
+            .iter()
+            .filter(|param| unused_parameters.contains(param.index).unwrap_or(false))
+            .filter(|param| !tcx.def_span(param.def_id).from_expansion())
+            .collect();
+        if unused.is_empty() {
+            continue;
+        }
+        let spans: Vec<_> = unused.iter().map(|param| tcx.def_span(param.def_id)).collect();
+        tcx.struct_span_lint_hir(UNUSED_GENERIC_PARAMETERS, hir_id, spans, |lint| {
+            let names: Vec<_> = unused.iter().map(|param| format!("`{}`", param.name)).collect();
+            let (names, plural) = match &names[..] {
+                [name] => (name.clone(), false),
+                [names @ .., last] => (format!("{} and {}", names.join(", "), last), true),
+                [] => unreachable!(),
+            };
+            let mut err = lint.build(&format!(
+                "generic parameter{} {} {} never used",
+                if plural { "s" } else { "" },
+                names,
+                if plural { "are" } else { "is" },
+            ));
+            err.help(&format!(
+                "consider removing {}, or using {} in the signature or body of the function",
+                names,
+                if plural { "them" } else { "it" },
+            ));
+            err.emit();
+        });
+    }
+}
+
 /// Returns `true` if the instance should be polymorphized.
 fn should_polymorphize<'tcx>(
     tcx: TyCtxt<'tcx>,
@@ -123,6 +123,8 @@ fn check_attributes(
                 | sym::rustc_if_this_changed
                 | sym::rustc_then_this_would_need => self.check_rustc_dirty_clean(&attr),
                 sym::cmse_nonsecure_entry => self.check_cmse_nonsecure_entry(attr, span, target),
+                sym::frame_pointer => self.check_frame_pointer(attr, span, target),
+                sym::ctor_priority => self.check_ctor_priority(attr, span, target),
                 sym::const_trait => self.check_const_trait(attr, span, target),
                 sym::must_not_suspend => self.check_must_not_suspend(&attr, span, target),
                 sym::must_use => self.check_must_use(hir_id, &attr, span, target),
@@ -199,7 +201,10 @@ fn check_attributes(
         }
 
         // FIXME(@lcnr): this doesn't belong here.
-        if matches!(target, Target::Closure | Target::Fn | Target::Method(_) | Target::ForeignFn) {
+        if matches!(
+            target,
+            Target::Closure | Target::Fn | Target::Method(_) | Target::ForeignFn | Target::Static
+        ) {
             self.tcx.ensure().codegen_fn_attrs(self.tcx.hir().local_def_id(hir_id));
         }
 
@@ -420,6 +425,41 @@ fn check_cmse_nonsecure_entry(&self, attr: &Attribute, span: Span, target: Targe
         }
     }
 
+    /// Checks if `#[frame_pointer]` is applied to a function definition or a closure.
+    fn check_frame_pointer(&self, attr: &Attribute, span: Span, target: Target) -> bool {
+        match target {
+            Target::Fn
+            | Target::Closure
+            | Target::Method(MethodKind::Trait { body: true } | MethodKind::Inherent) => true,
+            _ => {
+                self.tcx
+                    .sess
+                    .struct_span_err(
+                        attr.span,
+                        "attribute should be applied to a function definition or closure",
+                    )
+                    .span_label(span, "not a function definition or closure")
+                    .emit();
+                false
+            }
+        }
+    }
+
+    /// Checks if a `#[ctor_priority]` is applied to a static. Returns `true` if valid.
+    fn check_ctor_priority(&self, attr: &Attribute, span: Span, target: Target) -> bool {
+        match target {
+            Target::Static => true,
+            _ => {
+                self.tcx
+                    .sess
+                    .struct_span_err(attr.span, "attribute should be applied to a static")
+                    .span_label(span, "not a static")
+                    .emit();
+                false
+            }
+        }
+    }
+
     /// Checks if a `#[track_caller]` is applied to a non-naked function. Returns `true` if valid.
     fn check_track_caller(
         &self,
@@ -1766,7 +1806,7 @@ fn check_repr(
             .flatten()
             .collect();
 
-        let mut int_reprs = 0;
+        let mut int_reprs = Vec::new();
         let mut is_c = false;
         let mut is_simd = false;
         let mut is_transparent = false;
@@ -1856,7 +1896,7 @@ fn check_repr(
                 | sym::u128
                 | sym::isize
                 | sym::usize => {
-                    int_reprs += 1;
+                    int_reprs.push(hint.name_or_empty());
                     if target != Target::Enum {
                         ("an", "enum")
                     } else {
@@ -1906,25 +1946,56 @@ fn check_repr(
             .emit();
         }
         // Warn on repr(u8, u16), repr(C, simd), and c-like-enum-repr(C, u8)
-        if (int_reprs > 1)
-            || (is_simd && is_c)
-            || (int_reprs == 1
-                && is_c
-                && item.map_or(false, |item| {
-                    if let ItemLike::Item(item) = item {
-                        return is_c_like_enum(item);
-                    }
-                    return false;
-                }))
-        {
+        let is_c_like_enum_with_int = int_reprs.len() == 1
+            && is_c
+            && matches!(item, Some(ItemLike::Item(item)) if is_c_like_enum(item));
+        if int_reprs.len() > 1 || (is_simd && is_c) || is_c_like_enum_with_int {
             self.tcx.struct_span_lint_hir(
                 CONFLICTING_REPR_HINTS,
                 hir_id,
                 hint_spans.collect::<Vec<Span>>(),
                 |lint| {
-                    lint.build("conflicting representation hints")
-                        .code(rustc_errors::error_code!(E0566))
-                        .emit();
+                    let mut err = lint.build("conflicting representation hints");
+                    err.code(rustc_errors::error_code!(E0566));
+                    // Explain the layout each hint asks for, and the one `ReprOptions` ends up
+                    // with today, since that is what code relying on this currently gets.
+                    if int_reprs.len() > 1 && let [first @ .., last] = &int_reprs[..] {
+                        let first: Vec<_> =
+                            first.iter().map(|name| format!("`repr({})`", name)).collect();
+                        err.note(&format!(
+                            "{} and `repr({})` each set the type of the discriminant, but an \
+                             enum only has one",
+                            first.join(", "),
+                            last
+                        ));
+                        err.note(&format!(
+                            "the compiler currently uses the last of them, `repr({})`, and \
+                             ignores the others",
+                            last
+                        ));
+                    }
+                    if is_simd && is_c {
+                        err.note(
+                            "`repr(C)` asks for the field layout of the equivalent C struct, \
+                             while `repr(simd)` asks for a SIMD vector whose size and alignment \
+                             are chosen by the target",
+                        );
+                        err.note(
+                            "the compiler currently lays the type out as a SIMD vector and \
+                             ignores `repr(C)`",
+                        );
+                    }
+                    if is_c_like_enum_with_int && let [int] = int_reprs[..] {
+                        err.note(&format!(
+                            "`repr(C)` asks for the discriminant type a C compiler would use for \
+                             this enum, while `repr({int})` asks for a `{int}` discriminant",
+                        ));
+                        err.note(&format!(
+                            "the compiler currently uses a `{int}` discriminant and ignores \
+                             `repr(C)`",
+                        ));
+                    }
+                    err.emit();
                 },
             );
         }
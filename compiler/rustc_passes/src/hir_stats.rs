@@ -12,6 +12,7 @@
 use rustc_middle::hir::map::Map;
 use rustc_middle::ty::TyCtxt;
 use rustc_middle::util::common::to_readable_str;
+use rustc_span::symbol::{kw, Ident};
 use rustc_span::Span;
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash)]
@@ -26,31 +27,52 @@ struct NodeData {
     size: usize,
 }
 
+/// The nodes of a single item, not counting the items nested in it.
+struct ItemData {
+    path: String,
+    count: usize,
+    size: usize,
+}
+
 struct StatCollector<'k> {
     krate: Option<Map<'k>>,
     data: FxHashMap<&'static str, NodeData>,
     seen: FxHashSet<Id>,
+    /// How many of the largest items to print, from `-Z hir-stats-top-items`.
+    top_items: usize,
+    items: Vec<ItemData>,
+    /// Indices into `items` of the items enclosing the current node, innermost last.
+    item_stack: Vec<usize>,
 }
 
 pub fn print_hir_stats(tcx: TyCtxt<'_>) {
-    let mut collector = StatCollector {
-        krate: Some(tcx.hir()),
-        data: FxHashMap::default(),
-        seen: FxHashSet::default(),
-    };
+    let mut collector = StatCollector::new(
+        Some(tcx.hir()),
+        tcx.sess.opts.debugging_opts.hir_stats_top_items.unwrap_or(0),
+    );
     tcx.hir().walk_toplevel_module(&mut collector);
     tcx.hir().walk_attributes(&mut collector);
     collector.print("HIR STATS");
 }
 
-pub fn print_ast_stats(krate: &ast::Crate, title: &str) {
-    let mut collector =
-        StatCollector { krate: None, data: FxHashMap::default(), seen: FxHashSet::default() };
+pub fn print_ast_stats(krate: &ast::Crate, title: &str, top_items: Option<usize>) {
+    let mut collector = StatCollector::new(None, top_items.unwrap_or(0));
     ast_visit::walk_crate(&mut collector, krate);
     collector.print(title);
 }
 
 impl<'k> StatCollector<'k> {
+    fn new(krate: Option<Map<'k>>, top_items: usize) -> Self {
+        StatCollector {
+            krate,
+            data: FxHashMap::default(),
+            seen: FxHashSet::default(),
+            top_items,
+            items: Vec::new(),
+            item_stack: Vec::new(),
+        }
+    }
+
     fn record<T>(&mut self, label: &'static str, id: Id, node: &T) {
         if id != Id::None && !self.seen.insert(id) {
             return;
@@ -60,6 +82,30 @@ fn record<T>(&mut self, label: &'static str, id: Id, node: &T) {
 
         entry.count += 1;
         entry.size = std::mem::size_of_val(node);
+
+        if let Some(&index) = self.item_stack.last() {
+            let item = &mut self.items[index];
+            item.count += 1;
+            item.size += std::mem::size_of_val(node);
+        }
+    }
+
+    /// Attributes the nodes recorded by `f` to the item named `ident`, which is nested in the
+    /// item currently being visited, if any.
+    fn with_item(&mut self, ident: Ident, f: impl FnOnce(&mut Self)) {
+        if self.top_items == 0 {
+            return f(self);
+        }
+
+        let name = if ident.name == kw::Empty { "{impl}".to_string() } else { ident.to_string() };
+        let path = match self.item_stack.last() {
+            Some(&parent) => format!("{}::{}", self.items[parent].path, name),
+            None => name,
+        };
+        self.items.push(ItemData { path, count: 0, size: 0 });
+        self.item_stack.push(self.items.len() - 1);
+        f(self);
+        self.item_stack.pop();
     }
 
     fn print(&self, title: &str) {
@@ -87,6 +133,28 @@ fn print(&self, title: &str) {
         }
         eprintln!("----------------------------------------------------------------");
         eprintln!("{:<18}{:>18}\n", "Total", to_readable_str(total_size));
+
+        if self.top_items == 0 {
+            return;
+        }
+
+        let mut items: Vec<_> = self.items.iter().collect();
+        items.sort_by_key(|item| std::cmp::Reverse(item.size));
+
+        eprintln!("{} (top {} items)\n", title, self.top_items);
+
+        eprintln!("{:>18}{:>14}  {}", "Accumulated Size", "Count", "Item");
+        eprintln!("----------------------------------------------------------------");
+
+        for item in items.into_iter().take(self.top_items) {
+            eprintln!(
+                "{:>18}{:>14}  {}",
+                to_readable_str(item.size),
+                to_readable_str(item.count),
+                item.path
+            );
+        }
+        eprintln!("----------------------------------------------------------------\n");
     }
 }
 
@@ -122,13 +190,17 @@ fn visit_nested_body(&mut self, body_id: hir::BodyId) {
     }
 
     fn visit_item(&mut self, i: &'v hir::Item<'v>) {
-        self.record("Item", Id::Node(i.hir_id()), i);
-        hir_visit::walk_item(self, i)
+        self.with_item(i.ident, |this| {
+            this.record("Item", Id::Node(i.hir_id()), i);
+            hir_visit::walk_item(this, i)
+        })
     }
 
     fn visit_foreign_item(&mut self, i: &'v hir::ForeignItem<'v>) {
-        self.record("ForeignItem", Id::Node(i.hir_id()), i);
-        hir_visit::walk_foreign_item(self, i)
+        self.with_item(i.ident, |this| {
+            this.record("ForeignItem", Id::Node(i.hir_id()), i);
+            hir_visit::walk_foreign_item(this, i)
+        })
     }
 
     fn visit_local(&mut self, l: &'v hir::Local<'v>) {
@@ -184,13 +256,17 @@ fn visit_where_predicate(&mut self, predicate: &'v hir::WherePredicate<'v>) {
     }
 
     fn visit_trait_item(&mut self, ti: &'v hir::TraitItem<'v>) {
-        self.record("TraitItem", Id::Node(ti.hir_id()), ti);
-        hir_visit::walk_trait_item(self, ti)
+        self.with_item(ti.ident, |this| {
+            this.record("TraitItem", Id::Node(ti.hir_id()), ti);
+            hir_visit::walk_trait_item(this, ti)
+        })
     }
 
     fn visit_impl_item(&mut self, ii: &'v hir::ImplItem<'v>) {
-        self.record("ImplItem", Id::Node(ii.hir_id()), ii);
-        hir_visit::walk_impl_item(self, ii)
+        self.with_item(ii.ident, |this| {
+            this.record("ImplItem", Id::Node(ii.hir_id()), ii);
+            hir_visit::walk_impl_item(this, ii)
+        })
     }
 
     fn visit_param_bound(&mut self, bounds: &'v hir::GenericBound<'v>) {
@@ -245,13 +321,17 @@ fn visit_attribute(&mut self, _: hir::HirId, attr: &'v ast::Attribute) {
 
 impl<'v> ast_visit::Visitor<'v> for StatCollector<'v> {
     fn visit_foreign_item(&mut self, i: &'v ast::ForeignItem) {
-        self.record("ForeignItem", Id::None, i);
-        ast_visit::walk_foreign_item(self, i)
+        self.with_item(i.ident, |this| {
+            this.record("ForeignItem", Id::None, i);
+            ast_visit::walk_foreign_item(this, i)
+        })
     }
 
     fn visit_item(&mut self, i: &'v ast::Item) {
-        self.record("Item", Id::None, i);
-        ast_visit::walk_item(self, i)
+        self.with_item(i.ident, |this| {
+            this.record("Item", Id::None, i);
+            ast_visit::walk_item(this, i)
+        })
     }
 
     fn visit_local(&mut self, l: &'v ast::Local) {
@@ -299,8 +379,10 @@ fn visit_assoc_item(&mut self, item: &'v ast::AssocItem, ctxt: ast_visit::AssocC
             ast_visit::AssocCtxt::Trait => "TraitItem",
             ast_visit::AssocCtxt::Impl => "ImplItem",
         };
-        self.record(label, Id::None, item);
-        ast_visit::walk_assoc_item(self, item, ctxt);
+        self.with_item(item.ident, |this| {
+            this.record(label, Id::None, item);
+            ast_visit::walk_assoc_item(this, item, ctxt);
+        })
     }
 
     fn visit_param_bound(&mut self, bounds: &'v ast::GenericBound, _ctxt: BoundKind) {
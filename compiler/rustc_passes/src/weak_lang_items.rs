@@ -1,5 +1,6 @@
 //! Validity checking for weak lang items
 
+use rustc_ast::expand::allocator::AllocatorKind;
 use rustc_data_structures::fx::FxHashSet;
 use rustc_errors::struct_span_err;
 use rustc_hir::lang_items::{self, LangItem};
@@ -7,6 +8,7 @@
 use rustc_middle::middle::lang_items::required;
 use rustc_middle::ty::TyCtxt;
 use rustc_session::config::CrateType;
+use rustc_span::Symbol;
 
 /// Checks the crate for usage of weak lang items, returning a vector of all the
 /// language items required by this crate, but not defined yet.
@@ -68,24 +70,145 @@ fn verify<'tcx>(tcx: TyCtxt<'tcx>, items: &lang_items::LanguageItems) {
         }
     }
 
+    // The panic handler, the personality routine and the global allocator are what a
+    // `#![no_std]` binary most commonly has to provide itself. If more than one of them is
+    // missing, report them together rather than as a series of unrelated errors.
+    let mut runtime_items = Vec::new();
     for (name, item) in WEAK_ITEMS_REFS.clone().into_sorted_vector().into_iter() {
         if missing.contains(&item) && required(tcx, item) && items.require(item).is_err() {
-            if item == LangItem::PanicImpl {
-                tcx.sess.err("`#[panic_handler]` function required, but not found");
+            if item == LangItem::PanicImpl || item == LangItem::EhPersonality {
+                runtime_items.push(RuntimeItem::LangItem(item));
             } else if item == LangItem::Oom {
                 if !tcx.features().default_alloc_error_handler {
                     tcx.sess.err("`#[alloc_error_handler]` function required, but not found");
                     tcx.sess.note_without_error("use `#![feature(default_alloc_error_handler)]` for a default error handler");
                 }
             } else {
-                tcx
-                    .sess
-                    .diagnostic()
-                    .struct_err(&format!("language item required, but not found: `{}`", name))
-                    .note(&format!("this can occur when a binary crate with `#![no_std]` is compiled for a target where `{}` is defined in the standard library", name))
-                    .help(&format!("you may be able to compile for a target that doesn't need `{}`, specify a target with `--target` or in `.cargo/config`", name))
-                    .emit();
+                report_missing_lang_item(tcx, name);
             }
         }
     }
+    if tcx.missing_global_allocator(()) {
+        runtime_items.push(RuntimeItem::GlobalAllocator);
+    }
+    runtime_items.sort_by_key(|item| RuntimeItem::ALL.iter().position(|i| i == item));
+
+    match &runtime_items[..] {
+        [] => {}
+        [RuntimeItem::LangItem(LangItem::PanicImpl)] => {
+            tcx.sess.err("`#[panic_handler]` function required, but not found");
+        }
+        [RuntimeItem::LangItem(item)] => report_missing_lang_item(tcx, item.name()),
+        [RuntimeItem::GlobalAllocator] => {
+            tcx.sess.err(
+                "no global memory allocator found but one is required; link to std or add \
+                 `#[global_allocator]` to a static item that implements the GlobalAlloc trait",
+            );
+        }
+        _ => report_missing_runtime_items(tcx, items, &runtime_items),
+    }
+}
+
+/// An item that is normally provided by `std`, and which `#![no_std]` binaries have to define
+/// themselves.
+#[derive(Copy, Clone, PartialEq)]
+enum RuntimeItem {
+    LangItem(LangItem),
+    GlobalAllocator,
+}
+
+impl RuntimeItem {
+    const ALL: [RuntimeItem; 3] = [
+        RuntimeItem::LangItem(LangItem::PanicImpl),
+        RuntimeItem::LangItem(LangItem::EhPersonality),
+        RuntimeItem::GlobalAllocator,
+    ];
+
+    fn descr(self) -> &'static str {
+        match self {
+            RuntimeItem::LangItem(LangItem::PanicImpl) => "a `#[panic_handler]` function",
+            RuntimeItem::LangItem(LangItem::EhPersonality) => "the `eh_personality` language item",
+            RuntimeItem::LangItem(_) => unreachable!(),
+            RuntimeItem::GlobalAllocator => "a `#[global_allocator]`",
+        }
+    }
+
+    /// Whether this item is needed by the current crate graph, but is already provided.
+    fn is_present<'tcx>(self, tcx: TyCtxt<'tcx>, items: &lang_items::LanguageItems) -> bool {
+        match self {
+            RuntimeItem::LangItem(item) => required(tcx, item) && items.require(item).is_ok(),
+            RuntimeItem::GlobalAllocator => tcx.allocator_kind(()) == Some(AllocatorKind::Global),
+        }
+    }
+
+    fn skeleton(self) -> &'static str {
+        match self {
+            RuntimeItem::LangItem(LangItem::PanicImpl) => {
+                "define a panic handler:\n\
+                 #[panic_handler]\n\
+                 fn panic(_info: &core::panic::PanicInfo) -> ! {\n    loop {}\n}"
+            }
+            RuntimeItem::LangItem(LangItem::EhPersonality) => {
+                "compile with `-C panic=abort`, or define a personality routine:\n\
+                 #![feature(lang_items)]\n\
+                 #[lang = \"eh_personality\"]\n\
+                 extern \"C\" fn eh_personality() {}"
+            }
+            RuntimeItem::LangItem(_) => unreachable!(),
+            RuntimeItem::GlobalAllocator => {
+                "link to `std`, or define a global allocator:\n\
+                 struct Allocator;\n\
+                 unsafe impl core::alloc::GlobalAlloc for Allocator {\n    \
+                     unsafe fn alloc(&self, layout: core::alloc::Layout) -> *mut u8 { todo!() }\n    \
+                     unsafe fn dealloc(&self, ptr: *mut u8, layout: core::alloc::Layout) { todo!() }\n\
+                 }\n\
+                 #[global_allocator]\n\
+                 static ALLOCATOR: Allocator = Allocator;"
+            }
+        }
+    }
+}
+
+fn listify(descrs: &[&str]) -> String {
+    match descrs {
+        [] => String::new(),
+        [only] => only.to_string(),
+        [init @ .., last] => format!("{} and {}", init.join(", "), last),
+    }
+}
+
+fn report_missing_runtime_items<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    items: &lang_items::LanguageItems,
+    missing: &[RuntimeItem],
+) {
+    let missing_descrs: Vec<_> = missing.iter().map(|item| item.descr()).collect();
+    let mut err =
+        tcx.sess.struct_err(&format!("{} required, but not found", listify(&missing_descrs)));
+    err.note(
+        "these are normally provided by the standard library; a binary crate with \
+         `#![no_std]` has to define them itself",
+    );
+    let present: Vec<_> = RuntimeItem::ALL
+        .into_iter()
+        .filter(|item| !missing.contains(item) && item.is_present(tcx, items))
+        .map(|item| item.descr())
+        .collect();
+    if !present.is_empty() {
+        err.note(&format!("already defined: {}", listify(&present)));
+    }
+    for item in missing {
+        err.help(item.skeleton());
+    }
+    err.emit();
+}
+
+fn report_missing_lang_item<'tcx>(tcx: TyCtxt<'tcx>, name: Symbol) {
+    tcx
+        .sess
+        .diagnostic()
+        .struct_err(&format!("language item required, but not found: `{}`", name))
+        .note(&format!("this can occur when a binary crate with `#![no_std]` is compiled for a target where `{}` is defined in the standard library", name))
+        .help(&format!("you may be able to compile for a target that doesn't need `{}`, specify a target with `--target` or in `.cargo/config`", name))
+        .emit();
 }
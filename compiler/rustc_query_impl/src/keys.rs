@@ -380,8 +380,11 @@ impl<'tcx> Key for Ty<'tcx> {
     fn query_crate_is_local(&self) -> bool {
         true
     }
-    fn default_span(&self, _: TyCtxt<'_>) -> Span {
-        DUMMY_SP
+    fn default_span(&self, tcx: TyCtxt<'_>) -> Span {
+        match *self.kind() {
+            ty::Adt(adt, _) if adt.did().is_local() => tcx.def_span(adt.did()),
+            _ => DUMMY_SP,
+        }
     }
 }
 
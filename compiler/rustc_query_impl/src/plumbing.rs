@@ -7,11 +7,14 @@
 use rustc_middle::ty::tls::{self, ImplicitCtxt};
 use rustc_middle::ty::TyCtxt;
 use rustc_query_system::dep_graph::HasDepContext;
-use rustc_query_system::query::{QueryContext, QueryJobId, QueryMap, QuerySideEffects};
+use rustc_query_system::query::{
+    QueryContext, QueryJobId, QueryMap, QuerySideEffects, QueryStackFrame,
+};
 
 use rustc_data_structures::sync::Lock;
 use rustc_data_structures::thin_vec::ThinVec;
 use rustc_errors::{Diagnostic, Handler};
+use rustc_span::Span;
 
 use std::any::Any;
 use std::num::NonZeroU64;
@@ -165,6 +168,13 @@ pub fn try_print_query_stack(
     ) -> usize {
         rustc_query_system::query::print_query_stack(self, query, handler, num_frames)
     }
+
+    pub fn try_collect_query_stack(
+        self,
+        query: Option<QueryJobId>,
+    ) -> Vec<(QueryStackFrame, Span)> {
+        rustc_query_system::query::collect_query_stack(self, query)
+    }
 }
 
 macro_rules! handle_cycle_error {
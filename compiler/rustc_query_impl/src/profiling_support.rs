@@ -101,6 +101,10 @@ trait IntoSelfProfilingString {
     fn to_self_profile_string(&self, builder: &mut QueryKeyStringBuilder<'_, '_, '_>) -> StringId;
 }
 
+/// The maximum length in bytes of a query key rendered with `Debug`. Keys containing types
+/// can get very long and would otherwise bloat the trace files.
+const MAX_DEBUG_QUERY_KEY_LEN: usize = 256;
+
 // The default implementation of `IntoSelfProfilingString` just uses `Debug`
 // which is slow and causes lots of duplication of string data.
 // The specialized impls below take care of making the `DefId` case more
@@ -110,11 +114,25 @@ impl<T: Debug> IntoSelfProfilingString for T {
         &self,
         builder: &mut QueryKeyStringBuilder<'_, '_, '_>,
     ) -> StringId {
-        let s = format!("{:?}", self);
+        let mut s = format!("{:?}", self);
+        truncate_query_key(&mut s, MAX_DEBUG_QUERY_KEY_LEN);
         builder.profiler.alloc_string(&s[..])
     }
 }
 
+/// Truncates `key` to at most `max_len` bytes, at a char boundary, marking it with `...` if
+/// anything was cut off.
+fn truncate_query_key(key: &mut String, max_len: usize) {
+    if key.len() > max_len {
+        let mut end = max_len;
+        while !key.is_char_boundary(end) {
+            end -= 1;
+        }
+        key.truncate(end);
+        key.push_str("...");
+    }
+}
+
 impl<T: SpecIntoSelfProfilingString> IntoSelfProfilingString for T {
     fn to_self_profile_string(&self, builder: &mut QueryKeyStringBuilder<'_, '_, '_>) -> StringId {
         self.spec_to_self_profile_string(builder)
@@ -319,3 +337,6 @@ macro_rules! alloc_once {
 
     rustc_query_append! { [alloc_once!][<'tcx>] }
 }
+
+#[cfg(test)]
+mod tests;
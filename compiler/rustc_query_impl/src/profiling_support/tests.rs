@@ -0,0 +1,28 @@
+use super::truncate_query_key;
+
+fn truncated(key: &str, max_len: usize) -> String {
+    let mut key = key.to_string();
+    truncate_query_key(&mut key, max_len);
+    key
+}
+
+#[test]
+fn short_keys_are_kept() {
+    assert_eq!(truncated("abc", 3), "abc");
+    assert_eq!(truncated("", 0), "");
+}
+
+#[test]
+fn long_keys_are_truncated() {
+    assert_eq!(truncated("abcdef", 3), "abc...");
+}
+
+#[test]
+fn keys_are_truncated_at_a_char_boundary() {
+    // `é` takes two bytes, so cutting after three bytes would split it.
+    assert_eq!(truncated("abéd", 3), "ab...");
+    assert_eq!(truncated("abéd", 4), "abé...");
+    // `🦀` takes four bytes.
+    assert_eq!(truncated("🦀🦀", 5), "🦀...");
+    assert_eq!(truncated("🦀🦀", 3), "...");
+}
@@ -25,6 +25,9 @@
 #[cfg(debug_assertions)]
 use {super::debug::EdgeFilter, std::env};
 
+#[cfg(parallel_compiler)]
+use rustc_data_structures::{fx::FxHashSet, sharded::Sharded, sync::par_for_each_in};
+
 #[derive(Clone)]
 pub struct DepGraph<K: DepKind> {
     data: Option<Lrc<DepGraphData<K>>>,
@@ -718,6 +721,25 @@ fn try_mark_previous_green<Ctxt: QueryContext<DepKind = K>>(
 
         let prev_deps = data.previous.edge_targets_from(prev_dep_node_index);
 
+        // The dependencies have to be checked in order below, as an earlier red dependency may be
+        // what guards us from forcing a later one whose key no longer exists. Marking nodes green
+        // without forcing anything is fine in any order though, so with many dependencies we do
+        // that part concurrently first and leave only the forcing to the serial walk.
+        #[cfg(parallel_compiler)]
+        if prev_deps.len() >= PARALLEL_MARKING_MIN_DEPS && tcx.dep_context().sess().threads() > 1 {
+            // Only the nodes the pass reaches are recorded, so that its cost doesn't depend on the
+            // size of the whole previous graph.
+            let visited = Sharded::default();
+            par_for_each_in(prev_deps, |&dep_dep_node_index| {
+                self.try_mark_previous_green_without_forcing(
+                    tcx,
+                    data,
+                    &visited,
+                    dep_dep_node_index,
+                );
+            });
+        }
+
         for &dep_dep_node_index in prev_deps {
             self.try_mark_parent_green(tcx, data, dep_dep_node_index, dep_node)?
         }
@@ -762,8 +784,65 @@ fn try_mark_previous_green<Ctxt: QueryContext<DepKind = K>>(
         Some(dep_node_index)
     }
 
+    /// Tries to mark a dep-node which existed in the previous compilation session as green, like
+    /// `try_mark_previous_green` but without forcing any query. Nodes which are eval_always, have
+    /// side effects or depend on a node whose color is unknown, for example because its query is
+    /// still being executed on another thread, are left for `try_mark_previous_green` to decide.
+    /// Returns whether the node is green.
+    ///
+    /// `visited` records the nodes already visited by the current pass, so that each node is
+    /// only visited once even though the previous dependency graph is a DAG. A node visited
+    /// before, possibly by another thread which is still deciding it, is treated like a node whose
+    /// color is unknown.
+    #[cfg(parallel_compiler)]
+    fn try_mark_previous_green_without_forcing<Ctxt: QueryContext<DepKind = K>>(
+        &self,
+        tcx: Ctxt,
+        data: &DepGraphData<K>,
+        visited: &Sharded<FxHashSet<SerializedDepNodeIndex>>,
+        prev_dep_node_index: SerializedDepNodeIndex,
+    ) -> bool {
+        match data.colors.get(prev_dep_node_index) {
+            Some(DepNodeColor::Green(_)) => return true,
+            Some(DepNodeColor::Red) => return false,
+            None => {}
+        }
+
+        if !visited.get_shard_by_value(&prev_dep_node_index).lock().insert(prev_dep_node_index) {
+            return false;
+        }
+
+        let dep_node = data.previous.index_to_node(prev_dep_node_index);
+        let prev_deps = data.previous.edge_targets_from(prev_dep_node_index);
+        let deps_green = !tcx.dep_context().is_eval_always(dep_node.kind)
+            && prev_deps.iter().all(|&dep_dep_node_index| {
+                self.try_mark_previous_green_without_forcing(tcx, data, visited, dep_dep_node_index)
+            });
+        // The node may not be needed in this session at all, so replaying its diagnostics here
+        // could emit them spuriously.
+        if !deps_green || !tcx.load_side_effects(prev_dep_node_index).is_empty() {
+            return false;
+        }
+
+        let dep_node_index = data.current.promote_node_and_deps_to_current(
+            tcx.dep_context().profiler(),
+            &data.previous,
+            prev_dep_node_index,
+        );
+        data.colors.insert(prev_dep_node_index, DepNodeColor::Green(dep_node_index));
+
+        debug!("try_mark_previous_green_without_forcing({:?}) - marked as green", dep_node);
+        true
+    }
+
     /// Atomically emits some loaded diagnostics.
     /// This may be called concurrently on multiple threads for the same dep node.
+    ///
+    /// Lint diagnostics are replayed with the level they were emitted at. This is sound because
+    /// computing that level reads the `lint_levels` query, so a node which emitted a lint depends
+    /// on it and is not marked green once a lint level attribute changed anywhere in the crate.
+    /// Lint levels given on the command line are tracked options, so changing them discards the
+    /// whole cache.
     #[cold]
     #[inline(never)]
     fn emit_side_effects<Ctxt: QueryContext<DepKind = K>>(
@@ -1188,6 +1267,11 @@ fn debug_assert_not_in_new_nodes(
     }
 }
 
+/// The number of dependencies from which `try_mark_previous_green` first tries to mark them
+/// green concurrently when running with multiple threads.
+#[cfg(parallel_compiler)]
+const PARALLEL_MARKING_MIN_DEPS: usize = 16;
+
 /// The capacity of the `reads` field `SmallVec`
 const TASK_DEPS_READS_CAP: usize = 8;
 type EdgesVec = SmallVec<[DepNodeIndex; TASK_DEPS_READS_CAP]>;
@@ -491,6 +491,8 @@ fn remove_cycle(
 /// uses a query latch and then resuming that waiter.
 /// There may be multiple cycles involved in a deadlock, so this searches
 /// all active queries for cycles before finally resuming all the waiters at once.
+/// If there is no cycle, nothing can be resumed, so the deadlock is reported with
+/// the active queries and the process is aborted.
 #[cfg(parallel_compiler)]
 pub fn deadlock<CTX: QueryContext>(tcx: CTX, registry: &rayon_core::Registry) {
     let on_panic = OnDrop(|| {
@@ -499,7 +501,10 @@ pub fn deadlock<CTX: QueryContext>(tcx: CTX, registry: &rayon_core::Registry) {
     });
 
     let mut wakelist = Vec::new();
-    let query_map = tcx.try_collect_active_jobs().unwrap();
+    let Some(query_map) = tcx.try_collect_active_jobs() else {
+        eprintln!("deadlock detected while the active queries were locked, aborting process");
+        process::abort();
+    };
     let mut jobs: Vec<QueryJobId> = query_map.keys().cloned().collect();
 
     let mut found_cycle = false;
@@ -517,7 +522,9 @@ pub fn deadlock<CTX: QueryContext>(tcx: CTX, registry: &rayon_core::Registry) {
     // which in turn will wait on X causing a deadlock. We have a false dependency from
     // X to Y due to Rayon waiting and a true dependency from Y to X. The algorithm here
     // only considers the true dependency and won't detect a cycle.
-    assert!(found_cycle);
+    if !found_cycle {
+        report_deadlock(tcx, &query_map);
+    }
 
     // FIXME: Ensure this won't cause a deadlock before we return
     for waiter in wakelist.into_iter() {
@@ -527,6 +534,60 @@ pub fn deadlock<CTX: QueryContext>(tcx: CTX, registry: &rayon_core::Registry) {
     on_panic.disable();
 }
 
+/// Reports a deadlock which isn't caused by a query cycle, listing the active queries
+/// and the queries waiting on them, and aborts the process.
+///
+/// Unlike a cycle, this can't be recovered from by resuming a waiter with a cycle error:
+/// the waiters are blocked on a query which is still running on another thread, not on
+/// one of their own callers, so there is no cycle to break and whatever is resumed would
+/// still wait on the rayon work behind the deadlock. There is no test for it, since no
+/// reproduction is known and compiletest can't run a compiler built with
+/// `parallel-compiler = true`, which `-Zthreads` needs.
+#[cfg(parallel_compiler)]
+#[inline(never)]
+#[cold]
+fn report_deadlock<CTX: QueryContext>(tcx: CTX, query_map: &QueryMap) -> ! {
+    let sess = tcx.dep_context().sess();
+
+    // Sort the queries like `pick_query` does, so the report is deterministic.
+    let mut jobs: Vec<_> = query_map.iter().collect();
+    jobs.sort_by_key(|(_, info)| info.query.hash);
+
+    let mut err = sess.struct_err("deadlock detected without a query cycle");
+    for (&query, info) in jobs {
+        // Only the queries blocked on the latch are listed; the parent of the query runs it on
+        // its own thread rather than waiting on it.
+        let mut waiters = Vec::new();
+        if let Some(latch) = query.latch(query_map) {
+            for waiter in latch.info.lock().waiters.iter() {
+                if let Some(waiter_query) = waiter.query {
+                    waiters.push(format!("`{}`", waiter_query.query(query_map).description));
+                }
+            }
+        }
+        let span = sess.source_map().guess_head_span(info.query.default_span(info.job.span));
+        let msg = if waiters.is_empty() {
+            format!("active query `{}`", info.query.description)
+        } else {
+            format!(
+                "active query `{}`, waited on by {}",
+                info.query.description,
+                waiters.join(", ")
+            )
+        };
+        err.span_note(span, &msg);
+    }
+    err.note(
+        "this is a bug in the parallel compiler, which is likely caused by a query \
+         waiting on work that uses rayon internally",
+    );
+    err.help("compiling with `-Zthreads=1` avoids the deadlock");
+    err.emit();
+
+    eprintln!("aborting process due to the deadlock");
+    process::abort();
+}
+
 #[inline(never)]
 #[cold]
 pub(crate) fn report_cycle<'a>(
@@ -543,10 +604,29 @@ pub(crate) fn report_cycle<'a>(
     let mut err =
         struct_span_err!(sess, span, E0391, "cycle detected when {}", stack[0].query.description);
 
-    for i in 1..stack.len() {
-        let query = &stack[i].query;
-        let span = fix_span(stack[(i + 1) % stack.len()].span, query);
-        err.span_note(span, &format!("...which requires {}...", query.description));
+    let notes: Vec<_> = (1..stack.len())
+        .map(|i| {
+            let query = &stack[i].query;
+            (fix_span(stack[(i + 1) % stack.len()].span, query), &query.description)
+        })
+        .collect();
+    let mut i = 0;
+    while i < notes.len() {
+        let (span, description) = notes[i];
+        err.span_note(span, &format!("...which requires {}...", description));
+        i += 1;
+
+        // Long runs of queries about the same item, like the steps between type-checking and
+        // borrow-checking a function, are collapsed to their first and last query.
+        let same_item = notes[i..].iter().take_while(|&&(next_span, _)| next_span == span).count();
+        if same_item > 2 && !span.is_dummy() && !sess.verbose() {
+            err.note(&format!(
+                "...which requires {} other queries about the same item \
+                 (run with `-Z verbose` to see them)...",
+                same_item - 1
+            ));
+            i += same_item - 1;
+        }
     }
 
     if stack.len() == 1 {
@@ -575,6 +655,16 @@ pub(crate) fn report_cycle<'a>(
         }
     }
 
+    if stack.iter().all(|entry| entry.query.name == "layout_of") {
+        err.note(
+            "a type cannot contain itself without indirection, as it would be infinitely large",
+        );
+        err.help(
+            "insert some indirection (e.g., a `Box`, `Rc`, or `&`) where the type contains \
+             itself to break the cycle",
+        );
+    }
+
     if let Some((span, query)) = usage {
         err.span_note(fix_span(span, &query), &format!("cycle used when {}", query.description));
     }
@@ -615,3 +705,24 @@ pub fn print_query_stack<CTX: QueryContext>(
 
     i
 }
+
+/// The queries on the stack starting at `current_query`, innermost first, each with the span it
+/// was called for. Like `print_query_stack`, this is called from a panic hook.
+pub fn collect_query_stack<CTX: QueryContext>(
+    tcx: CTX,
+    mut current_query: Option<QueryJobId>,
+) -> Vec<(QueryStackFrame, Span)> {
+    let mut stack = Vec::new();
+    let query_map = tcx.try_collect_active_jobs();
+
+    while let Some(query) = current_query {
+        let Some(query_info) = query_map.as_ref().and_then(|map| map.get(&query)) else {
+            break;
+        };
+        let span = query_info.query.default_span(query_info.job.span);
+        stack.push((query_info.query.clone(), span));
+        current_query = query_info.job.parent;
+    }
+
+    stack
+}
@@ -4,7 +4,9 @@
 mod job;
 #[cfg(parallel_compiler)]
 pub use self::job::deadlock;
-pub use self::job::{print_query_stack, QueryInfo, QueryJob, QueryJobId, QueryJobInfo, QueryMap};
+pub use self::job::{
+    collect_query_stack, print_query_stack, QueryInfo, QueryJob, QueryJobId, QueryJobInfo, QueryMap,
+};
 
 mod caches;
 pub use self::caches::{
@@ -16,7 +18,7 @@
 
 use crate::dep_graph::{DepNodeIndex, HasDepContext, SerializedDepNodeIndex};
 
-use rustc_data_structures::sync::Lock;
+use rustc_data_structures::sync::{Lock, Sync};
 use rustc_data_structures::thin_vec::ThinVec;
 use rustc_errors::Diagnostic;
 use rustc_hir::def::DefKind;
@@ -91,7 +93,7 @@ pub fn append(&mut self, other: QuerySideEffects) {
     }
 }
 
-pub trait QueryContext: HasDepContext {
+pub trait QueryContext: HasDepContext + Sync {
     fn next_job_id(&self) -> QueryJobId;
 
     /// Get the query information from the TLS context.
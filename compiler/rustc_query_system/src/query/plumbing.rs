@@ -16,12 +16,13 @@
 use rustc_data_structures::sync::Lock;
 use rustc_data_structures::thin_vec::ThinVec;
 use rustc_errors::{DiagnosticBuilder, ErrorGuaranteed, FatalError};
+use rustc_session::config::IncrementalVerifyIch;
 use rustc_session::Session;
 use rustc_span::{Span, DUMMY_SP};
 use std::cell::Cell;
-use std::collections::hash_map::Entry;
+use std::collections::hash_map::{Entry, RandomState};
 use std::fmt::Debug;
-use std::hash::Hash;
+use std::hash::{BuildHasher, Hash, Hasher};
 use std::mem;
 use std::ptr;
 
@@ -482,13 +483,31 @@ fn try_load_from_disk_and_cache_in_memory<CTX, K, V>(
             // from disk. Re-hashing results is fairly expensive, so we can't
             // currently afford to verify every hash. This subset should still
             // give us some coverage of potential bugs though.
+            let verify_ich = tcx.dep_context().sess().opts.debugging_opts.incremental_verify_ich;
             let try_verify = prev_fingerprint.as_value().1 % 32 == 0;
-            if unlikely!(
-                try_verify || tcx.dep_context().sess().opts.debugging_opts.incremental_verify_ich
-            ) {
+            if unlikely!(try_verify || verify_ich == IncrementalVerifyIch::Full) {
                 incremental_verify_ich(*tcx.dep_context(), &result, dep_node, query);
             }
 
+            // With `-Zincremental-verify-ich=sample`, also re-execute a random
+            // sample of the queries loaded from disk. Re-hashing a cached result
+            // cannot catch a query whose result is not stable across sessions,
+            // re-executing it can. As the sample differs between sessions, the
+            // queries of a crate are eventually all covered by repeated builds.
+            // `sample:1` re-executes all of them, which makes it deterministic.
+            //
+            // Queries which emitted diagnostics are skipped, as re-executing them
+            // would emit those a second time.
+            if let IncrementalVerifyIch::Sample(rate) = verify_ich {
+                if sample_for_verification(rate)
+                    && tcx.load_side_effects(prev_dep_node_index).is_empty()
+                {
+                    let result =
+                        dep_graph.with_ignore(|| query.compute(*tcx.dep_context(), key.clone()));
+                    incremental_verify_ich(*tcx.dep_context(), &result, dep_node, query);
+                }
+            }
+
             return Some((result, dep_node_index));
         }
 
@@ -547,10 +566,23 @@ fn incremental_verify_ich<CTX, K, V: Debug>(
     debug!("END verify_ich({:?})", dep_node);
 
     if Some(new_hash) != old_hash {
-        incremental_verify_ich_cold(tcx.sess(), DebugArg::from(&dep_node), DebugArg::from(&result));
+        incremental_verify_ich_cold(
+            tcx.sess(),
+            DebugArg::from(&dep_node),
+            DebugArg::from(&result),
+            old_hash,
+            new_hash,
+        );
     }
 }
 
+/// Decides whether `-Zincremental-verify-ich=sample` re-executes a query,
+/// which it does for one in `rate` of them. The keys of a new `RandomState`
+/// differ each time, so this is random unless `rate` is 1.
+fn sample_for_verification(rate: u64) -> bool {
+    RandomState::new().build_hasher().finish() % rate == 0
+}
+
 // This DebugArg business is largely a mirror of std::fmt::ArgumentV1, which is
 // currently not exposed publicly.
 //
@@ -593,7 +625,13 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 // different implementations for LLVM to chew on (and filling up the final
 // binary, too).
 #[cold]
-fn incremental_verify_ich_cold(sess: &Session, dep_node: DebugArg<'_>, result: DebugArg<'_>) {
+fn incremental_verify_ich_cold(
+    sess: &Session,
+    dep_node: DebugArg<'_>,
+    result: DebugArg<'_>,
+    old_hash: Option<Fingerprint>,
+    new_hash: Fingerprint,
+) {
     let run_cmd = if let Some(crate_name) = &sess.opts.crate_name {
         format!("`cargo clean -p {}` or `cargo clean`", crate_name)
     } else {
@@ -623,7 +661,10 @@ fn incremental_verify_ich_cold(sess: &Session, dep_node: DebugArg<'_>, result: D
                 .note("Please follow the instructions below to create a bug report with the provided information")
                 .note("See <https://github.com/rust-lang/rust/issues/84970> for more information")
                 .emit();
-        panic!("Found unstable fingerprints for {:?}: {:?}", dep_node, result);
+        panic!(
+            "Found unstable fingerprints for {:?}: {:?} (expected {:?}, found {:?})",
+            dep_node, result, old_hash, new_hash
+        );
     }
 
     INSIDE_VERIFY_PANIC.with(|in_panic| in_panic.set(old_in_panic));
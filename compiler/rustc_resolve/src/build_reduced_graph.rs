@@ -865,6 +865,13 @@ fn build_reduced_graph_for_extern_crate(
         }
         .map(|module| {
             let used = self.process_macro_use_imports(item, module);
+            if ptr::eq(parent, self.r.graph_root)
+                && orig_name != Some(kw::SelfLower)
+                && !sp.from_expansion()
+                && !self.r.session.contains_name(&item.attrs, sym::macro_use)
+            {
+                self.r.extern_crates_without_macro_use.push((module, ident.name, sp));
+            }
             let binding =
                 (module, ty::Visibility::Public, sp, expansion).to_name_binding(self.r.arenas);
             (used, Some(ModuleOrUniformRoot::Module(module)), binding)
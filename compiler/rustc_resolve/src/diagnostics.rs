@@ -19,7 +19,7 @@
 use rustc_session::lint::BuiltinLintDiagnostics;
 use rustc_session::Session;
 use rustc_span::edition::Edition;
-use rustc_span::hygiene::MacroKind;
+use rustc_span::hygiene::{MacroKind, SyntaxContext};
 use rustc_span::lev_distance::find_best_match_for_name;
 use rustc_span::source_map::SourceMap;
 use rustc_span::symbol::{kw, sym, Ident, Symbol};
@@ -58,14 +58,40 @@ pub(crate) struct TypoSuggestion {
     pub candidate: Symbol,
     pub res: Res,
     pub target: SuggestionTarget,
+    /// Whether the candidate can be used from the module where the name failed to resolve.
+    /// Accessible candidates are preferred over inaccessible ones that are just as close.
+    pub accessible: bool,
 }
 
 impl TypoSuggestion {
     pub(crate) fn typo_from_res(candidate: Symbol, res: Res) -> TypoSuggestion {
-        Self { candidate, res, target: SuggestionTarget::SimilarlyNamed }
+        Self { candidate, res, target: SuggestionTarget::SimilarlyNamed, accessible: true }
     }
     pub(crate) fn single_item_from_res(candidate: Symbol, res: Res) -> TypoSuggestion {
-        Self { candidate, res, target: SuggestionTarget::SingleItem }
+        Self { candidate, res, target: SuggestionTarget::SingleItem, accessible: true }
+    }
+}
+
+/// Picks the candidate closest to `lookup`, preferring accessible candidates over inaccessible
+/// ones at the same distance.
+pub(crate) fn find_best_typo_suggestion(
+    mut suggestions: Vec<TypoSuggestion>,
+    lookup: Symbol,
+) -> Option<TypoSuggestion> {
+    // Make sure error reporting is deterministic.
+    suggestions.sort_by(|a, b| {
+        b.accessible.cmp(&a.accessible).then_with(|| a.candidate.as_str().cmp(b.candidate.as_str()))
+    });
+
+    match find_best_match_for_name(
+        &suggestions.iter().map(|suggestion| suggestion.candidate).collect::<Vec<Symbol>>(),
+        lookup,
+        None,
+    ) {
+        Some(found) if found != lookup => {
+            suggestions.into_iter().find(|suggestion| suggestion.candidate == found)
+        }
+        _ => None,
     }
 }
 
@@ -475,18 +501,36 @@ pub(crate) fn lint_if_path_starts_with_module(
         );
     }
 
+    /// Collects the names in `module`, including the ones brought in by glob imports, as typo
+    /// candidates. With a `lookup` context and the module the lookup happens in, names that
+    /// hygiene hides from that context are left out, and names that aren't accessible from
+    /// that module are marked as such.
     pub(crate) fn add_module_candidates(
         &mut self,
         module: Module<'a>,
         names: &mut Vec<TypoSuggestion>,
         filter_fn: &impl Fn(Res) -> bool,
+        lookup: Option<(SyntaxContext, Module<'a>)>,
     ) {
+        let lookup = lookup.map(|(ctxt, parent_module)| {
+            let mut ctxt = ctxt.normalize_to_macros_2_0();
+            ctxt.adjust(module.expansion);
+            (ctxt, parent_module)
+        });
         for (key, resolution) in self.resolutions(module).borrow().iter() {
             if let Some(binding) = resolution.borrow().binding {
                 let res = binding.res();
-                if filter_fn(res) {
-                    names.push(TypoSuggestion::typo_from_res(key.ident.name, res));
+                if !filter_fn(res) {
+                    continue;
                 }
+                let mut suggestion = TypoSuggestion::typo_from_res(key.ident.name, res);
+                if let Some((ctxt, parent_module)) = lookup {
+                    if key.ident.span.ctxt() != ctxt {
+                        continue;
+                    }
+                    suggestion.accessible = self.is_accessible_from(binding.vis, parent_module);
+                }
+                names.push(suggestion);
             }
         }
     }
@@ -1114,7 +1158,7 @@ fn early_lookup_typo_candidate(
     ) -> Option<TypoSuggestion> {
         let mut suggestions = Vec::new();
         let ctxt = ident.span.ctxt();
-        self.visit_scopes(scope_set, parent_scope, ctxt, |this, scope, use_prelude, _| {
+        self.visit_scopes(scope_set, parent_scope, ctxt, |this, scope, use_prelude, ctxt| {
             match scope {
                 Scope::DeriveHelpers(expn_id) => {
                     let res = Res::NonMacroAttr(NonMacroAttrKind::DeriveHelper);
@@ -1163,10 +1207,20 @@ fn early_lookup_typo_candidate(
                 Scope::CrateRoot => {
                     let root_ident = Ident::new(kw::PathRoot, ident.span);
                     let root_module = this.resolve_crate_root(root_ident);
-                    this.add_module_candidates(root_module, &mut suggestions, filter_fn);
+                    this.add_module_candidates(
+                        root_module,
+                        &mut suggestions,
+                        filter_fn,
+                        Some((ctxt, parent_scope.module)),
+                    );
                 }
                 Scope::Module(module, _) => {
-                    this.add_module_candidates(module, &mut suggestions, filter_fn);
+                    this.add_module_candidates(
+                        module,
+                        &mut suggestions,
+                        filter_fn,
+                        Some((ctxt, parent_scope.module)),
+                    );
                 }
                 Scope::RegisteredAttrs => {
                     let res = Res::NonMacroAttr(NonMacroAttrKind::Registered);
@@ -1213,7 +1267,7 @@ fn early_lookup_typo_candidate(
                 Scope::StdLibPrelude => {
                     if let Some(prelude) = this.prelude {
                         let mut tmp_suggestions = Vec::new();
-                        this.add_module_candidates(prelude, &mut tmp_suggestions, filter_fn);
+                        this.add_module_candidates(prelude, &mut tmp_suggestions, filter_fn, None);
                         suggestions.extend(
                             tmp_suggestions
                                 .into_iter()
@@ -1232,19 +1286,7 @@ fn early_lookup_typo_candidate(
             None::<()>
         });
 
-        // Make sure error reporting is deterministic.
-        suggestions.sort_by(|a, b| a.candidate.as_str().partial_cmp(b.candidate.as_str()).unwrap());
-
-        match find_best_match_for_name(
-            &suggestions.iter().map(|suggestion| suggestion.candidate).collect::<Vec<Symbol>>(),
-            ident.name,
-            None,
-        ) {
-            Some(found) if found != ident.name => {
-                suggestions.into_iter().find(|suggestion| suggestion.candidate == found)
-            }
-            _ => None,
-        }
+        find_best_typo_suggestion(suggestions, ident.name)
     }
 
     fn lookup_import_candidates_from_module<FilterFn>(
@@ -1490,6 +1532,42 @@ pub(crate) fn unresolved_macro_suggestions(
             vec![],
         );
 
+        // `#[macro_use]` on an `extern crate` item imports every macro at the root of that
+        // crate, which is the only way to get at them without a path in 2015 edition crates.
+        let extern_crates = self.extern_crates_without_macro_use.clone();
+        let macro_use_crate = extern_crates.into_iter().find(|&(module, ..)| {
+            self.maybe_resolve_ident_in_module(
+                ModuleOrUniformRoot::Module(module),
+                ident,
+                MacroNS,
+                parent_scope,
+            )
+            .map_or(false, |binding| is_expected(binding.res()))
+        });
+        if let Some((_, crate_name, item_span)) = macro_use_crate {
+            let indent =
+                self.session.source_map().indentation_before(item_span).unwrap_or_default();
+            // A `use` item only brings the macro into scope for the module it is placed in, and
+            // the only placement known during expansion is next to the `extern crate` item.
+            if parent_scope.module.nearest_parent_mod() == CRATE_DEF_ID.to_def_id() {
+                err.span_suggestion_verbose(
+                    item_span.shrink_to_hi(),
+                    &format!("import the {} from `{}`", macro_kind.descr(), crate_name),
+                    format!("\n{}use {}::{};", indent, crate_name, ident),
+                    Applicability::MaybeIncorrect,
+                );
+            }
+            err.span_suggestion_verbose(
+                item_span.shrink_to_lo(),
+                &format!(
+                    "or use `#[macro_use]` to import every macro exported by `{}`",
+                    crate_name
+                ),
+                format!("#[macro_use]\n{}", indent),
+                Applicability::MaybeIncorrect,
+            );
+        }
+
         if macro_kind == MacroKind::Derive && (ident.name == sym::Send || ident.name == sym::Sync) {
             let msg = format!("unsafe traits like `{}` should be implemented explicitly", ident);
             err.span_note(ident.span, &msg);
@@ -2518,6 +2596,15 @@ fn show_candidates(
                 Applicability::MaybeIncorrect,
             );
         } else if let Some(span) = use_placement_span {
+            // A single candidate from the standard library or another crate can be imported as
+            // is, unless it comes with a note that needs the user's attention first. An item of
+            // the local crate with the same name may just as well be one the user did not mean.
+            let applicability = match &accessible_path_strings[..] {
+                [(_, _, Some(def_id), None)] if !def_id.is_local() => {
+                    Applicability::MachineApplicable
+                }
+                _ => Applicability::MaybeIncorrect,
+            };
             for candidate in &mut accessible_path_strings {
                 // produce an additional newline to separate the new use statement
                 // from the directly following item.
@@ -2529,7 +2616,7 @@ fn show_candidates(
                 span,
                 &msg,
                 accessible_path_strings.into_iter().map(|a| a.0),
-                Applicability::MaybeIncorrect,
+                applicability,
             );
             if let [first, .., last] = &path[..] {
                 err.span_suggestion_verbose(
@@ -1492,14 +1492,18 @@ fn resolve_elided_lifetimes_in_path(
                 continue;
             }
 
-            let elided_lifetime_span = if segment.has_generic_args {
-                // If there are brackets, but not generic arguments, then use the opening bracket
-                segment.args_span.with_hi(segment.args_span.lo() + BytePos(1))
+            // HACK: we use find_ancestor_inside to properly suggest elided spans in paths
+            // originating from macros, since the segment's spans might be from a macro arg.
+            let (elided_lifetime_span, insertion_span) = if segment.has_generic_args {
+                // If there are brackets, use the opening bracket, and insert after it.
+                let args_span =
+                    segment.args_span.find_ancestor_inside(path_span).unwrap_or(segment.args_span);
+                (args_span.with_hi(args_span.lo() + BytePos(1)), args_span)
             } else {
                 // If there are no brackets, use the identifier span.
-                // HACK: we use find_ancestor_inside to properly suggest elided spans in paths
-                // originating from macros, since the segment's span might be from a macro arg.
-                segment.ident.span.find_ancestor_inside(path_span).unwrap_or(path_span)
+                let ident_span =
+                    segment.ident.span.find_ancestor_inside(path_span).unwrap_or(path_span);
+                (ident_span, ident_span)
             };
             if let LifetimeRes::Error = res {
                 let sess = self.r.session;
@@ -1515,7 +1519,7 @@ fn resolve_elided_lifetimes_in_path(
                     expected_lifetimes,
                     path_span,
                     !segment.has_generic_args,
-                    elided_lifetime_span,
+                    insertion_span,
                 );
                 err.note("assuming a `'static` lifetime...");
                 err.emit();
@@ -1529,7 +1533,7 @@ fn resolve_elided_lifetimes_in_path(
                         expected_lifetimes,
                         path_span,
                         !segment.has_generic_args,
-                        elided_lifetime_span,
+                        insertion_span,
                     ),
                 );
             }
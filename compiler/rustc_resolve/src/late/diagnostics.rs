@@ -1,4 +1,6 @@
-use crate::diagnostics::{ImportSuggestion, LabelSuggestion, TypoSuggestion};
+use crate::diagnostics::{
+    find_best_typo_suggestion, ImportSuggestion, LabelSuggestion, TypoSuggestion,
+};
 use crate::late::lifetimes::{ElisionFailureInfo, LifetimeContext};
 use crate::late::{AliasPossibility, LateResolutionVisitor, RibKind};
 use crate::late::{LifetimeBinderKind, LifetimeRibKind, LifetimeUseSet};
@@ -1351,20 +1353,38 @@ fn lookup_typo_candidate(
         filter_fn: &impl Fn(Res) -> bool,
     ) -> Option<TypoSuggestion> {
         let mut names = Vec::new();
+        let parent_module = self.parent_scope.module;
         if path.len() == 1 {
-            // Search in lexical scope.
+            // Search in lexical scope, skipping the names that hygiene hides from the path
+            // the same way `resolve_ident_in_lexical_scope` does.
+            let span = path[0].ident.span;
+            let params_ctxt = span.ctxt().normalize_to_macros_2_0();
+            let mut ctxt =
+                if ns == TypeNS { params_ctxt } else { span.ctxt().normalize_to_macro_rules() };
             // Walk backwards up the ribs in scope and collect candidates.
             for rib in self.ribs[ns].iter().rev() {
+                let rib_ctxt = if rib.kind.contains_params() { params_ctxt } else { ctxt };
                 // Locals and type parameters
                 for (ident, &res) in &rib.bindings {
-                    if filter_fn(res) {
+                    if filter_fn(res) && ident.span.ctxt() == rib_ctxt {
                         names.push(TypoSuggestion::typo_from_res(ident.name, res));
                     }
                 }
+                if let RibKind::MacroDefinition(def) = rib.kind && def == self.r.macro_def(ctxt) {
+                    // Past the definition of the macro that produced the path, only the names
+                    // visible where that macro was defined can be used.
+                    ctxt.remove_mark();
+                    continue;
+                }
                 // Items in scope
                 if let RibKind::ModuleRibKind(module) = rib.kind {
                     // Items from this module
-                    self.r.add_module_candidates(module, &mut names, &filter_fn);
+                    self.r.add_module_candidates(
+                        module,
+                        &mut names,
+                        &filter_fn,
+                        Some((ctxt, parent_module)),
+                    );
 
                     if let ModuleKind::Block(..) = module.kind {
                         // We can see through blocks
@@ -1390,7 +1410,12 @@ fn lookup_typo_candidate(
                             }));
 
                             if let Some(prelude) = self.r.prelude {
-                                self.r.add_module_candidates(prelude, &mut names, &filter_fn);
+                                self.r.add_module_candidates(
+                                    prelude,
+                                    &mut names,
+                                    &filter_fn,
+                                    None,
+                                );
                             }
                         }
                         break;
@@ -1409,24 +1434,17 @@ fn lookup_typo_candidate(
             if let PathResult::Module(ModuleOrUniformRoot::Module(module)) =
                 self.resolve_path(mod_path, Some(TypeNS), None)
             {
-                self.r.add_module_candidates(module, &mut names, &filter_fn);
+                let ctxt = path[path.len() - 1].ident.span.ctxt();
+                self.r.add_module_candidates(
+                    module,
+                    &mut names,
+                    &filter_fn,
+                    Some((ctxt, parent_module)),
+                );
             }
         }
 
-        let name = path[path.len() - 1].ident.name;
-        // Make sure error reporting is deterministic.
-        names.sort_by(|a, b| a.candidate.as_str().partial_cmp(b.candidate.as_str()).unwrap());
-
-        match find_best_match_for_name(
-            &names.iter().map(|suggestion| suggestion.candidate).collect::<Vec<Symbol>>(),
-            name,
-            None,
-        ) {
-            Some(found) if found != name => {
-                names.into_iter().find(|suggestion| suggestion.candidate == found)
-            }
-            _ => None,
-        }
+        find_best_typo_suggestion(names, path[path.len() - 1].ident.name)
     }
 
     // Returns the name of the Rust type approximately corresponding to
@@ -8,7 +8,7 @@
 
 use crate::late::diagnostics::{ForLifetimeSpanType, MissingLifetimeSpot};
 use rustc_ast::walk_list;
-use rustc_data_structures::fx::{FxHashSet, FxIndexMap, FxIndexSet};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexMap, FxIndexSet};
 use rustc_errors::struct_span_err;
 use rustc_hir as hir;
 use rustc_hir::def::{DefKind, Res};
@@ -353,6 +353,7 @@ pub fn provide(providers: &mut ty::query::Providers) {
 
         named_region_map: |tcx, id| resolve_lifetimes_for(tcx, id).defs.get(&id),
         is_late_bound_map,
+        lifetime_binding_map,
         object_lifetime_defaults: |tcx, id| match tcx.hir().find_by_def_id(id) {
             Some(Node::Item(item)) => compute_object_lifetime_defaults(tcx, item),
             _ => None,
@@ -2546,11 +2547,24 @@ fn uninsert_lifetime_on_error(&mut self, lifetime_ref: &'tcx hir::Lifetime, bad_
 /// not amongst the inputs to a projection. In other words, `<&'a
 /// T as Trait<''b>>::Foo` does not constrain `'a` or `'b`.
 fn is_late_bound_map(tcx: TyCtxt<'_>, def_id: LocalDefId) -> Option<&FxHashSet<LocalDefId>> {
+    let late_bound = tcx
+        .lifetime_binding_map(def_id)?
+        .iter()
+        .filter(|(_, binding)| binding.is_late_bound())
+        .map(|(&param_def_id, _)| param_def_id)
+        .collect();
+    Some(tcx.arena.alloc(late_bound))
+}
+
+fn lifetime_binding_map(
+    tcx: TyCtxt<'_>,
+    def_id: LocalDefId,
+) -> Option<&FxHashMap<LocalDefId, LifetimeBinding>> {
     let hir_id = tcx.hir().local_def_id_to_hir_id(def_id);
     let decl = tcx.hir().fn_decl_by_hir_id(hir_id)?;
     let generics = tcx.hir().get_generics(def_id)?;
 
-    let mut late_bound = FxHashSet::default();
+    let mut bindings = FxHashMap::default();
 
     let mut constrained_by_input = ConstrainedCollector::default();
     for arg_ty in decl.inputs {
@@ -2584,26 +2598,26 @@ fn is_late_bound_map(tcx: TyCtxt<'_>, def_id: LocalDefId) -> Option<&FxHashSet<L
 
         let param_def_id = tcx.hir().local_def_id(param.hir_id);
 
-        // appears in the where clauses? early-bound.
-        if appears_in_where_clause.regions.contains(&param_def_id) {
-            continue;
-        }
-
-        // does not appear in the inputs, but appears in the return type? early-bound.
-        if !constrained_by_input.regions.contains(&param_def_id)
-            && appears_in_output.regions.contains(&param_def_id)
-        {
-            continue;
-        }
+        let binding = if appears_in_where_clause.regions.contains(&param_def_id) {
+            // appears in the where clauses? early-bound.
+            LifetimeBinding::EarlyBoundByBounds
+        } else if constrained_by_input.regions.contains(&param_def_id) {
+            LifetimeBinding::LateBoundByInputs
+        } else if appears_in_output.regions.contains(&param_def_id) {
+            // does not appear in the inputs, but appears in the return type? early-bound.
+            LifetimeBinding::EarlyBoundByReturnType
+        } else {
+            LifetimeBinding::LateBoundUnconstrained
+        };
 
-        debug!("lifetime {:?} with id {:?} is late-bound", param.name.ident(), param.hir_id);
+        debug!("lifetime {:?} with id {:?} is {:?}", param.name.ident(), param.hir_id, binding);
 
-        let inserted = late_bound.insert(param_def_id);
-        assert!(inserted, "visited lifetime {:?} twice", param.hir_id);
+        let old = bindings.insert(param_def_id, binding);
+        assert!(old.is_none(), "visited lifetime {:?} twice", param.hir_id);
     }
 
-    debug!(?late_bound);
-    return Some(tcx.arena.alloc(late_bound));
+    debug!(?bindings);
+    return Some(tcx.arena.alloc(bindings));
 
     #[derive(Default)]
     struct ConstrainedCollector {
@@ -909,6 +909,9 @@ pub struct Resolver<'a> {
 
     /// `CrateNum` resolutions of `extern crate` items.
     extern_crate_map: FxHashMap<LocalDefId, CrateNum>,
+    /// `extern crate` items at the crate root without `#[macro_use]`, with the root module
+    /// and name of the crate they load.
+    extern_crates_without_macro_use: Vec<(Module<'a>, Symbol, Span)>,
     reexport_map: FxHashMap<LocalDefId, Vec<ModChild>>,
     trait_map: NodeMap<Vec<TraitCandidate>>,
 
@@ -1320,6 +1323,7 @@ pub fn new(
             lifetimes_res_map: Default::default(),
             extra_lifetime_params_map: Default::default(),
             extern_crate_map: Default::default(),
+            extern_crates_without_macro_use: Vec::new(),
             reexport_map: FxHashMap::default(),
             trait_map: NodeMap::default(),
             underscore_disambiguator: 0,
@@ -1963,7 +1967,7 @@ pub fn legacy_const_generic_args(&mut self, expr: &Expr) -> Option<Vec<usize>> {
 
                 let attr = self
                     .cstore()
-                    .item_attrs_untracked(def_id, self.session)
+                    .item_non_doc_attrs_untracked(def_id, self.session)
                     .find(|a| a.has_name(sym::rustc_legacy_const_generics))?;
                 let mut ret = Vec::new();
                 for meta in attr.meta_item_list()? {
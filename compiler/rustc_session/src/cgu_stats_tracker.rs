@@ -0,0 +1,95 @@
+//! Collects statistics about every codegen unit for `-Zprint-cgu-stats`: its
+//! estimated size together with its largest items, the time spent on it in the
+//! backend, and the size of the object file that was produced for it.
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::profiling::duration_to_secs_str;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Default)]
+struct CguStats {
+    size_estimate: Option<usize>,
+    largest_items: Vec<(String, usize)>,
+    backend_time: Duration,
+    object_size: Option<u64>,
+}
+
+#[derive(Clone)]
+pub struct CguStatsTracker {
+    data: Option<Arc<Mutex<FxHashMap<String, CguStats>>>>,
+}
+
+impl CguStatsTracker {
+    pub fn new() -> CguStatsTracker {
+        CguStatsTracker { data: Some(Default::default()) }
+    }
+
+    pub fn new_disabled() -> CguStatsTracker {
+        CguStatsTracker { data: None }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.data.is_some()
+    }
+
+    fn with_stats(&self, cgu_name: &str, f: impl FnOnce(&mut CguStats)) {
+        if let Some(ref data) = self.data {
+            let mut data = data.lock().unwrap();
+            f(data.entry(cgu_name.to_string()).or_default());
+        }
+    }
+
+    /// Records the estimated size of a codegen unit, and the estimated sizes of its
+    /// largest items, largest first.
+    pub fn set_size_estimate(
+        &self,
+        cgu_name: &str,
+        size_estimate: usize,
+        largest_items: Vec<(String, usize)>,
+    ) {
+        self.with_stats(cgu_name, |stats| {
+            stats.size_estimate = Some(size_estimate);
+            stats.largest_items = largest_items;
+        });
+    }
+
+    /// Adds to the time the backend spent optimizing and emitting code for a codegen unit.
+    pub fn add_backend_time(&self, cgu_name: &str, time: Duration) {
+        self.with_stats(cgu_name, |stats| stats.backend_time += time);
+    }
+
+    pub fn set_object_size(&self, cgu_name: &str, object_size: u64) {
+        self.with_stats(cgu_name, |stats| stats.object_size = Some(object_size));
+    }
+
+    /// Prints the collected statistics, largest codegen units first.
+    pub fn print(&self) {
+        if let Some(ref data) = self.data {
+            let data = data.lock().unwrap();
+
+            let mut cgus: Vec<_> = data.iter().collect();
+            cgus.sort_by(|(a_name, a), (b_name, b)| {
+                b.size_estimate.cmp(&a.size_estimate).then_with(|| a_name.cmp(b_name))
+            });
+
+            eprintln!("codegen unit stats:");
+            for (cgu_name, stats) in cgus {
+                let size_estimate =
+                    stats.size_estimate.map_or_else(|| "-".to_string(), |s| s.to_string());
+                let object_size =
+                    stats.object_size.map_or_else(|| "-".to_string(), |s| s.to_string());
+                eprintln!(
+                    "  {}: size estimate {}, backend time {}s, object size {}",
+                    cgu_name,
+                    size_estimate,
+                    duration_to_secs_str(stats.backend_time),
+                    object_size,
+                );
+                for (item, size) in &stats.largest_items {
+                    eprintln!("    {:>10} {}", size, item);
+                }
+            }
+        }
+    }
+}
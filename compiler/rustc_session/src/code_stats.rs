@@ -1,7 +1,9 @@
 use rustc_data_structures::fx::FxHashSet;
 use rustc_data_structures::sync::Lock;
 use rustc_target::abi::{Align, Size};
+use rustc_target::json::{Json, ToJson};
 use std::cmp::{self, Ordering};
+use std::collections::BTreeMap;
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct VariantInfo {
@@ -45,9 +47,118 @@ pub struct TypeSizeInfo {
     pub variants: Vec<VariantInfo>,
 }
 
+/// How the discriminant of an enum is stored, as reported by `-Zenum-layout-report`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum EnumTagEncoding {
+    /// Only one variant is inhabited, so no tag is stored at all.
+    None,
+    /// The discriminant is stored in a dedicated tag field at the start of the enum.
+    Direct { size: u64 },
+    /// The discriminant is stored in invalid values of a field of `untagged_variant`.
+    Niche { untagged_variant: String, niche_variants: u64, offset: u64, size: u64 },
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct EnumVariantLayout {
+    pub name: String,
+    /// Bytes of the enum occupied neither by the tag nor by this variant's fields.
+    pub padding: u64,
+    pub fields: Vec<FieldInfo>,
+}
+
+/// A field order that would make a `repr(C)` or primitive-`repr` enum smaller.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct EnumReorderHint {
+    /// The size of the enum if the suggested field orders were used.
+    pub size: u64,
+    /// The suggested field order, for each variant whose order would change.
+    pub variants: Vec<(String, Vec<String>)>,
+}
+
+#[derive(PartialEq, Eq, Hash, Debug)]
+pub struct EnumLayoutInfo {
+    pub type_description: String,
+    pub size: u64,
+    pub align: u64,
+    pub encoding: EnumTagEncoding,
+    /// The number of invalid values left in the largest niche of the enum, which enclosing
+    /// types may use to store their own discriminants.
+    pub available_niche_values: Option<u128>,
+    pub variants: Vec<EnumVariantLayout>,
+    pub reorder_hint: Option<EnumReorderHint>,
+}
+
+impl ToJson for FieldInfo {
+    fn to_json(&self) -> Json {
+        let mut d = BTreeMap::new();
+        d.insert("name", self.name.to_json());
+        d.insert("offset", self.offset.to_json());
+        d.insert("size", self.size.to_json());
+        d.insert("align", self.align.to_json());
+        d.to_json()
+    }
+}
+
+impl ToJson for EnumLayoutInfo {
+    fn to_json(&self) -> Json {
+        let mut encoding = BTreeMap::new();
+        match self.encoding {
+            EnumTagEncoding::None => {
+                encoding.insert("kind", "none".to_json());
+            }
+            EnumTagEncoding::Direct { size } => {
+                encoding.insert("kind", "direct".to_json());
+                encoding.insert("size", size.to_json());
+            }
+            EnumTagEncoding::Niche { ref untagged_variant, niche_variants, offset, size } => {
+                encoding.insert("kind", "niche".to_json());
+                encoding.insert("untagged_variant", untagged_variant.to_json());
+                encoding.insert("niche_variants", niche_variants.to_json());
+                encoding.insert("offset", offset.to_json());
+                encoding.insert("size", size.to_json());
+            }
+        }
+
+        let variants: Vec<_> = self
+            .variants
+            .iter()
+            .map(|variant| {
+                let mut d = BTreeMap::new();
+                d.insert("name", variant.name.to_json());
+                d.insert("padding", variant.padding.to_json());
+                d.insert("fields", variant.fields.to_json());
+                d.to_json()
+            })
+            .collect();
+
+        let mut d = BTreeMap::new();
+        d.insert("type", self.type_description.to_json());
+        d.insert("size", self.size.to_json());
+        d.insert("align", self.align.to_json());
+        d.insert("encoding", encoding.to_json());
+        // `u128` has no JSON representation, so saturate the (usually small) niche count.
+        d.insert(
+            "available_niche_values",
+            self.available_niche_values.map(|n| u64::try_from(n).unwrap_or(u64::MAX)).to_json(),
+        );
+        d.insert("variants", variants.to_json());
+        if let Some(hint) = &self.reorder_hint {
+            let mut h = BTreeMap::new();
+            h.insert("size", hint.size.to_json());
+            h.insert(
+                "variants",
+                hint.variants.iter().cloned().collect::<BTreeMap<_, _>>().to_json(),
+            );
+            d.insert("reorder_hint", h.to_json());
+        }
+        d.to_json()
+    }
+}
+
 #[derive(Default)]
 pub struct CodeStats {
     type_sizes: Lock<FxHashSet<TypeSizeInfo>>,
+    enum_layouts: Lock<FxHashSet<EnumLayoutInfo>>,
 }
 
 impl CodeStats {
@@ -77,6 +188,21 @@ pub fn record_type_size<S: ToString>(
         self.type_sizes.borrow_mut().insert(info);
     }
 
+    pub fn record_enum_layout(&self, info: EnumLayoutInfo) {
+        self.enum_layouts.borrow_mut().insert(info);
+    }
+
+    /// Prints one JSON object per recorded enum, sorted by type description.
+    pub fn print_enum_layout_report(&self) {
+        let enum_layouts = self.enum_layouts.borrow();
+        let mut sorted: Vec<_> = enum_layouts.iter().collect();
+        sorted.sort_by(|info1, info2| info1.type_description.cmp(&info2.type_description));
+
+        for info in sorted {
+            println!("{}", info.to_json());
+        }
+    }
+
     pub fn print_type_sizes(&self) {
         let type_sizes = self.type_sizes.borrow();
         let mut sorted: Vec<_> = type_sizes.iter().collect();
@@ -3,6 +3,7 @@
 
 pub use crate::options::*;
 
+use crate::lint_config::LintConfig;
 use crate::search_paths::SearchPath;
 use crate::utils::{CanonicalizedPath, NativeLib, NativeLibKind};
 use crate::{early_error, early_warn, Session};
@@ -13,19 +14,20 @@
 use rustc_data_structures::stable_hasher::ToStableHashKey;
 use rustc_target::abi::{Align, TargetDataLayout};
 use rustc_target::spec::{LinkerFlavor, SplitDebuginfo, Target, TargetTriple, TargetWarnings};
-use rustc_target::spec::{PanicStrategy, SanitizerSet, TARGETS};
+use rustc_target::spec::{PanicStrategy, SanitizerSet, TargetCapability, TARGETS};
 
 use crate::parse::{CrateCheckConfig, CrateConfig};
 use rustc_feature::UnstableFeatures;
 use rustc_span::edition::{Edition, DEFAULT_EDITION, EDITION_NAME_LIST, LATEST_STABLE_EDITION};
 use rustc_span::source_map::{FileName, FilePathMapping};
 use rustc_span::symbol::{sym, Symbol};
-use rustc_span::RealFileName;
 use rustc_span::SourceFileHashAlgorithm;
+use rustc_span::{FileNameDisplayPreference, RealFileName};
 
 use rustc_errors::emitter::HumanReadableErrorType;
 use rustc_errors::{ColorConfig, HandlerFlags};
 
+use std::cmp;
 use std::collections::btree_map::{
     Iter as BTreeMapIter, Keys as BTreeMapKeysIter, Values as BTreeMapValuesIter,
 };
@@ -140,6 +142,34 @@ pub enum MirSpanview {
     Block,
 }
 
+/// The different settings that the `-Z validate-mir` flag can have.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum MirValidationLevel {
+    /// Default `-Z validate-mir=off`: only the final optimized MIR is validated.
+    Off,
+    /// `-Z validate-mir=basic`: validate at the start and end of every phase transition.
+    Basic,
+    /// `-Z validate-mir` or `-Z validate-mir=full`: validate after every pass, additionally
+    /// checking that locals assigned exactly once are defined before all of their uses.
+    Full,
+}
+
+/// The different settings that the `-Z incremental-verify-ich` flag can have.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum IncrementalVerifyIch {
+    /// Default `-Z incremental-verify-ich=no`: only a fixed subset of the results loaded from
+    /// the incremental cache are re-hashed.
+    Off,
+    /// `-Z incremental-verify-ich` or `-Z incremental-verify-ich=full`: re-hash all results
+    /// loaded from the incremental cache.
+    Full,
+    /// `-Z incremental-verify-ich=sample` or `-Z incremental-verify-ich=sample:N`: additionally
+    /// re-execute a random sample of one in N, 32 by default, of the green queries whose results
+    /// were loaded from the incremental cache and compare fingerprints. With `sample:1`, all of
+    /// them are re-executed.
+    Sample(u64),
+}
+
 /// The different settings that the `-C instrument-coverage` flag can have.
 ///
 /// Coverage instrumentation now supports combining `-C instrument-coverage`
@@ -171,6 +201,14 @@ pub enum InstrumentCoverage {
     Off,
 }
 
+/// Extra options for `-C instrument-coverage`, set with `-Z coverage-options`.
+#[derive(Clone, Copy, PartialEq, Hash, Debug, Default)]
+pub struct CoverageOptions {
+    /// Add branch regions with separate true/false counters for every
+    /// condition of `if`/`while` expressions and of `&&`/`||` operands.
+    pub branch: bool,
+}
+
 #[derive(Clone, PartialEq, Hash, Debug)]
 pub enum LinkerPluginLto {
     LinkerPlugin(PathBuf),
@@ -374,6 +412,9 @@ pub enum ErrorOutputType {
         /// human output.
         json_rendered: HumanReadableErrorType,
     },
+    /// A SARIF log, for tools that collect the results of static analyzers, like GitHub
+    /// code scanning.
+    Sarif,
 }
 
 impl Default for ErrorOutputType {
@@ -382,6 +423,19 @@ fn default() -> Self {
     }
 }
 
+bitflags::bitflags! {
+    /// The kinds of paths that `--remap-path-prefix` applies to, chosen with
+    /// `-Zremap-path-scope`. Paths in crate metadata are always remapped.
+    pub struct RemapPathScopeComponents: u8 {
+        /// Paths expanded by `file!()` and the locations of `#[track_caller]`.
+        const MACRO = 1 << 0;
+        /// Paths printed in diagnostics.
+        const DIAGNOSTICS = 1 << 1;
+        /// Paths in debuginfo, including the paths of split debuginfo files.
+        const DEBUGINFO = 1 << 2;
+    }
+}
+
 /// Parameter to control path trimming.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub enum TrimmedDefPaths {
@@ -542,9 +596,13 @@ pub enum PrintRequest {
     CodeModels,
     TlsModels,
     TargetSpec,
+    AllTargetSpecs,
+    AllTargetCfgs,
+    TargetCapability(TargetCapability),
     NativeStaticLibs,
     StackProtectorStrategies,
     LinkArgs,
+    DeploymentTarget,
 }
 
 pub enum Input {
@@ -715,6 +773,7 @@ fn default() -> Options {
             debuginfo: DebugInfo::None,
             lint_opts: Vec::new(),
             lint_cap: None,
+            lint_config: LintConfig::default(),
             describe_lints: false,
             output_types: OutputTypes(BTreeMap::new()),
             search_paths: vec![],
@@ -756,7 +815,14 @@ pub fn build_dep_graph(&self) -> bool {
     }
 
     pub fn file_path_mapping(&self) -> FilePathMapping {
-        FilePathMapping::new(self.remap_path_prefix.clone())
+        let filename_display_for_diagnostics = if !self.remap_path_prefix.is_empty()
+            && self.debugging_opts.remap_path_scope.contains(RemapPathScopeComponents::DIAGNOSTICS)
+        {
+            FileNameDisplayPreference::Remapped
+        } else {
+            FileNameDisplayPreference::Local
+        };
+        FilePathMapping::new(self.remap_path_prefix.clone(), filename_display_for_diagnostics)
     }
 
     /// Returns `true` if there will be an output file generated.
@@ -790,6 +856,8 @@ pub fn diagnostic_handler_flags(&self, can_emit_warnings: bool) -> HandlerFlags
             report_delayed_bugs: self.report_delayed_bugs,
             macro_backtrace: self.macro_backtrace,
             deduplicate_diagnostics: self.deduplicate_diagnostics,
+            list_duplicate_errors: self.verbose,
+            max_errors: self.max_errors,
         }
     }
 }
@@ -875,26 +943,35 @@ pub const fn default_lib_output() -> CrateType {
 }
 
 fn default_configuration(sess: &Session) -> CrateConfig {
+    target_configuration(sess, &sess.target).unwrap_or_else(|err| sess.fatal(&err))
+}
+
+/// The configuration `target` would get with the options of `sess`, which is the default
+/// configuration of the crate when `target` is the target of `sess`.
+pub fn target_configuration(sess: &Session, target: &Target) -> Result<CrateConfig, String> {
     // NOTE: This should be kept in sync with `CrateCheckConfig::fill_well_known` below.
-    let end = &sess.target.endian;
-    let arch = &sess.target.arch;
-    let wordsz = sess.target.pointer_width.to_string();
-    let os = &sess.target.os;
-    let env = &sess.target.env;
-    let abi = &sess.target.abi;
-    let vendor = &sess.target.vendor;
-    let min_atomic_width = sess.target.min_atomic_width();
-    let max_atomic_width = sess.target.max_atomic_width();
-    let atomic_cas = sess.target.atomic_cas;
-    let layout = TargetDataLayout::parse(&sess.target).unwrap_or_else(|err| {
-        sess.fatal(&err);
-    });
+    let end = &target.endian;
+    let arch = &target.arch;
+    let wordsz = target.pointer_width.to_string();
+    let os = &target.os;
+    let env = &target.env;
+    let abi = &target.abi;
+    let vendor = &target.vendor;
+    let min_atomic_width = target.min_atomic_width();
+    let mut max_atomic_width = target.max_atomic_width();
+    let mut atomic_cas = target.atomic_cas;
+    if sess.opts.debugging_opts.atomic_shims && target.atomic_shim.is_some() {
+        // Shims make all atomic operations up to the pointer width available.
+        max_atomic_width = cmp::max(max_atomic_width, target.pointer_width.into());
+        atomic_cas = true;
+    }
+    let layout = TargetDataLayout::parse(target)?;
 
     let mut ret = FxHashSet::default();
     ret.reserve(7); // the minimum number of insertions
     // Target bindings.
     ret.insert((sym::target_os, Some(Symbol::intern(os))));
-    for fam in sess.target.families.as_ref() {
+    for fam in target.families.as_ref() {
         ret.insert((sym::target_family, Some(Symbol::intern(fam))));
         if fam == "windows" {
             ret.insert((sym::windows, None));
@@ -908,7 +985,7 @@ fn default_configuration(sess: &Session) -> CrateConfig {
     ret.insert((sym::target_env, Some(Symbol::intern(env))));
     ret.insert((sym::target_abi, Some(Symbol::intern(abi))));
     ret.insert((sym::target_vendor, Some(Symbol::intern(vendor))));
-    if sess.target.has_thread_local {
+    if target.has_thread_local {
         ret.insert((sym::target_thread_local, None));
     }
     for (i, align) in [
@@ -936,7 +1013,7 @@ fn default_configuration(sess: &Session) -> CrateConfig {
         }
     }
 
-    let panic_strategy = sess.panic_strategy();
+    let panic_strategy = sess.opts.cg.panic.unwrap_or(target.panic_strategy);
     ret.insert((sym::panic, Some(panic_strategy.desc_symbol())));
 
     for s in sess.opts.debugging_opts.sanitizer {
@@ -950,7 +1027,7 @@ fn default_configuration(sess: &Session) -> CrateConfig {
     if sess.opts.crate_types.contains(&CrateType::ProcMacro) {
         ret.insert((sym::proc_macro, None));
     }
-    ret
+    Ok(ret)
 }
 
 /// Converts the crate `cfg!` configuration from `String` to `Symbol`.
@@ -1344,8 +1421,9 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
             "Compiler information to print on stdout",
             "[crate-name|file-names|sysroot|target-libdir|cfg|target-list|\
              target-cpus|target-features|relocation-models|code-models|\
-             tls-models|target-spec-json|native-static-libs|stack-protector-strategies|\
-             link-args]",
+             tls-models|target-spec-json|all-target-specs-json|all-target-cfgs-json|\
+             target-capability=CAPABILITY|native-static-libs|stack-protector-strategies|\
+             link-args|deployment-target]",
         ),
         opt::flagmulti_s("g", "", "Equivalent to -C debuginfo=2"),
         opt::flagmulti_s("O", "", "Equivalent to -C opt-level=2"),
@@ -1421,6 +1499,12 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
             "Remap source names in all output (compiler messages and output files)",
             "FROM=TO",
         ),
+        opt::opt(
+            "",
+            "assemble-sysroot",
+            "Assemble a sysroot for the target out of the given library files",
+            "DIR",
+        ),
     ]);
     opts
 }
@@ -1432,7 +1516,7 @@ pub fn get_cmd_lint_options(
     let mut lint_opts_with_position = vec![];
     let mut describe_lints = false;
 
-    for level in [lint::Allow, lint::Warn, lint::ForceWarn, lint::Deny, lint::Forbid] {
+    for level in [lint::Allow, lint::Warn, lint::ForceWarn(None), lint::Deny, lint::Forbid] {
         for (arg_pos, lint_name) in matches.opt_strs_pos(level.as_str()) {
             if lint_name == "help" {
                 describe_lints = true;
@@ -1577,6 +1661,7 @@ pub fn parse_error_format(
             }
             Some("json") => ErrorOutputType::Json { pretty: false, json_rendered },
             Some("pretty-json") => ErrorOutputType::Json { pretty: true, json_rendered },
+            Some("sarif") => ErrorOutputType::Sarif,
             Some("short") => ErrorOutputType::HumanReadable(HumanReadableErrorType::Short(color)),
 
             Some(arg) => early_error(
@@ -1660,6 +1745,12 @@ fn check_debug_option_stability(
                 "`--error-format=human-annotate-rs` is unstable",
             );
         }
+        if let ErrorOutputType::Sarif = error_format {
+            early_error(
+                ErrorOutputType::Json { pretty: false, json_rendered },
+                "`--error-format=sarif` is unstable",
+            );
+        }
     }
 }
 
@@ -1794,7 +1885,53 @@ fn collect_print_requests(
                 );
             }
         }
+        "all-target-specs-json" => {
+            if dopts.unstable_options {
+                PrintRequest::AllTargetSpecs
+            } else {
+                early_error(
+                    error_format,
+                    "the `-Z unstable-options` flag must also be passed to \
+                     enable the all-target-specs-json print option",
+                );
+            }
+        }
+        "all-target-cfgs-json" => {
+            if dopts.unstable_options {
+                PrintRequest::AllTargetCfgs
+            } else {
+                early_error(
+                    error_format,
+                    "the `-Z unstable-options` flag must also be passed to \
+                     enable the all-target-cfgs-json print option",
+                );
+            }
+        }
         "link-args" => PrintRequest::LinkArgs,
+        "deployment-target" => PrintRequest::DeploymentTarget,
+        req if let Some(cap) = req.strip_prefix("target-capability=") => {
+            if !dopts.unstable_options {
+                early_error(
+                    error_format,
+                    "the `-Z unstable-options` flag must also be passed to \
+                     enable the target-capability print option",
+                );
+            }
+            match cap.parse() {
+                Ok(cap) => PrintRequest::TargetCapability(cap),
+                Err(()) => {
+                    let valid = TargetCapability::ALL
+                        .iter()
+                        .map(|cap| cap.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    early_error(
+                        error_format,
+                        &format!("unknown target capability `{cap}`, expected one of: {valid}"),
+                    )
+                }
+            }
+        }
         req => early_error(error_format, &format!("unknown print request `{req}`")),
     }));
 
@@ -2217,6 +2354,11 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
 
     check_debug_option_stability(&debugging_opts, error_format, json_rendered);
 
+    let lint_config = match &debugging_opts.lint_config {
+        Some(path) => LintConfig::load(path).unwrap_or_else(|e| early_error(error_format, &e)),
+        None => LintConfig::default(),
+    };
+
     if !debugging_opts.unstable_options && json_unused_externs.is_enabled() {
         early_error(
             error_format,
@@ -2242,6 +2384,10 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
     let assert_incr_state =
         parse_assert_incr_state(&debugging_opts.assert_incr_state, error_format);
 
+    if debugging_opts.dump_dep_graph_json.is_some() && incremental.is_none() {
+        early_error(error_format, "`-Z dump-dep-graph-json` requires `-C incremental`");
+    }
+
     if debugging_opts.profile && incremental.is_some() {
         early_error(
             error_format,
@@ -2415,8 +2561,12 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
 
     let pretty = parse_pretty(&debugging_opts, error_format);
 
+    // `packed` (and `off`) are stable on Linux, where a `*.dwp` is produced with `thorin`.
+    let split_debuginfo_is_stable = target_triple.triple().contains("apple")
+        || (target_triple.triple().contains("linux")
+            && matches!(cg.split_debuginfo, Some(SplitDebuginfo::Off | SplitDebuginfo::Packed)));
     if !debugging_opts.unstable_options
-        && !target_triple.triple().contains("apple")
+        && !split_debuginfo_is_stable
         && cg.split_debuginfo.is_some()
     {
         early_error(error_format, "`-Csplit-debuginfo` is unstable on this platform");
@@ -2454,8 +2604,11 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         early_error(error_format, &format!("Current directory is invalid: {e}"));
     });
 
-    let (path, remapped) =
-        FilePathMapping::new(remap_path_prefix.clone()).map_prefix(working_dir.clone());
+    let (path, remapped) = FilePathMapping::new(
+        remap_path_prefix.clone(),
+        FileNameDisplayPreference::Remapped,
+    )
+    .map_prefix(working_dir.clone());
     let working_dir = if remapped {
         RealFileName::Remapped { local_path: Some(working_dir), virtual_name: path }
     } else {
@@ -2469,6 +2622,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         debuginfo,
         lint_opts,
         lint_cap,
+        lint_config,
         describe_lints,
         output_types,
         search_paths,
@@ -2728,12 +2882,13 @@ pub fn needs_analysis(&self) -> bool {
 /// how the hash should be calculated when adding a new command-line argument.
 pub(crate) mod dep_tracking {
     use super::{
-        BranchProtection, CFGuard, CFProtection, CrateType, DebugInfo, ErrorOutputType,
-        InstrumentCoverage, LdImpl, LinkerPluginLto, LocationDetail, LtoCli, OomStrategy, OptLevel,
-        OutputType, OutputTypes, Passes, SourceFileHashAlgorithm, SwitchWithOptPath,
-        SymbolManglingVersion, TrimmedDefPaths,
+        BranchProtection, CFGuard, CFProtection, CoverageOptions, CrateType, DebugInfo,
+        ErrorOutputType, InstrumentCoverage, LdImpl, LinkerPluginLto, LocationDetail, LtoCli,
+        OomStrategy, OptLevel, OutputType, OutputTypes, Passes, RemapPathScopeComponents,
+        SourceFileHashAlgorithm, SwitchWithOptPath, SymbolManglingVersion, TrimmedDefPaths,
     };
     use crate::lint;
+    use crate::lint_config::LintConfig;
     use crate::options::WasiExecModel;
     use crate::utils::{NativeLib, NativeLibKind};
     use rustc_errors::LanguageIdentifier;
@@ -2794,12 +2949,14 @@ fn hash(
         String,
         PathBuf,
         lint::Level,
+        LintConfig,
         WasiExecModel,
         u32,
         RelocModel,
         CodeModel,
         TlsModel,
         InstrumentCoverage,
+        CoverageOptions,
         CrateType,
         MergeFunctions,
         PanicStrategy,
@@ -2823,6 +2980,7 @@ fn hash(
         SymbolManglingVersion,
         SourceFileHashAlgorithm,
         TrimmedDefPaths,
+        RemapPathScopeComponents,
         Option<LdImpl>,
         OutputType,
         RealFileName,
@@ -12,6 +12,7 @@
 extern crate rustc_macros;
 
 pub mod cgu_reuse_tracker;
+pub mod cgu_stats_tracker;
 pub mod utils;
 pub use lint::{declare_lint, declare_lint_pass, declare_tool_lint, impl_lint_pass};
 pub use rustc_lint_defs as lint;
@@ -22,6 +23,7 @@
 pub mod config;
 pub mod cstore;
 pub mod filesearch;
+pub mod lint_config;
 mod options;
 pub mod search_paths;
 
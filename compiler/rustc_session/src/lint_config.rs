@@ -0,0 +1,131 @@
+//! The lint configuration file passed with `-Zlint-config`.
+//!
+//! The file is a TOML document with a single `[lints]` table, which maps lint and lint group
+//! names to either a level or a table with an optional `level` and options of the lint:
+//!
+//! ```toml
+//! [lints]
+//! unused = "deny"
+//! unused-variables = "allow"
+//! "clippy::pedantic" = "warn"
+//! large-assignments = { level = "deny", limit = 4096 }
+//! ```
+//!
+//! The levels apply on top of the ones from the command line, and can be overridden with
+//! attributes. Group levels apply before the levels of individual lints, so the order of the
+//! entries doesn't matter.
+
+use crate::lint::Level;
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// The value of a lint option from the lint configuration file.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum LintOptionValue {
+    Integer(u64),
+    Bool(bool),
+    String(String),
+}
+
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct LintConfig {
+    /// The lint levels, with the names normalized like on the command line, in the order in
+    /// which they appear in the file.
+    pub levels: Vec<(String, Level)>,
+    /// The options of individual lints, by normalized lint name and option name.
+    options: BTreeMap<String, BTreeMap<String, LintOptionValue>>,
+}
+
+impl LintConfig {
+    /// Reads the lint configuration file at `path`.
+    pub fn load(path: &Path) -> Result<LintConfig, String> {
+        let contents = fs::read_to_string(path).map_err(|err| {
+            format!("failed to read lint configuration file `{}`: {}", path.display(), err)
+        })?;
+        LintConfig::parse(&contents)
+            .map_err(|err| format!("invalid lint configuration file `{}`: {}", path.display(), err))
+    }
+
+    fn parse(contents: &str) -> Result<LintConfig, String> {
+        let value: toml::Value = contents.parse().map_err(|err| format!("{}", err))?;
+        let mut config = LintConfig::default();
+        let Some(document) = value.as_table() else {
+            return Ok(config);
+        };
+        for (key, value) in document {
+            let lints = match (key.as_str(), value) {
+                ("lints", toml::Value::Table(lints)) => lints,
+                ("lints", _) => return Err("`lints` must be a table".to_owned()),
+                (key, _) => return Err(format!("unknown key `{}`", key)),
+            };
+            for (name, entry) in lints {
+                // Lint names are normalized like the ones of `-A` and friends.
+                let name = name.replace('-', "_");
+                match entry {
+                    toml::Value::String(level) => {
+                        config.levels.push((name.clone(), parse_level(&name, level)?));
+                    }
+                    toml::Value::Table(table) => {
+                        for (option, value) in table {
+                            if option == "level" {
+                                let level = value.as_str().ok_or_else(|| {
+                                    format!("the level of `{}` must be a string", name)
+                                })?;
+                                config.levels.push((name.clone(), parse_level(&name, level)?));
+                                continue;
+                            }
+                            let value = match value {
+                                toml::Value::Integer(n) if *n >= 0 => {
+                                    LintOptionValue::Integer(*n as u64)
+                                }
+                                toml::Value::Boolean(b) => LintOptionValue::Bool(*b),
+                                toml::Value::String(s) => LintOptionValue::String(s.clone()),
+                                _ => {
+                                    return Err(format!(
+                                        "option `{}` of `{}` must be a non-negative integer, \
+                                         a boolean or a string",
+                                        option, name
+                                    ));
+                                }
+                            };
+                            config
+                                .options
+                                .entry(name.clone())
+                                .or_default()
+                                .insert(option.replace('-', "_"), value);
+                        }
+                    }
+                    _ => {
+                        return Err(format!("`{}` must be set to a level or a table", name));
+                    }
+                }
+            }
+        }
+        Ok(config)
+    }
+
+    /// The value of the option `option` of the lint `lint`, with both names written with
+    /// underscores.
+    pub fn option(&self, lint: &str, option: &str) -> Option<&LintOptionValue> {
+        self.options.get(lint)?.get(option)
+    }
+
+    /// Like [`LintConfig::option`], for options that are integers.
+    pub fn integer_option(&self, lint: &str, option: &str) -> Option<u64> {
+        match self.option(lint, option)? {
+            LintOptionValue::Integer(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+fn parse_level(name: &str, level: &str) -> Result<Level, String> {
+    Level::from_str(level).ok_or_else(|| {
+        format!(
+            "unknown level `{}` for `{}`, expected one of `allow`, `warn`, `deny` or `forbid`",
+            level, name
+        )
+    })
+}
@@ -2,9 +2,11 @@
 
 use crate::early_error;
 use crate::lint;
+use crate::lint_config::LintConfig;
 use crate::search_paths::SearchPath;
 use crate::utils::NativeLib;
-use rustc_errors::LanguageIdentifier;
+use rustc_data_structures::profiling::TimePassesFormat;
+use rustc_errors::{LanguageIdentifier, TerminalUrl};
 use rustc_target::spec::{CodeModel, LinkerFlavor, MergeFunctions, PanicStrategy, SanitizerSet};
 use rustc_target::spec::{
     RelocModel, RelroLevel, SplitDebuginfo, StackProtector, TargetTriple, TlsModel,
@@ -122,6 +124,10 @@ pub fn instrument_coverage_except_unused_functions(&self) -> bool {
         self.cg.instrument_coverage.unwrap_or(InstrumentCoverage::Off)
             == InstrumentCoverage::ExceptUnusedFunctions
     }
+
+    pub fn instrument_coverage_branch(&self) -> bool {
+        self.instrument_coverage() && self.debugging_opts.coverage_options.branch
+    }
 }
 
 top_level_options!(
@@ -160,6 +166,8 @@ pub struct Options {
         debuginfo: DebugInfo [TRACKED],
         lint_opts: Vec<(String, lint::Level)> [TRACKED_NO_CRATE_HASH],
         lint_cap: Option<lint::Level> [TRACKED_NO_CRATE_HASH],
+        /// The contents of the `-Zlint-config` file.
+        lint_config: LintConfig [TRACKED_NO_CRATE_HASH],
         describe_lints: bool [UNTRACKED],
         output_types: OutputTypes [TRACKED],
         search_paths: Vec<SearchPath> [UNTRACKED],
@@ -376,7 +384,9 @@ mod desc {
     pub const parse_opt_panic_strategy: &str = parse_panic_strategy;
     pub const parse_oom_strategy: &str = "either `panic` or `abort`";
     pub const parse_relro_level: &str = "one of: `full`, `partial`, or `off`";
-    pub const parse_sanitizers: &str = "comma separated list of sanitizers: `address`, `cfi`, `hwaddress`, `leak`, `memory`, `memtag`, or `thread`";
+    pub const parse_remap_path_scope: &str =
+        "comma separated list of scopes: `macro`, `diagnostics`, `debuginfo`, or `all`";
+    pub const parse_sanitizers: &str = "comma separated list of sanitizers: `address`, `cfi`, `hwaddress`, `kcfi`, `leak`, `memory`, `memtag`, or `thread`";
     pub const parse_sanitizer_memory_track_origins: &str = "0, 1, or 2";
     pub const parse_cfguard: &str =
         "either a boolean (`yes`, `no`, `on`, `off`, etc), `checks`, or `nochecks`";
@@ -385,8 +395,13 @@ mod desc {
     pub const parse_linker_flavor: &str = ::rustc_target::spec::LinkerFlavor::one_of();
     pub const parse_optimization_fuel: &str = "crate=integer";
     pub const parse_mir_spanview: &str = "`statement` (default), `terminator`, or `block`";
+    pub const parse_incremental_verify_ich: &str =
+        "`no` (default), `full` (equivalent to passing no value), `sample`, or `sample:N`";
+    pub const parse_mir_validation_level: &str =
+        "`off` (default), `basic`, or `full` (equivalent to passing no value)";
     pub const parse_instrument_coverage: &str =
         "`all` (default), `except-unused-generics`, `except-unused-functions`, or `off`";
+    pub const parse_coverage_options: &str = "comma separated list of `branch` or `no-branch`";
     pub const parse_unpretty: &str = "`string` or `string=string`";
     pub const parse_treat_err_as_bug: &str = "either no value or a number bigger than 0";
     pub const parse_lto: &str =
@@ -415,6 +430,9 @@ mod desc {
         "one of (`none` (default), `basic`, `strong`, or `all`)";
     pub const parse_branch_protection: &str =
         "a `,` separated combination of `bti`, `b-key`, `pac-ret`, or `leaf`";
+    pub const parse_terminal_url: &str =
+        "either a boolean (`yes`, `no`, `on`, `off`, etc), or `auto`";
+    pub const parse_time_passes_format: &str = "`text` (default) or `json`";
 }
 
 mod parse {
@@ -673,6 +691,27 @@ pub(crate) fn parse_relro_level(slot: &mut Option<RelroLevel>, v: Option<&str>)
         true
     }
 
+    pub(crate) fn parse_remap_path_scope(
+        slot: &mut RemapPathScopeComponents,
+        v: Option<&str>,
+    ) -> bool {
+        if let Some(v) = v {
+            *slot = RemapPathScopeComponents::empty();
+            for s in v.split(',') {
+                *slot |= match s {
+                    "macro" => RemapPathScopeComponents::MACRO,
+                    "diagnostics" => RemapPathScopeComponents::DIAGNOSTICS,
+                    "debuginfo" => RemapPathScopeComponents::DEBUGINFO,
+                    "all" => RemapPathScopeComponents::all(),
+                    _ => return false,
+                }
+            }
+            true
+        } else {
+            false
+        }
+    }
+
     pub(crate) fn parse_sanitizers(slot: &mut SanitizerSet, v: Option<&str>) -> bool {
         if let Some(v) = v {
             for s in v.split(',') {
@@ -684,6 +723,7 @@ pub(crate) fn parse_sanitizers(slot: &mut SanitizerSet, v: Option<&str>) -> bool
                     "memtag" => SanitizerSet::MEMTAG,
                     "thread" => SanitizerSet::THREAD,
                     "hwaddress" => SanitizerSet::HWADDRESS,
+                    "kcfi" => SanitizerSet::KCFI,
                     _ => return false,
                 }
             }
@@ -822,6 +862,67 @@ pub(crate) fn parse_mir_spanview(slot: &mut Option<MirSpanview>, v: Option<&str>
         true
     }
 
+    pub(crate) fn parse_incremental_verify_ich(
+        slot: &mut IncrementalVerifyIch,
+        v: Option<&str>,
+    ) -> bool {
+        if v.is_some() {
+            let mut bool_arg = None;
+            if parse_opt_bool(&mut bool_arg, v) {
+                *slot = if bool_arg.unwrap() {
+                    IncrementalVerifyIch::Full
+                } else {
+                    IncrementalVerifyIch::Off
+                };
+                return true;
+            }
+        }
+
+        *slot = match v {
+            None | Some("full") => IncrementalVerifyIch::Full,
+            Some("sample") => IncrementalVerifyIch::Sample(32),
+            Some(v) => match v.strip_prefix("sample:").and_then(|rate| rate.parse().ok()) {
+                Some(rate) if rate > 0 => IncrementalVerifyIch::Sample(rate),
+                _ => return false,
+            },
+        };
+        true
+    }
+
+    pub(crate) fn parse_mir_validation_level(
+        slot: &mut MirValidationLevel,
+        v: Option<&str>,
+    ) -> bool {
+        if v.is_some() {
+            let mut bool_arg = None;
+            if parse_opt_bool(&mut bool_arg, v) {
+                *slot =
+                    if bool_arg.unwrap() { MirValidationLevel::Full } else { MirValidationLevel::Off };
+                return true;
+            }
+        }
+
+        *slot = match v {
+            None | Some("full") => MirValidationLevel::Full,
+            Some("basic") => MirValidationLevel::Basic,
+            _ => return false,
+        };
+        true
+    }
+
+    pub(crate) fn parse_coverage_options(slot: &mut CoverageOptions, v: Option<&str>) -> bool {
+        let Some(v) = v else { return false };
+
+        for option in v.split(',') {
+            match option {
+                "branch" => slot.branch = true,
+                "no-branch" | "no_branch" => slot.branch = false,
+                _ => return false,
+            }
+        }
+        true
+    }
+
     pub(crate) fn parse_instrument_coverage(
         slot: &mut Option<InstrumentCoverage>,
         v: Option<&str>,
@@ -1061,6 +1162,35 @@ pub(crate) fn parse_branch_protection(
         }
         true
     }
+
+    pub(crate) fn parse_terminal_url(slot: &mut TerminalUrl, v: Option<&str>) -> bool {
+        let mut bool_arg = None;
+        if parse_opt_bool(&mut bool_arg, v) {
+            *slot = if bool_arg.unwrap() { TerminalUrl::Yes } else { TerminalUrl::No };
+            return true;
+        }
+
+        *slot = match v {
+            Some("auto") => TerminalUrl::Auto,
+            _ => return false,
+        };
+        true
+    }
+
+    pub(crate) fn parse_time_passes_format(slot: &mut TimePassesFormat, v: Option<&str>) -> bool {
+        match v {
+            None => false,
+            Some("text") => {
+                *slot = TimePassesFormat::Text;
+                true
+            }
+            Some("json") => {
+                *slot = TimePassesFormat::Json;
+                true
+            }
+            Some(_) => false,
+        }
+    }
 }
 
 options! {
@@ -1204,6 +1334,9 @@ pub(crate) fn parse_branch_protection(
     assert_incr_state: Option<String> = (None, parse_opt_string, [UNTRACKED],
         "assert that the incremental cache is in given state: \
          either `loaded` or `not-loaded`."),
+    atomic_shims: bool = (false, parse_bool, [TRACKED],
+        "lower atomic operations that the target doesn't support natively to the shim \
+        selected by the target spec (default: no)"),
     binary_dep_depinfo: bool = (false, parse_bool, [TRACKED],
         "include artifacts (sysroot, crate dependencies) used during compilation in dep-info \
         (default: no)"),
@@ -1212,13 +1345,23 @@ pub(crate) fn parse_branch_protection(
     cf_protection: CFProtection = (CFProtection::None, parse_cfprotection, [TRACKED],
         "instrument control-flow architecture protection"),
     cgu_partitioning_strategy: Option<String> = (None, parse_opt_string, [TRACKED],
-        "the codegen unit partitioning strategy to use"),
+        "the codegen unit partitioning strategy to use: `default` or `affinity` \
+        (default: `default`)"),
     chalk: bool = (false, parse_bool, [TRACKED],
         "enable the experimental Chalk-based trait solving engine"),
     codegen_backend: Option<String> = (None, parse_opt_string, [TRACKED],
         "the backend to use"),
     combine_cgu: bool = (false, parse_bool, [TRACKED],
         "combine CGUs into a single one"),
+    const_eval_frame_args: bool = (false, parse_bool, [TRACKED],
+        "show the values of scalar arguments for each frame of a const evaluation \
+        backtrace (default: no)"),
+    const_eval_step_budget: usize = (2_000_000, parse_number, [TRACKED],
+        "report `long_running_const_eval` after this many const evaluation steps, and again \
+        every time the number of steps doubles (0 = never report) (default: 2000000)"),
+    coverage_options: CoverageOptions = (CoverageOptions::default(), parse_coverage_options, [TRACKED],
+        "control details of `-C instrument-coverage`: `branch` adds branch coverage regions \
+        (default: no-branch)"),
     crate_attr: Vec<String> = (Vec::new(), parse_string_push, [TRACKED],
         "inject the given attribute in the crate"),
     debug_info_for_profiling: bool = (false, parse_bool, [TRACKED],
@@ -1242,9 +1385,20 @@ pub(crate) fn parse_branch_protection(
         "enables drop tracking in generators (default: no)"),
     dual_proc_macros: bool = (false, parse_bool, [TRACKED],
         "load proc macros for both target and host, but only link to the target (default: no)"),
+    dylib_lto: bool = (false, parse_bool, [TRACKED],
+        "enables LTO for the dylib crate type (default: no)"),
     dump_dep_graph: bool = (false, parse_bool, [UNTRACKED],
         "dump the dependency graph to $RUST_DEP_GRAPH (default: /tmp/dep_graph.gv) \
         (default: no)"),
+    dump_dep_graph_json: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write the nodes and edges of the dependency graph to this file as JSON \
+        (requires `-C incremental`)"),
+    dump_dep_graph_json_filter: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "only dump the dependency graph nodes of these comma-separated query kinds or \
+        definition paths with `-Z dump-dep-graph-json`, and the edges between them"),
+    dump_ice: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "when the compiler panics, write the query stack, the enabled features, the compiler \
+        flags and the source of the failing item to this file"),
     dump_mir: Option<String> = (None, parse_opt_string, [UNTRACKED],
         "dump MIR state to file.
         `val` is used to select which passes and functions to dump. For example:
@@ -1271,13 +1425,25 @@ pub(crate) fn parse_branch_protection(
         an additional `.html` file showing the computed coverage spans."),
     emit_stack_sizes: bool = (false, parse_bool, [UNTRACKED],
         "emit a section containing stack size metadata (default: no)"),
+    enum_layout_report: bool = (false, parse_bool, [TRACKED],
+        "print a JSON report of the tag encoding, niche usage and padding of each enum used \
+        in optimized MIR (default: no)"),
+    extra_const_ub_checks: bool = (false, parse_bool, [TRACKED],
+        "check that `bool`, `char`, enum and `NonZero` values are valid when they are loaded or \
+        transmuted to during const evaluation, not only in the final value (default: no)"),
     fewer_names: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "reduce memory use by retaining fewer names within compilation artifacts (LLVM-IR) \
         (default: no)"),
+    flatten_format_args: bool = (true, parse_bool, [TRACKED],
+        "flatten nested `format_args!()` calls and inline literal arguments into the format \
+        string (default: yes)"),
     force_unstable_if_unmarked: bool = (false, parse_bool, [TRACKED],
         "force all crates to be `rustc_private` unstable (default: no)"),
     fuel: Option<(String, u64)> = (None, parse_optimization_fuel, [TRACKED],
         "set the optimization fuel quota for a crate"),
+    full_type_diffs: bool = (false, parse_bool, [UNTRACKED],
+        "print both types in full in expected/found notes instead of eliding the parts they \
+        have in common (default: no)"),
     function_sections: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "whether each function should go in its own section"),
     future_incompat_test: bool = (false, parse_bool, [UNTRACKED],
@@ -1290,19 +1456,30 @@ pub(crate) fn parse_branch_protection(
         environment variable `RUSTC_GRAPHVIZ_FONT` (default: `Courier, monospace`)"),
     hir_stats: bool = (false, parse_bool, [UNTRACKED],
         "print some statistics about AST and HIR (default: no)"),
+    hir_stats_top_items: Option<usize> = (None, parse_opt_number, [UNTRACKED],
+        "with `-Z hir-stats`, also print the N items with the largest AST and HIR nodes"),
     human_readable_cgu_names: bool = (false, parse_bool, [TRACKED],
         "generate human-readable, predictable names for codegen units (default: no)"),
     identify_regions: bool = (false, parse_bool, [UNTRACKED],
         "display unnamed regions as `'<id>`, using a non-ident unique id (default: no)"),
+    incremental_gc_sessions: Option<usize> = (None, parse_opt_number, [UNTRACKED],
+        "delete the incremental caches of a crate that were not used in the last N sessions \
+        compiling a crate of the same name in the same incremental directory"),
     incremental_ignore_spans: bool = (false, parse_bool, [UNTRACKED],
         "ignore spans during ICH computation -- used for testing (default: no)"),
     incremental_info: bool = (false, parse_bool, [UNTRACKED],
         "print high-level information about incremental reuse (or the lack thereof) \
         (default: no)"),
+    incremental_max_size: Option<u64> = (None, parse_opt_number, [UNTRACKED],
+        "limit the size of the incremental directory to N MiB by deleting the least recently \
+        used caches"),
     incremental_relative_spans: bool = (false, parse_bool, [TRACKED],
         "hash spans relative to their parent item for incr. comp. (default: no)"),
-    incremental_verify_ich: bool = (false, parse_bool, [UNTRACKED],
-        "verify incr. comp. hashes of green query instances (default: no)"),
+    incremental_verify_ich: IncrementalVerifyIch = (IncrementalVerifyIch::Off,
+        parse_incremental_verify_ich, [UNTRACKED],
+        "verify incr. comp. hashes of green query instances loaded from the cache (`full`), \
+        or also re-execute a random sample of one in 32 of them, or one in N with `sample:N`, \
+        and compare the results (`sample`) (default: no)"),
     inline_mir: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "enable MIR inlining (default: no)"),
     inline_mir_threshold: Option<usize> = (None, parse_opt_number, [TRACKED],
@@ -1329,6 +1506,9 @@ pub(crate) fn parse_branch_protection(
         "link native libraries in the linker invocation (default: yes)"),
     link_only: bool = (false, parse_bool, [TRACKED],
         "link the `.rlink` file generated by `-Z no-link` (default: no)"),
+    // The contents of the file are tracked with `Options::lint_config`.
+    lint_config: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "read lint levels and lint options from a TOML file"),
     llvm_plugins: Vec<String> = (Vec::new(), parse_list, [TRACKED],
         "a list LLVM plugins to enable (space separated)"),
     llvm_time_trace: bool = (false, parse_bool, [UNTRACKED],
@@ -1340,6 +1520,9 @@ pub(crate) fn parse_branch_protection(
         "list the symbols defined by a library crate (default: no)"),
     macro_backtrace: bool = (false, parse_bool, [UNTRACKED],
         "show macro backtraces (default: no)"),
+    max_errors: Option<usize> = (None, parse_opt_number, [UNTRACKED],
+        "print only the first `N` errors and summarize the remaining ones by error code and \
+        file; JSON output is not truncated (default: no limit)"),
     merge_functions: Option<MergeFunctions> = (None, parse_merge_functions, [TRACKED],
         "control the operation of the MergeFunctions LLVM pass, taking \
         the same values as the target option of the same name"),
@@ -1396,6 +1579,11 @@ pub(crate) fn parse_branch_protection(
         "parse only; do not compile, assemble, or link (default: no)"),
     perf_stats: bool = (false, parse_bool, [UNTRACKED],
         "print some performance-related statistics (default: no)"),
+    pgo_value_profiling: Option<bool> = (None, parse_opt_bool, [TRACKED],
+        "control value profiling of indirect calls with `-C profile-generate`, and promotion of \
+        hot indirect calls to direct calls with `-C profile-use`; when explicitly enabled, \
+        vtables also keep their symbol names so that profiles can be matched \
+        (default: LLVM's default)"),
     pick_stable_methods_before_any_unstable: bool = (true, parse_bool, [TRACKED],
         "try to pick stable methods first before picking any unstable methods (default: yes)"),
     plt: Option<bool> = (None, parse_opt_bool, [TRACKED],
@@ -1414,6 +1602,9 @@ pub(crate) fn parse_branch_protection(
         "use a more precise version of drop elaboration for matches on enums (default: yes). \
         This results in better codegen, but has caused miscompilations on some tier 2 platforms. \
         See #77382 and #74551."),
+    print_cgu_stats: bool = (false, parse_bool, [UNTRACKED],
+        "print the estimated size, backend time and object file size of each codegen unit, \
+        together with its largest items (default: no)"),
     print_fuel: Option<String> = (None, parse_opt_string, [TRACKED],
         "make rustc print the total optimization fuel used by a crate"),
     print_llvm_passes: bool = (false, parse_bool, [UNTRACKED],
@@ -1447,6 +1638,9 @@ pub(crate) fn parse_branch_protection(
         "choose which RELRO level to use"),
     remap_cwd_prefix: Option<PathBuf> = (None, parse_opt_pathbuf, [TRACKED],
         "remap paths under the current working directory to this path prefix"),
+    remap_path_scope: RemapPathScopeComponents = (RemapPathScopeComponents::all(),
+        parse_remap_path_scope, [TRACKED_NO_CRATE_HASH],
+        "the kinds of paths that `--remap-path-prefix` applies to (default: all)"),
     simulate_remapped_rust_src_base: Option<PathBuf> = (None, parse_opt_pathbuf, [TRACKED],
         "simulate the effect of remap-debuginfo = true at bootstrapping by remapping path \
         to rust's source base directory. only meant for testing purposes"),
@@ -1464,6 +1658,9 @@ pub(crate) fn parse_branch_protection(
     save_analysis: bool = (false, parse_bool, [UNTRACKED],
         "write syntax and type analysis (in JSON format) information, in \
         addition to normal output (default: no)"),
+    select_ld: bool = (false, parse_bool, [TRACKED],
+        "when linking with `cc` for an ELF target, use the best of lld, mold, gold and bfd that is \
+        installed, and retry with the next one if linking fails (default: no)"),
     self_profile: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
         parse_switch_with_opt_path, [UNTRACKED],
         "run the self profiler and output the raw event data"),
@@ -1508,6 +1705,13 @@ pub(crate) fn parse_branch_protection(
         "show extended diagnostic help (default: no)"),
     temps_dir: Option<String> = (None, parse_opt_string, [UNTRACKED],
         "the directory the intermediate files are written to"),
+    terminal_urls: TerminalUrl = (TerminalUrl::Auto, parse_terminal_url, [UNTRACKED],
+        "print error codes, lint names and primary source locations as terminal hyperlinks \
+        (OSC 8) (default: `auto`, which enables them for terminals known to support them)"),
+    terminal_urls_base: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "base URL of the documentation that error codes and lint names link to when printed \
+        as terminal hyperlinks, e.g. a `file://` URL of a local copy \
+        (default: `https://doc.rust-lang.org`)"),
     terminal_width: Option<usize> = (None, parse_opt_number, [UNTRACKED],
         "set the current terminal width"),
     // Diagnostics are considered side-effects of a query (see `QuerySideEffects`) and are saved
@@ -1537,6 +1741,8 @@ pub(crate) fn parse_branch_protection(
         "measure time of each LLVM pass (default: no)"),
     time_passes: bool = (false, parse_bool, [UNTRACKED],
         "measure time of each rustc pass (default: no)"),
+    time_passes_format: TimePassesFormat = (TimePassesFormat::Text, parse_time_passes_format, [UNTRACKED],
+        "the format to use for -Z time-passes (`text` (default) or `json`)"),
     tls_model: Option<TlsModel> = (None, parse_tls_model, [TRACKED],
         "choose the TLS model to use (`rustc --print tls-models` for details)"),
     trace_macros: bool = (false, parse_bool, [UNTRACKED],
@@ -1571,14 +1777,19 @@ pub(crate) fn parse_branch_protection(
         "adds unstable command line options to rustc interface (default: no)"),
     use_ctors_section: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "use legacy .ctors section for initializers rather than .init_array"),
-    validate_mir: bool = (false, parse_bool, [UNTRACKED],
-        "validate MIR after each transformation"),
+    validate_mir: MirValidationLevel = (MirValidationLevel::Off, parse_mir_validation_level,
+        [UNTRACKED],
+        "validate MIR at phase transitions (`basic`) or after each transformation (`full`) \
+        (default: off)"),
     verbose: bool = (false, parse_bool, [UNTRACKED],
         "in general, enable more debug printouts (default: no)"),
     verify_llvm_ir: bool = (false, parse_bool, [TRACKED],
         "verify LLVM IR (default: no)"),
     wasi_exec_model: Option<WasiExecModel> = (None, parse_wasi_exec_model, [TRACKED],
         "whether to build a wasi command or reactor"),
+    wasm_exception_handling: bool = (false, parse_bool, [TRACKED],
+        "use the WebAssembly exception handling instructions for unwinding, instead of \
+        calling into JavaScript (only supported on emscripten targets) (default: no)"),
 
     // This list is in alphabetical order.
     //
@@ -1,7 +1,8 @@
 use crate::cgu_reuse_tracker::CguReuseTracker;
+use crate::cgu_stats_tracker::CguStatsTracker;
 use crate::code_stats::CodeStats;
 pub use crate::code_stats::{DataTypeKind, FieldInfo, SizeKind, VariantInfo};
-use crate::config::{self, CrateType, OutputType, SwitchWithOptPath};
+use crate::config::{self, CrateType, OutputType, RemapPathScopeComponents, SwitchWithOptPath};
 use crate::parse::ParseSess;
 use crate::search_paths::{PathKind, SearchPath};
 use crate::{filesearch, lint};
@@ -19,6 +20,7 @@
 use rustc_errors::emitter::{Emitter, EmitterWriter, HumanReadableErrorType};
 use rustc_errors::json::JsonEmitter;
 use rustc_errors::registry::Registry;
+use rustc_errors::sarif::SarifEmitter;
 use rustc_errors::{
     fallback_fluent_bundle, DiagnosticBuilder, DiagnosticId, DiagnosticMessage, EmissionGuarantee,
     ErrorGuaranteed, FluentBundle, LazyFallbackBundle, MultiSpan,
@@ -27,9 +29,10 @@
 pub use rustc_span::def_id::StableCrateId;
 use rustc_span::edition::Edition;
 use rustc_span::source_map::{FileLoader, RealFileLoader, SourceMap, Span};
-use rustc_span::{sym, SourceFileHashAlgorithm, Symbol};
+use rustc_span::{sym, FileNameDisplayPreference, SourceFileHashAlgorithm, Symbol};
 use rustc_target::asm::InlineAsmArch;
-use rustc_target::spec::{CodeModel, PanicStrategy, RelocModel, RelroLevel};
+use rustc_target::spec::{AppleDeploymentTarget, DeploymentTargetSource};
+use rustc_target::spec::{AtomicShim, CodeModel, PanicStrategy, RelocModel, RelroLevel};
 use rustc_target::spec::{
     SanitizerSet, SplitDebuginfo, StackProtector, Target, TargetTriple, TlsModel,
 };
@@ -152,6 +155,8 @@ pub struct Session {
     /// Used for incremental compilation tests. Will only be populated if
     /// `-Zquery-dep-graph` is specified.
     pub cgu_reuse_tracker: CguReuseTracker,
+    /// Will only be populated if `-Zprint-cgu-stats` is specified.
+    pub cgu_stats_tracker: CguStatsTracker,
 
     /// Used by `-Z self-profile`.
     pub prof: SelfProfilerRef,
@@ -286,6 +291,14 @@ pub fn struct_span_warn<S: Into<MultiSpan>>(
     ) -> DiagnosticBuilder<'_, ()> {
         self.diagnostic().struct_span_warn(sp, msg)
     }
+    pub fn struct_span_warn_with_expectation<S: Into<MultiSpan>>(
+        &self,
+        sp: S,
+        msg: impl Into<DiagnosticMessage>,
+        id: lint::LintExpectationId,
+    ) -> DiagnosticBuilder<'_, ()> {
+        self.diagnostic().struct_span_warn_with_expectation(sp, msg, id)
+    }
     pub fn struct_span_warn_with_code<S: Into<MultiSpan>>(
         &self,
         sp: S,
@@ -297,6 +310,13 @@ pub fn struct_span_warn_with_code<S: Into<MultiSpan>>(
     pub fn struct_warn(&self, msg: impl Into<DiagnosticMessage>) -> DiagnosticBuilder<'_, ()> {
         self.diagnostic().struct_warn(msg)
     }
+    pub fn struct_warn_with_expectation(
+        &self,
+        msg: impl Into<DiagnosticMessage>,
+        id: lint::LintExpectationId,
+    ) -> DiagnosticBuilder<'_, ()> {
+        self.diagnostic().struct_warn_with_expectation(msg, id)
+    }
     pub fn struct_span_allow<S: Into<MultiSpan>>(
         &self,
         sp: S,
@@ -672,6 +692,9 @@ pub fn is_nightly_build(&self) -> bool {
     pub fn is_sanitizer_cfi_enabled(&self) -> bool {
         self.opts.debugging_opts.sanitizer.contains(SanitizerSet::CFI)
     }
+    pub fn is_sanitizer_kcfi_enabled(&self) -> bool {
+        self.opts.debugging_opts.sanitizer.contains(SanitizerSet::KCFI)
+    }
     pub fn overflow_checks(&self) -> bool {
         self.opts.cg.overflow_checks.unwrap_or(self.opts.debug_assertions)
     }
@@ -1057,6 +1080,41 @@ pub fn instrument_coverage_except_unused_functions(&self) -> bool {
         self.opts.instrument_coverage_except_unused_functions()
     }
 
+    pub fn instrument_coverage_branch(&self) -> bool {
+        self.opts.instrument_coverage_branch()
+    }
+
+    /// Whether `-Z pgo-value-profiling` was explicitly enabled for a build that either
+    /// generates or uses a profile.
+    pub fn pgo_value_profiling(&self) -> bool {
+        (self.opts.cg.profile_generate.enabled() || self.opts.cg.profile_use.is_some())
+            && self.opts.debugging_opts.pgo_value_profiling == Some(true)
+    }
+
+    /// Whether `--remap-path-prefix` applies to the paths of `scope`.
+    pub fn should_prefer_remapped(&self, scope: RemapPathScopeComponents) -> bool {
+        self.opts.debugging_opts.remap_path_scope.contains(scope)
+    }
+
+    /// How to display the file names of `scope`, which are remapped unless
+    /// `-Zremap-path-scope` excludes them.
+    pub fn filename_display_preference(
+        &self,
+        scope: RemapPathScopeComponents,
+    ) -> FileNameDisplayPreference {
+        if self.should_prefer_remapped(scope) {
+            FileNameDisplayPreference::Remapped
+        } else {
+            FileNameDisplayPreference::Local
+        }
+    }
+
+    /// The shim that atomic operations the target can't perform natively are lowered to, if
+    /// `-Zatomic-shims` is enabled.
+    pub fn atomic_shim(&self) -> Option<AtomicShim> {
+        if self.opts.debugging_opts.atomic_shims { self.target.atomic_shim } else { None }
+    }
+
     pub fn is_proc_macro_attr(&self, attr: &Attribute) -> bool {
         [sym::proc_macro, sym::proc_macro_attribute, sym::proc_macro_derive]
             .iter()
@@ -1111,6 +1169,7 @@ fn default_emitter(
                     bundle,
                     fallback_bundle,
                     short,
+                    color_config,
                     macro_backtrace,
                 );
                 Box::new(emitter.ui_testing(sopts.debugging_opts.ui_testing))
@@ -1125,7 +1184,11 @@ fn default_emitter(
                         sopts.debugging_opts.teach,
                         sopts.debugging_opts.terminal_width,
                         macro_backtrace,
-                    ),
+                    )
+                    .terminal_urls(
+                        sopts.debugging_opts.terminal_urls.enabled_for_stderr(color_config),
+                    )
+                    .docs_base_url(sopts.debugging_opts.terminal_urls_base.clone()),
                     Some(dst) => EmitterWriter::new(
                         dst,
                         Some(source_map),
@@ -1168,6 +1231,12 @@ fn default_emitter(
             )
             .ui_testing(sopts.debugging_opts.ui_testing),
         ),
+        (config::ErrorOutputType::Sarif, None) => {
+            Box::new(SarifEmitter::stderr(Some(registry), source_map, bundle, fallback_bundle))
+        }
+        (config::ErrorOutputType::Sarif, Some(dst)) => {
+            Box::new(SarifEmitter::new(dst, Some(registry), source_map, bundle, fallback_bundle))
+        }
     }
 }
 
@@ -1239,10 +1308,12 @@ pub fn build_session(
     let emitter =
         default_emitter(&sopts, registry, source_map.clone(), bundle, fallback_bundle, write_dest);
 
-    let span_diagnostic = rustc_errors::Handler::with_emitter_and_flags(
-        emitter,
-        sopts.debugging_opts.diagnostic_handler_flags(can_emit_warnings),
-    );
+    let mut handler_flags = sopts.debugging_opts.diagnostic_handler_flags(can_emit_warnings);
+    if let config::ErrorOutputType::Json { .. } = sopts.error_format {
+        // Tools consuming JSON diagnostics always get every error.
+        handler_flags.max_errors = None;
+    }
+    let span_diagnostic = rustc_errors::Handler::with_emitter_and_flags(emitter, handler_flags);
 
     let self_profiler = if let SwitchWithOptPath::Enabled(ref d) = sopts.debugging_opts.self_profile
     {
@@ -1267,6 +1338,11 @@ pub fn build_session(
 
     let mut parse_sess = ParseSess::with_span_handler(span_diagnostic, source_map);
     parse_sess.assume_incomplete_release = sopts.debugging_opts.assume_incomplete_release;
+    // Lint levels depend on the contents of the `-Zlint-config` file, so changing it has to
+    // rebuild the crate.
+    if let Some(lint_config) = &sopts.debugging_opts.lint_config {
+        parse_sess.file_depinfo.get_mut().insert(Symbol::intern(&lint_config.to_string_lossy()));
+    }
 
     let host_triple = config::host_triple();
     let target_triple = sopts.target_triple.triple();
@@ -1296,10 +1372,17 @@ pub fn build_session(
         CguReuseTracker::new_disabled()
     };
 
+    let cgu_stats_tracker = if sopts.debugging_opts.print_cgu_stats {
+        CguStatsTracker::new()
+    } else {
+        CguStatsTracker::new_disabled()
+    };
+
     let prof = SelfProfilerRef::new(
         self_profiler,
         sopts.debugging_opts.time_passes || sopts.debugging_opts.time,
         sopts.debugging_opts.time_passes,
+        sopts.debugging_opts.time_passes_format,
     );
 
     let ctfe_backtrace = Lock::new(match env::var("RUSTC_CTFE_BACKTRACE") {
@@ -1325,6 +1408,7 @@ pub fn build_session(
         features: OnceCell::new(),
         incr_comp_session: OneThread::new(RefCell::new(IncrCompSession::NotInitialized)),
         cgu_reuse_tracker,
+        cgu_stats_tracker,
         prof,
         perf_stats: PerfStats {
             symbol_hash_time: Lock::new(Duration::from_secs(0)),
@@ -1441,6 +1525,28 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
         }
     }
 
+    if let Some(branch_protection) = sess.opts.debugging_opts.branch_protection {
+        if sess.target.arch != "aarch64" {
+            sess.err("-Zbranch-protection is only supported on aarch64");
+        } else if branch_protection.bti {
+            // The linker only marks the output as BTI-compatible if every input
+            // object is, which isn't the case for the prebuilt sanitizer runtimes.
+            let with_runtime = SanitizerSet::ADDRESS
+                | SanitizerSet::HWADDRESS
+                | SanitizerSet::LEAK
+                | SanitizerSet::MEMORY
+                | SanitizerSet::THREAD;
+            let incompatible = sess.opts.debugging_opts.sanitizer & with_runtime;
+            if let Some(sanitizer) = incompatible.into_iter().next() {
+                sess.struct_err(&format!(
+                    "`-Zbranch-protection=bti` is incompatible with `-Zsanitizer={sanitizer}`"
+                ))
+                .note("the sanitizer runtimes are not built with BTI enabled")
+                .emit();
+            }
+        }
+    }
+
     if sess.opts.debugging_opts.stack_protector != StackProtector::None {
         if !sess.target.options.supports_stack_protector {
             sess.warn(&format!(
@@ -1449,6 +1555,38 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
             ))
         }
     }
+
+    if sess.opts.debugging_opts.wasm_exception_handling && !sess.target.is_like_emscripten {
+        sess.err("`-Zwasm-exception-handling` is only supported on emscripten targets");
+    }
+
+    if sess.opts.debugging_opts.atomic_shims {
+        match sess.target.atomic_shim {
+            None => sess.err(&format!(
+                "`-Zatomic-shims` is not supported for target {}",
+                sess.opts.target_triple
+            )),
+            Some(AtomicShim::Interrupts) if !matches!(&*sess.target.arch, "arm" | "riscv32") => {
+                sess.err("the `interrupts` atomic shim is only supported on ARM and RISC-V targets")
+            }
+            Some(_) => {}
+        }
+    }
+
+    // Reject deployment targets that the SDK in use cannot support, as the linker would otherwise
+    // fail with a much less helpful error, or not at all.
+    match rustc_target::spec::apple_deployment_target(&sess.target) {
+        Some(Ok(AppleDeploymentTarget {
+            version,
+            source: DeploymentTargetSource::Malformed { var, value },
+        })) => sess.warn(&format!(
+            "`{}` is set to `{}`, which is not a valid version; using the default deployment \
+             target {}.{} instead",
+            var, value, version.0, version.1
+        )),
+        Some(Err(err)) => sess.err(&err),
+        _ => {}
+    }
 }
 
 /// Holds data on the current incremental compilation session, if there is one.
@@ -1488,6 +1626,7 @@ fn early_error_handler(output: config::ErrorOutputType) -> rustc_errors::Handler
         config::ErrorOutputType::Json { pretty, json_rendered } => {
             Box::new(JsonEmitter::basic(pretty, json_rendered, None, fallback_bundle, None, false))
         }
+        config::ErrorOutputType::Sarif => Box::new(SarifEmitter::basic(None, fallback_bundle)),
     };
     rustc_errors::Handler::with_emitter(true, None, emitter)
 }
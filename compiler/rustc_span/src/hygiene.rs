@@ -165,6 +165,16 @@ pub enum Transparency {
     Opaque,
 }
 
+impl Transparency {
+    pub fn descr(self) -> &'static str {
+        match self {
+            Transparency::Transparent => "transparent",
+            Transparency::SemiTransparent => "semi-transparent",
+            Transparency::Opaque => "opaque",
+        }
+    }
+}
+
 impl LocalExpnId {
     /// The ID of the theoretical expansion that generates freshly parsed, unexpanded AST.
     pub const ROOT: LocalExpnId = LocalExpnId::from_u32(0);
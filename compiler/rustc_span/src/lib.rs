@@ -733,15 +733,25 @@ pub fn allows_unsafe(self) -> bool {
         self.ctxt().outer_expn_data().allow_internal_unsafe
     }
 
-    pub fn macro_backtrace(mut self) -> impl Iterator<Item = ExpnData> {
+    pub fn macro_backtrace(self) -> impl Iterator<Item = ExpnData> {
+        self.macro_backtrace_with_transparency().map(|(expn_data, _)| expn_data)
+    }
+
+    /// Like `macro_backtrace`, but also returns the transparency of the mark each expansion
+    /// applied to the span produced by it.
+    pub fn macro_backtrace_with_transparency(
+        mut self,
+    ) -> impl Iterator<Item = (ExpnData, Transparency)> {
         let mut prev_span = DUMMY_SP;
         std::iter::from_fn(move || {
             loop {
-                let expn_data = self.ctxt().outer_expn_data();
+                let ctxt = self.ctxt();
+                let expn_data = ctxt.outer_expn_data();
                 if expn_data.is_root() {
                     return None;
                 }
 
+                let (_, transparency) = ctxt.outer_mark();
                 let is_recursive = expn_data.call_site.source_equal(prev_span);
 
                 prev_span = self;
@@ -749,7 +759,7 @@ pub fn macro_backtrace(mut self) -> impl Iterator<Item = ExpnData> {
 
                 // Don't print recursive invocations.
                 if !is_recursive {
-                    return Some(expn_data);
+                    return Some((expn_data, transparency));
                 }
             }
         })
@@ -150,6 +150,7 @@
         BTreeEntry,
         BTreeMap,
         BTreeSet,
+        Binary,
         BinaryHeap,
         Borrow,
         Break,
@@ -213,9 +214,11 @@
         Left,
         LinkedList,
         LintPass,
+        LowerHex,
         Mutex,
         N,
         None,
+        Octal,
         Ok,
         Option,
         Ord,
@@ -270,6 +273,7 @@
         TyKind,
         Unknown,
         UnsafeArg,
+        UpperHex,
         Vec,
         VecDeque,
         Yield,
@@ -294,6 +298,7 @@
         abi_msp430_interrupt,
         abi_ptx,
         abi_sysv64,
+        abi_target_defined,
         abi_thiscall,
         abi_unadjusted,
         abi_vectorcall,
@@ -332,6 +337,7 @@
         append_const_msg,
         arbitrary_enum_discriminant,
         arbitrary_self_types,
+        arch,
         args,
         arith_offset,
         arm,
@@ -409,6 +415,7 @@
         c_str,
         c_unwind,
         c_variadic,
+        c_void,
         call,
         call_mut,
         call_once,
@@ -483,6 +490,7 @@
         const_fn,
         const_fn_floating_point_arithmetic,
         const_fn_fn_ptr_basics,
+        const_fn_ptr_calls,
         const_fn_trait_bound,
         const_fn_transmute,
         const_fn_union,
@@ -501,6 +509,7 @@
         const_mut_refs,
         const_panic,
         const_panic_fmt,
+        const_panic_fmt_args,
         const_precise_live_drops,
         const_raw_ptr_deref,
         const_raw_ptr_to_usize_cast,
@@ -539,6 +548,7 @@
         cstring_type,
         ctlz,
         ctlz_nonzero,
+        ctor_priority,
         ctpop,
         cttz,
         cttz_nonzero,
@@ -645,6 +655,7 @@
         existential_type,
         exp2f32,
         exp2f64,
+        expand_derives,
         expect,
         expected,
         expf32,
@@ -707,6 +718,8 @@
         format_args_nl,
         format_macro,
         fp,
+        frame_pointer,
+        frame_pointer_attribute,
         freeze,
         freg,
         frem_fast,
@@ -808,6 +821,7 @@
         item_like_imports,
         iter,
         iter_repeat,
+        kcfi,
         keyword,
         kind,
         kreg,
@@ -948,7 +962,10 @@
         never_type,
         never_type_fallback,
         new,
+        new_lower_exp,
+        new_pointer,
         new_unchecked,
+        new_upper_exp,
         next,
         nll,
         no,
@@ -972,7 +989,9 @@
         non_ascii_idents,
         non_exhaustive,
         non_exhaustive_omitted_patterns_lint,
+        non_leaf,
         non_modrs_mods,
+        none,
         none_error,
         nontemporal_store,
         noop_method_borrow,
@@ -1067,6 +1086,7 @@
         proc_dash_macro: "proc-macro",
         proc_macro,
         proc_macro_attribute,
+        proc_macro_cfg_eval,
         proc_macro_def_site,
         proc_macro_derive,
         proc_macro_expr,
@@ -1128,6 +1148,7 @@
         reg_upper,
         register_attr,
         register_tool,
+        register_tool_attr,
         relaxed_adts,
         relaxed_struct_unsize,
         rem,
@@ -1385,6 +1406,7 @@
         target,
         target_abi,
         target_arch,
+        target_clones,
         target_endian,
         target_env,
         target_family,
@@ -1398,6 +1420,7 @@
         target_target_vendor,
         target_thread_local,
         target_vendor,
+        targets,
         task,
         tbm_target_feature,
         termination,
@@ -12,8 +12,8 @@
 use rustc_middle::ty::{
     self, EarlyBinder, FloatTy, Instance, IntTy, Ty, TyCtxt, TypeFoldable, UintTy,
 };
-use rustc_span::symbol::kw;
-use rustc_target::abi::call::FnAbi;
+use rustc_span::symbol::{kw, sym};
+use rustc_target::abi::call::{Conv, FnAbi};
 use rustc_target::abi::Integer;
 use rustc_target::spec::abi::Abi;
 
@@ -95,6 +95,151 @@ pub(super) fn mangle_typeid_for_fnabi<'tcx>(
     format!("typeid{}", arg_count)
 }
 
+pub(super) fn mangle_kcfi_typeid_for_fnabi<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    fn_abi: &FnAbi<'tcx, Ty<'tcx>>,
+) -> String {
+    // KCFI checks an indirect call by comparing a hash of the callee's type identifier, stored in
+    // front of the callee, with a hash of the type identifier expected at the call site. Clang
+    // hashes the Itanium C++ ABI typeinfo name of the function type, so for functions that can
+    // be called from or call into C code (i.e., that use a non-Rust calling convention) the same
+    // encoding has to be used on both sides of the `extern "C"` boundary.
+    //
+    // Only signatures made up of C scalar types and pointers to them are encoded this way, as
+    // those are the only types with an unambiguous C counterpart. Everything else uses the same
+    // Rust-specific type identifiers as LLVM CFI.
+    //
+    // FIXME: Encode aggregates (e.g., `#[repr(C)]` structs) once there is a way to name them
+    // consistently with their C definitions.
+    if fn_abi.conv != Conv::Rust {
+        if let Some(typeid) = itanium_typeid_for_fnabi(tcx, fn_abi) {
+            return typeid;
+        }
+    }
+    mangle_typeid_for_fnabi(tcx, fn_abi)
+}
+
+/// Encodes the C prototype equivalent to `fn_abi` as an Itanium C++ ABI typeinfo name, e.g.,
+/// `_ZTSFiPKvS_E` for `int (const void *, const void *)`.
+fn itanium_typeid_for_fnabi<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    fn_abi: &FnAbi<'tcx, Ty<'tcx>>,
+) -> Option<String> {
+    let mut substitutions = Vec::new();
+    let mut typeid = String::from("_ZTSF");
+    let ret = fn_abi.ret.layout.ty;
+    if ret.is_unit() || ret.is_never() {
+        typeid.push('v');
+    } else {
+        typeid.push_str(&itanium_encode_ty(tcx, ret, &mut substitutions)?.1);
+    }
+    let args = &fn_abi.args[..fn_abi.fixed_count];
+    if args.is_empty() && !fn_abi.c_variadic {
+        typeid.push('v');
+    }
+    for arg in args {
+        typeid.push_str(&itanium_encode_ty(tcx, arg.layout.ty, &mut substitutions)?.1);
+    }
+    if fn_abi.c_variadic {
+        typeid.push('z');
+    }
+    typeid.push('E');
+    Some(typeid)
+}
+
+/// Returns both the full encoding of `ty`, which identifies it in `substitutions`, and the
+/// encoding to emit, which refers back to earlier occurrences of its components.
+fn itanium_encode_ty<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: Ty<'tcx>,
+    substitutions: &mut Vec<String>,
+) -> Option<(String, String)> {
+    let lp64 = tcx.data_layout.pointer_size.bits() == 64;
+    let builtin = match *ty.kind() {
+        ty::Bool => "b",
+        ty::Int(IntTy::I8) => "a",
+        ty::Int(IntTy::I16) => "s",
+        ty::Int(IntTy::I32) => "i",
+        ty::Int(IntTy::I64) => {
+            if lp64 {
+                "l"
+            } else {
+                "x"
+            }
+        }
+        ty::Int(IntTy::I128) => "n",
+        ty::Int(IntTy::Isize) => {
+            if lp64 {
+                "l"
+            } else {
+                "i"
+            }
+        }
+        ty::Uint(UintTy::U8) => "h",
+        ty::Uint(UintTy::U16) => "t",
+        ty::Uint(UintTy::U32) => "j",
+        ty::Uint(UintTy::U64) => {
+            if lp64 {
+                "m"
+            } else {
+                "y"
+            }
+        }
+        ty::Uint(UintTy::U128) => "o",
+        ty::Uint(UintTy::Usize) => {
+            if lp64 {
+                "m"
+            } else {
+                "j"
+            }
+        }
+        ty::Float(FloatTy::F32) => "f",
+        ty::Float(FloatTy::F64) => "d",
+        ty::Adt(adt_def, _) if tcx.is_diagnostic_item(sym::c_void, adt_def.did()) => "v",
+        ty::RawPtr(ty::TypeAndMut { ty: pointee, mutbl }) | ty::Ref(_, pointee, mutbl) => {
+            let (mut full, mut emitted) = itanium_encode_ty(tcx, pointee, substitutions)?;
+            if mutbl == hir::Mutability::Not {
+                full = format!("K{}", full);
+                emitted = itanium_substitute(substitutions, &full, format!("K{}", emitted));
+            }
+            let full = format!("P{}", full);
+            let emitted = itanium_substitute(substitutions, &full, format!("P{}", emitted));
+            return Some((full, emitted));
+        }
+        _ => return None,
+    };
+    // Builtin types are never substitution candidates.
+    Some((builtin.to_string(), builtin.to_string()))
+}
+
+/// Returns the substitution for `full` if it was encoded before, and records it as a
+/// substitution candidate otherwise.
+fn itanium_substitute(substitutions: &mut Vec<String>, full: &str, emitted: String) -> String {
+    let index = match substitutions.iter().position(|s| s == full) {
+        Some(index) => index,
+        None => {
+            substitutions.push(full.to_string());
+            return emitted;
+        }
+    };
+    if index == 0 {
+        return "S_".to_string();
+    }
+    // Substitutions after the first are numbered in base 36, using digits and upper-case letters.
+    let mut seq_id = Vec::new();
+    let mut n = index - 1;
+    loop {
+        let digit = (n % 36) as u8;
+        seq_id.push(if digit < 10 { b'0' + digit } else { b'A' + digit - 10 });
+        n /= 36;
+        if n == 0 {
+            break;
+        }
+    }
+    seq_id.reverse();
+    format!("S{}_", String::from_utf8(seq_id).unwrap())
+}
+
 struct BinderLevel {
     /// The range of distances from the root of what's
     /// being printed, to the lifetimes in a binder.
@@ -605,6 +605,10 @@ pub enum Conv {
     AmdGpuKernel,
     AvrInterrupt,
     AvrNonBlockingInterrupt,
+
+    /// A calling convention from a target-defined ABI, see `TargetOptions::custom_abis`.
+    /// The value is only meaningful to the codegen backend.
+    Custom(u32),
 }
 
 /// Metadata describing how the arguments to a native function
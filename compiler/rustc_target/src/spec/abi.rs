@@ -1,6 +1,7 @@
 use std::fmt;
 
 use rustc_macros::HashStable_Generic;
+use rustc_span::symbol::Symbol;
 
 #[cfg(test)]
 mod tests;
@@ -36,6 +37,8 @@ pub enum Abi {
     PlatformIntrinsic,
     Unadjusted,
     RustCold,
+    /// An ABI defined by the target specification, see `TargetOptions::custom_abis`.
+    Custom { name: Symbol },
 }
 
 #[derive(Copy, Clone)]
@@ -98,6 +101,8 @@ impl Abi {
     /// Default ABI chosen for `extern fn` declarations without an explicit ABI.
     pub const FALLBACK: Abi = Abi::C { unwind: false };
 
+    /// Returns the index of this ABI in the `AbiDatas` array. Must not be called for
+    /// target-defined ABIs, which aren't part of it.
     #[inline]
     pub fn index(self) -> usize {
         // N.B., this ordering MUST match the AbiDatas array above.
@@ -142,6 +147,7 @@ pub fn index(self) -> usize {
             PlatformIntrinsic => 32,
             Unadjusted => 33,
             RustCold => 34,
+            Custom { .. } => panic!("target-defined ABI {:?} has no associated data", self),
         };
         debug_assert!(
             AbiDatas
@@ -161,8 +167,11 @@ pub fn data(self) -> &'static AbiData {
         &AbiDatas[self.index()]
     }
 
-    pub fn name(self) -> &'static str {
-        self.data().name
+    pub fn name(&self) -> &str {
+        match self {
+            Abi::Custom { name } => name.as_str(),
+            abi => abi.data().name,
+        }
     }
 }
 
@@ -1,6 +1,6 @@
-use std::{borrow::Cow, env};
+use std::{borrow::Cow, env, fmt, path::Path};
 
-use crate::spec::{cvs, FramePointer, LldFlavor, SplitDebuginfo, TargetOptions};
+use crate::spec::{cvs, FramePointer, LldFlavor, SplitDebuginfo, Target, TargetOptions};
 
 pub fn opts(os: &'static str) -> TargetOptions {
     // ELF TLS is only available in macOS 10.7+. If you try to compile for 10.6
@@ -58,11 +58,18 @@ pub fn opts(os: &'static str) -> TargetOptions {
 }
 
 fn deployment_target(var_name: &str) -> Option<(u32, u32)> {
-    let deployment_target = env::var(var_name).ok();
-    deployment_target
-        .as_ref()
-        .and_then(|s| s.split_once('.'))
-        .and_then(|(a, b)| a.parse::<u32>().and_then(|a| b.parse::<u32>().map(|b| (a, b))).ok())
+    env::var(var_name).ok().as_deref().and_then(parse_version)
+}
+
+/// Parses a version like `10.12`, `11` or `10.12.1`; the patch version is ignored.
+fn parse_version(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map_or(Some(0), |minor| minor.parse().ok())?;
+    match parts.next() {
+        Some(patch) if patch.parse::<u32>().is_err() || parts.next().is_some() => None,
+        _ => Some((major, minor)),
+    }
 }
 
 fn macos_default_deployment_target(arch: &str) -> (u32, u32) {
@@ -70,8 +77,9 @@ fn macos_default_deployment_target(arch: &str) -> (u32, u32) {
 }
 
 fn macos_deployment_target(arch: &str) -> (u32, u32) {
-    deployment_target("MACOSX_DEPLOYMENT_TARGET")
-        .unwrap_or_else(|| macos_default_deployment_target(arch))
+    // The default is also the oldest version the architecture supports.
+    let default = macos_default_deployment_target(arch);
+    deployment_target("MACOSX_DEPLOYMENT_TARGET").map_or(default, |version| version.max(default))
 }
 
 pub fn macos_llvm_target(arch: &str) -> String {
@@ -96,7 +104,7 @@ pub fn macos_link_env_remove() -> Vec<Cow<'static, str>> {
 }
 
 fn ios_deployment_target() -> (u32, u32) {
-    deployment_target("IPHONEOS_DEPLOYMENT_TARGET").unwrap_or((7, 0))
+    deployment_target("IPHONEOS_DEPLOYMENT_TARGET").map_or((7, 0), |version| version.max((7, 0)))
 }
 
 pub fn ios_llvm_target(arch: &str) -> String {
@@ -114,3 +122,123 @@ pub fn ios_sim_llvm_target(arch: &str) -> String {
     let (major, minor) = ios_deployment_target();
     format!("{}-apple-ios{}.{}.0-simulator", arch, major, minor)
 }
+
+/// Where the deployment target of an Apple target comes from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeploymentTargetSource {
+    /// It was set with this environment variable.
+    Env(&'static str),
+    /// The environment variable with this name is not set, so the default for the target is used.
+    Default(&'static str),
+    /// The environment variable `var` is set to `value`, which is not a valid version, so the
+    /// default for the target is used.
+    Malformed { var: &'static str, value: String },
+}
+
+impl fmt::Display for DeploymentTargetSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeploymentTargetSource::Env(var) => write!(f, "from `{}`", var),
+            DeploymentTargetSource::Default(var) => {
+                write!(f, "the default for the target, as `{}` is not set", var)
+            }
+            DeploymentTargetSource::Malformed { var, .. } => {
+                write!(f, "the default for the target, as `{}` is not a valid version", var)
+            }
+        }
+    }
+}
+
+/// The oldest OS version that code built for an Apple target runs on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AppleDeploymentTarget {
+    pub version: (u32, u32),
+    pub source: DeploymentTargetSource,
+}
+
+impl fmt::Display for AppleDeploymentTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.version.0, self.version.1)
+    }
+}
+
+/// The environment variable that sets the deployment target of `target`, its default and the
+/// oldest version it supports, or `None` if `target` is not an Apple target with a configurable
+/// deployment target.
+fn deployment_target_var(target: &Target) -> Option<(&'static str, (u32, u32), (u32, u32))> {
+    match &*target.os {
+        "macos" => {
+            let arch = if target.arch == "aarch64" { "arm64" } else { "x86_64" };
+            let default = macos_default_deployment_target(arch);
+            Some(("MACOSX_DEPLOYMENT_TARGET", default, default))
+        }
+        // Mac Catalyst targets have a fixed deployment target.
+        "ios" if target.abi == "macabi" => None,
+        "ios" => Some(("IPHONEOS_DEPLOYMENT_TARGET", (7, 0), (7, 0))),
+        "tvos" => Some(("TVOS_DEPLOYMENT_TARGET", (7, 0), (7, 0))),
+        _ => None,
+    }
+}
+
+/// The prefix of the names of the SDKs for `target`, e.g. `MacOSX` for `MacOSX12.3.sdk`.
+fn sdk_platform(target: &Target) -> &'static str {
+    let simulator = target.abi == "sim"
+        || target.llvm_target.ends_with("-simulator")
+        || target.arch == "x86"
+        || target.arch == "x86_64";
+    match &*target.os {
+        "ios" if simulator => "iPhoneSimulator",
+        "ios" => "iPhoneOS",
+        "tvos" if simulator => "AppleTVSimulator",
+        "tvos" => "AppleTVOS",
+        _ => "MacOSX",
+    }
+}
+
+/// Returns the deployment target of `target`, or `None` if it is not an Apple target with a
+/// configurable deployment target. A deployment target older than the oldest version the target
+/// supports is raised to that version, as when computing the LLVM target, and a malformed one is
+/// replaced by the default. Errors if the deployment target is newer than the SDK that `SDKROOT`
+/// points to.
+pub fn apple_deployment_target(target: &Target) -> Option<Result<AppleDeploymentTarget, String>> {
+    let (var, default, oldest) = deployment_target_var(target)?;
+    let deployment_target = match env::var(var) {
+        Ok(value) => match parse_version(&value) {
+            Some(version) => AppleDeploymentTarget {
+                version: version.max(oldest),
+                source: DeploymentTargetSource::Env(var),
+            },
+            None => AppleDeploymentTarget {
+                version: default,
+                source: DeploymentTargetSource::Malformed { var, value },
+            },
+        },
+        Err(_) => {
+            AppleDeploymentTarget { version: default, source: DeploymentTargetSource::Default(var) }
+        }
+    };
+
+    // `SDKROOT` may be set for another platform, e.g. when building build scripts for the host
+    // while targeting iOS. It is then removed from the linker's environment, so ignore it here.
+    if let Ok(sdkroot) = env::var("SDKROOT") {
+        let sdk_name = Path::new(&sdkroot).file_name().and_then(|name| name.to_str());
+        let sdk_version = sdk_name
+            .and_then(|name| name.strip_suffix(".sdk"))
+            .and_then(|name| name.strip_prefix(sdk_platform(target)))
+            .and_then(parse_version);
+        if let Some(sdk_version) = sdk_version {
+            if deployment_target.version > sdk_version {
+                return Some(Err(format!(
+                    "the deployment target {} ({}) is newer than the SDK at `SDKROOT` ({}, \
+                     version {}.{})",
+                    deployment_target,
+                    deployment_target.source,
+                    sdkroot,
+                    sdk_version.0,
+                    sdk_version.1
+                )));
+            }
+        }
+    }
+    Some(Ok(deployment_target))
+}
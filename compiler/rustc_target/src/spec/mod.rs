@@ -88,6 +88,8 @@
 mod windows_uwp_gnu_base;
 mod windows_uwp_msvc_base;
 
+pub use apple_base::{apple_deployment_target, AppleDeploymentTarget, DeploymentTargetSource};
+
 #[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub enum LinkerFlavor {
     Em,
@@ -616,6 +618,7 @@ pub struct SanitizerSet: u8 {
         const HWADDRESS = 1 << 4;
         const CFI     = 1 << 5;
         const MEMTAG  = 1 << 6;
+        const KCFI    = 1 << 7;
     }
 }
 
@@ -632,6 +635,7 @@ pub fn as_str(self) -> Option<&'static str> {
             SanitizerSet::MEMTAG => "memtag",
             SanitizerSet::THREAD => "thread",
             SanitizerSet::HWADDRESS => "hwaddress",
+            SanitizerSet::KCFI => "kcfi",
             _ => return None,
         })
     }
@@ -666,6 +670,7 @@ fn into_iter(self) -> Self::IntoIter {
             SanitizerSet::MEMTAG,
             SanitizerSet::THREAD,
             SanitizerSet::HWADDRESS,
+            SanitizerSet::KCFI,
         ]
         .iter()
         .copied()
@@ -691,7 +696,7 @@ fn to_json(&self) -> Json {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+#[derive(Clone, Copy, PartialEq, Hash, Debug, Encodable, Decodable, HashStable_Generic)]
 pub enum FramePointer {
     /// Forces the machine code generator to always preserve the frame pointers.
     Always,
@@ -727,6 +732,38 @@ fn to_json(&self) -> Json {
     }
 }
 
+/// How atomic operations that the target can't perform natively are lowered with
+/// `-Zatomic-shims`.
+#[derive(Clone, Copy, PartialEq, Hash, Debug, Encodable, Decodable, HashStable_Generic)]
+pub enum AtomicShim {
+    /// Call the `__atomic_*` functions from libatomic (or compiler-builtins).
+    Libcall,
+    /// Perform the operation non-atomically while interrupts are masked. This is only
+    /// sound on single-core systems running in a privileged mode.
+    Interrupts,
+}
+
+impl FromStr for AtomicShim {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, ()> {
+        Ok(match s {
+            "libcall" => Self::Libcall,
+            "interrupts" => Self::Interrupts,
+            _ => return Err(()),
+        })
+    }
+}
+
+impl ToJson for AtomicShim {
+    fn to_json(&self) -> Json {
+        match *self {
+            Self::Libcall => "libcall",
+            Self::Interrupts => "interrupts",
+        }
+        .to_json()
+    }
+}
+
 /// Controls use of stack canaries.
 #[derive(Clone, Copy, Debug, PartialEq, Hash, Eq)]
 pub enum StackProtector {
@@ -780,6 +817,59 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+/// A property of a target that can be queried with `--print target-capability=<name>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TargetCapability {
+    /// The integer widths, in bits, on which the target can perform atomic operations.
+    AtomicWidths,
+    /// Whether the target supports atomic compare-and-swap operations.
+    AtomicCas,
+    /// The width of pointers, in bits.
+    PointerWidth,
+    /// The byte order of the target.
+    Endian,
+    /// Whether the target supports `#[thread_local]`.
+    ThreadLocal,
+    /// The sanitizers which are supported on the target.
+    Sanitizers,
+}
+
+impl TargetCapability {
+    pub const ALL: &'static [TargetCapability] = &[
+        TargetCapability::AtomicWidths,
+        TargetCapability::AtomicCas,
+        TargetCapability::PointerWidth,
+        TargetCapability::Endian,
+        TargetCapability::ThreadLocal,
+        TargetCapability::Sanitizers,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TargetCapability::AtomicWidths => "atomic-widths",
+            TargetCapability::AtomicCas => "atomic-cas",
+            TargetCapability::PointerWidth => "pointer-width",
+            TargetCapability::Endian => "endian",
+            TargetCapability::ThreadLocal => "thread-local",
+            TargetCapability::Sanitizers => "sanitizers",
+        }
+    }
+}
+
+impl FromStr for TargetCapability {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<TargetCapability, ()> {
+        TargetCapability::ALL.iter().copied().find(|cap| cap.as_str() == s).ok_or(())
+    }
+}
+
+impl fmt::Display for TargetCapability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 macro_rules! supported_targets {
     ( $(($( $triple:literal, )+ $module:ident ),)+ ) => {
         $(mod $module;)+
@@ -812,6 +902,17 @@ fn $module() {
     };
 }
 
+/// Returns the specifications of all built-in targets as a single JSON object, keyed by the
+/// target triple.
+pub fn builtin_targets_json() -> Json {
+    let mut targets = serde_json::Map::new();
+    for &triple in TARGETS {
+        let target = load_builtin(triple).expect("`TARGETS` contains an unknown target");
+        targets.insert(triple.to_string(), target.to_json());
+    }
+    Json::Object(targets)
+}
+
 supported_targets! {
     ("x86_64-unknown-linux-gnu", x86_64_unknown_linux_gnu),
     ("x86_64-unknown-linux-gnux32", x86_64_unknown_linux_gnux32),
@@ -1337,6 +1438,10 @@ pub struct TargetOptions {
     /// Whether the target supports atomic CAS operations natively
     pub atomic_cas: bool,
 
+    /// How `-Zatomic-shims` lowers the atomic operations that the target doesn't support
+    /// natively. `None` if the target doesn't support shims.
+    pub atomic_shim: Option<AtomicShim>,
+
     /// Panic strategy: "unwind" or "abort"
     pub panic_strategy: PanicStrategy,
 
@@ -1456,6 +1561,30 @@ pub struct TargetOptions {
     /// Whether the target supports stack canary checks. `true` by default,
     /// since this is most common among tier 1 and tier 2 targets.
     pub supports_stack_protector: bool,
+
+    /// Additional ABIs that can be used with `extern "..."` on this target, on top of the
+    /// built-in ones. This allows custom targets to use calling conventions (e.g. for interrupt
+    /// handlers) that rustc doesn't know about.
+    pub custom_abis: StaticCow<[CustomAbi]>,
+}
+
+/// An ABI defined by the target specification, see `TargetOptions::custom_abis`.
+#[derive(PartialEq, Clone, Debug)]
+pub struct CustomAbi {
+    /// The name used to refer to this ABI in `extern "..."`.
+    pub name: StaticCow<str>,
+    /// The calling convention the codegen backend should use for functions with this ABI. For
+    /// LLVM this is the numeric value of the `CallingConv::ID`.
+    pub calling_convention: u32,
+}
+
+impl ToJson for CustomAbi {
+    fn to_json(&self) -> Json {
+        let mut d = serde_json::Map::new();
+        d.insert("name".into(), self.name.to_json());
+        d.insert("calling-convention".into(), self.calling_convention.to_json());
+        Json::Object(d)
+    }
 }
 
 impl Default for TargetOptions {
@@ -1531,6 +1660,7 @@ fn default() -> TargetOptions {
             min_atomic_width: None,
             max_atomic_width: None,
             atomic_cas: true,
+            atomic_shim: None,
             panic_strategy: PanicStrategy::Unwind,
             crt_static_allows_dylibs: false,
             crt_static_default: false,
@@ -1563,6 +1693,7 @@ fn default() -> TargetOptions {
             c_enum_min_bits: 32,
             generate_arange_section: true,
             supports_stack_protector: true,
+            custom_abis: cvs![],
         }
     }
 }
@@ -1586,6 +1717,11 @@ fn deref_mut(&mut self) -> &mut Self::Target {
 }
 
 impl Target {
+    /// Returns the ABI with the given name from `custom_abis`, if this target defines one.
+    pub fn lookup_custom_abi(&self, name: &str) -> Option<&CustomAbi> {
+        self.custom_abis.iter().find(|abi| abi.name == name)
+    }
+
     /// Given a function ABI, turn it into the correct ABI for this target.
     pub fn adjust_abi(&self, abi: Abi) -> Abi {
         match abi {
@@ -1631,6 +1767,7 @@ pub fn is_abi_supported(&self, abi: Abi) -> Option<bool> {
             AmdGpuKernel => self.arch == "amdgcn",
             AvrInterrupt | AvrNonBlockingInterrupt => self.arch == "avr",
             Wasm => ["wasm32", "wasm64"].contains(&&self.arch[..]),
+            Custom { name } => self.lookup_custom_abi(name.as_str()).is_some(),
             Thiscall { .. } => self.arch == "x86",
             // On windows these fall-back to platform native calling convention (C) when the
             // architecture is not supported.
@@ -1684,6 +1821,32 @@ pub fn max_atomic_width(&self) -> u64 {
         self.max_atomic_width.unwrap_or_else(|| self.pointer_width.into())
     }
 
+    /// Queries a single capability of this target.
+    ///
+    /// Every capability is rendered as a list of values, which is empty if the target does not
+    /// have the capability at all.
+    pub fn capability(&self, cap: TargetCapability) -> Vec<String> {
+        match cap {
+            TargetCapability::AtomicWidths => {
+                let (min, max) = (self.min_atomic_width(), self.max_atomic_width());
+                [8, 16, 32, 64, 128]
+                    .into_iter()
+                    .filter(|&width| min <= width && width <= max)
+                    .map(|width| width.to_string())
+                    .collect()
+            }
+            TargetCapability::AtomicCas => vec![self.atomic_cas.to_string()],
+            TargetCapability::PointerWidth => vec![self.pointer_width.to_string()],
+            TargetCapability::Endian => vec![self.endian.as_str().to_string()],
+            TargetCapability::ThreadLocal => vec![self.has_thread_local.to_string()],
+            TargetCapability::Sanitizers => self
+                .supported_sanitizers
+                .into_iter()
+                .filter_map(|s| Some(s.as_str()?.to_string()))
+                .collect(),
+        }
+    }
+
     /// Loads a target descriptor from a JSON object.
     pub fn from_json(obj: Json) -> Result<(Target, TargetWarnings), String> {
         // While ugly, this code must remain this way to retain
@@ -1793,6 +1956,17 @@ macro_rules! key {
                     Some(Ok(()))
                 })).unwrap_or(Ok(()))
             } );
+            ($key_name:ident, Option<AtomicShim>) => ( {
+                let name = (stringify!($key_name)).replace("_", "-");
+                obj.remove(&name).and_then(|o| o.as_str().and_then(|s| {
+                    match s.parse::<AtomicShim>() {
+                        Ok(atomic_shim) => base.$key_name = Some(atomic_shim),
+                        _ => return Some(Err(format!("'{}' is not a valid atomic shim. \
+                                                      Use `libcall` or `interrupts`.", s))),
+                    }
+                    Some(Ok(()))
+                })).unwrap_or(Ok(()))
+            } );
             ($key_name:ident, TlsModel) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
                 obj.remove(&name).and_then(|o| o.as_str().and_then(|s| {
@@ -1924,6 +2098,7 @@ macro_rules! key {
                                 Some("memtag") => SanitizerSet::MEMTAG,
                                 Some("thread") => SanitizerSet::THREAD,
                                 Some("hwaddress") => SanitizerSet::HWADDRESS,
+                                Some("kcfi") => SanitizerSet::KCFI,
                                 Some(s) => return Err(format!("unknown sanitizer {}", s)),
                                 _ => return Err(format!("not a string: {:?}", s)),
                             };
@@ -1935,6 +2110,47 @@ macro_rules! key {
                 Ok::<(), String>(())
             } );
 
+            ($key_name:ident, custom_abis) => ( {
+                let name = (stringify!($key_name)).replace("_", "-");
+                if let Some(o) = obj.remove(&name) {
+                    if let Some(a) = o.as_array() {
+                        for o in a {
+                            let (Some(abi_name), Some(cconv)) =
+                                (o["name"].as_str(), o["calling-convention"].as_u64())
+                            else {
+                                return Err(format!(
+                                    "custom ABIs must have a `name` and a numeric \
+                                     `calling-convention`: {}",
+                                    o
+                                ));
+                            };
+                            if lookup_abi(abi_name).is_some() {
+                                return Err(format!(
+                                    "custom ABI `{}` conflicts with a built-in ABI",
+                                    abi_name
+                                ));
+                            }
+                            if base.$key_name.iter().any(|abi| abi.name == abi_name) {
+                                return Err(format!("custom ABI `{}` is defined twice", abi_name));
+                            }
+                            let Ok(calling_convention) = u32::try_from(cconv) else {
+                                return Err(format!(
+                                    "calling convention {} of custom ABI `{}` is out of range",
+                                    cconv, abi_name
+                                ));
+                            };
+                            base.$key_name.to_mut().push(CustomAbi {
+                                name: abi_name.to_string().into(),
+                                calling_convention,
+                            });
+                        }
+                    } else {
+                        incorrect_type.push(name)
+                    }
+                }
+                Ok::<(), String>(())
+            } );
+
             ($key_name:ident, crt_objects_fallback) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
                 obj.remove(&name).and_then(|o| o.as_str().and_then(|s| {
@@ -2127,6 +2343,7 @@ macro_rules! key {
         key!(max_atomic_width, Option<u64>);
         key!(min_atomic_width, Option<u64>);
         key!(atomic_cas, bool);
+        key!(atomic_shim, Option<AtomicShim>)?;
         key!(panic_strategy, PanicStrategy)?;
         key!(crt_static_allows_dylibs, bool);
         key!(crt_static_default, bool);
@@ -2159,6 +2376,7 @@ macro_rules! key {
         key!(c_enum_min_bits, u64);
         key!(generate_arange_section, bool);
         key!(supports_stack_protector, bool);
+        key!(custom_abis, custom_abis)?;
 
         if base.is_builtin {
             // This can cause unfortunate ICEs later down the line.
@@ -2375,6 +2593,7 @@ macro_rules! target_option_val {
         target_option_val!(min_atomic_width);
         target_option_val!(max_atomic_width);
         target_option_val!(atomic_cas);
+        target_option_val!(atomic_shim);
         target_option_val!(panic_strategy);
         target_option_val!(crt_static_allows_dylibs);
         target_option_val!(crt_static_default);
@@ -2406,9 +2625,10 @@ macro_rules! target_option_val {
         target_option_val!(c_enum_min_bits);
         target_option_val!(generate_arange_section);
         target_option_val!(supports_stack_protector);
+        target_option_val!(custom_abis);
 
         if let Some(abi) = self.default_adjusted_cabi {
-            d.insert("default-adjusted-cabi".into(), Abi::name(abi).to_json());
+            d.insert("default-adjusted-cabi".into(), Abi::name(&abi).to_json());
         }
 
         Json::Object(d)
@@ -44,6 +44,10 @@ fn check_consistency(&self) {
             // Keep the default "none" for bare metal targets instead.
             assert_ne!(self.os, "unknown");
         }
+        // Interrupt masking is only implemented for these architectures.
+        if self.atomic_shim == Some(AtomicShim::Interrupts) {
+            assert!(matches!(&*self.arch, "arm" | "riscv32"));
+        }
     }
 
     // Add your target to the whitelist if it has `std` library
@@ -1,6 +1,6 @@
 // Targets the Cortex-M0, Cortex-M0+ and Cortex-M1 processors (ARMv6-M architecture)
 
-use crate::spec::{Target, TargetOptions};
+use crate::spec::{AtomicShim, Target, TargetOptions};
 
 pub fn target() -> Target {
     Target {
@@ -17,6 +17,9 @@ pub fn target() -> Target {
             // There are no atomic CAS instructions available in the instruction set of the ARMv6-M
             // architecture
             atomic_cas: false,
+            // These are single-core microcontrollers, so masking interrupts is enough to make an
+            // operation atomic.
+            atomic_shim: Some(AtomicShim::Interrupts),
             ..super::thumb_base::opts()
         },
     }
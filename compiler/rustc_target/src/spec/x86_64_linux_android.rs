@@ -16,6 +16,9 @@ pub fn target() -> Target {
         data_layout: "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
             .into(),
         arch: "x86_64".into(),
-        options: TargetOptions { supported_sanitizers: SanitizerSet::ADDRESS, ..base },
+        options: TargetOptions {
+            supported_sanitizers: SanitizerSet::ADDRESS | SanitizerSet::KCFI,
+            ..base
+        },
     }
 }
@@ -198,10 +198,20 @@ fn drop_tys_helper<'tcx>(
 ) -> impl Iterator<Item = NeedsDropResult<Ty<'tcx>>> {
     fn with_query_cache<'tcx>(
         tcx: TyCtxt<'tcx>,
+        param_env: ty::ParamEnv<'tcx>,
         iter: impl IntoIterator<Item = Ty<'tcx>>,
     ) -> NeedsDropResult<Vec<Ty<'tcx>>> {
         iter.into_iter().try_fold(Vec::new(), |mut vec, subty| {
             match subty.kind() {
+                // The query is computed for the generic enum, so it does not know which variants
+                // are uninhabited with these substs. Check the type itself instead.
+                ty::Adt(adt_id, subst)
+                    if adt_id.variants().indices().any(|variant| {
+                        adt_id.is_variant_statically_uninhabited(tcx, variant, subst, param_env)
+                    }) =>
+                {
+                    vec.push(subty)
+                }
                 ty::Adt(adt_id, subst) => {
                     for subty in tcx.adt_drop_tys(adt_id.did())? {
                         vec.push(EarlyBinder(subty).subst(tcx, subst));
@@ -236,11 +246,23 @@ fn with_query_cache<'tcx>(
             debug!("drop_tys_helper: `{:?}` is a union", adt_def);
             Ok(Vec::new())
         } else {
-            let field_tys = adt_def.all_fields().map(|field| {
-                let r = tcx.bound_type_of(field.did).subst(tcx, substs);
-                debug!("drop_tys_helper: Subst into {:?} with {:?} gettng {:?}", field, substs, r);
-                r
-            });
+            // Values of an enum are never in an uninhabited variant, so its fields never need to
+            // be dropped.
+            let field_tys = adt_def
+                .variants()
+                .iter_enumerated()
+                .filter(|&(variant, _)| {
+                    !adt_def.is_variant_statically_uninhabited(tcx, variant, substs, param_env)
+                })
+                .flat_map(|(_, variant)| variant.fields.iter())
+                .map(|field| {
+                    let r = tcx.bound_type_of(field.did).subst(tcx, substs);
+                    debug!(
+                        "drop_tys_helper: Subst into {:?} with {:?} gettng {:?}",
+                        field, substs, r
+                    );
+                    r
+                });
             if only_significant {
                 // We can't recurse through the query system here because we might induce a cycle
                 Ok(field_tys.collect())
@@ -249,7 +271,7 @@ fn with_query_cache<'tcx>(
                 // ADTs are `needs_drop` exactly if they `impl Drop` or if any of their "transitive"
                 // fields do. There can be no cycles here, because ADTs cannot contain themselves as
                 // fields.
-                with_query_cache(tcx, field_tys)
+                with_query_cache(tcx, param_env, field_tys)
             }
         }
         .map(|v| v.into_iter())
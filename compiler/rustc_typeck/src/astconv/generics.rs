@@ -453,8 +453,9 @@ pub(crate) fn check_generic_arg_count(
             Self::prohibit_assoc_ty_binding(tcx, gen_args.bindings[0].span);
         }
 
-        let explicit_late_bound =
-            Self::prohibit_explicit_late_bound_lifetimes(tcx, gen_params, gen_args, gen_pos);
+        let explicit_late_bound = Self::prohibit_explicit_late_bound_lifetimes(
+            tcx, def_id, gen_params, gen_args, gen_pos,
+        );
 
         let mut invalid_args = vec![];
 
@@ -654,6 +655,7 @@ pub fn prohibit_assoc_ty_binding(tcx: TyCtxt<'_>, span: Span) {
     /// are present. This is used both for datatypes and function calls.
     pub(crate) fn prohibit_explicit_late_bound_lifetimes(
         tcx: TyCtxt<'_>,
+        def_id: DefId,
         def: &ty::Generics,
         args: &hir::GenericArgs<'_>,
         position: GenericArgPosition,
@@ -670,12 +672,22 @@ pub(crate) fn prohibit_explicit_late_bound_lifetimes(
                        if late bound lifetime parameters are present";
             let note = "the late bound lifetime parameter is introduced here";
             let span = args.args[0].span();
+            // Explain why the parameter is late-bound, unless it is an elided lifetime.
+            let binding_note = def_id.as_local().and_then(|def_id| {
+                let bindings = tcx.lifetime_binding_map(def_id)?;
+                let (&param, _) =
+                    bindings.iter().find(|(&param, _)| tcx.def_span(param) == span_late)?;
+                tcx.lifetime_binding_note(param)
+            });
 
             if position == GenericArgPosition::Value
                 && args.num_lifetime_params() != param_counts.lifetimes
             {
                 let mut err = tcx.sess.struct_span_err(span, msg);
                 err.span_note(span_late, note);
+                if let Some(binding_note) = &binding_note {
+                    err.note(binding_note);
+                }
                 err.emit();
             } else {
                 let mut multispan = MultiSpan::from_span(span);
@@ -685,7 +697,11 @@ pub(crate) fn prohibit_explicit_late_bound_lifetimes(
                     args.args[0].id(),
                     multispan,
                     |lint| {
-                        lint.build(msg).emit();
+                        let mut err = lint.build(msg);
+                        if let Some(binding_note) = &binding_note {
+                            err.note(binding_note);
+                        }
+                        err.emit();
                     },
                 );
             }
@@ -450,12 +450,7 @@ fn report_cast_error(&self, fcx: &FnCtxt<'a, 'tcx>, e: CastError) {
                 } else if let Some((sugg, remove_cast)) = sugg {
                     err.span_label(self.span, "invalid cast");
 
-                    let has_parens = fcx
-                        .tcx
-                        .sess
-                        .source_map()
-                        .span_to_snippet(self.expr_span)
-                        .map_or(false, |snip| snip.starts_with('('));
+                    let has_parens = fcx.tcx.parenthesized_inner_span(self.expr.hir_id).is_some();
 
                     // Very crude check to see whether the expression must be wrapped
                     // in parentheses for the suggestion to work (issue #89497).
@@ -1001,7 +996,8 @@ fn lossy_provenance_ptr2int_lint(&self, fcx: &FnCtxt<'a, 'tcx>, t_c: ty::cast::I
                 let msg = "use `.addr()` to obtain the address of a pointer";
 
                 let expr_prec = self.expr.precedence().order();
-                let needs_parens = expr_prec < rustc_ast::util::parser::PREC_POSTFIX;
+                let needs_parens = expr_prec < rustc_ast::util::parser::PREC_POSTFIX
+                    && fcx.tcx.parenthesized_inner_span(self.expr.hir_id).is_none();
 
                 let scalar_cast = match t_c {
                     ty::cast::IntTy::U(ty::UintTy::Usize) => String::new(),
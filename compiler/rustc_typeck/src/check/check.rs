@@ -6,7 +6,7 @@
 use super::*;
 
 use rustc_attr as attr;
-use rustc_errors::{Applicability, ErrorGuaranteed, MultiSpan};
+use rustc_errors::{pluralize, Applicability, ErrorGuaranteed, MultiSpan};
 use rustc_hir as hir;
 use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::{DefId, LocalDefId};
@@ -1213,17 +1213,50 @@ pub(super) fn check_packed(tcx: TyCtxt<'_>, sp: Span, def: ty::AdtDef<'_>) {
                             E0634,
                             "type has conflicting packed representation hints"
                         )
+                        .note(&format!(
+                            "`repr(packed({}))` limits the alignment of the fields to {} byte{}, \
+                             but another hint limits it to {} byte{}",
+                            pack,
+                            pack,
+                            pluralize!(pack),
+                            repr_pack.bytes(),
+                            pluralize!(repr_pack.bytes()),
+                        ))
+                        .note(&format!(
+                            "if both hints were applied, the smallest limit, {} byte{}, would be \
+                             used",
+                            repr_pack.bytes(),
+                            pluralize!(repr_pack.bytes()),
+                        ))
                         .emit();
             }
             }
         }
-        if repr.align.is_some() {
+        if let (Some(pack), Some(align)) = (repr.pack, repr.align) {
+            let (pack, align) = (pack.bytes(), align.bytes());
             struct_span_err!(
                 tcx.sess,
                 sp,
                 E0587,
                 "type has conflicting packed and align representation hints"
             )
+            .note(&format!(
+                "`repr(packed)` limits the alignment of the type to {} byte{}, while \
+                 `repr(align({}))` requires it to be at least {} bytes",
+                pack,
+                pluralize!(pack),
+                align,
+                align,
+            ))
+            .note(&format!(
+                "if both hints were applied, the fields would be packed but the type as a whole \
+                 would still be aligned to {} bytes",
+                align,
+            ))
+            .help(&format!(
+                "to align a packed type, wrap it in a struct with `#[repr(align({}))]`",
+                align
+            ))
             .emit();
         } else {
             if let Some(def_spans) = check_packed_inner(tcx, def.did(), &mut vec![]) {
@@ -691,6 +691,10 @@ pub fn check_ref(
                     }
                     if let Ok(src) = sm.span_to_snippet(sugg_sp) {
                         let needs_parens = match expr.kind {
+                            // the user already wrote the parentheses
+                            _ if self.tcx.parenthesized_inner_span(expr.hir_id).is_some() => {
+                                false
+                            }
                             // parenthesize if needed (Issue #46756)
                             hir::ExprKind::Cast(_, _) | hir::ExprKind::Binary(_, _, _) => true,
                             // parenthesize borrows of range literals (Issue #54505)
@@ -1042,7 +1046,10 @@ pub fn check_for_cast(
             "change the type of the numeric literal from `{checked_ty}` to `{expected_ty}`",
         );
 
-        let close_paren = if expr.precedence().order() < PREC_POSTFIX {
+        // Expressions the user already parenthesized don't need another pair.
+        let needs_parens = expr.precedence().order() < PREC_POSTFIX
+            && self.tcx.parenthesized_inner_span(expr.hir_id).is_none();
+        let close_paren = if needs_parens {
             sugg.push((expr.span.shrink_to_lo(), "(".to_string()));
             ")"
         } else {
@@ -1067,7 +1074,7 @@ pub fn check_for_cast(
                 let len = src.trim_end_matches(&checked_ty.to_string()).len();
                 expr.span.with_lo(expr.span.lo() + BytePos(len as u32))
             },
-            if expr.precedence().order() < PREC_POSTFIX {
+            if needs_parens {
                 // Readd `)`
                 format!("{expected_ty})")
             } else {
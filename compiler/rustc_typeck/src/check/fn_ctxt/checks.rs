@@ -23,7 +23,8 @@
 use rustc_middle::ty::adjustment::AllowTwoPhase;
 use rustc_middle::ty::error::TypeError;
 use rustc_middle::ty::fold::TypeFoldable;
-use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_middle::ty::subst::GenericArgKind;
+use rustc_middle::ty::{self, ToPredicate, Ty, TyCtxt};
 use rustc_session::Session;
 use rustc_span::symbol::Ident;
 use rustc_span::{self, Span};
@@ -64,6 +65,56 @@ pub(in super::super) fn check_asms(&self) {
         }
     }
 
+    /// Checks the hidden types of this body's return-position `impl Trait`s against the auto
+    /// traits declared on them, so that a hidden type that isn't `Send` or `Sync` is reported
+    /// where it is returned instead of at the signature or at some distant use of the opaque
+    /// type. The remaining bounds are left to `check_opaque_meets_bounds`.
+    pub(in super::super) fn check_opaque_auto_trait_bounds(&self, body: &'tcx hir::Body<'tcx>) {
+        let defining_use_span = match body.value.kind {
+            ExprKind::Block(block, _) => block.expr.map(|expr| expr.span),
+            _ => Some(body.value.span),
+        };
+        let Some(span) = defining_use_span.or_else(|| self.ret_coercion_span.get()) else {
+            return;
+        };
+
+        let opaque_types = self.infcx.inner.borrow().opaque_type_storage.opaque_types.clone();
+        for (opaque_type_key, decl) in opaque_types {
+            if !matches!(decl.origin, hir::OpaqueTyOrigin::FnReturn(_)) {
+                continue;
+            }
+            let hidden_ty = self.resolve_vars_if_possible(decl.hidden_type.ty);
+            // Leaking the auto traits of another opaque type of this crate needs its hidden type,
+            // which may in turn be waiting on this body, so those are only checked after typeck.
+            // Hidden types that are still unknown are also left alone here.
+            let mentions_local_opaque = hidden_ty.walk().any(|arg| {
+                matches!(arg.unpack(), GenericArgKind::Type(ty)
+                    if matches!(*ty.kind(), ty::Opaque(def_id, _) if def_id.is_local()))
+            });
+            if hidden_ty.references_error()
+                || hidden_ty.has_infer_types()
+                || mentions_local_opaque
+            {
+                continue;
+            }
+            for &(predicate, _) in self.tcx.explicit_item_bounds(opaque_type_key.def_id) {
+                let ty::PredicateKind::Trait(trait_pred) = predicate.kind().skip_binder() else {
+                    continue;
+                };
+                if !self.tcx.trait_is_auto(trait_pred.def_id()) {
+                    continue;
+                }
+                let trait_ref =
+                    ty::TraitRef::new(trait_pred.def_id(), self.tcx.mk_substs_trait(hidden_ty, &[]));
+                self.register_predicate(traits::Obligation::new(
+                    self.cause(span, ObligationCauseCode::OpaqueType),
+                    self.param_env,
+                    ty::Binder::dummy(trait_ref).without_const().to_predicate(self.tcx),
+                ));
+            }
+        }
+    }
+
     pub(in super::super) fn check_method_argument_types(
         &self,
         sp: Span,
@@ -249,6 +249,10 @@ pub fn suggest_deref_ref_or_into(
                                 } else {
                                     if expr.precedence().order()
                                         < ExprPrecedence::MethodCall.order()
+                                        && self
+                                            .tcx
+                                            .parenthesized_inner_span(expr.hir_id)
+                                            .is_none()
                                     {
                                         vec![
                                             (expr.span.shrink_to_lo(), "(".to_string()),
@@ -1340,6 +1340,7 @@ fn check_struct_pat_fields(
                     pat,
                     &accessible_unmentioned_fields,
                     adt_ty,
+                    fields,
                 )
             }
         }
@@ -1686,6 +1687,7 @@ fn lint_non_exhaustive_omitted_patterns(
         pat: &Pat<'_>,
         unmentioned_fields: &[(&ty::FieldDef, Ident)],
         ty: Ty<'tcx>,
+        fields: &'tcx [hir::PatField<'tcx>],
     ) {
         fn joined_uncovered_patterns(witnesses: &[&Ident]) -> String {
             const LIMIT: usize = 3;
@@ -1711,9 +1713,35 @@ fn joined_uncovered_patterns(witnesses: &[&Ident]) -> String {
         let mut lint = build.build("some fields are not explicitly listed");
         lint.span_label(pat.span, format!("field{} {} not listed", rustc_errors::pluralize!(unmentioned_fields.len()), joined_patterns));
 
-        lint.help(
-            "ensure that all fields are mentioned explicitly by adding the suggested fields",
-        );
+        // The pattern has a `..`, which the missing fields are inserted in front of.
+        let missing: String =
+            unmentioned_fields.iter().map(|(_, name)| format!("{}: _, ", name)).collect();
+        let suggestion = match (fields, &pat.kind) {
+            ([.., last], _) => Some((
+                last.span.shrink_to_hi(),
+                format!(", {}", missing.trim_end_matches(", ")),
+            )),
+            ([], PatKind::Struct(path, ..)) => Some((
+                path.span().shrink_to_hi().until(pat.span.shrink_to_hi()),
+                format!(" {{ {}.. }}", missing),
+            )),
+            _ => None,
+        };
+        match suggestion {
+            Some((span, suggestion)) => {
+                lint.span_suggestion_verbose(
+                    span,
+                    "ensure that all fields are mentioned explicitly by adding the suggested fields",
+                    suggestion,
+                    Applicability::MachineApplicable,
+                );
+            }
+            None => {
+                lint.help(
+                    "ensure that all fields are mentioned explicitly by adding the suggested fields",
+                );
+            }
+        }
         lint.note(&format!(
             "the pattern is of type `{}` and the `non_exhaustive_omitted_patterns` attribute was found",
             ty,
@@ -423,11 +423,14 @@ fn convert_place_op_to_mutable(
                 if let Adjust::Borrow(AutoBorrow::Ref(..)) = adjustment.kind {
                     debug!("convert_place_op_to_mutable: converting autoref {:?}", adjustment);
                     let mutbl = AutoBorrowMutability::Mut {
-                        // Deref/indexing can be desugared to a method call,
-                        // so maybe we could use two-phase here.
-                        // See the documentation of AllowTwoPhase for why that's
-                        // not the case today.
-                        allow_two_phase_borrow: AllowTwoPhase::No,
+                        // Indexing desugars to a call to `IndexMut::index_mut`, so, like
+                        // method receivers, it may use a two-phase borrow. This allows
+                        // e.g. `v[v.len() - 1] = x`. Overloaded derefs are not (yet)
+                        // treated the same way, see the documentation of AllowTwoPhase.
+                        allow_two_phase_borrow: match op {
+                            PlaceOp::Index => AllowTwoPhase::Yes,
+                            PlaceOp::Deref => AllowTwoPhase::No,
+                        },
                     };
                     adjustment.kind = Adjust::Borrow(AutoBorrow::Ref(*region, mutbl));
                     adjustment.target = self
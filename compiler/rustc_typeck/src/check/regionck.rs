@@ -82,7 +82,7 @@
 use rustc_hir::intravisit::{self, Visitor};
 use rustc_hir::PatKind;
 use rustc_infer::infer::outlives::env::OutlivesEnvironment;
-use rustc_infer::infer::{self, InferCtxt, RegionObligation, RegionckMode};
+use rustc_infer::infer::{self, InferCtxt, RegionObligation, RegionResolutionError, RegionckMode};
 use rustc_middle::hir::place::{PlaceBase, PlaceWithHirId};
 use rustc_middle::ty::adjustment;
 use rustc_middle::ty::{self, Ty};
@@ -178,6 +178,29 @@ pub fn regionck_item(&self, item_id: hir::HirId, span: Span, wf_tys: FxHashSet<T
         rcx.resolve_regions_and_report_errors(RegionckMode::default());
     }
 
+    /// Like `regionck_item`, but returns the region errors instead of reporting them.
+    pub fn regionck_item_errors(
+        &self,
+        item_id: hir::HirId,
+        span: Span,
+        wf_tys: FxHashSet<Ty<'tcx>>,
+    ) -> Vec<RegionResolutionError<'tcx>> {
+        let subject = self.tcx.hir().local_def_id(item_id);
+        let mut rcx = RegionCtxt::new(self, item_id, Subject(subject), self.param_env);
+        rcx.outlives_environment.add_implied_bounds(self, wf_tys, item_id, span);
+        rcx.visit_region_obligations(item_id);
+        rcx.infcx.process_registered_region_obligations(
+            rcx.outlives_environment.region_bound_pairs_map(),
+            Some(self.tcx.lifetimes.re_root_empty),
+            self.param_env,
+        );
+        self.resolve_regions(
+            subject.to_def_id(),
+            &rcx.outlives_environment,
+            RegionckMode::default(),
+        )
+    }
+
     /// Region check a function body. Not invoked on closures, but
     /// only on the "root" fn item (in which closures may be
     /// embedded). Walks the function body and adds various add'l
@@ -1,10 +1,17 @@
 use crate::check::regionck::OutlivesEnvironmentExt;
 use crate::check::{FnCtxt, Inherited};
 use crate::constrained_generic_params::{identify_constrained_generic_params, Parameter};
+use crate::errors::{
+    AutoTraitImplDefault, ConstParamTyForbidden, ConstParamTyNotStructural,
+    ConstParamTyParamNotStructural, ConstParamTyUnsupported, FnLangItemCallArgCount,
+    FnLangItemCallNotFn, FnLangItemCallSelfNotRef, InvalidSelfParameterType, MarkerTraitAssocItem,
+    MissingRequiredBoundsOnGat, NegativeImplDefault, SelfTyNeedsArbitrarySelfTypes,
+    TraitObjectOfOwnTrait, UnusedParameter, UnusedParameterHelp,
+};
 
 use rustc_ast as ast;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
-use rustc_errors::{struct_span_err, Applicability, DiagnosticBuilder, ErrorGuaranteed};
+use rustc_errors::{Applicability, ErrorGuaranteed};
 use rustc_hir as hir;
 use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_hir::intravisit as hir_visit;
@@ -15,17 +22,17 @@
 use rustc_infer::infer::outlives::env::OutlivesEnvironment;
 use rustc_infer::infer::outlives::obligations::TypeOutlives;
 use rustc_infer::infer::region_constraints::GenericKind;
-use rustc_infer::infer::{self, RegionckMode};
+use rustc_infer::infer::{self, RegionResolutionError, RegionckMode};
 use rustc_infer::infer::{InferCtxt, TyCtxtInferExt};
 use rustc_middle::hir::nested_filter;
 use rustc_middle::ty::subst::{GenericArgKind, InternalSubsts, Subst};
 use rustc_middle::ty::trait_def::TraitSpecializationKind;
 use rustc_middle::ty::{
-    self, AdtKind, EarlyBinder, GenericParamDefKind, ToPredicate, Ty, TyCtxt, TypeFoldable,
-    TypeSuperFoldable, TypeVisitor,
+    self, AdtKind, EarlyBinder, GenericParamDefKind, ToPredicate, Ty, TyCtxt, TypeFlags,
+    TypeFoldable, TypeSuperFoldable, TypeVisitor,
 };
-use rustc_session::parse::feature_err;
-use rustc_span::symbol::{sym, Ident, Symbol};
+use rustc_session::lint::builtin::RETURN_TYPE_IMPLIED_BOUNDS;
+use rustc_span::symbol::Ident;
 use rustc_span::{Span, DUMMY_SP};
 use rustc_trait_selection::traits::query::evaluate_obligation::InferCtxtExt as _;
 use rustc_trait_selection::traits::{self, ObligationCause, ObligationCauseCode, WellFormedLoc};
@@ -69,6 +76,40 @@ pub(super) fn with_fcx<F>(&mut self, f: F)
             fcx.regionck_item(id, span, wf_tys);
         });
     }
+
+    /// Like `with_fcx`, but returns the region errors instead of reporting them, as the bounds
+    /// that would fix them, or `None` for the errors that can't be fixed by a where clause. Only
+    /// meant for items that were already checked by `with_fcx` without errors.
+    fn missing_bounds<F>(&mut self, f: F) -> Vec<Option<String>>
+    where
+        F: for<'b> FnOnce(&FnCtxt<'b, 'tcx>) -> FxHashSet<Ty<'tcx>>,
+    {
+        let id = self.id;
+        let span = self.span;
+        let param_env = self.param_env;
+        self.inherited.enter(|inh| {
+            let fcx = FnCtxt::new(&inh, param_env, id);
+            let wf_tys = f(&fcx);
+            fcx.select_all_obligations_or_error();
+            let errors = fcx.regionck_item_errors(id, span, wf_tys);
+            errors
+                .iter()
+                .map(|error| match *error {
+                    RegionResolutionError::ConcreteFailure(_, sub, sup)
+                        if sub.has_name() && sup.has_name() =>
+                    {
+                        Some(format!("{}: {}", sup, sub))
+                    }
+                    RegionResolutionError::GenericBoundFailure(_, ref kind, region)
+                        if region.has_name() =>
+                    {
+                        Some(format!("{}: {}", kind, region))
+                    }
+                    _ => None,
+                })
+                .collect()
+        })
+    }
 }
 
 /// Checks that the field types (in a struct def'n) or argument types (in an enum def'n) are
@@ -117,11 +158,10 @@ pub fn check_item_well_formed(tcx: TyCtxt<'_>, def_id: LocalDefId) {
                 .map_or(false, |trait_ref| tcx.trait_is_auto(trait_ref.def_id));
             if let (hir::Defaultness::Default { .. }, true) = (impl_.defaultness, is_auto) {
                 let sp = impl_.of_trait.as_ref().map_or(item.span, |t| t.path.span);
-                let mut err =
-                    tcx.sess.struct_span_err(sp, "impls of auto traits cannot be default");
-                err.span_labels(impl_.defaultness_span, "default because of this");
-                err.span_label(sp, "auto trait");
-                err.emit();
+                tcx.sess.emit_err(AutoTraitImplDefault {
+                    span: sp,
+                    defaultness_span: impl_.defaultness_span,
+                });
             }
             // We match on both `ty::ImplPolarity` and `ast::ImplPolarity` just to get the `!` span.
             match (tcx.impl_polarity(def_id), impl_.polarity) {
@@ -133,13 +173,7 @@ pub fn check_item_well_formed(tcx: TyCtxt<'_>, def_id: LocalDefId) {
                     if let hir::Defaultness::Default { .. } = impl_.defaultness {
                         let mut spans = vec![span];
                         spans.extend(impl_.defaultness_span);
-                        struct_span_err!(
-                            tcx.sess,
-                            spans,
-                            E0750,
-                            "negative impls cannot be default impls"
-                        )
-                        .emit();
+                        tcx.sess.emit_err(NegativeImplDefault { spans });
                     }
                 }
                 (ty::ImplPolarity::Reservation, _) => {
@@ -227,34 +261,16 @@ pub fn check_trait_item(tcx: TyCtxt<'_>, def_id: LocalDefId) {
         if let Some(hir::FnSig { decl, span, .. }) = method_sig {
             if let [self_ty, _] = decl.inputs {
                 if !matches!(self_ty.kind, hir::TyKind::Rptr(_, _)) {
-                    tcx.sess
-                        .struct_span_err(
-                            self_ty.span,
-                            &format!(
-                                "first argument of `call` in `{fn_lang_item_name}` lang item must be a reference",
-                            ),
-                        )
-                        .emit();
+                    tcx.sess.emit_err(FnLangItemCallSelfNotRef {
+                        span: self_ty.span,
+                        fn_lang_item_name,
+                    });
                 }
             } else {
-                tcx.sess
-                    .struct_span_err(
-                        *span,
-                        &format!(
-                            "`call` function in `{fn_lang_item_name}` lang item takes exactly two arguments",
-                        ),
-                    )
-                    .emit();
+                tcx.sess.emit_err(FnLangItemCallArgCount { span: *span, fn_lang_item_name });
             }
         } else {
-            tcx.sess
-                .struct_span_err(
-                    trait_item.span,
-                    &format!(
-                        "`call` trait item in `{fn_lang_item_name}` lang item must be a function",
-                    ),
-                )
-                .emit();
+            tcx.sess.emit_err(FnLangItemCallNotFn { span: trait_item.span, fn_lang_item_name });
         }
     }
 }
@@ -413,37 +429,18 @@ fn check_gat_where_clauses(tcx: TyCtxt<'_>, associated_items: &[hir::TraitItemRe
         unsatisfied_bounds.sort();
 
         if !unsatisfied_bounds.is_empty() {
-            let plural = if unsatisfied_bounds.len() > 1 { "s" } else { "" };
-            let mut err = tcx.sess.struct_span_err(
-                gat_item_hir.span,
-                &format!("missing required bound{} on `{}`", plural, gat_item_hir.ident),
-            );
-
             let suggestion = format!(
                 "{} {}",
                 if !gat_item_hir.generics.predicates.is_empty() { "," } else { " where" },
                 unsatisfied_bounds.join(", "),
             );
-            err.span_suggestion(
-                gat_item_hir.generics.tail_span_for_predicate_suggestion(),
-                &format!("add the required where clause{plural}"),
+            tcx.sess.emit_err(MissingRequiredBoundsOnGat {
+                span: gat_item_hir.span,
+                suggestion_span: gat_item_hir.generics.tail_span_for_predicate_suggestion(),
                 suggestion,
-                Applicability::MachineApplicable,
-            );
-
-            let bound =
-                if unsatisfied_bounds.len() > 1 { "these bounds are" } else { "this bound is" };
-            err.note(&format!(
-                "{} currently required to ensure that impls have maximum flexibility",
-                bound
-            ));
-            err.note(
-                "we are soliciting feedback, see issue #87479 \
-                 <https://github.com/rust-lang/rust/issues/87479> \
-                 for more information",
-            );
-
-            err.emit();
+                ident: gat_item_hir.ident,
+                count: unsatisfied_bounds.len(),
+            });
         }
     }
 }
@@ -772,19 +769,10 @@ fn check_object_unsafe_self_trait_by_name(tcx: TyCtxt<'_>, item: &hir::TraitItem
         if tcx.object_safety_violations(trait_def_id).is_empty() {
             return;
         }
-        let sugg = trait_should_be_self.iter().map(|span| (*span, "Self".to_string())).collect();
-        tcx.sess
-            .struct_span_err(
-                trait_should_be_self,
-                "associated item referring to unboxed trait object for its own trait",
-            )
-            .span_label(trait_name.span, "in this trait")
-            .multipart_suggestion(
-                "you might have meant to use `Self` to refer to the implementing type",
-                sugg,
-                Applicability::MachineApplicable,
-            )
-            .emit();
+        tcx.sess.emit_err(TraitObjectOfOwnTrait {
+            spans: trait_should_be_self,
+            trait_name_span: trait_name.span,
+        });
     }
 }
 
@@ -801,6 +789,19 @@ pub fn check_impl_item(tcx: TyCtxt<'_>, def_id: LocalDefId) {
     check_associated_item(tcx, impl_item.def_id, span, method_sig);
 }
 
+/// Looks through the references, slices, arrays and tuples in the type of a const parameter for
+/// types that cannot be compared structurally at compile time.
+fn unsupported_const_param_ty(ty: Ty<'_>) -> Option<&'static str> {
+    match *ty.kind() {
+        ty::FnPtr(_) => Some("function pointers"),
+        ty::RawPtr(_) => Some("raw pointers"),
+        ty::Ref(_, _, hir::Mutability::Mut) => Some("mutable references"),
+        ty::Ref(_, ty, _) | ty::Slice(ty) | ty::Array(ty, _) => unsupported_const_param_ty(ty),
+        ty::Tuple(tys) => tys.iter().find_map(unsupported_const_param_ty),
+        _ => None,
+    }
+}
+
 fn check_param_wf(tcx: TyCtxt<'_>, param: &hir::GenericParam<'_>) {
     match param.kind {
         // We currently only check wf of const params here.
@@ -811,20 +812,9 @@ fn check_param_wf(tcx: TyCtxt<'_>, param: &hir::GenericParam<'_>) {
             let ty = tcx.type_of(tcx.hir().local_def_id(param.hir_id));
 
             if tcx.features().adt_const_params {
-                let err = match ty.peel_refs().kind() {
-                    ty::FnPtr(_) => Some("function pointers"),
-                    ty::RawPtr(_) => Some("raw pointers"),
-                    _ => None,
-                };
-
-                if let Some(unsupported_type) = err {
-                    tcx.sess.span_err(
-                        hir_ty.span,
-                        &format!(
-                            "using {} as const generic parameters is forbidden",
-                            unsupported_type
-                        ),
-                    );
+                if let Some(unsupported_type) = unsupported_const_param_ty(ty) {
+                    tcx.sess
+                        .emit_err(ConstParamTyForbidden { span: hir_ty.span, unsupported_type });
                 }
 
                 if let Some(non_structural_match_ty) =
@@ -837,82 +827,31 @@ fn check_param_wf(tcx: TyCtxt<'_>, param: &hir::GenericParam<'_>) {
                         // Const parameters may not have type parameters as their types,
                         // because we cannot be sure that the type parameter derives `PartialEq`
                         // and `Eq` (just implementing them is not enough for `structural_match`).
-                        struct_span_err!(
-                            tcx.sess,
-                            hir_ty.span,
-                            E0741,
-                            "`{}` is not guaranteed to `#[derive(PartialEq, Eq)]`, so may not be \
-                            used as the type of a const parameter",
-                            ty,
-                        )
-                        .span_label(
-                            hir_ty.span,
-                            format!("`{}` may not derive both `PartialEq` and `Eq`", ty),
-                        )
-                        .note(
-                            "it is not currently possible to use a type parameter as the type of a \
-                            const parameter",
-                        )
-                        .emit();
+                        tcx.sess
+                            .emit_err(ConstParamTyParamNotStructural { span: hir_ty.span, ty });
                     } else {
-                        let mut diag = struct_span_err!(
-                            tcx.sess,
-                            hir_ty.span,
-                            E0741,
-                            "`{}` must be annotated with `#[derive(PartialEq, Eq)]` to be used as \
-                            the type of a const parameter",
-                            non_structural_match_ty.ty,
-                        );
-
-                        if ty == non_structural_match_ty.ty {
-                            diag.span_label(
-                                hir_ty.span,
-                                format!("`{ty}` doesn't derive both `PartialEq` and `Eq`"),
-                            );
-                        }
-
-                        diag.emit();
+                        tcx.sess.emit_err(ConstParamTyNotStructural {
+                            span: hir_ty.span,
+                            label: (ty == non_structural_match_ty.ty).then_some(hir_ty.span),
+                            ty: non_structural_match_ty.ty,
+                        });
                     }
                 }
             } else {
-                let err_ty_str;
-                let mut is_ptr = true;
-
-                let err = match ty.kind() {
-                    ty::Bool | ty::Char | ty::Int(_) | ty::Uint(_) | ty::Error(_) => None,
-                    ty::FnPtr(_) => Some("function pointers"),
-                    ty::RawPtr(_) => Some("raw pointers"),
+                let unsupported_type = match ty.kind() {
+                    ty::Bool | ty::Char | ty::Int(_) | ty::Uint(_) | ty::Error(_) => return,
+                    ty::FnPtr(_) => "function pointers",
+                    ty::RawPtr(_) => "raw pointers",
                     _ => {
-                        is_ptr = false;
-                        err_ty_str = format!("`{ty}`");
-                        Some(err_ty_str.as_str())
+                        tcx.sess.emit_err(ConstParamTyUnsupported {
+                            span: hir_ty.span,
+                            ty,
+                            adt_const_params_help: tcx.sess.is_nightly_build().then_some(()),
+                        });
+                        return;
                     }
                 };
-
-                if let Some(unsupported_type) = err {
-                    if is_ptr {
-                        tcx.sess.span_err(
-                            hir_ty.span,
-                            &format!(
-                                "using {unsupported_type} as const generic parameters is forbidden",
-                            ),
-                        );
-                    } else {
-                        let mut err = tcx.sess.struct_span_err(
-                            hir_ty.span,
-                            &format!(
-                                "{unsupported_type} is forbidden as the type of a const generic parameter",
-                            ),
-                        );
-                        err.note("the only supported types are integers, `bool` and `char`");
-                        if tcx.sess.is_nightly_build() {
-                            err.help(
-                            "more complex types are supported with `#![feature(adt_const_params)]`",
-                        );
-                        }
-                        err.emit();
-                    }
-                }
+                tcx.sess.emit_err(ConstParamTyForbidden { span: hir_ty.span, unsupported_type });
             }
         }
     }
@@ -925,51 +864,66 @@ fn check_associated_item(
     span: Span,
     sig_if_method: Option<&hir::FnSig<'_>>,
 ) {
+    let errors = tcx.sess.err_count();
+    for_id(tcx, item_id, span)
+        .with_fcx(|fcx| check_associated_item_in(fcx, item_id, span, sig_if_method, true));
+    if let Some(sig) = sig_if_method && tcx.sess.err_count() == errors {
+        lint_return_type_implied_bounds(tcx, item_id, span, sig.decl, |fcx| {
+            check_associated_item_in(fcx, item_id, span, sig_if_method, false)
+        });
+    }
+}
+
+fn check_associated_item_in<'tcx>(
+    fcx: &FnCtxt<'_, 'tcx>,
+    item_id: LocalDefId,
+    span: Span,
+    sig_if_method: Option<&hir::FnSig<'_>>,
+    return_type_is_implied_bound: bool,
+) -> FxHashSet<Ty<'tcx>> {
     let code = ObligationCauseCode::WellFormed(Some(WellFormedLoc::Ty(item_id)));
-    for_id(tcx, item_id, span).with_fcx(|fcx| {
-        let item = fcx.tcx.associated_item(item_id);
+    let item = fcx.tcx.associated_item(item_id);
 
-        let (mut implied_bounds, self_ty) = match item.container {
-            ty::TraitContainer(_) => (FxHashSet::default(), fcx.tcx.types.self_param),
-            ty::ImplContainer(def_id) => {
-                (fcx.impl_implied_bounds(def_id, span), fcx.tcx.type_of(def_id))
-            }
-        };
+    let (mut implied_bounds, self_ty) = match item.container {
+        ty::TraitContainer(_) => (FxHashSet::default(), fcx.tcx.types.self_param),
+        ty::ImplContainer(def_id) => {
+            (fcx.impl_implied_bounds(def_id, span), fcx.tcx.type_of(def_id))
+        }
+    };
 
-        match item.kind {
-            ty::AssocKind::Const => {
+    match item.kind {
+        ty::AssocKind::Const => {
+            let ty = fcx.tcx.type_of(item.def_id);
+            let ty = fcx.normalize_associated_types_in_wf(span, ty, WellFormedLoc::Ty(item_id));
+            fcx.register_wf_obligation(ty.into(), span, code.clone());
+        }
+        ty::AssocKind::Fn => {
+            let sig = fcx.tcx.fn_sig(item.def_id);
+            let hir_sig = sig_if_method.expect("bad signature for method");
+            check_fn_or_method(
+                fcx,
+                item.ident(fcx.tcx).span,
+                sig,
+                hir_sig.decl,
+                item.def_id.expect_local(),
+                &mut implied_bounds,
+                return_type_is_implied_bound,
+            );
+            check_method_receiver(fcx, hir_sig, item, self_ty);
+        }
+        ty::AssocKind::Type => {
+            if let ty::AssocItemContainer::TraitContainer(_) = item.container {
+                check_associated_type_bounds(fcx, item, span)
+            }
+            if item.defaultness.has_value() {
                 let ty = fcx.tcx.type_of(item.def_id);
                 let ty = fcx.normalize_associated_types_in_wf(span, ty, WellFormedLoc::Ty(item_id));
                 fcx.register_wf_obligation(ty.into(), span, code.clone());
             }
-            ty::AssocKind::Fn => {
-                let sig = fcx.tcx.fn_sig(item.def_id);
-                let hir_sig = sig_if_method.expect("bad signature for method");
-                check_fn_or_method(
-                    fcx,
-                    item.ident(fcx.tcx).span,
-                    sig,
-                    hir_sig.decl,
-                    item.def_id.expect_local(),
-                    &mut implied_bounds,
-                );
-                check_method_receiver(fcx, hir_sig, item, self_ty);
-            }
-            ty::AssocKind::Type => {
-                if let ty::AssocItemContainer::TraitContainer(_) = item.container {
-                    check_associated_type_bounds(fcx, item, span)
-                }
-                if item.defaultness.has_value() {
-                    let ty = fcx.tcx.type_of(item.def_id);
-                    let ty =
-                        fcx.normalize_associated_types_in_wf(span, ty, WellFormedLoc::Ty(item_id));
-                    fcx.register_wf_obligation(ty.into(), span, code.clone());
-                }
-            }
         }
+    }
 
-        implied_bounds
-    })
+    implied_bounds
 }
 
 pub(super) fn for_item<'tcx>(tcx: TyCtxt<'tcx>, item: &hir::Item<'_>) -> CheckWfFcxBuilder<'tcx> {
@@ -1094,13 +1048,7 @@ fn check_trait(tcx: TyCtxt<'_>, item: &hir::Item<'_>) {
         || matches!(trait_def.specialization_kind, TraitSpecializationKind::Marker)
     {
         for associated_def_id in &*tcx.associated_item_def_ids(item.def_id) {
-            struct_span_err!(
-                tcx.sess,
-                tcx.def_span(*associated_def_id),
-                E0714,
-                "marker traits cannot have associated items",
-            )
-            .emit();
+            tcx.sess.emit_err(MarkerTraitAssocItem { span: tcx.def_span(*associated_def_id) });
         }
     }
 
@@ -1151,12 +1099,62 @@ fn check_item_fn(
     span: Span,
     decl: &hir::FnDecl<'_>,
 ) {
+    let errors = tcx.sess.err_count();
     for_id(tcx, def_id, span).with_fcx(|fcx| {
         let sig = tcx.fn_sig(def_id);
         let mut implied_bounds = FxHashSet::default();
-        check_fn_or_method(fcx, ident.span, sig, decl, def_id, &mut implied_bounds);
+        check_fn_or_method(fcx, ident.span, sig, decl, def_id, &mut implied_bounds, true);
         implied_bounds
-    })
+    });
+    if tcx.sess.err_count() == errors {
+        lint_return_type_implied_bounds(tcx, def_id, span, decl, |fcx| {
+            let sig = tcx.fn_sig(def_id);
+            let mut implied_bounds = FxHashSet::default();
+            check_fn_or_method(fcx, ident.span, sig, decl, def_id, &mut implied_bounds, false);
+            implied_bounds
+        });
+    }
+}
+
+/// Lints the functions whose signature is only well-formed because their return type is an
+/// implied bound, by checking them again with `check`, which must not add the return type to
+/// the implied bounds.
+fn lint_return_type_implied_bounds<'tcx, F>(
+    tcx: TyCtxt<'tcx>,
+    def_id: LocalDefId,
+    span: Span,
+    decl: &hir::FnDecl<'_>,
+    check: F,
+) where
+    F: for<'b> FnOnce(&FnCtxt<'b, 'tcx>) -> FxHashSet<Ty<'tcx>>,
+{
+    // Only the return types with regions imply bounds.
+    let output = tcx.fn_sig(def_id).output().skip_binder();
+    if !output.has_type_flags(TypeFlags::HAS_FREE_REGIONS | TypeFlags::HAS_RE_LATE_BOUND) {
+        return;
+    }
+    let bounds = for_id(tcx, def_id, span).missing_bounds(check);
+    if bounds.is_empty() {
+        return;
+    }
+
+    let hir_id = tcx.hir().local_def_id_to_hir_id(def_id);
+    tcx.struct_span_lint_hir(RETURN_TYPE_IMPLIED_BOUNDS, hir_id, decl.output.span(), |lint| {
+        let mut err = lint.build("this signature is only well-formed because of its return type");
+        let mut bounds: Vec<_> = bounds.into_iter().collect::<Option<_>>().unwrap_or_default();
+        bounds.sort();
+        bounds.dedup();
+        if let Some(generics) = tcx.hir().get_generics(def_id) && !bounds.is_empty() {
+            let separator = if generics.has_where_clause { "," } else { " where" };
+            err.span_suggestion_verbose(
+                generics.tail_span_for_predicate_suggestion(),
+                "add the bounds implied by the return type as where clauses",
+                format!("{} {}", separator, bounds.join(", ")),
+                Applicability::MachineApplicable,
+            );
+        }
+        err.emit();
+    });
 }
 
 fn check_item_type(tcx: TyCtxt<'_>, item_id: LocalDefId, ty_span: Span, allow_foreign_ty: bool) {
@@ -1460,6 +1458,7 @@ fn check_fn_or_method<'fcx, 'tcx>(
     hir_decl: &hir::FnDecl<'_>,
     def_id: LocalDefId,
     implied_bounds: &mut FxHashSet<Ty<'tcx>>,
+    return_type_is_implied_bound: bool,
 ) {
     let sig = fcx.tcx.liberate_late_bound_regions(def_id.to_def_id(), sig);
 
@@ -1512,18 +1511,17 @@ fn check_fn_or_method<'fcx, 'tcx>(
         ObligationCauseCode::ReturnType,
     );
 
-    // FIXME(#27579) return types should not be implied bounds
-    implied_bounds.insert(sig.output());
+    // FIXME(#27579) return types should not be implied bounds. The `return_type_implied_bounds`
+    // lint checks the signature without it to warn about the functions relying on it.
+    if return_type_is_implied_bound {
+        implied_bounds.insert(sig.output());
+    }
 
     debug!(?implied_bounds);
 
     check_where_clauses(fcx, span, def_id, Some((sig.output(), hir_decl.output.span())));
 }
 
-const HELP_FOR_SELF_TYPE: &str = "consider changing to `self`, `&self`, `&mut self`, `self: Box<Self>`, \
-     `self: Rc<Self>`, `self: Arc<Self>`, or `self: Pin<P>` (where P is one \
-     of the previous types except `Self`)";
-
 #[tracing::instrument(level = "debug", skip(fcx))]
 fn check_method_receiver<'fcx, 'tcx>(
     fcx: &FnCtxt<'fcx, 'tcx>,
@@ -1560,17 +1558,7 @@ fn check_method_receiver<'fcx, 'tcx>(
         if !receiver_is_valid(fcx, span, receiver_ty, self_ty, false) {
             if receiver_is_valid(fcx, span, receiver_ty, self_ty, true) {
                 // Report error; would have worked with `arbitrary_self_types`.
-                feature_err(
-                    &fcx.tcx.sess.parse_sess,
-                    sym::arbitrary_self_types,
-                    span,
-                    &format!(
-                        "`{receiver_ty}` cannot be used as the type of `self` without \
-                         the `arbitrary_self_types` feature",
-                    ),
-                )
-                .help(HELP_FOR_SELF_TYPE)
-                .emit();
+                fcx.tcx.sess.emit_err(SelfTyNeedsArbitrarySelfTypes { span, receiver_ty });
             } else {
                 // Report error; would not have worked with `arbitrary_self_types`.
                 e0307(fcx, span, receiver_ty);
@@ -1579,16 +1567,8 @@ fn check_method_receiver<'fcx, 'tcx>(
     }
 }
 
-fn e0307<'tcx>(fcx: &FnCtxt<'_, 'tcx>, span: Span, receiver_ty: Ty<'_>) {
-    struct_span_err!(
-        fcx.tcx.sess.diagnostic(),
-        span,
-        E0307,
-        "invalid `self` parameter type: {receiver_ty}"
-    )
-    .note("type of `self` must be `Self` or a type that dereferences to it")
-    .help(HELP_FOR_SELF_TYPE)
-    .emit();
+fn e0307<'tcx>(fcx: &FnCtxt<'_, 'tcx>, span: Span, receiver_ty: Ty<'tcx>) {
+    fcx.tcx.sess.emit_err(InvalidSelfParameterType { span, receiver_ty });
 }
 
 /// Returns whether `receiver_ty` would be considered a valid receiver type for `self_ty`. If
@@ -1774,30 +1754,17 @@ fn report_bivariance(
     param: &rustc_hir::GenericParam<'_>,
     has_explicit_bounds: bool,
 ) -> ErrorGuaranteed {
-    let span = param.span;
     let param_name = param.name.ident().name;
-    let mut err = error_392(tcx, span, param_name);
 
-    let suggested_marker_id = tcx.lang_items().phantom_data();
     // Help is available only in presence of lang items.
-    let msg = if let Some(def_id) = suggested_marker_id {
-        format!(
-            "consider removing `{}`, referring to it in a field, or using a marker such as `{}`",
-            param_name,
-            tcx.def_path_str(def_id),
-        )
-    } else {
-        format!("consider removing `{param_name}` or referring to it in a field")
+    let help = match tcx.lang_items().phantom_data() {
+        Some(def_id) => UnusedParameterHelp::Marker { marker: tcx.def_path_str(def_id) },
+        None => UnusedParameterHelp::NoMarker,
     };
-    err.help(&msg);
-
-    if matches!(param.kind, hir::GenericParamKind::Type { .. }) && !has_explicit_bounds {
-        err.help(&format!(
-            "if you intended `{0}` to be a const parameter, use `const {0}: usize` instead",
-            param_name
-        ));
-    }
-    err.emit()
+    let const_param_help =
+        (matches!(param.kind, hir::GenericParamKind::Type { .. }) && !has_explicit_bounds)
+            .then_some(());
+    tcx.sess.emit_err(UnusedParameter { span: param.span, param_name, help, const_param_help })
 }
 
 /// Feature gates RFC 2056 -- trivial bounds, checking for global bounds that
@@ -1980,13 +1947,3 @@ pub(super) fn impl_implied_bounds(
         }
     }
 }
-
-fn error_392(
-    tcx: TyCtxt<'_>,
-    span: Span,
-    param_name: Symbol,
-) -> DiagnosticBuilder<'_, ErrorGuaranteed> {
-    let mut err = struct_span_err!(tcx.sess, span, E0392, "parameter `{param_name}` is never used");
-    err.span_label(span, "unused parameter");
-    err
-}
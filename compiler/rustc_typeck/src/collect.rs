@@ -45,7 +45,7 @@
 use rustc_session::parse::feature_err;
 use rustc_span::symbol::{kw, sym, Ident, Symbol};
 use rustc_span::{Span, DUMMY_SP};
-use rustc_target::spec::{abi, SanitizerSet};
+use rustc_target::spec::{abi, FramePointer, SanitizerSet};
 use rustc_trait_selection::traits::error_reporting::suggestions::NextTypeParamName;
 use std::iter;
 
@@ -2713,6 +2713,7 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, did: DefId) -> CodegenFnAttrs {
     let mut inline_span = None;
     let mut link_ordinal_span = None;
     let mut no_sanitize_span = None;
+    let mut ctor_priority = None;
     for attr in attrs.iter() {
         if attr.has_name(sym::cold) {
             codegen_fn_attrs.flags |= CodegenFnAttrFlags::COLD;
@@ -2917,9 +2918,14 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, did: DefId) -> CodegenFnAttrs {
                     );
                     tcx.sess.span_err(attr.span, &msg);
                 } else {
+                    check_ctor_section(tcx, attr.span, val);
                     codegen_fn_attrs.link_section = Some(val);
                 }
             }
+        } else if attr.has_name(sym::ctor_priority) {
+            if matches!(tcx.def_kind(did), DefKind::Static(_)) {
+                ctor_priority = Some((attr.span, check_ctor_priority(tcx, attr)));
+            }
         } else if attr.has_name(sym::link_name) {
             codegen_fn_attrs.link_name = attr.value_str();
         } else if attr.has_name(sym::link_ordinal) {
@@ -2935,6 +2941,8 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, did: DefId) -> CodegenFnAttrs {
                         codegen_fn_attrs.no_sanitize |= SanitizerSet::ADDRESS;
                     } else if item.has_name(sym::cfi) {
                         codegen_fn_attrs.no_sanitize |= SanitizerSet::CFI;
+                    } else if item.has_name(sym::kcfi) {
+                        codegen_fn_attrs.no_sanitize |= SanitizerSet::KCFI;
                     } else if item.has_name(sym::memory) {
                         codegen_fn_attrs.no_sanitize |= SanitizerSet::MEMORY;
                     } else if item.has_name(sym::memtag) {
@@ -2946,7 +2954,7 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, did: DefId) -> CodegenFnAttrs {
                     } else {
                         tcx.sess
                             .struct_span_err(item.span(), "invalid argument for `no_sanitize`")
-                            .note("expected one of: `address`, `cfi`, `hwaddress`, `memory`, `memtag`, or `thread`")
+                            .note("expected one of: `address`, `cfi`, `hwaddress`, `kcfi`, `memory`, `memtag`, or `thread`")
                             .emit();
                     }
                 }
@@ -3050,6 +3058,8 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, did: DefId) -> CodegenFnAttrs {
                 },
                 None => None,
             };
+        } else if attr.has_name(sym::frame_pointer) {
+            codegen_fn_attrs.frame_pointer = frame_pointer_attr(tcx, attr);
         }
     }
 
@@ -3176,6 +3186,22 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, did: DefId) -> CodegenFnAttrs {
     }
     check_link_name_xor_ordinal(tcx, &codegen_fn_attrs, link_ordinal_span);
 
+    if let Some((span, priority)) = ctor_priority {
+        if codegen_fn_attrs.link_section.is_some() {
+            tcx.sess.span_err(span, "`#[ctor_priority]` cannot be used with `#[link_section]`");
+        } else if !codegen_fn_attrs
+            .flags
+            .intersects(CodegenFnAttrFlags::USED | CodegenFnAttrFlags::USED_LINKER)
+        {
+            tcx.sess
+                .struct_span_err(span, "`#[ctor_priority]` requires `#[used]`")
+                .note("otherwise the static may be removed, and the constructor never run")
+                .emit();
+        } else if let Some(priority) = priority {
+            codegen_fn_attrs.link_section = ctor_priority_section(tcx, span, priority);
+        }
+    }
+
     // Internal symbols to the standard library all have no_mangle semantics in
     // that they have defined symbol names present in the function name. This
     // also applies to weak symbols where they all have known symbol names.
@@ -3195,6 +3221,59 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, did: DefId) -> CodegenFnAttrs {
     codegen_fn_attrs
 }
 
+/// Parses a `#[frame_pointer(always|non_leaf|none)]` attribute, rejecting requests that would
+/// omit frame pointers the target requires.
+fn frame_pointer_attr(tcx: TyCtxt<'_>, attr: &ast::Attribute) -> Option<FramePointer> {
+    let items = match attr.meta_item_list() {
+        Some(items) => items,
+        None => {
+            tcx.sess.span_err(attr.span, "expected one argument");
+            return None;
+        }
+    };
+    let fp = match items.as_slice() {
+        [item] if item.has_name(sym::always) => FramePointer::Always,
+        [item] if item.has_name(sym::non_leaf) => FramePointer::NonLeaf,
+        [item] if item.has_name(sym::none) => FramePointer::MayOmit,
+        [item] => {
+            tcx.sess
+                .struct_span_err(item.span(), "invalid argument")
+                .help("expected one of `always`, `non_leaf` or `none`")
+                .emit();
+            return None;
+        }
+        _ => {
+            tcx.sess.span_err(attr.span, "expected one argument");
+            return None;
+        }
+    };
+    let required = tcx.sess.target.frame_pointer;
+    let strength = |fp| match fp {
+        FramePointer::Always => 2,
+        FramePointer::NonLeaf => 1,
+        FramePointer::MayOmit => 0,
+    };
+    if strength(fp) < strength(required) {
+        let required = match required {
+            FramePointer::Always => "always",
+            FramePointer::NonLeaf => "non_leaf",
+            FramePointer::MayOmit => unreachable!(),
+        };
+        tcx.sess
+            .struct_span_err(
+                attr.span,
+                &format!(
+                    "target `{}` does not allow omitting frame pointers",
+                    tcx.sess.opts.target_triple
+                ),
+            )
+            .note(&format!("this target requires `#[frame_pointer({})]` or stronger", required))
+            .emit();
+        return None;
+    }
+    Some(fp)
+}
+
 /// Computes the set of target features used in a function for the purposes of
 /// inline assembly.
 fn asm_target_features<'tcx>(tcx: TyCtxt<'tcx>, did: DefId) -> &'tcx FxHashSet<Symbol> {
@@ -3279,6 +3358,95 @@ fn check_link_ordinal(tcx: TyCtxt<'_>, attr: &ast::Attribute) -> Option<u16> {
     }
 }
 
+/// The lowest priority that may be given to a static constructor. Constructors with lower
+/// priorities are reserved for the implementation, e.g. the C runtime.
+const MIN_CTOR_PRIORITY: u128 = 101;
+
+fn check_ctor_priority(tcx: TyCtxt<'_>, attr: &ast::Attribute) -> Option<u16> {
+    use rustc_ast::{Lit, LitIntType, LitKind};
+    let meta_item_list = attr.meta_item_list();
+    let priority = match meta_item_list.as_deref() {
+        Some([item]) => item.literal(),
+        _ => None,
+    };
+    match priority {
+        Some(Lit { kind: LitKind::Int(priority, LitIntType::Unsuffixed), .. })
+            if (MIN_CTOR_PRIORITY..=u16::MAX as u128).contains(priority) =>
+        {
+            Some(*priority as u16)
+        }
+        Some(Lit { kind: LitKind::Int(priority, LitIntType::Unsuffixed), .. }) => {
+            let msg = format!("constructor priority `{}` is out of range", priority);
+            tcx.sess
+                .struct_span_err(attr.span, &msg)
+                .note(&format!(
+                    "priorities must be between {} and {}; lower priorities are reserved for \
+                     the implementation",
+                    MIN_CTOR_PRIORITY,
+                    u16::MAX,
+                ))
+                .emit();
+            None
+        }
+        _ => {
+            tcx.sess
+                .struct_span_err(attr.span, "malformed `ctor_priority` attribute input")
+                .note("an unsuffixed integer value, e.g., `#[ctor_priority(1000)]`, is expected")
+                .emit();
+            None
+        }
+    }
+}
+
+/// Returns the section in which the linker places a static constructor, so that it runs after
+/// the constructors with a lower priority, and before those with a higher one or without one.
+fn ctor_priority_section(tcx: TyCtxt<'_>, span: Span, priority: u16) -> Option<Symbol> {
+    let target = &tcx.sess.target;
+    if target.is_like_windows {
+        // The CRT runs the constructors in `.CRT$XC*` sections ordered by name, and the ones
+        // without a priority are in `.CRT$XCU`.
+        Some(Symbol::intern(&format!(".CRT$XCT{:05}", priority)))
+    } else if target.is_like_osx || target.is_like_wasm {
+        tcx.sess
+            .struct_span_err(span, "`#[ctor_priority]` is not supported on this target")
+            .note(&format!(
+                "the `{}` target does not order static constructors by priority",
+                tcx.sess.opts.target_triple
+            ))
+            .emit();
+        None
+    } else {
+        // ELF linkers sort `.init_array.N` sections by `N`, and place them before `.init_array`.
+        Some(Symbol::intern(&format!(".init_array.{:05}", priority)))
+    }
+}
+
+/// Warns about `#[link_section]`s that look like they give a static constructor a priority, but
+/// that the linker will not order as such.
+fn check_ctor_section(tcx: TyCtxt<'_>, span: Span, section: Symbol) {
+    let section = section.as_str();
+    let Some(priority) =
+        section.strip_prefix(".init_array.").or_else(|| section.strip_prefix(".fini_array."))
+    else {
+        return;
+    };
+    let target = &tcx.sess.target;
+    if target.is_like_windows || target.is_like_osx || target.is_like_wasm {
+        tcx.sess
+            .struct_span_warn(span, &format!("the section `{}` is not run on this target", section))
+            .note(&format!(
+                "the `{}` target does not use `.init_array` and `.fini_array` sections",
+                tcx.sess.opts.target_triple
+            ))
+            .emit();
+    } else if !priority.bytes().all(|b| b.is_ascii_digit()) || priority.parse::<u16>().is_err() {
+        tcx.sess
+            .struct_span_warn(span, &format!("invalid priority in the section `{}`", section))
+            .note("the linker only orders sections whose priority is a number up to 65535")
+            .emit();
+    }
+}
+
 fn check_link_name_xor_ordinal(
     tcx: TyCtxt<'_>,
     codegen_fn_attrs: &CodegenFnAttrs,
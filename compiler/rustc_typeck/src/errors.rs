@@ -2,8 +2,14 @@
 use rustc_errors::{error_code, Applicability, DiagnosticBuilder, ErrorGuaranteed};
 use rustc_macros::{SessionDiagnostic, SessionSubdiagnostic};
 use rustc_middle::ty::Ty;
-use rustc_session::{parse::ParseSess, SessionDiagnostic};
-use rustc_span::{symbol::Ident, Span, Symbol};
+use rustc_session::{
+    parse::{add_feature_diagnostics, ParseSess},
+    SessionDiagnostic,
+};
+use rustc_span::{
+    symbol::{sym, Ident},
+    Span, Symbol,
+};
 
 #[derive(SessionDiagnostic)]
 #[error(code = "E0062", slug = "typeck-field-multiply-specified-in-initializer")]
@@ -327,3 +333,177 @@ pub struct SubstsOnOverriddenImpl {
     #[primary_span]
     pub span: Span,
 }
+
+#[derive(SessionDiagnostic)]
+#[error(slug = "typeck-missing-required-bounds-on-gat")]
+#[note]
+#[note = "feedback-note"]
+pub struct MissingRequiredBoundsOnGat {
+    #[primary_span]
+    pub span: Span,
+    #[suggestion(code = "{suggestion}", applicability = "machine-applicable")]
+    pub suggestion_span: Span,
+    pub suggestion: String,
+    pub ident: Ident,
+    pub count: usize,
+}
+
+#[derive(SessionDiagnostic)]
+#[error(code = "E0741", slug = "typeck-const-param-ty-param-not-structural")]
+#[note]
+pub struct ConstParamTyParamNotStructural<'tcx> {
+    #[primary_span]
+    #[label]
+    pub span: Span,
+    pub ty: Ty<'tcx>,
+}
+
+#[derive(SessionDiagnostic)]
+#[error(code = "E0741", slug = "typeck-const-param-ty-not-structural")]
+pub struct ConstParamTyNotStructural<'tcx> {
+    #[primary_span]
+    pub span: Span,
+    #[label]
+    pub label: Option<Span>,
+    pub ty: Ty<'tcx>,
+}
+
+#[derive(SessionDiagnostic)]
+#[error(code = "E0307", slug = "typeck-invalid-self-parameter-type")]
+#[note]
+#[help]
+pub struct InvalidSelfParameterType<'tcx> {
+    #[primary_span]
+    pub span: Span,
+    pub receiver_ty: Ty<'tcx>,
+}
+
+#[derive(SessionDiagnostic)]
+#[error(code = "E0392", slug = "typeck-unused-parameter")]
+pub struct UnusedParameter {
+    #[primary_span]
+    #[label]
+    pub span: Span,
+    pub param_name: Symbol,
+    #[subdiagnostic]
+    pub help: UnusedParameterHelp,
+    #[help = "const-param-help"]
+    pub const_param_help: Option<()>,
+}
+
+#[derive(SessionSubdiagnostic)]
+pub enum UnusedParameterHelp {
+    #[help(slug = "typeck-unused-parameter-marker-help")]
+    Marker { marker: String },
+    #[help(slug = "typeck-unused-parameter-no-marker-help")]
+    NoMarker,
+}
+
+pub struct SelfTyNeedsArbitrarySelfTypes<'tcx> {
+    pub span: Span,
+    pub receiver_ty: Ty<'tcx>,
+}
+
+// Manual implementation of `SessionDiagnostic` to be able to add the feature gate note and help.
+impl<'a> SessionDiagnostic<'a> for SelfTyNeedsArbitrarySelfTypes<'_> {
+    fn into_diagnostic(self, sess: &'a ParseSess) -> DiagnosticBuilder<'a, ErrorGuaranteed> {
+        let mut err = sess.span_diagnostic.struct_span_err_with_code(
+            self.span,
+            rustc_errors::fluent::typeck::self_ty_needs_arbitrary_self_types,
+            error_code!(E0658),
+        );
+        err.set_arg("receiver_ty", self.receiver_ty);
+        add_feature_diagnostics(&mut err, sess, sym::arbitrary_self_types);
+        err.help(rustc_errors::fluent::typeck::help);
+        err
+    }
+}
+
+#[derive(SessionDiagnostic)]
+#[error(slug = "typeck-auto-trait-impl-default")]
+pub struct AutoTraitImplDefault {
+    #[primary_span]
+    #[label]
+    pub span: Span,
+    #[label = "defaultness-label"]
+    pub defaultness_span: Option<Span>,
+}
+
+#[derive(SessionDiagnostic)]
+#[error(code = "E0750", slug = "typeck-negative-impl-default")]
+pub struct NegativeImplDefault {
+    #[primary_span]
+    pub spans: Vec<Span>,
+}
+
+#[derive(SessionDiagnostic)]
+#[error(slug = "typeck-fn-lang-item-call-self-not-ref")]
+pub struct FnLangItemCallSelfNotRef {
+    #[primary_span]
+    pub span: Span,
+    pub fn_lang_item_name: &'static str,
+}
+
+#[derive(SessionDiagnostic)]
+#[error(slug = "typeck-fn-lang-item-call-arg-count")]
+pub struct FnLangItemCallArgCount {
+    #[primary_span]
+    pub span: Span,
+    pub fn_lang_item_name: &'static str,
+}
+
+#[derive(SessionDiagnostic)]
+#[error(slug = "typeck-fn-lang-item-call-not-fn")]
+pub struct FnLangItemCallNotFn {
+    #[primary_span]
+    pub span: Span,
+    pub fn_lang_item_name: &'static str,
+}
+
+#[derive(SessionDiagnostic)]
+#[error(code = "E0714", slug = "typeck-marker-trait-assoc-item")]
+pub struct MarkerTraitAssocItem {
+    #[primary_span]
+    pub span: Span,
+}
+
+pub struct TraitObjectOfOwnTrait {
+    pub spans: Vec<Span>,
+    pub trait_name_span: Span,
+}
+
+// Manual implementation of `SessionDiagnostic` to be able to make a multipart suggestion.
+impl<'a> SessionDiagnostic<'a> for TraitObjectOfOwnTrait {
+    fn into_diagnostic(self, sess: &'a ParseSess) -> DiagnosticBuilder<'a, ErrorGuaranteed> {
+        let mut err = sess.span_diagnostic.struct_span_err(
+            self.spans.clone(),
+            rustc_errors::fluent::typeck::trait_object_of_own_trait,
+        );
+        err.span_label(self.trait_name_span, rustc_errors::fluent::typeck::label);
+        err.multipart_suggestion(
+            rustc_errors::fluent::typeck::suggestion,
+            self.spans.into_iter().map(|span| (span, "Self".to_string())).collect(),
+            Applicability::MachineApplicable,
+        );
+        err
+    }
+}
+
+#[derive(SessionDiagnostic)]
+#[error(slug = "typeck-const-param-ty-forbidden")]
+pub struct ConstParamTyForbidden {
+    #[primary_span]
+    pub span: Span,
+    pub unsupported_type: &'static str,
+}
+
+#[derive(SessionDiagnostic)]
+#[error(slug = "typeck-const-param-ty-unsupported")]
+#[note]
+pub struct ConstParamTyUnsupported<'tcx> {
+    #[primary_span]
+    pub span: Span,
+    pub ty: Ty<'tcx>,
+    #[help]
+    pub adt_const_params_help: Option<()>,
+}
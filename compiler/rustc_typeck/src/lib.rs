@@ -105,6 +105,7 @@
 use rustc_infer::infer::{InferOk, TyCtxtInferExt};
 use rustc_infer::traits::TraitEngineExt as _;
 use rustc_middle::middle;
+use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::{self, Ty, TyCtxt};
 use rustc_middle::util;
@@ -525,7 +526,16 @@ pub fn check_crate(tcx: TyCtxt<'_>) -> Result<(), ErrorGuaranteed> {
 
     // NOTE: This is copy/pasted in librustdoc/core.rs and should be kept in sync.
     tcx.sess.time("item_types_checking", || {
-        tcx.hir().for_each_module(|module| tcx.ensure().check_mod_item_types(module))
+        tcx.hir().for_each_module(|module| {
+            // Only compute the module path when it's going to be reported.
+            let _timer = tcx.sess.time_passes().then(|| {
+                tcx.sess.prof.extra_verbose_generic_activity(
+                    "check_mod_item_types",
+                    with_no_trimmed_paths!(tcx.def_path_str(module.to_def_id())),
+                )
+            });
+            tcx.ensure().check_mod_item_types(module)
+        })
     });
 
     tcx.sess.time("item_bodies_checking", || tcx.typeck_item_bodies(()));
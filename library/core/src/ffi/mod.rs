@@ -199,6 +199,7 @@ mod c_long_definition {
 #[doc = include_str!("c_void.md")]
 #[repr(u8)]
 #[stable(feature = "core_c_void", since = "1.30.0")]
+#[rustc_diagnostic_item = "c_void"]
 pub enum c_void {
     #[unstable(
         feature = "c_void_variant",
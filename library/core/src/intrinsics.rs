@@ -1931,6 +1931,10 @@ pub unsafe fn drop_in_place<T: ?Sized>(to_drop: *mut T) {
     /// Allocates a block of memory at compile time.
     /// At runtime, just returns a null pointer.
     ///
+    /// Memory that is not deallocated may become part of the final value of a
+    /// constant or static, but only behind shared references, as it cannot be
+    /// deallocated or reallocated at runtime.
+    ///
     /// # Safety
     ///
     /// - The `align` argument must be a power of two.
@@ -165,7 +165,7 @@
 #![feature(auto_traits)]
 #![feature(cfg_target_has_atomic)]
 #![feature(cfg_target_has_atomic_equal_alignment)]
-#![feature(const_fn_floating_point_arithmetic)]
+#![cfg_attr(bootstrap, feature(const_fn_floating_point_arithmetic))]
 #![feature(const_mut_refs)]
 #![feature(const_precise_live_drops)]
 #![feature(const_refs_to_cell)]
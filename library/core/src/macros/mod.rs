@@ -1534,6 +1534,33 @@ macro_rules! trace_macros {
         /* compiler built-in */
     }
 
+    /// Compiles a function once per listed set of target features, plus once for the baseline
+    /// features, and dispatches each call to the best clone the running CPU supports.
+    ///
+    /// Each argument is a comma-separated list of target features. Clones are tried in order,
+    /// so the most capable feature sets should come first:
+    ///
+    /// ```ignore (only-x86_64)
+    /// #![feature(target_clones)]
+    ///
+    /// #[target_clones("avx2,fma", "sse4.1")]
+    /// fn dot(a: &[f32], b: &[f32]) -> f32 {
+    ///     a.iter().zip(b).map(|(a, b)| a * b).sum()
+    /// }
+    /// ```
+    ///
+    /// Detection uses the `std::arch::is_*_feature_detected!` macros, so the annotated crate
+    /// must link `std`.
+    #[unstable(
+        feature = "target_clones",
+        issue = "none",
+        reason = "`target_clones` is a recently implemented feature"
+    )]
+    #[rustc_builtin_macro]
+    pub macro target_clones($item:item) {
+        /* compiler built-in */
+    }
+
     /// Unstable implementation detail of the `rustc` compiler, do not use.
     #[rustc_builtin_macro]
     #[stable(feature = "rust1", since = "1.0.0")]
@@ -91,3 +91,10 @@
     reason = "`cfg_eval` is a recently implemented feature"
 )]
 pub use crate::macros::builtin::cfg_eval;
+
+#[unstable(
+    feature = "target_clones",
+    issue = "none",
+    reason = "`target_clones` is a recently implemented feature"
+)]
+pub use crate::macros::builtin::target_clones;
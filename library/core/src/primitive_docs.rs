@@ -1007,6 +1007,15 @@ mod prim_tuple {}
 ///   `f32::MANTISSA_DIGITS` − 1)</sup>, the result is ∞ or −∞ (preserving the
 ///   true value's sign).
 ///
+/// Floating point operations evaluated at compile time, such as in the initializers of constants
+/// and the `const fn`s they call, follow exactly these semantics independently of the host and
+/// target platform, so their results are bit-for-bit reproducible:
+///
+/// - Every operation is rounded on its own; operations are never fused, so `a * b + c`
+///   rounds twice, like it does without fused multiply-add instructions.
+/// - A NaN result is the first NaN operand with its quiet bit set, or [`f32::NAN`] if no
+///   operand is NaN. This may differ from the NaN the same calculation produces at runtime.
+///
 /// For more information on floating point numbers, see [Wikipedia][wikipedia].
 ///
 /// *[See also the `std::f32::consts` module](crate::f32::consts).*
@@ -1023,6 +1032,15 @@ mod prim_f32 {}
 /// `f32`][`f32`] or [Wikipedia on double precision
 /// values][wikipedia] for more information.
 ///
+/// Floating point operations evaluated at compile time, such as in the initializers of constants
+/// and the `const fn`s they call, follow exactly the semantics described for [`f32`]
+/// independently of the host and target platform, so their results are bit-for-bit reproducible:
+///
+/// - Every operation is rounded on its own; operations are never fused, so `a * b + c`
+///   rounds twice, like it does without fused multiply-add instructions.
+/// - A NaN result is the first NaN operand with its quiet bit set, or [`f64::NAN`] if no
+///   operand is NaN. This may differ from the NaN the same calculation produces at runtime.
+///
 /// *[See also the `std::f64::consts` module](crate::f64::consts).*
 ///
 /// [`f32`]: prim@f32
@@ -78,6 +78,14 @@
 )]
 pub use core::prelude::v1::cfg_eval;
 
+// Do not `doc(no_inline)` either.
+#[unstable(
+    feature = "target_clones",
+    issue = "none",
+    reason = "`target_clones` is a recently implemented feature"
+)]
+pub use core::prelude::v1::target_clones;
+
 // The file so far is equivalent to src/libcore/prelude/v1.rs,
 // and below to src/liballoc/prelude.rs.
 // Those files are duplicated rather than using glob imports
@@ -0,0 +1,49 @@
+// We specify incremental here because we want to test the partitioning for
+// incremental compilation
+// incremental
+// compile-flags:-Zprint-mono-items=lazy
+// compile-flags:-Zinline-in-all-cgus
+// compile-flags:-Ccodegen-units=2
+// compile-flags:-Zcgu-partitioning-strategy=affinity
+
+#![crate_type = "rlib"]
+
+// This test makes sure that the affinity partitioning strategy merges CGUs
+// that would otherwise end up with their own copy of the same inlined
+// function.
+//
+// `aaa` is the smallest CGU. The default strategy would merge it with `ccc`,
+// the second smallest one, but `aaa` and `bbb` both inline `shared::helper`,
+// so they are merged instead and only a single copy of `helper` remains.
+
+mod shared {
+    //~ MONO_ITEM fn shared::helper @@ affinity_merging-aaa--affinity_merging-bbb[Internal]
+    #[inline]
+    pub fn helper(a: u64) -> u64 {
+        a * 3 + 1
+    }
+}
+
+pub mod aaa {
+    //~ MONO_ITEM fn aaa::foo @@ affinity_merging-aaa--affinity_merging-bbb[External]
+    pub fn foo(a: u64) -> u64 {
+        crate::shared::helper(a)
+    }
+}
+
+pub mod bbb {
+    //~ MONO_ITEM fn bbb::foo @@ affinity_merging-aaa--affinity_merging-bbb[External]
+    pub fn foo(a: u64, b: u64, c: u64, d: u64) -> u64 {
+        let x = crate::shared::helper(a + b);
+        let y = crate::shared::helper(c * d);
+        let z = x * y + a * b + c * d;
+        if z > 10 { z - x - y } else { z + x + y }
+    }
+}
+
+pub mod ccc {
+    //~ MONO_ITEM fn ccc::foo @@ affinity_merging-ccc[External]
+    pub fn foo(a: u64, b: u64, c: u64) -> u64 {
+        a * b + c + 1
+    }
+}
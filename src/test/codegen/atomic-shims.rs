@@ -0,0 +1,68 @@
+// Test that `-Zatomic-shims` lowers the atomic operations the target doesn't support natively
+// to the shim selected by the target spec.
+
+// revisions: libcall interrupts
+// compile-flags: -Zatomic-shims -Copt-level=0 --crate-type=rlib
+//[libcall] compile-flags: --target riscv32i-unknown-none-elf
+//[libcall] needs-llvm-components: riscv
+//[interrupts] compile-flags: --target thumbv6m-none-eabi
+//[interrupts] needs-llvm-components: arm
+
+#![feature(no_core, lang_items, intrinsics)]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+
+extern "rust-intrinsic" {
+    fn atomic_load<T>(src: *const T) -> T;
+    fn atomic_xadd<T>(dst: *mut T, src: T) -> T;
+    fn atomic_cxchg<T>(dst: *mut T, old: T, src: T) -> (T, bool);
+    fn atomic_umax<T>(dst: *mut T, src: T) -> T;
+}
+
+// CHECK-LABEL: @load
+#[no_mangle]
+pub unsafe fn load(ptr: *const u32) -> u32 {
+    // libcall: call i32 @__atomic_load_4(i8* {{.*}}, i32 5)
+    // thumbv6m can load and store 32-bit values atomically.
+    // interrupts: load atomic i32, i32* {{.*}} seq_cst
+    atomic_load(ptr)
+}
+
+// CHECK-LABEL: @fetch_add
+#[no_mangle]
+pub unsafe fn fetch_add(ptr: *mut u8, val: u8) -> u8 {
+    // libcall: [[VAL:%.*]] = zext i8 {{.*}} to i32
+    // libcall: call i8 @__atomic_fetch_add_1(i8* {{.*}}, i32 [[VAL]], i32 5)
+    // interrupts: call i32 asm sideeffect "mrs $0, PRIMASK\0Acpsid i", "=r,~{memory}"()
+    // interrupts: load i8, i8*
+    // interrupts: add i8
+    // interrupts: store i8
+    // interrupts: call void asm sideeffect "msr PRIMASK, $0", "r,~{memory}"(i32
+    atomic_xadd(ptr, val)
+}
+
+// CHECK-LABEL: @compare_exchange
+#[no_mangle]
+pub unsafe fn compare_exchange(ptr: *mut u32, old: u32, new: u32) -> (u32, bool) {
+    // libcall: call i8 @__atomic_compare_exchange_4(i8* {{.*}}, i8* {{.*}}, i32 {{.*}}, i32 0, i32 5, i32 5)
+    // interrupts: call i32 asm sideeffect "mrs $0, PRIMASK\0Acpsid i"
+    // interrupts: icmp eq i32
+    // interrupts: select i1
+    // interrupts: call void asm sideeffect "msr PRIMASK, $0"
+    atomic_cxchg(ptr, old, new)
+}
+
+// CHECK-LABEL: @fetch_umax
+#[no_mangle]
+pub unsafe fn fetch_umax(ptr: *mut u32, val: u32) -> u32 {
+    // libcall: atomic_min_max_loop:
+    // libcall: icmp ugt i32
+    // libcall: call i8 @__atomic_compare_exchange_4(i8* {{.*}}, i8* {{.*}}, i32 {{.*}}, i32 1, i32 5, i32 0)
+    // libcall: atomic_min_max_done:
+    // interrupts: call i32 asm sideeffect "mrs $0, PRIMASK\0Acpsid i"
+    // interrupts: icmp ugt i32
+    // interrupts: call void asm sideeffect "msr PRIMASK, $0"
+    atomic_umax(ptr, val)
+}
@@ -0,0 +1,23 @@
+// compile-flags: -C no-prepopulate-passes -C force-frame-pointers=y
+// only-x86_64-unknown-linux-gnu
+
+#![crate_type = "lib"]
+#![feature(frame_pointer_attribute)]
+
+// CHECK: define {{.*}}void @forced() unnamed_addr #[[FORCED:[0-9]+]]
+#[no_mangle]
+pub fn forced() {}
+
+// CHECK: define {{.*}}void @non_leaf() unnamed_addr #[[NON_LEAF:[0-9]+]]
+#[no_mangle]
+#[frame_pointer(non_leaf)]
+pub fn non_leaf() {}
+
+// CHECK: define {{.*}}void @omitted() unnamed_addr #[[OMITTED:[0-9]+]]
+#[no_mangle]
+#[frame_pointer(none)]
+pub fn omitted() {}
+
+// CHECK: attributes #[[FORCED]] = {{.*}}"frame-pointer"="all"
+// CHECK: attributes #[[NON_LEAF]] = {{.*}}"frame-pointer"="non-leaf"
+// CHECK-NOT: attributes #[[OMITTED]] = {{.*}}"frame-pointer"
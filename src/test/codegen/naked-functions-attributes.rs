@@ -0,0 +1,36 @@
+// Checks that naked functions don't get any of the attributes that would make LLVM emit a
+// prologue or an epilogue for them.
+
+// revisions: x86_64 aarch64
+// [x86_64] compile-flags: --target x86_64-unknown-linux-gnu
+// [x86_64] needs-llvm-components: x86
+// [aarch64] compile-flags: --target aarch64-unknown-linux-gnu -Zbranch-protection=bti,pac-ret
+// [aarch64] needs-llvm-components: aarch64
+// compile-flags: -Zinstrument-mcount -Zstack-protector=all -Cforce-frame-pointers=yes
+
+#![crate_type = "lib"]
+#![feature(naked_functions, no_core, lang_items, rustc_attrs)]
+#![no_core]
+
+#[rustc_builtin_macro]
+macro_rules! asm {
+    () => {};
+}
+
+#[lang = "sized"]
+trait Sized {}
+
+// CHECK: define {{.*}}void @naked() unnamed_addr [[NAKED_ATTRS:#[0-9]+]]
+#[no_mangle]
+#[naked]
+pub unsafe extern "C" fn naked() {
+    asm!("ret", options(noreturn));
+}
+
+// CHECK: attributes [[NAKED_ATTRS]] = { naked noinline
+// CHECK-NOT: "frame-pointer"
+// CHECK-NOT: "instrument-function-entry-inlined"
+// CHECK-NOT: sspreq
+// CHECK-NOT: "branch-target-enforcement"
+// CHECK-NOT: "sign-return-address"
+// CHECK-SAME: }
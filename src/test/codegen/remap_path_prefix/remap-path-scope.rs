@@ -0,0 +1,20 @@
+// Checks that `-Zremap-path-scope` limits which paths `--remap-path-prefix` applies to.
+//
+// ignore-windows
+// revisions: DEBUGINFO MACRO
+// compile-flags: -g -C no-prepopulate-passes --remap-path-prefix={{src-base}}=/the/src
+// [DEBUGINFO] compile-flags: -Zremap-path-scope=debuginfo
+// [MACRO] compile-flags: -Zremap-path-scope=macro
+
+#![crate_type = "lib"]
+
+// The expansion of `file!()` is only remapped with the `macro` scope.
+// DEBUGINFO: c"{{.*}}/codegen/remap_path_prefix/remap-path-scope.rs"
+// MACRO: c"/the/src/remap_path_prefix/remap-path-scope.rs"
+pub static FILE_PATH: &'static str = file!();
+
+pub fn foo() {}
+
+// And the paths in debuginfo only with the `debuginfo` scope.
+// DEBUGINFO: !DIFile(filename: "/the/src/remap_path_prefix/remap-path-scope.rs", directory: ""
+// MACRO: !DIFile(filename: "{{.*}}/codegen/remap_path_prefix/remap-path-scope.rs"
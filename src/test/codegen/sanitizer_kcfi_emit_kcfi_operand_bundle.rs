@@ -0,0 +1,34 @@
+// Verifies that KCFI type metadata for functions and operand bundles for indirect calls are
+// emitted.
+//
+// needs-sanitizer-kcfi
+// min-llvm-version: 16.0
+// compile-flags: -Cno-prepopulate-passes -Zsanitizer=kcfi
+
+#![crate_type="lib"]
+#![feature(no_sanitize)]
+
+pub fn foo(f: fn(i32) -> i32, arg: i32) -> i32 {
+    // CHECK-LABEL: define{{.*}}foo{{.*}}!kcfi_type ![[TYPE1:[0-9]+]]
+    // CHECK:       call i32 %f(i32 %arg){{.*}}[ "kcfi"(i32 [[ID1:-?[0-9]+]]) ]
+    f(arg)
+}
+
+pub fn bar(f: fn(i32, i32) -> i32, arg1: i32, arg2: i32) -> i32 {
+    // CHECK-LABEL: define{{.*}}bar{{.*}}!kcfi_type ![[TYPE2:[0-9]+]]
+    // CHECK:       call i32 %f(i32 %arg1, i32 %arg2){{.*}}[ "kcfi"(i32 [[ID2:-?[0-9]+]]) ]
+    f(arg1, arg2)
+}
+
+#[no_sanitize(kcfi)]
+pub fn baz(f: fn(i32) -> i32, arg: i32) -> i32 {
+    // CHECK-LABEL: define{{.*}}baz
+    // CHECK-NOT:   !kcfi_type
+    // CHECK:       call i32 %f(i32 %arg)
+    // CHECK-NOT:   "kcfi"
+    // CHECK:       ret i32
+    f(arg)
+}
+
+// CHECK: ![[TYPE1]] = !{i32 [[ID2]]}
+// CHECK: ![[TYPE2]] = !{i32 {{-?[0-9]+}}}
@@ -0,0 +1,40 @@
+// Verifies that functions with a C calling convention and C-compatible signatures use the same
+// KCFI type identifiers at definitions and indirect call sites, and that those differ from the
+// identifiers of Rust functions of the same types.
+//
+// needs-sanitizer-kcfi
+// min-llvm-version: 16.0
+// compile-flags: -Cno-prepopulate-passes -Zsanitizer=kcfi
+
+#![crate_type="lib"]
+
+use std::ffi::c_void;
+
+#[no_mangle]
+pub extern "C" fn compare(a: *const c_void, b: *const c_void) -> i32 {
+    // CHECK-LABEL: define{{.*}}i32 @compare{{.*}}!kcfi_type ![[COMPARE:[0-9]+]]
+    (a as usize).cmp(&(b as usize)) as i32
+}
+
+pub fn call_compare(
+    f: extern "C" fn(*const c_void, *const c_void) -> i32,
+    a: *const c_void,
+    b: *const c_void,
+) -> i32 {
+    // CHECK-LABEL: define{{.*}}call_compare
+    // CHECK:       call i32 %f({{.*}}){{.*}}[ "kcfi"(i32 [[COMPARE_ID:-?[0-9]+]]) ]
+    f(a, b)
+}
+
+pub fn call_rust(
+    f: fn(*const c_void, *const c_void) -> i32,
+    a: *const c_void,
+    b: *const c_void,
+) -> i32 {
+    // CHECK-LABEL: define{{.*}}call_rust
+    // CHECK-NOT:   [ "kcfi"(i32 [[COMPARE_ID]]) ]
+    // CHECK:       call i32 %f({{.*}}){{.*}}[ "kcfi"(i32 {{-?[0-9]+}}) ]
+    f(a, b)
+}
+
+// CHECK: ![[COMPARE]] = !{i32 [[COMPARE_ID]]}
@@ -0,0 +1,46 @@
+// Test that `-Zwasm-exception-handling` uses the wasm personality function and
+// funclet based cleanup pads instead of `landingpad`.
+
+// compile-flags: --target wasm32-unknown-emscripten -Cpanic=unwind -Zwasm-exception-handling
+// compile-flags: -Copt-level=0
+// needs-llvm-components: webassembly
+
+#![crate_type = "lib"]
+#![feature(no_core, lang_items)]
+#![no_core]
+
+#[lang="sized"]
+trait Sized { }
+
+#[lang="drop"]
+trait Drop {
+    fn drop(&mut self);
+}
+
+#[lang = "drop_in_place"]
+#[allow(unconditional_recursion)]
+unsafe fn drop_in_place<T: ?Sized>(to_drop: *mut T) {
+    drop_in_place(to_drop);
+}
+
+struct Guard;
+
+impl Drop for Guard {
+    fn drop(&mut self) {}
+}
+
+extern "Rust" {
+    fn may_panic();
+}
+
+// CHECK-LABEL: @test
+// CHECK-SAME: personality {{.*}}@__gxx_wasm_personality_v0
+#[no_mangle]
+pub fn test() {
+    let _guard = Guard;
+    // CHECK: invoke void @may_panic()
+    // CHECK-NOT: landingpad
+    // CHECK: cleanuppad within none []
+    // CHECK: cleanupret from
+    unsafe { may_panic() };
+}
@@ -0,0 +1,16 @@
+// compile-flags:-g -Csplit-debuginfo=packed
+
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[inline(never)]
+pub fn sum(p: Point) -> i32 {
+    let total = p.x + p.y;
+    zzz(); // #break
+    total
+}
+
+#[inline(never)]
+fn zzz() {}
@@ -0,0 +1,48 @@
+// Check that debuggers find the debug information of both the current crate and its dependencies
+// in the `*.dwp` package produced by `-Csplit-debuginfo=packed`.
+
+// only-linux
+// aux-build:split_debuginfo_packed.rs
+// compile-flags:-g -Csplit-debuginfo=packed
+
+// === GDB TESTS ===================================================================================
+
+// gdb-command:break split_debuginfo_packed.rs:11
+// gdb-command:break split-debuginfo-packed.rs:44
+// gdb-command:run
+
+// gdb-command:print total
+// gdb-check:$1 = 3
+// gdb-command:continue
+
+// gdb-command:print doubled
+// gdb-check:$2 = 6
+// gdb-command:continue
+
+// === LLDB TESTS ==================================================================================
+
+// lldb-command:b split_debuginfo_packed.rs:11
+// lldb-command:b split-debuginfo-packed.rs:44
+// lldb-command:run
+
+// lldb-command:print total
+// lldbg-check:[...]$0 = 3
+// lldbr-check:(i32) total = 3
+// lldb-command:continue
+
+// lldb-command:print doubled
+// lldbg-check:[...]$1 = 6
+// lldbr-check:(i32) doubled = 6
+// lldb-command:continue
+
+extern crate split_debuginfo_packed;
+
+use split_debuginfo_packed::{sum, Point};
+
+fn main() {
+    let doubled = sum(Point { x: 1, y: 2 }) * 2;
+    zzz(); // #break
+}
+
+#[inline(never)]
+fn zzz() {}
@@ -0,0 +1,107 @@
+// Test that changing only the body of a function does not invalidate the
+// queries of its callers, which should only depend on its signature,
+// predicates and constness.
+//
+// No query needs to be split for this: `fn_sig`, `generics_of`,
+// `predicates_of`, `type_of` and `constness` of the callee only read its
+// `hir_owner`, whose hash leaves out bodies, so they stay green when only the
+// body changed, as checked on the callees below. `rustc_clean` on a function
+// checks `fn_sig`, `generics_of`, `predicates_of`, `type_of`, `typeck`,
+// `optimized_mir` and `promoted_mir`, so the callers are checked for all of
+// them.
+
+// revisions:cfail1 cfail2
+// compile-flags: -Z query-dep-graph
+// build-pass
+
+#![crate_type = "rlib"]
+#![feature(rustc_attrs)]
+#![allow(dead_code)]
+
+pub mod callee {
+    #[cfg(cfail1)]
+    pub fn plain(x: u32) -> u32 {
+        x + 1
+    }
+
+    #[cfg(not(cfail1))]
+    #[rustc_clean(cfg="cfail2", except="hir_owner_nodes,optimized_mir")]
+    pub fn plain(x: u32) -> u32 {
+        x + 2
+    }
+
+    #[cfg(cfail1)]
+    pub fn generic<T: Copy + Into<u64>>(x: T) -> u64 {
+        x.into() + 1
+    }
+
+    #[cfg(not(cfail1))]
+    #[rustc_clean(cfg="cfail2", except="hir_owner_nodes,optimized_mir")]
+    pub fn generic<T: Copy + Into<u64>>(x: T) -> u64 {
+        x.into() + 2
+    }
+
+    #[cfg(cfail1)]
+    pub const fn constant(x: u32) -> u32 {
+        x + 1
+    }
+
+    #[cfg(not(cfail1))]
+    #[rustc_clean(cfg="cfail2", except="hir_owner_nodes,optimized_mir")]
+    pub const fn constant(x: u32) -> u32 {
+        x + 2
+    }
+}
+
+/// A fn item that calls a non-generic function whose body changed
+pub mod fn_calls_plain {
+    #[rustc_clean(cfg="cfail2")]
+    pub fn check() -> u32 {
+        ::callee::plain(1)
+    }
+}
+
+/// A fn item that calls a generic function with predicates whose body changed
+pub mod fn_calls_generic {
+    #[rustc_clean(cfg="cfail2")]
+    pub fn check() -> u64 {
+        ::callee::generic(1u8)
+    }
+}
+
+/// A fn item that calls a const fn whose body changed
+pub mod fn_calls_constant {
+    #[rustc_clean(cfg="cfail2")]
+    pub fn check() -> u32 {
+        ::callee::constant(1)
+    }
+}
+
+/// A generic fn item with predicates that calls a generic function whose body changed
+pub mod generic_fn_calls_generic {
+    #[rustc_clean(cfg="cfail2")]
+    pub fn check<T: Copy + Into<u64>>(x: T) -> u64 {
+        ::callee::generic(x)
+    }
+}
+
+/// A method that calls a function whose body changed
+pub mod method_calls_plain {
+    pub struct S;
+
+    #[rustc_clean(cfg="cfail2")]
+    impl S {
+        #[rustc_clean(cfg="cfail2")]
+        pub fn check(&self) -> u32 {
+            ::callee::plain(1)
+        }
+    }
+}
+
+/// A fn item whose return type is inferred from a closure calling a function whose body changed
+pub mod fn_returns_closure_calling_plain {
+    #[rustc_clean(cfg="cfail2")]
+    pub fn check() -> impl Fn() -> u32 {
+        || ::callee::plain(1)
+    }
+}
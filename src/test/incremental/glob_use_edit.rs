@@ -0,0 +1,52 @@
+// Check that replacing a glob import by explicit imports of the same items, or adding a glob
+// import of names which are not used, does not invalidate the items using the imported names,
+// although the outputs of name resolution are recomputed in each session.
+
+// revisions: rpass1 rpass2 rpass3
+// compile-flags: -Z query-dep-graph
+
+#![feature(rustc_attrs)]
+#![allow(dead_code, unused_imports)]
+
+mod mod1 {
+    pub struct Foo(pub u32);
+
+    pub fn make() -> Foo {
+        Foo(0)
+    }
+}
+
+mod mod2 {
+    pub struct Bar;
+}
+
+mod mod3 {
+    #[cfg(rpass1)]
+    use mod1::*;
+
+    // In rpass2 the glob import is replaced by explicit imports.
+    #[cfg(rpass2)]
+    use mod1::{make, Foo};
+
+    // In rpass3 a glob import of unused names is added.
+    #[cfg(rpass3)]
+    use mod1::{make, Foo};
+    #[cfg(rpass3)]
+    use mod2::*;
+
+    #[rustc_clean(cfg="rpass2")]
+    #[rustc_clean(cfg="rpass3")]
+    pub fn in_expr() -> u32 {
+        make().0
+    }
+
+    #[rustc_clean(cfg="rpass2")]
+    #[rustc_clean(cfg="rpass3")]
+    pub fn in_type(foo: Foo) -> u32 {
+        foo.0
+    }
+}
+
+fn main() {
+    mod3::in_expr();
+}
@@ -0,0 +1,20 @@
+// revisions: cfail1 cfail2 cfail3
+// compile-flags: -Coverflow-checks=on
+// build-pass
+
+#![feature(lint_reasons)]
+#![deny(unfulfilled_lint_expectations)]
+
+// The expected lints are emitted by queries whose results are loaded from the
+// incremental cache in the later revisions. The replayed lints have to fulfill the
+// expectations again.
+
+fn main() {
+    #[expect(arithmetic_overflow)]
+    let _ = 255u8 + 1;
+}
+
+#[expect(dead_code, unused_variables)]
+fn unused() {
+    let x = 0;
+}
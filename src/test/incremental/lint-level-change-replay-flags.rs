@@ -0,0 +1,10 @@
+// Check that warnings replayed from the incremental cache respect lint levels
+// given on the command line and changed since they were cached.
+
+// revisions: cfail1 cfail2 cfail3
+//[cfail2] compile-flags: -A unused-variables
+// build-pass
+
+fn main() {
+    let x = 42; //[cfail1,cfail3]~ WARNING unused variable: `x`
+}
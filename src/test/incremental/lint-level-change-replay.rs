@@ -0,0 +1,21 @@
+// Check that warnings replayed from the incremental cache respect lint level
+// attributes changed since they were cached, on the crate or on an enclosing
+// module, without the items emitting the warnings being changed.
+
+// revisions: cfail1 cfail2 cfail3 cfail4
+// build-pass
+
+#![cfg_attr(cfail2, allow(unused_variables))]
+
+mod m {
+    #![cfg_attr(cfail3, allow(unused_variables))]
+
+    pub fn f() {
+        let y = 42; //[cfail1,cfail4]~ WARNING unused variable: `y`
+    }
+}
+
+fn main() {
+    let x = 42; //[cfail1,cfail3,cfail4]~ WARNING unused variable: `x`
+    m::f();
+}
@@ -0,0 +1,64 @@
+// Test that a node with many dependencies, which makes the dependency graph try to mark them
+// green on several threads before the serial walk, is still marked green when only the body of an
+// unrelated function changed. Each node is visited once per such pass, so this also checks that
+// the nodes shared by several dependencies are decided correctly. The concurrent pass only exists
+// in parallel compiler builds: other builds ignore `-Z threads` and only run the serial walk here.
+
+// revisions:cfail1 cfail2 cfail3
+// compile-flags: -Z query-dep-graph -Z threads=2
+// build-pass
+
+#![crate_type = "rlib"]
+#![feature(rustc_attrs)]
+#![allow(dead_code)]
+
+#![rustc_partition_reused(module = "many_deps_parallel_marking-callers", cfg = "cfail2")]
+#![rustc_partition_reused(module = "many_deps_parallel_marking-callers", cfg = "cfail3")]
+
+pub mod callees {
+    pub fn f0(x: u32) -> u32 { x + 0 }
+    pub fn f1(x: u32) -> u32 { x + 1 }
+    pub fn f2(x: u32) -> u32 { x + 2 }
+    pub fn f3(x: u32) -> u32 { x + 3 }
+    pub fn f4(x: u32) -> u32 { x + 4 }
+    pub fn f5(x: u32) -> u32 { x + 5 }
+    pub fn f6(x: u32) -> u32 { x + 6 }
+    pub fn f7(x: u32) -> u32 { x + 7 }
+    pub fn f8(x: u32) -> u32 { x + 8 }
+    pub fn f9(x: u32) -> u32 { x + 9 }
+    pub fn f10(x: u32) -> u32 { x + 10 }
+    pub fn f11(x: u32) -> u32 { x + 11 }
+    pub fn f12(x: u32) -> u32 { x + 12 }
+    pub fn f13(x: u32) -> u32 { x + 13 }
+    pub fn f14(x: u32) -> u32 { x + 14 }
+    pub fn f15(x: u32) -> u32 { x + 15 }
+    pub fn f16(x: u32) -> u32 { x + 16 }
+    pub fn f17(x: u32) -> u32 { x + 17 }
+    pub fn f18(x: u32) -> u32 { x + 18 }
+    pub fn f19(x: u32) -> u32 { x + 19 }
+
+    pub fn changed(x: u32) -> u32 {
+        #[cfg(cfail1)]
+        return x;
+
+        #[cfg(not(cfail1))]
+        return x + 1;
+    }
+}
+
+pub mod callers {
+    use crate::callees::*;
+
+    #[rustc_clean(cfg = "cfail2")]
+    #[rustc_clean(cfg = "cfail3")]
+    pub fn many_deps(x: u32) -> u32 {
+        f0(x) + f1(x) + f2(x) + f3(x) + f4(x) + f5(x) + f6(x) + f7(x) + f8(x) + f9(x) + f10(x)
+            + f11(x) + f12(x) + f13(x) + f14(x) + f15(x) + f16(x) + f17(x) + f18(x) + f19(x)
+    }
+
+    #[rustc_clean(cfg = "cfail2")]
+    #[rustc_clean(cfg = "cfail3")]
+    pub fn shares_deps(x: u32) -> u32 {
+        many_deps(x) + f0(x) + f19(x)
+    }
+}
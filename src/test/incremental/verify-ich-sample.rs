@@ -0,0 +1,32 @@
+// Check that `-Z incremental-verify-ich=sample:1`, which re-executes every
+// query whose result is loaded from the incremental cache, finds the same
+// results as the ones cached for the queries which stay green.
+
+// revisions: rpass1 rpass2 rpass3
+// compile-flags: -Z query-dep-graph -Z incremental-verify-ich=sample:1
+
+#![feature(rustc_attrs)]
+
+mod unchanged {
+    #[rustc_clean(cfg = "rpass2")]
+    #[rustc_clean(cfg = "rpass3")]
+    pub fn value() -> u32 {
+        let values = [1, 2, 3];
+        values.iter().sum()
+    }
+}
+
+mod changed {
+    pub fn value() -> u32 {
+        #[cfg(rpass1)]
+        return 1;
+
+        #[cfg(not(rpass1))]
+        return 2;
+    }
+}
+
+fn main() {
+    assert_eq!(unchanged::value(), 6);
+    assert_eq!(changed::value(), if cfg!(rpass1) { 1 } else { 2 });
+}
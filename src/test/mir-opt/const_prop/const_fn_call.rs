@@ -0,0 +1,9 @@
+#[inline(never)]
+const fn double(x: u32) -> u32 {
+    x * 2
+}
+
+// EMIT_MIR const_fn_call.main.ConstProp.diff
+fn main() {
+    let x = double(21) + 1;
+}
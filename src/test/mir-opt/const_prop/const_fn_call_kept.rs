@@ -0,0 +1,47 @@
+// Calls to `const fn`s that ConstProp must leave alone.
+
+#[inline(never)]
+const fn panics(x: u32) -> u32 {
+    if x > 10 {
+        panic!("too large");
+    }
+    x
+}
+
+#[inline(never)]
+#[track_caller]
+const fn tracked(x: u32) -> u32 {
+    x * 2
+}
+
+// More than `MAX_CALLEE_BLOCKS` basic blocks.
+#[inline(never)]
+const fn many_blocks(x: u32) -> u32 {
+    match x {
+        0 => 1,
+        1 => 2,
+        2 => 3,
+        3 => 4,
+        4 => 5,
+        5 => 6,
+        6 => 7,
+        7 => 8,
+        8 => 9,
+        9 => 10,
+        10 => 11,
+        11 => 12,
+        12 => 13,
+        13 => 14,
+        14 => 15,
+        15 => 16,
+        16 => 17,
+        _ => 0,
+    }
+}
+
+// EMIT_MIR const_fn_call_kept.main.ConstProp.diff
+fn main() {
+    let a = panics(21) + 1;
+    let b = tracked(21) + 1;
+    let c = many_blocks(3) + 1;
+}
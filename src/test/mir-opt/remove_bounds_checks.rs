@@ -0,0 +1,28 @@
+// unit-test: RemoveBoundsChecks
+// compile-flags: -Zmir-enable-passes=+LowerSliceLenCalls -Coverflow-checks=off
+
+// EMIT_MIR remove_bounds_checks.bound.RemoveBoundsChecks.diff
+pub fn bound(index: usize, slice: &[u8]) -> u8 {
+    if index < slice.len() {
+        slice[index]
+    } else {
+        42
+    }
+}
+
+// EMIT_MIR remove_bounds_checks.while_loop.RemoveBoundsChecks.diff
+pub fn while_loop(slice: &[u8]) -> u8 {
+    let len = slice.len();
+    let mut i = 0;
+    let mut last = 0;
+    while i < len {
+        last = slice[i];
+        i += 1;
+    }
+    last
+}
+
+fn main() {
+    let _ = bound(1, &[1, 2, 3]);
+    let _ = while_loop(&[1, 2, 3]);
+}
@@ -0,0 +1,54 @@
+// unit-test: RemoveBoundsChecks
+// compile-flags: -Zmir-enable-passes=+LowerSliceLenCalls -Coverflow-checks=off
+// Bounds checks dominated by a comparison which must be kept nonetheless.
+
+// EMIT_MIR remove_bounds_checks_kept.write_through_deref.RemoveBoundsChecks.diff
+pub fn write_through_deref(index: usize, slice: &mut &[u8], other: &[u8]) -> u8 {
+    if index < slice.len() {
+        *slice = other;
+        slice[index]
+    } else {
+        42
+    }
+}
+
+// EMIT_MIR remove_bounds_checks_kept.index_reassigned.RemoveBoundsChecks.diff
+pub fn index_reassigned(mut index: usize, slice: &[u8], other: usize) -> u8 {
+    if index < slice.len() {
+        index = other;
+        slice[index]
+    } else {
+        42
+    }
+}
+
+// EMIT_MIR remove_bounds_checks_kept.loop_back_edge.RemoveBoundsChecks.diff
+pub fn loop_back_edge(mut index: usize, slice: &[u8]) -> u8 {
+    if index < slice.len() {
+        loop {
+            index = slice[index] as usize;
+        }
+    } else {
+        42
+    }
+}
+
+// EMIT_MIR remove_bounds_checks_kept.len_changes_in_loop.RemoveBoundsChecks.diff
+pub fn len_changes_in_loop(slice: &[u8], other: &[u8]) -> u8 {
+    let mut len = slice.len();
+    let mut i = 0;
+    let mut last = 0;
+    while i < len {
+        last = slice[i];
+        len = other.len();
+        i += 1;
+    }
+    last
+}
+
+fn main() {
+    let _ = write_through_deref(1, &mut &[1, 2, 3][..], &[]);
+    let _ = index_reassigned(1, &[1, 2, 3], 2);
+    let _ = loop_back_edge(3, &[1, 2, 3]);
+    let _ = len_changes_in_loop(&[1, 2, 3], &[1, 2, 3, 4]);
+}
@@ -0,0 +1,60 @@
+#![feature(rustc_private)]
+
+//! This program implements a rustc driver that inspects the analyzed crate with
+//! `AnalysisContext`: the crate graph, the type checking results and MIR of every
+//! function, and the layout of a struct.
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_middle;
+extern crate rustc_span;
+
+use rustc_driver::analysis::AnalysisContext;
+use rustc_driver::Compilation;
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::ParamEnv;
+use rustc_span::Symbol;
+
+fn main() {
+    let exit_code = rustc_driver::catch_with_exit_code(move || {
+        let rustc_args: Vec<_> = std::env::args().collect();
+        rustc_driver::RunCompiler::new(&rustc_args, &mut CompilerCalls).run()
+    });
+    std::process::exit(exit_code);
+}
+
+pub struct CompilerCalls;
+
+impl rustc_driver::Callbacks for CompilerCalls {
+    fn after_typed_analysis<'tcx>(&mut self, cx: &AnalysisContext<'tcx>) -> Compilation {
+        let tcx = cx.tcx();
+        println!("crate: {}", cx.crate_name());
+        let std = cx.find_crate(Symbol::intern("std")).unwrap();
+        println!("std is local: {}", std.is_local);
+
+        let mut bodies: Vec<_> = cx.body_owners().collect();
+        bodies.sort_by_key(|&def_id| cx.def_path_str(def_id.to_def_id()));
+        for def_id in bodies {
+            let typeck_results = cx.typeck_results(def_id);
+            let hir_id = tcx.hir().local_def_id_to_hir_id(def_id);
+            let body = tcx.hir().body(tcx.hir().body_owned_by(hir_id));
+            let ty = typeck_results.expr_ty(&body.value);
+            let mir = cx.optimized_mir(def_id.to_def_id()).unwrap();
+            println!(
+                "{}: body has type `{}`, {} MIR arguments",
+                cx.def_path_str(def_id.to_def_id()),
+                ty,
+                mir.arg_count
+            );
+        }
+
+        for def_id in tcx.hir().items().map(|item| item.def_id) {
+            if tcx.def_kind(def_id) == DefKind::Struct {
+                let layout = cx.layout_of(ParamEnv::reveal_all(), tcx.type_of(def_id)).unwrap();
+                println!("{}: size {}", cx.def_path_str(def_id.to_def_id()), layout.size.bytes());
+            }
+        }
+
+        Compilation::Stop
+    }
+}
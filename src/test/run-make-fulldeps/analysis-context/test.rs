@@ -0,0 +1,13 @@
+pub struct Pair {
+    pub a: u32,
+    pub b: u8,
+}
+
+pub fn sum(pair: &Pair) -> u32 {
+    pair.a + pair.b as u32
+}
+
+fn main() {
+    let pair = Pair { a: 1, b: 2 };
+    println!("{}", sum(&pair));
+}
@@ -0,0 +1,27 @@
+// compile-flags: -Zcoverage-options=branch
+#![allow(unused_assignments, unused_variables)]
+
+fn main() {
+    // Initialize test constants in a way that cannot be determined at compile time, to ensure
+    // rustc and LLVM cannot optimize out statements (or coverage counters) downstream from
+    // dependent conditions.
+    let is_true = std::env::args().len() == 1;
+    let is_false = !is_true;
+
+    let mut a = 0;
+    if is_true {
+        a = 1;
+    }
+    else
+    {
+        a = 2;
+    }
+
+    if is_true && is_false {
+        a = 3;
+    }
+    else
+    {
+        a = 4;
+    }
+}
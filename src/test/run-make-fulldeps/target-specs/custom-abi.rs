@@ -0,0 +1,15 @@
+#![feature(lang_items, no_core, abi_target_defined)]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+
+#[no_mangle]
+pub extern "my-fast" fn custom_abi_callee(x: u32) -> u32 {
+    x
+}
+
+#[no_mangle]
+pub fn custom_abi_caller(f: extern "my-fast" fn(u32) -> u32) -> u32 {
+    f(42)
+}
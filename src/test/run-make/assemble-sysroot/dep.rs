@@ -0,0 +1,4 @@
+#![feature(no_core)]
+#![no_core]
+
+extern crate core;
@@ -0,0 +1,5 @@
+#![feature(no_core)]
+#![no_core]
+
+extern crate core;
+extern crate dep;
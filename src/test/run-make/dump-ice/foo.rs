@@ -0,0 +1,5 @@
+#![feature(rustc_attrs)]
+
+fn main() {
+    let x: u32 = "not a number";
+}
@@ -0,0 +1,5 @@
+#![warn(unused_variables)]
+
+fn main() {
+    let x = 1;
+}
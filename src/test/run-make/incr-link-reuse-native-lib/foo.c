@@ -0,0 +1,3 @@
+int foo_value(void) {
+    return VALUE;
+}
@@ -0,0 +1,7 @@
+extern "C" {
+    fn foo_value() -> i32;
+}
+
+fn main() {
+    println!("value {}", unsafe { foo_value() });
+}
@@ -0,0 +1,8 @@
+#!/bin/sh
+# A C compiler driver which can't print the commands it would run.
+for arg in "$@"; do
+    if [ "$arg" = "-###" ]; then
+        exit 1
+    fi
+done
+exec cc "$@"
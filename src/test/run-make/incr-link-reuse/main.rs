@@ -0,0 +1,7 @@
+fn main() {
+    if cfg!(goodbye) {
+        println!("goodbye");
+    } else {
+        println!("hello");
+    }
+}
@@ -0,0 +1,7 @@
+pub fn generic<T: std::fmt::Debug>(x: T) -> String {
+    format!("{:?}", x)
+}
+
+pub fn instantiate() -> String {
+    generic(1u8) + &generic("a")
+}
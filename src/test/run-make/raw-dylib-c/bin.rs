@@ -0,0 +1,20 @@
+#![feature(raw_dylib)]
+
+// Uses raw-dylib functions directly from the crate that gets linked into the executable,
+// without an intermediate rlib.
+#[link(name = "extern_1", kind = "raw-dylib")]
+extern {
+    fn extern_fn_1();
+}
+
+#[link(name = "extern_2", kind = "raw-dylib")]
+extern {
+    fn extern_fn_3();
+}
+
+fn main() {
+    unsafe {
+        extern_fn_1();
+        extern_fn_3();
+    }
+}
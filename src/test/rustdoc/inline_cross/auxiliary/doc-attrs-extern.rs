@@ -0,0 +1,16 @@
+pub mod inner {
+    /// Hidden struct.
+    #[doc(hidden)]
+    /// More docs after the hidden attribute.
+    pub struct Hidden;
+
+    /// Visible struct.
+    #[repr(C)]
+    /// More docs after the repr attribute.
+    pub struct Visible;
+}
+
+/// Re-exported struct.
+#[repr(C)]
+/// More docs after the repr attribute.
+pub struct Reexported;
@@ -0,0 +1,25 @@
+// Doc strings of extern items are decoded from their own metadata table. Check that the
+// `#[doc(...)]` attributes of extern items are still found and that no doc string is lost.
+
+// aux-build:doc-attrs-extern.rs
+// build-aux-docs
+// ignore-cross-compile
+
+extern crate doc_attrs_extern;
+
+// @has doc_attrs_side_table/index.html
+// @!has - 'Hidden'
+// @!has doc_attrs_side_table/struct.Hidden.html
+// @has doc_attrs_side_table/struct.Visible.html
+// @has - '//div[@class="docblock"]' 'Visible struct.'
+// @has - '//div[@class="docblock"]' 'More docs after the repr attribute.'
+// @has - '//*[@class="docblock item-decl"]' '#[repr(C)]'
+#[doc(inline)]
+pub use doc_attrs_extern::inner::*;
+
+// @has doc_attrs_side_table/struct.Inlined.html
+// @has - '//div[@class="docblock"]' 'Re-exported struct.'
+// @has - '//div[@class="docblock"]' 'More docs after the repr attribute.'
+// @has - '//*[@class="docblock item-decl"]' '#[repr(C)]'
+#[doc(inline)]
+pub use doc_attrs_extern::Reexported as Inlined;
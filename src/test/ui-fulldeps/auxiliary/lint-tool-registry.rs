@@ -0,0 +1,49 @@
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_lint;
+#[macro_use]
+extern crate rustc_session;
+
+use rustc_driver::plugin::Registry;
+use rustc_hir as hir;
+use rustc_lint::tool::{ToolLateContext, ToolLintRegistry, TOOL_API_VERSION};
+use rustc_lint::{LateContext, LateLintPass};
+
+declare_tool_lint!(pub clippy::TEST_LINT, Warn, "Warn about stuff");
+declare_tool_lint!(
+    pub clippy::TEST_FUTURE_LINT,
+    Warn,
+    "Warn about things that will break",
+    @future_incompatible = FutureIncompatibleInfo {
+        reference: "issue #00000 <https://github.com/rust-lang/rust/issues/00000>",
+    };
+);
+
+declare_lint_pass!(Pass => [TEST_LINT, TEST_FUTURE_LINT]);
+
+impl<'tcx> LateLintPass<'tcx> for Pass {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, it: &'tcx hir::Item<'tcx>) {
+        let cx = ToolLateContext::new(cx);
+        if it.ident.name.as_str() == "lintme" {
+            cx.emit_lint(TEST_LINT, it.span, |lint| {
+                lint.build("item is named 'lintme'").emit();
+            });
+        }
+        if it.ident.name.as_str() == "breakme" {
+            cx.emit_lint(TEST_FUTURE_LINT, it.span, |lint| {
+                lint.build("item is named 'breakme'").emit();
+            });
+        }
+    }
+}
+
+#[no_mangle]
+fn __rustc_plugin_registrar(reg: &mut Registry) {
+    assert_eq!(TOOL_API_VERSION, 1);
+    let mut tool = ToolLintRegistry::new(reg.lint_store, "clippy");
+    tool.register_lints(&[&TEST_LINT, &TEST_FUTURE_LINT]);
+    tool.register_group("clippy::test_group", &[&TEST_LINT]);
+    tool.register_late_pass(|| Box::new(Pass));
+}
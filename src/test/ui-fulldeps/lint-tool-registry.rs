@@ -0,0 +1,21 @@
+// aux-build:lint-tool-registry.rs
+// ignore-stage1
+
+#![feature(plugin)]
+#![plugin(lint_tool_registry)]
+//~^ WARN use of deprecated attribute `plugin`
+#![allow(dead_code)]
+#![deny(clippy::test_group)]
+
+fn lintme() {} //~ ERROR item is named 'lintme'
+
+fn breakme() {}
+//~^ WARN item is named 'breakme'
+//~| WARN this was previously accepted
+
+#[allow(future_incompatible)]
+mod allowed {
+    fn breakme() {}
+}
+
+fn main() {}
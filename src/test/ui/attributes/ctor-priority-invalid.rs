@@ -0,0 +1,26 @@
+#![feature(ctor_priority)]
+
+#[used]
+#[ctor_priority(50)] //~ ERROR constructor priority `50` is out of range
+static RESERVED: extern "C" fn() = ctor;
+
+#[used]
+#[ctor_priority(70000)] //~ ERROR constructor priority `70000` is out of range
+static TOO_LARGE: extern "C" fn() = ctor;
+
+#[used]
+#[ctor_priority("high")] //~ ERROR malformed `ctor_priority` attribute input
+static NOT_AN_INTEGER: extern "C" fn() = ctor;
+
+#[ctor_priority(1000)] //~ ERROR `#[ctor_priority]` requires `#[used]`
+static NOT_USED: extern "C" fn() = ctor;
+
+#[used]
+#[link_section = ".init_array"]
+#[ctor_priority(1000)] //~ ERROR `#[ctor_priority]` cannot be used with `#[link_section]`
+static WITH_SECTION: extern "C" fn() = ctor;
+
+#[ctor_priority(1000)] //~ ERROR attribute should be applied to a static
+extern "C" fn ctor() {}
+
+fn main() {}
@@ -0,0 +1,18 @@
+// check-pass
+// only-linux
+
+#[used]
+#[link_section = ".init_array.00500"]
+static VALID: extern "C" fn() = ctor;
+
+#[used]
+#[link_section = ".init_array.high"] //~ WARN invalid priority in the section `.init_array.high`
+static NOT_A_NUMBER: extern "C" fn() = ctor;
+
+#[used]
+#[link_section = ".fini_array.70000"] //~ WARN invalid priority in the section `.fini_array.70000`
+static TOO_LARGE: extern "C" fn() = ctor;
+
+extern "C" fn ctor() {}
+
+fn main() {}
@@ -0,0 +1,44 @@
+// run-pass
+// only-linux
+
+#![feature(ctor_priority)]
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static STEPS: AtomicU32 = AtomicU32::new(0);
+
+fn record(step: u32) {
+    STEPS.store(STEPS.load(Ordering::SeqCst) * 10 + step, Ordering::SeqCst);
+}
+
+// Constructors with a priority run in increasing order of priority, before the ones without.
+#[used]
+#[link_section = ".init_array"]
+static DEFAULT: extern "C" fn() = {
+    extern "C" fn default() {
+        record(3);
+    }
+    default
+};
+
+#[used]
+#[ctor_priority(300)]
+static LATE: extern "C" fn() = {
+    extern "C" fn late() {
+        record(2);
+    }
+    late
+};
+
+#[used]
+#[ctor_priority(200)]
+static EARLY: extern "C" fn() = {
+    extern "C" fn early() {
+        record(1);
+    }
+    early
+};
+
+fn main() {
+    assert_eq!(STEPS.load(Ordering::SeqCst), 123);
+}
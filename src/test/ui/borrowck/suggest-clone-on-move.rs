@@ -0,0 +1,38 @@
+// Check that moves of `Clone` values suggest cloning them, and that `&mut` references and
+// non-`Clone` values do not.
+
+#[derive(Clone)]
+struct Config {
+    name: String,
+}
+
+struct Handle;
+
+fn consume<T>(_: T) {}
+
+fn use_after_move(config: Config) {
+    consume(config);
+    consume(config); //~ ERROR use of moved value: `config`
+}
+
+fn shorthand_field(name: String) {
+    let _a = Config { name };
+    let _b = Config { name }; //~ ERROR use of moved value: `name`
+}
+
+fn move_out_of_borrow(config: &Config) {
+    consume(config.name); //~ ERROR cannot move out of `config.name`
+}
+
+fn not_clone(handle: Handle) {
+    consume(handle);
+    consume(handle); //~ ERROR use of moved value: `handle`
+}
+
+fn mutable_reference(config: &mut Config) {
+    let c = config;
+    consume(c);
+    consume(c); //~ ERROR use of moved value: `c`
+}
+
+fn main() {}
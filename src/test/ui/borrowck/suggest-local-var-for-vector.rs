@@ -1,4 +1,7 @@
+// check-pass
+// Overloaded indexing uses a two-phase borrow, so the index may read the indexed value.
+
 fn main() {
     let mut vec = vec![0u32; 420];
-    vec[vec.len() - 1] = 123; //~ ERROR cannot borrow `vec` as immutable because it is also borrowed as mutable
+    vec[vec.len() - 1] = 123;
 }
@@ -0,0 +1,33 @@
+// check-pass
+// Test that overloaded mutable indexing and overloaded compound assignment operators use
+// two-phase borrows, allowing the index or right-hand side to read the borrowed value.
+
+use std::ops::AddAssign;
+
+#[derive(Clone, Copy)]
+struct Counter(u32);
+
+impl Counter {
+    fn compute(&self) -> u32 {
+        self.0 * 2
+    }
+}
+
+impl AddAssign<u32> for Counter {
+    fn add_assign(&mut self, rhs: u32) {
+        self.0 += rhs;
+    }
+}
+
+fn main() {
+    let mut v = vec![1, 2, 3];
+    v[v.len() - 1] = v.len();
+    v[v[0]] += 1;
+
+    let mut nested = vec![vec![1, 2], vec![3]];
+    nested[nested.len() - 1][0] = 4;
+
+    let mut x = Counter(1);
+    x += x.compute();
+    assert_eq!(x.0, 3);
+}
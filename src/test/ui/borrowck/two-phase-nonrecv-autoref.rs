@@ -130,14 +130,12 @@ fn index_mut(&mut self, _: i32) -> &mut i32 {
 fn coerce_index_op() {
     let mut i = I(10);
     i[i[3]] = 4;
-    //~^ ERROR cannot borrow `i` as immutable because it is also borrowed as mutable [E0502]
-    // Shoud be accepted with g2p
+    // Accepted, as overloaded indexing uses a two-phase borrow
 
     i[3] = i[4];
 
     i[i[3]] = i[4];
-    //~^ ERROR cannot borrow `i` as immutable because it is also borrowed as mutable [E0502]
-    // Shoud be accepted with g2p
+    // Accepted, as overloaded indexing uses a two-phase borrow
 }
 
 fn main() {
@@ -0,0 +1,30 @@
+// run-pass
+
+#![feature(adt_const_params)]
+#![allow(incomplete_features)]
+
+// Check that references nested in the types of const parameters are supported.
+
+fn nested_str<const S: &'static &'static str>() -> &'static str {
+    *S
+}
+
+fn str_slice<const S: &'static [&'static str]>() -> usize {
+    S.iter().map(|s| s.len()).sum()
+}
+
+fn wide_slice<const S: &'static [u16]>() -> u16 {
+    S.iter().sum()
+}
+
+fn tuple<const T: (&'static str, &'static [u8])>() -> usize {
+    T.0.len() + T.1.len()
+}
+
+fn main() {
+    assert_eq!(nested_str::<{ &"Rust" }>(), "Rust");
+    assert_eq!(str_slice::<{ &["a", "bc", ""] }>(), 3);
+    assert_eq!(str_slice::<{ &[] }>(), 0);
+    assert_eq!(wide_slice::<{ &[1, 2, 300] }>(), 303);
+    assert_eq!(tuple::<{ ("abc", b"de") }>(), 5);
+}
@@ -0,0 +1,13 @@
+#![feature(adt_const_params)]
+#![allow(incomplete_features)]
+
+struct MutRef<const R: &'static mut u8>;
+//~^ ERROR using mutable references as const generic parameters is forbidden
+
+struct FnPtrSlice<const S: &'static [fn()]>;
+//~^ ERROR using function pointers as const generic parameters is forbidden
+
+struct RawPtrTuple<const T: (u8, *const u8)>;
+//~^ ERROR using raw pointers as const generic parameters is forbidden
+
+fn main() {}
@@ -0,0 +1,15 @@
+pub static ZEROES: [u8; 1 << 20] = [0; 1 << 20];
+
+pub const MIXED: [u8; 4096] = {
+    let mut table = [7; 4096];
+    table[100] = 1;
+    table[4000] = 2;
+    let mut i = 2000;
+    while i < 2010 {
+        table[i] = i as u8;
+        i += 1;
+    }
+    table
+};
+
+pub const UNIFORM: [u32; 1024] = [0x5a5a5a5a; 1024];
@@ -0,0 +1,36 @@
+#[derive(Debug, PartialEq, Eq)]
+pub enum Shape {
+    Point,
+    Rect { w: u32, h: u32 },
+}
+
+pub trait Named {
+    const NAME: &'static str;
+}
+
+pub struct Unit;
+
+impl Named for Unit {
+    const NAME: &'static str = "unit";
+}
+
+pub const LEN: usize = 2 + 2;
+pub const PAIR: (u8, i64) = (7, -7);
+pub const SHAPE: Shape = Shape::Rect { w: 3, h: 4 };
+pub const TABLE: [u16; 300] = {
+    let mut table = [0; 300];
+    let mut i = 0;
+    while i < table.len() {
+        table[i] = (i * i % 1000) as u16;
+        i += 1;
+    }
+    table
+};
+pub const GREETING: &str = "hello";
+pub const PRIMES: &[u32] = &[2, 3, 5, 7];
+pub const FLOAT: f64 = 1.5;
+pub const NAMED: &str = <Unit as Named>::NAME;
+
+pub const fn generic_len<T>() -> usize {
+    std::mem::size_of::<T>()
+}
@@ -0,0 +1,18 @@
+// Check that calling function pointers during const evaluation fails if the pointee is not a
+// const fn or the pointer was not created from a function at all.
+
+#![feature(const_fn_ptr_calls)]
+
+fn not_const() {}
+
+const fn call(f: fn()) {
+    f()
+    //~^ ERROR evaluation of constant value failed
+    //~| ERROR evaluation of constant value failed
+}
+
+const NON_CONST: () = call(not_const);
+
+const NO_PROVENANCE: () = call(unsafe { std::mem::transmute::<usize, fn()>(42) });
+
+fn main() {}
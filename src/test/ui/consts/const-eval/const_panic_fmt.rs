@@ -0,0 +1,33 @@
+// Test that the arguments of formatted `panic!()`s show up in const-eval errors.
+// edition:2021
+#![crate_type = "lib"]
+#![feature(const_panic_fmt_args)]
+
+const X: u32 = 42;
+
+const A: () = panic!("bad value: {}", X);
+//~^ ERROR evaluation of constant value failed
+
+const B: () = panic!("{X} is not less than {}", X / 2);
+//~^ ERROR evaluation of constant value failed
+
+const C: () = panic!("{:?} {:?} {}", "quote\"d", 'c', true);
+//~^ ERROR evaluation of constant value failed
+
+const D: () = panic!("{:#x} {:08b} {:+} {:o}", 255u8, 5, 3i8, -1i8);
+//~^ ERROR evaluation of constant value failed
+
+const E: () = panic!("[{:>5}] [{:<5}] [{:^5}] [{:*^7.2}]", 1, -1, "ab", "abcd");
+//~^ ERROR evaluation of constant value failed
+
+const F: () = panic!("{}", -128i8);
+//~^ ERROR evaluation of constant value failed
+
+const fn check(len: usize) {
+    if len > 4 {
+        panic!("length {} exceeds the maximum of {}", len, 4);
+        //~^ ERROR evaluation of constant value failed
+    }
+}
+
+const G: () = check(5);
@@ -0,0 +1,32 @@
+// Test that `panic!()` arguments of types that const-eval cannot format are rejected.
+// edition:2021
+#![crate_type = "lib"]
+#![feature(const_panic_fmt_args)]
+
+use std::fmt;
+
+struct Foo;
+
+impl fmt::Display for Foo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Foo")
+    }
+}
+
+const A: () = panic!("{}", Foo);
+//~^ ERROR cannot format a value of type `Foo` in a const context
+
+const B: () = panic!("value: {}", 1.5);
+//~^ ERROR cannot format a value of type `f64` in a const context
+
+const C: () = panic!("{} {}", 1, &&Foo);
+//~^ ERROR cannot format a value of type `Foo` in a const context
+
+const D: () = panic!("{:p}", &1);
+//~^ ERROR cannot format a value with `Pointer` in a const context
+
+const E: () = panic!("{:e}", 1.0);
+//~^ ERROR cannot format a value with `LowerExp` in a const context
+
+const F: () = panic!("{:1$}", 5, 3);
+//~^ ERROR widths and precisions taken from an argument cannot be used in a const context
@@ -0,0 +1,35 @@
+// run-pass
+// aux-build:upstream_const_values.rs
+// Check that the values of upstream constants, which are recorded in the metadata of the crate
+// that defines them, are the same as when evaluating the constants again.
+
+extern crate upstream_const_values as up;
+
+use up::Shape;
+
+const ARRAY: [u8; up::LEN] = [0; up::LEN];
+const SUM: u32 = match up::SHAPE {
+    Shape::Point => 0,
+    Shape::Rect { w, h } => w * h,
+};
+const LAST: u16 = up::TABLE[up::TABLE.len() - 1];
+const SIZE: usize = up::generic_len::<[u32; up::LEN]>();
+
+fn main() {
+    assert_eq!(ARRAY.len(), 4);
+    assert_eq!(up::PAIR, (7, -7));
+    assert_eq!(SUM, 12);
+    assert_eq!(up::SHAPE, Shape::Rect { w: 3, h: 4 });
+    assert_eq!(LAST, (299 * 299 % 1000) as u16);
+    assert_eq!(up::TABLE[17], 289);
+    assert_eq!(up::GREETING, "hello");
+    assert_eq!(up::PRIMES.iter().sum::<u32>(), 17);
+    assert_eq!(up::FLOAT, 1.5);
+    assert_eq!(up::NAMED, "unit");
+    assert_eq!(SIZE, 16);
+
+    match 4 {
+        up::LEN => {}
+        _ => unreachable!(),
+    }
+}
@@ -0,0 +1,35 @@
+// build-fail
+// Check that an error triggered by several instantiations is only reported once.
+
+trait Unsigned {
+    const MAX: u8;
+}
+
+struct U8(u8);
+impl Unsigned for U8 {
+    const MAX: u8 = 0xff;
+}
+
+struct U1(u8);
+impl Unsigned for U1 {
+    const MAX: u8 = 1;
+}
+
+struct Sum<A, B>(A, B);
+
+impl<A: Unsigned, B: Unsigned> Unsigned for Sum<A, B> {
+    const MAX: u8 = A::MAX + B::MAX;
+    //~^ ERROR any use of this value will cause an error [const_err]
+    //~| WARN this was previously accepted by the compiler but is being phased out
+}
+
+fn foo<A: Unsigned, B: Unsigned>() -> &'static u8 {
+    &Sum::<A, B>::MAX
+    //~^ ERROR evaluation of `foo::<U8, U8>` failed [E0080]
+    //~| ERROR evaluation of `foo::<U8, U1>` failed [E0080]
+}
+
+fn main() {
+    foo::<U8, U8>();
+    foo::<U8, U1>();
+}
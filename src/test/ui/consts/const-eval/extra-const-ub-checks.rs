@@ -0,0 +1,45 @@
+// compile-flags: -Zextra-const-ub-checks
+// normalize-stderr-test "alloc\d+" -> "allocN"
+
+use std::num::NonZeroU32;
+
+const fn make_bool(n: u8) -> bool {
+    let b: bool = unsafe { std::mem::transmute(n) }; //~ ERROR evaluation of constant value failed
+    b
+}
+
+const BAD_BOOL: bool = make_bool(3);
+
+union CharOrInt {
+    int: u32,
+    chr: char,
+}
+
+const BAD_CHAR: char = unsafe { CharOrInt { int: 0xD800 }.chr };
+//~^ ERROR evaluation of constant value failed
+
+const BAD_NONZERO: u32 = {
+    let zero = 0u32;
+    let ptr = &zero as *const u32 as *const NonZeroU32;
+    let n = unsafe { *ptr }; //~ ERROR evaluation of constant value failed
+    n.get()
+};
+
+#[derive(Copy, Clone)]
+enum Gap {
+    A = 0,
+    B = 2,
+}
+
+union GapOrInt {
+    int: u8,
+    gap: Gap,
+}
+
+const fn make_gap(int: u8) -> Gap {
+    unsafe { GapOrInt { int }.gap } //~ ERROR evaluation of constant value failed
+}
+
+const BAD_GAP: Gap = make_gap(1);
+
+fn main() {}
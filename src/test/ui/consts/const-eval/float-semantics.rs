@@ -0,0 +1,42 @@
+// run-pass
+// Check that floating point operations during const evaluation are never fused and produce NaNs
+// deterministically: the first NaN operand, quieted, or the default NaN if no operand is NaN.
+
+const fn bits(f: f32) -> u32 {
+    unsafe { std::mem::transmute(f) }
+}
+
+const fn float(bits: u32) -> f32 {
+    unsafe { std::mem::transmute(bits) }
+}
+
+const fn mul_add(a: f64, b: f64, c: f64) -> f64 {
+    a * b + c
+}
+
+// With a fused multiply-add, this would be the rounding error of `0.1 * 10.0` instead.
+const UNFUSED: f64 = mul_add(0.1, 10.0, -1.0);
+
+const SIGNALING: f32 = float(0x7f80_0042);
+const QUIET: f32 = float(0x7fc0_0013);
+const NEGATIVE: f32 = float(0xffc0_0001);
+
+const SIGNALING_PLUS_ONE: u32 = bits(SIGNALING + 1.0);
+const ONE_MINUS_QUIET: u32 = bits(1.0 - QUIET);
+const QUIET_TIMES_NEGATIVE: u32 = bits(QUIET * NEGATIVE);
+const NEGATIVE_DIV_QUIET: u32 = bits(NEGATIVE / QUIET);
+const QUIET_REM_SIGNALING: u32 = bits(QUIET % SIGNALING);
+const ZERO_DIV_ZERO: u32 = bits(0.0 / 0.0);
+const INF_MINUS_INF: u32 = bits(f32::NEG_INFINITY + f32::INFINITY);
+
+fn main() {
+    assert_eq!(UNFUSED, 0.0);
+
+    assert_eq!(SIGNALING_PLUS_ONE, 0x7fc0_0042);
+    assert_eq!(ONE_MINUS_QUIET, 0x7fc0_0013);
+    assert_eq!(QUIET_TIMES_NEGATIVE, 0x7fc0_0013);
+    assert_eq!(NEGATIVE_DIV_QUIET, 0xffc0_0001);
+    assert_eq!(QUIET_REM_SIGNALING, 0x7fc0_0013);
+    assert_eq!(ZERO_DIV_ZERO, 0x7fc0_0000);
+    assert_eq!(INF_MINUS_INF, 0x7fc0_0000);
+}
@@ -0,0 +1,21 @@
+// compile-flags: -Zconst-eval-frame-args
+// Check that the values of scalar arguments are shown for each frame of a const eval backtrace.
+
+#![crate_type = "lib"]
+
+const fn check(x: u32, enabled: bool) -> u32 {
+    if enabled { 100 / (x % 7) } else { 0 }
+    //~^ ERROR evaluation of constant value failed
+}
+
+const fn sum(from: u32, to: u32) -> u32 {
+    let mut total = 0;
+    let mut i = from;
+    while i < to {
+        total += check(i, true);
+        i += 1;
+    }
+    total
+}
+
+const SUM: u32 = sum(2, 10);
@@ -0,0 +1,25 @@
+#![feature(core_intrinsics)]
+#![feature(const_heap)]
+#![feature(const_mut_refs)]
+use std::intrinsics;
+
+struct SyncPtr(*const i32);
+unsafe impl Sync for SyncPtr {}
+
+// A heap allocation that is not behind a shared reference could be freed at runtime.
+static BAR: SyncPtr = SyncPtr(unsafe { intrinsics::const_allocate(4, 4) as *const i32 });
+//~^ ERROR heap allocation in the final value of a static
+
+const fn leaked() -> *const i32 {
+    unsafe {
+        let ptr = intrinsics::const_allocate(4, 4) as *mut i32;
+        *ptr = 42;
+        ptr
+    }
+}
+
+// Being behind a shared reference does not help if the allocation is reached by a raw pointer.
+const PTR: &SyncPtr = &SyncPtr(leaked());
+//~^ ERROR heap allocation in the final value of a constant
+
+fn main() {}
@@ -4,7 +4,7 @@
 use std::intrinsics;
 
 const FOO: *const i32 = foo();
-//~^ ERROR untyped pointers are not allowed in constant
+//~^ ERROR heap allocation in the final value of a constant
 
 const fn foo() -> &'static i32 {
     let t = unsafe {
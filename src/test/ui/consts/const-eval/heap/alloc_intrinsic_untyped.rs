@@ -4,6 +4,6 @@
 use std::intrinsics;
 
 const BAR: *mut i32 = unsafe { intrinsics::const_allocate(4, 4) as *mut i32};
-//~^ error: untyped pointers are not allowed in constant
+//~^ ERROR heap allocation in the final value of a constant
 
 fn main() {}
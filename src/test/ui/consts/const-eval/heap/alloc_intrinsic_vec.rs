@@ -0,0 +1,38 @@
+// run-pass
+// Check that a buffer that grows like a `Vec` can be built on the heap during const eval, and be
+// used at runtime through a shared reference.
+#![feature(core_intrinsics)]
+#![feature(const_heap)]
+#![feature(const_mut_refs)]
+#![feature(const_slice_from_raw_parts)]
+use std::intrinsics;
+use std::mem::{align_of, size_of};
+
+const fn squares(n: usize) -> &'static [u32] {
+    let (size, align) = (size_of::<u32>(), align_of::<u32>());
+    let mut cap = 1;
+    let mut len = 0;
+    unsafe {
+        let mut ptr = intrinsics::const_allocate(size * cap, align) as *mut u32;
+        while len < n {
+            if len == cap {
+                let new_ptr = intrinsics::const_allocate(size * cap * 2, align) as *mut u32;
+                std::ptr::copy_nonoverlapping(ptr, new_ptr, len);
+                intrinsics::const_deallocate(ptr as *mut u8, size * cap, align);
+                ptr = new_ptr;
+                cap *= 2;
+            }
+            *ptr.add(len) = (len * len) as u32;
+            len += 1;
+        }
+        std::slice::from_raw_parts(ptr, len)
+    }
+}
+
+const SQUARES: &[u32] = squares(5);
+static STATIC_SQUARES: &[u32] = squares(3);
+
+fn main() {
+    assert_eq!(SQUARES, [0, 1, 4, 9, 16]);
+    assert_eq!(STATIC_SQUARES, [0, 1, 4]);
+}
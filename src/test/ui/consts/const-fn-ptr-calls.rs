@@ -0,0 +1,40 @@
+// run-pass
+// Check that function pointers to const fns can be called during const evaluation.
+
+#![feature(const_fn_ptr_calls)]
+
+const fn double(x: u32) -> u32 {
+    x * 2
+}
+
+const fn add_one(x: u32) -> u32 {
+    x + 1
+}
+
+const fn apply(f: fn(u32) -> u32, x: u32) -> u32 {
+    f(x)
+}
+
+const fn apply_all(fs: &[fn(u32) -> u32], mut x: u32) -> u32 {
+    let mut i = 0;
+    while i < fs.len() {
+        x = fs[i](x);
+        i += 1;
+    }
+    x
+}
+
+const PIPELINE: [fn(u32) -> u32; 3] = [double, add_one, double];
+
+const DOUBLED: u32 = apply(double, 21);
+const PIPELINED: u32 = apply_all(&PIPELINE, 5);
+static STATIC_PIPELINED: u32 = apply_all(&PIPELINE, 1);
+
+fn main() {
+    assert_eq!(DOUBLED, 42);
+    assert_eq!(PIPELINED, 22);
+    assert_eq!(STATIC_PIPELINED, 6);
+    // The same functions can still be called at runtime.
+    assert_eq!(apply(add_one, 1), 2);
+    assert_eq!(apply_all(&PIPELINE, 0), 2);
+}
@@ -0,0 +1,14 @@
+// compile-flags: -Z const-eval-step-budget=100
+
+#![feature(const_eval_limit)]
+#![const_eval_limit = "0"]
+#![deny(long_running_const_eval)]
+
+const fn spin() {
+    loop {}
+}
+
+const SPIN: () = spin();
+//~^ ERROR constant evaluation is taking a long time
+
+fn main() {}
@@ -0,0 +1,39 @@
+// run-pass
+// aux-build:large_const_tables.rs
+
+// Large allocations with long runs of the same byte are stored compressed in memory and in
+// crate metadata; check that their contents survive the round-trip.
+
+extern crate large_const_tables;
+
+use large_const_tables::{MIXED, UNIFORM, ZEROES};
+
+const MIXED_COPY: [u8; 4096] = MIXED;
+const UNIFORM_SUM: u64 = {
+    let mut sum = 0;
+    let mut i = 0;
+    while i < UNIFORM.len() {
+        sum += UNIFORM[i] as u64;
+        i += 1;
+    }
+    sum
+};
+
+fn main() {
+    assert_eq!(ZEROES.len(), 1 << 20);
+    assert!(ZEROES.iter().all(|&b| b == 0));
+
+    for table in [MIXED, MIXED_COPY] {
+        assert_eq!(table[0], 7);
+        assert_eq!(table[100], 1);
+        assert_eq!(table[4000], 2);
+        assert_eq!(table[4095], 7);
+        for i in 2000..2010 {
+            assert_eq!(table[i], i as u8);
+        }
+        assert_eq!(table.iter().filter(|&&b| b == 7).count(), 4096 - 12);
+    }
+
+    assert!(UNIFORM.iter().all(|&x| x == 0x5a5a5a5a));
+    assert_eq!(UNIFORM_SUM, 0x5a5a5a5a * 1024);
+}
@@ -0,0 +1,40 @@
+// run-pass
+// Enum variants that are uninhabited cannot hold a value, so the types in them do not make the
+// enum need drop glue.
+
+use std::cell::Cell;
+use std::convert::Infallible;
+use std::mem::needs_drop;
+
+enum Either<L, R> {
+    Left(L),
+    #[allow(dead_code)]
+    Right(R),
+}
+
+struct CountDrops<'a>(&'a Cell<u32>);
+
+impl Drop for CountDrops<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+fn main() {
+    assert!(!needs_drop::<Result<u32, (Infallible, String)>>());
+    assert!(!needs_drop::<Either<u8, (Vec<u8>, Infallible)>>());
+    assert!(!needs_drop::<Option<(Box<u8>, Infallible)>>());
+    assert!(!needs_drop::<[Either<(), (String, Infallible)>; 4]>());
+
+    // Variants that can hold a value still need to be dropped.
+    assert!(needs_drop::<Result<String, Infallible>>());
+    assert!(needs_drop::<Either<String, (String, Infallible)>>());
+    assert!(needs_drop::<Either<u8, Vec<Infallible>>>());
+
+    let drops = Cell::new(0);
+    {
+        let _value: Either<CountDrops<'_>, (CountDrops<'_>, Infallible)> =
+            Either::Left(CountDrops(&drops));
+    }
+    assert_eq!(drops.get(), 1);
+}
@@ -0,0 +1,28 @@
+// compile-flags: -Z enum-layout-report
+// build-pass
+// ignore-pass
+// ^-- needed because `--pass check` does not emit the output needed.
+
+// Reports the tag encoding, niche and padding of enums used by a body, and suggests a field
+// order for enums whose fields are not reordered by the compiler.
+
+#![feature(start)]
+#![allow(dead_code)]
+
+#[repr(u8)]
+pub enum Padded {
+    A(u8, u32, u8),
+    B,
+}
+
+pub enum Niche {
+    Some(bool),
+    None,
+}
+
+#[start]
+fn start(_: isize, _: *const *const u8) -> isize {
+    let _padded = Padded::B;
+    let _niche = Niche::None;
+    0
+}
@@ -0,0 +1,2 @@
+// compile-flags: --explain E0706
+// check-pass
@@ -0,0 +1,6 @@
+const fn apply(f: fn(u32) -> u32, x: u32) -> u32 {
+    f(x)
+    //~^ ERROR function pointer calls are not allowed in constant functions
+}
+
+fn main() {}
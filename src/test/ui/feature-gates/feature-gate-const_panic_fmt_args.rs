@@ -0,0 +1,20 @@
+// edition:2021
+#![crate_type = "lib"]
+
+const A: () = panic!("{}", 1);
+//~^ ERROR formatting arguments is unstable in constants
+
+const fn check(len: usize) {
+    if len > 4 {
+        panic!("length {} is too large", len);
+        //~^ ERROR formatting arguments is unstable in constant functions
+    }
+}
+
+const fn stable(msg: &str, fail: bool) {
+    if fail {
+        panic!("no arguments");
+    } else {
+        panic!("{}", msg);
+    }
+}
@@ -0,0 +1,7 @@
+#[used]
+#[ctor_priority(1000)] //~ ERROR the `#[ctor_priority]` attribute is an experimental feature
+static CTOR: extern "C" fn() = ctor;
+
+extern "C" fn ctor() {}
+
+fn main() {}
@@ -0,0 +1,3 @@
+#[frame_pointer(always)]
+//~^ ERROR the `#[frame_pointer]` attribute is an experimental feature [E0658]
+fn main() {}
@@ -0,0 +1,19 @@
+// force-host
+// no-prefer-dynamic
+
+#![crate_type = "proc-macro"]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+
+#[proc_macro_attribute(cfg_eval)] //~ ERROR `#[proc_macro_attribute(cfg_eval)]` is experimental
+pub fn foo(_: TokenStream, input: TokenStream) -> TokenStream {
+    input
+}
+
+#[proc_macro_attribute(expand_derives)]
+//~^ ERROR `#[proc_macro_attribute(expand_derives)]` is experimental
+pub fn bar(_: TokenStream, input: TokenStream) -> TokenStream {
+    input
+}
@@ -1,3 +1,5 @@
 #![register_tool(tool)] //~ ERROR the `#[register_tool]` attribute is an experimental feature
+#![register_tool_attr(tool::attr)]
+//~^ ERROR the `#[register_tool_attr]` attribute is an experimental feature
 
 fn main() {}
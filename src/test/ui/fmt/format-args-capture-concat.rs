@@ -0,0 +1,17 @@
+// run-pass
+
+macro_rules! bracketed {
+    ($fmt:literal) => {
+        concat!("[", $fmt, "]")
+    };
+}
+
+fn main() {
+    let x = 1;
+    let name = "world";
+    assert_eq!(format!(concat!("{x}", "+", "{x}")), "1+1");
+    assert_eq!(format!(concat!("{", "x", "}")), "1");
+    assert_eq!(format!(concat!(concat!("{x}", " "), "{name:?}")), "1 \"world\"");
+    assert_eq!(format!(concat!(r#"{x} ""#, "{name}\n")), "1 \"world\n");
+    assert_eq!(format!(bracketed!("{name}")), "[world]");
+}
@@ -1,4 +1,11 @@
+macro_rules! format_str {
+    () => {
+        "{foo}"
+    };
+}
+
 fn main() {
-    format!(concat!("{foo}"));         //~ ERROR: there is no argument named `foo`
-    format!(concat!("{ba", "r} {}"), 1);     //~ ERROR: there is no argument named `bar`
+    format!(format_str!()); //~ ERROR: there is no argument named `foo`
+    format!(concat!("{foo}")); //~ ERROR: cannot find value `foo` in this scope
+    format!(concat!("{ba", "r} {}"), 1); //~ ERROR: cannot find value `bar` in this scope
 }
@@ -0,0 +1,42 @@
+// run-pass
+// revisions: default disabled
+//[disabled] compile-flags: -Zflatten-format-args=no
+
+// Check that literal arguments and nested `format_args!` calls are written into the format
+// string without changing the output.
+
+use std::cell::RefCell;
+
+fn main() {
+    let flattened = cfg!(default);
+
+    let check = |args: std::fmt::Arguments<'_>, expected: &str| {
+        assert_eq!(args.to_string(), expected);
+        assert_eq!(args.as_str(), flattened.then_some(expected));
+    };
+    check(format_args!("Hello, {}!", "world"), "Hello, world!");
+    check(format_args!("{} {} {} {}", 'a', 1, true, "b"), "a 1 true b");
+    check(format_args!("{}", format_args!("{}{}", "x", 1)), "x1");
+    check(format_args!("{{}} {}", "{}"), "{} {}");
+    check(format_args!("{0}{0}", "ab"), "abab");
+
+    // Arguments with formatting options are left alone.
+    assert_eq!(format_args!("{:?}", "a").as_str(), None);
+    assert_eq!(format!("{:?} {}", "a", "a"), "\"a\" a");
+    assert_eq!(format!("{:>3}{}", 1, 2), "  12");
+    assert_eq!(format!("{:1$}|{}", 'x', 3), "x  |3");
+
+    // Arguments of nested calls are evaluated once, in the order they were written.
+    let order = RefCell::new(Vec::new());
+    let arg = |i: u32| {
+        order.borrow_mut().push(i);
+        i
+    };
+    let s = format!("{2} {} {}", arg(0), format_args!("[{1} {0}]", arg(1), arg(2)), arg(3));
+    assert_eq!(s, "3 0 [2 1]");
+    assert_eq!(*order.borrow(), [0, 1, 2, 3]);
+
+    let x = 5;
+    assert_eq!(format!("{x} {}", format_args!("{x:02}{}", "!")), "5 05!");
+    assert_eq!(format!("{}", format_args!("{}", format_args!("{}", x))), "5");
+}
@@ -0,0 +1,6 @@
+fn main() {
+    format!(concat!("{} ", "{}"));
+    //~^ ERROR 2 positional arguments in format string, but no arguments were given
+    format!(concat!("{x:", "?}"));
+    //~^ ERROR cannot find value `x` in this scope
+}
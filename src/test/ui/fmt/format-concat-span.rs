@@ -7,7 +7,7 @@
 //   2 |     format!(concat!("abc}"));
 //     |                 ^ unmatched `}` in format string
 //
-// This test checks that this behavior has been fixed.
+// This test checks that the error points into the literal passed to `concat!`.
 
 fn main() {
     format!(concat!("abc}"));
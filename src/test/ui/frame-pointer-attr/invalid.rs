@@ -0,0 +1,15 @@
+// build-fail
+// only-x86_64-unknown-linux-gnu
+
+#![feature(frame_pointer_attribute)]
+
+#[frame_pointer(sometimes)] //~ ERROR invalid argument
+fn invalid() {}
+
+#[frame_pointer(always, none)] //~ ERROR expected one argument
+fn too_many() {}
+
+fn main() {
+    invalid();
+    too_many();
+}
@@ -0,0 +1,24 @@
+#![feature(frame_pointer_attribute)]
+#![allow(dead_code)]
+
+#[frame_pointer(always)] //~ ERROR attribute should be applied to a function definition or closure
+struct S;
+
+#[frame_pointer(always)] //~ ERROR attribute should be applied to a function definition or closure
+static X: u32 = 0;
+
+trait T {
+    #[frame_pointer(always)] //~ ERROR attribute should be applied to a function definition or closure
+    fn required();
+
+    #[frame_pointer(always)]
+    fn provided() {}
+}
+
+impl S {
+    #[frame_pointer(always)]
+    fn method() {}
+}
+
+#[frame_pointer(always)]
+fn main() {}
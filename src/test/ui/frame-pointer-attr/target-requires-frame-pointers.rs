@@ -0,0 +1,19 @@
+// build-fail
+// compile-flags: --target aarch64-apple-darwin --crate-type rlib
+// needs-llvm-components: aarch64
+
+#![feature(no_core, lang_items, frame_pointer_attribute)]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+
+#[frame_pointer(none)]
+//~^ ERROR target `aarch64-apple-darwin` does not allow omitting frame pointers
+pub fn omitted() {}
+
+#[frame_pointer(non_leaf)]
+pub fn non_leaf() {}
+
+#[frame_pointer(always)]
+pub fn always() {}
@@ -0,0 +1,37 @@
+// edition:2018
+// Check that a hidden type that doesn't implement an auto trait declared on its opaque type is
+// reported where it is returned, naming the component that is at fault.
+
+use std::cell::Cell;
+use std::future::{ready, Future};
+use std::rc::Rc;
+
+struct Counter {
+    count: Rc<u32>,
+}
+
+fn rc() -> impl Send {
+    Rc::new(0u32)
+    //~^ ERROR `Rc<u32>` cannot be sent between threads safely
+}
+
+fn counter() -> impl Sized + Send {
+    Counter { count: Rc::new(0) }
+    //~^ ERROR `Rc<u32>` cannot be sent between threads safely
+}
+
+fn cell() -> impl Sync {
+    Cell::new(0u8)
+    //~^ ERROR `Cell<u8>` cannot be shared between threads safely
+}
+
+fn future() -> impl Future<Output = ()> + Send {
+    async {
+        //~^ ERROR future cannot be sent between threads safely
+        let count = Rc::new(0u32);
+        ready(()).await;
+        drop(count);
+    }
+}
+
+fn main() {}
@@ -2,12 +2,14 @@
 
 #![stable(feature = "rust1", since = "1.0.0")]
 #![feature(staged_api)]
-#![feature(const_fn_floating_point_arithmetic)]
+#![feature(const_mut_refs)]
 
 #[stable(feature = "rust1", since = "1.0.0")]
 #[rustc_const_stable(feature = "rust1", since = "1.0.0")]
-pub const fn foo() -> f32 {
-    1.0 + 1.0 //~ ERROR const-stable function cannot use `#[feature(const_fn_floating_point_arithmetic)]`
+pub const fn foo() -> i32 {
+    let mut x = 0;
+    let y = &mut x; //~ ERROR const-stable function cannot use `#[feature(const_mut_refs)]`
+    *y
 }
 
 fn main() {}
@@ -0,0 +1,10 @@
+// compile-flags: --target=x86_64-unknown-linux-gnu -Zatomic-shims
+// check-fail
+// needs-llvm-components: x86
+
+#![crate_type = "lib"]
+#![feature(no_core, lang_items)]
+#![no_core]
+
+#[lang="sized"]
+trait Sized { }
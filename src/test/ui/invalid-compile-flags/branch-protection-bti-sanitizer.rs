@@ -0,0 +1,10 @@
+// compile-flags: --target=aarch64-unknown-linux-gnu -Zbranch-protection=bti -Zsanitizer=address
+// check-fail
+// needs-llvm-components: aarch64
+
+#![crate_type = "lib"]
+#![feature(no_core, lang_items)]
+#![no_core]
+
+#[lang="sized"]
+trait Sized { }
@@ -0,0 +1,10 @@
+// compile-flags: --target=wasm32-unknown-unknown -Zwasm-exception-handling
+// check-fail
+// needs-llvm-components: webassembly
+
+#![crate_type = "lib"]
+#![feature(no_core, lang_items)]
+#![no_core]
+
+#[lang="sized"]
+trait Sized { }
@@ -0,0 +1,24 @@
+// run-rustfix
+
+#![deny(elided_lifetimes_in_paths)]
+#![allow(dead_code)]
+
+struct Foo<'a>(&'a u8);
+struct Pair<'a, T>(&'a T);
+
+macro_rules! ty {
+    ($t:ty) => { $t };
+}
+
+fn fn_pointer(_: fn(Foo) -> u8) {}
+//~^ ERROR hidden lifetime parameters in types are deprecated
+
+fn turbofish() {
+    let _ = Vec::<Pair<u8>>::new();
+    //~^ ERROR hidden lifetime parameters in types are deprecated
+}
+
+fn macro_arg(_: ty!(Pair<u8>)) {}
+//~^ ERROR hidden lifetime parameters in types are deprecated
+
+fn main() {}
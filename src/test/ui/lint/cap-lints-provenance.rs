@@ -0,0 +1,12 @@
+// Checks that lints lowered by `--cap-lints` point at the level they were requested with.
+
+// check-pass
+// compile-flags: --cap-lints warn
+
+#![deny(unused_imports)]
+
+use std::option; //~ WARN unused import
+
+fn main() {
+    let _x: u8 = 256; //~ WARN literal out of range for `u8`
+}
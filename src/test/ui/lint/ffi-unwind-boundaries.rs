@@ -0,0 +1,26 @@
+// check-pass
+// needs-unwind
+#![warn(ffi_unwind_boundaries)]
+
+pub extern "C" fn get(bytes: &[u8; 4], i: usize) -> u8 {
+    //~^ WARNING this `extern "C"` function may panic, but its ABI doesn't allow unwinding
+    bytes[i]
+}
+
+pub extern "C" fn xor(a: u8, b: u8) -> u8 {
+    a ^ b
+}
+
+extern "C" {
+    fn abs(x: i32) -> i32;
+}
+
+pub extern "C" fn foreign_abs(x: i32) -> i32 {
+    unsafe { abs(x) }
+}
+
+pub fn rust_abi(bytes: &[u8]) -> u8 {
+    bytes[0]
+}
+
+fn main() {}
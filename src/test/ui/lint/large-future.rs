@@ -0,0 +1,23 @@
+// edition:2018
+// check-pass
+#![warn(large_future)]
+
+async fn wait() {}
+
+async fn small() {
+    let data = [0u8; 1024];
+    wait().await;
+    drop(data);
+}
+
+async fn large() {
+    //~^ WARN the future of this `async fn` takes 32770 bytes
+    let data = [0u8; 32768];
+    wait().await;
+    drop(data);
+}
+
+fn main() {
+    drop(small());
+    drop(large());
+}
@@ -0,0 +1,22 @@
+// check-pass
+#![warn(let_underscore_drop)]
+
+struct NontrivialDrop;
+
+impl Drop for NontrivialDrop {
+    fn drop(&mut self) {
+        println!("dropping");
+    }
+}
+
+fn main() {
+    let _ = NontrivialDrop; //~ WARNING non-binding `let` on a value with a destructor
+    println!("after");
+
+    let value = NontrivialDrop;
+    let _ = value;
+
+    let _ = 1u32;
+
+    let _ = NontrivialDrop; //~ WARNING non-binding `let` on a value with a destructor
+}
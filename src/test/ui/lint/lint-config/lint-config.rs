@@ -0,0 +1,16 @@
+// Lint levels from `-Zlint-config` apply between the command line and attributes, and the levels
+// of groups apply before the ones of individual lints.
+
+// compile-flags: -Zlint-config={{src-base}}/lint/lint-config/lints.toml
+
+struct snake_case; //~ ERROR should have an upper camel case name
+
+fn main() {
+    let _InappropriateCamelCasing = snake_case; //~ WARN should have a snake case name
+    let x = 1; //~ ERROR unused variable
+}
+
+#[allow(unused_variables)]
+fn allowed() {
+    let y = 1;
+}
@@ -0,0 +1,4 @@
+// compile-flags: -Zlint-config={{src-base}}/lint/lint-config/unknown-lint.toml
+// error-pattern: unknown lint: `unused_varibles`
+
+fn main() {}
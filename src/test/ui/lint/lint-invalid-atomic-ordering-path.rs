@@ -0,0 +1,30 @@
+// only-x86_64
+use std::sync::atomic::{self, AtomicBool, AtomicUsize, Ordering};
+
+fn main() {
+    let x = AtomicUsize::new(0);
+
+    // Allowed ordering modes
+    let _ = AtomicUsize::load(&x, Ordering::Acquire);
+    AtomicUsize::store(&x, 1, Ordering::Release);
+    let _ = AtomicUsize::compare_exchange(&x, 0, 0, Ordering::SeqCst, Ordering::Acquire);
+    atomic::fence(Ordering::SeqCst);
+
+    // Disallowed ordering modes
+    let _ = AtomicUsize::load(&x, Ordering::Release);
+    //~^ ERROR atomic loads cannot have `Release` or `AcqRel` ordering
+    <AtomicUsize>::store(&x, 1, Ordering::AcqRel);
+    //~^ ERROR atomic stores cannot have `Acquire` or `AcqRel` ordering
+    let _ = AtomicUsize::compare_exchange(&x, 0, 0, Ordering::Relaxed, Ordering::SeqCst);
+    //~^ ERROR compare_exchange's failure ordering may not be stronger
+    let _ = AtomicBool::compare_exchange_weak(
+        &AtomicBool::new(false),
+        false,
+        true,
+        Ordering::Acquire,
+        Ordering::Release,
+    );
+    //~^^ ERROR compare_exchange_weak's failure ordering may not be `Release` or `AcqRel`
+    std::sync::atomic::compiler_fence(Ordering::Relaxed);
+    //~^ ERROR memory fences cannot have `Relaxed` ordering
+}
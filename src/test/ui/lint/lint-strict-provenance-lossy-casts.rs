@@ -15,4 +15,8 @@ fn main() {
     //~^ ERROR under strict provenance it is considered bad style to cast pointer `*const u8` to integer `usize`
     let ptr_addr_32bit = ptr as u32;
     //~^ ERROR under strict provenance it is considered bad style to cast pointer `*const u8` to integer `u32`
+
+    // nor a second pair of parens to a parenthesized expression
+    let paren_addr = (&x as *const u8) as usize;
+    //~^ ERROR under strict provenance it is considered bad style to cast pointer `*const u8` to integer `usize`
 }
@@ -0,0 +1,32 @@
+// run-rustfix
+
+#![deny(redundant_bounds)]
+#![allow(dead_code)]
+
+use std::fmt::Debug;
+
+fn duplicate<T: Clone + Debug + Clone>(_: T) {} //~ ERROR duplicate trait bound
+
+fn duplicate_where<T>(_: T) where T: Debug, T: Debug {} //~ ERROR duplicate trait bound
+
+fn implied<T: Ord + PartialOrd>(_: T) {} //~ ERROR is implied by
+
+fn implied_where<T: PartialEq>(_: T) where T: Eq {}
+//~^ ERROR is implied by
+
+struct Wrapper<T: Copy + Clone>(T); //~ ERROR is implied by
+
+trait Trait {
+    fn method<T: Debug + Debug>(&self, _: T); //~ ERROR duplicate trait bound
+}
+
+impl<T: Eq + PartialEq + Copy> Trait for Wrapper<T> {
+    //~^ ERROR is implied by
+    fn method<U: Debug>(&self, _: U) {}
+}
+
+// Bounds with different generic arguments or associated type bindings aren't redundant.
+fn different_args<T: PartialEq<u8> + PartialEq<u16>>(_: T) {}
+fn bindings<T: Iterator<Item = u8> + Iterator>(_: T) {}
+
+fn main() {}
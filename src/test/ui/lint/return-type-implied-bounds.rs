@@ -0,0 +1,25 @@
+// check-pass
+// run-rustfix
+#![allow(dead_code)]
+
+pub fn leak<'a, T>(value: Box<T>) -> &'a mut T {
+    //~^ WARN this signature is only well-formed because of its return type
+    //~| WARN this was previously accepted
+    Box::leak(value)
+}
+
+pub fn first<'a, T>(items: &'a [T]) -> Option<&'a T> {
+    items.first()
+}
+
+struct Leaker;
+
+impl Leaker {
+    fn leak<'a, T>(&self, value: Box<T>) -> &'a mut T where T: Copy {
+        //~^ WARN this signature is only well-formed because of its return type
+        //~| WARN this was previously accepted
+        Box::leak(value)
+    }
+}
+
+fn main() {}
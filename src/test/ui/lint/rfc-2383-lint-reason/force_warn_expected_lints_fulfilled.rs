@@ -0,0 +1,30 @@
+// compile-flags: --force-warn while_true
+// compile-flags: --force-warn unused_variables
+// check-pass
+
+#![feature(lint_reasons)]
+
+// `--force-warn` overrides the `#[expect]` attributes, so the lints are emitted as
+// warnings. The expectations still count as fulfilled.
+
+fn expect_early_pass_lint() {
+    #[expect(while_true)]
+    while true {
+        //~^ WARNING denote infinite loops with `loop { ... }` [while_true]
+        println!("I never stop")
+    }
+}
+
+#[expect(unused_variables)]
+fn expect_late_pass_lint() {
+    let x = 2;
+    //~^ WARNING unused variable: `x` [unused_variables]
+}
+
+#[expect(unused_variables)]
+//~^ WARNING this lint expectation is unfulfilled [unfulfilled_lint_expectations]
+fn expect_without_lint() {
+    let _x = 2;
+}
+
+fn main() {}
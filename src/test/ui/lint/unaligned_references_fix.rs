@@ -0,0 +1,19 @@
+// run-rustfix
+
+#![deny(unaligned_references)]
+
+#[repr(packed)]
+pub struct Packed {
+    data: u64,
+    mutable: u32,
+}
+
+fn main() {
+    let mut packed = Packed { data: 0, mutable: 0 };
+    let _: *const u64 = &packed.data; //~ ERROR reference to packed field
+    //~^ previously accepted
+    let _: *mut u32 = &mut packed.mutable; //~ ERROR reference to packed field
+    //~^ previously accepted
+    let _ = packed.data.clone(); //~ ERROR reference to packed field
+    //~^ previously accepted
+}
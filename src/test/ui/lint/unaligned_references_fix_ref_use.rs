@@ -0,0 +1,25 @@
+// run-rustfix
+// rustfix-only-machine-applicable
+// check-pass
+
+// The `addr_of!` fix is only machine applicable when the reference is turned into a raw pointer
+// right away; a reference that is used as `&T` keeps the suggestion but rustfix leaves it alone.
+
+#![warn(unaligned_references)]
+
+#[repr(packed)]
+pub struct Packed {
+    data: u64,
+}
+
+fn read(data: &u64) -> u64 {
+    *data
+}
+
+fn main() {
+    let packed = Packed { data: 0 };
+    let _ = &packed.data as *const u64; //~ WARN reference to packed field
+    //~^ previously accepted
+    let _ = read(&packed.data); //~ WARN reference to packed field
+    //~^ previously accepted
+}
@@ -0,0 +1,39 @@
+#![deny(unused_generic_parameters)]
+#![allow(dead_code)]
+
+pub fn unused<T>() -> u32 {
+    //~^ ERROR generic parameter `T` is never used
+    42
+}
+
+pub fn two_unused<T, const N: usize>() {}
+//~^ ERROR generic parameters `T` and `N` are never used
+
+pub fn in_body<T>() -> usize {
+    std::mem::size_of::<T>()
+}
+
+pub fn in_signature<T>(_: Option<T>) {}
+
+pub fn in_where_clause<T>()
+where
+    T: Default,
+{
+}
+
+struct S;
+
+impl S {
+    fn method<T>(&self) {}
+    //~^ ERROR generic parameter `T` is never used
+}
+
+trait Tr {
+    fn method<T>(&self);
+}
+
+impl Tr for S {
+    fn method<T>(&self) {}
+}
+
+fn main() {}
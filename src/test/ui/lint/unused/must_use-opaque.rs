@@ -0,0 +1,52 @@
+// edition:2018
+
+#![deny(unused_must_use)]
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+#[must_use = "guards do nothing unless held"]
+struct Guard;
+
+struct Ready;
+
+impl Future for Ready {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+        Poll::Ready(())
+    }
+}
+
+trait FutureExt: Future {}
+
+impl<F: Future> FutureExt for F {}
+
+// `Future` is only a supertrait of the written bound.
+fn extended() -> impl FutureExt {
+    Ready
+}
+
+async fn async_fn() {}
+
+// The bound isn't `#[must_use]`, but the hidden type is.
+fn hidden() -> impl Sized {
+    Guard
+}
+
+fn boxed_hidden() -> impl Sized {
+    Box::new(Guard)
+}
+
+fn not_must_use() -> impl Sized {
+    Ready
+}
+
+fn main() {
+    extended(); //~ ERROR unused implementer of `Future` that must be used
+    async_fn(); //~ ERROR unused implementer of `Future` that must be used
+    hidden(); //~ ERROR unused opaque type hiding `Guard` that must be used
+    boxed_hidden(); //~ ERROR unused opaque type hiding boxed `Guard` that must be used
+    not_must_use();
+}
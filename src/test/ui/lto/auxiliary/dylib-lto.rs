@@ -0,0 +1,8 @@
+// compile-flags: -Z dylib-lto -C lto=thin
+
+#![crate_type = "dylib"]
+
+#[inline(never)]
+pub fn foo(x: u32) -> u32 {
+    x.to_string().len() as u32 + 1
+}
@@ -0,0 +1,11 @@
+// run-pass
+// aux-build:dylib-lto.rs
+
+// Checks that a dylib built with `-Zdylib-lto` (and with the default
+// `-Cprefer-dynamic` used for auxiliary crates) can be linked against.
+
+extern crate dylib_lto;
+
+fn main() {
+    assert_eq!(dylib_lto::foo(123), 4);
+}
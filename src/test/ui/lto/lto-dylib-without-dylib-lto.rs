@@ -0,0 +1,7 @@
+// build-fail
+// compile-flags: -C lto
+// no-prefer-dynamic
+
+#![crate_type = "dylib"]
+
+pub fn foo() {}
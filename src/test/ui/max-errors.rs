@@ -0,0 +1,10 @@
+// This test checks that only the first errors are printed with `-Z max-errors`
+// compile-flags: --error-format=human -Z max-errors=2
+
+fn main() {
+    let _ = x;
+    let _: u8 = "a";
+    let _: u8 = "b";
+    let _: u8 = "c";
+    0u32.foo();
+}
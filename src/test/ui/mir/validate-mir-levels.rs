@@ -0,0 +1,27 @@
+// Checks that every `-Zvalidate-mir` level accepts well-formed MIR.
+
+// build-pass
+// revisions: off basic full
+//[off] compile-flags: -Zvalidate-mir=off
+//[basic] compile-flags: -Zvalidate-mir=basic
+//[full] compile-flags: -Zvalidate-mir=full -Zmir-opt-level=3
+
+fn collatz(mut n: u64) -> u32 {
+    let mut steps = 0;
+    while n != 1 {
+        n = if n % 2 == 0 { n / 2 } else { 3 * n + 1 };
+        steps += 1;
+    }
+    steps
+}
+
+fn main() {
+    let words = vec![String::from("a"), String::from("bc")];
+    let total: usize = words.iter().map(|w| w.len()).sum();
+    let first = match words.first() {
+        Some(word) => word.clone(),
+        None => String::new(),
+    };
+    assert_eq!(total + first.len(), 4);
+    assert_eq!(collatz(6), 8);
+}
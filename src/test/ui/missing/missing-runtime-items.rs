@@ -0,0 +1,16 @@
+// Check that a `#![no_std]` binary missing several of the items normally provided by `std`
+// gets a single diagnostic listing all of them.
+
+// compile-flags: -C panic=unwind
+// needs-unwind
+// no-prefer-dynamic
+// error-pattern: a `#[panic_handler]` function and a `#[global_allocator]` required
+
+#![no_std]
+#![crate_type = "staticlib"]
+#![feature(default_alloc_error_handler, lang_items)]
+
+#[lang = "eh_personality"]
+extern "C" fn eh_personality() {}
+
+extern crate alloc;
@@ -0,0 +1,5 @@
+// Point out a module file placed next to a non-`mod.rs` file instead of in its directory.
+
+mod outer;
+
+fn main() {}
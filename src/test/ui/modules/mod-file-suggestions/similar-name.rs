@@ -0,0 +1,5 @@
+// Suggest a module file with a name close to the one in a `mod` declaration.
+
+mod widgest; //~ ERROR file not found for module `widgest`
+
+fn main() {}
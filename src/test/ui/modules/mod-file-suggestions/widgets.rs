@@ -0,0 +1,3 @@
+// ignore-test this is just a helper for the real tests in this dir
+
+pub struct Widget;
@@ -0,0 +1,12 @@
+// run-rustfix
+
+// Expressions the user already wrapped in parentheses shouldn't get a second pair.
+
+#![allow(unused_parens)]
+
+fn main() {
+    let a: u16 = 1;
+    let b: u16 = 2;
+    let _x: u32 = (a + b);
+    //~^ ERROR mismatched types
+}
@@ -1,6 +1,5 @@
 // aux-build:weak-lang-items.rs
-// error-pattern: `#[panic_handler]` function required, but not found
-// error-pattern: language item required, but not found: `eh_personality`
+// error-pattern: a `#[panic_handler]` function and the `eh_personality` language item required
 // needs-unwind since it affects the error output
 // ignore-emscripten compiled with panic=abort, personality not required
 
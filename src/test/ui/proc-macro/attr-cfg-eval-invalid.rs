@@ -0,0 +1,19 @@
+// force-host
+// no-prefer-dynamic
+
+#![crate_type = "proc-macro"]
+#![feature(proc_macro_cfg_eval)]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+
+#[proc_macro_attribute(cfg_eval, strip_docs)] //~ ERROR unknown `proc_macro_attribute` option
+pub fn foo(_: TokenStream, input: TokenStream) -> TokenStream {
+    input
+}
+
+#[proc_macro_attribute(cfg_eval = "yes")] //~ ERROR unknown `proc_macro_attribute` option
+pub fn bar(_: TokenStream, input: TokenStream) -> TokenStream {
+    input
+}
@@ -0,0 +1,28 @@
+// check-pass
+// aux-build:cfg-eval-attr.rs
+
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate cfg_eval_attr;
+
+#[print_cfg_evaled]
+struct S1 {
+    #[cfg(FALSE)]
+    field_false: u8,
+    #[cfg_attr(all(), allow(unused))]
+    field_true: u8,
+}
+
+#[print_derives_expanded]
+#[derive(Clone)]
+struct S2 {
+    #[cfg(FALSE)]
+    field_false: u8,
+    field_true: u8,
+}
+
+fn main() {
+    let s = S2 { field_true: 0 };
+    let _ = s.clone();
+}
@@ -0,0 +1,21 @@
+// force-host
+// no-prefer-dynamic
+
+#![crate_type = "proc-macro"]
+#![feature(proc_macro_cfg_eval)]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+
+#[proc_macro_attribute(cfg_eval)]
+pub fn print_cfg_evaled(_: TokenStream, input: TokenStream) -> TokenStream {
+    println!("CFG-EVALED INPUT (DISPLAY): {}", input);
+    input
+}
+
+#[proc_macro_attribute(cfg_eval, expand_derives)]
+pub fn print_derives_expanded(_: TokenStream, input: TokenStream) -> TokenStream {
+    println!("DERIVES-EXPANDED INPUT (DISPLAY): {}", input);
+    input
+}
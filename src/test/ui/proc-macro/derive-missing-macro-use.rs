@@ -0,0 +1,17 @@
+// aux-build:test-macros.rs
+
+// The suggested attribute keeps the indentation of the `extern crate` item.
+    extern crate test_macros;
+
+#[derive(Empty)]
+//~^ ERROR cannot find derive macro `Empty` in this scope
+struct A;
+
+mod inner {
+    // No `use` item is suggested here, it would have to go into this module.
+    #[derive(Empty)]
+    //~^ ERROR cannot find derive macro `Empty` in this scope
+    struct B;
+}
+
+fn main() {}
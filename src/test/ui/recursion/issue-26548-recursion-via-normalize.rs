@@ -1,8 +1,3 @@
-//~ ERROR cycle detected when computing layout of `S`
-//~| NOTE ...which requires computing layout of `core::option::Option<<S as Mirror>::It>`...
-//~| NOTE ...which requires computing layout of `core::option::Option<S>`...
-//~| NOTE ...which again requires computing layout of `S`, completing the cycle
-
 // build-fail
 
 trait Mirror {
@@ -12,6 +7,11 @@ impl<T: ?Sized> Mirror for T {
     type It = Self;
 }
 struct S(Option<<S as Mirror>::It>);
+//~^ ERROR cycle detected when computing layout of `S`
+//~| NOTE ...which requires computing layout of `core::option::Option<<S as Mirror>::It>`...
+//~| NOTE ...which requires computing layout of `core::option::Option<S>`...
+//~| NOTE ...which again requires computing layout of `S`, completing the cycle
+//~| NOTE a type cannot contain itself without indirection
 
 fn main() {
     //~^ NOTE cycle used when elaborating drops for `main`
@@ -0,0 +1,7 @@
+// Paths in diagnostics stay local when `-Zremap-path-scope` excludes them.
+
+// compile-flags: --remap-path-prefix={{src-base}}=remapped -Zremap-path-scope=macro,debuginfo
+
+fn main() {
+    ferris //~ ERROR cannot find value `ferris` in this scope
+}
@@ -0,0 +1,19 @@
+// Checks that `file!()` and `#[track_caller]` locations are only remapped when
+// `-Zremap-path-scope` includes macros.
+
+// run-pass
+// revisions: remapped local
+// compile-flags: --remap-path-prefix={{src-base}}=remapped
+// [remapped]compile-flags: -Zremap-path-scope=macro
+// [local]compile-flags: -Zremap-path-scope=diagnostics,debuginfo
+
+#[track_caller]
+fn caller_file() -> &'static str {
+    std::panic::Location::caller().file()
+}
+
+fn main() {
+    let remapped = cfg!(remapped);
+    assert_eq!(file!().starts_with("remapped"), remapped);
+    assert_eq!(caller_file().starts_with("remapped"), remapped);
+}
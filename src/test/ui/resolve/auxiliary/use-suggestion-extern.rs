@@ -0,0 +1,3 @@
+pub mod gadgets {
+    pub struct Widget;
+}
@@ -0,0 +1,30 @@
+// Check that typo suggestions leave out locals that hygiene hides from the misspelled name,
+// look through glob imports, and prefer accessible items over inaccessible ones that are
+// just as close.
+
+macro_rules! define_counter {
+    () => {
+        let counter = 0;
+    };
+}
+
+mod tools {
+    pub fn helper() {}
+}
+
+mod shapes {
+    fn squad() {}
+    pub fn square() {}
+}
+
+use tools::*;
+
+fn main() {
+    define_counter!();
+    let _ = countr;
+    //~^ ERROR cannot find value `countr` in this scope
+    helpr();
+    //~^ ERROR cannot find function `helpr` in this scope
+    shapes::squar();
+    //~^ ERROR cannot find function `squar` in module `shapes`
+}
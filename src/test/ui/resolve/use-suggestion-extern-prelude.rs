@@ -0,0 +1,12 @@
+// aux-crate:use_suggestion_extern=use-suggestion-extern.rs
+// edition:2018
+// run-rustfix
+// rustfix-only-machine-applicable
+// Check that a lone import candidate from the standard library or from a crate that is only
+// passed with `--extern` is suggested as an import that can be applied as is.
+
+fn main() {
+    let _ = HashMap::<u8, u8>::new(); //~ ERROR failed to resolve
+    let _ = Rc::new(0); //~ ERROR failed to resolve
+    let _ = Widget; //~ ERROR cannot find value `Widget`
+}
@@ -0,0 +1,12 @@
+// edition:2018
+// Check that a lone import candidate from the local crate is still suggested. Unlike a candidate
+// from the standard library or another crate (see use-suggestion-extern-prelude.rs), it is not
+// machine-applicable, as the user may have meant a different item with the same name.
+
+mod gadgets {
+    pub struct Widget;
+}
+
+fn main() {
+    let _ = Widget; //~ ERROR cannot find value `Widget`
+}
@@ -42,3 +42,9 @@ pub enum FieldLessWithNonExhaustiveVariant {
 impl Default for FieldLessWithNonExhaustiveVariant {
     fn default() -> Self { Self::A }
 }
+
+#[non_exhaustive]
+pub enum ManyVariants {
+    V1, V2, V3, V4, V5, V6, V7, V8, V9, V10,
+    V11, V12, V13, V14, V15, V16, V17, V18, V19, V20,
+}
@@ -0,0 +1,30 @@
+// Test that the `non_exhaustive_omitted_patterns` lint lists the variants matched by the
+// wildcard when there are too many of them for the label.
+
+#![feature(non_exhaustive_omitted_patterns_lint)]
+
+// aux-build:enums.rs
+extern crate enums;
+
+use enums::ManyVariants;
+
+#[deny(non_exhaustive_omitted_patterns)]
+fn main() {
+    let v = ManyVariants::V1;
+
+    match v {
+        ManyVariants::V1 | ManyVariants::V2 | ManyVariants::V3 => {}
+        ManyVariants::V4 | ManyVariants::V5 | ManyVariants::V6 => {}
+        ManyVariants::V7 | ManyVariants::V8 | ManyVariants::V9 => {}
+        ManyVariants::V10 | ManyVariants::V11 | ManyVariants::V12 => {}
+        ManyVariants::V13 | ManyVariants::V14 | ManyVariants::V15 => {}
+        _ => {}
+    }
+    //~^^ some variants are not matched explicitly
+
+    match v {
+        ManyVariants::V1 => {}
+        _ => {}
+    }
+    //~^^ some variants are not matched explicitly
+}
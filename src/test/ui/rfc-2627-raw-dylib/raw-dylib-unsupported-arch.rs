@@ -0,0 +1,8 @@
+// compile-flags: --crate-type lib --target thumbv7a-pc-windows-msvc
+// needs-llvm-components: arm
+#![feature(raw_dylib, no_core)]
+//~^ WARNING: the feature `raw_dylib` is incomplete
+#![no_core]
+#[link(name = "foo", kind = "raw-dylib")]
+//~^ ERROR: link kind `raw-dylib` is only supported on x86, x86_64 and aarch64
+extern "C" {}
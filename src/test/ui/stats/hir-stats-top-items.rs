@@ -0,0 +1,43 @@
+// check-pass
+// compile-flags: -Zhir-stats -Zhir-stats-top-items=2
+// Only the order of the items is checked: the rows of the per-node tables are removed and the
+// sizes are normalized, since they change with the layout of the AST and HIR.
+// normalize-stderr-test "\n[A-Za-z]+ +[0-9_]+ +[0-9_]+ +[0-9_]+" -> ""
+// normalize-stderr-test "\nTotal +[0-9_]+" -> "\nTotal N"
+// normalize-stderr-test "\n +[0-9_]+ +[0-9_]+  " -> "\nN N  "
+
+fn big(x: u32) -> u32 {
+    let a = x + 1;
+    let b = a * 2;
+    let c = b - 3;
+    let d = c / 4;
+    let e = d % 5;
+    let f = e + a;
+    let g = f * b;
+    let h = g - c;
+    let i = h / d;
+    let j = i % e;
+    let k = j + f;
+    let l = k * g;
+    let m = l - h;
+    let n = m / i;
+    let o = n % j;
+    let p = o + k;
+    a + b + c + d + e + f + g + h + i + j + k + l + m + n + o + p
+}
+
+mod m {
+    pub fn medium(x: u32) -> u32 {
+        let a = x + 1;
+        let b = a * 2;
+        let c = b - 3;
+        let d = c / 4;
+        a + b + c + d
+    }
+}
+
+fn small() {}
+
+fn main() {
+    small();
+}
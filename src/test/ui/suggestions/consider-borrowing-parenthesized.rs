@@ -0,0 +1,19 @@
+// run-rustfix
+
+// Expressions the user already wrapped in parentheses shouldn't get a second pair when
+// suggesting to borrow them.
+
+#![allow(unused, unused_parens)]
+
+fn takes_ref(x: &usize) -> usize {
+    x + 1
+}
+
+fn main() {
+    let behold: isize = 2;
+    let with_tears: usize = 3;
+    takes_ref((behold as usize));
+    //~^ ERROR mismatched types [E0308]
+    takes_ref((with_tears + 4));
+    //~^ ERROR mismatched types [E0308]
+}
@@ -0,0 +1,27 @@
+// edition:2018
+// only-x86_64
+
+#![feature(target_clones)]
+
+#[target_clones()] //~ ERROR requires at least one set of features
+fn no_features() {}
+
+#[target_clones(avx2)] //~ ERROR expected a string literal
+fn not_a_literal() {}
+
+#[target_clones("avx2,")] //~ ERROR expected a string literal
+fn empty_feature() {}
+
+#[target_clones("avx2")] //~ ERROR can only be applied to free functions
+struct NotAFunction;
+
+#[target_clones("avx2")] //~ ERROR cannot be `async` or `const`
+async fn not_sync() {}
+
+#[target_clones("avx2")] //~ ERROR cannot be `async` or `const`
+const fn not_runtime() {}
+
+#[target_clones("avx2")] //~ ERROR cannot have `impl Trait` parameters
+fn impl_trait(_: impl Copy) {}
+
+fn main() {}
@@ -0,0 +1,6 @@
+// only-x86_64
+
+#[target_clones("avx2")] //~ ERROR use of unstable library feature 'target_clones'
+fn f() {}
+
+fn main() {}
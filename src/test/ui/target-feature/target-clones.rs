@@ -0,0 +1,28 @@
+// run-pass
+// only-x86_64
+
+#![feature(target_clones)]
+#![deny(unused_unsafe)]
+
+#[target_clones("avx2,fma", "sse4.1")]
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+#[target_clones("popcnt")]
+fn count_ones<T: Copy + Into<u64>, const N: usize>(words: [T; N]) -> u32 {
+    words.iter().map(|&word| word.into().count_ones()).sum()
+}
+
+#[target_clones("avx2")]
+unsafe fn read<'a>(ptr: *const &'a str) -> &'a str {
+    *ptr
+}
+
+fn main() {
+    assert_eq!(dot(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]), 32.0);
+    assert_eq!(count_ones([0b1011u8, 0xff]), 11);
+    assert_eq!(count_ones::<u32, 0>([]), 0);
+    let s = "clone";
+    assert_eq!(unsafe { read(&s) }, "clone");
+}
@@ -0,0 +1,10 @@
+// check-pass
+// compile-flags: -Z terminal-urls=yes -Z terminal-urls-base=file:///usr/share/doc/rust/html/
+
+// This test checks that lint names link to the documentation found at the base URL given with
+// `-Z terminal-urls-base`.
+
+fn main() {
+    let x = 42;
+    //~^ WARN unused variable: `x`
+}
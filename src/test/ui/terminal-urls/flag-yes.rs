@@ -0,0 +1,9 @@
+// compile-flags: -Z terminal-urls=yes
+
+// This test checks that `-Z terminal-urls` prints the error code and the primary location of an
+// error as hyperlinks.
+
+fn main() {
+    let _: () = 42;
+    //~^ ERROR mismatched types
+}
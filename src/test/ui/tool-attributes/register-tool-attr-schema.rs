@@ -0,0 +1,11 @@
+#![feature(register_tool)]
+#![register_tool(analyzer)]
+#![register_tool_attr(other::attr)] //~ ERROR `other` is not a tool registered with `#![register_tool]`
+#![register_tool_attr(analyzer)] //~ ERROR malformed `register_tool_attr` attribute input
+#![register_tool_attr(analyzer::a, targets(closure))] //~ ERROR unknown tool attribute target
+#![register_tool_attr(analyzer::b, args(string))] //~ ERROR unknown tool attribute argument shape
+#![register_tool_attr(analyzer::c, flags(x))] //~ ERROR malformed `register_tool_attr` attribute input
+#![register_tool_attr(analyzer::d)]
+#![register_tool_attr(analyzer::d)] //~ ERROR tool attribute `analyzer::d` was already registered
+
+fn main() {}
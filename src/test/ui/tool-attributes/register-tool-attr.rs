@@ -0,0 +1,31 @@
+#![feature(register_tool)]
+#![register_tool(analyzer)]
+#![register_tool_attr(analyzer::skip, targets(fn, mod), args(word))]
+#![register_tool_attr(analyzer::level, args(name_value, list))]
+#![allow(dead_code)]
+
+#[analyzer::skip]
+fn skipped() {}
+
+#[analyzer::level = "high"]
+struct Leveled {
+    #[analyzer::level(low)]
+    field: u8,
+}
+
+#[analyzer::skip] //~ ERROR tool attribute `analyzer::skip` cannot be used on `struct`
+struct Misplaced;
+
+#[analyzer::skip(all)] //~ ERROR malformed `analyzer::skip` attribute input
+mod with_args {}
+
+#[analyzer::levle] //~ ERROR unknown tool attribute `analyzer::levle`
+fn typo() {}
+
+#[analyzer::level] //~ ERROR malformed `analyzer::level` attribute input
+fn word() {
+    #[analyzer::skip] //~ ERROR tool attribute `analyzer::skip` cannot be used on `let`
+    let _x = 0;
+}
+
+fn main() {}
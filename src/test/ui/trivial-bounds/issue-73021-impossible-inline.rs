@@ -27,9 +27,11 @@ fn generic_function<X: Foo>(x: X) {}
 
 struct S where i32: Foo;
 //~^ WARN trait bound i32: Foo does not depend on any type or lifetime parameters
+//~| WARN can never hold
 
 impl Foo for () where i32: Foo {
 //~^ WARN trait bound i32: Foo does not depend on any type or lifetime parameters
+//~| WARN can never hold
     fn test(&self) {
         3i32.test();
         Foo::test(&4i32);
@@ -39,6 +41,7 @@ fn test(&self) {
 
 fn f() where i32: Foo {
 //~^ WARN trait bound i32: Foo does not depend on any type or lifetime parameters
+//~| WARN can never hold
     let s = S;
     3i32.test();
     Foo::test(&4i32);
@@ -47,6 +50,7 @@ fn f() where i32: Foo {
 
 fn g() where &'static str: Foo {
 //~^ WARN trait bound &'static str: Foo does not depend on any type or lifetime parameters
+//~| WARN can never hold
     "Foo".test();
     Foo::test(&"Foo");
     generic_function("Foo");
@@ -56,6 +60,7 @@ fn use_op(s: String) -> String
 where
     String: ::std::ops::Neg<Output = String>,
 //~^ WARN trait bound String: Neg does not depend on any type or lifetime parameters
+//~| WARN can never hold
 {
     -s
 }
@@ -64,6 +69,7 @@ fn use_for()
 where
     i32: Iterator,
 //~^ WARN trait bound i32: Iterator does not depend on any type or lifetime parameters
+//~| WARN can never hold
 {
     for _ in 2i32 {}
 }
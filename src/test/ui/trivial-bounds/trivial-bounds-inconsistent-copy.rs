@@ -3,6 +3,7 @@
 #![feature(trivial_bounds)]
 
 fn copy_string(t: String) -> String where String: Copy { //~ WARNING trivial_bounds
+    //~| WARNING can never hold
     is_copy(&t);
     let x = t;
     drop(t);
@@ -10,10 +11,12 @@ fn copy_string(t: String) -> String where String: Copy { //~ WARNING trivial_bou
 }
 
 fn copy_out_string(t: &String) -> String where String: Copy { //~ WARNING trivial_bounds
+    //~| WARNING can never hold
     *t
 }
 
 fn copy_string_with_param<T>(x: String) where String: Copy { //~ WARNING trivial_bounds
+    //~| WARNING can never hold
     let y = x;
     let z = x;
 }
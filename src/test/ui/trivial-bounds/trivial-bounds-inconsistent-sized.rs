@@ -13,6 +13,7 @@ struct T<X: ?Sized> {
 
 struct S(str, str) where str: Sized;
 //~^ WARNING Sized does not depend on any type or lifetime
+//~| WARNING can never hold
 
 fn unsized_local() where for<'a> T<dyn A + 'a>: Sized {
     //~^ WARNING Sized does not depend on any type or lifetime
@@ -21,6 +22,7 @@ fn unsized_local() where for<'a> T<dyn A + 'a>: Sized {
 
 fn return_str() -> str where str: Sized {
     //~^ WARNING Sized does not depend on any type or lifetime
+    //~| WARNING can never hold
     *"Sized".to_string().into_boxed_str()
 }
 
@@ -7,6 +7,8 @@
 pub fn foo() where Vec<str>: Debug, str: Copy {
     //~^ WARNING Debug does not depend on any type or lifetime
     //~| WARNING Copy does not depend on any type or lifetime
+    //~| WARNING can never hold
+    //~| WARNING can never hold
     let x = vec![*"1"];
     println!("{:?}", x);
 }
@@ -12,18 +12,24 @@ pub trait Foo {
 fn generic_function<X: Foo>(x: X) {}
 
 enum E where i32: Foo { V } //~ WARNING trivial_bounds
+//~| WARNING can never hold
 
 struct S where i32: Foo; //~ WARNING trivial_bounds
+//~| WARNING can never hold
 
 trait T where i32: Foo {} //~ WARNING trivial_bounds
+//~| WARNING can never hold
 
 union U where i32: Foo { f: i32 } //~ WARNING trivial_bounds
+//~| WARNING can never hold
 
 type Y where i32: Foo = ();
 //~^ WARNING type_alias_bounds
 //~| WARNING trivial_bounds
+//~| WARNING can never hold
 
 impl Foo for () where i32: Foo { //~ WARNING trivial_bounds
+    //~| WARNING can never hold
     fn test(&self) {
         3i32.test();
         Foo::test(&4i32);
@@ -32,6 +38,7 @@ fn test(&self) {
 }
 
 fn f() where i32: Foo { //~ WARNING trivial_bounds
+    //~| WARNING can never hold
     let s = S;
     3i32.test();
     Foo::test(&4i32);
@@ -39,6 +46,7 @@ fn f() where i32: Foo { //~ WARNING trivial_bounds
 }
 
 fn g() where &'static str: Foo { //~ WARNING trivial_bounds
+    //~| WARNING can never hold
     "Foo".test();
     Foo::test(&"Foo");
     generic_function("Foo");
@@ -53,21 +61,25 @@ struct Dst<X: ?Sized> {
 }
 
 struct TwoStrs(str, str) where str: Sized; //~ WARNING trivial_bounds
+//~| WARNING can never hold
 
 fn unsized_local() where for<'a> Dst<dyn A + 'a>: Sized { //~ WARNING trivial_bounds
     let x: Dst<dyn A> = *(Box::new(Dst { x: 1 }) as Box<Dst<dyn A>>);
 }
 
 fn return_str() -> str where str: Sized { //~ WARNING trivial_bounds
+    //~| WARNING can never hold
     *"Sized".to_string().into_boxed_str()
 }
 
 fn use_op(s: String) -> String where String: ::std::ops::Neg<Output=String> {
     //~^ WARNING trivial_bounds
+    //~| WARNING can never hold
     -s
 }
 
 fn use_for() where i32: Iterator { //~ WARNING trivial_bounds
+    //~| WARNING can never hold
     for _ in 2i32 {}
 }
 
@@ -16,10 +16,12 @@ trait Z {
 fn trivial_elaboration<T>() where T: X<i32> + Z<S = i32>, i32: Y<T> {} // OK
 
 fn global_param() where i32: X<()> {} //~ ERROR
+//~| WARNING can never hold
 
 // Should only error on the trait bound, not the implicit
 // projection bound <i32 as Z>::S == i32.
 fn global_projection() where i32: Z<S = i32> {} //~ ERROR
+//~| WARNING can never hold
 
 impl A {
     fn new() -> A { A }
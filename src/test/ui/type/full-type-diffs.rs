@@ -0,0 +1,29 @@
+// revisions: elided full
+//[full] compile-flags: -Z full-type-diffs
+
+struct Foo<T, U>(T, U);
+struct Bar;
+struct Baz;
+
+trait Make {
+    type Output;
+}
+
+struct Maker;
+
+impl Make for Maker {
+    type Output = Foo<Vec<u8>, Baz>;
+}
+
+fn make() -> Foo<Vec<u8>, Baz> {
+    loop {}
+}
+
+fn want<M: Make<Output = Foo<Vec<u8>, Bar>>>(_: M) {}
+
+fn main() {
+    let _: Foo<Vec<u8>, Bar> = make();
+    //~^ ERROR mismatched types
+    want(Maker);
+    //~^ ERROR type mismatch resolving
+}
@@ -0,0 +1,20 @@
+// Checks that the `unsafe` block suggested for a `let` initializer is not merged with that of the
+// next statement, which would change the type of the binding to `()`.
+
+// run-rustfix
+// compile-flags: -Zthir-unsafeck
+
+#![deny(unsafe_op_in_unsafe_fn)]
+#![allow(dead_code)]
+
+unsafe fn unsf() -> u32 { 0 }
+static mut COUNT: u32 = 0;
+
+unsafe fn let_initializer() -> u32 {
+    let x = unsf(); COUNT += 1;
+    //~^ ERROR call to unsafe function `unsf` is unsafe and requires unsafe block
+    //~| ERROR use of mutable static is unsafe and requires unsafe block
+    x
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+// Checks that `unsafe_op_in_unsafe_fn` suggests wrapping only the unsafe operations, merging the
+// `unsafe` blocks of adjacent ones.
+
+// run-rustfix
+// compile-flags: -Zthir-unsafeck
+
+#![deny(unsafe_op_in_unsafe_fn)]
+#![allow(dead_code)]
+
+unsafe fn unsf() -> u32 { 0 }
+static mut COUNT: u32 = 0;
+
+unsafe fn adjacent(p: *mut u32) {
+    unsf(); COUNT += 1;
+    //~^ ERROR call to unsafe function `unsf` is unsafe and requires unsafe block
+    //~| ERROR use of mutable static is unsafe and requires unsafe block
+    let _x = *p + 1;
+    //~^ ERROR dereference of raw pointer is unsafe and requires unsafe block
+}
+
+unsafe fn nested(p: *const u32) -> u32 {
+    unsf() + *p
+    //~^ ERROR call to unsafe function `unsf` is unsafe and requires unsafe block
+    //~| ERROR dereference of raw pointer is unsafe and requires unsafe block
+}
+
+unsafe fn place(p: *mut u32) {
+    let r = &mut *p;
+    //~^ ERROR dereference of raw pointer is unsafe and requires unsafe block
+    *r += 1;
+}
+
+fn main() {}
@@ -6,7 +6,7 @@
 // Check that tautalogically false bounds are accepted, and are used
 // in type inference.
 #![feature(trivial_bounds)]
-#![allow(unused)]
+#![allow(unused, trivially_false_bounds)]
 trait A {}
 
 impl A for i32 {}
@@ -1,5 +1,5 @@
 #![feature(trivial_bounds)]
-#![allow(unused, trivial_bounds)]
+#![allow(unused, trivial_bounds, trivially_false_bounds)]
 
 fn test_trivial_bounds()
 where
@@ -120,6 +120,16 @@
 pub const MEMTAG_SUPPORTED_TARGETS: &[&str] =
     &["aarch64-linux-android", "aarch64-unknown-linux-gnu"];
 
+pub const KCFI_SUPPORTED_TARGETS: &[&str] = &[
+    "aarch64-linux-android",
+    "aarch64-unknown-linux-gnu",
+    "aarch64-unknown-none",
+    "x86_64-linux-android",
+    "x86_64-unknown-linux-gnu",
+    "x86_64-unknown-none",
+    "x86_64-unknown-none-linuxkernel",
+];
+
 const BIG_ENDIAN: &[&str] = &[
     "aarch64_be",
     "armebv7r",
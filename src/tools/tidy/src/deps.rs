@@ -219,6 +219,7 @@
     "time",
     "tinystr",
     "tinyvec",
+    "toml",
     "tracing",
     "tracing-attributes",
     "tracing-core",
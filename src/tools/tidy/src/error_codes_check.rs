@@ -79,6 +79,32 @@ fn check_if_error_code_is_test_in_explanation(f: &str, err_code: &str) -> bool {
     ignore_found
 }
 
+// Every `compile_fail` example of an error code which is still emitted should say which error
+// it fails with, so that the doctests of the error index catch examples which got stale.
+fn has_compile_fail_example_without_error_code(f: &str) -> bool {
+    let mut missing_error_code = false;
+
+    for line in f.lines() {
+        let s = line.trim();
+        if s.starts_with("#### Note: this error code is no longer emitted by the compiler") {
+            return false;
+        }
+        if let Some(attrs) = s.strip_prefix("```") {
+            let attrs = attrs.split(',').map(|attr| attr.trim()).collect::<Vec<_>>();
+            if attrs.contains(&"compile_fail")
+                && !attrs.iter().any(|attr| {
+                    attr.len() == 5
+                        && attr.starts_with('E')
+                        && attr[1..].chars().all(|c| c.is_ascii_digit())
+                })
+            {
+                missing_error_code = true;
+            }
+        }
+    }
+    missing_error_code
+}
+
 macro_rules! some_or_continue {
     ($e:expr) => {
         match $e {
@@ -140,6 +166,13 @@ fn extract_error_codes(
                             path.display(),
                         ));
                     }
+                    if has_compile_fail_example_without_error_code(&content) {
+                        errors.push(format!(
+                            "`{}` has a compile_fail example which doesn't say which error code \
+                             it emits",
+                            path.display(),
+                        ));
+                    }
                     if check_error_code_explanation(&content, error_codes, err_code) {
                         errors.push(format!(
                             "`{}` uses invalid tag `compile-fail` instead of `compile_fail`",